@@ -3,6 +3,7 @@
 use std::convert::TryFrom;
 use std::fs::File;
 use std::io::{stdin, stdout, Read, Write};
+use std::net::IpAddr;
 use std::process::{Command, Stdio};
 use std::str::FromStr;
 
@@ -101,6 +102,34 @@ where
     net_state.set_memory_only(
         matches.try_contains_id("MEMORY_ONLY").unwrap_or_default(),
     );
+    net_state.set_ignore_routes(
+        matches.try_contains_id("IGNORE_ROUTES").unwrap_or_default(),
+    );
+    net_state.set_ignore_route_rules(
+        matches.try_contains_id("IGNORE_ROUTE_RULES").unwrap_or_default(),
+    );
+    net_state.set_ignore_dns(
+        matches.try_contains_id("IGNORE_DNS").unwrap_or_default(),
+    );
+    net_state.set_protect_cni_owned_ovs(
+        matches
+            .try_contains_id("PROTECT_CNI_OWNED_OVS")
+            .unwrap_or_default(),
+    );
+    net_state.set_auto_absent_children(
+        matches
+            .try_contains_id("AUTO_ABSENT_CHILDREN")
+            .unwrap_or_default(),
+    );
+    net_state.set_ovn_auto_create_bridges(
+        matches
+            .try_contains_id("OVN_AUTO_CREATE_BRIDGES")
+            .unwrap_or_default(),
+    );
+    net_state.set_strict(matches.try_contains_id("STRICT").unwrap_or_default());
+    if let Ok(Some(ip)) = matches.try_get_one::<String>("MANAGEMENT_IP") {
+        net_state.set_management_ip(Some(parse_management_ip(ip)?));
+    }
 
     net_state.apply()?;
     if !matches.try_contains_id("SHOW_SECRETS").unwrap_or_default() {
@@ -110,6 +139,28 @@ where
     Ok(serde_yaml::to_string(&sorted_net_state)?)
 }
 
+// The `auto` means the client address of current SSH session stored in
+// `SSH_CONNECTION` environment variable.
+fn parse_management_ip(ip: &str) -> Result<IpAddr, CliError> {
+    let ip = if ip == "auto" {
+        std::env::var("SSH_CONNECTION")
+            .ok()
+            .and_then(|v| v.split_whitespace().next().map(|s| s.to_string()))
+            .ok_or_else(|| {
+                CliError::from(
+                    "Failed to find SSH client address from SSH_CONNECTION \
+                    environment variable for `--management-ip auto`",
+                )
+            })?
+    } else {
+        ip.to_string()
+    };
+    IpAddr::from_str(&ip).map_err(|e| CliError {
+        code: crate::error::EX_DATAERR,
+        error_msg: format!("Invalid management IP {ip}: {e}"),
+    })
+}
+
 pub(crate) fn commit(checkpoint: &str) -> Result<String, CliError> {
     match NetworkState::checkpoint_commit(checkpoint) {
         Ok(()) => Ok(checkpoint.to_string()),
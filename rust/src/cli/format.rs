@@ -2,6 +2,22 @@
 
 use crate::{error::CliError, state::state_from_file};
 
-pub(crate) fn format(state_file: &str) -> Result<String, CliError> {
-    Ok(serde_yaml::to_string(&state_from_file(state_file)?)?)
+pub(crate) fn format(
+    state_file: &str,
+    sanitize: bool,
+    canonicalize: bool,
+    stable_order: bool,
+) -> Result<String, CliError> {
+    let mut net_state = state_from_file(state_file)?;
+    if canonicalize {
+        return Ok(net_state.to_canonical_yaml()?);
+    } else if sanitize {
+        // The warnings are already logged
+        net_state = net_state.sanitize()?.0;
+    }
+    if stable_order {
+        Ok(net_state.to_stable_yaml()?)
+    } else {
+        Ok(serde_yaml::to_string(&net_state)?)
+    }
 }
@@ -2,9 +2,30 @@
 
 use crate::{error::CliError, state::state_from_file};
 
-pub(crate) fn gen_conf(file_path: &str) -> Result<String, CliError> {
+pub(crate) fn gen_conf(
+    file_path: &str,
+    backend: &str,
+    root: Option<&str>,
+) -> Result<String, CliError> {
     let net_state = state_from_file(file_path)?;
-    let confs = net_state.gen_conf()?;
+    if let Some(root) = root {
+        if backend != "NetworkManager" {
+            return Err(format!(
+                "The --root argument only support NetworkManager backend, \
+                but got {backend}"
+            )
+            .into());
+        }
+        return Ok(net_state.apply_offline(root)?.join("\n"));
+    }
+    if backend == "dracut" {
+        return Ok(net_state.gen_dracut_cmdline()?);
+    }
+    let confs = if backend == "systemd-networkd" {
+        net_state.gen_networkd_conf()?
+    } else {
+        net_state.gen_conf()?
+    };
     let escaped_string = serde_yaml::to_string(&confs)?;
     Ok(escaped_string.replace("\\n", "\n\n"))
 }
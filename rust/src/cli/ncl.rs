@@ -127,6 +127,24 @@ fn main() {
                         .takes_value(false)
                         .help("Show running configuration only"),
                 )
+                .arg(
+                    clap::Arg::new("STABLE_ORDER")
+                        .long("stable-order")
+                        .takes_value(false)
+                        .help(
+                            "Show properties in stable documented order \
+                            for diff based tooling",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("TRIM_DEFAULTS")
+                        .long("trim-defaults")
+                        .takes_value(false)
+                        .help(
+                            "Omit properties holding default values or \
+                            read-only status, e.g. maximum MTU",
+                        ),
+                )
                 .arg(
                     clap::Arg::new("SHOW_SECRETS")
                         .short('s')
@@ -134,6 +152,15 @@ fn main() {
                         .takes_value(false)
                         .help("Show secrets(hide by default)"),
                 )
+                .arg(
+                    clap::Arg::new("ROUTING_SUMMARY")
+                        .long("routing-summary")
+                        .takes_value(false)
+                        .help(
+                            "Show routing summary instead of full routes \
+                            and route rules",
+                        ),
+                )
         )
         .subcommand(
             clap::Command::new(SUB_CMD_APPLY)
@@ -191,6 +218,68 @@ fn main() {
                         .takes_value(false)
                         .help("Do not make the state persistent"),
                 )
+                .arg(
+                    clap::Arg::new("IGNORE_ROUTES")
+                        .long("ignore-routes")
+                        .takes_value(false)
+                        .help("Leave routes untouched"),
+                )
+                .arg(
+                    clap::Arg::new("IGNORE_ROUTE_RULES")
+                        .long("ignore-route-rules")
+                        .takes_value(false)
+                        .help("Leave route rules untouched"),
+                )
+                .arg(
+                    clap::Arg::new("IGNORE_DNS")
+                        .long("ignore-dns")
+                        .takes_value(false)
+                        .help("Leave DNS configuration untouched"),
+                )
+                .arg(
+                    clap::Arg::new("PROTECT_CNI_OWNED_OVS")
+                        .long("protect-cni-owned-ovs")
+                        .takes_value(false)
+                        .help("Refuse changing OVS bridge managed by CNI"),
+                )
+                .arg(
+                    clap::Arg::new("AUTO_ABSENT_CHILDREN")
+                        .long("auto-absent-children")
+                        .takes_value(false)
+                        .help(
+                            "Remove child interfaces(e.g. VLAN) of \
+                            interface marked as absent",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("OVN_AUTO_CREATE_BRIDGES")
+                        .long("ovn-auto-create-bridges")
+                        .takes_value(false)
+                        .help(
+                            "Create OVS bridge referred by OVN bridge \
+                            mapping if not exist",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("STRICT")
+                        .long("strict")
+                        .takes_value(false)
+                        .help(
+                            "Fail on risky desired state instead of \
+                            warning, for example overlapping subnets \
+                            across interfaces",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("MANAGEMENT_IP")
+                        .long("management-ip")
+                        .takes_value(true)
+                        .help(
+                            "Rollback if route to this IP address is gone \
+                            after applying. Use `auto` for the SSH client \
+                            address of current session",
+                        ),
+                )
         )
         .subcommand(
             clap::Command::new(SUB_CMD_GEN_CONF)
@@ -200,6 +289,29 @@ fn main() {
                         .required(true)
                         .index(1)
                         .help("Network state file"),
+                )
+                .arg(
+                    clap::Arg::new("BACKEND")
+                        .long("backend")
+                        .takes_value(true)
+                        .possible_values([
+                            "NetworkManager",
+                            "systemd-networkd",
+                            "dracut",
+                        ])
+                        .default_value("NetworkManager")
+                        .help("Backend of generated configuration"),
+                )
+                .arg(
+                    clap::Arg::new("ROOT")
+                        .long("root")
+                        .takes_value(true)
+                        .help(
+                            "Apply the state offline into specified root \
+                            folder by storing NetworkManager keyfiles, \
+                            /etc/hostname and /etc/resolv.conf instead of \
+                            printing them",
+                        ),
                 ),
         )
         .subcommand(
@@ -326,6 +438,30 @@ fn main() {
                         .index(1)
                         .default_value("-")
                         .help("Network state file"),
+                )
+                .arg(
+                    clap::Arg::new("SANITIZE")
+                        .long("sanitize")
+                        .takes_value(false)
+                        .help("Validate and sanitize the state"),
+                )
+                .arg(
+                    clap::Arg::new("CANONICALIZE")
+                        .long("canonicalize")
+                        .takes_value(false)
+                        .help(
+                            "Validate and convert the state into canonical \
+                            form with sorted interfaces and routes",
+                        ),
+                )
+                .arg(
+                    clap::Arg::new("STABLE_ORDER")
+                        .long("stable-order")
+                        .takes_value(false)
+                        .help(
+                            "Print properties in stable documented order \
+                            for diff based tooling",
+                        ),
                 ),
         )
         .subcommand(
@@ -453,7 +589,11 @@ fn main() {
 
     if let Some(matches) = matches.subcommand_matches(SUB_CMD_GEN_CONF) {
         if let Some(file_path) = matches.value_of("STATE_FILE") {
-            print_result_and_exit(gen_conf(file_path));
+            print_result_and_exit(gen_conf(
+                file_path,
+                matches.value_of("BACKEND").unwrap_or("NetworkManager"),
+                matches.value_of("ROOT"),
+            ));
         }
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_SHOW) {
         print_result_and_exit(show(matches));
@@ -494,6 +634,9 @@ fn main() {
         // The default_value() has ensured the unwrap() will never fail
         print_result_and_exit(format::format(
             matches.value_of("STATE_FILE").unwrap(),
+            matches.try_contains_id("SANITIZE").unwrap_or_default(),
+            matches.try_contains_id("CANONICALIZE").unwrap_or_default(),
+            matches.try_contains_id("STABLE_ORDER").unwrap_or_default(),
         ));
     } else if let Some(matches) = matches.subcommand_matches(SUB_CMD_STATISTIC)
     {
@@ -533,7 +676,11 @@ fn main() {
 }
 
 #[cfg(not(feature = "gen_conf"))]
-fn gen_conf(_file_path: &str) -> Result<String, crate::error::CliError> {
+fn gen_conf(
+    _file_path: &str,
+    _backend: &str,
+    _root: Option<&str>,
+) -> Result<String, crate::error::CliError> {
     Err("The gc sub-command require `gen_conf` feature been \
         enabled during compiling"
         .into())
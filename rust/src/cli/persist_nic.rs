@@ -117,8 +117,9 @@ fn run_persist_immediately(
             iface
                 .base_iface()
                 .permanent_mac_address
-                .clone()
-                .or_else(|| iface.base_iface().mac_address.clone())
+                .as_ref()
+                .or(iface.base_iface().mac_address.as_ref())
+                .map(|m| m.to_string())
         };
         let mac = match mac {
             Some(m) => m,
@@ -245,9 +246,9 @@ pub(crate) fn clean_up(
         .filter_map(|i| {
             i.base_iface()
                 .permanent_mac_address
-                .as_deref()
-                .or_else(|| i.base_iface().mac_address.as_deref())
-                .map(|m| (i.name(), m))
+                .as_ref()
+                .or(i.base_iface().mac_address.as_ref())
+                .map(|m| (i.name(), m.as_str()))
         })
         .collect();
 
@@ -443,7 +444,7 @@ fn has_any_kargs(kargs: &[&str]) -> bool {
 // 1. Permanent MAC address
 // 2. MAC address stored in `IFLA_BOND_PORT_PERM_HWADDR`
 fn get_bond_port_mac(iface: &Interface) -> Option<String> {
-    if let Some(mac) = iface.base_iface().permanent_mac_address.as_deref() {
+    if let Some(mac) = iface.base_iface().permanent_mac_address.as_ref() {
         return Some(mac.to_string());
     }
 
@@ -2,7 +2,7 @@
 
 use nmstate::{
     DnsState, HostNameState, NetworkState, OvnConfiguration, OvsDbGlobalConfig,
-    RouteRules, Routes,
+    RouteRules, Routes, RoutingSummary,
 };
 use serde::Serialize;
 use serde_yaml::Value;
@@ -18,6 +18,11 @@ pub(crate) struct SortedNetworkState {
     #[serde(rename = "route-rules", default)]
     rules: RouteRules,
     routes: Routes,
+    #[serde(
+        rename = "routing-summary",
+        skip_serializing_if = "Option::is_none"
+    )]
+    routing_summary: Option<RoutingSummary>,
     interfaces: Vec<Value>,
     #[serde(rename = "ovs-db")]
     ovsdb: OvsDbGlobalConfig,
@@ -37,15 +42,38 @@ pub(crate) fn show(matches: &clap::ArgMatches) -> Result<String, CliError> {
         net_state.set_running_config_only(true);
     }
     net_state.set_include_secrets(matches.is_present("SHOW_SECRETS"));
+    if matches.is_present("ROUTING_SUMMARY") {
+        net_state.set_include_routing_summary(true);
+    }
     net_state.retrieve()?;
+    if matches.is_present("ROUTING_SUMMARY") {
+        net_state.routes = Routes::new();
+        net_state.rules = RouteRules::new();
+    }
+    if matches.is_present("TRIM_DEFAULTS") {
+        net_state.trim_defaults();
+    }
+    let stable_order = matches.is_present("STABLE_ORDER");
     Ok(if let Some(ifname) = matches.value_of("IFNAME") {
         let mut new_net_state = filter_net_state_with_iface(&net_state, ifname);
         new_net_state.set_kernel_only(matches.is_present("KERNEL"));
-        if matches.is_present("JSON") {
+        if stable_order {
+            if matches.is_present("JSON") {
+                new_net_state.to_stable_json()?
+            } else {
+                new_net_state.to_stable_yaml()?
+            }
+        } else if matches.is_present("JSON") {
             serde_json::to_string_pretty(&new_net_state)?
         } else {
             serde_yaml::to_string(&new_net_state)?
         }
+    } else if stable_order {
+        if matches.is_present("JSON") {
+            net_state.to_stable_json()?
+        } else {
+            net_state.to_stable_yaml()?
+        }
     } else if matches.is_present("JSON") {
         serde_json::to_string_pretty(&sort_netstate(net_state)?)?
     } else {
@@ -90,6 +118,7 @@ pub(crate) fn sort_netstate(
             hostname: net_state.hostname,
             interfaces: new_ifaces,
             routes: net_state.routes,
+            routing_summary: net_state.routing_summary,
             rules: net_state.rules,
             dns: net_state.dns,
             ovsdb: net_state.ovsdb,
@@ -101,6 +130,7 @@ pub(crate) fn sort_netstate(
         hostname: net_state.hostname,
         interfaces: Vec::new(),
         routes: net_state.routes,
+        routing_summary: net_state.routing_summary,
         rules: net_state.rules,
         dns: net_state.dns,
         ovsdb: net_state.ovsdb,
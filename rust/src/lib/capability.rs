@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+
+use crate::{
+    nispor::NISPOR_APPLY_IFACE_TYPES,
+    nm::{is_nm_version_at_least, nm_unsupported_features, nm_version},
+    ovsdb::ovsdb_is_running,
+    InterfaceType, NetworkState, NmstateError, NmstateFeature,
+};
+
+const NM_LIBRESWAN_PLUGIN_PATH: &str =
+    "/usr/lib/NetworkManager/VPN/nm-libreswan-service.name";
+
+// Kernel interface types supported by NetworkManager backend
+const NM_KERNEL_IFACE_TYPES: [InterfaceType; 19] = [
+    InterfaceType::Bond,
+    InterfaceType::Dummy,
+    InterfaceType::Ethernet,
+    InterfaceType::Gre,
+    InterfaceType::Gretap,
+    InterfaceType::InfiniBand,
+    InterfaceType::Ip6Gre,
+    InterfaceType::Ip6Gretap,
+    InterfaceType::Ip6Tnl,
+    InterfaceType::IpVlan,
+    InterfaceType::LinuxBridge,
+    InterfaceType::Loopback,
+    InterfaceType::MacSec,
+    InterfaceType::MacVlan,
+    InterfaceType::MacVtap,
+    InterfaceType::Veth,
+    InterfaceType::Vlan,
+    InterfaceType::Vrf,
+    InterfaceType::Vxlan,
+];
+
+// Kernel interface types configured by nmstate via netlink regardless of
+// backend
+const NETLINK_IFACE_TYPES: [InterfaceType; 3] = [
+    InterfaceType::Batman,
+    InterfaceType::Can,
+    InterfaceType::Ifb,
+];
+
+const OVS_FEATURES: [NmstateFeature; 6] = [
+    NmstateFeature::OvnMapping,
+    NmstateFeature::OvsBond,
+    NmstateFeature::OvsDbGlobal,
+    NmstateFeature::OvsDbInterface,
+    NmstateFeature::OvsDpdk,
+    NmstateFeature::OvsPatch,
+];
+
+#[derive(Clone, Debug, Serialize, Default, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// The backends and features available on this host.
+/// Generated by [NetworkState::query_capabilities()].
+pub struct NmstateCapabilities {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Version of running NetworkManager daemon. `None` if NetworkManager is
+    /// not running or in `kernel only` mode.
+    pub nm_version: Option<String>,
+    /// Whether OpenvSwitch database is reachable.
+    pub ovsdb: bool,
+    /// Whether NetworkManager libreswan VPN plugin is installed.
+    pub libreswan: bool,
+    /// Interface types could be applied on this host. Without
+    /// NetworkManager, only the interface types supported by kernel backend
+    /// are included.
+    pub iface_types: Vec<InterfaceType>,
+    /// Features cannot be applied on this host.
+    pub unsupported_features: Vec<NmstateFeature>,
+}
+
+impl NetworkState {
+    /// Query the backends and features available on this host, so that
+    /// caller may avoid applying network state which is doomed to fail.
+    /// The [NetworkState::set_kernel_only()] and backend endpoint options of
+    /// `self` are honored, other network state stored in `self` is ignored.
+    /// Only available for feature `query_apply`.
+    pub fn query_capabilities(
+        &self,
+    ) -> Result<NmstateCapabilities, NmstateError> {
+        let mut ret = NmstateCapabilities {
+            ovsdb: ovsdb_is_running(self.ovsdb_socket_path.as_deref()),
+            iface_types: NISPOR_APPLY_IFACE_TYPES.to_vec(),
+            ..Default::default()
+        };
+        if !self.kernel_only {
+            match nm_version(self.nm_dbus_address.as_deref()) {
+                Ok(v) => ret.nm_version = Some(v),
+                Err(e) => {
+                    log::info!("NetworkManager is not available: {e}");
+                }
+            }
+        }
+
+        if let Some(nm_version) = ret.nm_version.as_deref() {
+            ret.iface_types = NM_KERNEL_IFACE_TYPES.to_vec();
+            ret.iface_types.push(InterfaceType::Modem);
+            ret.libreswan =
+                std::path::Path::new(NM_LIBRESWAN_PLUGIN_PATH).exists();
+            if ret.libreswan {
+                ret.iface_types.push(InterfaceType::Ipsec);
+            }
+            if ret.ovsdb {
+                ret.iface_types.push(InterfaceType::OvsBridge);
+                ret.iface_types.push(InterfaceType::OvsInterface);
+            }
+            if !is_nm_version_at_least(nm_version, 1, 40) {
+                ret.unsupported_features.push(NmstateFeature::Mptcp);
+            }
+            ret.unsupported_features
+                .append(&mut nm_unsupported_features(nm_version));
+        } else {
+            ret.unsupported_features.push(NmstateFeature::Lldp);
+        }
+        ret.iface_types.extend_from_slice(&NETLINK_IFACE_TYPES);
+        if ret.nm_version.is_none() || !ret.ovsdb {
+            ret.unsupported_features.extend_from_slice(&OVS_FEATURES);
+        }
+        ret.iface_types.sort_unstable_by_key(|t| t.to_string());
+        ret.unsupported_features.sort_unstable();
+        Ok(ret)
+    }
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Global connectivity check(also known as captive portal detection) of
+/// NetworkManager. Serialize and deserialize to/from `connectivity-check`
+/// of [crate::NetworkState].
+///
+/// Example yaml output would be:
+///
+/// ```yml
+/// connectivity-check:
+///   enabled: false
+///   uri: http://example.org/check_network_status.txt
+/// ```
+pub struct ConnectivityCheckConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether NetworkManager should periodically check internet
+    /// connectivity. Stored by NetworkManager in its state file.
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// URI to check connectivity against. Stored into NetworkManager
+    /// configuration folder. Set to empty string to disable connectivity
+    /// check regardless of `enabled` property.
+    pub uri: Option<String>,
+}
+
+impl ConnectivityCheckConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct MergedConnectivityCheckConfig {
+    pub(crate) desired: Option<ConnectivityCheckConfig>,
+    pub(crate) current: Option<ConnectivityCheckConfig>,
+}
+
+impl MergedConnectivityCheckConfig {
+    pub(crate) fn new(
+        desired: Option<ConnectivityCheckConfig>,
+        current: Option<ConnectivityCheckConfig>,
+    ) -> Result<Self, NmstateError> {
+        let ret = Self { desired, current };
+        ret.validate_uri_for_enabled()?;
+        Ok(ret)
+    }
+
+    // Enabling connectivity check without URI is silently ignored by
+    // NetworkManager, raise error instead.
+    fn validate_uri_for_enabled(&self) -> Result<(), NmstateError> {
+        let desired = match self.desired.as_ref() {
+            Some(d) if d.enabled == Some(true) => d,
+            _ => return Ok(()),
+        };
+        let uri = match desired.uri.as_deref() {
+            Some(u) => u,
+            // Current state is unknown in kernel only or gen_conf mode
+            None => match self.current.as_ref() {
+                Some(c) => c.uri.as_deref().unwrap_or_default(),
+                None => return Ok(()),
+            },
+        };
+        if uri.is_empty() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "Cannot enable connectivity check without URI defined, \
+                please define `uri` in `connectivity-check` section"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn is_changed(&self) -> bool {
+        let desired = match self.desired.as_ref() {
+            Some(d) => d,
+            None => return false,
+        };
+        let current = self.current.clone().unwrap_or_default();
+        (desired.enabled.is_some() && desired.enabled != current.enabled)
+            || (desired.uri.is_some() && desired.uri != current.uri)
+    }
+}
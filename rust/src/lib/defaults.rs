@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    state::merge_json_value, ErrorKind, Interface, InterfaceType, NetworkState,
+    NmstateError,
+};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[non_exhaustive]
+/// Default properties expanded into desired interfaces of specified type
+/// when applying, so that large homogeneous setup does not need to repeat
+/// them for every interface. The properties defined explicitly in the
+/// interface take precedence over the defaults. Only interfaces in up
+/// state with interface type defined are affected.
+///
+/// Example yaml(many lines omitted) would be:
+///
+/// ```yml
+/// defaults:
+///   interfaces:
+///   - type: ethernet
+///     mtu: 9000
+///     lldp:
+///       enabled: true
+/// interfaces:
+/// - name: eth1
+///   type: ethernet
+///   state: up
+/// - name: eth2
+///   type: ethernet
+///   state: up
+///   mtu: 1500
+/// ```
+pub struct NetworkStateDefaults {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    /// Interface properties with mandatory `type` property. The `name` and
+    /// `state` properties are not allowed.
+    pub interfaces: Vec<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl NetworkStateDefaults {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn get_iface_templates(
+        &self,
+    ) -> Result<Vec<(InterfaceType, serde_json::Value)>, NmstateError> {
+        let mut ret = Vec::new();
+        for template in self.interfaces.as_slice() {
+            let mut template = template.clone();
+            let iface_type = match template.remove("type") {
+                Some(serde_json::Value::String(t)) => {
+                    InterfaceType::from(t.as_str())
+                }
+                _ => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The interface defaults {template:?} does not \
+                            have interface type defined as string"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            };
+            for key in ["name", "state"] {
+                if template.contains_key(key) {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The interface defaults for {iface_type} \
+                            should not contain `{key}` property"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+            ret.push((iface_type, serde_json::Value::Object(template)));
+        }
+        Ok(ret)
+    }
+}
+
+impl NetworkState {
+    // Expand the `defaults` section into desired interfaces
+    pub(crate) fn apply_defaults(&mut self) -> Result<(), NmstateError> {
+        let templates = match self.defaults.as_ref() {
+            Some(defaults) => defaults.get_iface_templates()?,
+            None => return Ok(()),
+        };
+        for iface in self.interfaces.iter_mut().filter(|i| i.is_up()) {
+            let iface_type = iface.iface_type();
+            for (_, template) in
+                templates.iter().filter(|(t, _)| t == &iface_type)
+            {
+                log::debug!(
+                    "Applying interface defaults {template} to interface {}",
+                    iface.name()
+                );
+                let mut iface_value = serde_json::to_value(&*iface)?;
+                merge_json_value(&mut iface_value, template);
+                *iface = Interface::deserialize(iface_value).map_err(|e| {
+                    NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Invalid interface defaults for {iface_type}: {e}"
+                        ),
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+}
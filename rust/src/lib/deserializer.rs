@@ -4,9 +4,15 @@ use std::convert::TryFrom;
 use std::marker::PhantomData;
 use std::str::FromStr;
 
-use serde::{de, de::Visitor, Deserialize, Deserializer};
+use serde::{de, de::DeserializeOwned, de::Visitor, Deserialize, Deserializer};
 
-use crate::{ErrorKind, NmstateError};
+use crate::{
+    unknown_field::{
+        collect_paths, is_removal_changing_error, path_to_string,
+        strip_unknown_properties, PathSeg, Schema,
+    },
+    ErrorKind, IpNetwork, NmstateError,
+};
 
 pub(crate) fn u8_or_string<'de, D>(deserializer: D) -> Result<u8, D::Error>
 where
@@ -171,6 +177,16 @@ where
     })
 }
 
+// Include the invalid string in error message, so the property could be
+// located by `locate_invalid_property()`.
+fn invalid_integer_str<E, T>(value: &str, e: T) -> E
+where
+    E: de::Error,
+    T: std::fmt::Display,
+{
+    de::Error::custom(format!("Invalid integer string {value:?}: {e}"))
+}
+
 // This function is inspired by https://serde.rs/string-or-struct.html
 pub(crate) fn option_u64_or_string<'de, D>(
     deserializer: D,
@@ -194,13 +210,15 @@ where
         where
             E: de::Error,
         {
+            // Templating engines might leave surrounding whitespace
+            let value = value.trim();
             if let Some(prefix_len) = value.strip_prefix("0x") {
                 u64::from_str_radix(prefix_len, 16)
-                    .map_err(de::Error::custom)
+                    .map_err(|e| invalid_integer_str(value, e))
                     .map(Some)
             } else {
                 FromStr::from_str(value)
-                    .map_err(de::Error::custom)
+                    .map_err(|e| invalid_integer_str(value, e))
                     .map(Some)
             }
         }
@@ -211,6 +229,13 @@ where
         {
             Ok(Some(value))
         }
+
+        fn visit_i64<E>(self, value: i64) -> Result<Option<u64>, E>
+        where
+            E: de::Error,
+        {
+            u64::try_from(value).map_err(de::Error::custom).map(Some)
+        }
     }
 
     deserializer.deserialize_any(IntegerOrString(PhantomData))
@@ -256,8 +281,8 @@ where
         where
             E: de::Error,
         {
-            FromStr::from_str(value)
-                .map_err(de::Error::custom)
+            FromStr::from_str(value.trim())
+                .map_err(|e| invalid_integer_str(value, e))
                 .map(Some)
         }
 
@@ -287,8 +312,8 @@ pub(crate) struct NumberAsString {
 }
 
 impl NumberAsString {
-    pub(crate) fn as_str(&self) -> &str {
-        self.value.as_str()
+    pub(crate) fn to_enum_str(&self) -> String {
+        normalize_enum_str(self.value.as_str())
     }
 }
 
@@ -310,3 +335,183 @@ impl std::convert::TryFrom<serde_json::Value> for NumberAsString {
         }
     }
 }
+
+// Lowercase and use dash instead of underscore, so `Active_Backup` could
+// match `active-backup`.
+// Empty string is deserialized into empty `IpNetwork`.
+pub(crate) fn option_ip_network_or_empty<'de, D>(
+    deserializer: D,
+) -> Result<Option<IpNetwork>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<String>::deserialize(deserializer)? {
+        Some(s) if s.is_empty() => Ok(Some(IpNetwork::default())),
+        Some(s) => IpNetwork::try_from(s).map(Some).map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn normalize_enum_str(s: &str) -> String {
+    s.to_lowercase().replace('_', "-")
+}
+
+// Deserialize enum value in case-insensitive way with underscore treated as
+// dash. Serialization is untouched, hence the output is always canonical.
+pub(crate) fn enum_ignore_case<'de, D, T>(
+    deserializer: D,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let v = serde_json::Value::deserialize(deserializer)?;
+    T::deserialize(EnumIgnoreCase(v)).map_err(de::Error::custom)
+}
+
+pub(crate) fn option_enum_ignore_case<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    match Option::<serde_json::Value>::deserialize(deserializer)? {
+        Some(v) => T::deserialize(EnumIgnoreCase(v))
+            .map(Some)
+            .map_err(de::Error::custom),
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn option_vec_enum_ignore_case<'de, D, T>(
+    deserializer: D,
+) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    match Option::<Vec<serde_json::Value>>::deserialize(deserializer)? {
+        Some(values) => {
+            let mut ret = Vec::new();
+            for v in values {
+                ret.push(
+                    T::deserialize(EnumIgnoreCase(v))
+                        .map_err(de::Error::custom)?,
+                );
+            }
+            Ok(Some(ret))
+        }
+        None => Ok(None),
+    }
+}
+
+// Deserializer wrapping `serde_json::Value` which replaces the string of enum
+// with the canonical variant name before handing it to the derived visitor.
+struct EnumIgnoreCase(serde_json::Value);
+
+impl<'de> Deserializer<'de> for EnumIgnoreCase {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        let v = match self.0 {
+            serde_json::Value::String(s) => {
+                let normalized = normalize_enum_str(s.as_str());
+                match variants
+                    .iter()
+                    .find(|v| normalize_enum_str(v) == normalized)
+                {
+                    Some(v) => serde_json::Value::String(v.to_string()),
+                    None => serde_json::Value::String(s),
+                }
+            }
+            v => v,
+        };
+        v.deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+// Serde does not include the path of the invalid property in its error
+// message. The unknown property of `schema` mentioned by the error message is
+// reported first. Otherwise only the properties whose name or value is
+// mentioned by the error message are checked, the one whose removal changes
+// the error is the invalid property. No path is reported when zero or
+// multiple properties matched.
+// The `skip_keys` of top level object are never checked as they decide how
+// the remaining properties are deserialized, e.g. interface type.
+pub(crate) fn locate_invalid_property<T>(
+    v: &serde_json::Value,
+    schema: &Schema,
+    err_msg: &str,
+    skip_keys: &[&str],
+) -> Option<String>
+where
+    T: DeserializeOwned,
+{
+    let mut unknown_paths = strip_unknown_properties(&mut v.clone(), schema)
+        .into_iter()
+        .filter(|path| match path.last() {
+            Some(PathSeg::Key(k)) => is_mentioned(err_msg, k.as_str()),
+            _ => false,
+        });
+    if let (Some(path), None) = (unknown_paths.next(), unknown_paths.next()) {
+        return Some(path_to_string(path.as_slice()));
+    }
+    let mut candidates = Vec::new();
+    collect_paths(v, skip_keys, &mut Vec::new(), &mut candidates, &|k, v| {
+        k.map(|k| is_mentioned(err_msg, k)).unwrap_or_default()
+            || match v {
+                serde_json::Value::String(s) => is_mentioned(err_msg, s),
+                serde_json::Value::Number(n) => {
+                    is_mentioned(err_msg, n.to_string().as_str())
+                }
+                _ => false,
+            }
+    });
+    let mut found = candidates.into_iter().filter(|path| {
+        is_removal_changing_error::<T>(v, path.as_slice(), err_msg)
+    });
+    match (found.next(), found.next()) {
+        (Some(path), None) => Some(path_to_string(path.as_slice())),
+        _ => None,
+    }
+}
+
+// Whether `word` is found in `msg` as a whole word
+fn is_mentioned(msg: &str, word: &str) -> bool {
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    !word.is_empty()
+        && msg.match_indices(word).any(|(i, _)| {
+            !msg[..i]
+                .chars()
+                .next_back()
+                .map(is_word_char)
+                .unwrap_or(false)
+                && !msg[i + word.len()..]
+                    .chars()
+                    .next()
+                    .map(is_word_char)
+                    .unwrap_or(false)
+        })
+}
@@ -5,7 +5,10 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use crate::{ip::is_ipv6_addr, ErrorKind, MergedNetworkState, NmstateError};
+use crate::{
+    ip::is_ipv6_addr, ErrorKind, MergedInterfaces, MergedNetworkState,
+    NmstateError,
+};
 
 const SUPPORTED_DNS_OPTS_NO_VALUE: [&str; 15] = [
     "debug",
@@ -112,6 +115,22 @@ pub struct DnsClientState {
     /// To remove all existing search, please use `Some(Vec::new())`.
     /// If undefined(set to `None`), will preserve current config.
     pub options: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Kernel interface to hold the static DNS config. When defined, nmstate
+    /// will store DNS config to this interface instead of choosing one
+    /// automatically. The interface should have IPv4 or IPv6 enabled for
+    /// corresponding name servers.
+    /// Only valid in desired state, not shown in query.
+    pub interface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// When set to true, nmstate will store the DNS config via global DNS
+    /// configuration of NetworkManager instead of choosing interfaces to hold
+    /// it, DNS config stored in interface profiles will be removed.
+    /// Cannot be used along with [DnsClientState::interface] or IPv6 link
+    /// local name servers.
+    /// Only supported by NetworkManager backend.
+    /// Only valid in desired state, not shown in query.
+    pub global: Option<bool>,
     #[serde(skip)]
     // Lower is better
     pub(crate) priority: Option<i32>,
@@ -123,15 +142,23 @@ impl DnsClientState {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.server.is_none() && self.search.is_none() && self.options.is_none()
+        self.server.is_none()
+            && self.search.is_none()
+            && self.options.is_none()
+            && self.interface.is_none()
+            && self.global.is_none()
     }
 
     // Any of these conditions means purge full DNS config:
-    //  * `server`, `search` and `options` are None. Equal to desire state
-    //    `config: {}`
+    //  * `server`, `search`, `options`, `interface` and `global` are None.
+    //    Equal to desire state `config: {}`
     //  * `server`, `search` and `options` are `Some<Vec::new()>`.
     pub(crate) fn is_purge(&self) -> bool {
-        self.server.is_none() && self.search.is_none() & self.options.is_none()
+        self.server.is_none()
+            && self.search.is_none()
+            && self.options.is_none()
+            && self.interface.is_none()
+            && self.global.is_none()
             || self.server.as_deref() == Some(&[])
                 && self.search.as_deref() == Some(&[])
                 && self.options.as_deref() == Some(&[])
@@ -214,6 +241,27 @@ impl DnsClientState {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+/// How the order of DNS name servers is verified after applying.
+/// Some DNS resolvers or backends reorder name servers, for example storing
+/// IPv4 and IPv6 name servers separately.
+pub enum DnsVerifyOrderMode {
+    /// The order of name servers should be identical to desired.
+    Strict,
+    /// Only the order of name servers within the same address family(IPv4
+    /// or IPv6) should be identical to desired.
+    PerFamily,
+    /// The order of name servers is ignored.
+    Ignore,
+}
+
+impl Default for DnsVerifyOrderMode {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub(crate) struct MergedDnsState {
     pub(crate) desired: DnsState,
@@ -221,12 +269,22 @@ pub(crate) struct MergedDnsState {
     pub(crate) servers: Vec<String>,
     pub(crate) searches: Vec<String>,
     pub(crate) options: Vec<String>,
+    // Interface explicitly desired to hold the DNS config
+    pub(crate) interface: Option<String>,
+    // Interfaces currently holding static DNS config
+    pub(crate) cur_ifaces: Vec<String>,
+    // User requested to leave DNS config untouched
+    pub(crate) ignored: bool,
+    // User desired to store DNS config via NetworkManager global DNS
+    pub(crate) use_global: bool,
+    pub(crate) verify_order_mode: DnsVerifyOrderMode,
 }
 
 impl MergedDnsState {
     pub(crate) fn new(
         mut desired: DnsState,
         mut current: DnsState,
+        verify_order_mode: DnsVerifyOrderMode,
     ) -> Result<Self, NmstateError> {
         desired.sanitize()?;
         current.sanitize().ok();
@@ -268,16 +326,69 @@ impl MergedDnsState {
             }
         }
 
+        let interface = desired
+            .config
+            .as_ref()
+            .and_then(|c| c.interface.clone())
+            .filter(|i| !i.is_empty());
+
+        let use_global =
+            desired.config.as_ref().and_then(|c| c.global) == Some(true);
+
         Ok(Self {
             desired,
             current,
             servers,
             searches,
             options,
+            interface,
+            cur_ifaces: Vec::new(),
+            ignored: false,
+            use_global,
+            verify_order_mode,
         })
     }
 
+    // Store the name of interfaces currently holding static DNS config, so
+    // that desired DNS interface could be compared with current binding.
+    pub(crate) fn set_cur_ifaces(&mut self, merged_ifaces: &MergedInterfaces) {
+        let mut cur_ifaces: Vec<String> = merged_ifaces
+            .kernel_ifaces
+            .values()
+            .filter_map(|i| i.current.as_ref())
+            .filter(|cur_iface| {
+                let base_iface = cur_iface.base_iface();
+                let ipv4_dns = base_iface
+                    .ipv4
+                    .as_ref()
+                    .filter(|i| i.enabled)
+                    .and_then(|i| i.dns.as_ref());
+                let ipv6_dns = base_iface
+                    .ipv6
+                    .as_ref()
+                    .filter(|i| i.enabled)
+                    .and_then(|i| i.dns.as_ref());
+                ipv4_dns.map(|d| !d.is_null()) == Some(true)
+                    || ipv6_dns.map(|d| !d.is_null()) == Some(true)
+            })
+            .map(|cur_iface| cur_iface.name().to_string())
+            .collect();
+        cur_ifaces.sort_unstable();
+        self.cur_ifaces = cur_ifaces;
+    }
+
+    // Explicitly desired global DNS is always treated as changed, so the DNS
+    // config will be moved to it. Explicitly desired DNS interface is treated
+    // as changed when it is not the only interface holding DNS config.
     pub(crate) fn is_changed(&self) -> bool {
+        if self.use_global {
+            return true;
+        }
+        if let Some(iface_name) = self.interface.as_deref() {
+            if self.cur_ifaces.as_slice() != [iface_name] {
+                return true;
+            }
+        }
         let cur_servers = self
             .current
             .config
@@ -358,6 +469,105 @@ impl MergedNetworkState {
 
         Ok(())
     }
+
+    // The explicitly desired DNS interface should:
+    // * Exist and not marked as absent.
+    // * Have IP stack enabled for each family of desired name servers.
+    // * Be the same with the one used by IPv6 link local name server.
+    pub(crate) fn validate_dns_interface(&self) -> Result<(), NmstateError> {
+        if self.dns.use_global {
+            return self.validate_global_dns();
+        }
+        let iface_name = if let Some(i) = self.dns.interface.as_deref() {
+            i
+        } else {
+            return Ok(());
+        };
+        let iface = match self.interfaces.kernel_ifaces.get(iface_name) {
+            Some(iface) if !iface.merged.is_absent() => iface,
+            _ => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Desired DNS interface {iface_name} does not exist \
+                        or is marked as absent"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        let has_v4_srv =
+            self.dns.servers.iter().any(|s| !is_ipv6_addr(s.as_str()));
+        let has_v6_srv =
+            self.dns.servers.iter().any(|s| is_ipv6_addr(s.as_str()));
+        let is_valid = if has_v4_srv || has_v6_srv {
+            (!has_v4_srv || iface.is_iface_valid_for_dns(false))
+                && (!has_v6_srv || iface.is_iface_valid_for_dns(true))
+        } else {
+            iface.is_iface_valid_for_dns(false)
+                || iface.is_iface_valid_for_dns(true)
+        };
+        if !is_valid {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Desired DNS interface {iface_name} does not have \
+                    IP enabled for DNS servers '{}'",
+                    self.dns.servers.as_slice().join(" ")
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        for srv in self.dns.servers.as_slice() {
+            if let Some((_, srv_iface)) = parse_dns_ipv6_link_local_srv(srv)? {
+                if srv_iface != iface_name {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Desired IPv6 link local DNS server {srv} is \
+                            pointing to interface {srv_iface} which is not \
+                            the desired DNS interface {iface_name}"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Global DNS config is not bound to any interface, hence cannot be used
+    // along with desired DNS interface or IPv6 link local name server.
+    fn validate_global_dns(&self) -> Result<(), NmstateError> {
+        if let Some(iface_name) = self.dns.interface.as_deref() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Desired DNS interface {iface_name} cannot be used \
+                    along with global DNS"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        for srv in self.dns.servers.as_slice() {
+            if parse_dns_ipv6_link_local_srv(srv)?.is_some() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "IPv6 link local DNS server {srv} cannot be stored \
+                        in global DNS"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
 }
 
 pub(crate) fn parse_dns_ipv6_link_local_srv(
@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+use crate::{
+    ip::is_ipv6_unicast_link_local, nm::gen_nm_bond_options, BondInterface,
+    ErrorKind, Interface, InterfaceType, MergedNetworkState, NmstateError,
+    RouteEntry,
+};
+
+// Bond options holding a list of IP addresses, dracut expects them separated
+// by semicolon as comma is used between bond options.
+const BOND_MULTI_VALUE_OPTS: [&str; 2] = ["arp_ip_target", "ns_ip6_target"];
+
+pub(crate) fn dracut_gen_cmdline(
+    merged_state: &MergedNetworkState,
+) -> Result<String, NmstateError> {
+    if merged_state
+        .hostname
+        .desired
+        .as_ref()
+        .and_then(|c| c.config.as_ref())
+        .is_some()
+    {
+        log::warn!(
+            "Ignoring hostname configuration as dracut kernel command line \
+            does not support it"
+        );
+    }
+    if !merged_state.dns.searches.is_empty() {
+        log::warn!(
+            "Ignoring DNS search {:?} as dracut kernel command line does not \
+            support it",
+            merged_state.dns.searches
+        );
+    }
+
+    let mut ifaces: Vec<&Interface> = Vec::new();
+    for iface in merged_state
+        .interfaces
+        .iter()
+        .filter_map(|i| i.for_apply.as_ref())
+    {
+        if !iface.is_up() {
+            log::warn!(
+                "Ignoring interface {} as dracut kernel command line only \
+                hold interface in up state",
+                iface.name(),
+            );
+            continue;
+        }
+        if !matches!(
+            iface.iface_type(),
+            InterfaceType::Ethernet
+                | InterfaceType::Bond
+                | InterfaceType::LinuxBridge
+                | InterfaceType::Vlan
+        ) {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Interface {} of type {} is not supported by dracut \
+                    kernel command line generator",
+                    iface.name(),
+                    iface.iface_type()
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        ifaces.push(iface);
+    }
+    ifaces.sort_unstable_by_key(|i| i.name());
+
+    let mut ports: Vec<&str> = Vec::new();
+    let mut ret: Vec<String> = Vec::new();
+    for iface in ifaces.as_slice() {
+        let iface_ports = iface.ports().unwrap_or_default();
+        match iface {
+            Interface::Bond(bond_iface) => {
+                let mut arg =
+                    format!("bond={}:{}", iface.name(), iface_ports.join(","));
+                let opts = gen_bond_opts(bond_iface)?;
+                arg.push(':');
+                arg.push_str(opts.join(",").as_str());
+                if let Some(mtu) = iface.base_iface().mtu {
+                    arg.push_str(format!(":{mtu}").as_str());
+                }
+                ret.push(arg);
+            }
+            Interface::LinuxBridge(_) => {
+                ret.push(format!(
+                    "bridge={}:{}",
+                    iface.name(),
+                    iface_ports.join(",")
+                ));
+            }
+            Interface::Vlan(vlan_iface) => {
+                if let Some(vlan_conf) = vlan_iface.vlan.as_ref() {
+                    ret.push(format!(
+                        "vlan={}:{}",
+                        iface.name(),
+                        vlan_conf.base_iface
+                    ));
+                }
+            }
+            _ => (),
+        }
+        ports.extend(iface_ports);
+    }
+
+    let mut routes: HashMap<&str, Vec<&RouteEntry>> = HashMap::new();
+    for (iface_name, rts) in merged_state.routes.indexed.iter() {
+        routes.insert(
+            iface_name.as_str(),
+            rts.iter().filter(|r| !r.is_absent()).collect(),
+        );
+    }
+
+    for iface in ifaces.iter().filter(|i| !ports.contains(&i.name())) {
+        let mut iface_routes: Vec<&RouteEntry> =
+            routes.remove(iface.name()).unwrap_or_default();
+        ret.extend(gen_ip_args(iface, &mut iface_routes));
+        for rt in iface_routes {
+            if let Some((dst, dst_ip)) = rt
+                .destination
+                .as_ref()
+                .and_then(|dst| dst.ip().map(|ip| (dst, ip)))
+            {
+                let mut arg = format!(
+                    "rd.route={}/{}:",
+                    ip_to_dracut(&dst_ip),
+                    dst.prefix_length()
+                );
+                if let Some(gw) = route_gateway(rt) {
+                    arg.push_str(ip_to_dracut(&gw).as_str());
+                }
+                arg.push(':');
+                arg.push_str(iface.name());
+                ret.push(arg);
+            }
+        }
+    }
+
+    for srv in merged_state.dns.servers.as_slice() {
+        if let Ok(ip) = srv.parse::<IpAddr>() {
+            ret.push(format!("nameserver={}", ip_to_dracut(&ip)));
+        } else {
+            log::warn!(
+                "Ignoring DNS server {srv} as dracut kernel command line \
+                only support plain IP address"
+            );
+        }
+    }
+
+    Ok(ret.join(" "))
+}
+
+// Generate `ip=` arguments of specified interface, the default gateway used
+// by static IP address will be removed from `routes`.
+fn gen_ip_args(
+    iface: &Interface,
+    routes: &mut Vec<&RouteEntry>,
+) -> Vec<String> {
+    let mut ret = Vec::new();
+    let base_iface = iface.base_iface();
+    let mut suffix = String::new();
+    if let Some(mtu) = base_iface.mtu {
+        suffix.push_str(format!(":{mtu}").as_str());
+    }
+    if let Some(mac) = base_iface.mac_address.as_ref() {
+        if suffix.is_empty() {
+            suffix.push(':');
+        }
+        suffix.push_str(format!(":{mac}").as_str());
+    }
+
+    if let Some(ipv4) = base_iface.ipv4.as_ref().filter(|i| i.enabled) {
+        if ipv4.dhcp == Some(true) {
+            ret.push(format!("ip={}:dhcp{suffix}", iface.name()));
+        } else {
+            for addr in ipv4.addresses.as_deref().unwrap_or_default() {
+                ret.push(gen_static_ip_arg(
+                    iface.name(),
+                    &addr.ip,
+                    addr.prefix_length,
+                    take_default_gateway(routes, false),
+                    suffix.as_str(),
+                ));
+            }
+        }
+    }
+    if let Some(ipv6) = base_iface.ipv6.as_ref().filter(|i| i.enabled) {
+        if ipv6.dhcp == Some(true) {
+            ret.push(format!("ip={}:dhcp6{suffix}", iface.name()));
+        } else if ipv6.autoconf == Some(true) {
+            ret.push(format!("ip={}:auto6{suffix}", iface.name()));
+        }
+        for addr in
+            ipv6.addresses
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .filter(|a| match a.ip {
+                    IpAddr::V6(ip) => !is_ipv6_unicast_link_local(&ip),
+                    IpAddr::V4(_) => true,
+                })
+        {
+            ret.push(gen_static_ip_arg(
+                iface.name(),
+                &addr.ip,
+                addr.prefix_length,
+                take_default_gateway(routes, true),
+                suffix.as_str(),
+            ));
+        }
+    }
+    ret
+}
+
+fn gen_static_ip_arg(
+    iface_name: &str,
+    ip: &IpAddr,
+    prefix_length: u8,
+    gateway: Option<IpAddr>,
+    suffix: &str,
+) -> String {
+    format!(
+        "ip={}::{}:{prefix_length}::{iface_name}:none{suffix}",
+        ip_to_dracut(ip),
+        gateway.as_ref().map(ip_to_dracut).unwrap_or_default(),
+    )
+}
+
+fn take_default_gateway(
+    routes: &mut Vec<&RouteEntry>,
+    is_ipv6: bool,
+) -> Option<IpAddr> {
+    let index = routes.iter().position(|rt| {
+        rt.is_ipv6() == is_ipv6
+            && rt.destination.as_ref().map(|d| d.prefix_length()) == Some(0)
+            && route_gateway(rt).is_some()
+    })?;
+    route_gateway(routes.remove(index))
+}
+
+fn route_gateway(rt: &RouteEntry) -> Option<IpAddr> {
+    rt.next_hop_addr
+        .as_deref()
+        .and_then(|a| a.parse::<IpAddr>().ok())
+        .filter(|a| !a.is_unspecified())
+}
+
+fn ip_to_dracut(ip: &IpAddr) -> String {
+    match ip {
+        IpAddr::V4(_) => ip.to_string(),
+        IpAddr::V6(_) => format!("[{ip}]"),
+    }
+}
+
+// Generate the initscripts compatible `key=value` bond options with `mode`
+// placed first.
+fn gen_bond_opts(
+    bond_iface: &BondInterface,
+) -> Result<Vec<String>, NmstateError> {
+    let mut bond_opts: Vec<(String, String)> =
+        gen_nm_bond_options(bond_iface).into_iter().collect();
+    bond_opts.sort_unstable_by(|(a, _), (b, _)| {
+        (a != "mode", a.as_str()).cmp(&(b != "mode", b.as_str()))
+    });
+
+    let mut ret = Vec::new();
+    for (key, mut value) in bond_opts {
+        if BOND_MULTI_VALUE_OPTS.contains(&key.as_str()) {
+            value = value.replace(',', ";");
+        }
+        if value.contains(',') || value.contains(':') {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Bond option {key}={value} of interface {} is not \
+                    supported by dracut kernel command line generator as \
+                    comma and colon are used as delimiters",
+                    bond_iface.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        ret.push(format!("{key}={value}"));
+    }
+    Ok(ret)
+}
@@ -0,0 +1,5 @@
+// SPDX-License-Identifier: Apache-2.0
+
+mod gen_conf;
+
+pub(crate) use gen_conf::dracut_gen_cmdline;
@@ -15,6 +15,9 @@ pub enum ErrorKind {
     PolicyError,
     PermissionError,
     SrIovVfNotFound,
+    /// Backend daemon is not reachable via D-Bus even after retries, for
+    /// example NetworkManager keeps restarting or D-Bus call timeout.
+    BackendUnavailable,
 }
 
 #[cfg(feature = "query_apply")]
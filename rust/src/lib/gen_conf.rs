@@ -1,8 +1,40 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::fs::{DirBuilder, OpenOptions, Permissions};
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt, PermissionsExt};
+use std::path::Path;
 
-use crate::{nm::nm_gen_conf, MergedNetworkState, NetworkState, NmstateError};
+use crate::{
+    dracut::dracut_gen_cmdline,
+    networkd::networkd_gen_conf,
+    nm::{nm_gen_conf, nm_verify_keyfiles},
+    warning::nmstate_warn,
+    ErrorKind, MergedNetworkState, NetworkState, NmstateError,
+};
+
+const NM_KEYFILE_FOLDER: &str = "etc/NetworkManager/system-connections";
+// NetworkManager ignores keyfiles readable by other users
+const NM_KEYFILE_MODE: u32 = 0o600;
+const NM_KEYFILE_FOLDER_MODE: u32 = 0o755;
+const HOSTNAME_FILE: &str = "etc/hostname";
+const RESOLV_CONF_FILE: &str = "etc/resolv.conf";
+const RESOLV_CONF_HEADER: &str = "# Generated by nmstate";
+const ETC_FILE_MODE: u32 = 0o644;
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// NetworkManager keyfile generated by [NetworkState::gen_nm_keyfiles()].
+pub struct NmKeyfile {
+    /// File name of keyfile, e.g. `eth1.nmconnection`.
+    pub file_name: String,
+    /// Content of keyfile.
+    pub content: String,
+    /// UUID of the NetworkManager connection stored in this keyfile.
+    pub uuid: String,
+}
 
 impl NetworkState {
     /// Generate offline network configurations.
@@ -23,9 +55,249 @@ impl NetworkState {
             true,  // gen_conf mode
             false, // memory only
         )?;
-        ret.insert("NetworkManager".to_string(), nm_gen_conf(&merged_state)?);
+        ret.insert(
+            "NetworkManager".to_string(),
+            nm_gen_conf(&merged_state)?
+                .into_iter()
+                .map(|f| (f.file_name, f.content))
+                .collect(),
+        );
         Ok(ret)
     }
+
+    /// Generate NetworkManager keyfiles, one [NmKeyfile] per connection.
+    pub fn gen_nm_keyfiles(&self) -> Result<Vec<NmKeyfile>, NmstateError> {
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            NetworkState::new(),
+            true,  // gen_conf mode
+            false, // memory only
+        )?;
+        nm_gen_conf(&merged_state)
+    }
+
+    /// Parse the content of NetworkManager keyfiles back and verify them
+    /// against the desired state, so the keyfiles generated by
+    /// [NetworkState::gen_nm_keyfiles()] or modified afterwards could get
+    /// the similar correctness guarantee as live apply verification.
+    /// Currently verifying interface type, controller, MTU, IP method and
+    /// static IP addresses of interfaces in up state.
+    /// Return [ErrorKind::VerificationError] on mismatch.
+    pub fn verify_nm_keyfiles(
+        &self,
+        keyfiles: &[&str],
+    ) -> Result<(), NmstateError> {
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            NetworkState::new(),
+            true,  // gen_conf mode
+            false, // memory only
+        )?;
+        nm_verify_keyfiles(&merged_state, keyfiles)
+    }
+
+    /// Generate NetworkManager keyfiles and store them into
+    /// `<root>/etc/NetworkManager/system-connections` with file mode `0600`,
+    /// for example to prepare network configuration of an OS image.
+    /// Existing keyfiles with the same name will be overridden.
+    /// Return the full paths of stored keyfiles.
+    pub fn write_nm_keyfiles(
+        &self,
+        root: &str,
+    ) -> Result<Vec<String>, NmstateError> {
+        let folder = Path::new(root).join(NM_KEYFILE_FOLDER);
+        DirBuilder::new()
+            .recursive(true)
+            .mode(NM_KEYFILE_FOLDER_MODE)
+            .create(&folder)
+            .map_err(|e| {
+                io_error_to_nmstate(
+                    format!("Failed to create folder {}", folder.display()),
+                    e,
+                )
+            })?;
+
+        let mut ret = Vec::new();
+        for keyfile in self.gen_nm_keyfiles()? {
+            let file_path = folder.join(keyfile.file_name.as_str());
+            write_file(&file_path, keyfile.content.as_str(), NM_KEYFILE_MODE)?;
+            ret.push(file_path.display().to_string());
+        }
+        Ok(ret)
+    }
+
+    /// Apply the desired state into alternate root filesystem without
+    /// D-Bus or any running daemon, so image builders can bake network
+    /// configuration without booting the target:
+    ///  * NetworkManager keyfiles as [NetworkState::write_nm_keyfiles()].
+    ///  * Static hostname into `<root>/etc/hostname`.
+    ///  * DNS configuration into `<root>/etc/resolv.conf`. Skipped with
+    ///    warning if that file is a symbolic link(e.g. managed by
+    ///    systemd-resolved or NetworkManager) or not generated by nmstate.
+    ///
+    /// Return the full paths of stored files.
+    pub fn apply_offline(
+        &self,
+        root: &str,
+    ) -> Result<Vec<String>, NmstateError> {
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            NetworkState::new(),
+            true,  // gen_conf mode
+            false, // memory only
+        )?;
+        let mut ret = self.write_nm_keyfiles(root)?;
+
+        if let Some(hostname) = merged_state
+            .hostname
+            .desired
+            .as_ref()
+            .and_then(|h| h.config.as_deref())
+        {
+            let file_path = Path::new(root).join(HOSTNAME_FILE);
+            write_file(
+                &file_path,
+                format!("{hostname}\n").as_str(),
+                ETC_FILE_MODE,
+            )?;
+            ret.push(file_path.display().to_string());
+        }
+
+        let dns = &merged_state.dns;
+        if !dns.servers.is_empty()
+            || !dns.searches.is_empty()
+            || !dns.options.is_empty()
+        {
+            let mut content = format!("{RESOLV_CONF_HEADER}\n");
+            if !dns.searches.is_empty() {
+                let _ = writeln!(content, "search {}", dns.searches.join(" "));
+            }
+            for srv in dns.servers.as_slice() {
+                let _ = writeln!(content, "nameserver {srv}");
+            }
+            if !dns.options.is_empty() {
+                let _ = writeln!(content, "options {}", dns.options.join(" "));
+            }
+            let file_path = Path::new(root).join(RESOLV_CONF_FILE);
+            if is_owned_by_nmstate(&file_path, RESOLV_CONF_HEADER)? {
+                write_file(&file_path, content.as_str(), ETC_FILE_MODE)?;
+                ret.push(file_path.display().to_string());
+            } else {
+                nmstate_warn!(
+                    "Not storing DNS configuration to {} as it is not \
+                    generated by nmstate, it might be managed by \
+                    systemd-resolved or NetworkManager",
+                    file_path.display()
+                );
+            }
+        }
+        Ok(ret)
+    }
+
+    /// Generate offline systemd-networkd configurations for early boot
+    /// network, for example bond, VLAN and linux bridge for root on
+    /// iSCSI/NFS. Only ethernet, bond, linux bridge, VLAN and dummy
+    /// interfaces are supported. Each property not expressible in
+    /// systemd-networkd configuration is ignored with a warning.
+    ///
+    /// The output is the same format of [NetworkState::gen_conf()] with
+    /// `systemd-networkd` as backend name, configuration file names are
+    /// `<iface_name>.netdev` and `<iface_name>.network`.
+    pub fn gen_networkd_conf(
+        &self,
+    ) -> Result<HashMap<String, Vec<(String, String)>>, NmstateError> {
+        let mut ret = HashMap::new();
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            NetworkState::new(),
+            true,  // gen_conf mode
+            false, // memory only
+        )?;
+        ret.insert(
+            "systemd-networkd".to_string(),
+            networkd_gen_conf(&merged_state)?,
+        );
+        Ok(ret)
+    }
+
+    /// Generate dracut network kernel command line arguments(`ip=`, `bond=`,
+    /// `bridge=`, `vlan=`, `rd.route=` and `nameserver=`) for early boot
+    /// network. Only ethernet, bond, linux bridge and VLAN interfaces are
+    /// supported. The arguments are separated by space.
+    pub fn gen_dracut_cmdline(&self) -> Result<String, NmstateError> {
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            NetworkState::new(),
+            true,  // gen_conf mode
+            false, // memory only
+        )?;
+        dracut_gen_cmdline(&merged_state)
+    }
+}
+
+fn write_file(
+    file_path: &Path,
+    content: &str,
+    mode: u32,
+) -> Result<(), NmstateError> {
+    let write = || -> Result<(), std::io::Error> {
+        let mut fd = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .mode(mode)
+            .open(file_path)?;
+        // The `mode()` above only applies to newly created file
+        fd.set_permissions(Permissions::from_mode(mode))?;
+        fd.write_all(content.as_bytes())
+    };
+    write().map_err(|e| {
+        io_error_to_nmstate(
+            format!("Failed to write file {}", file_path.display()),
+            e,
+        )
+    })?;
+    log::info!("File {} stored", file_path.display());
+    Ok(())
+}
+
+// The file is owned by nmstate when not exist, empty or starting with
+// specified header line. Symbolic link is never owned by nmstate.
+fn is_owned_by_nmstate(
+    file_path: &Path,
+    header: &str,
+) -> Result<bool, NmstateError> {
+    match std::fs::symlink_metadata(file_path) {
+        Ok(metadata) if metadata.file_type().is_symlink() => return Ok(false),
+        Ok(_) => (),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(true),
+        Err(e) => {
+            return Err(io_error_to_nmstate(
+                format!("Failed to query file {}", file_path.display()),
+                e,
+            ))
+        }
+    }
+    let content = std::fs::read_to_string(file_path).map_err(|e| {
+        io_error_to_nmstate(
+            format!("Failed to read file {}", file_path.display()),
+            e,
+        )
+    })?;
+    Ok(content.is_empty() || content.lines().next() == Some(header))
+}
+
+fn io_error_to_nmstate(msg: String, e: std::io::Error) -> NmstateError {
+    let e = NmstateError::new(
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            ErrorKind::PermissionError
+        } else {
+            ErrorKind::InvalidArgument
+        },
+        format!("{msg}: {e}"),
+    );
+    log::error!("{}", e);
+    e
 }
 
 #[cfg(test)]
@@ -0,0 +1,46 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, NmstateError};
+
+const HOSTNAMED_DBUS_NAME: &str = "org.freedesktop.hostname1";
+const HOSTNAMED_DBUS_OBJ_PATH: &str = "/org/freedesktop/hostname1";
+const HOSTNAMED_DBUS_IFACE: &str = "org.freedesktop.hostname1";
+
+// Set static hostname via systemd-hostnamed D-Bus API, empty string means
+// removing static hostname. Use the same D-Bus as NetworkManager, system bus
+// when address is None.
+pub(crate) fn hostnamed_set_static_hostname(
+    dbus_address: Option<&str>,
+    hostname: &str,
+) -> Result<(), NmstateError> {
+    let to_nmstate_err = |e: zbus::Error| {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to set static hostname {hostname} via \
+                systemd-hostnamed: {e}"
+            ),
+        );
+        log::error!("{}", e);
+        e
+    };
+    let connection = if let Some(address) = dbus_address {
+        zbus::Connection::new_for_address(address, true)
+    } else {
+        zbus::Connection::new_system()
+    }
+    .map_err(to_nmstate_err)?;
+    let proxy = zbus::Proxy::new(
+        &connection,
+        HOSTNAMED_DBUS_NAME,
+        HOSTNAMED_DBUS_OBJ_PATH,
+        HOSTNAMED_DBUS_IFACE,
+    )
+    .map_err(to_nmstate_err)?;
+    // The second argument is `interactive` for polkit authorization
+    proxy
+        .call::<_, ()>("SetStaticHostname", &(hostname, false))
+        .map_err(to_nmstate_err)?;
+    log::info!("Static hostname set to {hostname:?} via systemd-hostnamed");
+    Ok(())
+}
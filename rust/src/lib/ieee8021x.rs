@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::NetworkState;
+use crate::Secret;
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
@@ -40,14 +40,13 @@ pub struct Ieee8021XConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Deserialize and serialize from/to `private-key-password`.
     /// Replaced to `<_password_hid_by_nmstate>` when querying.
-    pub private_key_password: Option<String>,
+    pub private_key_password: Option<Secret>,
 }
 
 impl Ieee8021XConfig {
     pub(crate) fn hide_secrets(&mut self) {
         if self.private_key_password.is_some() {
-            self.private_key_password =
-                Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            self.private_key_password = Some(Secret::hidden());
         }
     }
 }
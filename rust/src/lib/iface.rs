@@ -6,14 +6,18 @@ use std::iter::FromIterator;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{
-    BaseInterface, BondInterface, DummyInterface, ErrorKind, EthernetInterface,
-    InfiniBandInterface, IpsecInterface, LinuxBridgeInterface,
-    LoopbackInterface, MacSecInterface, MacVlanInterface, MacVtapInterface,
-    NmstateError, OvsBridgeInterface, OvsInterface, VlanInterface,
-    VrfInterface, VxlanInterface,
+    BaseInterface, BatmanInterface, BondInterface, CanInterface,
+    CustomInterface, DummyInterface, ErrorKind, EthernetInterface,
+    GreInterface, GretapInterface, IfbInterface, InfiniBandInterface,
+    Ip6GreInterface, Ip6GretapInterface, Ip6TnlInterface, IpVlanInterface,
+    IpsecInterface, LinuxBridgeInterface, LoopbackInterface, MacAddress,
+    MacSecInterface, MacVlanInterface, MacVtapInterface, ModemInterface,
+    NmstateError, OvsBridgeInterface, OvsInterface, TeamInterface,
+    VlanInterface, VrfInterface, VxlanInterface,
 };
 
 use crate::state::merge_json_value;
+use crate::warning::nmstate_warn;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 #[non_exhaustive]
@@ -69,6 +73,40 @@ pub enum InterfaceType {
     MacSec,
     /// Ipsec connection.
     Ipsec,
+    /// IPv4 GRE tunnel interface.
+    /// Deserialize and serialize from/to 'gre'.
+    Gre,
+    /// IPv4 GRE tunnel interface carrying ethernet frames.
+    /// Deserialize and serialize from/to 'gretap'.
+    Gretap,
+    /// IPv6 GRE tunnel interface.
+    /// Deserialize and serialize from/to 'ip6gre'.
+    Ip6Gre,
+    /// IPv6 GRE tunnel interface carrying ethernet frames.
+    /// Deserialize and serialize from/to 'ip6gretap'.
+    Ip6Gretap,
+    /// IPv4 or IPv6 over IPv6 tunnel interface.
+    /// Deserialize and serialize from/to 'ip6tnl'.
+    Ip6Tnl,
+    /// Team interface. Only used for query, please convert it to bond via
+    /// [crate::TeamInterface::to_bond()].
+    /// Deserialize and serialize from/to 'team'.
+    Team,
+    /// IP VLAN interface.
+    /// Deserialize and serialize from/to 'ipvlan'.
+    IpVlan,
+    /// SocketCAN interface.
+    /// Deserialize and serialize from/to 'can'.
+    Can,
+    /// Mobile broadband modem.
+    /// Deserialize and serialize from/to 'modem'.
+    Modem,
+    /// B.A.T.M.A.N. advanced mesh interface.
+    /// Deserialize and serialize from/to 'batman-adv'.
+    Batman,
+    /// Intermediate Functional Block interface.
+    /// Deserialize and serialize from/to 'ifb'.
+    Ifb,
     /// Unknown interface.
     Unknown,
     /// Reserved for future use.
@@ -101,6 +139,17 @@ impl From<&str> for InterfaceType {
             "tun" => InterfaceType::Tun,
             "macsec" => InterfaceType::MacSec,
             "ipsec" => InterfaceType::Ipsec,
+            "gre" => InterfaceType::Gre,
+            "gretap" => InterfaceType::Gretap,
+            "ip6gre" => InterfaceType::Ip6Gre,
+            "ip6gretap" => InterfaceType::Ip6Gretap,
+            "ip6tnl" => InterfaceType::Ip6Tnl,
+            "team" => InterfaceType::Team,
+            "ipvlan" => InterfaceType::IpVlan,
+            "can" => InterfaceType::Can,
+            "modem" => InterfaceType::Modem,
+            "batman-adv" => InterfaceType::Batman,
+            "ifb" => InterfaceType::Ifb,
             "unknown" => InterfaceType::Unknown,
             _ => InterfaceType::Other(s.to_string()),
         }
@@ -131,6 +180,17 @@ impl std::fmt::Display for InterfaceType {
                 InterfaceType::Tun => "tun",
                 InterfaceType::MacSec => "macsec",
                 InterfaceType::Ipsec => "ipsec",
+                InterfaceType::Gre => "gre",
+                InterfaceType::Gretap => "gretap",
+                InterfaceType::Ip6Gre => "ip6gre",
+                InterfaceType::Ip6Gretap => "ip6gretap",
+                InterfaceType::Ip6Tnl => "ip6tnl",
+                InterfaceType::Team => "team",
+                InterfaceType::IpVlan => "ipvlan",
+                InterfaceType::Can => "can",
+                InterfaceType::Modem => "modem",
+                InterfaceType::Batman => "batman-adv",
+                InterfaceType::Ifb => "ifb",
                 InterfaceType::Other(ref s) => s,
             }
         )
@@ -153,16 +213,31 @@ impl<'de> Deserialize<'de> for InterfaceType {
     {
         let v = serde_json::Value::deserialize(deserializer)?;
         match v.as_str() {
-            Some(s) => Ok(InterfaceType::from(s)),
+            Some(s) => {
+                // Case-insensitive matching for known interface types only,
+                // unknown types are kept untouched.
+                match InterfaceType::from(
+                    crate::deserializer::normalize_enum_str(s).as_str(),
+                ) {
+                    InterfaceType::Other(_) => Ok(InterfaceType::from(s)),
+                    t => Ok(t),
+                }
+            }
             None => Ok(InterfaceType::Unknown),
         }
     }
 }
 
 impl InterfaceType {
-    const USERSPACE_IFACE_TYPES: [Self; 2] = [Self::OvsBridge, Self::Ipsec];
-    const CONTROLLER_IFACES_TYPES: [Self; 4] =
-        [Self::Bond, Self::LinuxBridge, Self::OvsBridge, Self::Vrf];
+    const USERSPACE_IFACE_TYPES: [Self; 3] =
+        [Self::OvsBridge, Self::Ipsec, Self::Modem];
+    const CONTROLLER_IFACES_TYPES: [Self; 5] = [
+        Self::Bond,
+        Self::LinuxBridge,
+        Self::OvsBridge,
+        Self::Vrf,
+        Self::Batman,
+    ];
 
     // other interfaces are also considered as userspace
     pub(crate) fn is_userspace(&self) -> bool {
@@ -209,6 +284,12 @@ pub enum InterfaceState {
     ///
     /// Deserialize and serialize from/to 'ignore'.
     Ignore,
+    /// Only for apply action to keep the existing interface as it is.
+    /// Interface marked as present will not be changed and will not cause
+    /// verification failure, but it is still required to exist in current
+    /// state and will not be removed along with its absent parent.
+    /// Deserialize and serialize from/to 'present'.
+    Present,
 }
 
 impl Default for InterfaceState {
@@ -224,11 +305,78 @@ impl From<&str> for InterfaceState {
             "down" => Self::Down,
             "absent" => Self::Absent,
             "ignore" => Self::Ignore,
+            "present" => Self::Present,
             _ => Self::Unknown,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// The backend managing the interface.
+pub enum InterfaceBackend {
+    /// Managed by NetworkManager.
+    /// Deserialize and serialize from/to 'network-manager'.
+    NetworkManager,
+    /// Activated outside of NetworkManager, NetworkManager only tracks its
+    /// state. Nmstate will take over the interface when it is desired.
+    /// Deserialize and serialize from/to 'network-manager-external'.
+    NetworkManagerExternal,
+    /// Marked as unmanaged in NetworkManager.
+    /// Deserialize and serialize from/to 'network-manager-unmanaged'.
+    NetworkManagerUnmanaged,
+    /// Managed by OpenvSwitch database only.
+    /// Deserialize and serialize from/to 'ovsdb'.
+    Ovsdb,
+    /// Only found in kernel, not managed by any backend.
+    /// Deserialize and serialize from/to 'kernel'.
+    Kernel,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Query only information on which backend is managing the interface.
+pub struct InterfaceManagedBy {
+    /// The backend managing this interface.
+    pub backend: InterfaceBackend,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// UUID of NetworkManager profile activated on this interface.
+    /// Serialize and deserialize to/from `profile-uuid`.
+    pub profile_uuid: Option<String>,
+}
+
+impl InterfaceManagedBy {
+    pub fn new(backend: InterfaceBackend) -> Self {
+        Self {
+            backend,
+            profile_uuid: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Query only information of network interface found in kernel but not
+/// supported by nmstate. Such interface is still shown in
+/// [crate::NetworkState::interfaces] as [UnknownInterface] if possible.
+pub struct UnsupportedInterface {
+    /// Interface name.
+    pub name: String,
+    #[serde(rename = "type")]
+    /// Interface type reported by kernel.
+    /// Serialize and deserialize to/from `type`.
+    pub iface_type: InterfaceType,
+}
+
+impl UnsupportedInterface {
+    pub fn new(name: String, iface_type: InterfaceType) -> Self {
+        Self { name, iface_type }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Default)]
 #[non_exhaustive]
 /// Holder for interface with known interface type defined.
@@ -309,6 +457,30 @@ pub enum Interface {
     MacSec(MacSecInterface),
     /// Ipsec connection
     Ipsec(IpsecInterface),
+    /// IPv4 GRE tunnel interface.
+    Gre(GreInterface),
+    /// IPv4 GRE tunnel interface carrying ethernet frames.
+    Gretap(GretapInterface),
+    /// IPv6 GRE tunnel interface.
+    Ip6Gre(Ip6GreInterface),
+    /// IPv6 GRE tunnel interface carrying ethernet frames.
+    Ip6Gretap(Ip6GretapInterface),
+    /// IPv4 or IPv6 over IPv6 tunnel interface.
+    Ip6Tnl(Ip6TnlInterface),
+    /// Team interface. Only used for query.
+    Team(TeamInterface),
+    /// IP VLAN interface.
+    IpVlan(IpVlanInterface),
+    /// SocketCAN interface.
+    Can(CanInterface),
+    /// Mobile broadband modem.
+    Modem(ModemInterface),
+    /// B.A.T.M.A.N. advanced mesh interface.
+    Batman(BatmanInterface),
+    /// Intermediate Functional Block interface.
+    Ifb(IfbInterface),
+    /// Interface handled by [crate::NmstateInterfacePlugin].
+    Custom(CustomInterface),
 }
 
 impl<'de> Deserialize<'de> for Interface {
@@ -320,7 +492,7 @@ impl<'de> Deserialize<'de> for Interface {
 
         // Ignore all properties except type if state: absent
         if matches!(
-            Option::deserialize(&v["state"])
+            crate::deserializer::option_enum_ignore_case(&v["state"])
                 .map_err(serde::de::Error::custom)?,
             Some(InterfaceState::Absent)
         ) {
@@ -420,8 +592,63 @@ impl<'de> Deserialize<'de> for Interface {
                     .map_err(serde::de::Error::custom)?;
                 Ok(Interface::Ipsec(inner))
             }
+            Some(InterfaceType::Gre) => {
+                let inner = GreInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Gre(inner))
+            }
+            Some(InterfaceType::Gretap) => {
+                let inner = GretapInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Gretap(inner))
+            }
+            Some(InterfaceType::Ip6Gre) => {
+                let inner = Ip6GreInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Ip6Gre(inner))
+            }
+            Some(InterfaceType::Ip6Gretap) => {
+                let inner = Ip6GretapInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Ip6Gretap(inner))
+            }
+            Some(InterfaceType::Ip6Tnl) => {
+                let inner = Ip6TnlInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Ip6Tnl(inner))
+            }
+            Some(InterfaceType::Team) => {
+                let inner = TeamInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Team(inner))
+            }
+            Some(InterfaceType::IpVlan) => {
+                let inner = IpVlanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::IpVlan(inner))
+            }
+            Some(InterfaceType::Can) => {
+                let inner = CanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Can(inner))
+            }
+            Some(InterfaceType::Modem) => {
+                let inner = ModemInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Modem(inner))
+            }
+            Some(InterfaceType::Batman) => {
+                let inner = BatmanInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Batman(inner))
+            }
+            Some(InterfaceType::Ifb) => {
+                let inner = IfbInterface::deserialize(v)
+                    .map_err(serde::de::Error::custom)?;
+                Ok(Interface::Ifb(inner))
+            }
             Some(iface_type) => {
-                log::warn!("Unsupported interface type {}", iface_type);
+                nmstate_warn!("Unsupported interface type {}", iface_type);
                 let inner = UnknownInterface::deserialize(v)
                     .map_err(serde::de::Error::custom)?;
                 Ok(Interface::Unknown(inner))
@@ -441,8 +668,11 @@ impl Interface {
         self.base_iface().name.as_str()
     }
 
+    // Interfaces handled by plugins are kernel interfaces, so they could be
+    // used as port or parent of other interfaces.
     pub(crate) fn is_userspace(&self) -> bool {
-        self.base_iface().iface_type.is_userspace()
+        !matches!(self, Self::Custom(_))
+            && self.base_iface().iface_type.is_userspace()
     }
 
     pub(crate) fn is_controller(&self) -> bool {
@@ -454,6 +684,20 @@ impl Interface {
         self.base_iface().iface_type.clone()
     }
 
+    pub(crate) fn hide_secrets(&mut self) {
+        self.base_iface_mut().hide_secrets();
+        match self {
+            Self::MacSec(iface) => {
+                if let Some(macsec_conf) = iface.macsec.as_mut() {
+                    macsec_conf.hide_secrets();
+                }
+            }
+            Self::Ipsec(iface) => iface.hide_secrets(),
+            Self::Modem(iface) => iface.hide_secrets(),
+            _ => (),
+        }
+    }
+
     pub(crate) fn clone_name_type_only(&self) -> Self {
         match self {
             Self::LinuxBridge(iface) => {
@@ -535,6 +779,67 @@ impl Interface {
                 new_iface.base = iface.base.clone_name_type_only();
                 Self::Ipsec(new_iface)
             }
+            Self::Gre(iface) => {
+                let mut new_iface = GreInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Gre(new_iface)
+            }
+            Self::Gretap(iface) => {
+                let mut new_iface = GretapInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Gretap(new_iface)
+            }
+            Self::Ip6Gre(iface) => {
+                let mut new_iface = Ip6GreInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Ip6Gre(new_iface)
+            }
+            Self::Ip6Gretap(iface) => {
+                let mut new_iface = Ip6GretapInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Ip6Gretap(new_iface)
+            }
+            Self::Ip6Tnl(iface) => {
+                let mut new_iface = Ip6TnlInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Ip6Tnl(new_iface)
+            }
+            Self::Team(iface) => {
+                let mut new_iface = TeamInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Team(new_iface)
+            }
+            Self::IpVlan(iface) => {
+                let mut new_iface = IpVlanInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::IpVlan(new_iface)
+            }
+            Self::Can(iface) => {
+                let mut new_iface = CanInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Can(new_iface)
+            }
+            Self::Modem(iface) => {
+                let mut new_iface = ModemInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Modem(new_iface)
+            }
+            Self::Batman(iface) => {
+                let mut new_iface = BatmanInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Batman(new_iface)
+            }
+            Self::Ifb(iface) => {
+                let mut new_iface = IfbInterface::new();
+                new_iface.base = iface.base.clone_name_type_only();
+                Self::Ifb(new_iface)
+            }
+            Self::Custom(iface) => {
+                let mut new_iface = iface.clone();
+                new_iface.base = iface.base.clone_name_type_only();
+                new_iface.config = None;
+                Self::Custom(new_iface)
+            }
             Self::Unknown(iface) => {
                 let mut new_iface = UnknownInterface::new();
                 new_iface.base = iface.base.clone_name_type_only();
@@ -563,9 +868,16 @@ impl Interface {
         self.base_iface().state == InterfaceState::Ignore
     }
 
+    /// Whether interface is marked as present.
+    pub fn is_present(&self) -> bool {
+        self.base_iface().state == InterfaceState::Present
+    }
+
     // Whether desire state only has `name, type, state`.
     pub(crate) fn is_up_exist_config(&self) -> bool {
+        // The `nm` section is skip_serializing
         self.is_up()
+            && self.base_iface().nm.is_none()
             && match serde_json::to_value(self) {
                 Ok(v) => {
                     if let Some(obj) = v.as_object() {
@@ -631,6 +943,18 @@ impl Interface {
             Self::Loopback(iface) => &iface.base,
             Self::MacSec(iface) => &iface.base,
             Self::Ipsec(iface) => &iface.base,
+            Self::Gre(iface) => &iface.base,
+            Self::Gretap(iface) => &iface.base,
+            Self::Ip6Gre(iface) => &iface.base,
+            Self::Ip6Gretap(iface) => &iface.base,
+            Self::Ip6Tnl(iface) => &iface.base,
+            Self::Team(iface) => &iface.base,
+            Self::IpVlan(iface) => &iface.base,
+            Self::Can(iface) => &iface.base,
+            Self::Modem(iface) => &iface.base,
+            Self::Batman(iface) => &iface.base,
+            Self::Ifb(iface) => &iface.base,
+            Self::Custom(iface) => &iface.base,
             Self::Unknown(iface) => &iface.base,
         }
     }
@@ -652,6 +976,18 @@ impl Interface {
             Self::Loopback(iface) => &mut iface.base,
             Self::MacSec(iface) => &mut iface.base,
             Self::Ipsec(iface) => &mut iface.base,
+            Self::Gre(iface) => &mut iface.base,
+            Self::Gretap(iface) => &mut iface.base,
+            Self::Ip6Gre(iface) => &mut iface.base,
+            Self::Ip6Gretap(iface) => &mut iface.base,
+            Self::Ip6Tnl(iface) => &mut iface.base,
+            Self::Team(iface) => &mut iface.base,
+            Self::IpVlan(iface) => &mut iface.base,
+            Self::Can(iface) => &mut iface.base,
+            Self::Modem(iface) => &mut iface.base,
+            Self::Batman(iface) => &mut iface.base,
+            Self::Ifb(iface) => &mut iface.base,
+            Self::Custom(iface) => &mut iface.base,
             Self::Unknown(iface) => &mut iface.base,
         }
     }
@@ -665,6 +1001,7 @@ impl Interface {
                 Self::OvsBridge(_) => Some(Vec::new()),
                 Self::Bond(_) => Some(Vec::new()),
                 Self::Vrf(_) => Some(Vec::new()),
+                Self::Batman(_) => Some(Vec::new()),
                 _ => None,
             }
         } else {
@@ -673,6 +1010,7 @@ impl Interface {
                 Self::OvsBridge(iface) => iface.ports(),
                 Self::Bond(iface) => iface.ports(),
                 Self::Vrf(iface) => iface.ports(),
+                Self::Batman(iface) => iface.ports(),
                 _ => None,
             }
         }
@@ -690,6 +1028,16 @@ impl Interface {
         is_desired: bool,
     ) -> Result<(), NmstateError> {
         self.base_iface_mut().sanitize(is_desired)?;
+        if self.base_iface().ports_ordered.is_some() && !self.is_controller() {
+            if is_desired {
+                nmstate_warn!(
+                    "The ports-ordered is only valid for controller \
+                    interface, ignoring it for interface {}",
+                    self.name()
+                );
+            }
+            self.base_iface_mut().ports_ordered = None;
+        }
         match self {
             Interface::Ethernet(iface) => iface.sanitize()?,
             Interface::LinuxBridge(iface) => iface.sanitize(is_desired)?,
@@ -701,6 +1049,17 @@ impl Interface {
             Interface::MacVtap(iface) => iface.sanitize(is_desired)?,
             Interface::Loopback(iface) => iface.sanitize(is_desired)?,
             Interface::MacSec(iface) => iface.sanitize(is_desired)?,
+            Interface::Gre(iface) => iface.sanitize(is_desired)?,
+            Interface::Gretap(iface) => iface.sanitize(is_desired)?,
+            Interface::Ip6Gre(iface) => iface.sanitize(is_desired)?,
+            Interface::Ip6Gretap(iface) => iface.sanitize(is_desired)?,
+            Interface::Ip6Tnl(iface) => iface.sanitize(is_desired)?,
+            Interface::Team(iface) => iface.sanitize(is_desired)?,
+            Interface::IpVlan(iface) => iface.sanitize(is_desired)?,
+            Interface::Can(iface) => iface.sanitize(is_desired)?,
+            Interface::Modem(iface) => iface.sanitize(is_desired)?,
+            Interface::Batman(iface) => iface.sanitize(is_desired)?,
+            Interface::Ipsec(iface) => iface.sanitize(is_desired)?,
             _ => (),
         }
         Ok(())
@@ -715,6 +1074,12 @@ impl Interface {
             Interface::MacVtap(vtap) => vtap.parent(),
             Interface::InfiniBand(ib) => ib.parent(),
             Interface::MacSec(macsec) => macsec.parent(),
+            Interface::Gre(gre) => gre.parent(),
+            Interface::Gretap(gretap) => gretap.parent(),
+            Interface::Ip6Gre(ip6gre) => ip6gre.parent(),
+            Interface::Ip6Gretap(ip6gretap) => ip6gretap.parent(),
+            Interface::Ip6Tnl(ip6tnl) => ip6tnl.parent(),
+            Interface::IpVlan(ipvlan) => ipvlan.parent(),
             _ => None,
         }
     }
@@ -828,6 +1193,8 @@ impl MergedInterface {
         self.post_inter_ifaces_process_sriov()?;
         self.post_inter_ifaces_process_vrf()?;
         self.post_inter_ifaces_process_bond()?;
+        self.post_inter_ifaces_process_mac_vlan()?;
+        self.post_inter_ifaces_process_linux_bridge()?;
 
         if let Some(apply_iface) = self.for_apply.as_mut() {
             apply_iface.sanitize(true)?;
@@ -869,16 +1236,20 @@ impl MergedInterface {
                 {
                     br_iface.special_merge(des_br_iface, cur_br_iface);
                 }
+            } else if let Interface::Ipsec(ipsec_iface) = &mut self.merged {
+                if let Interface::Ipsec(des_ipsec_iface) = desired {
+                    ipsec_iface.special_merge(des_ipsec_iface);
+                }
             }
         }
     }
 
-    pub(crate) fn set_copy_from_mac(&mut self, mac: String) {
+    pub(crate) fn set_copy_from_mac(&mut self, mac: MacAddress) {
         if let Some(apply_iface) =
             self.for_apply.as_mut().map(|i| i.base_iface_mut())
         {
             apply_iface.copy_mac_from = None;
-            apply_iface.mac_address = Some(mac.to_string());
+            apply_iface.mac_address = Some(mac.clone());
         }
         if let Some(verify_iface) =
             self.for_verify.as_mut().map(|i| i.base_iface_mut())
@@ -1115,6 +1486,17 @@ fn merge_desire_with_current(
 
     let iface: Interface = serde_json::from_value(desired_value)?;
 
+    // Interface handled by plugin is deserialized as UnknownInterface
+    if let (Interface::Custom(des_iface), Interface::Unknown(iface)) =
+        (desired, &iface)
+    {
+        return Ok(Interface::Custom(CustomInterface::from_unknown(
+            iface,
+            des_iface.base.iface_type.to_string().as_str(),
+            des_iface.config_section(),
+        )?));
+    }
+
     Ok(iface)
 }
 
@@ -0,0 +1,160 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use crate::{
+    CustomInterface, ErrorKind, Interface, InterfaceType, Interfaces,
+    NmstateError,
+};
+
+/// Extension point for interface types not supported by nmstate, for example
+/// experimental kernel devices. Register via
+/// [crate::NetworkState::register_iface_plugin()].
+///
+/// Desired interfaces with `type` matching [Self::iface_type()] are stored as
+/// [CustomInterface] which flow through the same merge and verification
+/// process as built-in interface types. They are treated as kernel
+/// interfaces, hence could be used as port of controller or parent of
+/// other interfaces. NetworkManager and kernel backends never touch them,
+/// the plugin is responsible for creating, modifying and deleting them,
+/// including attaching to the controller stated in `base.controller`.
+///
+/// Plugin interfaces without controller are applied before backends, so
+/// they exist when backends create their children. Plugin interfaces with
+/// controller and absent ones are applied after backends.
+pub trait NmstateInterfacePlugin: Send + Sync {
+    /// Interface type handled by this plugin, used as `type` property of
+    /// interface. Should not be any type supported by nmstate.
+    fn iface_type(&self) -> &str;
+
+    /// Property name holding type specific configuration. Default to
+    /// [Self::iface_type()].
+    fn config_section(&self) -> &str {
+        self.iface_type()
+    }
+
+    /// Validate desired interface before merging with current state.
+    /// Default to accepting everything.
+    fn validate(&self, _iface: &CustomInterface) -> Result<(), NmstateError> {
+        Ok(())
+    }
+
+    /// Retrieve all interfaces of this type. Invoked when retrieving
+    /// [crate::NetworkState], the result is used as current state for
+    /// merging and verification.
+    fn retrieve(&self) -> Result<Vec<CustomInterface>, NmstateError>;
+
+    /// Create or modify the interface. The `iface` is the desired state
+    /// merged with the current state, while the `current` is None for new
+    /// interface. Might be invoked more than once when nmstate retries,
+    /// hence should be idempotent.
+    fn apply(
+        &self,
+        iface: &CustomInterface,
+        current: Option<&CustomInterface>,
+    ) -> Result<(), NmstateError>;
+
+    /// Delete the interface.
+    fn delete(&self, iface_name: &str) -> Result<(), NmstateError>;
+
+    /// Restore interfaces of this type to the `previous` state retrieved
+    /// before applying. Invoked when apply failed and NetworkManager
+    /// checkpoint is rolled back. Default to deleting interfaces not
+    /// existing in `previous` and applying the changed ones.
+    fn rollback(
+        &self,
+        previous: &[CustomInterface],
+    ) -> Result<(), NmstateError> {
+        let current = self.retrieve()?;
+        for cur_iface in current
+            .iter()
+            .filter(|c| !previous.iter().any(|p| p.base.name == c.base.name))
+        {
+            self.delete(cur_iface.base.name.as_str())?;
+        }
+        for pre_iface in previous {
+            let cur_iface =
+                current.iter().find(|c| c.base.name == pre_iface.base.name);
+            if cur_iface != Some(pre_iface) {
+                self.apply(pre_iface, cur_iface)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Wrapper of interface plugin allowing `NetworkState` to derive `Debug`,
+// `PartialEq` and `Eq`.
+#[derive(Clone)]
+pub(crate) struct IfacePluginHolder(pub(crate) Arc<dyn NmstateInterfacePlugin>);
+
+impl std::fmt::Debug for IfacePluginHolder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IfacePlugin({})", self.0.iface_type())
+    }
+}
+
+impl PartialEq for IfacePluginHolder {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for IfacePluginHolder {}
+
+impl Interfaces {
+    // Convert desired interfaces of type handled by plugins from
+    // [crate::UnknownInterface] to [CustomInterface] and validate them.
+    pub(crate) fn resolve_plugin_ifaces(
+        &mut self,
+        plugins: &[IfacePluginHolder],
+    ) -> Result<(), NmstateError> {
+        for plugin in plugins.iter().map(|p| p.0.as_ref()) {
+            let iface_type = InterfaceType::from(plugin.iface_type());
+            if !iface_type.is_other() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Interface plugin type {iface_type} conflicts with \
+                        interface type supported by nmstate"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            let iface_names: Vec<String> = self
+                .kernel_ifaces
+                .values()
+                .filter_map(|i| match i {
+                    Interface::Unknown(i)
+                        if i.other.get("type").and_then(|t| t.as_str())
+                            == Some(plugin.iface_type()) =>
+                    {
+                        Some(i.base.name.clone())
+                    }
+                    _ => None,
+                })
+                .collect();
+            for iface_name in iface_names {
+                if let Some(Interface::Unknown(iface)) =
+                    self.kernel_ifaces.remove(&iface_name)
+                {
+                    let new_iface = CustomInterface::from_unknown(
+                        &iface,
+                        plugin.iface_type(),
+                        plugin.config_section(),
+                    )?;
+                    plugin.validate(&new_iface)?;
+                    for (name, t) in self.insert_order.iter_mut() {
+                        if name == &iface_name && t == &InterfaceType::Unknown {
+                            *t = iface_type.clone();
+                        }
+                    }
+                    self.kernel_ifaces
+                        .insert(iface_name, Interface::Custom(new_iface));
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -4,9 +4,10 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     DispatchConfig, ErrorKind, EthtoolConfig, Ieee8021XConfig,
-    InterfaceIdentifier, InterfaceIpv4, InterfaceIpv6, InterfaceState,
-    InterfaceType, LldpConfig, MergedInterface, MptcpConfig, NmstateError,
-    OvsDbIfaceConfig, RouteEntry, WaitIp,
+    InterfaceIdentifier, InterfaceIpv4, InterfaceIpv6, InterfaceManagedBy,
+    InterfaceState, InterfaceType, LldpConfig, MacAddress, MergedInterface,
+    MptcpConfig, NmPassthroughConfig, NmstateError, OvsDbIfaceConfig,
+    RouteEntry, WaitIp,
 };
 
 const MINIMUM_IPV6_MTU: u64 = 1280;
@@ -32,7 +33,10 @@ pub struct BaseInterface {
     #[serde(rename = "type", default = "default_iface_type")]
     /// Interface type. Serialize and deserialize to/from `type`
     pub iface_type: InterfaceType,
-    #[serde(default = "default_state")]
+    #[serde(
+        default = "default_state",
+        deserialize_with = "crate::deserializer::enum_ignore_case"
+    )]
     /// Interface state. Default to [InterfaceState::Up] when applying.
     pub state: InterfaceState,
     #[serde(default, skip_serializing_if = "InterfaceIdentifier::is_default")]
@@ -50,13 +54,13 @@ pub struct BaseInterface {
     /// MAC address in the format: upper case hex string separated by `:` on
     /// every two characters. Case insensitive when applying.
     /// Serialize and deserialize to/from `mac-address`.
-    pub mac_address: Option<String>,
+    pub mac_address: Option<MacAddress>,
     #[serde(skip)]
     /// MAC address never change after reboots(normally stored in firmware of
     /// network interface). Using the same format as `mac_address` property.
     /// Ignored during apply.
     /// TODO: expose it and we do not special merge for this.
-    pub permanent_mac_address: Option<String>,
+    pub permanent_mac_address: Option<MacAddress>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
@@ -72,11 +76,26 @@ pub struct BaseInterface {
     /// Maximum MTU allowed. Ignored during apply.
     /// Serialize and deserialize to/from `max-mtu`.
     pub max_mtu: Option<u64>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     /// Whether system should wait certain IP stack before considering
     /// network interface activated.
     /// Serialize and deserialize to/from `wait-ip`.
     pub wait_ip: Option<WaitIp>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Seconds to wait for the IP stack not required by `wait-ip` before
+    /// considering network interface activated. Only valid for `wait-ip` set
+    /// to `ipv4`, `ipv6` or `any-with-grace`. Default to
+    /// [WaitIp::DEFAULT_GRACE_TIMEOUT] for `any-with-grace`.
+    /// Serialize and deserialize to/from `wait-ip-timeout`.
+    pub wait_ip_timeout: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// IPv4 information.
     /// Hided if interface is not allowed to hold IP information(e.g. port of
@@ -110,9 +129,54 @@ pub struct BaseInterface {
     pub accept_all_mac_addresses: Option<bool>,
     #[serde(skip_serializing)]
     /// Copy the MAC address from specified interface.
+    /// For bond interface, the specified interface should be one of its
+    /// ports and the permanent MAC address of it will be used when
+    /// available, so bond MAC address stays the same after port swaps.
     /// Ignored during serializing.
     /// Deserialize from `copy-mac-from`.
     pub copy_mac_from: Option<String>,
+    #[serde(
+        skip_serializing,
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether the declared order of ports in controller interface (bond,
+    /// linux bridge, OVS bridge) should be enforced when applying.
+    /// When set to true, ports will be activated one by one in the order
+    /// specified by user in the port list, this is useful when the port
+    /// order matters, for example bond MAC address or OVS bond hashing.
+    /// When unset or false, nmstate does not enforce any activation order
+    /// and leaves it to the backend.
+    /// The port order is not verified as kernel does not guarantee it.
+    /// Ignored during serializing.
+    /// Deserialize from `ports-ordered`.
+    pub ports_ordered: Option<bool>,
+    #[serde(
+        skip_serializing,
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether the NetworkManager profile of this interface should be
+    /// volatile: kept in memory only and deleted automatically once the
+    /// interface is deactivated, so ephemeral configurations never touch
+    /// disk. Only supported by NetworkManager backend.
+    /// Ignored during serializing.
+    /// Deserialize from `volatile`.
+    pub volatile: Option<bool>,
+    #[serde(
+        skip_serializing,
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Set to false to apply changes of this interface to memory only,
+    /// while the other interfaces are still persisted. The existing
+    /// persistent configuration of this interface is left untouched, but
+    /// interface marked as absent is still removed persistently.
+    /// Has no effect when the whole state is applied in memory only mode.
+    /// Only supported by NetworkManager backend.
+    /// Ignored during serializing.
+    /// Deserialize from `save-to-disk`.
+    pub save_to_disk: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "ovs-db")]
     /// Interface specific OpenvSwitch database configurations.
     pub ovsdb: Option<OvsDbIfaceConfig>,
@@ -129,6 +193,18 @@ pub struct BaseInterface {
     /// Dispatch script configurations
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dispatch: Option<DispatchConfig>,
+    #[serde(skip_serializing)]
+    /// NetworkManager settings not modeled by nmstate, applied to the
+    /// NetworkManager profile of this interface as it is.
+    /// Only supported by NetworkManager backend.
+    /// Ignored during serializing.
+    /// Deserialize from `nm`.
+    pub nm: Option<NmPassthroughConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Which backend is managing this interface.
+    /// Query only, ignored when applying.
+    /// Serialize and deserialize to/from `managed-by`.
+    pub managed_by: Option<InterfaceManagedBy>,
     #[serde(skip)]
     pub controller_type: Option<InterfaceType>,
     // The interface lowest up_priority will be activated first.
@@ -147,6 +223,10 @@ impl BaseInterface {
     // Besides normal HashMap merging:
     //  * the IP stacks need extra care
     //  * `copy_mac_from` is skip_serializing
+    //  * `ports_ordered` is skip_serializing
+    //  * `volatile` is skip_serializing
+    //  * `save_to_disk` is skip_serializing
+    //  * `nm` is skip_serializing
     //  * `permanent_mac_address` is skip_serializing
     pub(crate) fn special_merge(&mut self, desired: &Self, current: &Self) {
         if let Some(ipv4) = self.ipv4.as_mut() {
@@ -167,6 +247,10 @@ impl BaseInterface {
             self.permanent_mac_address = current.permanent_mac_address.clone();
         }
         self.copy_mac_from = desired.copy_mac_from.clone();
+        self.ports_ordered = desired.ports_ordered;
+        self.volatile = desired.volatile;
+        self.save_to_disk = desired.save_to_disk;
+        self.nm = desired.nm.clone();
     }
 
     fn has_controller(&self) -> bool {
@@ -215,6 +299,36 @@ impl BaseInterface {
         }
     }
 
+    // Remove properties holding kernel or NetworkManager default values or
+    // read-only status, so the queried state could be used as a starting point
+    // of desired state.
+    pub(crate) fn trim_defaults(&mut self) {
+        self.permanent_mac_address = None;
+        self.max_mtu = None;
+        self.min_mtu = None;
+        if self.accept_all_mac_addresses == Some(false) {
+            self.accept_all_mac_addresses = None;
+        }
+        if self.lldp.as_ref().map(|l| l.enabled) == Some(false) {
+            self.lldp = None;
+        }
+        if self
+            .mptcp
+            .as_ref()
+            .and_then(|m| m.address_flags.as_ref())
+            .map(|f| f.is_empty())
+            != Some(false)
+        {
+            self.mptcp = None;
+        }
+        if let Some(ethtool_conf) = self.ethtool.as_mut() {
+            ethtool_conf.trim_defaults();
+            if ethtool_conf == &EthtoolConfig::default() {
+                self.ethtool = None;
+            }
+        }
+    }
+
     pub(crate) fn is_ipv4_enabled(&self) -> bool {
         self.ipv4.as_ref().map(|i| i.enabled) == Some(true)
     }
@@ -227,14 +341,12 @@ impl BaseInterface {
         &mut self,
         is_desired: bool,
     ) -> Result<(), NmstateError> {
-        if let Some(mac) = self.mac_address.as_mut() {
-            mac.make_ascii_uppercase();
-        }
         // These are not for apply or verify
         self.permanent_mac_address = None;
         self.max_mtu = None;
         self.min_mtu = None;
         self.copy_mac_from = None;
+        self.managed_by = None;
 
         if let Some(ipv4_conf) = self.ipv4.as_mut() {
             ipv4_conf.sanitize(is_desired)?;
@@ -263,6 +375,7 @@ impl BaseInterface {
 
         if !self.can_have_ip() {
             self.wait_ip = None;
+            self.wait_ip_timeout = None;
         }
 
         if is_desired
@@ -0,0 +1,145 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel B.A.T.M.A.N. advanced mesh interface. Neither
+/// NetworkManager nor nispor could manage batman-adv devices, hence nmstate
+/// configures them directly in kernel via netlink which means the
+/// configuration is not persistent.
+/// The ports are the hard interfaces participating in the mesh.
+/// The example yaml output of [crate::NetworkState] with a batman-adv
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: bat0
+///   type: batman-adv
+///   state: up
+///   batman-adv:
+///     routing-algorithm: batman-iv
+///     port:
+///     - eth1
+///     - wlan0
+/// ```
+pub struct BatmanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(rename = "batman-adv", skip_serializing_if = "Option::is_none")]
+    /// Deserialize and serialize from/to `batman-adv`.
+    pub batman: Option<BatmanConfig>,
+}
+
+impl Default for BatmanInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Batman,
+                ..Default::default()
+            },
+            batman: None,
+        }
+    }
+}
+
+impl BatmanInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn ports(&self) -> Option<Vec<&str>> {
+        self.batman
+            .as_ref()
+            .and_then(|conf| conf.port.as_ref())
+            .map(|ports| ports.as_slice().iter().map(|p| p.as_str()).collect())
+    }
+
+    pub(crate) fn sanitize(
+        &mut self,
+        _is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        // Sort ports
+        if let Some(ports) = self.batman.as_mut().and_then(|c| c.port.as_mut())
+        {
+            ports.sort();
+            ports.dedup();
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct BatmanConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
+    /// Routing algorithm of the mesh. Kernel only allows setting it on
+    /// creation, hence changing it will recreate the interface.
+    pub routing_algorithm: Option<BatmanRoutingAlgorithm>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "ports")]
+    /// Hard interfaces attached to this mesh interface.
+    /// Deserialize and serialize from/to `port`.
+    /// Also deserialize from `ports`.
+    pub port: Option<Vec<String>>,
+}
+
+impl BatmanConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum BatmanRoutingAlgorithm {
+    /// Deserialize and serialize from/to `batman-iv`.
+    BatmanIv,
+    /// Deserialize and serialize from/to `batman-v`.
+    BatmanV,
+}
+
+impl Default for BatmanRoutingAlgorithm {
+    fn default() -> Self {
+        Self::BatmanIv
+    }
+}
+
+impl std::fmt::Display for BatmanRoutingAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::BatmanIv => "BATMAN_IV",
+                Self::BatmanV => "BATMAN_V",
+            }
+        )
+    }
+}
+
+// Parse the kernel representation, e.g. `BATMAN_IV`
+impl FromStr for BatmanRoutingAlgorithm {
+    type Err = NmstateError;
+    fn from_str(s: &str) -> Result<Self, NmstateError> {
+        match crate::deserializer::normalize_enum_str(s.trim()).as_str() {
+            "batman-iv" => Ok(Self::BatmanIv),
+            "batman-v" => Ok(Self::BatmanV),
+            _ => Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid batman-adv routing algorithm {s}, \
+                    expecting BATMAN_IV or BATMAN_V"
+                ),
+            )),
+        }
+    }
+}
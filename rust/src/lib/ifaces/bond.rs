@@ -5,11 +5,16 @@ use std::convert::TryFrom;
 
 use serde::{Deserialize, Serialize};
 
+use crate::warning::nmstate_warn;
 use crate::{
     deserializer::NumberAsString, BaseInterface, ErrorKind, Interface,
     InterfaceState, InterfaceType, MergedInterface, NmstateError,
 };
 
+pub(crate) const DEFAULT_ARP_MISSED_MAX: u8 = 2;
+// Kernel `BOND_MAX_NS_TARGETS`
+const MAX_NS_IP6_TARGET_COUNT: usize = 16;
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
@@ -113,10 +118,59 @@ impl BondInterface {
         }
     }
 
+    // Kernel is showing IPv6 address in compressed format, convert
+    // to it for verification.
+    fn sanitize_ns_ip6_target(&mut self) -> Result<(), NmstateError> {
+        if let Some(bond_opts) =
+            self.bond.as_mut().and_then(|b| b.options.as_mut())
+        {
+            if let Some(ns_ip6_target) = bond_opts.ns_ip6_target.as_ref() {
+                if ns_ip6_target.is_empty() {
+                    bond_opts.ns_ip6_target = None;
+                    return Ok(());
+                }
+                let mut addrs: Vec<String> = Vec::new();
+                for addr_str in ns_ip6_target.split(',').map(|s| s.trim()) {
+                    match addr_str.parse::<std::net::Ipv6Addr>() {
+                        Ok(addr) => addrs.push(addr.to_string()),
+                        Err(e) => {
+                            let e = NmstateError::new(
+                                ErrorKind::InvalidArgument,
+                                format!(
+                                    "Invalid IPv6 address {addr_str} in \
+                                    ns_ip6_target of bond {}: {e}",
+                                    self.base.name
+                                ),
+                            );
+                            log::error!("{}", e);
+                            return Err(e);
+                        }
+                    }
+                }
+                if addrs.len() > MAX_NS_IP6_TARGET_COUNT {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The maximum number of ns_ip6_target of bond {} \
+                            is {MAX_NS_IP6_TARGET_COUNT}, but got {}",
+                            self.base.name,
+                            addrs.len()
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                bond_opts.ns_ip6_target = Some(addrs.join(","));
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn sanitize(&mut self) -> Result<(), NmstateError> {
         self.sort_ports();
         self.sort_ports_config();
         self.drop_empty_arp_ip_target();
+        self.sanitize_ns_ip6_target()?;
         self.make_ad_actor_system_mac_upper_case();
         Ok(())
     }
@@ -163,7 +217,7 @@ impl BondInterface {
         Self::default()
     }
 
-    fn is_mac_restricted_mode(&self) -> bool {
+    pub(crate) fn is_mac_restricted_mode(&self) -> bool {
         self.bond
             .as_ref()
             .and_then(|bond_conf| {
@@ -240,6 +294,42 @@ impl BondInterface {
         Ok(())
     }
 
+    // Fail on
+    // * copy-mac-from pointing to interface which is not bond port
+    // * copy-mac-from in mac restricted mode, bond MAC is following active
+    //   port in that mode
+    pub(crate) fn validate_copy_mac_from(&self) -> Result<(), NmstateError> {
+        let src_iface_name = match self.base.copy_mac_from.as_deref() {
+            Some(n) => n,
+            None => return Ok(()),
+        };
+        if self.is_mac_restricted_mode() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The copy-mac-from cannot be specified in bond interface \
+                    {} along with fail_over_mac active on active backup mode",
+                    self.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if !self.ports().unwrap_or_default().contains(&src_iface_name) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The copy-mac-from of bond interface {} should be one \
+                    of its ports, but got {src_iface_name}",
+                    self.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
     fn validate_conflict_in_port_and_port_configs(
         &self,
     ) -> Result<(), NmstateError> {
@@ -279,6 +369,48 @@ impl BondInterface {
         Ok(())
     }
 
+    // Fail on
+    // * active port defined in bond mode other than active-backup,
+    //   balance-tlb and balance-alb
+    // * active port is not port of this bond
+    fn validate_active_port(&self, merged: &Self) -> Result<(), NmstateError> {
+        let active_port =
+            match self.bond.as_ref().and_then(|b| b.active_port.as_deref()) {
+                Some(p) => p,
+                None => return Ok(()),
+            };
+        if ![
+            Some(BondMode::ActiveBackup),
+            Some(BondMode::TLB),
+            Some(BondMode::ALB),
+        ]
+        .contains(&merged.mode())
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The `active-port` of bond {} is only valid for \
+                    active-backup, balance-tlb and balance-alb mode",
+                    self.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if !merged.ports().unwrap_or_default().contains(&active_port) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The `active-port` {active_port} is not port of bond {}",
+                    self.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
     pub(crate) fn is_options_reset(&self) -> bool {
         if let Some(bond_opts) = self
             .bond
@@ -423,7 +555,7 @@ pub enum BondMode {
 impl TryFrom<NumberAsString> for BondMode {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "balance-rr" => Ok(Self::RoundRobin),
             "1" | "active-backup" => Ok(Self::ActiveBackup),
             "2" | "balance-xor" => Ok(Self::XOR),
@@ -497,6 +629,19 @@ pub struct BondConfig {
     /// names specified in `port` and `ports-config` conflict with each
     /// other.
     pub ports_config: Option<Vec<BondPortConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Deserialize and serialize from/to `active-port`.
+    /// When applying, set the `active_slave` bond option of NetworkManager
+    /// to make specified port as the active port of bond, useful for
+    /// steering traffic away from a port before servicing it. Only valid
+    /// for `active-backup`, `balance-tlb` and `balance-alb` mode.
+    /// The `active_slave` option is persistent in NetworkManager profile and
+    /// takes effect on every activation of the bond, applying bond without
+    /// this property removes it from the profile.
+    /// The active port might be changed by kernel afterwards according to
+    /// `primary` and `primary_reselect` options, hence it is not verified.
+    /// Not supported in kernel only mode.
+    pub active_port: Option<String>,
 }
 
 impl BondConfig {
@@ -506,7 +651,7 @@ impl BondConfig {
 
     pub(crate) fn post_deserialize_cleanup(&mut self) {
         if self.slaves.as_ref().is_some() {
-            log::warn!(
+            nmstate_warn!(
                 "The `slaves` is deprecated, please replace with `ports`."
             );
             self.port = self.slaves.clone();
@@ -532,7 +677,7 @@ pub enum BondAdSelect {
 impl TryFrom<NumberAsString> for BondAdSelect {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "stable" => Ok(Self::Stable),
             "1" | "bandwidth" => Ok(Self::Bandwidth),
             "2" | "count" => Ok(Self::Count),
@@ -590,7 +735,7 @@ pub enum BondLacpRate {
 impl TryFrom<NumberAsString> for BondLacpRate {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "slow" => Ok(Self::Slow),
             "1" | "fast" => Ok(Self::Fast),
             v => Err(NmstateError::new(
@@ -637,7 +782,7 @@ pub enum BondAllPortsActive {
 impl TryFrom<NumberAsString> for BondAllPortsActive {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "dropped" => Ok(Self::Dropped),
             "1" | "delivered" => Ok(Self::Delivered),
             v => Err(NmstateError::new(
@@ -691,7 +836,7 @@ pub enum BondArpAllTargets {
 impl TryFrom<NumberAsString> for BondArpAllTargets {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "any" => Ok(Self::Any),
             "1" | "all" => Ok(Self::All),
             v => Err(NmstateError::new(
@@ -761,14 +906,14 @@ pub enum BondArpValidate {
 impl TryFrom<NumberAsString> for BondArpValidate {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "none" => Ok(Self::None),
             "1" | "active" => Ok(Self::Active),
             "2" | "backup" => Ok(Self::Backup),
             "3" | "all" => Ok(Self::All),
             "4" | "filter" => Ok(Self::Filter),
-            "5" | "filter_active" => Ok(Self::FilterActive),
-            "6" | "filter_backup" => Ok(Self::FilterBackup),
+            "5" | "filter-active" => Ok(Self::FilterActive),
+            "6" | "filter-backup" => Ok(Self::FilterBackup),
             v => Err(NmstateError::new(
                 ErrorKind::InvalidArgument,
                 format!(
@@ -834,7 +979,7 @@ pub enum BondFailOverMac {
 impl TryFrom<NumberAsString> for BondFailOverMac {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "none" => Ok(Self::None),
             "1" | "active" => Ok(Self::Active),
             "2" | "follow" => Ok(Self::Follow),
@@ -892,7 +1037,7 @@ pub enum BondPrimaryReselect {
 impl TryFrom<NumberAsString> for BondPrimaryReselect {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "always" => Ok(Self::Always),
             "1" | "better" => Ok(Self::Better),
             "2" | "failure" => Ok(Self::Failure),
@@ -956,7 +1101,7 @@ pub enum BondXmitHashPolicy {
 impl TryFrom<NumberAsString> for BondXmitHashPolicy {
     type Error = NmstateError;
     fn try_from(s: NumberAsString) -> Result<Self, NmstateError> {
-        match s.as_str() {
+        match s.to_enum_str().as_str() {
             "0" | "layer2" => Ok(Self::Layer2),
             "1" | "layer3+4" => Ok(Self::Layer34),
             "2" | "layer2+3" => Ok(Self::Layer23),
@@ -1317,7 +1462,20 @@ pub struct BondOptions {
         default,
         deserialize_with = "crate::deserializer::option_u8_or_string"
     )]
+    /// Specifies the number of arp_interval monitor checks that must fail in
+    /// order for an interface to be marked down by the ARP monitor. Also
+    /// used by the IPv6 NS monitor. Requires kernel 5.17+. The default
+    /// value is 2. Not supported in 802.3ad, balance-tlb and balance-alb
+    /// mode.
     pub arp_missed_max: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Specifies the IPv6 addresses to use as IPv6 monitoring peers when
+    /// arp_interval is > 0. These are the targets of the NS request sent to
+    /// determine the health of the link to the targets. Multiple IPv6
+    /// addresses must be separated by a comma. The maximum number of targets
+    /// that can be specified is 16. Requires kernel 6.0+. Not supported in
+    /// 802.3ad, balance-tlb and balance-alb mode.
+    pub ns_ip6_target: Option<String>,
 }
 
 impl BondOptions {
@@ -1358,6 +1516,39 @@ impl BondOptions {
         Ok(())
     }
 
+    // Kernel does not support ARP or NS monitoring in 802.3ad, balance-tlb
+    // and balance-alb mode.
+    fn validate_arp_ns_monitor_mode(
+        &self,
+        mode: BondMode,
+    ) -> Result<(), NmstateError> {
+        if ![BondMode::LACP, BondMode::TLB, BondMode::ALB].contains(&mode) {
+            return Ok(());
+        }
+        let opt_name = if self
+            .arp_missed_max
+            .map(|v| v != DEFAULT_ARP_MISSED_MAX)
+            .unwrap_or_default()
+        {
+            "arp_missed_max"
+        } else if self
+            .ns_ip6_target
+            .as_ref()
+            .map(|v| !v.is_empty())
+            .unwrap_or_default()
+        {
+            "ns_ip6_target"
+        } else {
+            return Ok(());
+        };
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Bond option {opt_name} is not supported in {mode} mode"),
+        );
+        log::error!("{}", e);
+        Err(e)
+    }
+
     fn validate_balance_slb(
         &self,
         current: Option<&Self>,
@@ -1395,6 +1586,9 @@ impl MergedInterface {
                 .validate_new_iface_with_no_mode(self.current.as_ref())?;
             apply_iface.validate_mac_restricted_mode(self.current.as_ref())?;
             apply_iface.validate_conflict_in_port_and_port_configs()?;
+            if let Interface::Bond(merged_iface) = &self.merged {
+                apply_iface.validate_active_port(merged_iface)?;
+            }
 
             if let Some(bond_opts) =
                 apply_iface.bond.as_ref().and_then(|b| b.options.as_ref())
@@ -1417,6 +1611,7 @@ impl MergedInterface {
                             } else {
                                 None
                             };
+                        bond_opts.validate_arp_ns_monitor_mode(mode)?;
                         bond_opts.validate_balance_slb(cur_bond_opts, mode)?
                     }
                 }
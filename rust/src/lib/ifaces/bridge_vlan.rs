@@ -1,6 +1,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
@@ -23,7 +22,11 @@ pub struct BridgePortVlanConfig {
     /// Enable native VLAN.
     /// Deserialize and serialize from/to `enable-native`.
     pub enable_native: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     /// Bridge VLAN filtering mode
     pub mode: Option<BridgePortVlanMode>,
     #[serde(
@@ -63,39 +66,41 @@ impl BridgePortVlanConfig {
             && self.trunk_tags.is_none()
     }
 
-    pub(crate) fn sort_trunk_tags(&mut self) {
+    // Sort the trunk tags and merge the contiguous ones into VLAN ID range,
+    // so that the same set of VLANs always has the same representation
+    // without expanding ranges into individual IDs which is slow for large
+    // ranges like 2-4094 on many ports.
+    // Overlapping tags are merged also, please validate them beforehand.
+    pub(crate) fn compact_trunk_tags(&mut self) {
         if let Some(trunk_tags) = self.trunk_tags.as_mut() {
-            trunk_tags.sort_unstable_by(|tag_a, tag_b| match (tag_a, tag_b) {
-                (BridgePortTrunkTag::Id(a), BridgePortTrunkTag::Id(b)) => {
-                    a.cmp(b)
-                }
-                _ => {
-                    log::warn!(
-                        "Please call flatten_vlan_ranges() \
-                        before sort_port_vlans()"
-                    );
-                    std::cmp::Ordering::Equal
-                }
-            })
-        }
-    }
-
-    pub(crate) fn flatten_vlan_ranges(&mut self) {
-        if let Some(trunk_tags) = &self.trunk_tags {
-            let mut new_trunk_tags = Vec::new();
-            for trunk_tag in trunk_tags {
-                match trunk_tag {
-                    BridgePortTrunkTag::Id(_) => {
-                        new_trunk_tags.push(trunk_tag.clone())
+            let mut ranges: Vec<(u16, u16)> = trunk_tags
+                .iter()
+                .map(BridgePortTrunkTag::get_vlan_tag_range)
+                .collect();
+            ranges.sort_unstable();
+            let mut merged: Vec<(u16, u16)> = Vec::new();
+            for (min, max) in ranges {
+                if let Some(last) = merged.last_mut() {
+                    if u32::from(min) <= u32::from(last.1) + 1 {
+                        last.1 = std::cmp::max(last.1, max);
+                        continue;
                     }
-                    BridgePortTrunkTag::IdRange(range) => {
-                        for i in range.min..range.max + 1 {
-                            new_trunk_tags.push(BridgePortTrunkTag::Id(i));
-                        }
-                    }
-                };
+                }
+                merged.push((min, max));
             }
-            self.trunk_tags = Some(new_trunk_tags);
+            *trunk_tags = merged
+                .into_iter()
+                .map(|(min, max)| {
+                    if min == max {
+                        BridgePortTrunkTag::Id(min)
+                    } else {
+                        BridgePortTrunkTag::IdRange(BridgePortVlanRange {
+                            min,
+                            max,
+                        })
+                    }
+                })
+                .collect();
         }
     }
 
@@ -302,47 +307,23 @@ pub struct BridgePortVlanRange {
     pub max: u16,
 }
 
-fn validate_overlap_trunk_tags(
+pub(crate) fn validate_overlap_trunk_tags(
     tags: &[BridgePortTrunkTag],
 ) -> Result<(), NmstateError> {
-    let mut found: HashMap<u16, &BridgePortTrunkTag> = HashMap::new();
-    for tag in tags {
-        match tag {
-            BridgePortTrunkTag::Id(d) => match found.entry(*d) {
-                Entry::Occupied(o) => {
-                    let existing_tag = o.get();
-                    return Err(NmstateError::new(
-                        ErrorKind::InvalidArgument,
-                        format!(
-                            "Bridge VLAN trunk tag {tag} is \
-                            overlapping with other tag {existing_tag}"
-                        ),
-                    ));
-                }
-                Entry::Vacant(v) => {
-                    v.insert(tag);
-                }
-            },
-
-            BridgePortTrunkTag::IdRange(range) => {
-                for i in range.min..range.max + 1 {
-                    match found.entry(i) {
-                        Entry::Occupied(o) => {
-                            let existing_tag = o.get();
-                            return Err(NmstateError::new(
-                                ErrorKind::InvalidArgument,
-                                format!(
-                                    "Bridge VLAN trunk tag {tag} is \
-                                    overlapping with other tag {existing_tag}"
-                                ),
-                            ));
-                        }
-                        Entry::Vacant(v) => {
-                            v.insert(tag);
-                        }
-                    }
-                }
-            }
+    let mut sorted_tags: Vec<&BridgePortTrunkTag> = tags.iter().collect();
+    sorted_tags.sort_unstable_by_key(|t| t.get_vlan_tag_range());
+    for pair in sorted_tags.windows(2) {
+        let (_, pre_max) = pair[0].get_vlan_tag_range();
+        let (cur_min, _) = pair[1].get_vlan_tag_range();
+        if cur_min <= pre_max {
+            return Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Bridge VLAN trunk tag {} is \
+                    overlapping with other tag {}",
+                    pair[1], pair[0]
+                ),
+            ));
         }
     }
     Ok(())
@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel SocketCAN interface. Neither NetworkManager nor nispor could
+/// manage CAN devices, hence nmstate configures them directly in kernel via
+/// netlink which means the configuration is not persistent.
+/// CAN devices are backed by hardware, `state: absent` or `state: down` only
+/// brings the link down.
+/// The example yaml output of [crate::NetworkState] with a CAN interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: can0
+///   type: can
+///   state: up
+///   can:
+///     bitrate: 500000
+///     sample-point: 875
+///     restart-ms: 100
+///     fd: true
+///     data-bitrate: 2000000
+/// ```
+pub struct CanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can: Option<CanConfig>,
+}
+
+impl Default for CanInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Can,
+                ..Default::default()
+            },
+            can: None,
+        }
+    }
+}
+
+impl CanInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if self.base.ipv4.as_ref().map(|i| i.enabled) == Some(true)
+                || self.base.ipv6.as_ref().map(|i| i.enabled) == Some(true)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "CAN interface {} cannot have IP configuration",
+                        self.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if let Some(conf) = self.can.as_ref() {
+                conf.validate(self.base.name.as_str())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct CanConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Bit-rate in bits per second of the nominal (arbitration) phase.
+    pub bitrate: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u16_or_string"
+    )]
+    /// Sample point in one-tenth of a percent, e.g. `875` means 87.5%.
+    /// Should be smaller than 1000.
+    pub sample_point: Option<u16>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Delay in milliseconds before automatically restarting the controller
+    /// after bus-off. Use 0 to disable automatic restart.
+    pub restart_ms: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Enable CAN FD (flexible data-rate) mode.
+    pub fd: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Bit-rate in bits per second of the data phase. Only valid when `fd`
+    /// is enabled.
+    pub data_bitrate: Option<u32>,
+}
+
+impl CanConfig {
+    pub(crate) const SAMPLE_POINT_MAX: u16 = 999;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(&self, iface_name: &str) -> Result<(), NmstateError> {
+        if let Some(sample_point) = self.sample_point {
+            if sample_point > Self::SAMPLE_POINT_MAX {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid sample-point {sample_point} of CAN interface \
+                        {iface_name}, should be in the range of 0 - {}",
+                        Self::SAMPLE_POINT_MAX
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if self.bitrate == Some(0) || self.data_bitrate == Some(0) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The bitrate and data-bitrate of CAN interface \
+                    {iface_name} cannot be 0"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if self.data_bitrate.is_some() && self.fd == Some(false) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The data-bitrate of CAN interface {iface_name} is only \
+                    valid when fd is enabled"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
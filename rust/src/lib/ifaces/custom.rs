@@ -0,0 +1,111 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+
+use crate::{
+    BaseInterface, ErrorKind, InterfaceType, NmstateError,
+    NmstateInterfacePlugin, UnknownInterface,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+/// Interface of type handled by [NmstateInterfacePlugin] registered via
+/// [crate::NetworkState::register_iface_plugin()].
+/// The type specific configuration is stored as JSON value under the
+/// [NmstateInterfacePlugin::config_section()] property, all other
+/// properties are [BaseInterface] properties.
+/// The example yaml output of [crate::NetworkState] with interface handled
+/// by plugin of type `foo` would be:
+/// ```yml
+/// interfaces:
+/// - name: foo0
+///   type: foo
+///   state: up
+///   foo:
+///     mode: fast
+/// ```
+pub struct CustomInterface {
+    pub base: BaseInterface,
+    /// Type specific configuration, serialize to the property named by
+    /// [NmstateInterfacePlugin::config_section()].
+    pub config: Option<serde_json::Value>,
+    pub(crate) config_section: String,
+}
+
+impl CustomInterface {
+    /// Create empty interface of type handled by specified plugin.
+    pub fn new(plugin: &dyn NmstateInterfacePlugin) -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Other(
+                    plugin.iface_type().to_string(),
+                ),
+                ..Default::default()
+            },
+            config: None,
+            config_section: plugin.config_section().to_string(),
+        }
+    }
+
+    /// The property name holding type specific configuration.
+    pub fn config_section(&self) -> &str {
+        self.config_section.as_str()
+    }
+
+    // Convert the interface deserialized without plugin registered.
+    pub(crate) fn from_unknown(
+        iface: &UnknownInterface,
+        iface_type: &str,
+        config_section: &str,
+    ) -> Result<Self, NmstateError> {
+        let mut ret = Self {
+            config_section: config_section.to_string(),
+            ..Default::default()
+        };
+        let mut v = match iface.other.as_object() {
+            Some(v) => v.clone(),
+            None => serde_json::Map::new(),
+        };
+        v.remove("type");
+        ret.config = v.remove(config_section);
+        v.insert(
+            "name".to_string(),
+            serde_json::Value::String(iface.base.name.clone()),
+        );
+        v.insert("state".to_string(), serde_json::to_value(iface.base.state)?);
+        ret.base = BaseInterface::deserialize(serde_json::Value::Object(v))
+            .map_err(|e| {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid {iface_type} interface {}: {e}",
+                        iface.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                e
+            })?;
+        ret.base.iface_type = InterfaceType::Other(iface_type.to_string());
+        Ok(ret)
+    }
+}
+
+impl Serialize for CustomInterface {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let base_value = serde_json::to_value(&self.base)
+            .map_err(serde::ser::Error::custom)?;
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(base_map) = base_value.as_object() {
+            for (k, v) in base_map {
+                map.serialize_entry(k, v)?;
+            }
+        }
+        if let Some(config) = self.config.as_ref() {
+            map.serialize_entry(self.config_section.as_str(), config)?;
+        }
+        map.end()
+    }
+}
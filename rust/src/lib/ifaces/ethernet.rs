@@ -3,8 +3,8 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    BaseInterface, ErrorKind, Interface, InterfaceType, Interfaces,
-    MergedInterfaces, NmstateError, SrIovConfig,
+    BaseInterface, ErrorKind, Interface, InterfaceState, InterfaceType,
+    Interfaces, MergedInterfaces, NmstateError, SrIovConfig,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -92,7 +92,7 @@ impl EthernetInterface {
         if let Some(sriov_conf) =
             self.ethernet.as_mut().and_then(|e| e.sr_iov.as_mut())
         {
-            sriov_conf.sanitize();
+            sriov_conf.sanitize()?;
         }
 
         Ok(())
@@ -148,7 +148,11 @@ pub struct EthernetConfig {
         deserialize_with = "crate::deserializer::option_u32_or_string"
     )]
     pub speed: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     pub duplex: Option<EthernetDuplex>,
 }
 
@@ -160,6 +164,23 @@ impl EthernetConfig {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[non_exhaustive]
+/// The veth peer could also be described in the same desired state with its
+/// own configurations (e.g. IP, MTU, controller), nmstate will link it back
+/// to this veth automatically, hence both ends could be created in single
+/// apply. The example yaml would be:
+/// ```yml
+/// interfaces:
+/// - name: veth1
+///   type: veth
+///   state: up
+///   veth:
+///     peer: veth1peer
+/// - name: veth1peer
+///   type: veth
+///   state: up
+///   mtu: 1400
+///   controller: br0
+/// ```
 pub struct VethConfig {
     /// The name of veth peer.
     pub peer: String,
@@ -254,6 +275,75 @@ impl MergedInterfaces {
 }
 
 impl Interfaces {
+    // When veth peer is also described in desired state, complete its veth
+    // configuration pointing back to this veth, so both ends could be
+    // configured in single desired state.
+    pub(crate) fn link_veth_peers(&mut self) -> Result<(), NmstateError> {
+        let veth_pairs: Vec<(String, String)> = self
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.is_up())
+            .filter_map(|i| {
+                if let Interface::Ethernet(eth_iface) = i {
+                    eth_iface.veth.as_ref().map(|v| {
+                        (eth_iface.base.name.to_string(), v.peer.to_string())
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        for (iface_name, peer_name) in veth_pairs {
+            let peer_iface = match self.kernel_ifaces.get_mut(&peer_name) {
+                Some(Interface::Ethernet(i))
+                    if i.base.state == InterfaceState::Up =>
+                {
+                    i
+                }
+                Some(i) if i.is_up() => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Veth interface {iface_name} is using {peer_name} \
+                            as peer, but {peer_name} is defined as {} \
+                            interface",
+                            i.iface_type()
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                _ => continue,
+            };
+            match peer_iface.veth.as_ref() {
+                Some(veth_conf) if veth_conf.peer != iface_name => {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Veth interface {iface_name} is using {peer_name} \
+                            as peer, but {peer_name} is using {} as peer",
+                            veth_conf.peer
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                Some(_) => (),
+                None => {
+                    log::debug!(
+                        "Setting veth peer of {peer_name} to {iface_name}"
+                    );
+                    peer_iface.base.iface_type = InterfaceType::Veth;
+                    peer_iface.veth = Some(VethConfig {
+                        peer: iface_name.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
     // Not allowing changing veth peer away from ignored peer unless previous
     // peer changed from ignore to managed
     pub(crate) fn validate_change_veth_ignored_peer(
@@ -31,6 +31,18 @@ const ETHTOOL_FEATURE_CLI_ALIAS: [(&str, &str); 17] = [
     ("receive-hashing", "rx-hashing"),
 ];
 
+// Features enabled or disabled by kernel regardless of network driver.
+const ETHTOOL_FEATURE_KERNEL_DEFAULTS: [(&str, bool); 8] = [
+    ("tx-generic-segmentation", true),
+    ("rx-gro", true),
+    ("rx-gro-list", false),
+    ("rx-udp-gro-forwarding", false),
+    ("tx-nocache-copy", false),
+    ("loopback", false),
+    ("rx-fcs", false),
+    ("rx-all", false),
+];
+
 #[derive(Deserialize, Debug, Eq, PartialEq, Clone, Default)]
 #[serde(from = "HashMap<String, bool>")]
 #[non_exhaustive]
@@ -141,6 +153,30 @@ impl EthtoolConfig {
         Self::default()
     }
 
+    // Remove features holding kernel default state, the ring maximum values
+    // are read-only.
+    pub(crate) fn trim_defaults(&mut self) {
+        if let Some(features) = self.feature.as_mut() {
+            for (name, value) in ETHTOOL_FEATURE_KERNEL_DEFAULTS {
+                if features.get(name) == Some(&value) {
+                    features.remove(name);
+                }
+            }
+            if features == &EthtoolFeatureConfig::default() {
+                self.feature = None;
+            }
+        }
+        if let Some(ring) = self.ring.as_mut() {
+            ring.rx_max = None;
+            ring.rx_jumbo_max = None;
+            ring.rx_mini_max = None;
+            ring.tx_max = None;
+            if ring == &EthtoolRingConfig::default() {
+                self.ring = None;
+            }
+        }
+    }
+
     // There are some alias on ethtool features.
     pub(crate) fn apply_feature_alias(&mut self) {
         if let Some(features) = self.feature.as_mut() {
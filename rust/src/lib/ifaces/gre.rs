@@ -0,0 +1,197 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IPv4 GRE tunnel interface. The example yaml output of
+/// [crate::NetworkState] with a GRE interface would be:
+/// ```yml
+/// interfaces:
+/// - name: gre1
+///   type: gre
+///   state: up
+///   mtu: 1476
+///   gre:
+///     base-iface: eth1
+///     local: 192.0.2.1
+///     remote: 192.0.2.2
+///     ikey: 100
+///     okey: 100
+///     ttl: 64
+/// ```
+pub struct GreInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gre: Option<GreConfig>,
+}
+
+impl Default for GreInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Gre,
+                ..Default::default()
+            },
+            gre: None,
+        }
+    }
+}
+
+impl GreInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.gre.as_ref() {
+                conf.validate_ipv4(self.base.name.as_str())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.gre.as_ref().and_then(|cfg| cfg.parent())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IPv4 GRE tunnel interface carrying ethernet frames.
+/// The example yaml output of [crate::NetworkState] with a GRETAP interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: gretap1
+///   type: gretap
+///   state: up
+///   mac-address: 0E:00:95:53:19:55
+///   gretap:
+///     local: 192.0.2.1
+///     remote: 192.0.2.2
+/// ```
+pub struct GretapInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gretap: Option<GreConfig>,
+}
+
+impl Default for GretapInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Gretap,
+                ..Default::default()
+            },
+            gretap: None,
+        }
+    }
+}
+
+impl GretapInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.gretap.as_ref() {
+                conf.validate_ipv4(self.base.name.as_str())?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.gretap.as_ref().and_then(|cfg| cfg.parent())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct GreConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Interface used for sending tunnel packets.
+    pub base_iface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Local endpoint address of the tunnel.
+    pub local: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote endpoint address of the tunnel.
+    pub remote: Option<IpAddr>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Key for incoming packets.
+    pub ikey: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Key for outgoing packets.
+    pub okey: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Time to live of tunnel packets. Use 0 to inherit from the
+    /// encapsulated packet.
+    pub ttl: Option<u8>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Type of service of tunnel packets. Use 1 to inherit from the
+    /// encapsulated packet.
+    pub tos: Option<u8>,
+}
+
+impl GreConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.base_iface.as_deref().filter(|p| !p.is_empty())
+    }
+
+    fn validate_ipv4(&self, iface_name: &str) -> Result<(), NmstateError> {
+        for addr in [self.local.as_ref(), self.remote.as_ref()]
+            .into_iter()
+            .flatten()
+        {
+            if !addr.is_ipv4() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Interface {iface_name} is IPv4 GRE tunnel, but \
+                        IPv6 endpoint address {addr} defined"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, InterfaceType};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel Intermediate Functional Block interface, commonly used as
+/// target of traffic redirected from ingress qdisc for ingress shaping.
+/// Only contain information of [BaseInterface].
+/// Neither NetworkManager nor nispor could manage IFB devices, hence nmstate
+/// configures them directly in kernel via netlink which means the
+/// configuration is not persistent.
+/// Example yaml output of [crate::NetworkState] with IFB interface:
+/// ```yml
+/// interfaces:
+/// - name: ifb0
+///   type: ifb
+///   state: up
+///   mac-address: 1E:E4:0A:26:9C:0B
+///   mtu: 1500
+/// ```
+pub struct IfbInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+}
+
+impl Default for IfbInterface {
+    fn default() -> Self {
+        let mut base = BaseInterface::new();
+        base.iface_type = InterfaceType::Ifb;
+        Self { base }
+    }
+}
+
+impl IfbInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
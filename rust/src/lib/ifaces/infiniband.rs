@@ -76,6 +76,7 @@ impl std::fmt::Display for InfiniBandMode {
 #[non_exhaustive]
 pub struct InfiniBandConfig {
     /// Mode of InfiniBand interface.
+    #[serde(deserialize_with = "crate::deserializer::enum_ignore_case")]
     pub mode: InfiniBandMode,
     #[serde(skip_serializing_if = "crate::serializer::is_option_string_empty")]
     /// For pkey sub-interface only. Empty for base interface.
@@ -2,14 +2,18 @@
 
 use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
+use std::net::IpAddr;
 
 use serde::{
     ser::SerializeSeq, Deserialize, Deserializer, Serialize, Serializer,
 };
 
+use crate::ip::is_ipv6_unicast_link_local;
+use crate::warning::nmstate_warn;
 use crate::{
     ErrorKind, EthernetInterface, Interface, InterfaceIdentifier,
-    InterfaceState, InterfaceType, MergedInterface, NmstateError,
+    InterfaceIpAddr, InterfaceState, InterfaceType, MacAddress,
+    MergedInterface, NmstateError,
 };
 
 // The max loop count for Interfaces.set_ifaces_up_priority()
@@ -24,6 +28,57 @@ const COPY_MAC_ALLOWED_IFACE_TYPES: [InterfaceType; 3] = [
     InterfaceType::OvsInterface,
 ];
 
+// Interface types carrying ethernet frames.
+const ETHERNET_LIKE_IFACE_TYPES: [InterfaceType; 15] = [
+    InterfaceType::Ethernet,
+    InterfaceType::Veth,
+    InterfaceType::Bond,
+    InterfaceType::Team,
+    InterfaceType::Batman,
+    InterfaceType::Vlan,
+    InterfaceType::LinuxBridge,
+    InterfaceType::MacVlan,
+    InterfaceType::MacVtap,
+    InterfaceType::MacSec,
+    InterfaceType::Vxlan,
+    InterfaceType::Gretap,
+    InterfaceType::Ip6Gretap,
+    InterfaceType::Dummy,
+    InterfaceType::OvsInterface,
+];
+
+// Controller types holding the receive path of their ports, hence ports of
+// them cannot be used as base interface of MAC VLAN, MAC VTAP, IP VLAN or
+// MACsec.
+const RX_HANDLER_CONTROLLER_TYPES: [InterfaceType; 3] = [
+    InterfaceType::Bond,
+    InterfaceType::LinuxBridge,
+    InterfaceType::OvsBridge,
+];
+
+const IPV4_HOST_PREFIX_LEN: u8 = 32;
+const IPV6_HOST_PREFIX_LEN: u8 = 128;
+
+// Compatibility matrix of child interface type to:
+//  * Supported parent interface types, None means any.
+//  * Whether parent is allowed to be port of bond, linux bridge or OVS
+//    bridge.
+fn get_parent_compatibility(
+    child_type: &InterfaceType,
+) -> Option<(Option<&'static [InterfaceType]>, bool)> {
+    match child_type {
+        InterfaceType::Vlan => Some((Some(&ETHERNET_LIKE_IFACE_TYPES), true)),
+        InterfaceType::Vxlan => Some((None, false)),
+        InterfaceType::MacVlan
+        | InterfaceType::MacVtap
+        | InterfaceType::IpVlan
+        | InterfaceType::MacSec => {
+            Some((Some(&ETHERNET_LIKE_IFACE_TYPES), false))
+        }
+        _ => None,
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 #[non_exhaustive]
 /// Represent a list of [Interface] with special [serde::Deserializer] and
@@ -46,14 +101,51 @@ impl<'de> Deserialize<'de> for Interfaces {
         D: Deserializer<'de>,
     {
         let mut ret = Self::new();
-        for iface in <Vec<Interface> as Deserialize>::deserialize(deserializer)?
+        for (i, v) in
+            <Vec<serde_json::Value> as Deserialize>::deserialize(deserializer)?
+                .into_iter()
+                .enumerate()
         {
-            ret.push(iface);
+            match Interface::deserialize(&v) {
+                Ok(iface) => ret.push(iface),
+                Err(e) => {
+                    return Err(serde::de::Error::custom(
+                        gen_iface_deserialize_error(i, &v, e.to_string()),
+                    ));
+                }
+            }
         }
         Ok(ret)
     }
 }
 
+// Include the path of invalid property and the interface name in error
+// message, e.g.
+//  interfaces[1].ipv4.address[0].prefix-length of interface eth1:
+//  invalid type: string "abc", expected u8
+fn gen_iface_deserialize_error(
+    index: usize,
+    v: &serde_json::Value,
+    err_msg: String,
+) -> String {
+    let mut path = format!("interfaces[{index}]");
+    if let Some(sub_path) =
+        crate::deserializer::locate_invalid_property::<Interface>(
+            v,
+            &crate::unknown_field::Schema::Interface,
+            err_msg.as_str(),
+            &["name", "type"],
+        )
+    {
+        path.push('.');
+        path.push_str(sub_path.as_str());
+    }
+    match v.get("name").and_then(|n| n.as_str()) {
+        Some(name) => format!("{path} of interface {name}: {err_msg}"),
+        None => format!("{path}: {err_msg}"),
+    }
+}
+
 impl Serialize for Interfaces {
     // Serialize is also used for verification.
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -88,7 +180,8 @@ impl Interfaces {
         for iface in self.user_ifaces.values() {
             ifaces.push(iface);
         }
-        ifaces.sort_unstable_by_key(|iface| iface.name());
+        // User space interface might share the same name with kernel one
+        ifaces.sort_unstable_by_key(|iface| (iface.name(), iface.iface_type()));
         // Use sort_by_key() instead of unstable one, do we can alphabet
         // activation order which is required to simulate the OS boot-up.
         ifaces.sort_by_key(|iface| iface.base_iface().up_priority);
@@ -111,7 +204,15 @@ impl Interfaces {
                     .find(|&iface| iface.name() == iface_name)
             })
         } else if iface_type.is_userspace() {
-            self.user_ifaces.get(&(iface_name.to_string(), iface_type))
+            self.user_ifaces
+                .get(&(iface_name.to_string(), iface_type.clone()))
+                .or_else(|| {
+                    // Interfaces handled by plugins are stored as kernel
+                    // interfaces
+                    self.kernel_ifaces
+                        .get(iface_name)
+                        .filter(|i| i.iface_type() == iface_type)
+                })
         } else {
             self.kernel_ifaces.get(&iface_name.to_string())
         }
@@ -139,7 +240,19 @@ impl Interfaces {
                 })
         } else if iface_type.is_userspace() {
             self.user_ifaces
-                .remove(&(iface_name.to_string(), iface_type))
+                .remove(&(iface_name.to_string(), iface_type.clone()))
+                .or_else(|| {
+                    if self
+                        .kernel_ifaces
+                        .get(iface_name)
+                        .map(|i| i.iface_type())
+                        == Some(iface_type)
+                    {
+                        self.kernel_ifaces.remove(iface_name)
+                    } else {
+                        None
+                    }
+                })
         } else {
             self.kernel_ifaces.remove(&iface_name.to_string())
         }
@@ -157,21 +270,60 @@ impl Interfaces {
         }
     }
 
+    // Append absent interfaces for child interfaces in current state whose
+    // parent is marked as absent in desired state, recursively.
+    pub(crate) fn mark_children_of_absent_as_absent(&mut self, current: &Self) {
+        let mut absent_names: Vec<String> = self
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.is_absent())
+            .map(|i| i.name().to_string())
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for cur_iface in current.kernel_ifaces.values() {
+                // OVS internal interface is using its controller as parent
+                if cur_iface.iface_type() == InterfaceType::OvsInterface
+                    || self.kernel_ifaces.contains_key(cur_iface.name())
+                {
+                    continue;
+                }
+                if let Some(parent) = cur_iface.parent() {
+                    if absent_names.iter().any(|n| n == parent) {
+                        log::info!(
+                            "Marking interface {} as absent as its parent \
+                            {parent} is so",
+                            cur_iface.name()
+                        );
+                        let mut iface = cur_iface.clone_name_type_only();
+                        iface.base_iface_mut().state = InterfaceState::Absent;
+                        absent_names.push(iface.name().to_string());
+                        self.push(iface);
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    pub(crate) fn trim_defaults(&mut self) {
+        for iface in self
+            .kernel_ifaces
+            .values_mut()
+            .chain(self.user_ifaces.values_mut())
+        {
+            iface.base_iface_mut().trim_defaults();
+        }
+    }
+
     pub(crate) fn hide_secrets(&mut self) {
         for iface in self
             .kernel_ifaces
             .values_mut()
             .chain(self.user_ifaces.values_mut())
         {
-            iface.base_iface_mut().hide_secrets();
-            if let Interface::MacSec(macsec_iface) = iface {
-                if let Some(macsec_conf) = macsec_iface.macsec.as_mut() {
-                    macsec_conf.hide_secrets();
-                }
-            }
-            if let Interface::Ipsec(ipsec_iface) = iface {
-                ipsec_iface.hide_secrets();
-            }
+            iface.hide_secrets();
         }
     }
 
@@ -229,6 +381,46 @@ impl Interfaces {
         }
     }
 
+    // Remove interfaces marked as `state: present` and return their names and
+    // types. In gen_conf mode, current state is empty, hence the existence
+    // check is skipped.
+    pub(crate) fn remove_present_ifaces(
+        &mut self,
+        current: &Self,
+        gen_conf_mode: bool,
+    ) -> Result<Vec<(String, InterfaceType)>, NmstateError> {
+        let present_ifaces: Vec<(String, InterfaceType)> = self
+            .kernel_ifaces
+            .values()
+            .chain(self.user_ifaces.values())
+            .filter(|i| i.is_present())
+            .map(|i| (i.name().to_string(), i.iface_type()))
+            .collect();
+        let mut ret = Vec::new();
+        for (iface_name, iface_type) in present_ifaces {
+            let cur_iface = current.get_iface(&iface_name, iface_type.clone());
+            if !gen_conf_mode && cur_iface.is_none() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Interface {iface_name} is marked as present but \
+                        does not exist in current state"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            self.remove_iface(&iface_name, iface_type.clone());
+            self.insert_order.retain(|(n, _)| n != &iface_name);
+            log::info!("Keeping interface {iface_name} as it is");
+            ret.push((
+                iface_name,
+                cur_iface.map(|i| i.iface_type()).unwrap_or(iface_type),
+            ));
+        }
+        Ok(ret)
+    }
+
     pub(crate) fn remove_ignored_ifaces(
         &mut self,
         ignored_ifaces: &[(String, InterfaceType)],
@@ -370,8 +562,8 @@ impl Interfaces {
             i.base_iface().identifier == InterfaceIdentifier::MacAddress
                 && i.base_iface().profile_name.is_none()
         }) {
-            let mac_address = match iface.base_iface().mac_address.as_deref() {
-                Some(m) => m.to_ascii_uppercase(),
+            let mac_address = match iface.base_iface().mac_address.as_ref() {
+                Some(m) => m.as_str().to_ascii_uppercase(),
                 None => {
                     return Err(NmstateError::new(
                         ErrorKind::InvalidArgument,
@@ -386,8 +578,12 @@ impl Interfaces {
             };
             let mut has_match = false;
             for cur_iface in current.kernel_ifaces.values() {
-                if cur_iface.base_iface().mac_address.as_deref()
-                    == Some(&mac_address)
+                if cur_iface
+                    .base_iface()
+                    .mac_address
+                    .as_ref()
+                    .map(|m| m.as_str())
+                    == Some(mac_address.as_str())
                 {
                     let mut new_iface = if iface.iface_type()
                         == InterfaceType::Unknown
@@ -498,8 +694,17 @@ impl Interfaces {
         iface_type: InterfaceType,
     ) -> Option<&'a mut Interface> {
         if iface_type.is_userspace() {
-            self.user_ifaces
-                .get_mut(&(iface_name.to_string(), iface_type))
+            if self
+                .user_ifaces
+                .contains_key(&(iface_name.to_string(), iface_type.clone()))
+            {
+                self.user_ifaces
+                    .get_mut(&(iface_name.to_string(), iface_type))
+            } else {
+                self.kernel_ifaces
+                    .get_mut(iface_name)
+                    .filter(|i| i.iface_type() == iface_type)
+            }
         } else {
             self.kernel_ifaces.get_mut(&iface_name.to_string())
         }
@@ -521,7 +726,7 @@ impl Interfaces {
         for iface_name in iface_names_to_add {
             let mut iface = EthernetInterface::default();
             iface.base.name = iface_name.clone();
-            log::warn!("Assuming undefined port {} as ethernet", iface_name);
+            nmstate_warn!("Assuming undefined port {} as ethernet", iface_name);
             self.kernel_ifaces
                 .insert(iface_name, Interface::Ethernet(iface));
         }
@@ -533,7 +738,7 @@ impl Interfaces {
         let mut new_ifaces = Vec::new();
         for iface in self.kernel_ifaces.values_mut() {
             if let Interface::Unknown(iface) = iface {
-                log::warn!(
+                nmstate_warn!(
                     "Setting unknown type interface {} to ethernet",
                     iface.base.name.as_str()
                 );
@@ -582,14 +787,6 @@ impl Interfaces {
     }
 }
 
-fn is_opt_str_empty(opt_string: &Option<String>) -> bool {
-    if let Some(s) = opt_string {
-        s.is_empty()
-    } else {
-        true
-    }
-}
-
 // When merging desire interface with current, we perform actions in the order
 // of:
 //  * Action might alter the results of follow-up actions:
@@ -609,6 +806,8 @@ pub(crate) struct MergedInterfaces {
     pub(crate) user_ifaces: HashMap<(String, InterfaceType), MergedInterface>,
     pub(crate) insert_order: Vec<(String, InterfaceType)>,
     pub(crate) ignored_ifaces: Vec<(String, InterfaceType)>,
+    // Interfaces marked as `state: present` in desired state
+    pub(crate) present_ifaces: Vec<(String, InterfaceType)>,
     pub(crate) memory_only: bool,
     pub(crate) gen_conf_mode: bool,
 }
@@ -638,6 +837,9 @@ impl MergedInterfaces {
             iface.post_deserialize_cleanup();
         }
 
+        let present_ifaces =
+            desired.remove_present_ifaces(&current, gen_conf_mode)?;
+
         if gen_conf_mode {
             desired.set_unknown_iface_to_eth()?;
             desired.set_missing_port_to_eth();
@@ -648,6 +850,8 @@ impl MergedInterfaces {
             desired.resolve_mac_identifider_in_desired(&current)?;
         }
 
+        desired.link_veth_peers()?;
+
         desired.auto_managed_controller_ports(&current);
 
         let ignored_ifaces = get_ignored_ifaces(&desired, &current);
@@ -720,6 +924,7 @@ impl MergedInterfaces {
             user_ifaces: merged_user_ifaces,
             insert_order: desired.insert_order,
             ignored_ifaces,
+            present_ifaces,
             memory_only,
             gen_conf_mode,
         };
@@ -741,7 +946,13 @@ impl MergedInterfaces {
                     .find(|&iface| iface.merged.name() == iface_name)
             })
         } else if iface_type.is_userspace() {
-            self.user_ifaces.get(&(iface_name.to_string(), iface_type))
+            self.user_ifaces
+                .get(&(iface_name.to_string(), iface_type.clone()))
+                .or_else(|| {
+                    self.kernel_ifaces
+                        .get(iface_name)
+                        .filter(|i| i.merged.iface_type() == iface_type)
+                })
         } else {
             self.kernel_ifaces.get(&iface_name.to_string())
         }
@@ -751,6 +962,25 @@ impl MergedInterfaces {
         self.user_ifaces.values().chain(self.kernel_ifaces.values())
     }
 
+    // Index of port in the port list of its controller which has
+    // `ports-ordered: true`.
+    pub(crate) fn get_ordered_ports_index(&self) -> HashMap<&str, usize> {
+        let mut ret: HashMap<&str, usize> = HashMap::new();
+        for merged_iface in self.iter().filter(|i| {
+            i.for_apply
+                .as_ref()
+                .and_then(|i| i.base_iface().ports_ordered)
+                == Some(true)
+        }) {
+            if let Some(ports) = merged_iface.merged.ports() {
+                for (index, port_name) in ports.into_iter().enumerate() {
+                    ret.insert(port_name, index);
+                }
+            }
+        }
+        ret
+    }
+
     pub(crate) fn iter_mut(
         &mut self,
     ) -> impl Iterator<Item = &mut MergedInterface> {
@@ -759,19 +989,252 @@ impl MergedInterfaces {
             .chain(self.kernel_ifaces.values_mut())
     }
 
+    // Validate parent of VLAN, VxLAN, MAC VLAN, MAC VTAP, IP VLAN and MACsec
+    // interfaces against the compatibility matrix, so user get actionable
+    // error instead of kernel failure during activation.
+    fn validate_parent_types(&self) -> Result<(), NmstateError> {
+        for iface in self.kernel_ifaces.values().filter(|i| i.merged.is_up()) {
+            let (allowed_types, allow_port) =
+                match get_parent_compatibility(&iface.merged.iface_type()) {
+                    Some(c) => c,
+                    None => continue,
+                };
+            let parent_name = match iface.merged.parent() {
+                Some(p) if !p.is_empty() => p,
+                _ => continue,
+            };
+            let parent = match self.kernel_ifaces.get(parent_name) {
+                Some(p) if p.merged.is_up() => p,
+                _ => continue,
+            };
+            if !iface.is_desired() && !parent.is_desired() {
+                continue;
+            }
+            let child_type = iface.merged.iface_type();
+            let parent_type = parent.merged.iface_type();
+            // Interface types unknown to nmstate might carry ethernet frames
+            if matches!(
+                parent_type,
+                InterfaceType::Unknown | InterfaceType::Other(_)
+            ) {
+                continue;
+            }
+            if let Some(allowed_types) = allowed_types {
+                if !allowed_types.contains(&parent_type) {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The {child_type} interface {} cannot use \
+                            {parent_type} interface {parent_name} as base \
+                            interface, supported base interface types are: \
+                            {}",
+                            iface.merged.name(),
+                            allowed_types
+                                .iter()
+                                .map(|t| t.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+            if !allow_port && parent_type != InterfaceType::OvsInterface {
+                if let Some(ctrl_type) = self.get_controller_type(parent) {
+                    if RX_HANDLER_CONTROLLER_TYPES.contains(&ctrl_type) {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "The {child_type} interface {} cannot use \
+                                {parent_name} as base interface because it \
+                                is port of {ctrl_type} {}, please use the \
+                                {ctrl_type} as base interface instead",
+                                iface.merged.name(),
+                                parent
+                                    .merged
+                                    .base_iface()
+                                    .controller
+                                    .as_deref()
+                                    .unwrap_or_default()
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Return static IP addresses of up kernel interfaces along with the VRF
+    // they belong to, empty string for the main routing domain.
+    // IPv6 link local addresses are excluded.
+    fn get_static_ip_addrs(
+        &self,
+    ) -> Vec<(&MergedInterface, &str, &InterfaceIpAddr)> {
+        let mut ret = Vec::new();
+        for iface in self.kernel_ifaces.values().filter(|i| i.merged.is_up()) {
+            let base_iface = iface.merged.base_iface();
+            let vrf = if self.get_controller_type(iface)
+                == Some(InterfaceType::Vrf)
+            {
+                base_iface.controller.as_deref().unwrap_or_default()
+            } else {
+                ""
+            };
+            let ipv4_addrs = base_iface
+                .ipv4
+                .as_ref()
+                .filter(|i| i.enabled)
+                .and_then(|i| i.addresses.as_deref())
+                .unwrap_or_default();
+            let ipv6_addrs = base_iface
+                .ipv6
+                .as_ref()
+                .filter(|i| i.enabled)
+                .and_then(|i| i.addresses.as_deref())
+                .unwrap_or_default();
+            for addr in ipv4_addrs
+                .iter()
+                .chain(ipv6_addrs.iter())
+                .filter(|a| !a.is_auto())
+            {
+                if let IpAddr::V6(ip) = addr.ip {
+                    if is_ipv6_unicast_link_local(&ip) {
+                        continue;
+                    }
+                }
+                ret.push((iface, vrf, addr));
+            }
+        }
+        ret.sort_unstable_by_key(|(iface, _, _)| iface.merged.name());
+        ret
+    }
+
+    // Warn about the same static IP address assigned to multiple interfaces
+    // within the same VRF. Anycast setups do this on purpose, hence not
+    // treated as error.
+    fn warn_duplicate_static_ips(&self) {
+        let mut ip_to_ifaces: HashMap<(IpAddr, &str), Vec<&str>> =
+            HashMap::new();
+        let mut changed_ips: HashSet<(IpAddr, &str)> = HashSet::new();
+        for (iface, vrf, addr) in self.get_static_ip_addrs() {
+            ip_to_ifaces
+                .entry((addr.ip, vrf))
+                .or_default()
+                .push(iface.merged.name());
+            if iface.is_desired() {
+                changed_ips.insert((addr.ip, vrf));
+            }
+        }
+        for key in changed_ips {
+            if let Some(iface_names) = ip_to_ifaces.get(&key) {
+                if iface_names.len() > 1 {
+                    nmstate_warn!(
+                        "Static IP address {} is assigned to multiple \
+                        interfaces: {}",
+                        key.0,
+                        iface_names.join(", ")
+                    );
+                }
+            }
+        }
+    }
+
+    // Interfaces holding overlapping subnets within the same VRF is a
+    // frequent source of asymmetric routing. Warn about it, or raise error
+    // when `strict` is true.
+    // Host addresses(/32 and /128) are ignored as they are commonly used for
+    // anycast or loopback service IP within routed subnet. Identical IP
+    // addresses are handled by `warn_duplicate_static_ips()`.
+    pub(crate) fn check_overlapping_subnets(
+        &self,
+        strict: bool,
+    ) -> Result<(), NmstateError> {
+        let addrs: Vec<(&MergedInterface, &str, &InterfaceIpAddr)> = self
+            .get_static_ip_addrs()
+            .into_iter()
+            .filter(|(_, _, addr)| {
+                addr.prefix_length
+                    < if addr.ip.is_ipv4() {
+                        IPV4_HOST_PREFIX_LEN
+                    } else {
+                        IPV6_HOST_PREFIX_LEN
+                    }
+            })
+            .collect();
+        for (i, (iface, vrf, addr)) in addrs.iter().enumerate() {
+            for (other_iface, other_vrf, other_addr) in &addrs[i + 1..] {
+                if vrf != other_vrf
+                    || iface.merged.name() == other_iface.merged.name()
+                    || addr.ip == other_addr.ip
+                    || !(iface.is_desired() || other_iface.is_desired())
+                    || !addr.is_subnet_overlap(other_addr)
+                {
+                    continue;
+                }
+                let msg = format!(
+                    "IP address {addr} of interface {} is in subnet \
+                    overlapping with IP address {other_addr} of interface \
+                    {}, this might cause asymmetric routing",
+                    iface.merged.name(),
+                    other_iface.merged.name(),
+                );
+                if strict {
+                    let e = NmstateError::new(ErrorKind::InvalidArgument, msg);
+                    log::error!("{}", e);
+                    return Err(e);
+                } else {
+                    nmstate_warn!("{}", msg);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_controller_type(
+        &self,
+        iface: &MergedInterface,
+    ) -> Option<InterfaceType> {
+        let ctrl_name = iface
+            .merged
+            .base_iface()
+            .controller
+            .as_deref()
+            .filter(|c| !c.is_empty())?;
+        if let Some(ctrl_type) =
+            iface.merged.base_iface().controller_type.as_ref()
+        {
+            return Some(ctrl_type.clone());
+        }
+        self.user_ifaces
+            .get(&(ctrl_name.to_string(), InterfaceType::OvsBridge))
+            .or_else(|| self.kernel_ifaces.get(ctrl_name))
+            .map(|i| i.merged.iface_type())
+    }
+
     // Contains all the smart modifications, validations among interfaces
     fn process(&mut self) -> Result<(), NmstateError> {
         self.process_allow_extra_ovs_patch_ports_for_apply();
         self.apply_copy_mac_from()?;
+        self.validate_port_controller_exist()?;
         self.validate_controller_and_port_list_confliction()?;
         self.handle_changed_ports()?;
         self.resolve_port_iface_controller_type()?;
+        self.check_controller_port_cycle()?;
         self._set_up_priority()?;
         self.check_overbook_ports()?;
         self.check_infiniband_as_ports()?;
+        self.validate_parent_types()?;
+        self.validate_macsec_mtu()?;
+        self.validate_bond_sriov_vf_ports()?;
+        self.warn_duplicate_static_ips();
         self.mark_orphan_interface_as_absent()?;
         self.process_veth_peer_changes()?;
         self.validate_dispatch_script_has_no_checkpoint()?;
+        self.validate_linux_bridge_no_linklocal_learn()?;
         for iface in self
             .kernel_ifaces
             .values_mut()
@@ -804,7 +1267,7 @@ impl MergedInterfaces {
     }
 
     fn apply_copy_mac_from(&mut self) -> Result<(), NmstateError> {
-        let mut pending_changes: HashMap<String, String> = HashMap::new();
+        let mut pending_changes: HashMap<String, MacAddress> = HashMap::new();
         for (iface_name, merged_iface) in self.kernel_ifaces.iter() {
             if !merged_iface.is_desired()
                 || !COPY_MAC_ALLOWED_IFACE_TYPES
@@ -812,36 +1275,36 @@ impl MergedInterfaces {
             {
                 continue;
             }
+            if let Interface::Bond(bond_iface) = &merged_iface.merged {
+                bond_iface.validate_copy_mac_from()?;
+            }
             if let Some(src_iface_name) =
                 &merged_iface.merged.base_iface().copy_mac_from
             {
                 if let Some(src_iface) =
                     self.kernel_ifaces.get(src_iface_name).map(|i| &i.merged)
                 {
-                    if !is_opt_str_empty(
-                        &src_iface.base_iface().permanent_mac_address,
-                    ) {
-                        if let Some(mac) = src_iface
-                            .base_iface()
-                            .permanent_mac_address
-                            .as_ref()
-                        {
-                            pending_changes.insert(
-                                iface_name.to_string(),
-                                mac.to_string(),
-                            );
-                        }
-                    } else if !is_opt_str_empty(
-                        &src_iface.base_iface().mac_address,
-                    ) {
-                        if let Some(mac) =
-                            src_iface.base_iface().mac_address.as_ref()
+                    if let Some(mac) =
+                        src_iface.base_iface().permanent_mac_address.as_ref()
+                    {
+                        pending_changes
+                            .insert(iface_name.to_string(), mac.clone());
+                    } else if let Some(mac) =
+                        src_iface.base_iface().mac_address.as_ref()
+                    {
+                        // Bond port is using bond MAC address, hence its
+                        // current MAC might change after port swaps.
+                        if merged_iface.merged.iface_type()
+                            == InterfaceType::Bond
                         {
-                            pending_changes.insert(
-                                iface_name.to_string(),
-                                mac.to_string(),
+                            nmstate_warn!(
+                                "Interface {src_iface_name} has no permanent \
+                                MAC address, bond {iface_name} will copy its \
+                                current MAC address instead"
                             );
                         }
+                        pending_changes
+                            .insert(iface_name.to_string(), mac.clone());
                     } else {
                         let e = NmstateError::new(
                             ErrorKind::InvalidArgument,
@@ -893,6 +1356,13 @@ impl MergedInterfaces {
             .map(|i| i.merged.name().to_string())
             .collect();
 
+        let present_ifaces: HashSet<String> = self
+            .present_ifaces
+            .iter()
+            .filter(|(_, t)| !t.is_userspace())
+            .map(|(n, _)| n.to_string())
+            .collect();
+
         // OvsInterface is already checked by `apply_ctrller_change()`.
         for iface in self.kernel_ifaces.values_mut().filter(|i| {
             i.merged.is_up()
@@ -900,6 +1370,19 @@ impl MergedInterfaces {
         }) {
             if let Some(parent) = iface.merged.parent() {
                 if gone_ifaces.contains(&parent.to_string()) {
+                    if present_ifaces.contains(iface.merged.name()) {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "Interface {} cannot be kept as present \
+                                as its parent {parent} has been marked \
+                                as absent",
+                                iface.merged.name(),
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
                     if iface.is_desired() && iface.merged.is_up() {
                         return Err(NmstateError::new(
                             ErrorKind::InvalidArgument,
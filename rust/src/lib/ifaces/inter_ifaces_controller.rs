@@ -42,6 +42,49 @@ impl MergedInterfaces {
         Ok(())
     }
 
+    // Check whether the `controller` property of desired port references
+    // an interface exists in desired or current state and not marked as
+    // absent, e.g. typo in bridge name.
+    pub(crate) fn validate_port_controller_exist(
+        &self,
+    ) -> Result<(), NmstateError> {
+        for iface in self
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.is_desired() && i.merged.is_up())
+        {
+            let ctrl_name = match iface
+                .desired
+                .as_ref()
+                .and_then(|i| i.base_iface().controller.as_deref())
+            {
+                Some(c) if !c.is_empty() => c,
+                _ => continue,
+            };
+            let err_msg = match self
+                .user_ifaces
+                .get(&(ctrl_name.to_string(), InterfaceType::OvsBridge))
+                .or_else(|| self.kernel_ifaces.get(ctrl_name))
+            {
+                Some(ctrl_iface) if ctrl_iface.merged.is_absent() => format!(
+                    "Interface {} references controller {ctrl_name} which \
+                    is marked as absent",
+                    iface.merged.name()
+                ),
+                Some(_) => continue,
+                None => format!(
+                    "Interface {} references controller {ctrl_name} which \
+                    does not exist in desired or current state",
+                    iface.merged.name()
+                ),
+            };
+            let e = NmstateError::new(ErrorKind::InvalidArgument, err_msg);
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
     fn validate_controller_not_in_port_list(&self) -> Result<(), NmstateError> {
         for merged_iface in self.kernel_ifaces.values() {
             if merged_iface.desired.is_none() || !merged_iface.merged.is_up() {
@@ -321,6 +364,113 @@ impl MergedInterfaces {
         Ok(())
     }
 
+    // Check whether the interface stacking graph contains a cycle, e.g.
+    // bridge br0 holding port vlan10 which is using br0 as base interface.
+    // Both controller to port and child to parent (e.g. VLAN over its base
+    // interface) are considered as stacking.
+    pub(crate) fn check_controller_port_cycle(
+        &self,
+    ) -> Result<(), NmstateError> {
+        // HashMap of interface name to its lower interface names
+        let mut lowers: HashMap<&str, Vec<&str>> = HashMap::new();
+        for iface in self
+            .kernel_ifaces
+            .values()
+            .map(|i| &i.merged)
+            .filter(|i| i.is_up())
+        {
+            let mut iface_lowers = iface.ports().unwrap_or_default();
+            // OVS internal interface is using its controller as parent
+            if iface.iface_type() != InterfaceType::OvsInterface {
+                if let Some(parent) = iface.parent() {
+                    iface_lowers.push(parent);
+                }
+            }
+            iface_lowers.retain(|l| self.kernel_ifaces.contains_key(*l));
+            if !iface_lowers.is_empty() {
+                lowers.insert(iface.name(), iface_lowers);
+            }
+        }
+
+        let mut iface_names: Vec<&str> = lowers.keys().copied().collect();
+        iface_names.sort_unstable();
+        let mut checked: HashSet<&str> = HashSet::new();
+        for iface_name in iface_names {
+            let mut path: Vec<&str> = Vec::new();
+            if let Some(cycle) =
+                find_cycle(iface_name, &lowers, &mut path, &mut checked)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Found cycle in interface controller and port \
+                        relationship: {}",
+                        cycle.join(" -> ")
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    // Return changed absent interfaces in safe teardown order: interfaces
+    // stacked on top (e.g. VLAN over bond) are placed before their lower
+    // interfaces (e.g. the bond and its ports).
+    pub(crate) fn get_absent_ifaces_in_teardown_order(
+        &self,
+    ) -> Vec<&MergedInterface> {
+        let mut depths: HashMap<&str, usize> = HashMap::new();
+        let mut ifaces: Vec<(usize, &MergedInterface)> = self
+            .iter()
+            .filter(|i| i.is_changed() && i.merged.is_absent())
+            .map(|i| (self.get_stack_depth(i, &mut depths), i))
+            .collect();
+        ifaces.sort_unstable_by(|(a_depth, a), (b_depth, b)| {
+            b_depth.cmp(a_depth).then_with(|| {
+                (a.merged.name(), a.merged.iface_type())
+                    .cmp(&(b.merged.name(), b.merged.iface_type()))
+            })
+        });
+        ifaces.into_iter().map(|(_, i)| i).collect()
+    }
+
+    // The stack depth of interface without lower interface is 0, otherwise
+    // is the maximum depth of its ports and parent plus 1.
+    fn get_stack_depth<'a>(
+        &'a self,
+        iface: &'a MergedInterface,
+        depths: &mut HashMap<&'a str, usize>,
+    ) -> usize {
+        let is_kernel_iface = !iface.merged.is_userspace();
+        if is_kernel_iface {
+            if let Some(depth) = depths.get(iface.merged.name()) {
+                return *depth;
+            }
+            // Guard against loop in current state
+            depths.insert(iface.merged.name(), 0);
+        }
+        let stack_iface = iface.current.as_ref().unwrap_or(&iface.merged);
+        let mut lowers = stack_iface.ports().unwrap_or_default();
+        // OVS internal interface is using its controller as parent
+        if stack_iface.iface_type() != InterfaceType::OvsInterface {
+            if let Some(parent) = stack_iface.parent() {
+                lowers.push(parent);
+            }
+        }
+        let depth = lowers
+            .iter()
+            .filter_map(|l| self.kernel_ifaces.get(*l))
+            .map(|l| self.get_stack_depth(l, depths) + 1)
+            .max()
+            .unwrap_or_default();
+        if is_kernel_iface {
+            depths.insert(iface.merged.name(), depth);
+        }
+        depth
+    }
+
     // Return True if we have all up_priority fixed.
     pub(crate) fn set_ifaces_up_priority(&mut self) -> bool {
         // Return true when all interface has correct priority.
@@ -389,43 +539,60 @@ impl MergedInterfaces {
         }
 
         // If not remaining unknown up_priority, we set up the parent/child
-        // up_priority
+        // up_priority. Repeat till no change, so that nested children
+        // (e.g. MACsec over VLAN over bond) are placed after all their
+        // lower interfaces regardless of the order in desire state.
         if ret {
-            for (iface_name, iface_type) in &self.insert_order {
-                let iface = match self.get_iface(iface_name, iface_type.clone())
-                {
-                    Some(i) => {
-                        if let Some(i) = i.for_apply.as_ref() {
-                            i
-                        } else {
-                            continue;
-                        }
-                    }
-                    None => continue,
-                };
-                if !iface.is_up() {
-                    continue;
-                }
-                if let Some(parent) = iface.parent() {
-                    let parent_priority = pending_changes.get(parent).cloned();
-                    if let Some(parent_priority) = parent_priority {
-                        pending_changes.insert(
-                            iface_name.to_string(),
-                            parent_priority + 1,
-                        );
-                    } else if let Some(parent_iface) = self
-                        .kernel_ifaces
-                        .get(parent)
+            for _ in 0..self.insert_order.len() {
+                let mut changed = false;
+                for (iface_name, iface_type) in &self.insert_order {
+                    let iface = match self
+                        .get_iface(iface_name, iface_type.clone())
                         .and_then(|i| i.for_apply.as_ref())
                     {
-                        if parent_iface.base_iface().is_up_priority_valid() {
-                            pending_changes.insert(
-                                iface_name.to_string(),
-                                parent_iface.base_iface().up_priority + 1,
-                            );
+                        Some(i) => i,
+                        None => continue,
+                    };
+                    if !iface.is_up() {
+                        continue;
+                    }
+                    let parent = match iface.parent() {
+                        Some(p) => p,
+                        None => continue,
+                    };
+                    let mut lowers = vec![parent];
+                    // MACsec need its parent to have carrier, hence should
+                    // be activated after ports of its parent.
+                    if iface.iface_type() == InterfaceType::MacSec {
+                        if let Some(ports) = self
+                            .kernel_ifaces
+                            .get(parent)
+                            .and_then(|i| i.for_apply.as_ref())
+                            .and_then(|i| i.ports())
+                        {
+                            lowers.extend(ports);
+                        }
+                    }
+                    let priority = lowers
+                        .iter()
+                        .filter_map(|l| {
+                            self.get_pending_up_priority(l, &pending_changes)
+                        })
+                        .max()
+                        .map(|p| p + 1);
+                    if let Some(priority) = priority {
+                        if pending_changes.get(iface_name.as_str())
+                            != Some(&priority)
+                        {
+                            pending_changes
+                                .insert(iface_name.to_string(), priority);
+                            changed = true;
                         }
                     }
                 }
+                if !changed {
+                    break;
+                }
             }
         }
 
@@ -443,6 +610,21 @@ impl MergedInterfaces {
         ret
     }
 
+    fn get_pending_up_priority(
+        &self,
+        iface_name: &str,
+        pending_changes: &HashMap<String, u32>,
+    ) -> Option<u32> {
+        if let Some(priority) = pending_changes.get(iface_name) {
+            return Some(*priority);
+        }
+        self.kernel_ifaces
+            .get(iface_name)
+            .and_then(|i| i.for_apply.as_ref())
+            .filter(|i| i.base_iface().is_up_priority_valid())
+            .map(|i| i.base_iface().up_priority)
+    }
+
     pub(crate) fn check_overbook_ports(&self) -> Result<(), NmstateError> {
         let mut port_to_ctrl: HashMap<String, String> = HashMap::new();
         for iface in self.iter().filter(|i| {
@@ -581,3 +763,34 @@ impl Interfaces {
         }
     }
 }
+
+// Depth-first search on interface stacking graph, return the interface names
+// forming the cycle with first interface repeated at the end.
+fn find_cycle<'a>(
+    iface_name: &'a str,
+    lowers: &HashMap<&'a str, Vec<&'a str>>,
+    path: &mut Vec<&'a str>,
+    checked: &mut HashSet<&'a str>,
+) -> Option<Vec<&'a str>> {
+    if let Some(pos) = path.iter().position(|n| *n == iface_name) {
+        let mut cycle = path[pos..].to_vec();
+        cycle.push(iface_name);
+        return Some(cycle);
+    }
+    if checked.contains(iface_name) {
+        return None;
+    }
+    path.push(iface_name);
+    for lower in lowers
+        .get(iface_name)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        if let Some(cycle) = find_cycle(lower, lowers, path, checked) {
+            return Some(cycle);
+        }
+    }
+    path.pop();
+    checked.insert(iface_name);
+    None
+}
@@ -0,0 +1,319 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::net::Ipv6Addr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IPv6 GRE tunnel interface. The example yaml output of
+/// [crate::NetworkState] with an IPv6 GRE interface would be:
+/// ```yml
+/// interfaces:
+/// - name: ip6gre1
+///   type: ip6gre
+///   state: up
+///   mtu: 1448
+///   ip6gre:
+///     base-iface: eth1
+///     local: 2001:db8::1
+///     remote: 2001:db8::2
+///     ikey: 100
+///     okey: 100
+///     encap-limit: 4
+///     flow-label: 0
+///     hop-limit: 64
+/// ```
+pub struct Ip6GreInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip6gre: Option<Ip6TunnelConfig>,
+}
+
+impl Default for Ip6GreInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Ip6Gre,
+                ..Default::default()
+            },
+            ip6gre: None,
+        }
+    }
+}
+
+impl Ip6GreInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.ip6gre.as_ref() {
+                conf.validate(self.base.name.as_str(), false)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.ip6gre.as_ref().and_then(|cfg| cfg.parent())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IPv6 GRE tunnel interface carrying ethernet frames.
+/// The example yaml output of [crate::NetworkState] with an IPv6 GRETAP
+/// interface would be:
+/// ```yml
+/// interfaces:
+/// - name: ip6gretap1
+///   type: ip6gretap
+///   state: up
+///   mac-address: 0E:00:95:53:19:56
+///   ip6gretap:
+///     local: 2001:db8::1
+///     remote: 2001:db8::2
+/// ```
+pub struct Ip6GretapInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip6gretap: Option<Ip6TunnelConfig>,
+}
+
+impl Default for Ip6GretapInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Ip6Gretap,
+                ..Default::default()
+            },
+            ip6gretap: None,
+        }
+    }
+}
+
+impl Ip6GretapInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.ip6gretap.as_ref() {
+                conf.validate(self.base.name.as_str(), false)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.ip6gretap.as_ref().and_then(|cfg| cfg.parent())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IPv4 or IPv6 over IPv6 tunnel interface. The example yaml
+/// output of [crate::NetworkState] with an ip6tnl interface would be:
+/// ```yml
+/// interfaces:
+/// - name: ip6tnl1
+///   type: ip6tnl
+///   state: up
+///   ip6tnl:
+///     mode: ip6ip6
+///     local: 2001:db8::1
+///     remote: 2001:db8::2
+///     encap-limit: 4
+/// ```
+pub struct Ip6TnlInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip6tnl: Option<Ip6TunnelConfig>,
+}
+
+impl Default for Ip6TnlInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Ip6Tnl,
+                ..Default::default()
+            },
+            ip6tnl: None,
+        }
+    }
+}
+
+impl Ip6TnlInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.ip6tnl.as_ref() {
+                conf.validate(self.base.name.as_str(), true)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.ip6tnl.as_ref().and_then(|cfg| cfg.parent())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct Ip6TunnelConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
+    /// Inner protocol of ip6tnl interface. Only valid for `ip6tnl`
+    /// interface.
+    pub mode: Option<Ip6TnlMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Interface used for sending tunnel packets.
+    pub base_iface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Local endpoint address of the tunnel.
+    pub local: Option<Ipv6Addr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remote endpoint address of the tunnel.
+    pub remote: Option<Ipv6Addr>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Key for incoming packets. Only valid for `ip6gre` and `ip6gretap`
+    /// interfaces.
+    pub ikey: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// Key for outgoing packets. Only valid for `ip6gre` and `ip6gretap`
+    /// interfaces.
+    pub okey: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Maximum level of nested tunnel encapsulation.
+    pub encap_limit: Option<u8>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    /// 20 bits flow label of tunnel packets.
+    pub flow_label: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u8_or_string"
+    )]
+    /// Hop limit of tunnel packets.
+    pub hop_limit: Option<u8>,
+}
+
+impl Ip6TunnelConfig {
+    pub(crate) const FLOW_LABEL_MAX: u32 = 0xfffff;
+
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn parent(&self) -> Option<&str> {
+        self.base_iface.as_deref().filter(|p| !p.is_empty())
+    }
+
+    fn validate(
+        &self,
+        iface_name: &str,
+        is_ip6tnl: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(flow_label) = self.flow_label {
+            if flow_label > Self::FLOW_LABEL_MAX {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid flow-label {flow_label} of interface \
+                        {iface_name}, should be in the range of 0 - {}",
+                        Self::FLOW_LABEL_MAX
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        if is_ip6tnl && (self.ikey.is_some() || self.okey.is_some()) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Interface {iface_name} is ip6tnl tunnel which does not \
+                    support ikey or okey"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if !is_ip6tnl && self.mode.is_some() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "The mode property is only valid for ip6tnl tunnel, \
+                    but defined for interface {iface_name}"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum Ip6TnlMode {
+    /// IPv6 over IPv6
+    Ip6ip6,
+    /// IPv4 over IPv6
+    Ipip6,
+}
+
+impl std::fmt::Display for Ip6TnlMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Ip6ip6 => "ip6ip6",
+                Self::Ipip6 => "ipip6",
+            }
+        )
+    }
+}
@@ -0,0 +1,138 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Linux kernel IP VLAN interface. The example yaml output of
+/// [crate::NetworkState] with an IP VLAN interface would be:
+/// ```yaml
+/// ---
+/// interfaces:
+///   - name: ipvlan0
+///     type: ipvlan
+///     state: up
+///     ipvlan:
+///       base-iface: eth1
+///       mode: l3s
+///       private: true
+/// ```
+pub struct IpVlanInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ipvlan: Option<IpVlanConfig>,
+}
+
+impl Default for IpVlanInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::IpVlan,
+                ..Default::default()
+            },
+            ipvlan: None,
+        }
+    }
+}
+
+impl IpVlanInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = &self.ipvlan {
+                if conf.private == Some(true) && conf.vepa == Some(true) {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The private and vepa flags of IP VLAN {} cannot \
+                            be enabled at the same time",
+                            self.base.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn parent(&self) -> Option<&str> {
+        self.ipvlan.as_ref().map(|cfg| cfg.base_iface.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct IpVlanConfig {
+    pub base_iface: String,
+    #[serde(deserialize_with = "crate::deserializer::enum_ignore_case")]
+    pub mode: IpVlanMode,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Only allow communication with the base interface, not with other IP
+    /// VLAN interfaces sharing the same base interface.
+    pub private: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Send traffic between IP VLAN interfaces sharing the same base
+    /// interface to the external switch instead of forwarding it internally.
+    pub vepa: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum IpVlanMode {
+    /// Deserialize and serialize from/to `l2`.
+    L2,
+    /// Deserialize and serialize from/to `l3`.
+    L3,
+    /// Deserialize and serialize from/to `l3s`.
+    L3S,
+    Unknown,
+}
+
+impl From<IpVlanMode> for u32 {
+    fn from(v: IpVlanMode) -> u32 {
+        match v {
+            IpVlanMode::Unknown => 0,
+            IpVlanMode::L2 => 1,
+            IpVlanMode::L3 => 2,
+            IpVlanMode::L3S => 3,
+        }
+    }
+}
+
+impl From<u32> for IpVlanMode {
+    fn from(v: u32) -> Self {
+        match v {
+            1 => Self::L2,
+            2 => Self::L3,
+            3 => Self::L3S,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+impl Default for IpVlanMode {
+    fn default() -> Self {
+        Self::Unknown
+    }
+}
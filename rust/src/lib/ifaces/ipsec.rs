@@ -2,7 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{BaseInterface, InterfaceType, NetworkState};
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError, Secret};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -26,6 +26,29 @@ use crate::{BaseInterface, InterfaceType, NetworkState};
 ///     leftcert: hosta.example.org
 ///     ikev2: insist
 /// ```
+///
+/// Each [IpsecInterface] holds exactly one libreswan connection named after
+/// the interface name, which is also the NetworkManager connection ID.
+/// For VPN gateway with multiple tunnels, please define one [IpsecInterface]
+/// per tunnel. Each of them can be added, changed or removed(via
+/// `state: absent`) without touching the others:
+/// ```yaml
+/// ---
+/// interfaces:
+/// - name: tunnel_to_site_a
+///   type: ipsec
+///   libreswan:
+///     right: 192.0.2.252
+///     ikev2: insist
+/// - name: tunnel_to_site_b
+///   type: ipsec
+///   libreswan:
+///     right: 198.51.100.252
+///     ikev2: insist
+/// - name: tunnel_to_site_c
+///   type: ipsec
+///   state: absent
+/// ```
 pub struct IpsecInterface {
     #[serde(flatten)]
     pub base: BaseInterface,
@@ -50,10 +73,48 @@ impl IpsecInterface {
         Self::default()
     }
 
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if let Some(conf) = self.libreswan.as_ref() {
+            if is_desired
+                && conf.psk.is_some()
+                && conf.psk_from_secret_agent == Some(true)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The libreswan psk and psk-from-secret-agent of \
+                        ipsec interface {} cannot be used together",
+                        self.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    // The `psk` and `psk-from-secret-agent` are exclusive, the desired one
+    // overrides the current one.
+    pub(crate) fn special_merge(&mut self, desired: &Self) {
+        if let (Some(conf), Some(des_conf)) =
+            (self.libreswan.as_mut(), desired.libreswan.as_ref())
+        {
+            if des_conf.psk.is_some() {
+                conf.psk_from_secret_agent = des_conf.psk_from_secret_agent;
+            } else if des_conf.psk_from_secret_agent == Some(true) {
+                conf.psk = None;
+            }
+        }
+    }
+
     pub(crate) fn hide_secrets(&mut self) {
         if let Some(c) = self.libreswan.as_mut() {
             if c.psk.is_some() {
-                c.psk = Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+                c.psk = Some(Secret::hidden());
             }
         }
     }
@@ -79,8 +140,20 @@ pub struct LibreswanConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ikev2: Option<String>,
     /// PSK authentication, if not defined, will use X.509 PKI authentication
+    /// unless `psk-from-secret-agent` is set to true.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub psk: Option<String>,
+    pub psk: Option<Secret>,
+    /// Instead of defining `psk` inline, request it from the secret provider
+    /// registered via [crate::NetworkState::set_secret_provider()] during
+    /// activation. The provider is queried with interface name, setting name
+    /// `vpn` and property `pskvalue`. The PSK is never stored in
+    /// NetworkManager profile. Cannot be used with `psk`.
+    /// Deserialize and serialize from/to `psk-from-secret-agent`.
+    #[serde(
+        rename = "psk-from-secret-agent",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub psk_from_secret_agent: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ikelifetime: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
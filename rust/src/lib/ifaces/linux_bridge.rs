@@ -7,9 +7,10 @@ use std::str::FromStr;
 
 use serde::{de, de::Visitor, Deserialize, Deserializer, Serialize};
 
+use crate::warning::nmstate_warn;
 use crate::{
-    BaseInterface, BridgePortVlanConfig, ErrorKind, InterfaceType,
-    NmstateError, VlanProtocol,
+    BaseInterface, BridgePortVlanConfig, ErrorKind, Interface, InterfaceType,
+    MergedInterface, MergedInterfaces, NmstateError, VlanProtocol,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -122,9 +123,8 @@ impl LinuxBridgeInterface {
             self.sanitize_stp_opts()?;
         }
         self.use_upper_case_of_mac_address();
-        self.flatten_port_vlan_ranges();
-        self.sort_port_vlans();
         self.remove_runtime_only_timers();
+        self.remove_port_hw_offload();
         if let Some(port_confs) = self
             .bridge
             .as_ref()
@@ -136,6 +136,14 @@ impl LinuxBridgeInterface {
                 }
             }
         }
+        if let Some(vlan_conf) = self
+            .bridge
+            .as_ref()
+            .and_then(|br_conf| br_conf.vlan.as_ref())
+        {
+            vlan_conf.sanitize(is_desired)?;
+        }
+        self.compact_port_vlan_ranges();
         Ok(())
     }
 
@@ -150,22 +158,14 @@ impl LinuxBridgeInterface {
         }
     }
 
-    fn flatten_port_vlan_ranges(&mut self) {
-        if let Some(port_confs) = self
+    fn compact_port_vlan_ranges(&mut self) {
+        if let Some(vlan_conf) = self
             .bridge
             .as_mut()
-            .and_then(|br_conf| br_conf.port.as_mut())
+            .and_then(|br_conf| br_conf.vlan.as_mut())
         {
-            for port_conf in port_confs {
-                port_conf
-                    .vlan
-                    .as_mut()
-                    .map(BridgePortVlanConfig::flatten_vlan_ranges);
-            }
+            vlan_conf.compact_trunk_tags();
         }
-    }
-
-    fn sort_port_vlans(&mut self) {
         if let Some(port_confs) = self
             .bridge
             .as_mut()
@@ -175,7 +175,7 @@ impl LinuxBridgeInterface {
                 port_conf
                     .vlan
                     .as_mut()
-                    .map(BridgePortVlanConfig::sort_trunk_tags);
+                    .map(BridgePortVlanConfig::compact_trunk_tags);
             }
         }
     }
@@ -197,6 +197,18 @@ impl LinuxBridgeInterface {
         }
     }
 
+    fn remove_port_hw_offload(&mut self) {
+        if let Some(port_confs) = self
+            .bridge
+            .as_mut()
+            .and_then(|br_conf| br_conf.port.as_mut())
+        {
+            for port_conf in port_confs {
+                port_conf.hw_offload = None;
+            }
+        }
+    }
+
     fn sanitize_stp_opts(&self) -> Result<(), NmstateError> {
         if let Some(stp_opts) = self
             .bridge
@@ -224,6 +236,13 @@ impl LinuxBridgeInterface {
     }
 
     pub(crate) fn vlan_filtering_is_enabled(&self) -> bool {
+        if let Some(vlan_conf) =
+            self.bridge.as_ref().and_then(|b| b.vlan.as_ref())
+        {
+            if vlan_conf != &BridgePortVlanConfig::default() {
+                return true;
+            }
+        }
         if let Some(ports) = self.bridge.as_ref().and_then(|b| b.port.as_ref())
         {
             ports.as_slice().iter().any(|port_conf| {
@@ -362,6 +381,16 @@ pub struct LinuxBridgeConfig {
     /// current port list.
     /// Serialize to 'port'. Deserialize from `port` or `ports`.
     pub port: Option<Vec<LinuxBridgePortConfig>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// VLAN filtering configure of the bridge device itself(the `self` port
+    /// of `bridge vlan` command), using the same semantics of
+    /// [LinuxBridgePortConfig.vlan]. For example, with access mode and tag
+    /// 100, the IP address of the bridge interface will be in VLAN 100
+    /// without a separate VLAN interface. If not defined, current VLAN
+    /// filtering of bridge itself is preserved.
+    /// Setting to empty dict `{}` will remove all VLAN filtering of the
+    /// bridge itself.
+    pub vlan: Option<BridgePortVlanConfig>,
     // Deprecated, please use `ports`, this is only for backwards compatibility
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -378,7 +407,7 @@ impl LinuxBridgeConfig {
 
     pub(crate) fn post_deserialize_cleanup(&mut self) {
         if self.slaves.as_ref().is_some() {
-            log::warn!(
+            nmstate_warn!(
                 "The `slaves` is deprecated, please replace with `ports`."
             );
             self.port = self.slaves.clone();
@@ -421,6 +450,13 @@ pub struct LinuxBridgePortConfig {
     /// Linux bridge VLAN filtering configure. If not defined, current VLAN
     /// filtering is preserved for the specified port.
     pub vlan: Option<BridgePortVlanConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Query only. Set to true when the forwarding of this port is offloaded
+    /// to switchdev capable hardware(e.g. smart NIC or DPU). Kernel offloads
+    /// the bridge port automatically when its driver supports switchdev,
+    /// hence changing it will fail with [crate::ErrorKind::NotSupportedError].
+    /// Deserialize and serialize from/to `hw-offload`.
+    pub hw_offload: Option<bool>,
 }
 
 impl LinuxBridgePortConfig {
@@ -450,7 +486,11 @@ impl LinuxBridgePortConfig {
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct LinuxBridgeOptions {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u64_or_string"
+    )]
     pub gc_timer: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group_addr: Option<String>,
@@ -556,10 +596,34 @@ pub struct LinuxBridgeOptions {
     pub multicast_startup_query_interval: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stp: Option<LinuxBridgeStpOptions>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     pub vlan_protocol: Option<VlanProtocol>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vlan_default_pvid: Option<u16>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether to collect per-VLAN statistics.
+    /// Deserialize and serialize from/to `vlan-stats-enabled`.
+    pub vlan_stats_enabled: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether to disable learning MAC address from link-local
+    /// (01:80:C2:00:00:0X) packets.
+    /// Deserialize and serialize from/to `no-linklocal-learn`.
+    /// NetworkManager has no setting for this option, nmstate will set it via
+    /// sysfs after bridge activated, hence it will not persist after reboot
+    /// or bridge reactivation. Not supported in gen_conf mode.
+    pub no_linklocal_learn: Option<bool>,
 }
 
 impl LinuxBridgeOptions {
@@ -842,3 +906,85 @@ where
 
     deserializer.deserialize_any(IntegerOrString(PhantomData))
 }
+
+impl MergedInterfaces {
+    // The `no-linklocal-learn` option is applied to kernel at runtime, which
+    // cannot be stored in generated NetworkManager keyfile.
+    pub(crate) fn validate_linux_bridge_no_linklocal_learn(
+        &self,
+    ) -> Result<(), NmstateError> {
+        if !self.gen_conf_mode {
+            return Ok(());
+        }
+        for iface in self.kernel_ifaces.values().filter(|i| i.is_desired()) {
+            if let Some(Interface::LinuxBridge(br_iface)) =
+                iface.for_apply.as_ref()
+            {
+                if br_iface
+                    .bridge
+                    .as_ref()
+                    .and_then(|b| b.options.as_ref())
+                    .and_then(|o| o.no_linklocal_learn)
+                    .is_some()
+                {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The `no-linklocal-learn` option of linux bridge \
+                            {} is not supported in gc(gen_conf) mode",
+                            br_iface.base.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl MergedInterface {
+    pub(crate) fn post_inter_ifaces_process_linux_bridge(
+        &self,
+    ) -> Result<(), NmstateError> {
+        let des_port_confs = match self.for_apply.as_ref() {
+            Some(Interface::LinuxBridge(i)) => {
+                match i.bridge.as_ref().and_then(|b| b.port.as_ref()) {
+                    Some(p) => p,
+                    None => return Ok(()),
+                }
+            }
+            _ => return Ok(()),
+        };
+        let cur_br_iface = match self.current.as_ref() {
+            Some(Interface::LinuxBridge(i)) => Some(i),
+            _ => None,
+        };
+        for des_port_conf in des_port_confs {
+            let des_hw_offload = match des_port_conf.hw_offload {
+                Some(v) => v,
+                None => continue,
+            };
+            let cur_hw_offload = cur_br_iface
+                .and_then(|i| i.get_port_conf(des_port_conf.name.as_str()))
+                .and_then(|p| p.hw_offload)
+                .unwrap_or_default();
+            if des_hw_offload != cur_hw_offload {
+                let e = NmstateError::new(
+                    ErrorKind::NotSupportedError,
+                    format!(
+                        "Changing hw-offload of linux bridge {} port {} is \
+                        not supported, kernel offloads the port \
+                        automatically when its driver supports switchdev",
+                        self.merged.name(),
+                        des_port_conf.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
@@ -2,7 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
+use crate::{
+    BaseInterface, ErrorKind, Interface, InterfaceType, MacAddress,
+    MergedInterface, NmstateError,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -19,6 +22,20 @@ use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError};
 ///       mode: vepa
 ///       promiscuous: true
 /// ```
+/// The mac vlan interface in `source` mode would be:
+/// ```yaml
+/// ---
+/// interfaces:
+///   - name: mac0
+///     type: mac-vlan
+///     state: up
+///     mac-vlan:
+///       base-iface: eth1
+///       mode: source
+///       source-mac-addresses:
+///       - 00:23:45:67:89:1A
+///       - 00:23:45:67:89:1B
+/// ```
 pub struct MacVlanInterface {
     #[serde(flatten)]
     pub base: BaseInterface,
@@ -45,11 +62,33 @@ impl MacVlanInterface {
     }
 
     pub(crate) fn sanitize(
-        &self,
+        &mut self,
         is_desired: bool,
     ) -> Result<(), NmstateError> {
+        if let Some(macs) = self
+            .mac_vlan
+            .as_mut()
+            .and_then(|c| c.source_mac_addresses.as_mut())
+        {
+            macs.sort_unstable();
+            macs.dedup();
+        }
         if is_desired {
             if let Some(conf) = &self.mac_vlan {
+                if conf.source_mac_addresses.is_some()
+                    && conf.mode != MacVlanMode::Source
+                {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "The source-mac-addresses of mac vlan {} is only \
+                            allowed on source mode",
+                            self.base.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
                 if conf.accept_all_mac == Some(false)
                     && conf.mode != MacVlanMode::Passthru
                 {
@@ -77,6 +116,7 @@ impl MacVlanInterface {
 #[non_exhaustive]
 pub struct MacVlanConfig {
     pub base_iface: String,
+    #[serde(deserialize_with = "crate::deserializer::enum_ignore_case")]
     pub mode: MacVlanMode,
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -88,6 +128,17 @@ pub struct MacVlanConfig {
     /// Serialize to `promiscuous`.
     /// Deserialize from `promiscuous` or `accept-all-mac`.
     pub accept_all_mac: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        alias = "macvlan-source-mac-list"
+    )]
+    /// The source MAC addresses allowed in `source` mode as reported by
+    /// kernel. Query only, as neither NetworkManager nor nispor could
+    /// configure it yet. Desired state holding a list different from current
+    /// will fail with [crate::ErrorKind::NotSupportedError].
+    /// Deserialize and serialize from/to `source-mac-addresses`.
+    /// Also deserialize from `macvlan-source-mac-list`.
+    pub source_mac_addresses: Option<Vec<MacAddress>>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -125,3 +176,52 @@ impl Default for MacVlanMode {
         Self::Unknown
     }
 }
+
+impl MergedInterface {
+    // The `source_mac_addresses` is query only, only allow desired state
+    // copied from current.
+    pub(crate) fn post_inter_ifaces_process_mac_vlan(
+        &self,
+    ) -> Result<(), NmstateError> {
+        let des_macs = match self.for_apply.as_ref() {
+            Some(Interface::MacVlan(i)) => {
+                match i.mac_vlan.as_ref().and_then(|c| {
+                    c.source_mac_addresses.as_ref().map(|m| (c.mode, m))
+                }) {
+                    Some((MacVlanMode::Source, m)) => m,
+                    _ => return Ok(()),
+                }
+            }
+            _ => return Ok(()),
+        };
+        let cur_macs = match self.current.as_ref() {
+            Some(Interface::MacVlan(i)) => i
+                .mac_vlan
+                .as_ref()
+                .filter(|c| c.mode == MacVlanMode::Source)
+                .and_then(|c| c.source_mac_addresses.as_deref())
+                .unwrap_or_default(),
+            _ => &[],
+        };
+        let mut des_macs = des_macs.clone();
+        des_macs.sort_unstable();
+        des_macs.dedup();
+        let mut cur_macs = cur_macs.to_vec();
+        cur_macs.sort_unstable();
+        cur_macs.dedup();
+        if des_macs != cur_macs {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Changing source-mac-addresses of mac vlan {} is not \
+                    supported, neither NetworkManager nor nispor could \
+                    configure it",
+                    self.merged.name()
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+}
@@ -77,6 +77,7 @@ impl MacVtapInterface {
 #[non_exhaustive]
 pub struct MacVtapConfig {
     pub base_iface: String,
+    #[serde(deserialize_with = "crate::deserializer::enum_ignore_case")]
     pub mode: MacVtapMode,
     #[serde(
         skip_serializing_if = "Option::is_none",
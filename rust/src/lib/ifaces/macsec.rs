@@ -3,9 +3,13 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    BaseInterface, ErrorKind, InterfaceType, NetworkState, NmstateError,
+    BaseInterface, ErrorKind, Interface, InterfaceType, MergedInterfaces,
+    NmstateError, Secret,
 };
 
+// SecTAG with SCI(16 bytes) plus default ICV length(16 bytes)
+const MACSEC_MTU_OVERHEAD: u64 = 32;
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 #[serde(rename_all = "kebab-case")]
 #[non_exhaustive]
@@ -52,41 +56,12 @@ impl MacSecInterface {
     }
 
     pub(crate) fn sanitize(
-        &self,
+        &mut self,
         is_desired: bool,
     ) -> Result<(), NmstateError> {
-        if is_desired {
-            if let Some(conf) = &self.macsec {
-                if conf.mka_cak.is_none() ^ conf.mka_ckn.is_none() {
-                    let e = NmstateError::new(
-                        ErrorKind::InvalidArgument,
-                        "The mka_cak and mka_cnk must be all missing or present.".to_string(),
-                    );
-                    log::error!("{}", e);
-                    return Err(e);
-                }
-                if let Some(mka_cak) = &conf.mka_cak {
-                    if mka_cak.len() != 32 {
-                        let e = NmstateError::new(
-                            ErrorKind::InvalidArgument,
-                            "The mka_cak must be a string of 32 characters"
-                                .to_string(),
-                        );
-                        log::error!("{}", e);
-                        return Err(e);
-                    }
-                }
-                if let Some(mka_ckn) = &conf.mka_ckn {
-                    if mka_ckn.len() > 64
-                        || mka_ckn.len() < 2
-                        || mka_ckn.len() % 2 == 1
-                    {
-                        let e = NmstateError::new(ErrorKind::InvalidArgument,
-                        "The mka_ckn must be a string of even size between 2 and 64 characters".to_string());
-                        log::error!("{}", e);
-                        return Err(e);
-                    }
-                }
+        if let Some(conf) = self.macsec.as_mut() {
+            if is_desired {
+                conf.validate()?;
             }
         }
         Ok(())
@@ -103,25 +78,40 @@ impl MacSecInterface {
 #[derive(Default)]
 pub struct MacSecConfig {
     /// Wether the transmitted traffic must be encrypted.
+    #[serde(deserialize_with = "crate::deserializer::bool_or_string")]
     pub encrypt: bool,
     /// The parent interface used by the MACsec interface.
     pub base_iface: String,
     /// The pre-shared CAK (Connectivity Association Key) for MACsec Key
     /// Agreement. Must be a string of 32 hexadecimal characters.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub mka_cak: Option<String>,
+    pub mka_cak: Option<Secret>,
     /// The pre-shared CKN (Connectivity-association Key Name) for MACsec Key
     /// Agreement. Must be a string of hexadecimal characters with a even
     /// length between 2 and 64.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mka_ckn: Option<String>,
+    /// List of pre-shared CAK/CKN pairs with optional activation windows.
+    /// As NetworkManager only supports single CAK, nmstate will use the key
+    /// which is active at the time of applying, if several keys are active,
+    /// the one activated latest wins. This allows defining the next key ahead
+    /// of time and re-applying the same desired state for graceful key
+    /// rollover. Verification passes when any of these keys is in use.
+    /// Not supported when generating NetworkManager keyfiles.
+    /// Cannot be used together with `mka_cak` and `mka_ckn`.
+    /// Serialize and deserialize to/from `mka-keys`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mka_keys: Option<Vec<MacSecKeyConfig>>,
     /// The port component of the SCI (Secure Channel Identifier), between 1
     /// and 65534.
+    #[serde(deserialize_with = "crate::deserializer::u32_or_string")]
     pub port: u32,
     /// Specifies the validation mode for incoming frames.
+    #[serde(deserialize_with = "crate::deserializer::enum_ignore_case")]
     pub validation: MacSecValidate,
     /// Specifies whether the SCI (Secure Channel Identifier) is included in
     /// every packet.
+    #[serde(deserialize_with = "crate::deserializer::bool_or_string")]
     pub send_sci: bool,
 }
 
@@ -132,8 +122,335 @@ impl MacSecConfig {
 
     pub(crate) fn hide_secrets(&mut self) {
         if self.mka_cak.is_some() {
-            self.mka_cak =
-                Some(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string());
+            self.mka_cak = Some(Secret::hidden());
+        }
+        if let Some(keys) = self.mka_keys.as_mut() {
+            for key in keys {
+                key.mka_cak = Secret::hidden();
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), NmstateError> {
+        if self.mka_cak.is_none() ^ self.mka_ckn.is_none() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "The mka_cak and mka_cnk must be all missing or present."
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(mka_cak) = &self.mka_cak {
+            validate_mka_cak(mka_cak.as_str())?;
+        }
+        if let Some(mka_ckn) = &self.mka_ckn {
+            validate_mka_ckn(mka_ckn)?;
+        }
+        if let Some(keys) = self.mka_keys.as_ref() {
+            if self.mka_cak.is_some() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    "The mka-keys cannot be used together with mka-cak \
+                    and mka-ckn"
+                        .to_string(),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if keys.is_empty() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    "The mka-keys should contain at least one key".to_string(),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            let mut ckns: Vec<&str> = Vec::new();
+            for key in keys {
+                key.validate()?;
+                if ckns.contains(&key.mka_ckn.as_str()) {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Duplicate mka-ckn {} found in mka-keys",
+                            key.mka_ckn
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                ckns.push(key.mka_ckn.as_str());
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the key which should be used at specified unix time(seconds
+    /// since epoch). When multiple keys are active, the one with the latest
+    /// `not-before` is preferred.
+    pub fn get_active_mka_key(&self, now: u64) -> Option<&MacSecKeyConfig> {
+        self.mka_keys
+            .as_ref()?
+            .iter()
+            .filter(|k| k.is_active(now))
+            .max_by_key(|k| k.not_before.unwrap_or(0))
+    }
+
+    // Convert `mka_keys` into `mka_cak` and `mka_ckn` which backends support.
+    // Should only be invoked right before backend applying.
+    pub(crate) fn resolve_mka_keys(
+        &mut self,
+        now: u64,
+    ) -> Result<(), NmstateError> {
+        let key = match self.get_active_mka_key(now) {
+            Some(k) => k.clone(),
+            None => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "None of the MACsec mka-keys is active at \
+                        unix time {now}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        log::info!("Using MACsec mka-ckn {} from mka-keys", key.mka_ckn);
+        if let Some(next_key) = self.mka_keys.as_ref().and_then(|keys| {
+            keys.iter()
+                .filter(|k| k.not_before.unwrap_or(0) > now)
+                .min_by_key(|k| k.not_before.unwrap_or(0))
+        }) {
+            log::info!(
+                "MACsec mka-ckn {} will become active at unix time {}, \
+                please apply again after that for key rollover",
+                next_key.mka_ckn,
+                next_key.not_before.unwrap_or(0)
+            );
+        }
+        self.mka_cak = Some(key.mka_cak);
+        self.mka_ckn = Some(key.mka_ckn);
+        self.mka_keys = None;
+        Ok(())
+    }
+}
+
+impl MergedInterfaces {
+    // Choose the active key of `mka-keys` for backend applying.
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn resolve_macsec_mka_keys(
+        &mut self,
+    ) -> Result<(), NmstateError> {
+        let now = get_unix_time_now();
+        for iface in self.kernel_ifaces.values_mut() {
+            if let Some(Interface::MacSec(macsec_iface)) =
+                iface.for_apply.as_mut()
+            {
+                if let Some(conf) = macsec_iface
+                    .macsec
+                    .as_mut()
+                    .filter(|c| c.mka_keys.is_some())
+                {
+                    conf.resolve_mka_keys(now)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "gen_conf")]
+    pub(crate) fn validate_macsec_mka_keys_for_gen_conf(
+        &self,
+    ) -> Result<(), NmstateError> {
+        for iface in self.kernel_ifaces.values() {
+            if let Some(Interface::MacSec(macsec_iface)) =
+                iface.for_apply.as_ref()
+            {
+                if macsec_iface
+                    .macsec
+                    .as_ref()
+                    .map(|c| c.mka_keys.is_some())
+                    .unwrap_or_default()
+                {
+                    let e = NmstateError::new(
+                        ErrorKind::NotSupportedError,
+                        format!(
+                            "The mka-keys of MACsec interface {} is not \
+                            supported when generating NetworkManager \
+                            keyfiles as the active key is chosen at the time \
+                            of applying, please use mka-cak and mka-ckn instead",
+                            macsec_iface.base.name
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Parent MTU should hold the MACsec MTU plus SecTAG and ICV.
+    pub(crate) fn validate_macsec_mtu(&self) -> Result<(), NmstateError> {
+        for iface in self.kernel_ifaces.values().filter(|i| {
+            i.merged.iface_type() == InterfaceType::MacSec && i.merged.is_up()
+        }) {
+            let parent_name = match iface.merged.parent() {
+                Some(p) if !p.is_empty() => p,
+                _ => continue,
+            };
+            let parent = match self.kernel_ifaces.get(parent_name) {
+                Some(p) => p,
+                None => continue,
+            };
+            // Kernel will reduce MACsec MTU when parent MTU changed, hence
+            // only check when MACsec MTU is desired.
+            if let (Some(mtu), Some(parent_mtu)) = (
+                iface.desired.as_ref().and_then(|i| i.base_iface().mtu),
+                parent.merged.base_iface().mtu,
+            ) {
+                if mtu + MACSEC_MTU_OVERHEAD > parent_mtu {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "MACsec interface {} MTU {mtu} is too big for \
+                            its base interface {parent_name} MTU \
+                            {parent_mtu}, base interface MTU should be at \
+                            least {}",
+                            iface.merged.name(),
+                            mtu + MACSEC_MTU_OVERHEAD
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Pre-shared MACsec Key Agreement key with optional activation window.
+/// The example YAML of MACsec using two keys for rollover would be:
+/// ```yaml
+/// ---
+/// interfaces:
+///   - name: macsec0
+///     type: macsec
+///     state: up
+///     macsec:
+///       encrypt: true
+///       base-iface: eth1
+///       mka-keys:
+///         - mka-cak: 50b71a8ef0bd5751ea76de6d6c98c03a
+///           mka-ckn: f2b4297d39da7330910a74abc0449feb
+///           not-after: 1767225600
+///         - mka-cak: 7e0b3c46ee0bd5751ea76de6d6c98c12
+///           mka-ckn: a1b4297d39da7330910a74abc0449fcc
+///           not-before: 1767139200
+///       port: 0
+///       validation: strict
+///       send-sci: true
+/// ```
+pub struct MacSecKeyConfig {
+    /// The pre-shared CAK (Connectivity Association Key). Must be a string of
+    /// 32 hexadecimal characters.
+    pub mka_cak: Secret,
+    /// The pre-shared CKN (Connectivity-association Key Name). Must be a
+    /// string of hexadecimal characters with a even length between 2 and 64.
+    pub mka_ckn: String,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u64_or_string"
+    )]
+    /// Unix time(seconds since epoch) this key become active. Undefined
+    /// means active since ever.
+    pub not_before: Option<u64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u64_or_string"
+    )]
+    /// Unix time(seconds since epoch) this key expires. Undefined means
+    /// never expire.
+    pub not_after: Option<u64>,
+}
+
+impl MacSecKeyConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether this key is active at specified unix time.
+    pub fn is_active(&self, now: u64) -> bool {
+        self.not_before.unwrap_or(0) <= now
+            && self.not_after.map(|t| now < t).unwrap_or(true)
+    }
+
+    fn validate(&self) -> Result<(), NmstateError> {
+        validate_mka_cak(self.mka_cak.as_str())?;
+        validate_mka_ckn(&self.mka_ckn)?;
+        if let (Some(not_before), Some(not_after)) =
+            (self.not_before, self.not_after)
+        {
+            if not_before >= not_after {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The not-before {not_before} of MACsec key \
+                        {} should be smaller than its not-after {not_after}",
+                        self.mka_ckn
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn validate_mka_cak(mka_cak: &str) -> Result<(), NmstateError> {
+    if mka_cak.len() != 32 {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            "The mka_cak must be a string of 32 characters".to_string(),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(())
+}
+
+fn validate_mka_ckn(mka_ckn: &str) -> Result<(), NmstateError> {
+    if mka_ckn.len() > 64 || mka_ckn.len() < 2 || mka_ckn.len() % 2 == 1 {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            "The mka_ckn must be a string of even size between 2 and 64 \
+            characters"
+                .to_string(),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(())
+}
+
+#[cfg(feature = "query_apply")]
+fn get_unix_time_now() -> u64 {
+    use std::time::SystemTime;
+
+    match SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        Ok(d) => d.as_secs(),
+        Err(e) => {
+            log::warn!("BUG: Failed to get current unix time: {e}");
+            0
         }
     }
 }
@@ -1,10 +1,17 @@
 mod base;
+mod batman;
 mod bond;
 mod bridge_vlan;
+mod can;
+mod custom;
 mod dummy;
 mod ethernet;
 mod ethtool;
+mod gre;
+mod ifb;
 pub(crate) mod inter_ifaces;
+mod ip6_tunnel;
+mod ip_vlan;
 mod ipsec;
 mod loopback;
 mod vrf;
@@ -16,11 +23,15 @@ mod linux_bridge;
 mod mac_vlan;
 mod mac_vtap;
 mod macsec;
+mod modem;
 mod ovs;
 mod sriov;
+mod team;
 mod vlan;
 
 pub use base::*;
+pub use batman::{BatmanConfig, BatmanInterface, BatmanRoutingAlgorithm};
+pub(crate) use bond::DEFAULT_ARP_MISSED_MAX;
 pub use bond::{
     BondAdSelect, BondAllPortsActive, BondArpAllTargets, BondArpValidate,
     BondConfig, BondFailOverMac, BondInterface, BondLacpRate, BondMode,
@@ -30,6 +41,8 @@ pub use bridge_vlan::{
     BridgePortTrunkTag, BridgePortVlanConfig, BridgePortVlanMode,
     BridgePortVlanRange,
 };
+pub use can::{CanConfig, CanInterface};
+pub use custom::CustomInterface;
 pub use dummy::DummyInterface;
 pub use ethernet::{
     EthernetConfig, EthernetDuplex, EthernetInterface, VethConfig,
@@ -38,9 +51,16 @@ pub use ethtool::{
     EthtoolCoalesceConfig, EthtoolConfig, EthtoolFeatureConfig,
     EthtoolPauseConfig, EthtoolRingConfig,
 };
+pub use gre::{GreConfig, GreInterface, GretapInterface};
+pub use ifb::IfbInterface;
 pub use infiniband::{InfiniBandConfig, InfiniBandInterface, InfiniBandMode};
 pub(crate) use inter_ifaces::MergedInterfaces;
 pub use inter_ifaces::*;
+pub use ip6_tunnel::{
+    Ip6GreInterface, Ip6GretapInterface, Ip6TnlInterface, Ip6TnlMode,
+    Ip6TunnelConfig,
+};
+pub use ip_vlan::{IpVlanConfig, IpVlanInterface, IpVlanMode};
 pub use ipsec::{IpsecInterface, LibreswanConfig};
 pub use linux_bridge::{
     LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeMulticastRouterType,
@@ -49,13 +69,17 @@ pub use linux_bridge::{
 pub use loopback::LoopbackInterface;
 pub use mac_vlan::{MacVlanConfig, MacVlanInterface, MacVlanMode};
 pub use mac_vtap::{MacVtapConfig, MacVtapInterface, MacVtapMode};
-pub use macsec::{MacSecConfig, MacSecInterface, MacSecValidate};
+pub use macsec::{
+    MacSecConfig, MacSecInterface, MacSecKeyConfig, MacSecValidate,
+};
+pub use modem::{ModemConfig, ModemInterface};
 pub use ovs::{
     OvsBridgeBondConfig, OvsBridgeBondMode, OvsBridgeBondPortConfig,
     OvsBridgeConfig, OvsBridgeInterface, OvsBridgeOptions, OvsBridgePortConfig,
     OvsBridgeStpOptions, OvsDpdkConfig, OvsInterface, OvsPatchConfig,
 };
 pub use sriov::{SrIovConfig, SrIovVfConfig};
+pub use team::{TeamConfig, TeamInterface, TeamRunner};
 pub use vlan::{VlanConfig, VlanInterface, VlanProtocol};
 pub use vrf::{VrfConfig, VrfInterface};
 pub use vxlan::{VxlanConfig, VxlanInterface};
@@ -0,0 +1,126 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{BaseInterface, ErrorKind, InterfaceType, NmstateError, Secret};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Mobile broadband (GSM/UMTS/LTE) modem managed by NetworkManager via
+/// ModemManager. The interface name is the control port of the modem, for
+/// example `cdc-wdm0`, which is not a kernel network interface, hence
+/// this interface only exist in user space tools.
+/// The example yaml output of [crate::NetworkState] with a LTE modem used as
+/// backup uplink of ethernet would be:
+/// ```yml
+/// interfaces:
+/// - name: eth1
+///   type: ethernet
+///   state: up
+///   ipv4:
+///     enabled: true
+///     dhcp: true
+///     auto-route-metric: 100
+/// - name: cdc-wdm0
+///   type: modem
+///   state: up
+///   ipv4:
+///     enabled: true
+///     dhcp: true
+///     auto-route-metric: 700
+///   modem:
+///     apn: internet.example.com
+///     pin: "1234"
+///     roaming: false
+/// ```
+pub struct ModemInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modem: Option<ModemConfig>,
+}
+
+impl Default for ModemInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Modem,
+                ..Default::default()
+            },
+            modem: None,
+        }
+    }
+}
+
+impl ModemInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn hide_secrets(&mut self) {
+        if let Some(conf) = self.modem.as_mut() {
+            if conf.pin.is_some() {
+                conf.pin = Some(Secret::hidden());
+            }
+        }
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired {
+            if let Some(conf) = self.modem.as_ref() {
+                conf.validate(self.base.name.as_str())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct ModemConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Access Point Name of the mobile network. Use empty string to let
+    /// modem use its default bearer settings.
+    pub apn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// PIN of the SIM card. Should be 4 to 8 digits.
+    pub pin: Option<Secret>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    /// Whether allowing connection when modem is registered to roaming
+    /// network.
+    pub roaming: Option<bool>,
+}
+
+impl ModemConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn validate(&self, iface_name: &str) -> Result<(), NmstateError> {
+        if let Some(pin) = self.pin.as_ref() {
+            if !pin.is_hidden()
+                && (!(4..=8).contains(&pin.as_str().len())
+                    || !pin.as_str().chars().all(|c| c.is_ascii_digit()))
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid SIM PIN of modem interface {iface_name}, \
+                        should be 4 to 8 digits"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
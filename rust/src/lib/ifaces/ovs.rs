@@ -5,6 +5,7 @@ use std::convert::TryFrom;
 
 use serde::{de, Deserialize, Deserializer, Serialize};
 
+use crate::warning::nmstate_warn;
 use crate::{
     BaseInterface, BridgePortVlanConfig, ErrorKind, Interface, InterfaceState,
     InterfaceType, LinuxBridgeStpOptions, MergedInterface, MergedInterfaces,
@@ -124,7 +125,7 @@ impl OvsBridgeInterface {
     ) -> Result<(), NmstateError> {
         if let Some(mtu) = self.base.mtu.as_ref() {
             if is_desired {
-                log::warn!(
+                nmstate_warn!(
                     "OVS Bridge {} could not hold 'mtu:{mtu}' configuration \
                     as it only exists in OVS database, ignoring",
                     self.base.name.as_str()
@@ -280,7 +281,7 @@ impl OvsBridgeConfig {
 
     pub(crate) fn post_deserialize_cleanup(&mut self) {
         if self.slaves.as_ref().is_some() {
-            log::warn!(
+            nmstate_warn!(
                 "The `slaves` is deprecated, please replace with `ports`."
             );
             self.ports = self.slaves.clone();
@@ -531,7 +532,11 @@ impl OvsInterface {
 ///           - name: eth1
 /// ```
 pub struct OvsBridgeBondConfig {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     pub mode: Option<OvsBridgeBondMode>,
     #[serde(
         skip_serializing_if = "Option::is_none",
@@ -665,18 +670,29 @@ pub struct OvsDpdkConfig {
     pub devargs: String,
     #[serde(
         skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string",
         alias = "n_rxq",
         rename = "rx-queue"
     )]
+    /// Number of rx queues of the DPDK port.
     /// Deserialize and serialize from/to `rx-queue`. You may also use
     /// OVS terminology `n_rxq` for this property.
     pub rx_queue: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
     /// Specifies  the  rx  queue  size (number rx descriptors) for dpdk ports.
     /// Must be power of 2 in the range of 1 to 4096.
     /// Setting to 0 means remove this setting from OVS database.
     pub n_rxq_desc: Option<u32>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
     /// Specifies  the  tx  queue  size (number tx descriptors) for dpdk ports.
     /// Must be power of 2 in the range of 1 to 4096.
     /// Setting to 0 means remove this setting from OVS database.
@@ -710,6 +726,15 @@ impl OvsDpdkConfig {
         is_desired: bool,
     ) -> Result<(), NmstateError> {
         if is_desired {
+            if self.rx_queue == Some(0) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    "OVS DPDK rx-queue(n_rxq) must be bigger than 0"
+                        .to_string(),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
             if let Some(n_rxq_desc) = self.n_rxq_desc {
                 validate_dpdk_queue_desc(n_rxq_desc, "n_rxq_desc")?;
             }
@@ -730,7 +755,7 @@ impl MergedInterface {
             && self.merged.iface_type() == InterfaceType::OvsBridge
             && self.merged.ports().map(|p| p.is_empty()).unwrap_or(true)
         {
-            log::warn!(
+            nmstate_warn!(
                 "OVS bridge {} cannot exist with empty port list, adding a \
                 OVS internal interface with the same name",
                 self.merged.name()
@@ -791,6 +816,51 @@ impl MergedInterface {
 }
 
 impl MergedInterfaces {
+    // Raise error if desired interface or ports of desired OVS bridge are
+    // currently managed by CNI(e.g. ovn-kubernetes), so nmstate will not
+    // clobber them by accident. Only invoked when user opt-in via
+    // `NetworkState::set_protect_cni_owned_ovs()`.
+    pub(crate) fn validate_cni_owned_ovs(&self) -> Result<(), NmstateError> {
+        for merged_iface in self.iter().filter(|i| i.is_desired()) {
+            let cur_iface = match merged_iface.current.as_ref() {
+                Some(i) => i,
+                None => continue,
+            };
+            let mut owned_by_cni = get_cni_external_id(cur_iface)
+                .map(|eid| (cur_iface.name(), eid));
+            if owned_by_cni.is_none()
+                && cur_iface.iface_type() == InterfaceType::OvsBridge
+            {
+                owned_by_cni = cur_iface
+                    .ports()
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|p| {
+                        self.kernel_ifaces
+                            .get(p)
+                            .and_then(|i| i.current.as_ref())
+                            .and_then(get_cni_external_id)
+                            .map(|eid| (p, eid))
+                    })
+                    .next();
+            }
+            if let Some((name, eid)) = owned_by_cni {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Interface {} is managed by CNI as OVS interface {name} \
+                        has external ID {eid}, please disable \
+                        NetworkState::set_protect_cni_owned_ovs() to override",
+                        merged_iface.merged.name(),
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
     // This function remove extra(undesired) ovs patch port from pre-apply
     // current, so it will not interfere with port change.
     pub(crate) fn process_allow_extra_ovs_patch_ports_for_apply(&mut self) {
@@ -882,3 +952,11 @@ where
         )),
     }
 }
+
+fn get_cni_external_id(iface: &Interface) -> Option<&str> {
+    iface
+        .base_iface()
+        .ovsdb
+        .as_ref()
+        .and_then(|o| o.get_cni_external_id())
+}
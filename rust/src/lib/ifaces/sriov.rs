@@ -1,9 +1,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::bridge_vlan::validate_overlap_trunk_tags;
+use crate::warning::nmstate_warn;
 use crate::{
-    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterface,
+    BridgePortTrunkTag, BridgePortVlanRange, ErrorKind, Interface,
+    InterfaceType, Interfaces, MacAddress, MergedInterface, MergedInterfaces,
     NmstateError,
 };
 
@@ -67,17 +72,16 @@ impl SrIovConfig {
         Self::default()
     }
 
-    // * Convert VF MAC address to upper case
     // * Sort by VF ID
-    pub(crate) fn sanitize(&mut self) {
+    // * Validate VLAN trunk tags
+    pub(crate) fn sanitize(&mut self) -> Result<(), NmstateError> {
         if let Some(vfs) = self.vfs.as_mut() {
             for vf in vfs.iter_mut() {
-                if let Some(address) = vf.mac_address.as_mut() {
-                    address.make_ascii_uppercase()
-                }
+                vf.validate_vlan_trunk_tags()?;
             }
             vfs.sort_unstable_by(|a, b| a.id.cmp(&b.id));
         }
+        Ok(())
     }
 
     // * Auto fill unmentioned VF ID
@@ -86,11 +90,6 @@ impl SrIovConfig {
         current: Option<&Self>,
     ) {
         if let Some(vfs) = self.vfs.as_mut() {
-            for vf in vfs.iter_mut() {
-                if let Some(address) = vf.mac_address.as_mut() {
-                    address.make_ascii_uppercase()
-                }
-            }
             vfs.sort_unstable_by(|a, b| a.id.cmp(&b.id));
 
             if !vfs.is_empty() {
@@ -133,7 +132,7 @@ pub struct SrIovVfConfig {
     pub iface_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Deserialize and serialize from/to `mac-address`.
-    pub mac_address: Option<String>,
+    pub mac_address: Option<MacAddress>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
@@ -174,12 +173,95 @@ pub struct SrIovVfConfig {
         deserialize_with = "crate::deserializer::option_u32_or_string"
     )]
     pub qos: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Multiple VLANs allowed on this VF for trunked guest. Each VLAN is
+    /// using the `qos` of this VF. Cannot be used along with non-zero
+    /// `vlan-id`. Only supported by NetworkManager backend and NIC driver
+    /// supporting VLAN list on VF. When querying, retrieved from
+    /// NetworkManager for VF holding multiple VLANs.
+    /// Deserialize and serialize from/to `vlan-trunk-tags`.
+    pub vlan_trunk_tags: Option<Vec<BridgePortTrunkTag>>,
 }
 
 impl SrIovVfConfig {
     pub fn new() -> Self {
         Self::default()
     }
+
+    fn validate_vlan_trunk_tags(&self) -> Result<(), NmstateError> {
+        if let Some(tags) = self.vlan_trunk_tags.as_deref() {
+            if self.vlan_id.unwrap_or_default() != 0 {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "SR-IOV VF {} cannot have both vlan-id and \
+                        vlan-trunk-tags defined",
+                        self.id
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if let Some(tag) = tags.iter().find(|t| {
+                let (min, max) = t.get_vlan_tag_range();
+                min == 0 || min > max || max > 4094
+            }) {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid VLAN trunk tag {tag} of SR-IOV VF {}, \
+                        VLAN ID should be in the range of 1 to 4094",
+                        self.id
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            validate_overlap_trunk_tags(tags)?;
+        }
+        Ok(())
+    }
+
+    // Expand VLAN trunk tags into VLAN ID list
+    pub(crate) fn get_vlan_trunk_ids(&self) -> Option<Vec<u32>> {
+        self.vlan_trunk_tags.as_ref().map(|tags| {
+            tags.iter()
+                .flat_map(|t| {
+                    let (min, max) = t.get_vlan_tag_range();
+                    (min..=max).map(u32::from)
+                })
+                .collect()
+        })
+    }
+
+    // Compress VLAN ID list into VLAN trunk tags, consecutive IDs are merged
+    // into ID range.
+    pub(crate) fn set_vlan_trunk_ids(&mut self, mut ids: Vec<u32>) {
+        ids.sort_unstable();
+        ids.dedup();
+        let mut ranges: Vec<(u16, u16)> = Vec::new();
+        for id in ids.into_iter().filter_map(|i| u16::try_from(i).ok()) {
+            match ranges.last_mut() {
+                Some((_, max)) if max.checked_add(1) == Some(id) => *max = id,
+                _ => ranges.push((id, id)),
+            }
+        }
+        self.vlan_trunk_tags = Some(
+            ranges
+                .into_iter()
+                .map(|(min, max)| {
+                    if min == max {
+                        BridgePortTrunkTag::Id(min)
+                    } else {
+                        BridgePortTrunkTag::IdRange(BridgePortVlanRange {
+                            min,
+                            max,
+                        })
+                    }
+                })
+                .collect(),
+        );
+    }
 }
 
 impl Interfaces {
@@ -396,3 +478,106 @@ impl MergedInterface {
         Ok(())
     }
 }
+
+impl MergedInterfaces {
+    // Bond using SR-IOV VFs as ports:
+    //  * Warn when VFs are from the same PF which provides no redundancy on
+    //    PF failure.
+    //  * Fail when VF has MAC address administratively set by PF without
+    //    trust enabled, kernel bond cannot change MAC address of this VF
+    //    unless fail_over_mac is active in active-backup mode.
+    pub(crate) fn validate_bond_sriov_vf_ports(
+        &self,
+    ) -> Result<(), NmstateError> {
+        let vf_infos = self.get_sriov_vf_infos();
+        if vf_infos.is_empty() {
+            return Ok(());
+        }
+        for bond_iface in self.kernel_ifaces.values().filter_map(|i| {
+            if let (true, Interface::Bond(bond_iface)) =
+                (i.is_desired() && i.merged.is_up(), &i.merged)
+            {
+                Some(bond_iface)
+            } else {
+                None
+            }
+        }) {
+            let mut pf_to_port: HashMap<&str, &str> = HashMap::new();
+            for port in bond_iface.ports().unwrap_or_default() {
+                let (pf_name, vf_conf) = match vf_infos.get(port) {
+                    Some(v) => v,
+                    None => continue,
+                };
+                if let Some(other_port) = pf_to_port.insert(pf_name, port) {
+                    nmstate_warn!(
+                        "Bond {} ports {} and {} are SR-IOV VFs of the same \
+                        PF {}, bond cannot survive failure of this PF",
+                        bond_iface.base.name,
+                        other_port,
+                        port,
+                        pf_name
+                    );
+                }
+                if let Some(vf_conf) = vf_conf {
+                    if vf_conf.mac_address.is_some()
+                        && vf_conf.trust != Some(true)
+                        && !bond_iface.is_mac_restricted_mode()
+                    {
+                        let e = NmstateError::new(
+                            ErrorKind::InvalidArgument,
+                            format!(
+                                "Bond {} port {port} is SR-IOV VF {} of PF \
+                                {pf_name} with MAC address administratively \
+                                set, bond cannot change its MAC address. \
+                                Please use fail_over_mac active in \
+                                active-backup mode or set trust: true on \
+                                this VF",
+                                bond_iface.base.name, vf_conf.id
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Return HashMap of VF interface name to PF name and desired VF config.
+    fn get_sriov_vf_infos(
+        &self,
+    ) -> HashMap<&str, (&str, Option<&SrIovVfConfig>)> {
+        let mut ret = HashMap::new();
+        for merged_iface in self.kernel_ifaces.values() {
+            let cur_vfs = match merged_iface.current.as_ref() {
+                Some(Interface::Ethernet(cur_iface)) => cur_iface
+                    .ethernet
+                    .as_ref()
+                    .and_then(|e| e.sr_iov.as_ref())
+                    .and_then(|s| s.vfs.as_deref())
+                    .unwrap_or_default(),
+                _ => continue,
+            };
+            let des_vfs = match merged_iface.desired.as_ref() {
+                Some(Interface::Ethernet(des_iface)) => des_iface
+                    .ethernet
+                    .as_ref()
+                    .and_then(|e| e.sr_iov.as_ref())
+                    .and_then(|s| s.vfs.as_deref())
+                    .unwrap_or_default(),
+                _ => &[],
+            };
+            for cur_vf in cur_vfs.iter().filter(|v| !v.iface_name.is_empty()) {
+                ret.insert(
+                    cur_vf.iface_name.as_str(),
+                    (
+                        merged_iface.merged.name(),
+                        des_vfs.iter().find(|v| v.id == cur_vf.id),
+                    ),
+                );
+            }
+        }
+        ret
+    }
+}
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    BaseInterface, BondConfig, BondInterface, BondMode, ErrorKind,
+    InterfaceState, InterfaceType, NmstateError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+/// Team interface managed by teamd. Only used for query, nmstate cannot
+/// create or modify team interface, please use [TeamInterface::to_bond()] to
+/// generate equivalent bond interface for migration. Removing team interface
+/// via `state: absent` is still allowed.
+/// The example yaml output of [crate::NetworkState] with a team interface
+/// would be:
+/// ```yml
+/// interfaces:
+/// - name: team0
+///   type: team
+///   state: ignore
+///   team:
+///     runner: activebackup
+///     port:
+///     - eth1
+///     - eth2
+/// ```
+pub struct TeamInterface {
+    #[serde(flatten)]
+    pub base: BaseInterface,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team: Option<TeamConfig>,
+}
+
+impl Default for TeamInterface {
+    fn default() -> Self {
+        Self {
+            base: BaseInterface {
+                iface_type: InterfaceType::Team,
+                ..Default::default()
+            },
+            team: None,
+        }
+    }
+}
+
+impl TeamInterface {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn sanitize(
+        &self,
+        is_desired: bool,
+    ) -> Result<(), NmstateError> {
+        if is_desired && self.base.state == InterfaceState::Up {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Team interface {} is only supported for query, please \
+                    convert it to bond interface",
+                    self.base.name
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Port list of team interface.
+    pub fn ports(&self) -> Option<Vec<&str>> {
+        self.team
+            .as_ref()
+            .and_then(|t| t.port.as_ref())
+            .map(|ports| ports.iter().map(|p| p.as_str()).collect())
+    }
+
+    /// Generate [BondInterface] with equivalent bond mode and the same port
+    /// list, MTU, MAC address and IP settings for migrating off teamd.
+    /// Return error if team runner has no equivalent bond mode.
+    pub fn to_bond(&self) -> Result<BondInterface, NmstateError> {
+        let mode = match self.team.as_ref().and_then(|t| t.runner) {
+            Some(runner) => Some(runner.to_bond_mode(&self.base.name)?),
+            None => None,
+        };
+        let mut bond_iface = BondInterface::new();
+        bond_iface.base.name.clone_from(&self.base.name);
+        bond_iface
+            .base
+            .description
+            .clone_from(&self.base.description);
+        bond_iface.base.state = InterfaceState::Up;
+        bond_iface.base.mtu = self.base.mtu;
+        bond_iface
+            .base
+            .mac_address
+            .clone_from(&self.base.mac_address);
+        bond_iface.base.ipv4.clone_from(&self.base.ipv4);
+        bond_iface.base.ipv6.clone_from(&self.base.ipv6);
+        bond_iface.base.controller.clone_from(&self.base.controller);
+        let mut bond_conf = BondConfig::new();
+        bond_conf.mode = mode;
+        bond_conf.port = Some(
+            self.ports()
+                .unwrap_or_default()
+                .into_iter()
+                .map(|p| p.to_string())
+                .collect(),
+        );
+        bond_iface.bond = Some(bond_conf);
+        Ok(bond_iface)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+pub struct TeamConfig {
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
+    /// Runner of teamd. Unknown when only kernel information available.
+    pub runner: Option<TeamRunner>,
+    #[serde(skip_serializing_if = "Option::is_none", alias = "ports")]
+    /// Deserialize and serialize from/to `port`.
+    /// You can also use `ports` for deserializing.
+    pub port: Option<Vec<String>>,
+}
+
+impl TeamConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+#[non_exhaustive]
+pub enum TeamRunner {
+    /// Deserialize and serialize from/to `broadcast`.
+    Broadcast,
+    /// Deserialize and serialize from/to `roundrobin`.
+    Roundrobin,
+    /// Deserialize and serialize from/to `random`.
+    Random,
+    /// Deserialize and serialize from/to `activebackup`.
+    Activebackup,
+    /// Deserialize and serialize from/to `loadbalance`.
+    Loadbalance,
+    /// Deserialize and serialize from/to `lacp`.
+    Lacp,
+}
+
+impl std::fmt::Display for TeamRunner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Broadcast => "broadcast",
+                Self::Roundrobin => "roundrobin",
+                Self::Random => "random",
+                Self::Activebackup => "activebackup",
+                Self::Loadbalance => "loadbalance",
+                Self::Lacp => "lacp",
+            }
+        )
+    }
+}
+
+impl TeamRunner {
+    fn to_bond_mode(self, iface_name: &str) -> Result<BondMode, NmstateError> {
+        match self {
+            Self::Broadcast => Ok(BondMode::Broadcast),
+            Self::Roundrobin => Ok(BondMode::RoundRobin),
+            Self::Activebackup => Ok(BondMode::ActiveBackup),
+            Self::Loadbalance => Ok(BondMode::XOR),
+            Self::Lacp => Ok(BondMode::LACP),
+            Self::Random => {
+                let e = NmstateError::new(
+                    ErrorKind::NotSupportedError,
+                    format!(
+                        "Team interface {iface_name} is using runner {self} \
+                        which has no equivalent bond mode"
+                    ),
+                );
+                log::error!("{}", e);
+                Err(e)
+            }
+        }
+    }
+}
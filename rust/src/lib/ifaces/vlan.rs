@@ -61,7 +61,11 @@ pub struct VlanConfig {
     #[serde(deserialize_with = "crate::deserializer::u16_or_string")]
     pub id: u16,
     /// Could be `802.1q` or `802.1ad`. Default to `802.1q` if not defined.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     pub protocol: Option<VlanProtocol>,
 }
 
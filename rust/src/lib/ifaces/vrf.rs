@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::warning::nmstate_warn;
 use crate::{
     BaseInterface, ErrorKind, Interface, InterfaceType, MergedInterface,
     NmstateError,
@@ -71,7 +72,7 @@ impl VrfInterface {
         // interface.
         if is_desired {
             if let Some(mac) = self.base.mac_address.as_ref() {
-                log::warn!(
+                nmstate_warn!(
                     "Ignoring MAC address {mac} of VRF interface {} \
                     as it is a layer 3(IP) interface",
                     self.base.name.as_str()
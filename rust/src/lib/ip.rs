@@ -2,10 +2,12 @@
 
 use std::fmt::Write;
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::ops::RangeInclusive;
 use std::str::FromStr;
 
 use serde::{self, Deserialize, Deserializer, Serialize};
 
+use crate::warning::nmstate_warn;
 use crate::{
     BaseInterface, DnsClientState, ErrorKind, MergedInterface,
     MptcpAddressFlag, NmstateError, RouteRuleEntry,
@@ -16,11 +18,12 @@ const AF_INET6: u8 = 10;
 const IPV4_ADDR_LEN: usize = 32;
 const IPV6_ADDR_LEN: usize = 128;
 const FOREVER: &str = "forever";
+const IPV4_DAD_TIMEOUT_MAX: u32 = 30000;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[non_exhaustive]
 #[serde(deny_unknown_fields)]
-struct InterfaceIp {
+pub(crate) struct InterfaceIp {
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
@@ -46,6 +49,13 @@ struct InterfaceIp {
     pub dhcp_client_id: Option<Dhcpv4ClientId>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "dhcp-duid")]
     pub dhcp_duid: Option<Dhcpv6Duid>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "dhcp-mode",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
+    pub dhcp_mode: Option<Dhcpv6Mode>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "address")]
     pub addresses: Option<Vec<InterfaceIpAddr>>,
     #[serde(
@@ -93,6 +103,20 @@ struct InterfaceIp {
     pub allow_extra_address: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub token: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "dad-timeout",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string"
+    )]
+    pub dad_timeout: Option<u32>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "address-conflict",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub address_conflict: Option<bool>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         rename = "dhcp-send-hostname"
@@ -103,6 +127,18 @@ struct InterfaceIp {
         rename = "dhcp-custom-hostname"
     )]
     pub dhcp_custom_hostname: Option<String>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "dhcp-send-release",
+        default,
+        deserialize_with = "crate::deserializer::option_bool_or_string"
+    )]
+    pub dhcp_send_release: Option<bool>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        rename = "router-advertisement"
+    )]
+    pub ra: Option<Ipv6RaInfo>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -190,6 +226,24 @@ pub struct InterfaceIpv4 {
     /// If not defined, current non-dynamic hostname will be used.
     /// Deserialize from `dhcp-custom-hostname`
     pub dhcp_custom_hostname: Option<String>,
+    /// Whether to send DHCP RELEASE message to DHCP server and remove the
+    /// leased IP address when interface is deactivated.
+    /// Set to false to retain the lease across interface down or absent
+    /// done by nmstate.
+    /// If not defined, the default of network backend will be used.
+    /// Only available for DHCPv4 enabled interface.
+    /// Serialize and deserialize to/from `dhcp-send-release`.
+    pub dhcp_send_release: Option<bool>,
+    /// Timeout in milliseconds for IPv4 address conflict detection(ACD)
+    /// defined in RFC 5227, the maximum value is 30000. Set to 0 to disable
+    /// ACD. If not defined, the default of network backend will be used.
+    /// Serialize and deserialize to/from `dad-timeout`.
+    pub dad_timeout: Option<u32>,
+    /// Query only. Set to true when network backend failed to activate this
+    /// interface because of IPv4 address conflict detected.
+    /// Ignored when applying.
+    /// Serialize and deserialize to/from `address-conflict`.
+    pub address_conflict: Option<bool>,
     pub(crate) dns: Option<DnsClientState>,
     pub(crate) rules: Option<Vec<RouteRuleEntry>>,
 }
@@ -212,6 +266,9 @@ impl Default for InterfaceIpv4 {
             auto_route_metric: None,
             dhcp_send_hostname: None,
             dhcp_custom_hostname: None,
+            dhcp_send_release: None,
+            dad_timeout: None,
+            address_conflict: None,
         }
     }
 }
@@ -362,9 +419,30 @@ impl InterfaceIpv4 {
             });
         }
 
+        if is_desired && self.address_conflict.is_some() {
+            log::info!("Ignoring query only `address-conflict` property");
+        }
+        if is_desired {
+            self.address_conflict = None;
+        }
+        if let Some(dad_timeout) = self.dad_timeout {
+            if is_desired && dad_timeout > IPV4_DAD_TIMEOUT_MAX {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid IPv4 dad-timeout {dad_timeout}, should be \
+                        in the range of 0 to {IPV4_DAD_TIMEOUT_MAX}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+
         if !self.enabled {
             self.dhcp = None;
             self.addresses = None;
+            self.dad_timeout = None;
         }
 
         if self.dhcp != Some(true) {
@@ -374,7 +452,7 @@ impl InterfaceIpv4 {
             self.auto_table_id = None;
             self.auto_route_metric = None;
             if is_desired && self.dhcp_client_id.is_some() {
-                log::warn!(
+                nmstate_warn!(
                     "Ignoring `dhcp-client-id` setting when DHCPv4 is \
                     disabled"
                 );
@@ -382,6 +460,7 @@ impl InterfaceIpv4 {
             self.dhcp_client_id = None;
             self.dhcp_send_hostname = None;
             self.dhcp_custom_hostname = None;
+            self.dhcp_send_release = None;
         }
         if self.dhcp_send_hostname == Some(false) {
             if is_desired {
@@ -389,7 +468,7 @@ impl InterfaceIpv4 {
                     self.dhcp_custom_hostname.as_deref()
                 {
                     if !custom_hostname.is_empty() {
-                        log::warn!(
+                        nmstate_warn!(
                             "Ignoring `dhcp-custom-hostname: \
                             {custom_hostname}` as `dhcp-send-hostname` is \
                             disabled"
@@ -430,6 +509,16 @@ impl<'de> Deserialize<'de> for InterfaceIpv4 {
                 "dhcp-duid is not allowed for IPv4",
             ));
         }
+        if prop_list.contains(&"dhcp_mode") {
+            return Err(serde::de::Error::custom(
+                "dhcp-mode is not allowed for IPv4",
+            ));
+        }
+        if prop_list.contains(&"ra") {
+            return Err(serde::de::Error::custom(
+                "router-advertisement is not allowed for IPv4",
+            ));
+        }
 
         let ip: InterfaceIp = match serde_json::from_value(v) {
             Ok(i) => i,
@@ -458,6 +547,9 @@ impl From<InterfaceIp> for InterfaceIpv4 {
             auto_route_metric: ip.auto_route_metric,
             dhcp_send_hostname: ip.dhcp_send_hostname,
             dhcp_custom_hostname: ip.dhcp_custom_hostname,
+            dhcp_send_release: ip.dhcp_send_release,
+            dad_timeout: ip.dad_timeout,
+            address_conflict: ip.address_conflict,
             ..Default::default()
         }
     }
@@ -483,6 +575,9 @@ impl From<InterfaceIpv4> for InterfaceIp {
             auto_route_metric: ip.auto_route_metric,
             dhcp_send_hostname: ip.dhcp_send_hostname,
             dhcp_custom_hostname: ip.dhcp_custom_hostname,
+            dhcp_send_release: ip.dhcp_send_release,
+            dad_timeout: ip.dad_timeout,
+            address_conflict: ip.address_conflict,
             ..Default::default()
         }
     }
@@ -523,6 +618,11 @@ pub struct InterfaceIpv6 {
     /// DHCPv6 Unique Identifier
     /// Serialize and deserialize to/from `dhcp-duid`.
     pub dhcp_duid: Option<Dhcpv6Duid>,
+    /// How DHCPv6 works along with IPv6 router announcement.
+    /// When defined, `dhcp` is implied as true and `autoconf` is implied by
+    /// the mode, conflicting `dhcp` or `autoconf` will be treated as error.
+    /// Serialize and deserialize to/from `dhcp-mode`.
+    pub dhcp_mode: Option<Dhcpv6Mode>,
     /// Whether autoconf via IPv6 router announcement enabled.
     pub autoconf: Option<bool>,
     /// IPv6 address generation mode.
@@ -577,6 +677,18 @@ pub struct InterfaceIpv6 {
     /// If not defined, current non-dynamic hostname will be used.
     /// Deserialize from `dhcp-custom-hostname`
     pub dhcp_custom_hostname: Option<String>,
+    /// Whether to send DHCPv6 RELEASE message to DHCP server and remove the
+    /// leased IP address when interface is deactivated.
+    /// Set to false to retain the lease across interface down or absent
+    /// done by nmstate.
+    /// If not defined, the default of network backend will be used.
+    /// Only available for DHCPv6 enabled interface.
+    /// Serialize and deserialize to/from `dhcp-send-release`.
+    pub dhcp_send_release: Option<bool>,
+    /// Routes and DNS information learned from IPv6 router advertisement.
+    /// This property is query only, it will be ignored when applying.
+    /// Serialize to `router-advertisement`.
+    pub ra: Option<Ipv6RaInfo>,
 
     pub(crate) dns: Option<DnsClientState>,
     pub(crate) rules: Option<Vec<RouteRuleEntry>>,
@@ -589,6 +701,7 @@ impl Default for InterfaceIpv6 {
             prop_list: Vec::new(),
             dhcp: None,
             dhcp_duid: None,
+            dhcp_mode: None,
             autoconf: None,
             addr_gen_mode: None,
             addresses: None,
@@ -603,6 +716,8 @@ impl Default for InterfaceIpv6 {
             token: None,
             dhcp_send_hostname: None,
             dhcp_custom_hostname: None,
+            dhcp_send_release: None,
+            ra: None,
         }
     }
 }
@@ -627,11 +742,18 @@ impl InterfaceIpv6 {
     //   those options is None
     // * Disable DHCP and remove address if enabled: false
     // * Set DHCP options to None if DHCP is false
-    // * Remove `mptcp_flags` as they are for query only
+    // * Remove `mptcp_flags` and `ra` as they are for query only
     pub(crate) fn sanitize(
         &mut self,
         is_desired: bool,
     ) -> Result<(), NmstateError> {
+        if self.enabled {
+            self.sanitize_dhcp_mode()?;
+        }
+        if is_desired && self.ra.is_some() {
+            log::info!("Ignoring query only IPv6 router-advertisement");
+        }
+        self.ra = None;
         if let Some(addrs) = self.addresses.as_mut() {
             if is_desired {
                 for addr in addrs.as_slice().iter().filter(|a| a.is_auto()) {
@@ -695,7 +817,7 @@ impl InterfaceIpv6 {
                 if let IpAddr::V6(ip_addr) = addr.ip {
                     if is_ipv6_unicast_link_local(&ip_addr) {
                         if is_desired {
-                            log::warn!(
+                            nmstate_warn!(
                                 "Ignoring IPv6 link local address {}/{}",
                                 &addr.ip,
                                 addr.prefix_length
@@ -713,9 +835,13 @@ impl InterfaceIpv6 {
 
         if !self.enabled {
             self.dhcp = None;
+            self.dhcp_mode = None;
             self.autoconf = None;
             self.addresses = None;
         }
+        if self.dhcp == Some(false) {
+            self.dhcp_mode = None;
+        }
 
         if !self.is_auto() {
             self.auto_dns = None;
@@ -726,6 +852,9 @@ impl InterfaceIpv6 {
             self.dhcp_send_hostname = None;
             self.dhcp_custom_hostname = None;
         }
+        if self.dhcp != Some(true) {
+            self.dhcp_send_release = None;
+        }
         if let Some(addrs) = self.addresses.as_mut() {
             for addr in addrs.iter_mut() {
                 addr.mptcp_flags = None;
@@ -754,7 +883,7 @@ impl InterfaceIpv6 {
                     self.dhcp_custom_hostname.as_deref()
                 {
                     if !custom_hostname.is_empty() {
-                        log::warn!(
+                        nmstate_warn!(
                             "Ignoring `dhcp-custom-hostname: \
                             {custom_hostname}` as `dhcp-send-hostname` is \
                             disabled"
@@ -767,6 +896,34 @@ impl InterfaceIpv6 {
         Ok(())
     }
 
+    // Set `dhcp` and `autoconf` implied by `dhcp_mode`, raise error if they
+    // are conflicting with each other.
+    pub(crate) fn sanitize_dhcp_mode(&mut self) -> Result<(), NmstateError> {
+        let mode = if let Some(m) = self.dhcp_mode {
+            m
+        } else {
+            return Ok(());
+        };
+        let autoconf = mode != Dhcpv6Mode::NoRa;
+        if self.dhcp == Some(false) || self.autoconf == Some(!autoconf) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "IPv6 `dhcp-mode: {mode}` requires `dhcp: true` and \
+                    `autoconf: {autoconf}`, but got `dhcp: {}` and \
+                    `autoconf: {}`",
+                    self.dhcp.unwrap_or(true),
+                    self.autoconf.unwrap_or(autoconf),
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        self.dhcp = Some(true);
+        self.autoconf = Some(autoconf);
+        Ok(())
+    }
+
     // Special action for generating merged state from desired and current.
     pub(crate) fn special_merge(&mut self, desired: &Self, current: &Self) {
         if !desired.prop_list.contains(&"enabled") {
@@ -841,6 +998,16 @@ impl<'de> Deserialize<'de> for InterfaceIpv6 {
                 "dhcp-client-id is not allowed for IPv6",
             ));
         }
+        if prop_list.contains(&"dad_timeout") {
+            return Err(serde::de::Error::custom(
+                "dad-timeout is not allowed for IPv6",
+            ));
+        }
+        if prop_list.contains(&"address_conflict") {
+            return Err(serde::de::Error::custom(
+                "address-conflict is not allowed for IPv6",
+            ));
+        }
         let ip: InterfaceIp = match serde_json::from_value(v) {
             Ok(i) => i,
             Err(e) => {
@@ -861,6 +1028,7 @@ impl From<InterfaceIp> for InterfaceIpv6 {
             autoconf: ip.autoconf,
             addresses: ip.addresses,
             dhcp_duid: ip.dhcp_duid,
+            dhcp_mode: ip.dhcp_mode,
             auto_dns: ip.auto_dns,
             auto_routes: ip.auto_routes,
             auto_gateway: ip.auto_gateway,
@@ -871,6 +1039,8 @@ impl From<InterfaceIp> for InterfaceIpv6 {
             token: ip.token,
             dhcp_send_hostname: ip.dhcp_send_hostname,
             dhcp_custom_hostname: ip.dhcp_custom_hostname,
+            dhcp_send_release: ip.dhcp_send_release,
+            ra: ip.ra,
             ..Default::default()
         }
     }
@@ -889,6 +1059,7 @@ impl From<InterfaceIpv6> for InterfaceIp {
             autoconf: ip.autoconf,
             addresses: ip.addresses,
             dhcp_duid: ip.dhcp_duid,
+            dhcp_mode: ip.dhcp_mode,
             auto_dns: ip.auto_dns,
             auto_routes: ip.auto_routes,
             auto_gateway: ip.auto_gateway,
@@ -899,16 +1070,116 @@ impl From<InterfaceIpv6> for InterfaceIp {
             token: ip.token,
             dhcp_send_hostname: ip.dhcp_send_hostname,
             dhcp_custom_hostname: ip.dhcp_custom_hostname,
+            dhcp_send_release: ip.dhcp_send_release,
+            ra: ip.ra,
             ..Default::default()
         }
     }
 }
 
-#[derive(
-    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize,
-)]
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
+/// Information learned from IPv6 router advertisement(RA), useful for
+/// debugging interface with IPv6 autoconf enabled.
+/// This is query only, will be ignored when applying.
+/// The lifetime is the remaining time in the format of "32sec" or
+/// "forever", `None` if backend does not provide it.
+/// Example yaml output of interface with RA information:
+/// ```yml
+/// interfaces:
+/// - name: eth1
+///   type: ethernet
+///   state: up
+///   ipv6:
+///     enabled: true
+///     autoconf: true
+///     dhcp: false
+///     router-advertisement:
+///       route:
+///       - destination: ::/0
+///         next-hop-address: fe80::1
+///         metric: 1024
+///         lifetime: 1795sec
+///       rdnss:
+///       - server: 2001:db8::53
+///       dnssl:
+///       - domain: example.org
+/// ```
+pub struct Ipv6RaInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Routes(including default gateway) learned from RA.
+    pub route: Option<Vec<Ipv6RaRoute>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Recursive DNS servers learned from RA.
+    pub rdnss: Option<Vec<Ipv6RaRdnss>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// DNS search list learned from RA.
+    pub dnssl: Option<Vec<Ipv6RaDnssl>>,
+}
+
+impl Ipv6RaInfo {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Route learned from IPv6 router advertisement.
+pub struct Ipv6RaRoute {
+    /// Route destination, `::/0` for default gateway.
+    pub destination: IpNetwork,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Serialize and deserialize to/from `next-hop-address`.
+    pub next_hop_address: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metric: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remaining time for this route been valid.
+    pub lifetime: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Recursive DNS server learned from IPv6 router advertisement.
+pub struct Ipv6RaRdnss {
+    pub server: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remaining time for this DNS server been valid.
+    pub lifetime: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// DNS search domain learned from IPv6 router advertisement.
+pub struct Ipv6RaDnssl {
+    pub domain: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Remaining time for this search domain been valid.
+    pub lifetime: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// IP address of interface.
+/// Besides the `ip` and `prefix-length` object form, also deserialize from
+/// the `ip/prefix_length` string, for example:
+/// ```yaml
+/// ---
+/// interfaces:
+/// - name: eth1
+///   ipv4:
+///     address:
+///     - 192.0.2.252/24
+///     - ip: 192.0.2.251
+///       prefix-length: 24
+///     enabled: true
+/// ```
 pub struct InterfaceIpAddr {
     /// IP address.
     pub ip: IpAddr,
@@ -947,6 +1218,52 @@ pub struct InterfaceIpAddr {
     pub preferred_life_time: Option<String>,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub(crate) struct InterfaceIpAddrObj {
+    ip: IpAddr,
+    #[serde(deserialize_with = "crate::deserializer::u8_or_string")]
+    prefix_length: u8,
+    #[serde(
+        default,
+        deserialize_with = "crate::deserializer::option_vec_enum_ignore_case"
+    )]
+    mptcp_flags: Option<Vec<MptcpAddressFlag>>,
+    #[serde(default, alias = "valid-left", alias = "valid-lft")]
+    valid_life_time: Option<String>,
+    #[serde(default, alias = "preferred-left", alias = "preferred-lft")]
+    preferred_life_time: Option<String>,
+}
+
+impl From<InterfaceIpAddrObj> for InterfaceIpAddr {
+    fn from(obj: InterfaceIpAddrObj) -> Self {
+        Self {
+            ip: obj.ip,
+            prefix_length: obj.prefix_length,
+            mptcp_flags: obj.mptcp_flags,
+            valid_life_time: obj.valid_life_time,
+            preferred_life_time: obj.preferred_life_time,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for InterfaceIpAddr {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let v = serde_json::Value::deserialize(deserializer)?;
+        if let Some(addr) = v.as_str() {
+            Self::try_from(addr)
+                .map_err(|e| serde::de::Error::custom(e.msg().to_string()))
+        } else {
+            serde_json::from_value::<InterfaceIpAddrObj>(v)
+                .map(Self::from)
+                .map_err(serde::de::Error::custom)
+        }
+    }
+}
+
 impl Default for InterfaceIpAddr {
     fn default() -> Self {
         Self {
@@ -981,6 +1298,153 @@ impl InterfaceIpAddr {
         self.valid_life_time.is_some()
             && self.valid_life_time.as_deref() != Some(FOREVER)
     }
+
+    // IP address with bits beyond `prefix_length` cleared, prefix length
+    // larger than the address length of the IP family is capped.
+    pub(crate) fn mask_ip(ip: &IpAddr, prefix_length: usize) -> IpAddr {
+        match ip {
+            IpAddr::V4(i) => {
+                let prefix_length = prefix_length.min(IPV4_ADDR_LEN);
+                Ipv4Addr::from(
+                    u32::from(*i)
+                        & u32::MAX
+                            .checked_shl((IPV4_ADDR_LEN - prefix_length) as u32)
+                            .unwrap_or(0),
+                )
+                .into()
+            }
+            IpAddr::V6(i) => {
+                let prefix_length = prefix_length.min(IPV6_ADDR_LEN);
+                Ipv6Addr::from(
+                    u128::from(*i)
+                        & u128::MAX
+                            .checked_shl((IPV6_ADDR_LEN - prefix_length) as u32)
+                            .unwrap_or(0),
+                )
+                .into()
+            }
+        }
+    }
+
+    // Whether the subnets of two IP addresses overlap, which means the
+    // address of longer prefix length is in the network of the other.
+    pub(crate) fn is_subnet_overlap(&self, other: &Self) -> bool {
+        if self.prefix_length <= other.prefix_length {
+            self.contains(&other.ip)
+        } else {
+            other.contains(&self.ip)
+        }
+    }
+
+    // Prefix length capped to the address length of the IP family
+    fn capped_prefix_length(&self) -> usize {
+        let max_len = if self.ip.is_ipv6() {
+            IPV6_ADDR_LEN
+        } else {
+            IPV4_ADDR_LEN
+        };
+        usize::from(self.prefix_length).min(max_len)
+    }
+
+    /// Network this IP address belongs to with host bits cleared, for
+    /// example `192.0.2.0/24` for `192.0.2.1/24`.
+    pub fn network(&self) -> IpNetwork {
+        let prefix_len = self.capped_prefix_length();
+        IpNetwork(format!(
+            "{}/{prefix_len}",
+            Self::mask_ip(&self.ip, prefix_len)
+        ))
+    }
+
+    /// Whether specified IP address belongs to the network of this IP
+    /// address. Always false for IP address of different family.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        is_ip_in_network(ip, &self.ip, self.prefix_length)
+    }
+
+    /// Broadcast address of the IPv4 network this IP address belongs to.
+    /// Return None for IPv6 address or IPv4 address with prefix length 31
+    /// (RFC 3021) or 32 which have no broadcast address.
+    pub fn broadcast(&self) -> Option<Ipv4Addr> {
+        match self.ip {
+            IpAddr::V4(ip) if self.capped_prefix_length() < 31 => {
+                let host_mask = u32::MAX >> self.capped_prefix_length() as u32;
+                Some(Ipv4Addr::from(u32::from(ip) | host_mask))
+            }
+            _ => None,
+        }
+    }
+
+    /// Iterate all host IP addresses of the network this IP address belongs
+    /// to, in ascending order, with the same prefix length. For IPv4 network
+    /// with prefix length smaller than 31, the network and broadcast
+    /// addresses are excluded.
+    /// Useful for assigning addresses from a pool, for example:
+    /// ```rust
+    /// use nmstate::InterfaceIpAddr;
+    ///
+    /// let pool = InterfaceIpAddr::try_from("192.0.2.0/30").unwrap();
+    /// let addrs: Vec<String> =
+    ///     pool.hosts().map(|ip| ip.to_string()).collect();
+    /// assert_eq!(addrs, vec!["192.0.2.1/30", "192.0.2.2/30"]);
+    /// ```
+    pub fn hosts(&self) -> InterfaceIpAddrHosts {
+        let prefix_len = self.capped_prefix_length();
+        let (start, end) = match self.ip {
+            IpAddr::V4(ip) => {
+                let host_mask =
+                    u32::MAX.checked_shr(prefix_len as u32).unwrap_or(0);
+                let net = u32::from(ip) & !host_mask;
+                let bcast = net | host_mask;
+                if prefix_len < 31 {
+                    (u128::from(net) + 1, u128::from(bcast) - 1)
+                } else {
+                    (u128::from(net), u128::from(bcast))
+                }
+            }
+            IpAddr::V6(ip) => {
+                let host_mask =
+                    u128::MAX.checked_shr(prefix_len as u32).unwrap_or(0);
+                let net = u128::from(ip) & !host_mask;
+                (net, net | host_mask)
+            }
+        };
+        InterfaceIpAddrHosts {
+            is_ipv6: self.ip.is_ipv6(),
+            prefix_length: prefix_len as u8,
+            next: Some(start),
+            end,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+/// Iterator of host IP addresses in a network, created by
+/// [InterfaceIpAddr::hosts()].
+pub struct InterfaceIpAddrHosts {
+    is_ipv6: bool,
+    prefix_length: u8,
+    next: Option<u128>,
+    end: u128,
+}
+
+impl Iterator for InterfaceIpAddrHosts {
+    type Item = InterfaceIpAddr;
+
+    fn next(&mut self) -> Option<InterfaceIpAddr> {
+        let cur = self.next.filter(|c| *c <= self.end)?;
+        self.next = cur.checked_add(1);
+        let ip = if self.is_ipv6 {
+            IpAddr::V6(Ipv6Addr::from(cur))
+        } else {
+            IpAddr::V4(Ipv4Addr::from(cur as u32))
+        };
+        Some(InterfaceIpAddr {
+            ip,
+            prefix_length: self.prefix_length,
+            ..Default::default()
+        })
+    }
 }
 
 pub(crate) fn is_ipv6_addr(addr: &str) -> bool {
@@ -1033,9 +1497,125 @@ impl std::convert::TryFrom<&str> for InterfaceIpAddr {
     }
 }
 
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    Default,
+    Serialize,
+    Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+/// IP network in the format of `ip/prefix_length` with host bits cleared, for
+/// example `192.0.2.0/24` or `2001:db8:1::/64`.
+/// When deserializing, IP address without prefix length is treated as host
+/// network(`/32` for IPv4 and `/128` for IPv6) and host bits are cleared.
+/// The default value is an empty network which is only used by
+/// [crate::RouteRuleEntry] to match route rules without `ip-from` or `ip-to`.
+pub struct IpNetwork(String);
+
+impl IpNetwork {
+    /// IP network string in the format of `ip/prefix_length`.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Network address. None for the empty network.
+    pub fn ip(&self) -> Option<IpAddr> {
+        self.0
+            .split('/')
+            .next()
+            .and_then(|i| IpAddr::from_str(i).ok())
+    }
+
+    /// Prefix length of the network.
+    pub fn prefix_length(&self) -> u8 {
+        self.0
+            .split('/')
+            .nth(1)
+            .and_then(|p| p.parse::<u8>().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_ipv6(&self) -> bool {
+        is_ipv6_addr(self.0.as_str())
+    }
+
+    /// Whether this is the empty network.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Whether specified IP address belongs to this network.
+    pub fn contains(&self, ip: &IpAddr) -> bool {
+        self.ip()
+            .map(|net_ip| is_ip_in_network(ip, &net_ip, self.prefix_length()))
+            .unwrap_or_default()
+    }
+}
+
+// Whether the IP address belongs to network defined by `net_ip` and
+// `prefix_len`.
+pub(crate) fn is_ip_in_network(
+    ip: &IpAddr,
+    net_ip: &IpAddr,
+    prefix_len: u8,
+) -> bool {
+    ip.is_ipv6() == net_ip.is_ipv6()
+        && InterfaceIpAddr::mask_ip(ip, prefix_len.into())
+            == InterfaceIpAddr::mask_ip(net_ip, prefix_len.into())
+}
+
+impl std::convert::TryFrom<&str> for IpNetwork {
+    type Error = NmstateError;
+    fn try_from(ip_net: &str) -> Result<Self, Self::Error> {
+        let new_ip_net = sanitize_ip_network(ip_net)?;
+        if ip_net != new_ip_net {
+            nmstate_warn!("IP network {} sanitized to {}", ip_net, new_ip_net);
+        }
+        Ok(Self(new_ip_net))
+    }
+}
+
+impl std::convert::TryFrom<String> for IpNetwork {
+    type Error = NmstateError;
+    fn try_from(ip_net: String) -> Result<Self, Self::Error> {
+        Self::try_from(ip_net.as_str())
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = NmstateError;
+    fn from_str(ip_net: &str) -> Result<Self, Self::Err> {
+        Self::try_from(ip_net)
+    }
+}
+
+impl From<IpNetwork> for String {
+    fn from(ip_net: IpNetwork) -> Self {
+        ip_net.0
+    }
+}
+
+impl std::fmt::Display for IpNetwork {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for IpNetwork {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
-#[serde(from = "String", into = "String")]
+#[serde(try_from = "String", into = "String")]
 /// DHCPv4 client ID
 pub enum Dhcpv4ClientId {
     /// Use link layer address as DHCPv4 client ID.
@@ -1044,7 +1624,12 @@ pub enum Dhcpv4ClientId {
     /// RFC 4361 type 255, 32 bits IAID followed by DUID.
     /// Serialize and deserialize to/from `iaid+duid`.
     IaidPlusDuid,
-    /// hex string or backend specific client id type
+    /// Raw client ID including the leading type byte, 2 to 255 bytes.
+    /// Serialize and deserialize to/from colon separated hex string, for
+    /// example `01:00:23:45:67:89:ab`.
+    Hex(Vec<u8>),
+    /// Backend specific client id type, for example `stable` of
+    /// NetworkManager.
     Other(String),
 }
 
@@ -1054,13 +1639,21 @@ impl std::fmt::Display for Dhcpv4ClientId {
     }
 }
 
-impl From<String> for Dhcpv4ClientId {
-    fn from(s: String) -> Self {
-        return match s.as_str() {
+impl std::convert::TryFrom<String> for Dhcpv4ClientId {
+    type Error = NmstateError;
+    fn try_from(s: String) -> Result<Self, NmstateError> {
+        Ok(match s.as_str() {
             "ll" | "LL" => Self::LinkLayerAddress,
             "iaid+duid" | "IAID+DUID" => Self::IaidPlusDuid,
-            _ => Self::Other(s),
-        };
+            _ => match parse_dhcp_hex_id(
+                "DHCPv4 client ID",
+                s.as_str(),
+                DHCPV4_CLIENT_ID_LEN_RANGE,
+            )? {
+                Some(raw) => Self::Hex(raw),
+                None => Self::Other(s),
+            },
+        })
     }
 }
 
@@ -1069,6 +1662,7 @@ impl From<Dhcpv4ClientId> for String {
         match v {
             Dhcpv4ClientId::LinkLayerAddress => "ll".to_string(),
             Dhcpv4ClientId::IaidPlusDuid => "iaid+duid".to_string(),
+            Dhcpv4ClientId::Hex(raw) => bytes_to_hex_string(raw.as_slice()),
             Dhcpv4ClientId::Other(s) => s,
         }
     }
@@ -1076,7 +1670,7 @@ impl From<Dhcpv4ClientId> for String {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
-#[serde(from = "String", into = "String")]
+#[serde(try_from = "String", into = "String")]
 /// DHCPv6 Unique Identifier
 pub enum Dhcpv6Duid {
     /// DUID Based on Link-Layer Address Plus Time
@@ -1091,7 +1685,11 @@ pub enum Dhcpv6Duid {
     /// DUID Based on Universally Unique Identifier
     /// Serialize and deserialize to/from `uuid`.
     Uuid,
-    /// Backend specific
+    /// Raw DUID including the leading 2 bytes DUID type, 3 to 130 bytes.
+    /// Serialize and deserialize to/from colon separated hex string, for
+    /// example `00:04:ec:b1:6d:80:a6:11:47:b2:a7:3c:37:06:47:f5:a7:55`.
+    Hex(Vec<u8>),
+    /// Backend specific, for example `stable-llt` of NetworkManager.
     Other(String),
 }
 
@@ -1101,15 +1699,23 @@ impl std::fmt::Display for Dhcpv6Duid {
     }
 }
 
-impl From<String> for Dhcpv6Duid {
-    fn from(s: String) -> Self {
-        return match s.as_str() {
+impl std::convert::TryFrom<String> for Dhcpv6Duid {
+    type Error = NmstateError;
+    fn try_from(s: String) -> Result<Self, NmstateError> {
+        Ok(match s.as_str() {
             "llt" | "LLT" => Self::LinkLayerAddressPlusTime,
             "en" | "EN" => Self::EnterpriseNumber,
             "ll" | "LL" => Self::LinkLayerAddress,
             "uuid" | "UUID" => Self::Uuid,
-            _ => Self::Other(s),
-        };
+            _ => match parse_dhcp_hex_id(
+                "DHCPv6 DUID",
+                s.as_str(),
+                DHCPV6_DUID_LEN_RANGE,
+            )? {
+                Some(raw) => Self::Hex(raw),
+                None => Self::Other(s),
+            },
+        })
     }
 }
 
@@ -1120,11 +1726,118 @@ impl From<Dhcpv6Duid> for String {
             Dhcpv6Duid::EnterpriseNumber => "en".to_string(),
             Dhcpv6Duid::LinkLayerAddress => "ll".to_string(),
             Dhcpv6Duid::Uuid => "uuid".to_string(),
+            Dhcpv6Duid::Hex(raw) => bytes_to_hex_string(raw.as_slice()),
             Dhcpv6Duid::Other(s) => s,
         }
     }
 }
 
+// RFC 2132: the client identifier holds type byte and at least one byte of
+// data.
+const DHCPV4_CLIENT_ID_LEN_RANGE: RangeInclusive<usize> = 2..=255;
+// RFC 8415: DUID holds 2 bytes type and up to 128 bytes of data.
+const DHCPV6_DUID_LEN_RANGE: RangeInclusive<usize> = 3..=130;
+
+// Parse colon separated hex string into bytes.
+// Return `None` for backend specific keyword like `stable`.
+fn parse_dhcp_hex_id(
+    id_type: &str,
+    s: &str,
+    len_range: RangeInclusive<usize>,
+) -> Result<Option<Vec<u8>>, NmstateError> {
+    if !s.contains(':') {
+        if !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '+')
+        {
+            return Ok(None);
+        }
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Invalid {id_type} '{s}': should be colon separated hex \
+                string or keyword"
+            ),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    let mut raw = Vec::new();
+    for byte_str in s.split(':') {
+        match u8::from_str_radix(byte_str, 16) {
+            Ok(b) if byte_str.len() == 2 => raw.push(b),
+            _ => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid {id_type} '{s}': '{byte_str}' is not a \
+                        two digits hex byte"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+    }
+    if !len_range.contains(&raw.len()) {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "Invalid {id_type} '{s}': should be {} to {} bytes, \
+                but got {} bytes",
+                len_range.start(),
+                len_range.end(),
+                raw.len()
+            ),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(Some(raw))
+}
+
+fn bytes_to_hex_string(raw: &[u8]) -> String {
+    raw.iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<Vec<String>>()
+        .join(":")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// How DHCPv6 works along with IPv6 router announcement(RA).
+pub enum Dhcpv6Mode {
+    /// IPv6 autoconf enabled, DHCPv6 is started when RA has managed or
+    /// other-config flag set.
+    /// Equal to `dhcp: true` and `autoconf: true`.
+    /// Serialize and deserialize to/from `ra-triggered`.
+    RaTriggered,
+    /// IPv6 autoconf enabled for address, stateless DHCPv6 is always started
+    /// for other configurations like DNS regardless of RA flags.
+    /// Not supported by NetworkManager backend yet.
+    /// Serialize and deserialize to/from `stateless`.
+    Stateless,
+    /// DHCPv6 is started without waiting RA, IPv6 autoconf disabled.
+    /// Equal to `dhcp: true` and `autoconf: false`.
+    /// Serialize and deserialize to/from `no-ra`.
+    NoRa,
+}
+
+impl std::fmt::Display for Dhcpv6Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::RaTriggered => "ra-triggered",
+                Self::Stateless => "stateless",
+                Self::NoRa => "no-ra",
+            }
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[non_exhaustive]
 #[serde(from = "String", into = "String")]
@@ -1181,6 +1894,17 @@ pub enum WaitIp {
     /// Serialize and deserialize to/from `ipv4+ipv6`.
     #[serde(rename = "ipv4+ipv6")]
     Ipv4AndIpv6,
+    /// The activation is considered done once IPv4 stack or IPv6 stack is
+    /// configured and the other stack is also configured or failed to
+    /// configure within [BaseInterface.wait_ip_timeout] seconds.
+    /// Serialize and deserialize to/from `any-with-grace`.
+    AnyWithGrace,
+}
+
+impl WaitIp {
+    /// Default seconds to wait for the other IP stack in
+    /// [WaitIp::AnyWithGrace] mode.
+    pub const DEFAULT_GRACE_TIMEOUT: u32 = 30;
 }
 
 impl std::fmt::Display for WaitIp {
@@ -1193,11 +1917,60 @@ impl std::fmt::Display for WaitIp {
                 Self::Ipv4 => "ipv4",
                 Self::Ipv6 => "ipv6",
                 Self::Ipv4AndIpv6 => "ipv4+ipv6",
+                Self::AnyWithGrace => "any-with-grace",
             }
         )
     }
 }
 
+// With `wait-ip: any-with-grace`, NetworkManager considers activation done
+// even when one IP stack failed to get address within grace timeout. Log
+// it so user could know about it.
+pub(crate) fn warn_wait_ip_missing_family(
+    des_iface: &BaseInterface,
+    cur_iface: &BaseInterface,
+) {
+    if des_iface.wait_ip != Some(WaitIp::AnyWithGrace) {
+        return;
+    }
+    if let Some(ipv4) = cur_iface.ipv4.as_ref() {
+        if ipv4.enabled
+            && ipv4
+                .addresses
+                .as_ref()
+                .map(|a| a.is_empty())
+                .unwrap_or(true)
+        {
+            nmstate_warn!(
+                "Interface {} has no IPv4 address after activation with \
+                'wait-ip: {}'",
+                cur_iface.name,
+                WaitIp::AnyWithGrace
+            );
+        }
+    }
+    if let Some(ipv6) = cur_iface.ipv6.as_ref() {
+        if ipv6.enabled
+            && !ipv6
+                .addresses
+                .as_deref()
+                .unwrap_or_default()
+                .iter()
+                .any(|a| match a.ip {
+                    IpAddr::V6(ip) => !is_ipv6_unicast_link_local(&ip),
+                    IpAddr::V4(_) => false,
+                })
+        {
+            nmstate_warn!(
+                "Interface {} has no global IPv6 address after activation \
+                with 'wait-ip: {}'",
+                cur_iface.name,
+                WaitIp::AnyWithGrace
+            );
+        }
+    }
+}
+
 fn validate_wait_ip(base_iface: &BaseInterface) -> Result<(), NmstateError> {
     if let Some(wait_ip) = base_iface.wait_ip.as_ref() {
         if (wait_ip == &WaitIp::Ipv4 || wait_ip == &WaitIp::Ipv4AndIpv6)
@@ -1237,6 +2010,25 @@ fn validate_wait_ip(base_iface: &BaseInterface) -> Result<(), NmstateError> {
             return Err(e);
         }
     }
+    if base_iface.wait_ip_timeout.is_some()
+        && !matches!(
+            base_iface.wait_ip,
+            Some(WaitIp::Ipv4)
+                | Some(WaitIp::Ipv6)
+                | Some(WaitIp::AnyWithGrace)
+        )
+    {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "The 'wait-ip-timeout' is only valid with 'wait-ip' set to \
+                ipv4, ipv6 or any-with-grace. Interface: {}({})",
+                &base_iface.name, &base_iface.iface_type
+            ),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
 
     Ok(())
 }
@@ -1261,6 +2053,9 @@ fn get_ip_prop_list(
     if map.contains_key("dhcp-duid") {
         ret.push("dhcp_duid")
     }
+    if map.contains_key("dhcp-mode") {
+        ret.push("dhcp_mode")
+    }
     if map.contains_key("address") {
         ret.push("addresses")
     }
@@ -1285,6 +2080,18 @@ fn get_ip_prop_list(
     if map.contains_key("dhcp-custom-hostname") {
         ret.push("dhcp_custom_hostname")
     }
+    if map.contains_key("dhcp-send-release") {
+        ret.push("dhcp_send_release")
+    }
+    if map.contains_key("dad-timeout") {
+        ret.push("dad_timeout")
+    }
+    if map.contains_key("address-conflict") {
+        ret.push("address_conflict")
+    }
+    if map.contains_key("router-advertisement") {
+        ret.push("ra")
+    }
     ret
 }
 
@@ -1321,8 +2128,10 @@ pub(crate) fn sanitize_ip_network(
                     ),
                 )
             })?;
-            let ip =
-                apply_ip_prefix_len(IpAddr::from_str(ip_nets[0])?, prefix_len);
+            let ip = InterfaceIpAddr::mask_ip(
+                &IpAddr::from_str(ip_nets[0])?,
+                prefix_len,
+            );
             if ip.is_ipv6() {
                 if prefix_len > IPV6_ADDR_LEN {
                     Err(NmstateError::new(
@@ -1448,6 +2257,8 @@ impl MergedInterface {
             if !apply_iface.can_have_ip() {
                 apply_iface.wait_ip = None;
                 verify_iface.wait_ip = None;
+                apply_iface.wait_ip_timeout = None;
+                verify_iface.wait_ip_timeout = None;
             }
         }
 
@@ -1513,30 +2324,3 @@ fn is_ip_addrs_none_or_all_auto(addrs: Option<&[InterfaceIpAddr]>) -> bool {
         })
     })
 }
-
-fn apply_ip_prefix_len(ip: IpAddr, prefix_length: usize) -> IpAddr {
-    if prefix_length == 0 {
-        return if ip.is_ipv6() {
-            IpAddr::V6(0.into())
-        } else {
-            IpAddr::V4(0.into())
-        };
-    }
-
-    if (ip.is_ipv6() && prefix_length >= IPV6_ADDR_LEN)
-        || (ip.is_ipv4() && prefix_length >= IPV4_ADDR_LEN)
-    {
-        return ip;
-    }
-
-    match ip {
-        IpAddr::V6(i) => Ipv6Addr::from(
-            u128::from(i) & (u128::MAX << (IPV6_ADDR_LEN - prefix_length)),
-        )
-        .into(),
-        IpAddr::V4(i) => Ipv4Addr::from(
-            u32::from(i) & (u32::MAX << (IPV4_ADDR_LEN - prefix_length)),
-        )
-        .into(),
-    }
-}
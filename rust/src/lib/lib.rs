@@ -83,23 +83,38 @@
 //! }
 //! ```
 
+#[cfg(feature = "query_apply")]
+mod capability;
+mod connectivity;
+mod defaults;
 mod deserializer;
 mod dispatch;
 mod dns;
+#[cfg(feature = "gen_conf")]
+mod dracut;
 mod error;
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
 mod hostname;
+#[cfg(feature = "query_apply")]
+mod hostnamed;
 mod ieee8021x;
 mod iface;
+mod iface_plugin;
 mod ifaces;
 mod ip;
 mod lldp;
+mod mac;
+mod merge;
+mod migrate;
 mod mptcp;
 mod net_state;
+#[cfg(feature = "gen_conf")]
+mod networkd;
 #[cfg(feature = "query_apply")]
 mod nispor;
 mod nm;
+mod nm_passthrough;
 #[allow(deprecated)]
 mod ovn;
 mod ovs;
@@ -113,59 +128,80 @@ mod query_apply;
 mod revert;
 mod route;
 mod route_rule;
+mod routing_summary;
+mod secret;
 mod serializer;
 mod state;
 #[cfg(feature = "query_apply")]
 mod statistic;
 mod unit_tests;
+mod unknown_field;
+mod warning;
 
+#[cfg(feature = "query_apply")]
+pub use crate::capability::NmstateCapabilities;
+pub use crate::connectivity::ConnectivityCheckConfig;
+pub(crate) use crate::connectivity::MergedConnectivityCheckConfig;
+pub use crate::defaults::NetworkStateDefaults;
 pub use crate::dispatch::DispatchConfig;
 pub(crate) use crate::dns::MergedDnsState;
-pub use crate::dns::{DnsClientState, DnsState};
+pub use crate::dns::{DnsClientState, DnsState, DnsVerifyOrderMode};
 pub use crate::error::{ErrorKind, NmstateError};
+#[cfg(feature = "gen_conf")]
+pub use crate::gen_conf::NmKeyfile;
 pub use crate::hostname::HostNameState;
 pub(crate) use crate::hostname::MergedHostNameState;
 pub use crate::ieee8021x::Ieee8021XConfig;
 pub(crate) use crate::iface::MergedInterface;
 pub use crate::iface::{
-    Interface, InterfaceIdentifier, InterfaceState, InterfaceType,
-    UnknownInterface,
+    Interface, InterfaceBackend, InterfaceIdentifier, InterfaceManagedBy,
+    InterfaceState, InterfaceType, UnknownInterface, UnsupportedInterface,
 };
+pub use crate::iface_plugin::NmstateInterfacePlugin;
 pub(crate) use crate::ifaces::MergedInterfaces;
 pub use crate::ifaces::{
-    BaseInterface, BondAdSelect, BondAllPortsActive, BondArpAllTargets,
-    BondArpValidate, BondConfig, BondFailOverMac, BondInterface, BondLacpRate,
-    BondMode, BondOptions, BondPortConfig, BondPrimaryReselect,
-    BondXmitHashPolicy, BridgePortTrunkTag, BridgePortVlanConfig,
-    BridgePortVlanMode, BridgePortVlanRange, DummyInterface, EthernetConfig,
-    EthernetDuplex, EthernetInterface, EthtoolCoalesceConfig, EthtoolConfig,
-    EthtoolFeatureConfig, EthtoolPauseConfig, EthtoolRingConfig,
-    InfiniBandConfig, InfiniBandInterface, InfiniBandMode, Interfaces,
-    IpsecInterface, LibreswanConfig, LinuxBridgeConfig, LinuxBridgeInterface,
-    LinuxBridgeMulticastRouterType, LinuxBridgeOptions, LinuxBridgePortConfig,
-    LinuxBridgeStpOptions, LoopbackInterface, MacSecConfig, MacSecInterface,
+    BaseInterface, BatmanConfig, BatmanInterface, BatmanRoutingAlgorithm,
+    BondAdSelect, BondAllPortsActive, BondArpAllTargets, BondArpValidate,
+    BondConfig, BondFailOverMac, BondInterface, BondLacpRate, BondMode,
+    BondOptions, BondPortConfig, BondPrimaryReselect, BondXmitHashPolicy,
+    BridgePortTrunkTag, BridgePortVlanConfig, BridgePortVlanMode,
+    BridgePortVlanRange, CanConfig, CanInterface, CustomInterface,
+    DummyInterface, EthernetConfig, EthernetDuplex, EthernetInterface,
+    EthtoolCoalesceConfig, EthtoolConfig, EthtoolFeatureConfig,
+    EthtoolPauseConfig, EthtoolRingConfig, GreConfig, GreInterface,
+    GretapInterface, IfbInterface, InfiniBandConfig, InfiniBandInterface,
+    InfiniBandMode, Interfaces, Ip6GreInterface, Ip6GretapInterface,
+    Ip6TnlInterface, Ip6TnlMode, Ip6TunnelConfig, IpVlanConfig,
+    IpVlanInterface, IpVlanMode, IpsecInterface, LibreswanConfig,
+    LinuxBridgeConfig, LinuxBridgeInterface, LinuxBridgeMulticastRouterType,
+    LinuxBridgeOptions, LinuxBridgePortConfig, LinuxBridgeStpOptions,
+    LoopbackInterface, MacSecConfig, MacSecInterface, MacSecKeyConfig,
     MacSecValidate, MacVlanConfig, MacVlanInterface, MacVlanMode,
-    MacVtapConfig, MacVtapInterface, MacVtapMode, OvsBridgeBondConfig,
-    OvsBridgeBondMode, OvsBridgeBondPortConfig, OvsBridgeConfig,
-    OvsBridgeInterface, OvsBridgeOptions, OvsBridgePortConfig,
+    MacVtapConfig, MacVtapInterface, MacVtapMode, ModemConfig, ModemInterface,
+    OvsBridgeBondConfig, OvsBridgeBondMode, OvsBridgeBondPortConfig,
+    OvsBridgeConfig, OvsBridgeInterface, OvsBridgeOptions, OvsBridgePortConfig,
     OvsBridgeStpOptions, OvsDpdkConfig, OvsInterface, OvsPatchConfig,
-    SrIovConfig, SrIovVfConfig, VethConfig, VlanConfig, VlanInterface,
-    VlanProtocol, VrfConfig, VrfInterface, VxlanConfig, VxlanInterface,
+    SrIovConfig, SrIovVfConfig, TeamConfig, TeamInterface, TeamRunner,
+    VethConfig, VlanConfig, VlanInterface, VlanProtocol, VrfConfig,
+    VrfInterface, VxlanConfig, VxlanInterface,
 };
 pub use crate::ip::{
-    AddressFamily, Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpAddr, InterfaceIpv4,
-    InterfaceIpv6, Ipv6AddrGenMode, WaitIp,
+    AddressFamily, Dhcpv4ClientId, Dhcpv6Duid, Dhcpv6Mode, InterfaceIpAddr,
+    InterfaceIpAddrHosts, InterfaceIpv4, InterfaceIpv6, IpNetwork,
+    Ipv6AddrGenMode, Ipv6RaDnssl, Ipv6RaInfo, Ipv6RaRdnss, Ipv6RaRoute, WaitIp,
 };
 pub use crate::lldp::{
     LldpAddressFamily, LldpChassisId, LldpChassisIdType, LldpConfig,
     LldpMacPhyConf, LldpMaxFrameSize, LldpMgmtAddr, LldpMgmtAddrs,
-    LldpNeighborTlv, LldpPortId, LldpPortIdType, LldpPpvids,
-    LldpSystemCapabilities, LldpSystemCapability, LldpSystemDescription,
-    LldpSystemName, LldpVlan, LldpVlans,
+    LldpNeighborTlv, LldpNeighborsChange, LldpPortId, LldpPortIdType,
+    LldpPpvids, LldpSystemCapabilities, LldpSystemCapability,
+    LldpSystemDescription, LldpSystemName, LldpVlan, LldpVlans,
 };
+pub use crate::mac::MacAddress;
 pub use crate::mptcp::{MptcpAddressFlag, MptcpConfig};
 pub(crate) use crate::net_state::MergedNetworkState;
 pub use crate::net_state::NetworkState;
+pub use crate::nm_passthrough::NmPassthroughConfig;
 pub(crate) use crate::ovn::MergedOvnConfiguration;
 pub use crate::ovn::{
     OvnBridgeMapping, OvnBridgeMappingState, OvnConfiguration,
@@ -176,11 +212,19 @@ pub use crate::ovs::{OvsDbGlobalConfig, OvsDbIfaceConfig};
 pub use crate::policy::{
     NetworkCaptureRules, NetworkPolicy, NetworkStateTemplate,
 };
+#[cfg(feature = "query_apply")]
+pub use crate::query_apply::logger::{NmstateLogEntry, NmstateLogger};
+#[cfg(feature = "query_apply")]
+pub use crate::query_apply::stale_profile::NmstateStaleProfile;
 pub(crate) use crate::route::MergedRoutes;
 pub use crate::route::{RouteEntry, RouteState, RouteType, Routes};
 pub(crate) use crate::route_rule::MergedRouteRules;
 pub use crate::route_rule::{
     RouteRuleAction, RouteRuleEntry, RouteRuleState, RouteRules,
 };
+pub use crate::routing_summary::{RoutingSummary, RoutingSummaryGateway};
+pub use crate::secret::{NmstateSecretProvider, Secret};
 #[cfg(feature = "query_apply")]
 pub use crate::statistic::{NmstateFeature, NmstateStatistic};
+pub use crate::unknown_field::UnknownFieldMode;
+pub use crate::warning::NmstateWarning;
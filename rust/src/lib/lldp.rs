@@ -88,6 +88,18 @@ impl LldpConfig {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// Latest LLDP neighbors of interface whose LLDP neighbors changed.
+/// Generated by `NetworkState::wait_lldp_neighbors_change()`.
+pub struct LldpNeighborsChange {
+    /// Interface name.
+    pub iface_name: String,
+    /// Latest LLDP neighbors, empty if all neighbors are gone.
+    pub neighbors: Vec<Vec<LldpNeighborTlv>>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 #[serde(untagged)]
 #[non_exhaustive]
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{ErrorKind, NmstateError};
+
+const ETHERNET_MAC_ADDRESS_LEN: usize = 6;
+const INFINIBAND_MAC_ADDRESS_LEN: usize = 20;
+
+#[derive(
+    Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+#[serde(try_from = "String", into = "String")]
+/// Hardware address(MAC address) of network interface.
+/// Stored in the format of upper case hex string separated by `:` on every two
+/// characters, for example `00:23:45:67:89:1A`. Case insensitive when
+/// deserializing.
+/// Both 6 bytes ethernet MAC address and 20 bytes InfiniBand hardware address
+/// are supported.
+pub struct MacAddress(String);
+
+impl MacAddress {
+    /// MAC address string in upper case.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// MAC address in bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.0
+            .split(':')
+            .filter_map(|b| u8::from_str_radix(b, 16).ok())
+            .collect()
+    }
+}
+
+impl TryFrom<&str> for MacAddress {
+    type Error = NmstateError;
+    fn try_from(mac: &str) -> Result<Self, Self::Error> {
+        let octets: Vec<&str> = mac.split(':').collect();
+        if (octets.len() != ETHERNET_MAC_ADDRESS_LEN
+            && octets.len() != INFINIBAND_MAC_ADDRESS_LEN)
+            || octets.iter().any(|o| {
+                o.len() != 2 || !o.chars().all(|c| c.is_ascii_hexdigit())
+            })
+        {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid MAC address '{mac}', expecting 6 bytes \
+                    ethernet MAC address or 20 bytes InfiniBand hardware \
+                    address in hex string separated by ':' on every two \
+                    characters, for example: 00:23:45:67:89:1A"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        Ok(Self(mac.to_ascii_uppercase()))
+    }
+}
+
+impl TryFrom<String> for MacAddress {
+    type Error = NmstateError;
+    fn try_from(mac: String) -> Result<Self, Self::Error> {
+        Self::try_from(mac.as_str())
+    }
+}
+
+impl FromStr for MacAddress {
+    type Err = NmstateError;
+    fn from_str(mac: &str) -> Result<Self, Self::Err> {
+        Self::try_from(mac)
+    }
+}
+
+impl From<MacAddress> for String {
+    fn from(mac: MacAddress) -> Self {
+        mac.0
+    }
+}
+
+impl std::fmt::Display for MacAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl AsRef<str> for MacAddress {
+    fn as_ref(&self) -> &str {
+        self.0.as_str()
+    }
+}
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{DnsClientState, DnsState, MergedDnsState};
+
+impl MergedDnsState {
+    pub(crate) fn generate_merged(&self) -> DnsState {
+        if self.is_changed() {
+            DnsState {
+                config: Some(DnsClientState {
+                    server: Some(self.servers.clone()),
+                    search: Some(self.searches.clone()),
+                    options: Some(self.options.clone()),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }
+        } else {
+            DnsState::new()
+        }
+    }
+}
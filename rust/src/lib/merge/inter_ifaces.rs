@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Interfaces, MergedInterfaces};
+
+impl MergedInterfaces {
+    pub(crate) fn generate_merged(&self) -> Interfaces {
+        let mut ret = Interfaces::default();
+        for iface in self.iter().filter_map(|i| i.for_apply.as_ref()) {
+            ret.push(iface.clone());
+        }
+        ret
+    }
+}
@@ -0,0 +1,8 @@
+// SPDX-License-Identifier: Apache-2.0
+
+mod dns;
+mod inter_ifaces;
+mod net_state;
+mod ovsdb;
+mod route;
+mod route_rule;
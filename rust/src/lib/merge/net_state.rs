@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedNetworkState, NetworkState, NmstateError};
+
+impl NetworkState {
+    /// Generate the network state nmstate will apply when using current
+    /// [NetworkState] as desired state on top of specified current network
+    /// state, without applying it.
+    /// Only changed parts are included:
+    ///  * Interfaces been desired or changed by desired state, in the form
+    ///    nmstate will apply.
+    ///  * Full route list of interfaces which have route changed.
+    ///  * Full route rule list when route rule changed.
+    ///  * Merged DNS and OVS database global configuration when changed.
+    ///  * Desired connectivity check configuration.
+    pub fn generate_merged(
+        &self,
+        current: &Self,
+    ) -> Result<Self, NmstateError> {
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            current.clone(),
+            false,
+            false,
+        )?;
+        Ok(Self {
+            interfaces: merged_state.interfaces.generate_merged(),
+            routes: merged_state.routes.generate_merged(),
+            rules: merged_state.rules.generate_merged(),
+            dns: merged_state.dns.generate_merged(),
+            ovsdb: merged_state.ovsdb.generate_merged(),
+            ovn: merged_state.ovn.desired.clone(),
+            connectivity_check: merged_state.connectivity_check.desired.clone(),
+            hostname: merged_state.hostname.desired.clone(),
+            prop_list: merged_state.prop_list,
+            ..Default::default()
+        })
+    }
+}
@@ -0,0 +1,27 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::{MergedOvsDbGlobalConfig, OvsDbGlobalConfig};
+
+impl MergedOvsDbGlobalConfig {
+    // The OVN bridge mappings are stored in `ovn` section.
+    pub(crate) fn generate_merged(&self) -> OvsDbGlobalConfig {
+        if !self.is_changed {
+            return OvsDbGlobalConfig::default();
+        }
+        let external_ids: HashMap<String, Option<String>> = self
+            .external_ids
+            .iter()
+            .filter(|(k, _)| {
+                k.as_str() != OvsDbGlobalConfig::OVN_BRIDGE_MAPPINGS_KEY
+            })
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect();
+        OvsDbGlobalConfig {
+            external_ids: Some(external_ids),
+            other_config: Some(self.other_config.clone()),
+            ..Default::default()
+        }
+    }
+}
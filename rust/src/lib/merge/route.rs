@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedRoutes, RouteEntry, Routes};
+
+impl MergedRoutes {
+    pub(crate) fn generate_merged(&self) -> Routes {
+        if !self.is_changed() {
+            return Routes::new();
+        }
+        let mut rts: Vec<RouteEntry> = self
+            .route_changed_ifaces
+            .iter()
+            .filter_map(|iface_name| self.indexed.get(iface_name))
+            .flatten()
+            .cloned()
+            .collect();
+        rts.sort_unstable();
+        Routes {
+            config: Some(rts),
+            ..Default::default()
+        }
+    }
+}
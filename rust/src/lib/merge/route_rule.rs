@@ -0,0 +1,15 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{MergedRouteRules, RouteRules};
+
+impl MergedRouteRules {
+    pub(crate) fn generate_merged(&self) -> RouteRules {
+        if self.is_changed() {
+            RouteRules {
+                config: Some(self.for_apply.clone()),
+            }
+        } else {
+            RouteRules::new()
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde_json::{Map, Value};
+
+use crate::warning::nmstate_warn;
+use crate::{ErrorKind, NmstateError};
+
+/// The schema version of [crate::NetworkState] supported by this nmstate.
+pub(crate) const NET_STATE_SCHEMA_VERSION: u32 = 2;
+
+// Deprecated property name in interface, the tuple is:
+//  * Parent section
+//  * Deprecated property name
+//  * Current property name
+const IFACE_DEPRECATED_KEYS: [(&str, &str, &str); 4] = [
+    ("link-aggregation", "slaves", "port"),
+    ("bridge", "slaves", "port"),
+    ("mac-vlan", "accept-all-mac", "promiscuous"),
+    ("mac-vtap", "accept-all-mac", "promiscuous"),
+];
+
+// Deprecated property name in route rule, the tuple is:
+//  * Deprecated property name
+//  * Current property name
+const ROUTE_RULE_DEPRECATED_KEYS: [(&str, &str); 1] =
+    [("suppress_prefixlength", "suppress-prefix-length")];
+
+pub(crate) fn get_schema_version(
+    v: &Map<String, Value>,
+) -> Result<Option<u32>, NmstateError> {
+    let version = match v.get("version") {
+        Some(Value::Number(n)) => n.as_u64(),
+        Some(Value::String(s)) => s.parse::<u64>().ok(),
+        Some(_) => None,
+        None => return Ok(None),
+    };
+    match version.and_then(|i| u32::try_from(i).ok()) {
+        Some(i) if i > 0 && i <= NET_STATE_SCHEMA_VERSION => Ok(Some(i)),
+        _ => {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Unsupported schema version {}, only support version \
+                    1 to {NET_STATE_SCHEMA_VERSION}",
+                    v.get("version").cloned().unwrap_or_default()
+                ),
+            );
+            log::error!("{}", e);
+            Err(e)
+        }
+    }
+}
+
+// The deprecated property names are still deserialized into current model
+// via serde aliases, this function only warns about each of them found in
+// the network state.
+pub(crate) fn warn_deprecated_keys(v: &Map<String, Value>) {
+    if let Some(ifaces) = v.get("interfaces").and_then(|i| i.as_array()) {
+        for iface in ifaces.iter().filter_map(|i| i.as_object()) {
+            let iface_name = iface
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or_default();
+            for (section, old_key, new_key) in IFACE_DEPRECATED_KEYS {
+                if iface
+                    .get(section)
+                    .and_then(|s| s.as_object())
+                    .map(|s| s.contains_key(old_key))
+                    .unwrap_or_default()
+                {
+                    nmstate_warn!(
+                        "The `{old_key}` of interface {iface_name} \
+                        {section} is deprecated, please use `{new_key}` \
+                        instead"
+                    );
+                }
+            }
+        }
+    }
+    if let Some(rules) = v
+        .get("route-rules")
+        .and_then(|r| r.get("config"))
+        .and_then(|c| c.as_array())
+    {
+        for rule in rules.iter().filter_map(|r| r.as_object()) {
+            for (old_key, new_key) in ROUTE_RULE_DEPRECATED_KEYS {
+                if rule.contains_key(old_key) {
+                    nmstate_warn!(
+                        "The `{old_key}` of route rule is deprecated, \
+                        please use `{new_key}` instead"
+                    );
+                }
+            }
+        }
+    }
+}
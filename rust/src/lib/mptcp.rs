@@ -5,13 +5,18 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::warning::nmstate_warn;
 use crate::{BaseInterface, ErrorKind, MergedInterface, NmstateError};
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
 #[non_exhaustive]
 pub struct MptcpConfig {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_vec_enum_ignore_case"
+    )]
     /// Automatically assign MPTCP flags to all valid IP addresses of this
     /// interface including both static and dynamic ones.
     pub address_flags: Option<Vec<MptcpAddressFlag>>,
@@ -107,7 +112,7 @@ fn validate_iface_mptcp_and_addr_mptcp_flags(iface: &BaseInterface) {
         if let Some(mut addr_flags) = ip_addr.mptcp_flags.as_ref().cloned() {
             addr_flags.sort_unstable();
             if iface_flags != addr_flags {
-                log::warn!(
+                nmstate_warn!(
                     "Nmstate does not support setting different \
                     MPTCP flags within the interface. Ignoring MPTCP \
                     flags {:?} of IP address {}/{} as it is different \
@@ -2,14 +2,32 @@
 
 #[cfg(not(feature = "gen_conf"))]
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
 
 use serde::{Deserialize, Deserializer, Serialize};
 
+use crate::iface_plugin::{IfacePluginHolder, NmstateInterfacePlugin};
+use crate::secret::{NmstateSecretProvider, SecretProviderHolder};
+use crate::serializer::{
+    lowercase_mac_addresses, net_state_value_to_stable_order,
+};
+use crate::unknown_field::{path_to_string, strip_unknown_properties, Schema};
+use crate::warning::{
+    nmstate_warn, start_warning_collection, stop_warning_collection,
+};
 use crate::{
-    DnsState, ErrorKind, HostNameState, Interface, Interfaces, MergedDnsState,
-    MergedHostNameState, MergedInterfaces, MergedOvnConfiguration,
-    MergedOvsDbGlobalConfig, MergedRouteRules, MergedRoutes, NmstateError,
-    OvnConfiguration, OvsDbGlobalConfig, RouteRules, Routes,
+    migrate::{
+        get_schema_version, warn_deprecated_keys, NET_STATE_SCHEMA_VERSION,
+    },
+    ConnectivityCheckConfig, DnsState, DnsVerifyOrderMode, ErrorKind,
+    HostNameState, Interface, Interfaces, MergedConnectivityCheckConfig,
+    MergedDnsState, MergedHostNameState, MergedInterfaces,
+    MergedOvnConfiguration, MergedOvsDbGlobalConfig, MergedRouteRules,
+    MergedRoutes, NetworkStateDefaults, NmstateError, NmstateWarning,
+    OvnConfiguration, OvsDbGlobalConfig, RouteRules, Routes, RoutingSummary,
+    UnknownFieldMode, UnsupportedInterface,
 };
 
 #[derive(Clone, Debug, Serialize, Default, PartialEq, Eq)]
@@ -77,6 +95,12 @@ use crate::{
 ///   other_config: {}
 /// ```
 pub struct NetworkState {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Schema version of this network state. Deprecated properties of older
+    /// schema version are still accepted with warning logged, and this
+    /// property is set to current schema version after deserializing.
+    /// Error will be raised for unknown schema version.
+    pub version: Option<u32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Hostname of current host.
     pub hostname: Option<HostNameState>,
@@ -102,6 +126,34 @@ pub struct NetworkState {
     #[serde(default, skip_serializing_if = "OvnConfiguration::is_none")]
     /// The OVN configuration in the system
     pub ovn: OvnConfiguration,
+    #[serde(
+        rename = "connectivity-check",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    /// Global connectivity check configuration of NetworkManager,
+    /// deserialize and serialize from/to `connectivity-check`.
+    pub connectivity_check: Option<ConnectivityCheckConfig>,
+    #[serde(
+        rename = "unsupported-interfaces",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    /// Query only information on network interfaces found in kernel but not
+    /// supported by nmstate, deserialize and serialize from/to
+    /// `unsupported-interfaces`. Ignored during apply.
+    pub unsupported_interfaces: Vec<UnsupportedInterface>,
+    #[serde(
+        rename = "routing-summary",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    /// Query only summary of running routes and route rules, deserialize and
+    /// serialize from/to `routing-summary`. Ignored during apply.
+    pub routing_summary: Option<RoutingSummary>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Default properties expanded into desired interfaces when applying.
+    pub defaults: Option<NetworkStateDefaults>,
     #[serde(skip)]
     // Contain a list of struct member name which is defined explicitly in
     // desire state instead of generated.
@@ -123,7 +175,41 @@ pub struct NetworkState {
     #[serde(skip)]
     pub(crate) running_config_only: bool,
     #[serde(skip)]
+    pub(crate) include_routing_summary: bool,
+    #[serde(skip)]
     pub(crate) memory_only: bool,
+    #[serde(skip)]
+    pub(crate) nm_dbus_address: Option<String>,
+    #[serde(skip)]
+    pub(crate) ovsdb_socket_path: Option<String>,
+    #[serde(skip)]
+    pub(crate) apply_lock_timeout: Option<u32>,
+    #[serde(skip)]
+    pub(crate) dns_verify_order_mode: DnsVerifyOrderMode,
+    #[serde(skip)]
+    pub(crate) ignored_route_rule_priorities: Vec<RangeInclusive<i64>>,
+    #[serde(skip)]
+    pub(crate) ignore_routes: bool,
+    #[serde(skip)]
+    pub(crate) ignore_route_rules: bool,
+    #[serde(skip)]
+    pub(crate) ignore_dns: bool,
+    #[serde(skip)]
+    pub(crate) protect_cni_owned_ovs: bool,
+    #[serde(skip)]
+    pub(crate) auto_absent_children: bool,
+    #[serde(skip)]
+    pub(crate) ovn_auto_create_bridges: bool,
+    #[serde(skip)]
+    pub(crate) strict: bool,
+    #[serde(skip)]
+    pub(crate) management_ip: Option<IpAddr>,
+    #[serde(skip)]
+    pub(crate) secret_provider: Option<SecretProviderHolder>,
+    #[serde(skip)]
+    pub(crate) secrets_agent_owned: bool,
+    #[serde(skip)]
+    pub(crate) iface_plugins: Vec<IfacePluginHolder>,
 }
 
 impl<'de> Deserialize<'de> for NetworkState {
@@ -141,6 +227,11 @@ impl<'de> Deserialize<'de> for NetworkState {
                 )));
             }
         };
+        get_schema_version(v).map_err(serde::de::Error::custom)?;
+        warn_deprecated_keys(v);
+        if v.remove("version").is_some() {
+            net_state.version = Some(NET_STATE_SCHEMA_VERSION);
+        }
         if let Some(ifaces_value) = v.remove("interfaces") {
             net_state.prop_list.push("interfaces");
             net_state.interfaces = Interfaces::deserialize(ifaces_value)
@@ -181,6 +272,30 @@ impl<'de> Deserialize<'de> for NetworkState {
                     .map_err(serde::de::Error::custom)?,
             );
         }
+        if let Some(connectivity_value) = v.remove("connectivity-check") {
+            net_state.prop_list.push("connectivity_check");
+            net_state.connectivity_check = Some(
+                ConnectivityCheckConfig::deserialize(connectivity_value)
+                    .map_err(serde::de::Error::custom)?,
+            );
+        }
+        if let Some(defaults_value) = v.remove("defaults") {
+            net_state.defaults = Some(
+                NetworkStateDefaults::deserialize(defaults_value)
+                    .map_err(serde::de::Error::custom)?,
+            );
+        }
+        if let Some(unsupported_value) = v.remove("unsupported-interfaces") {
+            net_state.unsupported_interfaces =
+                Vec::<UnsupportedInterface>::deserialize(unsupported_value)
+                    .map_err(serde::de::Error::custom)?;
+        }
+        if let Some(summary_value) = v.remove("routing-summary") {
+            net_state.routing_summary = Some(
+                RoutingSummary::deserialize(summary_value)
+                    .map_err(serde::de::Error::custom)?,
+            );
+        }
         if !v.is_empty() {
             Err(serde::de::Error::custom(format!(
                 "Unsupported keys found: {:?}",
@@ -201,6 +316,7 @@ impl NetworkState {
             && self.interfaces.is_empty()
             && self.ovsdb.is_none()
             && self.ovn.is_none()
+            && self.connectivity_check.is_none()
     }
 
     pub(crate) const PASSWORD_HID_BY_NMSTATE: &'static str =
@@ -244,7 +360,17 @@ impl NetworkState {
         self
     }
 
-    /// Whether to include secrets(like password) in [NetworkState::retrieve()]
+    /// Whether to include secrets in [NetworkState::retrieve()],
+    /// [NetworkState::retrieve_kernel_only()] and
+    /// [NetworkState::retrieve_config_only()]. The secrets are:
+    ///  * IEEE 802.1X `private-key-password`
+    ///  * MACsec `mka-cak`
+    ///  * IPsec libreswan `psk`
+    ///
+    /// When set to false, existing secrets are replaced by
+    /// `<_password_hid_by_nmstate>` which could be applied back to preserve
+    /// the secret stored in backend.
+    /// This is the library equivalent of `nmstatectl show --show-secrets`.
     /// Default is false.
     pub fn set_include_secrets(&mut self, value: bool) -> &mut Self {
         self.include_secrets = value;
@@ -267,6 +393,16 @@ impl NetworkState {
         self
     }
 
+    /// Whether to include [RoutingSummary] in [NetworkState::retrieve()],
+    /// holding per route table route counts, route rule count and default
+    /// gateways. Useful for monitoring host with large routing table without
+    /// processing full route list.
+    /// Default is false.
+    pub fn set_include_routing_summary(&mut self, value: bool) -> &mut Self {
+        self.include_routing_summary = value;
+        self
+    }
+
     /// When set to true, the network state be applied and only stored in memory
     /// which will be purged after system reboot.
     pub fn set_memory_only(&mut self, value: bool) -> &mut Self {
@@ -274,6 +410,253 @@ impl NetworkState {
         self
     }
 
+    /// Use specified D-Bus address to communicate with NetworkManager daemon
+    /// instead of system bus, for example
+    /// `unix:path=/host/run/dbus/system_bus_socket`. Useful when running in
+    /// container with host D-Bus socket mounted at non-standard path.
+    /// Not used by [NetworkState::checkpoint_rollback()] and
+    /// [NetworkState::checkpoint_commit()].
+    pub fn set_nm_dbus_address(&mut self, address: &str) -> &mut Self {
+        self.nm_dbus_address = Some(address.to_string());
+        self
+    }
+
+    /// Use specified unix socket path to communicate with OpenvSwitch
+    /// database instead of default `/run/openvswitch/db.sock`.
+    pub fn set_ovsdb_socket_path(&mut self, path: &str) -> &mut Self {
+        self.ovsdb_socket_path = Some(path.to_string());
+        self
+    }
+
+    /// The time in seconds to wait for other nmstate instance to finish its
+    /// apply action. [NetworkState::apply()] holds an inter-process lock
+    /// on `/run/nmstate.lock` to prevent multiple nmstate instances from
+    /// interleaving their applies. The checkpoint of `no commit` apply is
+    /// also waited till committed, rollbacked or expired. Default to 60
+    /// seconds.
+    pub fn set_apply_lock_timeout(&mut self, value: u32) -> &mut Self {
+        self.apply_lock_timeout = Some(value);
+        self
+    }
+
+    /// How the order of DNS name servers is verified after applying.
+    /// Default is [DnsVerifyOrderMode::Strict].
+    pub fn set_dns_verify_order_mode(
+        &mut self,
+        value: DnsVerifyOrderMode,
+    ) -> &mut Self {
+        self.dns_verify_order_mode = value;
+        self
+    }
+
+    /// Route rules with priority in specified ranges are treated as managed
+    /// by other tools(e.g. `wg-quick` or `systemd-networkd`). Nmstate will not
+    /// remove them even matched by absent route rule, and will not fail the
+    /// verification because of them.
+    /// Default is empty.
+    pub fn set_ignored_route_rule_priorities(
+        &mut self,
+        ranges: &[RangeInclusive<i64>],
+    ) -> &mut Self {
+        self.ignored_route_rule_priorities = ranges.to_vec();
+        self
+    }
+
+    /// Leave routes completely untouched when applying, for example when
+    /// routes are owned by other daemon like FRR. Desired routes will be
+    /// ignored with warning. Default is false.
+    pub fn set_ignore_routes(&mut self, value: bool) -> &mut Self {
+        self.ignore_routes = value;
+        self
+    }
+
+    /// Leave route rules completely untouched when applying. Desired route
+    /// rules will be ignored with warning. Default is false.
+    pub fn set_ignore_route_rules(&mut self, value: bool) -> &mut Self {
+        self.ignore_route_rules = value;
+        self
+    }
+
+    /// Leave DNS configuration completely untouched when applying. Desired
+    /// DNS configuration will be ignored with warning. Default is false.
+    pub fn set_ignore_dns(&mut self, value: bool) -> &mut Self {
+        self.ignore_dns = value;
+        self
+    }
+
+    /// Refuse changing OVS bridge or interface managed by CNI, for example
+    /// ovn-kubernetes, detected by the OVS external IDs. When set to true,
+    /// [ErrorKind::InvalidArgument] is raised on such change. Default is
+    /// false.
+    pub fn set_protect_cni_owned_ovs(&mut self, value: bool) -> &mut Self {
+        self.protect_cni_owned_ovs = value;
+        self
+    }
+
+    /// Mark child interfaces (e.g. VLAN, VXLAN, MACVLAN) found in current
+    /// state as absent when their parent interface is marked as absent in
+    /// desired state, recursively. Children explicitly defined in desired
+    /// state are untouched. Default is false which only remove children of
+    /// absent virtual interface.
+    pub fn set_auto_absent_children(&mut self, value: bool) -> &mut Self {
+        self.auto_absent_children = value;
+        self
+    }
+
+    /// Create OVS bridge for desired OVN bridge mapping referring to OVS
+    /// bridge not found in desired or current state. Default is false which
+    /// means raising [ErrorKind::InvalidArgument] on such bridge mapping.
+    pub fn set_ovn_auto_create_bridges(&mut self, value: bool) -> &mut Self {
+        self.ovn_auto_create_bridges = value;
+        self
+    }
+
+    /// Raise [ErrorKind::InvalidArgument] instead of warning on risky but
+    /// valid desired state, for example static IP addresses of different
+    /// interfaces in overlapping subnets. Default is false.
+    pub fn set_strict(&mut self, value: bool) -> &mut Self {
+        self.strict = value;
+        self
+    }
+
+    /// IP address of the management client, for example the SSH client
+    /// running nmstate remotely. When defined and current network state has
+    /// route to this IP, NetworkManager checkpoint is rolled back if the
+    /// route to this IP is gone after applying, even the verification of
+    /// desired state passed. Only supported by NetworkManager backend.
+    /// Default is None.
+    pub fn set_management_ip(&mut self, value: Option<IpAddr>) -> &mut Self {
+        self.management_ip = value;
+        self
+    }
+
+    /// Register secret provider consulted by NetworkManager backend during
+    /// activation for secrets not defined in desired state, for example
+    /// 802.1X private key password or MACsec CAK. The secrets remain system
+    /// owned unless [NetworkState::set_secrets_agent_owned()] is set.
+    pub fn set_secret_provider(
+        &mut self,
+        provider: Arc<dyn NmstateSecretProvider>,
+    ) -> &mut Self {
+        self.secret_provider = Some(SecretProviderHolder(provider));
+        self
+    }
+
+    /// When set to true along with [NetworkState::set_secret_provider()],
+    /// secrets not defined in desired state will be marked as agent owned,
+    /// so that NetworkManager never stores them in profiles but always
+    /// requests them from secret provider during activation.
+    /// Default is false.
+    pub fn set_secrets_agent_owned(&mut self, value: bool) -> &mut Self {
+        self.secrets_agent_owned = value;
+        self
+    }
+
+    /// Register plugin handling interfaces of type not supported by nmstate.
+    /// Desired interfaces of [NmstateInterfacePlugin::iface_type()] will be
+    /// applied by this plugin and interfaces retrieved by this plugin will be
+    /// included in [NetworkState::retrieve()].
+    /// Plugin of the same interface type registered previously is replaced.
+    pub fn register_iface_plugin(
+        &mut self,
+        plugin: Arc<dyn NmstateInterfacePlugin>,
+    ) -> &mut Self {
+        self.iface_plugins
+            .retain(|p| p.0.iface_type() != plugin.iface_type());
+        self.iface_plugins.push(IfacePluginHolder(plugin));
+        self
+    }
+
+    // Remove sections requested to be left untouched, so that they are
+    // neither applied nor verified.
+    fn remove_ignored_sections(&mut self) {
+        if self.ignore_routes && !self.routes.is_empty() {
+            nmstate_warn!("Ignoring desired routes as requested");
+            self.routes = Routes::new();
+            self.prop_list.retain(|p| p != &"routes");
+        }
+        if self.ignore_route_rules && !self.rules.is_empty() {
+            nmstate_warn!("Ignoring desired route rules as requested");
+            self.rules = RouteRules::new();
+            self.prop_list.retain(|p| p != &"rules");
+        }
+        if self.ignore_dns && !self.dns.is_empty() {
+            nmstate_warn!("Ignoring desired DNS configuration as requested");
+            self.dns = DnsState::new();
+            self.prop_list.retain(|p| p != &"dns");
+        }
+    }
+
+    /// Validate the desired state without touching the system and return its
+    /// sanitized copy along with the non-fatal warnings, for example IP
+    /// network sanitized to its prefix, deprecated or ignored properties.
+    /// The sanitized state could be stored in place of user input.
+    /// Warnings emitted during deserialization are not included.
+    pub fn sanitize(
+        &self,
+    ) -> Result<(NetworkState, Vec<NmstateWarning>), NmstateError> {
+        start_warning_collection();
+        let result = self.gen_sanitized();
+        let warnings = stop_warning_collection();
+        result.map(|s| (s, warnings))
+    }
+
+    /// Return the canonical form of desired state, so two semantically
+    /// equal states serialize identically. Besides [NetworkState::sanitize()]
+    /// which also sorts ports and normalizes IP addresses to RFC 5952 form,
+    /// this function sorts interfaces, routes and route rules and removes
+    /// duplicate IP addresses. The order of IP addresses is preserved as the
+    /// first one is the primary address. MAC addresses are kept in upper
+    /// case, use [NetworkState::to_canonical_yaml()] for serialized
+    /// canonical form with lower case MAC addresses.
+    pub fn canonicalize(&self) -> Result<NetworkState, NmstateError> {
+        let (mut ret, _) = self.sanitize()?;
+        for iface in ret.interfaces.iter_mut() {
+            let base_iface = iface.base_iface_mut();
+            if let Some(addrs) =
+                base_iface.ipv4.as_mut().and_then(|i| i.addresses.as_mut())
+            {
+                remove_duplicates(addrs);
+            }
+            if let Some(addrs) =
+                base_iface.ipv6.as_mut().and_then(|i| i.addresses.as_mut())
+            {
+                remove_duplicates(addrs);
+            }
+        }
+        if let Some(routes) = ret.routes.config.as_mut() {
+            routes.sort_unstable();
+        }
+        if let Some(rules) = ret.rules.config.as_mut() {
+            rules.sort_unstable();
+        }
+        Ok(ret)
+    }
+
+    fn gen_sanitized(&self) -> Result<NetworkState, NmstateError> {
+        // Validate the whole state without current state like gen_conf
+        MergedNetworkState::new(
+            self.clone(),
+            NetworkState::new(),
+            true,  // gen_conf mode
+            false, // memory only
+        )?;
+
+        let mut ret = self.clone();
+        ret.remove_ignored_sections();
+        for iface in ret.interfaces.iter_mut() {
+            iface.sanitize(true)?;
+        }
+        for route in ret.routes.config.as_deref_mut().unwrap_or_default() {
+            route.sanitize()?;
+        }
+        for rule in ret.rules.config.as_deref_mut().unwrap_or_default() {
+            rule.sanitize()?;
+        }
+        ret.dns.sanitize()?;
+        Ok(ret)
+    }
+
     /// Create empty [NetworkState]
     pub fn new() -> Self {
         Default::default()
@@ -303,6 +686,131 @@ impl NetworkState {
         }
     }
 
+    /// Like [NetworkState::new_from_json()] but with unknown property handling
+    /// controlled by [UnknownFieldMode]. The returned warnings hold the full
+    /// paths of unknown properties removed in [UnknownFieldMode::Warn] mode.
+    pub fn new_from_json_with_mode(
+        net_state_json: &str,
+        mode: UnknownFieldMode,
+    ) -> Result<(Self, Vec<NmstateWarning>), NmstateError> {
+        let v = match serde_json::from_str(net_state_json) {
+            Ok(v) => v,
+            Err(e) => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid JSON string: {e}"),
+                ));
+            }
+        };
+        Self::new_from_value_with_mode(v, mode).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid JSON string: {e}"),
+            )
+        })
+    }
+
+    /// Like [NetworkState::new_from_yaml()] but with unknown property handling
+    /// controlled by [UnknownFieldMode]. The returned warnings hold the full
+    /// paths of unknown properties removed in [UnknownFieldMode::Warn] mode.
+    pub fn new_from_yaml_with_mode(
+        net_state_yaml: &str,
+        mode: UnknownFieldMode,
+    ) -> Result<(Self, Vec<NmstateWarning>), NmstateError> {
+        let v = match serde_yaml::from_str(net_state_yaml) {
+            Ok(v) => v,
+            Err(e) => {
+                return Err(NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!("Invalid YAML string: {e}"),
+                ));
+            }
+        };
+        Self::new_from_value_with_mode(v, mode).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid YAML string: {e}"),
+            )
+        })
+    }
+
+    fn new_from_value_with_mode(
+        mut v: serde_json::Value,
+        mode: UnknownFieldMode,
+    ) -> Result<(Self, Vec<NmstateWarning>), serde_json::Error> {
+        start_warning_collection();
+        let result = match mode {
+            UnknownFieldMode::Reject => Self::deserialize(v),
+            UnknownFieldMode::Warn => {
+                for path in
+                    strip_unknown_properties(&mut v, &Schema::net_state())
+                {
+                    nmstate_warn!(
+                        "Ignoring unknown property {}",
+                        path_to_string(path.as_slice())
+                    );
+                }
+                Self::deserialize(v)
+            }
+        };
+        let warnings = stop_warning_collection();
+        result.map(|s| (s, warnings))
+    }
+
+    /// Serialize [NetworkState] into YAML string with stable property order
+    /// regardless of struct declaration order, so generated states stay
+    /// diffable across nmstate versions. The top level properties are
+    /// ordered as `hostname`, `dns-resolver`, `route-rules`, `routes`,
+    /// `interfaces`, `ovs-db`, `ovn`. Interface properties start with
+    /// `name`, `profile-name`, `description`, `type`, `state`, `identifier`,
+    /// `mac-address`, `mtu`, `controller`, `accept-all-mac-addresses`,
+    /// `ipv4`, `ipv6`, `mptcp`, `lldp`, `ethtool`. All other properties are
+    /// ordered alphabetically. The order of list items is untouched.
+    pub fn to_stable_yaml(&self) -> Result<String, NmstateError> {
+        serde_yaml::to_string(&self.to_stable_value()?).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to serialize NetworkState to YAML: {e}"),
+            )
+        })
+    }
+
+    /// Serialize [NetworkState] into pretty JSON string with the stable
+    /// property order described in [NetworkState::to_stable_yaml()].
+    pub fn to_stable_json(&self) -> Result<String, NmstateError> {
+        serde_json::to_string_pretty(&self.to_stable_value()?).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to serialize NetworkState to JSON: {e}"),
+            )
+        })
+    }
+
+    /// Serialize the canonical form generated by
+    /// [NetworkState::canonicalize()] into YAML string with the stable
+    /// property order described in [NetworkState::to_stable_yaml()] and
+    /// MAC addresses in lower case.
+    pub fn to_canonical_yaml(&self) -> Result<String, NmstateError> {
+        let mut value = self.canonicalize()?.to_stable_value()?;
+        lowercase_mac_addresses(&mut value);
+        serde_yaml::to_string(&value).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to serialize NetworkState to YAML: {e}"),
+            )
+        })
+    }
+
+    fn to_stable_value(&self) -> Result<serde_json::Value, NmstateError> {
+        let value = serde_json::to_value(self).map_err(|e| {
+            NmstateError::new(
+                ErrorKind::Bug,
+                format!("Failed to serialize NetworkState: {e}"),
+            )
+        })?;
+        Ok(net_state_value_to_stable_order(value))
+    }
+
     /// Append [Interface] into [NetworkState]
     pub fn append_interface_data(&mut self, iface: Interface) {
         self.interfaces.push(iface);
@@ -317,6 +825,34 @@ impl NetworkState {
         ))
     }
 
+    #[cfg(not(feature = "query_apply"))]
+    pub fn retrieve_kernel_only(&mut self) -> Result<&mut Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::retrieve_kernel_only() need `query_apply` feature \
+            enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn retrieve_config_only(&mut self) -> Result<&mut Self, NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::retrieve_config_only() need `query_apply` feature \
+            enabled"
+                .into(),
+        ))
+    }
+
+    /// Remove properties holding kernel or NetworkManager default values
+    /// or read-only status from interfaces, for example ethtool features in
+    /// kernel default state, disabled LLDP and maximum MTU, so queried state
+    /// could be used as a starting point of hand-edited desired state.
+    pub fn trim_defaults(&mut self) {
+        self.interfaces.trim_defaults();
+    }
+
     /// Replace secret string with `<_password_hid_by_nmstate>`
     pub fn hide_secrets(&mut self) {
         self.interfaces.hide_secrets();
@@ -330,6 +866,72 @@ impl NetworkState {
         ))
     }
 
+    #[cfg(not(feature = "query_apply"))]
+    pub fn apply_interface(
+        &self,
+        _iface: &Interface,
+    ) -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::apply_interface() need `query_apply` \
+            feature enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn verify_interface(
+        &self,
+        _iface: &Interface,
+    ) -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::verify_interface() need `query_apply` \
+            feature enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn apply_routes(&self, _routes: &Routes) -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::apply_routes() need `query_apply` \
+            feature enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn verify_routes(&self, _routes: &Routes) -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::verify_routes() need `query_apply` \
+            feature enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn apply_dns(&self, _dns: &DnsState) -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::apply_dns() need `query_apply` \
+            feature enabled"
+                .into(),
+        ))
+    }
+
+    #[cfg(not(feature = "query_apply"))]
+    pub fn verify_dns(&self, _dns: &DnsState) -> Result<(), NmstateError> {
+        Err(NmstateError::new(
+            ErrorKind::DependencyError,
+            "NetworkState::verify_dns() need `query_apply` \
+            feature enabled"
+                .into(),
+        ))
+    }
+
     #[cfg(not(feature = "gen_conf"))]
     pub fn gen_conf(
         &self,
@@ -368,32 +970,58 @@ pub(crate) struct MergedNetworkState {
     pub(crate) interfaces: MergedInterfaces,
     pub(crate) ovn: MergedOvnConfiguration,
     pub(crate) ovsdb: MergedOvsDbGlobalConfig,
+    pub(crate) connectivity_check: MergedConnectivityCheckConfig,
     pub(crate) routes: MergedRoutes,
     pub(crate) rules: MergedRouteRules,
     pub(crate) memory_only: bool,
+    // Whether secrets missing in desired state should be marked as owned by
+    // secret agent
+    pub(crate) secrets_agent_owned: bool,
+    pub(crate) iface_plugins: Vec<IfacePluginHolder>,
     pub(crate) prop_list: Vec<&'static str>,
 }
 
 impl MergedNetworkState {
     pub(crate) fn new(
-        desired: NetworkState,
+        mut desired: NetworkState,
         current: NetworkState,
         gen_conf_mode: bool,
         memory_only: bool,
     ) -> Result<Self, NmstateError> {
+        desired.remove_ignored_sections();
+        desired.apply_defaults()?;
+        desired
+            .interfaces
+            .resolve_plugin_ifaces(desired.iface_plugins.as_slice())?;
+        if desired.auto_absent_children {
+            desired
+                .interfaces
+                .mark_children_of_absent_as_absent(&current.interfaces);
+        }
+        if desired.ovn_auto_create_bridges {
+            desired.add_ovs_bridges_for_ovn(&current.interfaces);
+        }
         let interfaces = MergedInterfaces::new(
             desired.interfaces,
             current.interfaces,
             gen_conf_mode,
             memory_only,
         )?;
+        if !gen_conf_mode && desired.protect_cni_owned_ovs {
+            interfaces.validate_cni_owned_ovs()?;
+        }
+        interfaces.check_overlapping_subnets(desired.strict)?;
         let ignored_ifaces = interfaces.ignored_ifaces.as_slice();
 
         let mut routes =
             MergedRoutes::new(desired.routes, current.routes, &interfaces)?;
         routes.remove_routes_to_ignored_ifaces(ignored_ifaces);
 
-        let mut rules = MergedRouteRules::new(desired.rules, current.rules)?;
+        let mut rules = MergedRouteRules::new(
+            desired.rules,
+            current.rules,
+            desired.ignored_route_rule_priorities.as_slice(),
+        )?;
         rules.remove_rules_to_ignored_ifaces(ignored_ifaces);
 
         let hostname =
@@ -403,19 +1031,52 @@ impl MergedNetworkState {
 
         let ovsdb =
             MergedOvsDbGlobalConfig::new(desired.ovsdb, current.ovsdb, &ovn)?;
+
+        let connectivity_check = MergedConnectivityCheckConfig::new(
+            desired.connectivity_check,
+            current.connectivity_check,
+        )?;
+        let mut dns = MergedDnsState::new(
+            desired.dns,
+            current.dns,
+            desired.dns_verify_order_mode,
+        )?;
+        dns.set_cur_ifaces(&interfaces);
+        dns.ignored = desired.ignore_dns;
         let ret = Self {
             interfaces,
             routes,
             rules,
-            dns: MergedDnsState::new(desired.dns, current.dns)?,
+            dns,
             ovn,
             ovsdb,
+            connectivity_check,
             hostname,
             memory_only,
+            secrets_agent_owned: desired.secret_provider.is_some()
+                && desired.secrets_agent_owned,
+            iface_plugins: desired.iface_plugins,
             prop_list: desired.prop_list,
         };
         ret.validate_ipv6_link_local_address_dns_srv()?;
+        ret.validate_dns_interface()?;
 
         Ok(ret)
     }
 }
+
+// Remove duplicate items while preserving the order of first occurrences
+fn remove_duplicates<T>(items: &mut Vec<T>)
+where
+    T: PartialEq + Clone,
+{
+    let mut seen: Vec<T> = Vec::new();
+    items.retain(|i| {
+        if seen.contains(i) {
+            false
+        } else {
+            seen.push(i.clone());
+            true
+        }
+    });
+}
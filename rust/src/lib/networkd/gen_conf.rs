@@ -0,0 +1,487 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::{
+    warning::nmstate_warn, ErrorKind, Interface, InterfaceType, MergedDnsState,
+    MergedNetworkState, NmstateError, VlanProtocol,
+};
+
+// Interface properties stored into systemd-networkd configuration, all other
+// properties are dropped with warning.
+const SUPPORTED_IFACE_PROPS: [&str; 23] = [
+    "name",
+    "type",
+    "state",
+    "mtu",
+    "mac-address",
+    "controller",
+    "ipv4.enabled",
+    "ipv4.dhcp",
+    "ipv4.address.ip",
+    "ipv4.address.prefix-length",
+    "ipv6.enabled",
+    "ipv6.dhcp",
+    "ipv6.autoconf",
+    "ipv6.address.ip",
+    "ipv6.address.prefix-length",
+    "link-aggregation.mode",
+    "link-aggregation.port",
+    "link-aggregation.options.miimon",
+    "bridge.options.stp.enabled",
+    "bridge.port.name",
+    "vlan.base-iface",
+    "vlan.id",
+    "vlan.protocol",
+];
+
+const SUPPORTED_ROUTE_PROPS: [&str; 6] = [
+    "state",
+    "destination",
+    "next-hop-interface",
+    "next-hop-address",
+    "metric",
+    "table-id",
+];
+
+// The systemd-networkd configuration file holding ordered sections, same
+// section name could be repeated, for example `[Route]`.
+#[derive(Debug, Default)]
+struct NetworkdConfFile {
+    sections: Vec<(&'static str, Vec<(&'static str, String)>)>,
+}
+
+impl NetworkdConfFile {
+    fn push(
+        &mut self,
+        section: &'static str,
+        props: Vec<(&'static str, String)>,
+    ) {
+        if !props.is_empty() {
+            self.sections.push((section, props));
+        }
+    }
+
+    fn to_conf_string(&self) -> String {
+        let mut ret = String::new();
+        for (section, props) in self.sections.as_slice() {
+            if !ret.is_empty() {
+                ret.push('\n');
+            }
+            let _ = writeln!(ret, "[{section}]");
+            for (key, value) in props {
+                let _ = writeln!(ret, "{key}={value}");
+            }
+        }
+        ret
+    }
+}
+
+pub(crate) fn networkd_gen_conf(
+    merged_state: &MergedNetworkState,
+) -> Result<Vec<(String, String)>, NmstateError> {
+    if merged_state
+        .hostname
+        .desired
+        .as_ref()
+        .and_then(|c| c.config.as_ref())
+        .is_some()
+    {
+        nmstate_warn!(
+            "Cannot store hostname configuration to systemd-networkd \
+            configuration, please edit /etc/hostname manually"
+        );
+    }
+    if merged_state
+        .rules
+        .desired
+        .config
+        .as_ref()
+        .map(|r| !r.is_empty())
+        .unwrap_or_default()
+    {
+        nmstate_warn!(
+            "Ignoring route rules as systemd-networkd configuration \
+            generator does not support it"
+        );
+    }
+    if !merged_state.dns.options.is_empty() {
+        nmstate_warn!(
+            "Ignoring DNS options {:?} as systemd-networkd configuration \
+            generator does not support it",
+            merged_state.dns.options
+        );
+    }
+
+    let mut ifaces: Vec<&Interface> = Vec::new();
+    for iface in merged_state
+        .interfaces
+        .iter()
+        .filter_map(|i| i.for_apply.as_ref())
+    {
+        if !iface.is_up() {
+            log::warn!(
+                "Ignoring interface {} as systemd-networkd configuration \
+                only hold interface in up state",
+                iface.name(),
+            );
+            continue;
+        }
+        if !matches!(
+            iface.iface_type(),
+            InterfaceType::Ethernet
+                | InterfaceType::Bond
+                | InterfaceType::LinuxBridge
+                | InterfaceType::Vlan
+                | InterfaceType::Dummy
+        ) {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Interface {} of type {} is not supported by \
+                    systemd-networkd configuration generator",
+                    iface.name(),
+                    iface.iface_type()
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        for prop in get_dropped_iface_props(iface) {
+            nmstate_warn!(
+                "Ignoring property {prop} of interface {} as \
+                systemd-networkd configuration generator does not support it",
+                iface.name()
+            );
+        }
+        ifaces.push(iface);
+    }
+    ifaces.sort_unstable_by_key(|i| i.name());
+
+    // HashMap of port name to its controller name and controller type
+    let mut port_to_ctrl: HashMap<&str, (&str, InterfaceType)> = HashMap::new();
+    // HashMap of interface name to its VLAN interface names
+    let mut iface_to_vlans: HashMap<&str, Vec<&str>> = HashMap::new();
+    for iface in ifaces.as_slice() {
+        for port in iface.ports().unwrap_or_default() {
+            port_to_ctrl.insert(port, (iface.name(), iface.iface_type()));
+        }
+        if let Interface::Vlan(vlan_iface) = iface {
+            if let Some(vlan_conf) = vlan_iface.vlan.as_ref() {
+                iface_to_vlans
+                    .entry(vlan_conf.base_iface.as_str())
+                    .or_default()
+                    .push(iface.name());
+            }
+        }
+    }
+
+    let dns_ifaces: Vec<&str> =
+        if let Some(iface_name) = merged_state.dns.interface.as_deref() {
+            vec![iface_name]
+        } else {
+            ifaces
+                .iter()
+                .filter(|i| {
+                    !port_to_ctrl.contains_key(i.name())
+                        && (i.base_iface().is_ipv4_enabled()
+                            || i.base_iface().is_ipv6_enabled())
+                })
+                .map(|i| i.name())
+                .collect()
+        };
+
+    let mut ret = Vec::new();
+    for iface in ifaces {
+        if iface.iface_type() != InterfaceType::Ethernet {
+            ret.push((
+                format!("{}.netdev", iface.name()),
+                gen_netdev(iface).to_conf_string(),
+            ));
+        }
+        let mut network = gen_network(
+            iface,
+            port_to_ctrl.get(iface.name()),
+            iface_to_vlans.get(iface.name()).map(Vec::as_slice),
+            if dns_ifaces.contains(&iface.name()) {
+                Some(&merged_state.dns)
+            } else {
+                None
+            },
+        );
+        if let Some(routes) = merged_state.routes.indexed.get(iface.name()) {
+            for route in routes.iter().filter(|r| !r.is_absent()) {
+                for prop in get_dropped_props(route, &SUPPORTED_ROUTE_PROPS) {
+                    nmstate_warn!(
+                        "Ignoring property {prop} of route {route} as \
+                        systemd-networkd configuration generator does not \
+                        support it"
+                    );
+                }
+                let mut props = Vec::new();
+                if let Some(v) = route.destination.as_ref() {
+                    props.push(("Destination", v.to_string()));
+                }
+                if let Some(v) =
+                    route.next_hop_addr.as_deref().filter(|v| !v.is_empty())
+                {
+                    props.push(("Gateway", v.to_string()));
+                }
+                if let Some(v) = route.metric {
+                    props.push(("Metric", v.to_string()));
+                }
+                if let Some(v) = route.table_id {
+                    props.push(("Table", v.to_string()));
+                }
+                network.push("Route", props);
+            }
+        }
+        ret.push((
+            format!("{}.network", iface.name()),
+            network.to_conf_string(),
+        ));
+    }
+    Ok(ret)
+}
+
+fn gen_netdev(iface: &Interface) -> NetworkdConfFile {
+    let mut ret = NetworkdConfFile::default();
+    let base_iface = iface.base_iface();
+    let mut props = vec![("Name", iface.name().to_string())];
+    props.push((
+        "Kind",
+        match iface.iface_type() {
+            InterfaceType::Bond => "bond",
+            InterfaceType::LinuxBridge => "bridge",
+            InterfaceType::Vlan => "vlan",
+            _ => "dummy",
+        }
+        .to_string(),
+    ));
+    if let Some(v) = base_iface.mtu {
+        props.push(("MTUBytes", v.to_string()));
+    }
+    if let Some(v) = base_iface.mac_address.as_ref() {
+        props.push(("MACAddress", v.to_string()));
+    }
+    ret.push("NetDev", props);
+
+    match iface {
+        Interface::Bond(bond_iface) => {
+            let mut props = Vec::new();
+            if let Some(bond_conf) = bond_iface.bond.as_ref() {
+                if let Some(v) = bond_conf.mode {
+                    props.push(("Mode", v.to_string()));
+                }
+                if let Some(v) =
+                    bond_conf.options.as_ref().and_then(|o| o.miimon)
+                {
+                    props.push(("MIIMonitorSec", format!("{v}ms")));
+                }
+            }
+            ret.push("Bond", props);
+        }
+        Interface::LinuxBridge(br_iface) => {
+            let mut props = Vec::new();
+            if let Some(v) = br_iface
+                .bridge
+                .as_ref()
+                .and_then(|b| b.options.as_ref())
+                .and_then(|o| o.stp.as_ref())
+                .and_then(|s| s.enabled)
+            {
+                props.push(("STP", bool_to_str(v).to_string()));
+            }
+            ret.push("Bridge", props);
+        }
+        Interface::Vlan(vlan_iface) => {
+            let mut props = Vec::new();
+            if let Some(vlan_conf) = vlan_iface.vlan.as_ref() {
+                props.push(("Id", vlan_conf.id.to_string()));
+                if vlan_conf.protocol == Some(VlanProtocol::Ieee8021Ad) {
+                    props.push(("Protocol", "802.1ad".to_string()));
+                }
+            }
+            ret.push("VLAN", props);
+        }
+        _ => (),
+    }
+    ret
+}
+
+fn gen_network(
+    iface: &Interface,
+    ctrl: Option<&(&str, InterfaceType)>,
+    vlans: Option<&[&str]>,
+    dns: Option<&MergedDnsState>,
+) -> NetworkdConfFile {
+    let mut ret = NetworkdConfFile::default();
+    let base_iface = iface.base_iface();
+    ret.push("Match", vec![("Name", iface.name().to_string())]);
+
+    if iface.iface_type() == InterfaceType::Ethernet {
+        let mut props = Vec::new();
+        if let Some(v) = base_iface.mtu {
+            props.push(("MTUBytes", v.to_string()));
+        }
+        if let Some(v) = base_iface.mac_address.as_ref() {
+            props.push(("MACAddress", v.to_string()));
+        }
+        ret.push("Link", props);
+    }
+
+    let mut props = Vec::new();
+    if let Some((ctrl_name, ctrl_type)) = ctrl {
+        props.push((
+            if *ctrl_type == InterfaceType::Bond {
+                "Bond"
+            } else {
+                "Bridge"
+            },
+            ctrl_name.to_string(),
+        ));
+        props.push(("LinkLocalAddressing", "no".to_string()));
+    } else {
+        let ipv4_dhcp = base_iface.is_ipv4_enabled()
+            && base_iface.ipv4.as_ref().and_then(|i| i.dhcp) == Some(true);
+        let ipv6_dhcp = base_iface.is_ipv6_enabled()
+            && base_iface.ipv6.as_ref().and_then(|i| i.dhcp) == Some(true);
+        props.push((
+            "DHCP",
+            match (ipv4_dhcp, ipv6_dhcp) {
+                (true, true) => "yes",
+                (true, false) => "ipv4",
+                (false, true) => "ipv6",
+                (false, false) => "no",
+            }
+            .to_string(),
+        ));
+        if base_iface.is_ipv6_enabled() {
+            props.push((
+                "IPv6AcceptRA",
+                bool_to_str(
+                    base_iface.ipv6.as_ref().and_then(|i| i.autoconf)
+                        == Some(true),
+                )
+                .to_string(),
+            ));
+        } else {
+            props.push(("LinkLocalAddressing", "no".to_string()));
+        }
+        for addr in base_iface
+            .ipv4
+            .as_ref()
+            .filter(|i| i.enabled)
+            .and_then(|i| i.addresses.as_deref())
+            .unwrap_or_default()
+            .iter()
+            .chain(
+                base_iface
+                    .ipv6
+                    .as_ref()
+                    .filter(|i| i.enabled)
+                    .and_then(|i| i.addresses.as_deref())
+                    .unwrap_or_default()
+                    .iter(),
+            )
+        {
+            props.push((
+                "Address",
+                format!("{}/{}", addr.ip, addr.prefix_length),
+            ));
+        }
+    }
+    for vlan in vlans.unwrap_or_default() {
+        props.push(("VLAN", vlan.to_string()));
+    }
+    if let Some(dns) = dns {
+        for srv in dns.servers.as_slice() {
+            props.push(("DNS", srv.to_string()));
+        }
+        if !dns.searches.is_empty() {
+            props.push(("Domains", dns.searches.join(" ")));
+        }
+    }
+    ret.push("Network", props);
+    ret
+}
+
+fn bool_to_str(v: bool) -> &'static str {
+    if v {
+        "yes"
+    } else {
+        "no"
+    }
+}
+
+// The DHCP and autoconf of systemd-networkd are always using DNS, routes and
+// gateway learned, hence only `false` of `auto-dns`, `auto-gateway` and
+// `auto-routes` is dropped.
+fn get_dropped_iface_props(iface: &Interface) -> Vec<String> {
+    let mut value = match serde_json::to_value(iface) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+    for ip_key in ["ipv4", "ipv6"] {
+        if let Some(ip_conf) =
+            value.get_mut(ip_key).and_then(|v| v.as_object_mut())
+        {
+            ip_conf.retain(|k, v| {
+                !(["auto-dns", "auto-gateway", "auto-routes"]
+                    .contains(&k.as_str())
+                    && v.as_bool() == Some(true))
+            });
+        }
+    }
+    get_dropped_props(&value, &SUPPORTED_IFACE_PROPS)
+}
+
+// Dotted paths of serialized properties not listed in `supported`.
+fn get_dropped_props<T: serde::Serialize>(
+    data: &T,
+    supported: &[&str],
+) -> Vec<String> {
+    let mut ret = Vec::new();
+    if let Ok(value) = serde_json::to_value(data) {
+        collect_dropped_props("", &value, supported, &mut ret);
+    }
+    ret.sort_unstable();
+    ret.dedup();
+    ret
+}
+
+fn collect_dropped_props(
+    path: &str,
+    value: &serde_json::Value,
+    supported: &[&str],
+    ret: &mut Vec<String>,
+) {
+    match value {
+        serde_json::Value::Null => (),
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter() {
+                let path = if path.is_empty() {
+                    key.to_string()
+                } else {
+                    format!("{path}.{key}")
+                };
+                if supported.contains(&path.as_str()) {
+                    continue;
+                }
+                let prefix = format!("{path}.");
+                if supported.iter().any(|s| s.starts_with(prefix.as_str())) {
+                    collect_dropped_props(path.as_str(), value, supported, ret);
+                } else if !value.is_null() {
+                    ret.push(path);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_dropped_props(path, item, supported, ret);
+            }
+        }
+        _ => ret.push(path.to_string()),
+    }
+}
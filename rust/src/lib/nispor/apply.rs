@@ -13,6 +13,7 @@ use crate::{
 pub(crate) fn nispor_apply(
     merged_state: &MergedNetworkState,
 ) -> Result<(), NmstateError> {
+    validate_bond_active_port(&merged_state.interfaces)?;
     delete_ifaces(&merged_state.interfaces)?;
 
     let mut ifaces: Vec<&MergedInterface> = merged_state
@@ -21,7 +22,17 @@ pub(crate) fn nispor_apply(
         .filter(|i| i.is_changed())
         .collect();
 
+    let ordered_ports_index = merged_state.interfaces.get_ordered_ports_index();
+
     ifaces.sort_unstable_by_key(|iface| iface.merged.name());
+    // Ports of controller with `ports-ordered: true` should be activated in
+    // the declared order.
+    ifaces.sort_by_key(|iface| {
+        ordered_ports_index
+            .get(iface.merged.name())
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
     // Use sort_by_key() instead of unstable one, do we can alphabet
     // activation order which is required to simulate the OS boot-up.
     ifaces.sort_by_key(|iface| {
@@ -34,7 +45,12 @@ pub(crate) fn nispor_apply(
 
     let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
     for merged_iface in ifaces.iter().filter(|i| {
-        i.merged.iface_type() != InterfaceType::Unknown && !i.merged.is_absent()
+        i.merged.iface_type() != InterfaceType::Unknown
+            && i.merged.iface_type() != InterfaceType::Can
+            && i.merged.iface_type() != InterfaceType::Batman
+            && i.merged.iface_type() != InterfaceType::Ifb
+            && !matches!(i.merged, Interface::Custom(_))
+            && !i.merged.is_absent()
     }) {
         if let Some(iface) = merged_iface.for_apply.as_ref() {
             np_ifaces.push(nmstate_iface_to_np(iface)?);
@@ -45,15 +61,26 @@ pub(crate) fn nispor_apply(
     net_conf.ifaces = Some(np_ifaces);
 
     if let Err(e) = net_conf.apply() {
-        Err(NmstateError::new(
+        return Err(NmstateError::new(
             ErrorKind::PluginFailure,
             format!("Unknown error from nipsor plugin: {}, {}", e.kind, e.msg),
-        ))
-    } else {
-        Ok(())
+        ));
     }
+    merged_state.interfaces.apply_can_config_via_kernel()?;
+    merged_state.interfaces.apply_batman_config_via_kernel()?;
+    merged_state.interfaces.apply_ifb_config_via_kernel()
 }
 
+// Interface types could be created or modified by nispor, should be kept
+// in sync with `nmstate_iface_type_to_np()`.
+pub(crate) const NISPOR_APPLY_IFACE_TYPES: [InterfaceType; 5] = [
+    InterfaceType::Bond,
+    InterfaceType::Ethernet,
+    InterfaceType::LinuxBridge,
+    InterfaceType::Veth,
+    InterfaceType::Vlan,
+];
+
 fn nmstate_iface_type_to_np(
     nms_iface_type: &InterfaceType,
 ) -> nispor::IfaceType {
@@ -90,15 +117,20 @@ fn nmstate_iface_to_np(
     np_iface.state = nispor::IfaceState::Up;
 
     let base_iface = &nms_iface.base_iface();
+    // The batman-adv ports are attached after the controller been created
+    // via kernel
     if let Some(ctrl_name) = &base_iface.controller {
-        np_iface.controller = Some(ctrl_name.to_string())
+        if base_iface.controller_type != Some(InterfaceType::Batman) {
+            np_iface.controller = Some(ctrl_name.to_string())
+        }
     }
     if base_iface.can_have_ip() {
         np_iface.ipv4 = Some(nmstate_ipv4_to_np(base_iface.ipv4.as_ref()));
         np_iface.ipv6 = Some(nmstate_ipv6_to_np(base_iface.ipv6.as_ref()));
     }
 
-    np_iface.mac_address = base_iface.mac_address.clone();
+    np_iface.mac_address =
+        base_iface.mac_address.as_ref().map(|m| m.to_string());
 
     if let Interface::Ethernet(eth_iface) = nms_iface {
         np_iface.veth = nms_veth_conf_to_np(eth_iface.veth.as_ref());
@@ -113,9 +145,15 @@ fn delete_ifaces(merged_ifaces: &MergedInterfaces) -> Result<(), NmstateError> {
     let mut deleted_veths: Vec<&str> = Vec::new();
     let mut np_ifaces: Vec<nispor::IfaceConf> = Vec::new();
     for iface in merged_ifaces
-        .kernel_ifaces
-        .values()
-        .filter(|i| i.merged.is_absent())
+        .get_absent_ifaces_in_teardown_order()
+        .into_iter()
+        .filter(|i| {
+            !i.merged.is_userspace()
+                && !matches!(i.merged, Interface::Custom(_))
+                && i.merged.iface_type() != InterfaceType::Can
+                && i.merged.iface_type() != InterfaceType::Batman
+                && i.merged.iface_type() != InterfaceType::Ifb
+        })
     {
         // Deleting one end of veth peer is enough
         if deleted_veths.contains(&iface.merged.name()) {
@@ -149,3 +187,35 @@ fn delete_ifaces(merged_ifaces: &MergedInterfaces) -> Result<(), NmstateError> {
         Ok(())
     }
 }
+
+// Nispor cannot change active port of bond
+fn validate_bond_active_port(
+    merged_ifaces: &MergedInterfaces,
+) -> Result<(), NmstateError> {
+    for iface in merged_ifaces
+        .kernel_ifaces
+        .values()
+        .filter_map(|i| i.for_apply.as_ref())
+    {
+        if let Interface::Bond(bond_iface) = iface {
+            if bond_iface
+                .bond
+                .as_ref()
+                .and_then(|b| b.active_port.as_ref())
+                .is_some()
+            {
+                let e = NmstateError::new(
+                    ErrorKind::NotSupportedError,
+                    format!(
+                        "The `active-port` of bond {} is not supported in \
+                        kernel only mode",
+                        iface.name()
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
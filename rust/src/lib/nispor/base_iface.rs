@@ -1,10 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::convert::TryFrom;
+
 use crate::{
     nispor::ethtool::np_ethtool_to_nmstate,
     nispor::ip::{np_ipv4_to_nmstate, np_ipv6_to_nmstate},
     nispor::mptcp::get_iface_mptcp_conf,
-    BaseInterface, InterfaceState, InterfaceType,
+    BaseInterface, InterfaceBackend, InterfaceManagedBy, InterfaceState,
+    InterfaceType, MacAddress,
 };
 
 fn np_iface_type_to_nmstate(
@@ -26,6 +29,48 @@ fn np_iface_type_to_nmstate(
         nispor::IfaceType::Vxlan => InterfaceType::Vxlan,
         nispor::IfaceType::Ipoib => InterfaceType::InfiniBand,
         nispor::IfaceType::Tun => InterfaceType::Tun,
+        nispor::IfaceType::Other(kind) if kind.eq_ignore_ascii_case("gre") => {
+            InterfaceType::Gre
+        }
+        nispor::IfaceType::Other(kind)
+            if kind.eq_ignore_ascii_case("gretap") =>
+        {
+            InterfaceType::Gretap
+        }
+        nispor::IfaceType::Other(kind)
+            if kind.eq_ignore_ascii_case("ip6gre") =>
+        {
+            InterfaceType::Ip6Gre
+        }
+        nispor::IfaceType::Other(kind)
+            if kind.eq_ignore_ascii_case("ip6gretap") =>
+        {
+            InterfaceType::Ip6Gretap
+        }
+        nispor::IfaceType::Other(kind)
+            if kind.eq_ignore_ascii_case("ip6tnl") =>
+        {
+            InterfaceType::Ip6Tnl
+        }
+        nispor::IfaceType::Other(kind) if kind.eq_ignore_ascii_case("team") => {
+            InterfaceType::Team
+        }
+        nispor::IfaceType::Other(kind)
+            if kind.eq_ignore_ascii_case("ipvlan") =>
+        {
+            InterfaceType::IpVlan
+        }
+        nispor::IfaceType::Other(kind) if kind.eq_ignore_ascii_case("can") => {
+            InterfaceType::Can
+        }
+        nispor::IfaceType::Other(kind)
+            if kind.eq_ignore_ascii_case("batadv") =>
+        {
+            InterfaceType::Batman
+        }
+        nispor::IfaceType::Other(kind) if kind.eq_ignore_ascii_case("ifb") => {
+            InterfaceType::Ifb
+        }
         _ => InterfaceType::Other(format!("{np_iface_type:?}")),
     }
 }
@@ -56,7 +101,7 @@ pub(crate) fn np_iface_to_base_iface(
         iface_type: np_iface_type_to_nmstate(&np_iface.iface_type),
         ipv4: np_ipv4_to_nmstate(np_iface, running_config_only),
         ipv6: np_ipv6_to_nmstate(np_iface, running_config_only),
-        mac_address: Some(np_iface.mac_address.to_uppercase()),
+        mac_address: MacAddress::try_from(np_iface.mac_address.as_str()).ok(),
         permanent_mac_address: get_permanent_mac_address(np_iface),
         controller: np_iface.controller.as_ref().map(|c| c.to_string()),
         mtu: if np_iface.mtu >= 0 {
@@ -99,6 +144,7 @@ pub(crate) fn np_iface_to_base_iface(
             Some(false)
         },
         ethtool: np_ethtool_to_nmstate(np_iface),
+        managed_by: Some(InterfaceManagedBy::new(InterfaceBackend::Kernel)),
         prop_list: vec![
             "name",
             "state",
@@ -111,6 +157,7 @@ pub(crate) fn np_iface_to_base_iface(
             "mtu",
             "accept_all_mac_addresses",
             "ethtool",
+            "managed_by",
         ],
         ..Default::default()
     };
@@ -128,7 +175,7 @@ pub(crate) fn np_iface_to_base_iface(
     base_iface
 }
 
-fn get_permanent_mac_address(iface: &nispor::Iface) -> Option<String> {
+fn get_permanent_mac_address(iface: &nispor::Iface) -> Option<MacAddress> {
     if iface.permanent_mac_address.is_empty() {
         // Bond port also hold perm_hwaddr which is the mac address before
         // this interface been assgined to bond as subordinate.
@@ -136,12 +183,12 @@ fn get_permanent_mac_address(iface: &nispor::Iface) -> Option<String> {
             if bond_port_info.perm_hwaddr.is_empty() {
                 None
             } else {
-                Some(bond_port_info.perm_hwaddr.as_str().to_uppercase())
+                MacAddress::try_from(bond_port_info.perm_hwaddr.as_str()).ok()
             }
         } else {
             None
         }
     } else {
-        Some(iface.permanent_mac_address.clone())
+        MacAddress::try_from(iface.permanent_mac_address.as_str()).ok()
     }
 }
@@ -0,0 +1,49 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, BatmanConfig, BatmanInterface, BatmanRoutingAlgorithm,
+};
+
+// Nispor does not expose batman-adv properties, port list is generated by
+// searching interfaces using batman-adv interface as controller.
+pub(crate) fn np_batman_to_nmstate(
+    np_state: &nispor::NetState,
+    base_iface: BaseInterface,
+) -> BatmanInterface {
+    let mut ports: Vec<String> = np_state
+        .ifaces
+        .values()
+        .filter(|i| i.controller.as_deref() == Some(base_iface.name.as_str()))
+        .map(|i| i.name.to_string())
+        .collect();
+    ports.sort_unstable();
+    let mut batman_conf = BatmanConfig::new();
+    batman_conf.routing_algorithm =
+        get_kernel_batman_routing_algo(base_iface.name.as_str());
+    batman_conf.port = Some(ports);
+    BatmanInterface {
+        base: base_iface,
+        batman: Some(batman_conf),
+    }
+}
+
+// Nispor does not expose batman-adv properties yet, retrieve the routing
+// algorithm from sysfs.
+fn get_kernel_batman_routing_algo(
+    iface_name: &str,
+) -> Option<BatmanRoutingAlgorithm> {
+    let path = format!("/sys/class/net/{iface_name}/mesh/routing_algo");
+    match std::fs::read_to_string(&path) {
+        Ok(content) => match content.parse() {
+            Ok(algo) => Some(algo),
+            Err(e) => {
+                log::debug!("Failed to parse {path}: {e}");
+                None
+            }
+        },
+        Err(e) => {
+            log::debug!("Failed to read {path}: {e}");
+            None
+        }
+    }
+}
@@ -217,6 +217,17 @@ fn np_bond_options_to_nmstate(np_iface: &nispor::Iface) -> BondOptions {
                 }
             });
         options.arp_missed_max = np_bond.arp_missed_max;
+        options.ns_ip6_target = get_ns_ip6_target(np_iface.name.as_str());
     }
     options
 }
+
+// The nispor does not expose IFLA_BOND_NS_IP6_TARGET yet, read from sysfs.
+// Kernel is using space to separate IPv6 addresses there.
+fn get_ns_ip6_target(bond_name: &str) -> Option<String> {
+    std::fs::read_to_string(format!(
+        "/sys/class/net/{bond_name}/bonding/ns_ip6_target"
+    ))
+    .ok()
+    .map(|v| v.split_whitespace().collect::<Vec<&str>>().join(","))
+}
@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    query_apply::can::get_kernel_can_conf, BaseInterface, CanInterface,
+};
+
+pub(crate) fn np_can_to_nmstate(base_iface: BaseInterface) -> CanInterface {
+    let can = get_kernel_can_conf(base_iface.name.as_str());
+    CanInterface {
+        base: base_iface,
+        can,
+    }
+}
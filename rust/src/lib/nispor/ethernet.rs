@@ -1,6 +1,8 @@
+use std::convert::TryFrom;
+
 use crate::{
     BaseInterface, EthernetConfig, EthernetDuplex, EthernetInterface,
-    SrIovConfig, SrIovVfConfig,
+    MacAddress, SrIovConfig, SrIovVfConfig,
 };
 
 pub(crate) fn np_ethernet_to_nmstate(
@@ -47,7 +49,7 @@ fn gen_sriov_conf(sriov_info: &nispor::SriovInfo) -> SrIovConfig {
         vf.id = vf_info.id;
         vf.iface_name =
             vf_info.iface_name.as_ref().cloned().unwrap_or_default();
-        vf.mac_address = Some(vf_info.mac.to_ascii_uppercase());
+        vf.mac_address = MacAddress::try_from(vf_info.mac.as_str()).ok();
         vf.spoof_check = Some(vf_info.spoof_check);
         vf.trust = Some(vf_info.trust);
         vf.min_tx_rate = Some(vf_info.min_tx_rate);
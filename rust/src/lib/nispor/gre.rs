@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseInterface, GreInterface, GretapInterface};
+
+// Nispor does not expose GRE tunnel properties yet, the `gre` and `gretap`
+// sections are filled by NetworkManager plugin.
+pub(crate) fn np_gre_to_nmstate(base_iface: BaseInterface) -> GreInterface {
+    GreInterface {
+        base: base_iface,
+        gre: None,
+    }
+}
+
+pub(crate) fn np_gretap_to_nmstate(
+    base_iface: BaseInterface,
+) -> GretapInterface {
+    GretapInterface {
+        base: base_iface,
+        gretap: None,
+    }
+}
@@ -0,0 +1,34 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BaseInterface, Ip6GreInterface, Ip6GretapInterface, Ip6TnlInterface,
+};
+
+// Nispor does not expose IPv6 tunnel properties yet, the `ip6gre`,
+// `ip6gretap` and `ip6tnl` sections are filled by NetworkManager plugin.
+pub(crate) fn np_ip6gre_to_nmstate(
+    base_iface: BaseInterface,
+) -> Ip6GreInterface {
+    Ip6GreInterface {
+        base: base_iface,
+        ip6gre: None,
+    }
+}
+
+pub(crate) fn np_ip6gretap_to_nmstate(
+    base_iface: BaseInterface,
+) -> Ip6GretapInterface {
+    Ip6GretapInterface {
+        base: base_iface,
+        ip6gretap: None,
+    }
+}
+
+pub(crate) fn np_ip6tnl_to_nmstate(
+    base_iface: BaseInterface,
+) -> Ip6TnlInterface {
+    Ip6TnlInterface {
+        base: base_iface,
+        ip6tnl: None,
+    }
+}
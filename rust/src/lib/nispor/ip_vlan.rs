@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseInterface, IpVlanInterface};
+
+// Nispor does not expose IP VLAN properties yet, the `ipvlan` section is
+// filled by NetworkManager plugin.
+pub(crate) fn np_ipvlan_to_nmstate(
+    base_iface: BaseInterface,
+) -> IpVlanInterface {
+    IpVlanInterface {
+        base: base_iface,
+        ipvlan: None,
+    }
+}
@@ -48,6 +48,9 @@ pub(crate) fn append_bridge_port_config(
             port_conf.stp_hairpin_mode = Some(np_port_info.hairpin_mode);
             port_conf.stp_path_cost = Some(np_port_info.stp_path_cost);
             port_conf.stp_priority = Some(np_port_info.stp_priority);
+            if is_port_hw_offloaded(port_conf.name.as_str()) {
+                port_conf.hw_offload = Some(true);
+            }
             if np_iface
                 .bridge
                 .as_ref()
@@ -140,10 +143,32 @@ fn np_bridge_options_to_nmstate(
                 }
             });
         options.vlan_default_pvid = np_bridge.default_pvid;
+        options.vlan_stats_enabled = np_bridge.vlan_stats_enabled;
+        options.no_linklocal_learn =
+            get_no_linklocal_learn(np_iface.name.as_str());
     }
     Ok(options)
 }
 
+// Kernel set non-zero `offload_fwd_mark` to bridge port when switchdev
+// driver offloaded the forwarding.
+fn is_port_hw_offloaded(port_name: &str) -> bool {
+    std::fs::read_to_string(format!(
+        "/sys/class/net/{port_name}/brport/offload_fwd_mark"
+    ))
+    .map(|v| !["", "0"].contains(&v.trim()))
+    .unwrap_or_default()
+}
+
+// The nispor does not expose IFLA_BR_MULTI_BOOLOPT yet, read from sysfs.
+fn get_no_linklocal_learn(br_name: &str) -> Option<bool> {
+    std::fs::read_to_string(format!(
+        "/sys/class/net/{br_name}/bridge/no_linklocal_learn"
+    ))
+    .ok()
+    .map(|v| v.trim() == "1")
+}
+
 // The kernel is multiplying these bridge properties by USER_HZ, we should
 // divide into seconds:
 //   * forward_delay
@@ -1,6 +1,8 @@
+use std::convert::TryFrom;
+
 use crate::{
-    BaseInterface, MacVlanConfig, MacVlanInterface, MacVlanMode, MacVtapConfig,
-    MacVtapInterface, MacVtapMode,
+    BaseInterface, MacAddress, MacVlanConfig, MacVlanInterface, MacVlanMode,
+    MacVtapConfig, MacVtapInterface, MacVtapMode,
 };
 
 const MACVLAN_FLAG_NOPROMISC: u16 = 1;
@@ -33,6 +35,24 @@ pub(crate) fn np_mac_vlan_to_nmstate(
                     np_vlan_info.flags & MACVLAN_FLAG_NOPROMISC == 0,
                 ),
                 base_iface: np_vlan_info.base_iface.clone(),
+                source_mac_addresses: if matches!(
+                    np_vlan_info.mode,
+                    nispor::MacVlanMode::Source
+                ) {
+                    Some(
+                        np_vlan_info
+                            .allowed_mac_addresses
+                            .as_deref()
+                            .unwrap_or_default()
+                            .iter()
+                            .filter_map(|m| {
+                                MacAddress::try_from(m.as_str()).ok()
+                            })
+                            .collect(),
+                    )
+                } else {
+                    None
+                },
             });
 
     MacVlanInterface {
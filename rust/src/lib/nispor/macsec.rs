@@ -29,6 +29,7 @@ pub(crate) fn np_macsec_to_nmstate(
             base_iface: np_macsec_info.base_iface.clone().unwrap_or_default(),
             mka_cak: None,
             mka_ckn: None,
+            mka_keys: None,
         });
 
     MacSecInterface {
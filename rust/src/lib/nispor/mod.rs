@@ -1,12 +1,17 @@
 mod apply;
 mod base_iface;
+mod batman;
 mod bond;
+mod can;
 mod error;
 mod ethernet;
 mod ethtool;
+mod gre;
 mod hostname;
 mod infiniband;
 mod ip;
+mod ip6_tunnel;
+mod ip_vlan;
 mod linux_bridge;
 mod linux_bridge_port_vlan;
 mod mac_vlan;
@@ -15,11 +20,13 @@ mod mptcp;
 mod route;
 mod route_rule;
 mod show;
+mod team;
 mod veth;
 mod vlan;
 mod vrf;
 mod vxlan;
 
-pub(crate) use apply::nispor_apply;
-pub(crate) use hostname::set_running_hostname;
+pub(crate) use apply::{nispor_apply, NISPOR_APPLY_IFACE_TYPES};
+pub(crate) use hostname::{get_hostname_state, set_running_hostname};
+pub(crate) use route::get_ipv6_ra_routes;
 pub(crate) use show::nispor_retrieve;
@@ -1,6 +1,11 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::net::IpAddr;
+use std::str::FromStr;
+
 use log::warn;
 
-use crate::{RouteEntry, RouteType, Routes};
+use crate::{IpNetwork, Ipv6RaRoute, RouteEntry, RouteType, Routes};
 
 const SUPPORTED_ROUTE_SCOPE: [nispor::RouteScope; 2] =
     [nispor::RouteScope::Universe, nispor::RouteScope::Link];
@@ -22,6 +27,8 @@ const IPV4_DEFAULT_GATEWAY: &str = "0.0.0.0/0";
 const IPV6_DEFAULT_GATEWAY: &str = "::/0";
 const IPV4_EMPTY_NEXT_HOP_ADDRESS: &str = "0.0.0.0";
 const IPV6_EMPTY_NEXT_HOP_ADDRESS: &str = "::";
+// Kernel reports route expiry time in clock ticks of USER_HZ
+const USER_HZ: u32 = 100;
 
 pub(crate) fn get_routes(running_config_only: bool) -> Routes {
     let mut ret = Routes::new();
@@ -117,7 +124,8 @@ fn np_routetype_to_nmstate(np_route: &nispor::Route) -> RouteEntry {
     };
 
     let mut route_entry = RouteEntry::new();
-    route_entry.destination = destination;
+    route_entry.destination =
+        destination.and_then(|d| IpNetwork::try_from(d.as_str()).ok());
     if np_route.address_family == nispor::AddressFamily::IPv6 {
         route_entry.next_hop_iface = np_route.oif.as_ref().cloned();
     }
@@ -183,7 +191,8 @@ fn np_route_to_nmstate(np_route: &nispor::Route) -> RouteEntry {
     };
 
     let mut route_entry = RouteEntry::new();
-    route_entry.destination = destination;
+    route_entry.destination =
+        destination.and_then(|d| IpNetwork::try_from(d.as_str()).ok());
     route_entry.next_hop_iface = np_route.oif.as_ref().cloned();
     route_entry.next_hop_addr = next_hop_addr;
     route_entry.metric = np_route.metric.map(i64::from);
@@ -216,3 +225,58 @@ fn flat_multipath_route(np_route: &nispor::Route) -> Vec<RouteEntry> {
     }
     ret
 }
+
+// Return routes learned from IPv6 router advertisement indexed by interface
+// name
+pub(crate) fn get_ipv6_ra_routes() -> HashMap<String, Vec<Ipv6RaRoute>> {
+    let mut ret: HashMap<String, Vec<Ipv6RaRoute>> = HashMap::new();
+    let mut rt_filter = nispor::NetStateRouteFilter::default();
+    rt_filter.protocol = Some(nispor::RouteProtocol::Ra);
+    let mut filter = nispor::NetStateFilter::minimum();
+    filter.route = Some(rt_filter);
+    let np_state = match nispor::NetState::retrieve_with_filter(&filter) {
+        Ok(s) => s,
+        Err(e) => {
+            log::warn!("Failed to retrieve IPv6 RA routes via nispor: {}", e);
+            return ret;
+        }
+    };
+    for np_route in np_state
+        .routes
+        .iter()
+        .filter(|r| r.address_family == nispor::AddressFamily::IPv6)
+    {
+        let iface_name = match np_route.oif.as_ref() {
+            Some(i) => i.to_string(),
+            None => continue,
+        };
+        let destination = match np_route.dst.as_deref() {
+            Some(d) if !d.contains('/') => format!("{d}/128"),
+            Some(d) => d.to_string(),
+            None => IPV6_DEFAULT_GATEWAY.to_string(),
+        };
+        let destination = match IpNetwork::try_from(destination) {
+            Ok(d) => d,
+            Err(e) => {
+                log::debug!("Ignoring IPv6 RA route {:?}: {}", np_route, e);
+                continue;
+            }
+        };
+        // Route with infinite lifetime has no expiry time
+        let lifetime = match np_route.cache_expires {
+            Some(v) if v > 0 => format!("{}sec", v / USER_HZ),
+            _ => "forever".to_string(),
+        };
+        ret.entry(iface_name).or_default().push(Ipv6RaRoute {
+            destination,
+            next_hop_address: np_route
+                .via
+                .as_ref()
+                .or(np_route.gateway.as_ref())
+                .and_then(|g| IpAddr::from_str(g).ok()),
+            metric: np_route.metric,
+            lifetime: Some(lifetime),
+        });
+    }
+    ret
+}
@@ -1,8 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::convert::TryFrom;
+
 use log::warn;
 
-use crate::{AddressFamily, RouteRuleAction, RouteRuleEntry, RouteRules};
+use crate::{
+    AddressFamily, IpNetwork, RouteRuleAction, RouteRuleEntry, RouteRules,
+};
 
 // Due to a bug in NetworkManager all route rules added using NetworkManager are
 // using RTM_PROTOCOL Unspec. Therefore, we need to support it until it is
@@ -63,8 +67,14 @@ pub(crate) fn get_route_rules(
             }
         }
         rule.iif = np_rule.iif.clone();
-        rule.ip_to = np_rule.dst.clone();
-        rule.ip_from = np_rule.src.clone();
+        rule.ip_to = np_rule
+            .dst
+            .as_deref()
+            .and_then(|i| IpNetwork::try_from(i).ok());
+        rule.ip_from = np_rule
+            .src
+            .as_deref()
+            .and_then(|i| IpNetwork::try_from(i).ok());
         rule.table_id = np_rule.table;
         rule.priority = np_rule.priority.map(i64::from);
         rule.fwmark = np_rule.fw_mark;
@@ -5,23 +5,32 @@ use std::collections::HashMap;
 use crate::{
     nispor::{
         base_iface::np_iface_to_base_iface,
+        batman::np_batman_to_nmstate,
         bond::{append_bond_port_config, np_bond_to_nmstate},
+        can::np_can_to_nmstate,
         error::np_error_to_nmstate,
         ethernet::np_ethernet_to_nmstate,
+        gre::{np_gre_to_nmstate, np_gretap_to_nmstate},
         hostname::get_hostname_state,
         infiniband::np_ib_to_nmstate,
+        ip6_tunnel::{
+            np_ip6gre_to_nmstate, np_ip6gretap_to_nmstate, np_ip6tnl_to_nmstate,
+        },
+        ip_vlan::np_ipvlan_to_nmstate,
         linux_bridge::{append_bridge_port_config, np_bridge_to_nmstate},
         mac_vlan::{np_mac_vlan_to_nmstate, np_mac_vtap_to_nmstate},
         macsec::np_macsec_to_nmstate,
         route::get_routes,
         route_rule::get_route_rules,
+        team::np_team_to_nmstate,
         veth::np_veth_to_nmstate,
         vlan::np_vlan_to_nmstate,
         vrf::np_vrf_to_nmstate,
         vxlan::np_vxlan_to_nmstate,
     },
-    DummyInterface, Interface, InterfaceType, Interfaces, LoopbackInterface,
-    NetworkState, NmstateError, OvsInterface, UnknownInterface,
+    DummyInterface, IfbInterface, Interface, InterfaceType, Interfaces,
+    LoopbackInterface, NetworkState, NmstateError, OvsInterface,
+    UnknownInterface, UnsupportedInterface,
 };
 
 pub(crate) fn nispor_retrieve(
@@ -29,7 +38,13 @@ pub(crate) fn nispor_retrieve(
 ) -> Result<NetworkState, NmstateError> {
     let mut net_state = NetworkState {
         hostname: get_hostname_state(),
-        prop_list: vec!["interfaces", "routes", "rules", "hostname"],
+        prop_list: vec![
+            "interfaces",
+            "routes",
+            "rules",
+            "hostname",
+            "unsupported_interfaces",
+        ],
         ..Default::default()
     };
     let mut filter = nispor::NetStateFilter::default();
@@ -120,6 +135,12 @@ pub(crate) fn nispor_retrieve(
                         "Ignoring unsupported HFI interface {}",
                         base_iface.name
                     );
+                    net_state.unsupported_interfaces.push(
+                        UnsupportedInterface::new(
+                            base_iface.name.clone(),
+                            base_iface.iface_type.clone(),
+                        ),
+                    );
                     continue;
                 }
                 Interface::InfiniBand(np_ib_to_nmstate(np_iface, base_iface))
@@ -130,12 +151,44 @@ pub(crate) fn nispor_retrieve(
             InterfaceType::MacSec => {
                 Interface::MacSec(np_macsec_to_nmstate(np_iface, base_iface))
             }
+            InterfaceType::Gre => Interface::Gre(np_gre_to_nmstate(base_iface)),
+            InterfaceType::Gretap => {
+                Interface::Gretap(np_gretap_to_nmstate(base_iface))
+            }
+            InterfaceType::Ip6Gre => {
+                Interface::Ip6Gre(np_ip6gre_to_nmstate(base_iface))
+            }
+            InterfaceType::Ip6Gretap => {
+                Interface::Ip6Gretap(np_ip6gretap_to_nmstate(base_iface))
+            }
+            InterfaceType::Ip6Tnl => {
+                Interface::Ip6Tnl(np_ip6tnl_to_nmstate(base_iface))
+            }
+            InterfaceType::Team => {
+                Interface::Team(np_team_to_nmstate(&np_state, base_iface))
+            }
+            InterfaceType::IpVlan => {
+                Interface::IpVlan(np_ipvlan_to_nmstate(base_iface))
+            }
+            InterfaceType::Can => Interface::Can(np_can_to_nmstate(base_iface)),
+            InterfaceType::Batman => {
+                Interface::Batman(np_batman_to_nmstate(&np_state, base_iface))
+            }
+            InterfaceType::Ifb => {
+                Interface::Ifb(IfbInterface { base: base_iface })
+            }
             _ => {
                 log::info!(
                     "Got unsupported interface {} type {:?}",
                     np_iface.name,
                     np_iface.iface_type
                 );
+                net_state.unsupported_interfaces.push(
+                    UnsupportedInterface::new(
+                        base_iface.name.clone(),
+                        base_iface.iface_type.clone(),
+                    ),
+                );
                 Interface::Unknown({
                     let mut iface = UnknownInterface::new();
                     iface.base = base_iface;
@@ -148,6 +201,9 @@ pub(crate) fn nispor_retrieve(
     set_controller_type(&mut net_state.interfaces);
     net_state.routes = get_routes(running_config_only);
     net_state.rules = get_route_rules(&np_state.rules, running_config_only);
+    net_state
+        .unsupported_interfaces
+        .sort_unstable_by(|a, b| a.name.cmp(&b.name));
 
     Ok(net_state)
 }
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{BaseInterface, TeamConfig, TeamInterface};
+
+// Nispor does not expose teamd runner, only port list is generated by
+// searching interfaces using team interface as controller.
+pub(crate) fn np_team_to_nmstate(
+    np_state: &nispor::NetState,
+    base_iface: BaseInterface,
+) -> TeamInterface {
+    let mut ports: Vec<String> = np_state
+        .ifaces
+        .values()
+        .filter(|i| i.controller.as_deref() == Some(base_iface.name.as_str()))
+        .map(|i| i.name.to_string())
+        .collect();
+    ports.sort_unstable();
+    let mut team_conf = TeamConfig::new();
+    team_conf.port = Some(ports);
+    TeamInterface {
+        base: base_iface,
+        team: Some(team_conf),
+    }
+}
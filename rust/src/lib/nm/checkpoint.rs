@@ -1,24 +1,30 @@
+use std::cell::{Cell, RefCell};
+
 use crate::nm::nm_dbus::NmApi;
 use log::warn;
 
-use crate::{nm::error::nm_error_to_nmstate, NmstateError};
+use crate::{nm::error::nm_error_to_nmstate, ErrorKind, NmstateError};
 
 // Wait maximum 60 seconds for rollback
 pub(crate) const CHECKPOINT_ROLLBACK_TIMEOUT: u32 = 60;
 
 pub(crate) fn nm_checkpoint_create(
+    nm_dbus_address: Option<&str>,
     timeout: u32,
 ) -> Result<String, NmstateError> {
-    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let mut nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
     nm_api
         .checkpoint_create(timeout)
         .map_err(nm_error_to_nmstate)
 }
 
 pub(crate) fn nm_checkpoint_rollback(
+    nm_dbus_address: Option<&str>,
     checkpoint: &str,
 ) -> Result<(), NmstateError> {
-    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let mut nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
     nm_api
         .checkpoint_rollback(checkpoint)
         .map_err(nm_error_to_nmstate)?;
@@ -30,20 +36,126 @@ pub(crate) fn nm_checkpoint_rollback(
 }
 
 pub(crate) fn nm_checkpoint_destroy(
+    nm_dbus_address: Option<&str>,
     checkpoint: &str,
 ) -> Result<(), NmstateError> {
-    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let mut nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
     nm_api
         .checkpoint_destroy(checkpoint)
         .map_err(nm_error_to_nmstate)
 }
 
+// Return false if failed to query NetworkManager, as all checkpoints are
+// dropped when NetworkManager stopped.
+pub(crate) fn nm_checkpoint_exists(
+    nm_dbus_address: Option<&str>,
+    checkpoint: &str,
+) -> bool {
+    match NmApi::new_with_address(nm_dbus_address)
+        .and_then(|nm_api| nm_api.checkpoints())
+    {
+        Ok(checkpoints) => checkpoints.iter().any(|c| c == checkpoint),
+        Err(e) => {
+            log::debug!("Failed to query NetworkManager checkpoints: {e}");
+            false
+        }
+    }
+}
+
 pub(crate) fn nm_checkpoint_timeout_extend(
+    nm_dbus_address: Option<&str>,
     checkpoint: &str,
     added_time_sec: u32,
 ) -> Result<(), NmstateError> {
-    let nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
     nm_api
         .checkpoint_timeout_extend(checkpoint, added_time_sec)
         .map_err(nm_error_to_nmstate)
 }
+
+// NetworkManager drops all checkpoints on restart. This struct tracks the
+// D-Bus name owner of NetworkManager, so that daemon restart in the middle of
+// apply action could be detected. The apply only resumes when the original
+// checkpoint survived the restart, otherwise the checkpoint is marked as lost
+// and the apply fails, caller should rollback without checkpoint then.
+#[derive(Debug)]
+pub(crate) struct NmCheckpoint {
+    nm_dbus_address: Option<String>,
+    path: String,
+    nm_owner: RefCell<Option<String>>,
+    lost: Cell<bool>,
+}
+
+impl NmCheckpoint {
+    pub(crate) fn new(nm_dbus_address: Option<&str>, path: String) -> Self {
+        let nm_owner = match nm_name_owner(nm_dbus_address) {
+            Ok(o) => Some(o),
+            Err(e) => {
+                log::debug!("Failed to query NetworkManager D-Bus owner: {e}");
+                None
+            }
+        };
+        Self {
+            nm_dbus_address: nm_dbus_address.map(|a| a.to_string()),
+            path,
+            nm_owner: RefCell::new(nm_owner),
+            lost: Cell::new(false),
+        }
+    }
+
+    pub(crate) fn path(&self) -> &str {
+        self.path.as_str()
+    }
+
+    // Whether checkpoint is lost due to NetworkManager restart
+    pub(crate) fn is_lost(&self) -> bool {
+        self.lost.get()
+    }
+
+    // Return true if NetworkManager restarted since last check.
+    // Return error if checkpoint is lost due to the restart.
+    pub(crate) fn revalidate(&self) -> Result<bool, NmstateError> {
+        let nm_dbus_address = self.nm_dbus_address.as_deref();
+        let nm_owner = nm_name_owner(nm_dbus_address)?;
+        match self.nm_owner.borrow().as_deref() {
+            Some(o) if o != nm_owner => (),
+            _ => {
+                self.nm_owner.replace(Some(nm_owner));
+                return Ok(false);
+            }
+        }
+        let nm_api = NmApi::new_with_address(nm_dbus_address)
+            .map_err(nm_error_to_nmstate)?;
+        let checkpoints = nm_api.checkpoints().map_err(nm_error_to_nmstate)?;
+        if !checkpoints.iter().any(|c| c == &self.path) {
+            self.lost.set(true);
+            let e = NmstateError::new(
+                ErrorKind::PluginFailure,
+                format!(
+                    "NetworkManager restarted during apply and lost \
+                    checkpoint {}",
+                    self.path
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        log::warn!(
+            "NetworkManager restarted during apply, checkpoint {} still \
+            exists",
+            self.path
+        );
+        self.nm_owner.replace(Some(nm_owner));
+        Ok(true)
+    }
+}
+
+fn nm_name_owner(
+    nm_dbus_address: Option<&str>,
+) -> Result<String, NmstateError> {
+    let nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
+    nm_api.name_owner().map_err(nm_error_to_nmstate)
+}
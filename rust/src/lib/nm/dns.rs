@@ -60,8 +60,9 @@ pub(crate) fn store_dns_config_to_iface(
     Ok(())
 }
 
-// If DNS changed or desired, we find out interface to hold the DNS entry in the
-// order of:
+// If DNS changed or desired, we use the interface explicitly desired in
+// `dns-resolver.config.interface`, otherwise find out interface to hold the
+// DNS entry in the order of:
 //  * If current interface which holding the DNS still valid for DNS also listed
 //    in desire state
 //  * Interfaces in desired with manual IP stack enabled or `auto_dns: false`
@@ -76,6 +77,10 @@ pub(crate) fn reselect_dns_ifaces(
     nm_acs: &[NmActiveConnection],
     nm_devs: &[NmDevice],
 ) -> (String, String) {
+    if let Some(iface_name) = merged_state.dns.interface.as_ref() {
+        return (iface_name.to_string(), iface_name.to_string());
+    }
+
     let ipv4_iface = find_dns_iface(
         false,
         &merged_state.interfaces,
@@ -395,6 +400,7 @@ fn set_iface_dns_conf(
         search: Some(searches),
         options: Some(options),
         priority,
+        ..Default::default()
     };
     if is_ipv6 {
         if let Some(ip_conf) = iface.base_iface_mut().ipv6.as_mut() {
@@ -458,6 +464,25 @@ fn get_cur_dns_ifaces(
     (v4_ifaces, v6_ifaces)
 }
 
+pub(crate) fn has_cur_iface_dns_config(
+    merged_ifaces: &MergedInterfaces,
+) -> bool {
+    let (cur_v4_ifaces, cur_v6_ifaces) = get_cur_dns_ifaces(merged_ifaces);
+    !cur_v4_ifaces.is_empty() || !cur_v6_ifaces.is_empty()
+}
+
+// Remove DNS config from all interfaces currently holding it, used when DNS
+// config is stored via NetworkManager global DNS.
+pub(crate) fn purge_cur_iface_dns_config(
+    merged_state: &mut MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let (cur_v4_ifaces, cur_v6_ifaces) =
+        get_cur_dns_ifaces(&merged_state.interfaces);
+    purge_dns_config(false, cur_v4_ifaces.as_slice(), merged_state)?;
+    purge_dns_config(true, cur_v6_ifaces.as_slice(), merged_state)?;
+    Ok(())
+}
+
 pub(crate) fn cur_dns_ifaces_still_valid_for_dns(
     merged_ifaces: &MergedInterfaces,
 ) -> bool {
@@ -578,6 +603,13 @@ pub(crate) fn store_dns_search_or_option_to_iface(
     nm_acs: &[NmActiveConnection],
     nm_devs: &[NmDevice],
 ) -> Result<(), NmstateError> {
+    if let Some(iface_name) = merged_state.dns.interface.clone() {
+        return store_dns_search_or_option_to_desired_iface(
+            iface_name.as_str(),
+            merged_state,
+        );
+    }
+
     let (cur_v4_ifaces, cur_v6_ifaces) =
         get_cur_dns_ifaces(&merged_state.interfaces);
 
@@ -667,6 +699,52 @@ fn set_iface_dns_search_or_option(
     Ok(())
 }
 
+// Store DNS search and options to the explicitly desired DNS interface.
+// IPv6 is preferred if both IP stacks are valid for DNS.
+fn store_dns_search_or_option_to_desired_iface(
+    iface_name: &str,
+    merged_state: &mut MergedNetworkState,
+) -> Result<(), NmstateError> {
+    let (cur_v4_ifaces, cur_v6_ifaces) =
+        get_cur_dns_ifaces(&merged_state.interfaces);
+    purge_dns_config(false, cur_v4_ifaces.as_slice(), merged_state)?;
+    purge_dns_config(true, cur_v6_ifaces.as_slice(), merged_state)?;
+
+    if let Some(iface) =
+        merged_state.interfaces.kernel_ifaces.get_mut(iface_name)
+    {
+        let is_ipv6 = iface.is_iface_valid_for_dns(true);
+        if !iface.is_changed() {
+            iface.mark_as_changed();
+        }
+        if let Some(apply_iface) = iface.for_apply.as_mut() {
+            if is_ipv6 {
+                if apply_iface.base_iface().ipv6.is_none() {
+                    apply_iface.base_iface_mut().ipv6 =
+                        iface.merged.base_iface_mut().ipv6.clone();
+                }
+            } else if apply_iface.base_iface().ipv4.is_none() {
+                apply_iface.base_iface_mut().ipv4 =
+                    iface.merged.base_iface_mut().ipv4.clone();
+            }
+            set_iface_dns_conf(
+                is_ipv6,
+                apply_iface,
+                Vec::new(),
+                merged_state.dns.searches.clone(),
+                merged_state.dns.options.clone(),
+                Some(DEFAULT_DNS_PRIORITY),
+            )?;
+        }
+        Ok(())
+    } else {
+        Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Desired DNS interface {iface_name} does not exist"),
+        ))
+    }
+}
+
 fn store_dns_search_or_options_to_auto_iface(
     merged_state: &mut MergedNetworkState,
     nm_acs: &[NmActiveConnection],
@@ -37,6 +37,10 @@ pub(crate) fn nm_error_to_nmstate(nm_error: NmError) -> NmstateError {
                 ),
             )
         }
+        NmErrorKind::DbusTransientError => NmstateError::new(
+            ErrorKind::BackendUnavailable,
+            format!("NetworkManager D-Bus failure: {}", nm_error.msg),
+        ),
         NmErrorKind::Connection(_) => {
             NmstateError::new(ErrorKind::InvalidArgument, nm_error.to_string())
         }
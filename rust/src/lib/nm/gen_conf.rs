@@ -1,17 +1,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{ErrorKind, MergedNetworkState, NmstateError};
+use std::collections::{HashMap, HashSet};
+use std::net::IpAddr;
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, MergedNetworkState, NmKeyfile,
+    NmstateError,
+};
 
 use super::{
     dns::{store_dns_config_to_iface, store_dns_search_or_option_to_iface},
+    nm_dbus::{NmConnection, NmSettingIp, NmSettingIpMethod},
     profile::perpare_nm_conns,
     route::store_route_config,
     route_rule::store_route_rule_config,
+    settings::iface_type_to_nm,
 };
 
 pub(crate) fn nm_gen_conf(
     merged_state: &MergedNetworkState,
-) -> Result<Vec<(String, String)>, NmstateError> {
+) -> Result<Vec<NmKeyfile>, NmstateError> {
     if merged_state
         .hostname
         .desired
@@ -25,6 +33,17 @@ pub(crate) fn nm_gen_conf(
         );
     }
 
+    if merged_state.connectivity_check.is_changed() {
+        log::warn!(
+            "Cannot store connectivity check configuration to keyfile \
+            of NetworkManager, please edit NetworkManager.conf manually"
+        );
+    }
+
+    merged_state
+        .interfaces
+        .validate_macsec_mka_keys_for_gen_conf()?;
+
     let mut merged_state = merged_state.clone();
     store_route_config(&mut merged_state)?;
     store_route_rule_config(&mut merged_state)?;
@@ -39,6 +58,7 @@ pub(crate) fn nm_gen_conf(
         &Vec::new(),
         &Vec::new(),
         true, // MPTCP support enabled
+        true, // accept-all-mac-addresses support enabled
         true, // gen_conf mode
     )?
     .to_store;
@@ -48,7 +68,11 @@ pub(crate) fn nm_gen_conf(
         match nm_conn.to_keyfile() {
             Ok(s) => {
                 if let Some(id) = nm_conn.id() {
-                    ret.push((format!("{id}.nmconnection"), s));
+                    ret.push(NmKeyfile {
+                        file_name: format!("{id}.nmconnection"),
+                        content: s,
+                        uuid: nm_conn.uuid().unwrap_or_default().to_string(),
+                    });
                 }
             }
             Err(e) => {
@@ -63,3 +87,203 @@ pub(crate) fn nm_gen_conf(
     }
     Ok(ret)
 }
+
+// Parse the keyfiles back into NmConnection and compare them with desired
+// interfaces on interface type, controller, IP method and addresses and MTU.
+pub(crate) fn nm_verify_keyfiles(
+    merged_state: &MergedNetworkState,
+    keyfiles: &[&str],
+) -> Result<(), NmstateError> {
+    let mut nm_conns = Vec::new();
+    for keyfile in keyfiles {
+        nm_conns.push(NmConnection::from_keyfile(keyfile).map_err(|e| {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Failed to parse keyfile: {e}"),
+            );
+            log::error!("{}", e);
+            e
+        })?);
+    }
+    // HashMap of UUID to interface name
+    let uuid_to_iface: HashMap<&str, &str> = nm_conns
+        .iter()
+        .filter_map(|c| c.connection.as_ref())
+        .filter_map(|c| Some((c.uuid.as_deref()?, c.iface_name.as_deref()?)))
+        .collect();
+
+    for iface in merged_state
+        .interfaces
+        .iter()
+        .filter_map(|i| i.for_apply.as_ref())
+        .filter(|i| i.is_up())
+    {
+        let nm_iface_type = match iface_type_to_nm(&iface.iface_type()) {
+            Ok(t) => t,
+            Err(_) => continue,
+        };
+        let nm_conn = match nm_conns.iter().find(|c| {
+            c.iface_name() == Some(iface.name())
+                && c.iface_type() == Some(nm_iface_type.as_str())
+        }) {
+            Some(c) => c,
+            None => {
+                return Err(verify_error(format!(
+                    "No keyfile found for interface {} with type {}",
+                    iface.name(),
+                    iface.iface_type()
+                )));
+            }
+        };
+        verify_nm_conn(iface, nm_conn, &uuid_to_iface)?;
+    }
+    Ok(())
+}
+
+fn verify_nm_conn(
+    iface: &Interface,
+    nm_conn: &NmConnection,
+    uuid_to_iface: &HashMap<&str, &str>,
+) -> Result<(), NmstateError> {
+    let base_iface = iface.base_iface();
+    // OVS system interface is attached to OVS port profile instead of
+    // OVS bridge
+    if base_iface.controller_type != Some(InterfaceType::OvsBridge) {
+        let desired_ctrl =
+            base_iface.controller.as_deref().filter(|c| !c.is_empty());
+        let ctrl = nm_conn
+            .connection
+            .as_ref()
+            .and_then(|c| c.controller.as_deref())
+            .map(|c| uuid_to_iface.get(c).copied().unwrap_or(c));
+        if desired_ctrl != ctrl {
+            return Err(verify_error(format!(
+                "Keyfile of interface {} has controller {:?}, but desired \
+                {:?}",
+                iface.name(),
+                ctrl,
+                desired_ctrl
+            )));
+        }
+    }
+
+    // InfiniBand and loopback interfaces have no ethernet setting
+    if let Some(mtu) = base_iface.mtu.filter(|_| {
+        !matches!(
+            iface.iface_type(),
+            InterfaceType::InfiniBand | InterfaceType::Loopback
+        )
+    }) {
+        let nm_mtu = nm_conn.wired.as_ref().and_then(|w| w.mtu);
+        if nm_mtu != Some(mtu as u32) {
+            return Err(verify_error(format!(
+                "Keyfile of interface {} has MTU {nm_mtu:?}, but desired \
+                {mtu}",
+                iface.name(),
+            )));
+        }
+    }
+
+    if let Some(ipv4) = base_iface.ipv4.as_ref() {
+        let addrs: HashSet<(IpAddr, u8)> = ipv4
+            .addresses
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|a| !a.is_auto())
+            .map(|a| (a.ip, a.prefix_length))
+            .collect();
+        let expected_method = if !ipv4.enabled {
+            Some(NmSettingIpMethod::Disabled)
+        } else if ipv4.dhcp == Some(true) {
+            Some(NmSettingIpMethod::Auto)
+        } else {
+            None
+        };
+        verify_nm_ip(
+            iface.name(),
+            "ipv4",
+            nm_conn.ipv4.as_ref(),
+            expected_method,
+            &addrs,
+        )?;
+    }
+    if let Some(ipv6) = base_iface.ipv6.as_ref() {
+        let addrs: HashSet<(IpAddr, u8)> = ipv6
+            .addresses
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|a| !a.is_auto())
+            .map(|a| (a.ip, a.prefix_length))
+            .collect();
+        let expected_method = if !ipv6.enabled {
+            Some(NmSettingIpMethod::Disabled)
+        } else if ipv6.dhcp == Some(true) {
+            Some(if ipv6.autoconf == Some(true) {
+                NmSettingIpMethod::Auto
+            } else {
+                NmSettingIpMethod::Dhcp
+            })
+        } else {
+            None
+        };
+        verify_nm_ip(
+            iface.name(),
+            "ipv6",
+            nm_conn.ipv6.as_ref(),
+            expected_method,
+            &addrs,
+        )?;
+    }
+    Ok(())
+}
+
+fn verify_nm_ip(
+    iface_name: &str,
+    family: &str,
+    nm_ip_set: Option<&NmSettingIp>,
+    expected_method: Option<NmSettingIpMethod>,
+    desired_addrs: &HashSet<(IpAddr, u8)>,
+) -> Result<(), NmstateError> {
+    let method = nm_ip_set.and_then(|s| s.method.as_ref());
+    if let Some(expected_method) = expected_method.as_ref() {
+        if method != Some(expected_method) {
+            return Err(verify_error(format!(
+                "Keyfile of interface {iface_name} has {family} method \
+                {method:?}, but desired {expected_method}"
+            )));
+        }
+    }
+    let nm_addrs: HashSet<(IpAddr, u8)> = nm_ip_set
+        .map(|s| s.addresses.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|a| parse_ip_prefix(a))
+        .collect();
+    if &nm_addrs != desired_addrs {
+        return Err(verify_error(format!(
+            "Keyfile of interface {iface_name} has {family} addresses \
+            {:?}, but desired {:?}",
+            nm_ip_set
+                .map(|s| s.addresses.as_slice())
+                .unwrap_or_default(),
+            desired_addrs
+                .iter()
+                .map(|(ip, prefix)| format!("{ip}/{prefix}"))
+                .collect::<Vec<String>>()
+        )));
+    }
+    Ok(())
+}
+
+fn parse_ip_prefix(addr: &str) -> Option<(IpAddr, u8)> {
+    let (ip, prefix) = addr.split_once('/')?;
+    Some((ip.parse().ok()?, prefix.parse().ok()?))
+}
+
+fn verify_error(msg: String) -> NmstateError {
+    let e = NmstateError::new(ErrorKind::VerificationError, msg);
+    log::error!("{}", e);
+    e
+}
@@ -27,12 +27,27 @@ mod version;
 
 #[cfg(feature = "query_apply")]
 pub(crate) use checkpoint::{
-    nm_checkpoint_create, nm_checkpoint_destroy, nm_checkpoint_rollback,
-    nm_checkpoint_timeout_extend,
+    nm_checkpoint_create, nm_checkpoint_destroy, nm_checkpoint_exists,
+    nm_checkpoint_rollback, nm_checkpoint_timeout_extend, NmCheckpoint,
 };
 #[cfg(feature = "gen_conf")]
-pub(crate) use gen_conf::nm_gen_conf;
-#[cfg(feature = "query_apply")]
-pub(crate) use query_apply::nm_apply;
+pub(crate) use gen_conf::{nm_gen_conf, nm_verify_keyfiles};
+#[cfg(all(test, feature = "query_apply"))]
+pub(crate) use query_apply::dns::is_dns_apply_required;
+#[cfg(feature = "query_apply")]
+pub(crate) use query_apply::{
+    nm_apply, nm_apply_hostname_dns_only, nm_can_apply_hostname_dns_only,
+    nm_hostname_set, nm_lldp_neighbors_wait_change, nm_secret_agent_start,
+    nm_stale_profiles_get, NmConnectivityConfBackup,
+};
+#[cfg(feature = "gen_conf")]
+pub(crate) use settings::gen_nm_bond_options;
 #[cfg(feature = "query_apply")]
 pub(crate) use show::nm_retrieve;
+#[cfg(all(test, feature = "query_apply"))]
+pub(crate) use version::check_feature_versions;
+#[cfg(feature = "query_apply")]
+pub(crate) use version::{
+    is_nm_version_at_least, nm_check_features, nm_unsupported_features,
+    nm_version,
+};
@@ -11,7 +11,7 @@ use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
 #[non_exhaustive]
 pub struct NmSettingBond {
     pub options: HashMap<String, String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingBond {
@@ -47,7 +47,7 @@ impl ToDbusValue for NmSettingBond {
 pub struct NmSettingBondPort {
     pub priority: Option<i32>,
     pub queue_id: Option<u32>,
-    _other: DbusDictionary,
+    pub(super) _other: DbusDictionary,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingBondPort {
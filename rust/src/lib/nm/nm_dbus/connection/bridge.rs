@@ -56,7 +56,7 @@ pub struct NmSettingBridge {
     pub vlan_protocol: Option<NmVlanProtocol>,
     pub vlan_stats_enabled: Option<bool>,
     pub vlans: Option<Vec<NmSettingBridgeVlanRange>>,
-    _other: DbusDictionary,
+    pub(super) _other: DbusDictionary,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingBridge {
@@ -346,7 +346,7 @@ pub struct NmSettingBridgePort {
     pub path_cost: Option<u32>,
     pub priority: Option<u32>,
     pub vlans: Option<Vec<NmSettingBridgeVlanRange>>,
-    _other: DbusDictionary,
+    pub(super) _other: DbusDictionary,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingBridgePort {
@@ -12,9 +12,12 @@ use super::super::{
     connection::bond::{NmSettingBond, NmSettingBondPort},
     connection::bridge::{NmSettingBridge, NmSettingBridgePort},
     connection::ethtool::NmSettingEthtool,
+    connection::gsm::NmSettingGsm,
     connection::ieee8021x::NmSetting8021X,
     connection::infiniband::NmSettingInfiniBand,
     connection::ip::NmSettingIp,
+    connection::ip_tunnel::NmSettingIpTunnel,
+    connection::ip_vlan::NmSettingIpVlan,
     connection::loopback::NmSettingLoopback,
     connection::mac_vlan::NmSettingMacVlan,
     connection::macsec::NmSettingMacSec,
@@ -96,7 +99,9 @@ pub struct NmConnection {
     pub wired: Option<NmSettingWired>,
     pub vlan: Option<NmSettingVlan>,
     pub vxlan: Option<NmSettingVxlan>,
+    pub ip_tunnel: Option<NmSettingIpTunnel>,
     pub mac_vlan: Option<NmSettingMacVlan>,
+    pub ip_vlan: Option<NmSettingIpVlan>,
     pub sriov: Option<NmSettingSriov>,
     pub vrf: Option<NmSettingVrf>,
     pub veth: Option<NmSettingVeth>,
@@ -106,12 +111,13 @@ pub struct NmConnection {
     pub infiniband: Option<NmSettingInfiniBand>,
     pub loopback: Option<NmSettingLoopback>,
     pub macsec: Option<NmSettingMacSec>,
+    pub gsm: Option<NmSettingGsm>,
     pub vpn: Option<NmSettingVpn>,
     #[serde(skip)]
     pub obj_path: String,
     #[serde(skip)]
     pub(crate) flags: Vec<NmSettingsConnectionFlag>,
-    _other: HashMap<String, HashMap<String, zvariant::OwnedValue>>,
+    pub(crate) _other: HashMap<String, HashMap<String, zvariant::OwnedValue>>,
 }
 
 // The signature is the same as the NmConnectionDbusOwnedValue because we are
@@ -169,9 +175,12 @@ impl TryFrom<NmConnectionDbusOwnedValue> for NmConnection {
             wired: _from_map!(v, "802-3-ethernet", NmSettingWired::try_from)?,
             vlan: _from_map!(v, "vlan", NmSettingVlan::try_from)?,
             vxlan: _from_map!(v, "vxlan", NmSettingVxlan::try_from)?,
+            ip_tunnel: _from_map!(v, "ip-tunnel", NmSettingIpTunnel::try_from)?,
             sriov: _from_map!(v, "sriov", NmSettingSriov::try_from)?,
             mac_vlan: _from_map!(v, "macvlan", NmSettingMacVlan::try_from)?,
+            ip_vlan: _from_map!(v, "ipvlan", NmSettingIpVlan::try_from)?,
             macsec: _from_map!(v, "macsec", NmSettingMacSec::try_from)?,
+            gsm: _from_map!(v, "gsm", NmSettingGsm::try_from)?,
             vrf: _from_map!(v, "vrf", NmSettingVrf::try_from)?,
             veth: _from_map!(v, "veth", NmSettingVeth::try_from)?,
             ieee8021x: _from_map!(v, "802-1x", NmSetting8021X::try_from)?,
@@ -190,6 +199,81 @@ impl TryFrom<NmConnectionDbusOwnedValue> for NmConnection {
     }
 }
 
+// Get the `_other` of setting struct matching NetworkManager setting name
+macro_rules! _setting_other {
+    ($nm_conn:ident, $setting_name:expr, $as_ref:ident, $($ref:tt)+) => {
+        match $setting_name {
+            "connection" => {
+                $nm_conn.connection.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "bond" => $nm_conn.bond.$as_ref().map(|s| $($ref)+ s._other),
+            "bond-port" => {
+                $nm_conn.bond_port.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "bridge" => $nm_conn.bridge.$as_ref().map(|s| $($ref)+ s._other),
+            "bridge-port" => {
+                $nm_conn.bridge_port.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "ipv4" => $nm_conn.ipv4.$as_ref().map(|s| $($ref)+ s._other),
+            "ipv6" => $nm_conn.ipv6.$as_ref().map(|s| $($ref)+ s._other),
+            "ovs-bridge" => {
+                $nm_conn.ovs_bridge.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "ovs-port" => {
+                $nm_conn.ovs_port.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "ovs-interface" => {
+                $nm_conn.ovs_iface.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "ovs-external-ids" => {
+                $nm_conn.ovs_ext_ids.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "ovs-other-config" => $nm_conn
+                .ovs_other_config
+                .$as_ref()
+                .map(|s| $($ref)+ s._other),
+            "ovs-patch" => {
+                $nm_conn.ovs_patch.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "ovs-dpdk" => {
+                $nm_conn.ovs_dpdk.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "802-3-ethernet" => {
+                $nm_conn.wired.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "vlan" => $nm_conn.vlan.$as_ref().map(|s| $($ref)+ s._other),
+            "vxlan" => $nm_conn.vxlan.$as_ref().map(|s| $($ref)+ s._other),
+            "ip-tunnel" => {
+                $nm_conn.ip_tunnel.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "macvlan" => {
+                $nm_conn.mac_vlan.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "ipvlan" => $nm_conn.ip_vlan.$as_ref().map(|s| $($ref)+ s._other),
+            "sriov" => $nm_conn.sriov.$as_ref().map(|s| $($ref)+ s._other),
+            "vrf" => $nm_conn.vrf.$as_ref().map(|s| $($ref)+ s._other),
+            "veth" => $nm_conn.veth.$as_ref().map(|s| $($ref)+ s._other),
+            "802-1x" => {
+                $nm_conn.ieee8021x.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "user" => $nm_conn.user.$as_ref().map(|s| $($ref)+ s._other),
+            "ethtool" => {
+                $nm_conn.ethtool.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "infiniband" => {
+                $nm_conn.infiniband.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "loopback" => {
+                $nm_conn.loopback.$as_ref().map(|s| $($ref)+ s._other)
+            }
+            "macsec" => $nm_conn.macsec.$as_ref().map(|s| $($ref)+ s._other),
+            "gsm" => $nm_conn.gsm.$as_ref().map(|s| $($ref)+ s._other),
+            "vpn" => $nm_conn.vpn.$as_ref().map(|s| $($ref)+ s._other),
+            _ => None,
+        }
+    };
+}
+
 impl NmConnection {
     pub fn iface_name(&self) -> Option<&str> {
         _connection_inner_string_member!(self, iface_name)
@@ -211,6 +295,22 @@ impl NmConnection {
         _connection_inner_string_member!(self, controller_type)
     }
 
+    // Mark the secrets not defined as owned by secret agent, so that
+    // NetworkManager will request them from registered secret agent during
+    // activation.
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn set_missing_secrets_agent_owned(&mut self) {
+        if let Some(ieee8021x_set) = self.ieee8021x.as_mut() {
+            ieee8021x_set.set_missing_secrets_agent_owned();
+        }
+        if let Some(macsec_set) = self.macsec.as_mut() {
+            macsec_set.set_missing_secrets_agent_owned();
+        }
+        if let Some(gsm_set) = self.gsm.as_mut() {
+            gsm_set.set_missing_secrets_agent_owned();
+        }
+    }
+
     #[cfg(feature = "query_apply")]
     pub(crate) fn to_value(&self) -> Result<NmConnectionDbusValue, NmError> {
         let mut ret = HashMap::new();
@@ -262,15 +362,24 @@ impl NmConnection {
         if let Some(vxlan) = &self.vxlan {
             ret.insert("vxlan", vxlan.to_value()?);
         }
+        if let Some(ip_tunnel) = &self.ip_tunnel {
+            ret.insert("ip-tunnel", ip_tunnel.to_value()?);
+        }
         if let Some(sriov) = &self.sriov {
             ret.insert("sriov", sriov.to_value()?);
         }
         if let Some(mac_vlan) = &self.mac_vlan {
             ret.insert("macvlan", mac_vlan.to_value()?);
         }
+        if let Some(ip_vlan) = &self.ip_vlan {
+            ret.insert("ipvlan", ip_vlan.to_value()?);
+        }
         if let Some(macsec) = &self.macsec {
             ret.insert("macsec", macsec.to_value()?);
         }
+        if let Some(gsm) = &self.gsm {
+            ret.insert("gsm", gsm.to_value()?);
+        }
         if let Some(vrf) = &self.vrf {
             ret.insert("vrf", vrf.to_value()?);
         }
@@ -298,20 +407,159 @@ impl NmConnection {
         if let Some(v) = &self.vpn {
             ret.insert("vpn", v.to_value()?);
         }
+        // Merge into known setting as it might hold properties not
+        // supported by above setting structs
         for (key, setting_value) in &self._other {
-            let mut other_setting_value: HashMap<&str, zvariant::Value> =
-                HashMap::new();
+            let other_setting_value = ret.entry(key.as_str()).or_default();
             for (sub_key, sub_value) in setting_value {
                 other_setting_value.insert(
                     sub_key.as_str(),
                     zvariant::Value::from(sub_value.clone()),
                 );
             }
-            ret.insert(key, other_setting_value);
         }
         Ok(ret)
     }
 
+    // Properties not supported by setting structs are stored in the `_other`
+    // of that setting struct, or in the `_other` of NmConnection when the
+    // whole setting is not supported.
+    pub(crate) fn get_other_setting_prop(
+        &self,
+        setting_name: &str,
+        prop_name: &str,
+    ) -> Option<&zvariant::OwnedValue> {
+        _setting_other!(self, setting_name, as_ref, &)
+            .and_then(|setting| setting.get(prop_name))
+            .or_else(|| {
+                self._other
+                    .get(setting_name)
+                    .and_then(|setting| setting.get(prop_name))
+            })
+    }
+
+    // Store property not supported by setting structs, it will override
+    // the existing value of the same property.
+    pub(crate) fn set_other_setting_prop(
+        &mut self,
+        setting_name: &str,
+        prop_name: &str,
+        value: zvariant::OwnedValue,
+    ) {
+        self.remove_other_setting_prop(setting_name, prop_name);
+        if let Some(setting) = _setting_other!(self, setting_name, as_mut, &mut)
+        {
+            setting.insert(prop_name.to_string(), value);
+        } else {
+            self._other
+                .entry(setting_name.to_string())
+                .or_default()
+                .insert(prop_name.to_string(), value);
+        }
+    }
+
+    pub(crate) fn remove_other_setting_prop(
+        &mut self,
+        setting_name: &str,
+        prop_name: &str,
+    ) {
+        if let Some(setting) = _setting_other!(self, setting_name, as_mut, &mut)
+        {
+            setting.remove(prop_name);
+        }
+        if let Some(setting) = self._other.get_mut(setting_name) {
+            setting.remove(prop_name);
+        }
+    }
+
+    // Whether specified property is supported by setting structs, the
+    // supported properties are not stored in `_other` after parsing.
+    pub(crate) fn is_setting_prop_supported(
+        setting_name: &str,
+        prop_name: &str,
+    ) -> bool {
+        let mut nm_conn_value = NmConnectionDbusOwnedValue::new();
+        nm_conn_value.insert(
+            setting_name.to_string(),
+            DbusDictionary::from([(
+                prop_name.to_string(),
+                zvariant::OwnedValue::from(zvariant::Value::new(true)),
+            )]),
+        );
+        match NmConnection::try_from(nm_conn_value) {
+            Ok(nm_conn) => nm_conn
+                .get_other_setting_prop(setting_name, prop_name)
+                .is_none(),
+            // Supported property holding other type than boolean
+            Err(_) => true,
+        }
+    }
+
+    // The settings regenerated by nmstate only hold properties supported by
+    // nmstate, copy the unknown properties and unknown settings from the
+    // existing profile to prevent losing them. Settings removed by nmstate
+    // are not restored.
+    pub(crate) fn preserve_unknown_settings(&mut self, exist: &NmConnection) {
+        macro_rules! _preserve_setting_other {
+            ($($setting:ident),+ $(,)?) => {
+                $(
+                    if let (Some(new_set), Some(exist_set)) =
+                        (self.$setting.as_mut(), exist.$setting.as_ref())
+                    {
+                        for (key, value) in exist_set._other.iter() {
+                            new_set
+                                ._other
+                                .entry(key.to_string())
+                                .or_insert_with(|| value.clone());
+                        }
+                    }
+                )+
+            };
+        }
+        _preserve_setting_other!(
+            connection,
+            bond,
+            bond_port,
+            bridge,
+            bridge_port,
+            ipv4,
+            ipv6,
+            ovs_bridge,
+            ovs_port,
+            ovs_iface,
+            ovs_ext_ids,
+            ovs_other_config,
+            ovs_patch,
+            ovs_dpdk,
+            wired,
+            vlan,
+            vxlan,
+            ip_tunnel,
+            mac_vlan,
+            ip_vlan,
+            sriov,
+            vrf,
+            veth,
+            ieee8021x,
+            user,
+            ethtool,
+            infiniband,
+            loopback,
+            macsec,
+            gsm,
+            vpn,
+        );
+        for (setting_name, exist_setting) in exist._other.iter() {
+            let setting =
+                self._other.entry(setting_name.to_string()).or_default();
+            for (key, value) in exist_setting.iter() {
+                setting
+                    .entry(key.to_string())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+
     pub fn set_parent(&mut self, parent: &str) {
         if let Some(setting) = self.vlan.as_mut() {
             setting.parent = Some(parent.to_string());
@@ -325,9 +573,15 @@ impl NmConnection {
         if let Some(setting) = self.mac_vlan.as_mut() {
             setting.parent = Some(parent.to_string());
         }
+        if let Some(setting) = self.ip_vlan.as_mut() {
+            setting.parent = Some(parent.to_string());
+        }
         if let Some(setting) = self.macsec.as_mut() {
             setting.parent = Some(parent.to_string());
         }
+        if let Some(setting) = self.ip_tunnel.as_mut() {
+            setting.parent = Some(parent.to_string());
+        }
     }
 
     pub fn uuid(&self) -> Option<&str> {
@@ -354,7 +608,7 @@ pub struct NmSettingConnection {
     pub autoconnect_ports: Option<bool>,
     pub lldp: Option<bool>,
     pub mptcp_flags: Option<u32>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingConnection {
@@ -476,6 +730,15 @@ pub(crate) fn nm_con_get_from_obj_path(
             }
         }
     }
+    if let Some(gsm_conf) = nm_conn.gsm.as_mut() {
+        if let Ok(nm_secrets) =
+            proxy.call::<&str, NmConnectionDbusOwnedValue>("GetSecrets", &"gsm")
+        {
+            if let Some(nm_secret) = nm_secrets.get("gsm") {
+                gsm_conf.fill_secrets(nm_secret);
+            }
+        }
+    }
     if let Some(vpn_conf) = nm_conn.vpn.as_mut() {
         if let Ok(nm_secrets) =
             proxy.call::<&str, NmConnectionDbusOwnedValue>("GetSecrets", &"vpn")
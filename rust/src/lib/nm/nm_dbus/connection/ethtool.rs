@@ -102,7 +102,7 @@ pub struct NmSettingEthtool {
     pub ring_rx_jumbo: Option<u32>,
     pub ring_rx_mini: Option<u32>,
     pub ring_tx: Option<u32>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingEthtool {
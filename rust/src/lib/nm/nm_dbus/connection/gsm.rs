@@ -0,0 +1,81 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+#[cfg(feature = "query_apply")]
+use super::NM_SETTING_SECRET_FLAG_AGENT_OWNED;
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingGsm {
+    pub apn: Option<String>,
+    pub pin: Option<String>,
+    pub pin_flags: Option<u32>,
+    pub home_only: Option<bool>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
+}
+
+impl TryFrom<DbusDictionary> for NmSettingGsm {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            apn: _from_map!(v, "apn", String::try_from)?,
+            pin: None,
+            pin_flags: _from_map!(v, "pin-flags", u32::try_from)?,
+            home_only: _from_map!(v, "home-only", bool::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingGsm {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = &self.apn {
+            ret.insert("apn", zvariant::Value::new(v.clone()));
+        }
+        if let Some(v) = &self.pin {
+            ret.insert("pin", zvariant::Value::new(v.clone()));
+        }
+        if let Some(v) = self.pin_flags {
+            ret.insert("pin-flags", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.home_only {
+            ret.insert("home-only", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
+
+impl NmSettingGsm {
+    // Let NetworkManager request the missing SIM PIN from secret agent
+    // instead of storing it.
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn set_missing_secrets_agent_owned(&mut self) {
+        if self.pin.is_none() {
+            self.pin_flags = Some(NM_SETTING_SECRET_FLAG_AGENT_OWNED);
+        }
+    }
+
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn fill_secrets(&mut self, secrets: &DbusDictionary) {
+        if let Some(v) = secrets.get("pin") {
+            match String::try_from(v.clone()) {
+                Ok(s) => {
+                    self.pin = Some(s);
+                }
+                Err(e) => {
+                    log::warn!("Failed to convert gsm pin: {:?} {:?}", v, e);
+                }
+            }
+        }
+    }
+}
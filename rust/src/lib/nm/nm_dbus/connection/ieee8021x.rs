@@ -6,6 +6,8 @@ use serde::Deserialize;
 use super::super::{
     connection::DbusDictionary, ErrorKind, NmError, ToDbusValue,
 };
+#[cfg(feature = "query_apply")]
+use super::NM_SETTING_SECRET_FLAG_AGENT_OWNED;
 
 const GLIB_FILE_PATH_PREFIX: &str = "file://";
 
@@ -19,7 +21,8 @@ pub struct NmSetting8021X {
     pub client_cert: Option<Vec<u8>>,
     pub ca_cert: Option<Vec<u8>>,
     pub private_key_password: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub private_key_password_flags: Option<u32>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSetting8021X {
@@ -32,6 +35,11 @@ impl TryFrom<DbusDictionary> for NmSetting8021X {
             client_cert: _from_map!(v, "client-cert", <Vec<u8>>::try_from)?,
             ca_cert: _from_map!(v, "ca-cert", <Vec<u8>>::try_from)?,
             private_key_password: None,
+            private_key_password_flags: _from_map!(
+                v,
+                "private-key-password-flags",
+                u32::try_from
+            )?,
             _other: v,
         })
     }
@@ -58,6 +66,9 @@ impl ToDbusValue for NmSetting8021X {
         if let Some(v) = &self.private_key_password {
             ret.insert("private-key-password", zvariant::Value::new(v));
         }
+        if let Some(v) = &self.private_key_password_flags {
+            ret.insert("private-key-password-flags", zvariant::Value::new(v));
+        }
         ret.extend(self._other.iter().map(|(key, value)| {
             (key.as_str(), zvariant::Value::from(value.clone()))
         }));
@@ -66,6 +77,16 @@ impl ToDbusValue for NmSetting8021X {
 }
 
 impl NmSetting8021X {
+    // Let NetworkManager request the missing private key password from
+    // secret agent instead of storing it.
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn set_missing_secrets_agent_owned(&mut self) {
+        if self.private_key.is_some() && self.private_key_password.is_none() {
+            self.private_key_password_flags =
+                Some(NM_SETTING_SECRET_FLAG_AGENT_OWNED);
+        }
+    }
+
     #[cfg(feature = "query_apply")]
     pub(crate) fn fill_secrets(&mut self, secrets: &DbusDictionary) {
         if let Some(v) = secrets.get("private-key-password") {
@@ -13,7 +13,7 @@ pub struct NmSettingInfiniBand {
     pub pkey: Option<i32>,
     pub mode: Option<String>,
     pub mtu: Option<u32>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingInfiniBand {
@@ -19,6 +19,7 @@ use super::super::{
     connection::route_rule::{
         nm_ip_rules_to_value, parse_nm_ip_rule_data, NmIpRouteRule,
     },
+    connection::sriov::{NM_TERNARY_FALSE, NM_TERNARY_TRUE},
     connection::DbusDictionary,
     ErrorKind, NmError, ToDbusValue,
 };
@@ -98,8 +99,11 @@ pub struct NmSettingIp {
     pub route_table: Option<u32>,
     pub dhcp_client_id: Option<String>,
     pub dhcp_timeout: Option<i32>,
+    // IPv4 only
+    pub dad_timeout: Option<i32>,
     pub gateway: Option<String>,
     pub may_fail: Option<bool>,
+    pub required_timeout: Option<i32>,
     pub route_metric: Option<i64>,
     // IPv6 only
     pub ra_timeout: Option<i32>,
@@ -114,7 +118,8 @@ pub struct NmSettingIp {
     pub dhcp_send_hostname: Option<bool>,
     pub dhcp_fqdn: Option<String>,
     pub dhcp_hostname: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub dhcp_send_release: Option<bool>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingIp {
@@ -140,6 +145,7 @@ impl TryFrom<DbusDictionary> for NmSettingIp {
             )?,
             dhcp_client_id: _from_map!(v, "dhcp-client-id", String::try_from)?,
             dhcp_timeout: _from_map!(v, "dhcp-timeout", i32::try_from)?,
+            dad_timeout: _from_map!(v, "dad-timeout", i32::try_from)?,
             ra_timeout: _from_map!(v, "ra-timeout", i32::try_from)?,
             addr_gen_mode: _from_map!(v, "addr-gen-mode", i32::try_from)?,
             dhcp_duid: _from_map!(v, "dhcp-duid", String::try_from)?,
@@ -147,6 +153,7 @@ impl TryFrom<DbusDictionary> for NmSettingIp {
             route_table: _from_map!(v, "route-table", u32::try_from)?,
             gateway: _from_map!(v, "gateway", String::try_from)?,
             may_fail: _from_map!(v, "may-fail", bool::try_from)?,
+            required_timeout: _from_map!(v, "required-timeout", i32::try_from)?,
             route_metric: _from_map!(v, "route-metric", i64::try_from)?,
             token: _from_map!(v, "token", String::try_from)?,
             dhcp_send_hostname: _from_map!(
@@ -156,6 +163,15 @@ impl TryFrom<DbusDictionary> for NmSettingIp {
             )?,
             dhcp_fqdn: _from_map!(v, "dhcp-fqdn", String::try_from)?,
             dhcp_hostname: _from_map!(v, "dhcp-hostname", String::try_from)?,
+            dhcp_send_release: match _from_map!(
+                v,
+                "dhcp-send-release",
+                i32::try_from
+            )? {
+                Some(NM_TERNARY_TRUE) => Some(true),
+                Some(NM_TERNARY_FALSE) => Some(false),
+                _ => None,
+            },
             ..Default::default()
         };
 
@@ -257,6 +273,9 @@ impl ToDbusValue for NmSettingIp {
         if let Some(v) = self.ra_timeout {
             ret.insert("ra-timeout", zvariant::Value::new(v));
         }
+        if let Some(v) = self.dad_timeout {
+            ret.insert("dad-timeout", zvariant::Value::new(v));
+        }
         if let Some(v) = self.addr_gen_mode {
             ret.insert("addr-gen-mode", zvariant::Value::new(v));
         }
@@ -275,6 +294,9 @@ impl ToDbusValue for NmSettingIp {
         if let Some(v) = &self.may_fail {
             ret.insert("may-fail", zvariant::Value::new(v));
         }
+        if let Some(v) = &self.required_timeout {
+            ret.insert("required-timeout", zvariant::Value::new(v));
+        }
         if let Some(v) = &self.route_metric {
             ret.insert("route-metric", zvariant::Value::new(v));
         }
@@ -290,6 +312,15 @@ impl ToDbusValue for NmSettingIp {
         if let Some(v) = &self.dhcp_hostname {
             ret.insert("dhcp-hostname", zvariant::Value::new(v));
         }
+        if let Some(v) = &self.dhcp_send_release {
+            ret.insert(
+                "dhcp-send-release",
+                zvariant::Value::new(match v {
+                    true => NM_TERNARY_TRUE,
+                    false => NM_TERNARY_FALSE,
+                }),
+            );
+        }
         ret.extend(self._other.iter().map(|(key, value)| {
             (key.as_str(), zvariant::Value::from(value.clone()))
         }));
@@ -0,0 +1,104 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+
+#[derive(Debug, Clone, PartialEq, Default, Deserialize)]
+#[serde(try_from = "DbusDictionary")]
+#[non_exhaustive]
+pub struct NmSettingIpTunnel {
+    pub mode: Option<u32>,
+    pub parent: Option<String>,
+    pub local: Option<String>,
+    pub remote: Option<String>,
+    pub input_key: Option<String>,
+    pub output_key: Option<String>,
+    pub ttl: Option<u32>,
+    pub tos: Option<u32>,
+    pub encapsulation_limit: Option<u32>,
+    pub flow_label: Option<u32>,
+    pub mtu: Option<u32>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
+}
+
+impl NmSettingIpTunnel {
+    pub const MODE_GRE: u32 = 2;
+    pub const MODE_IPIP6: u32 = 5;
+    pub const MODE_IP6IP6: u32 = 6;
+    pub const MODE_IP6GRE: u32 = 8;
+    pub const MODE_GRETAP: u32 = 10;
+    pub const MODE_IP6GRETAP: u32 = 11;
+}
+
+impl TryFrom<DbusDictionary> for NmSettingIpTunnel {
+    type Error = NmError;
+    fn try_from(mut v: DbusDictionary) -> Result<Self, Self::Error> {
+        Ok(Self {
+            mode: _from_map!(v, "mode", u32::try_from)?,
+            parent: _from_map!(v, "parent", String::try_from)?,
+            local: _from_map!(v, "local", String::try_from)?,
+            remote: _from_map!(v, "remote", String::try_from)?,
+            input_key: _from_map!(v, "input-key", String::try_from)?,
+            output_key: _from_map!(v, "output-key", String::try_from)?,
+            ttl: _from_map!(v, "ttl", u32::try_from)?,
+            tos: _from_map!(v, "tos", u32::try_from)?,
+            encapsulation_limit: _from_map!(
+                v,
+                "encapsulation-limit",
+                u32::try_from
+            )?,
+            flow_label: _from_map!(v, "flow-label", u32::try_from)?,
+            mtu: _from_map!(v, "mtu", u32::try_from)?,
+            _other: v,
+        })
+    }
+}
+
+impl ToDbusValue for NmSettingIpTunnel {
+    fn to_value(&self) -> Result<HashMap<&str, zvariant::Value>, NmError> {
+        let mut ret = HashMap::new();
+        if let Some(v) = self.mode {
+            ret.insert("mode", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.parent.as_deref() {
+            if !v.is_empty() {
+                ret.insert("parent", zvariant::Value::new(v));
+            }
+        }
+        if let Some(v) = &self.local {
+            ret.insert("local", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.remote {
+            ret.insert("remote", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.input_key {
+            ret.insert("input-key", zvariant::Value::new(v));
+        }
+        if let Some(v) = &self.output_key {
+            ret.insert("output-key", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.ttl {
+            ret.insert("ttl", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.tos {
+            ret.insert("tos", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.encapsulation_limit {
+            ret.insert("encapsulation-limit", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.flow_label {
+            ret.insert("flow-label", zvariant::Value::new(v));
+        }
+        if let Some(v) = self.mtu {
+            ret.insert("mtu", zvariant::Value::new(v));
+        }
+        ret.extend(self._other.iter().map(|(key, value)| {
+            (key.as_str(), zvariant::Value::from(value.clone()))
+        }));
+        Ok(ret)
+    }
+}
@@ -13,7 +13,7 @@ pub struct NmSettingMacVlan {
     pub mode: Option<u32>,
     pub accept_all_mac: Option<bool>,
     pub tap: Option<bool>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingMacVlan {
@@ -4,6 +4,11 @@ use std::convert::TryFrom;
 use serde::Deserialize;
 
 use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+#[cfg(feature = "query_apply")]
+use super::NM_SETTING_SECRET_FLAG_AGENT_OWNED;
+
+#[cfg(feature = "query_apply")]
+const NM_SETTING_MACSEC_MODE_PSK: i32 = 0;
 
 #[derive(Debug, Clone, PartialEq, Default, Deserialize)]
 #[serde(try_from = "DbusDictionary")]
@@ -13,11 +18,12 @@ pub struct NmSettingMacSec {
     pub mode: Option<i32>,
     pub encrypt: Option<bool>,
     pub mka_cak: Option<String>,
+    pub mka_cak_flags: Option<u32>,
     pub mka_ckn: Option<String>,
     pub port: Option<i32>,
     pub validation: Option<i32>,
     pub send_sci: Option<bool>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingMacSec {
@@ -32,6 +38,7 @@ impl TryFrom<DbusDictionary> for NmSettingMacSec {
             validation: _from_map!(v, "validation", i32::try_from)?,
             send_sci: _from_map!(v, "send-sci", bool::try_from)?,
             mka_cak: None,
+            mka_cak_flags: _from_map!(v, "mka-cak-flags", u32::try_from)?,
             _other: v,
         })
     }
@@ -52,6 +59,9 @@ impl ToDbusValue for NmSettingMacSec {
         if let Some(v) = &self.mka_cak {
             ret.insert("mka-cak", zvariant::Value::new(v.clone()));
         }
+        if let Some(v) = &self.mka_cak_flags {
+            ret.insert("mka-cak-flags", zvariant::Value::new(*v));
+        }
         if let Some(v) = &self.mka_ckn {
             ret.insert("mka-ckn", zvariant::Value::new(v.clone()));
         }
@@ -74,6 +84,17 @@ impl ToDbusValue for NmSettingMacSec {
 }
 
 impl NmSettingMacSec {
+    // Let NetworkManager request the missing pre-shared CAK from secret agent
+    // instead of storing it.
+    #[cfg(feature = "query_apply")]
+    pub(crate) fn set_missing_secrets_agent_owned(&mut self) {
+        if self.mode == Some(NM_SETTING_MACSEC_MODE_PSK)
+            && self.mka_cak.is_none()
+        {
+            self.mka_cak_flags = Some(NM_SETTING_SECRET_FLAG_AGENT_OWNED);
+        }
+    }
+
     #[cfg(feature = "query_apply")]
     pub(crate) fn fill_secrets(&mut self, secrets: &DbusDictionary) {
         if let Some(v) = secrets.get("mka-cak") {
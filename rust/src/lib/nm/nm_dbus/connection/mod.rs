@@ -21,9 +21,12 @@ mod bridge;
 mod conn;
 mod dns;
 mod ethtool;
+mod gsm;
 mod ieee8021x;
 mod infiniband;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod loopback;
 mod mac_vlan;
 mod macsec;
@@ -47,9 +50,12 @@ pub use self::conn::{
     NmConnection, NmRange, NmSettingConnection, NmSettingsConnectionFlag,
 };
 pub use self::ethtool::NmSettingEthtool;
+pub use self::gsm::NmSettingGsm;
 pub use self::ieee8021x::NmSetting8021X;
 pub use self::infiniband::NmSettingInfiniBand;
 pub use self::ip::{NmSettingIp, NmSettingIpMethod};
+pub use self::ip_tunnel::NmSettingIpTunnel;
+pub use self::ip_vlan::NmSettingIpVlan;
 pub use self::loopback::NmSettingLoopback;
 pub use self::mac_vlan::NmSettingMacVlan;
 pub use self::macsec::NmSettingMacSec;
@@ -74,3 +80,8 @@ pub(crate) use self::conn::DbusDictionary;
 pub(crate) use self::conn::{nm_con_get_from_obj_path, NmConnectionDbusValue};
 #[cfg(feature = "query_apply")]
 pub(crate) use self::macros::_from_map;
+
+// The secret is provided by the secret agent on request instead of being
+// stored in the profile.
+#[cfg(feature = "query_apply")]
+pub(crate) const NM_SETTING_SECRET_FLAG_AGENT_OWNED: u32 = 1;
@@ -16,7 +16,7 @@ pub struct NmSettingOvsBridge {
     pub rstp: Option<bool>,
     pub fail_mode: Option<String>,
     pub datapath_type: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingOvsBridge {
@@ -73,7 +73,7 @@ pub struct NmSettingOvsPort {
     pub vlan_mode: Option<String>,
     pub trunks: Option<Vec<NmRange>>,
     pub lacp: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingOvsPort {
@@ -145,7 +145,7 @@ impl ToDbusValue for NmSettingOvsPort {
 #[non_exhaustive]
 pub struct NmSettingOvsIface {
     pub iface_type: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingOvsIface {
@@ -176,7 +176,7 @@ impl ToDbusValue for NmSettingOvsIface {
 #[non_exhaustive]
 pub struct NmSettingOvsExtIds {
     pub data: Option<HashMap<String, String>>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingOvsExtIds {
@@ -215,7 +215,7 @@ impl ToDbusValue for NmSettingOvsExtIds {
 #[non_exhaustive]
 pub struct NmSettingOvsOtherConfig {
     pub data: Option<HashMap<String, String>>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingOvsOtherConfig {
@@ -254,7 +254,7 @@ impl ToDbusValue for NmSettingOvsOtherConfig {
 #[non_exhaustive]
 pub struct NmSettingOvsPatch {
     pub peer: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingOvsPatch {
@@ -288,7 +288,7 @@ pub struct NmSettingOvsDpdk {
     pub n_rxq: Option<u32>,
     pub n_rxq_desc: Option<u32>,
     pub n_txq_desc: Option<u32>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingOvsDpdk {
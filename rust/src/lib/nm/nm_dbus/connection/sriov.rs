@@ -32,7 +32,7 @@ pub struct NmSettingSriov {
     pub autoprobe_drivers: Option<bool>,
     pub total_vfs: Option<u32>,
     pub vfs: Option<Vec<NmSettingSriovVf>>,
-    _other: DbusDictionary,
+    pub(super) _other: DbusDictionary,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingSriov {
@@ -25,7 +25,7 @@ use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
 #[non_exhaustive]
 pub struct NmSettingUser {
     pub data: Option<HashMap<String, String>>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingUser {
@@ -10,7 +10,7 @@ use super::super::{connection::DbusDictionary, convert::ToDbusValue, NmError};
 #[non_exhaustive]
 pub struct NmSettingVeth {
     pub peer: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingVeth {
@@ -15,7 +15,7 @@ pub struct NmSettingVlan {
     pub parent: Option<String>,
     pub id: Option<u32>,
     pub protocol: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingVlan {
@@ -5,7 +5,10 @@ use std::convert::TryFrom;
 
 use serde::Deserialize;
 
-use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
+use super::super::{
+    connection::{DbusDictionary, NM_SETTING_SECRET_FLAG_AGENT_OWNED},
+    NmError, ToDbusValue,
+};
 
 #[derive(Debug, Clone, PartialEq, Default, Deserialize)]
 #[serde(try_from = "DbusDictionary")]
@@ -17,12 +20,32 @@ pub struct NmSettingVpn {
     pub secrets: Option<HashMap<String, String>>,
     pub timeout: Option<u32>,
     pub user_name: Option<String>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl NmSettingVpn {
     pub const SERVICE_TYPE_LIBRESWAN: &'static str =
         "org.freedesktop.NetworkManager.libreswan";
+    // Secret flags of VPN plugin are stored in data as `<secret>-flags`
+    const LIBRESWAN_PSK_FLAGS: &'static str = "pskvalue-flags";
+
+    // Let NetworkManager request the libreswan PSK from secret agent instead
+    // of storing it.
+    pub(crate) fn set_psk_agent_owned(&mut self) {
+        self.data.get_or_insert(HashMap::new()).insert(
+            Self::LIBRESWAN_PSK_FLAGS.to_string(),
+            NM_SETTING_SECRET_FLAG_AGENT_OWNED.to_string(),
+        );
+    }
+
+    pub(crate) fn is_psk_agent_owned(&self) -> bool {
+        self.data
+            .as_ref()
+            .and_then(|d| d.get(Self::LIBRESWAN_PSK_FLAGS))
+            .and_then(|f| f.parse::<u32>().ok())
+            .map(|f| f & NM_SETTING_SECRET_FLAG_AGENT_OWNED > 0)
+            .unwrap_or_default()
+    }
 }
 
 impl TryFrom<DbusDictionary> for NmSettingVpn {
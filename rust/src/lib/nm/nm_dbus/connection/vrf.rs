@@ -10,7 +10,7 @@ use super::super::{connection::DbusDictionary, NmError, ToDbusValue};
 #[non_exhaustive]
 pub struct NmSettingVrf {
     pub table: Option<u32>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingVrf {
@@ -15,7 +15,7 @@ pub struct NmSettingVxlan {
     pub local: Option<String>,
     pub remote: Option<String>,
     pub dst_port: Option<u32>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingVxlan {
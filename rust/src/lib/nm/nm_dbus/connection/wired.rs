@@ -23,7 +23,7 @@ pub struct NmSettingWired {
     pub speed: Option<u32>,
     pub duplex: Option<String>,
     pub auto_negotiate: Option<bool>,
-    _other: HashMap<String, zvariant::OwnedValue>,
+    pub(super) _other: HashMap<String, zvariant::OwnedValue>,
 }
 
 impl TryFrom<DbusDictionary> for NmSettingWired {
@@ -35,6 +35,7 @@ const NM_SETTINGS_CREATE2_FLAGS_BLOCK_AUTOCONNECT: u32 = 32;
 
 const NM_SETTINGS_UPDATE2_FLAGS_TO_DISK: u32 = 1;
 const NM_SETTINGS_UPDATE2_FLAGS_IN_MEMORY: u32 = 2;
+const NM_SETTINGS_UPDATE2_FLAGS_VOLATILE: u32 = 16;
 const NM_SETTINGS_UPDATE2_FLAGS_BLOCK_AUTOCONNECT: u32 = 32;
 
 pub(crate) struct NmDbus<'a> {
@@ -45,8 +46,13 @@ pub(crate) struct NmDbus<'a> {
 }
 
 impl<'a> NmDbus<'a> {
-    pub(crate) fn new() -> Result<Self, NmError> {
-        let connection = zbus::Connection::new_system()?;
+    // Use system bus when address is None
+    pub(crate) fn new(address: Option<&str>) -> Result<Self, NmError> {
+        let connection = if let Some(address) = address {
+            zbus::Connection::new_for_address(address, true)?
+        } else {
+            zbus::Connection::new_system()?
+        };
         let proxy = NetworkManagerProxy::new(&connection)?;
         let setting_proxy = NetworkManagerSettingProxy::new(&connection)?;
         let dns_proxy = NetworkManagerDnsProxy::new(&connection)?;
@@ -115,6 +121,12 @@ impl<'a> NmDbus<'a> {
         Ok(())
     }
 
+    // Unique D-Bus name of NetworkManager daemon, changed on daemon restart.
+    pub(crate) fn name_owner(&self) -> Result<String, NmError> {
+        Ok(zbus::fdo::DBusProxy::new(&self.connection)?
+            .get_name_owner(NM_DBUS_INTERFACE_ROOT)?)
+    }
+
     pub(crate) fn checkpoints(&self) -> Result<Vec<String>, NmError> {
         Ok(self
             .proxy
@@ -244,6 +256,36 @@ impl<'a> NmDbus<'a> {
         Ok(())
     }
 
+    // NetworkManager only allows marking profile as volatile via Update2
+    // with the settings left untouched. Inactive profile will be deleted
+    // immediately.
+    pub(crate) fn connection_set_volatile(
+        &self,
+        con_obj_path: &str,
+    ) -> Result<(), NmError> {
+        let proxy = zbus::Proxy::new(
+            &self.connection,
+            NM_DBUS_INTERFACE_ROOT,
+            con_obj_path,
+            NM_DBUS_INTERFACE_SETTING,
+        )?;
+        let value: NmConnectionDbusValue = HashMap::new();
+        proxy.call::<(
+                NmConnectionDbusValue,
+                u32,
+                HashMap<&str, zvariant::Value>,
+            ), HashMap<String, zvariant::OwnedValue>>(
+                "Update2",
+                &(
+                    value,
+                    NM_SETTINGS_UPDATE2_FLAGS_IN_MEMORY
+                        + NM_SETTINGS_UPDATE2_FLAGS_VOLATILE,
+                    HashMap::new()
+                ),
+            )?;
+        Ok(())
+    }
+
     pub(crate) fn nm_dev_obj_path_get(
         &self,
         iface_name: &str,
@@ -370,6 +412,25 @@ impl<'a> NmDbus<'a> {
     ) -> Result<(), NmError> {
         Ok(self.proxy.set_property("GlobalDnsConfiguration", value)?)
     }
+
+    pub(crate) fn connectivity_check_enabled(&self) -> Result<bool, NmError> {
+        Ok(self.proxy.connectivity_check_enabled()?)
+    }
+
+    pub(crate) fn set_connectivity_check_enabled(
+        &self,
+        value: bool,
+    ) -> Result<(), NmError> {
+        Ok(self.proxy.set_property("ConnectivityCheckEnabled", value)?)
+    }
+
+    pub(crate) fn connectivity_check_uri(&self) -> Result<String, NmError> {
+        Ok(self.proxy.connectivity_check_uri()?)
+    }
+
+    pub(crate) fn reload(&self, flags: u32) -> Result<(), NmError> {
+        Ok(self.proxy.reload(flags)?)
+    }
 }
 
 fn str_to_obj_path(obj_path: &str) -> Result<zvariant::ObjectPath, NmError> {
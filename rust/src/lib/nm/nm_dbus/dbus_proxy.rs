@@ -26,6 +26,15 @@ trait NetworkManager {
         &self,
     ) -> zbus::Result<HashMap<String, zvariant::OwnedValue>>;
 
+    #[dbus_proxy(property)]
+    fn connectivity_check_enabled(&self) -> zbus::Result<bool>;
+
+    #[dbus_proxy(property)]
+    fn connectivity_check_uri(&self) -> zbus::Result<String>;
+
+    /// Reload method
+    fn reload(&self, flags: u32) -> zbus::Result<()>;
+
     /// CheckpointCreate method
     fn checkpoint_create(
         &self,
@@ -122,3 +131,16 @@ trait NetworkManagerDns {
         &self,
     ) -> zbus::Result<Vec<HashMap<String, zvariant::OwnedValue>>>;
 }
+
+#[dbus_proxy(
+    interface = "org.freedesktop.NetworkManager.AgentManager",
+    default_service = "org.freedesktop.NetworkManager",
+    default_path = "/org/freedesktop/NetworkManager/AgentManager"
+)]
+trait NetworkManagerAgentManager {
+    /// Register method
+    fn register(&self, identifier: &str) -> zbus::Result<()>;
+
+    /// Unregister method
+    fn unregister(&self) -> zbus::Result<()>;
+}
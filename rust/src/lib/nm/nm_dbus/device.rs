@@ -344,6 +344,8 @@ pub struct NmDevice {
     pub state: NmDeviceState,
     pub state_reason: NmDeviceStateReason,
     pub is_mac_vtap: bool,
+    /// Tunnel mode of `ip-tunnel` device, 0 for other device types.
+    pub ip_tunnel_mode: u32,
     pub obj_path: String,
     pub real: bool,
     pub mac_address: String,
@@ -5,6 +5,9 @@
 #[allow(dead_code)]
 pub enum ErrorKind {
     DbusConnectionError,
+    // Transient D-Bus failure which might be resolved by retry, for example
+    // NetworkManager restarting or D-Bus call timeout.
+    DbusTransientError,
     CheckpointConflict,
     InvalidArgument,
     NotFound,
@@ -183,6 +186,15 @@ impl From<&str> for NmManagerError {
 #[cfg(feature = "query_apply")]
 const NM_DBUS_ERR_PREFIX: &str = "org.freedesktop.NetworkManager.";
 
+#[cfg(feature = "query_apply")]
+const DBUS_TRANSIENT_ERRORS: [&str; 5] = [
+    "org.freedesktop.DBus.Error.Disconnected",
+    "org.freedesktop.DBus.Error.NameHasNoOwner",
+    "org.freedesktop.DBus.Error.NoReply",
+    "org.freedesktop.DBus.Error.TimedOut",
+    "org.freedesktop.DBus.Error.Timeout",
+];
+
 #[cfg(feature = "query_apply")]
 fn parse_nm_dbus_error(nm_err_kind: &str, nm_err_msg: &str) -> NmError {
     if nm_err_kind.len() > NM_DBUS_ERR_PREFIX.len() {
@@ -280,6 +292,13 @@ impl From<zbus::Error> for NmError {
             }
         }
 
+        if is_dbus_transient_error(&e) {
+            return Self {
+                kind: ErrorKind::DbusTransientError,
+                msg: format!("{e}"),
+            };
+        }
+
         log::warn!("Unknown DBUS error {:?}", e);
 
         Self {
@@ -292,6 +311,12 @@ impl From<zbus::Error> for NmError {
 #[cfg(feature = "query_apply")]
 impl From<zbus::fdo::Error> for NmError {
     fn from(e: zbus::fdo::Error) -> Self {
+        if is_dbus_fdo_transient_error(&e) {
+            return Self {
+                kind: ErrorKind::DbusTransientError,
+                msg: format!("zbus fdo error {e}"),
+            };
+        }
         Self {
             kind: ErrorKind::Bug,
             msg: format!("zbus fdo error {e}"),
@@ -299,6 +324,29 @@ impl From<zbus::fdo::Error> for NmError {
     }
 }
 
+#[cfg(feature = "query_apply")]
+fn is_dbus_transient_error(e: &zbus::Error) -> bool {
+    match e {
+        zbus::Error::MethodError(dbus_err_kind, _, _) => {
+            DBUS_TRANSIENT_ERRORS.contains(&dbus_err_kind.as_str())
+        }
+        _ => false,
+    }
+}
+
+#[cfg(feature = "query_apply")]
+fn is_dbus_fdo_transient_error(e: &zbus::fdo::Error) -> bool {
+    match e {
+        zbus::fdo::Error::ZBus(e) => is_dbus_transient_error(e),
+        zbus::fdo::Error::Disconnected(_)
+        | zbus::fdo::Error::NameHasNoOwner(_)
+        | zbus::fdo::Error::NoReply(_)
+        | zbus::fdo::Error::TimedOut(_)
+        | zbus::fdo::Error::Timeout(_) => true,
+        _ => false,
+    }
+}
+
 impl From<zvariant::Error> for NmError {
     fn from(e: zvariant::Error) -> Self {
         Self {
@@ -3,8 +3,11 @@
 use std::collections::HashMap;
 
 use super::{
-    super::{NmConnection, NmError, NmSettingConnection, ToKeyfile},
-    keyfile::keyfile_sections_to_string,
+    super::{
+        NmConnection, NmError, NmSettingConnection, NmSettingIp,
+        NmSettingIpMethod, NmSettingWired, ToKeyfile,
+    },
+    keyfile::{keyfile_sections_to_string, keyfile_string_to_sections},
 };
 
 impl ToKeyfile for NmSettingConnection {}
@@ -58,12 +61,21 @@ impl NmConnection {
         if let Some(vxlan) = &self.vxlan {
             sections.push(("vxlan", vxlan.to_keyfile()?));
         }
+        if let Some(ip_tunnel) = &self.ip_tunnel {
+            sections.push(("ip-tunnel", ip_tunnel.to_keyfile()?));
+        }
         if let Some(sriov) = &self.sriov {
             sections.push(("sriov", sriov.to_keyfile()?));
         }
         if let Some(mac_vlan) = &self.mac_vlan {
             sections.push(("macvlan", mac_vlan.to_keyfile()?));
         }
+        if let Some(ip_vlan) = &self.ip_vlan {
+            sections.push(("ipvlan", ip_vlan.to_keyfile()?));
+        }
+        if let Some(gsm) = &self.gsm {
+            sections.push(("gsm", gsm.to_keyfile()?));
+        }
         if let Some(vrf) = &self.vrf {
             sections.push(("vrf", vrf.to_keyfile()?));
         }
@@ -94,7 +106,88 @@ impl NmConnection {
                 sections.push(("vpn-secrets", s));
             }
         }
+        // Sort the settings to generate stable output
+        let mut other_settings: Vec<(&String, _)> =
+            self._other.iter().collect();
+        other_settings.sort_unstable_by_key(|(n, _)| *n);
+        for (setting_name, setting_value) in other_settings {
+            let section_name = match setting_name.as_str() {
+                "802-3-ethernet" => "ethernet",
+                n => n,
+            };
+            let values = setting_value.iter().map(|(k, v)| {
+                (k.to_string(), zvariant::Value::from(v.clone()))
+            });
+            if let Some((_, section)) =
+                sections.iter_mut().find(|(n, _)| *n == section_name)
+            {
+                section.extend(values);
+            } else {
+                sections.push((section_name, values.collect()));
+            }
+        }
 
         keyfile_sections_to_string(&sections)
     }
+
+    // Parse keyfile back into NmConnection. Only these properties are
+    // supported:
+    //  * connection: id, uuid, type, interface-name, master, slave-type
+    //  * ipv4 and ipv6: method and addresses
+    //  * ethernet: mtu
+    pub(crate) fn from_keyfile(content: &str) -> Result<Self, NmError> {
+        let mut ret = Self::default();
+        for (section_name, mut data) in keyfile_string_to_sections(content)? {
+            match section_name.as_str() {
+                "connection" => {
+                    let mut nm_set = NmSettingConnection::default();
+                    nm_set.id = data.remove("id");
+                    nm_set.uuid = data.remove("uuid");
+                    nm_set.iface_type = data.remove("type");
+                    nm_set.iface_name = data.remove("interface-name");
+                    nm_set.controller = data.remove("master");
+                    nm_set.controller_type = data.remove("slave-type");
+                    ret.connection = Some(nm_set);
+                }
+                "ipv4" | "ipv6" => {
+                    let mut nm_set = NmSettingIp::default();
+                    if let Some(v) = data.remove("method") {
+                        nm_set.method = Some(NmSettingIpMethod::try_from(
+                            zvariant::OwnedValue::from(zvariant::Value::new(v)),
+                        )?);
+                    }
+                    let mut addr_keys: Vec<(usize, &String)> = data
+                        .keys()
+                        .filter_map(|k| {
+                            k.strip_prefix("address")
+                                .and_then(|i| i.parse::<usize>().ok())
+                                .map(|i| (i, k))
+                        })
+                        .collect();
+                    addr_keys.sort_unstable();
+                    for (_, key) in addr_keys {
+                        if let Some(v) = data.get(key) {
+                            // Strip the optional gateway
+                            if let Some(addr) = v.split([',', ';']).next() {
+                                nm_set.addresses.push(addr.to_string());
+                            }
+                        }
+                    }
+                    if section_name == "ipv4" {
+                        ret.ipv4 = Some(nm_set);
+                    } else {
+                        ret.ipv6 = Some(nm_set);
+                    }
+                }
+                "ethernet" => {
+                    let mut nm_set = NmSettingWired::default();
+                    nm_set.mtu =
+                        data.remove("mtu").and_then(|v| v.parse::<u32>().ok());
+                    ret.wired = Some(nm_set);
+                }
+                _ => (),
+            }
+        }
+        Ok(ret)
+    }
 }
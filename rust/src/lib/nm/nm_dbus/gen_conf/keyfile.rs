@@ -32,6 +32,40 @@ pub(crate) fn keyfile_sections_to_string(
     Ok(ret)
 }
 
+// Parse keyfile into HashMap of section name to key and value strings.
+// Comment lines starting with `#` or `;` are ignored.
+pub(crate) fn keyfile_string_to_sections(
+    content: &str,
+) -> Result<HashMap<String, HashMap<String, String>>, NmError> {
+    let mut ret: HashMap<String, HashMap<String, String>> = HashMap::new();
+    let mut cur_section: Option<&str> = None;
+    for line in content.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(section_name) =
+            line.strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+        {
+            ret.entry(section_name.to_string()).or_default();
+            cur_section = Some(section_name);
+        } else if let (Some((key, value)), Some(section_name)) =
+            (line.split_once('='), cur_section)
+        {
+            ret.entry(section_name.to_string())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        } else {
+            let e = NmError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid line in keyfile: {line}"),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    }
+    Ok(ret)
+}
+
 fn zvariant_value_to_string(
     value: &zvariant::Value,
 ) -> Result<String, NmError> {
@@ -4,9 +4,12 @@ mod bond;
 mod bridge;
 mod conn;
 mod ethtool;
+mod gsm;
 mod ieee8021x;
 mod infiniband;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod keyfile;
 mod mac_vlan;
 mod ovs;
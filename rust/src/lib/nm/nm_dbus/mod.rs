@@ -15,6 +15,8 @@ mod error;
 mod lldp;
 #[cfg(feature = "query_apply")]
 mod nm_api;
+#[cfg(feature = "query_apply")]
+mod secret_agent;
 
 #[cfg(feature = "gen_conf")]
 mod gen_conf;
@@ -28,13 +30,13 @@ pub use self::connection::{
     NmConnection, NmIpRoute, NmIpRouteRule, NmIpRouteRuleAction, NmRange,
     NmSetting8021X, NmSettingBond, NmSettingBondPort, NmSettingBridge,
     NmSettingBridgePort, NmSettingBridgeVlanRange, NmSettingConnection,
-    NmSettingEthtool, NmSettingInfiniBand, NmSettingIp, NmSettingIpMethod,
-    NmSettingLoopback, NmSettingMacVlan, NmSettingOvsBridge, NmSettingOvsDpdk,
-    NmSettingOvsExtIds, NmSettingOvsIface, NmSettingOvsOtherConfig,
-    NmSettingOvsPatch, NmSettingOvsPort, NmSettingSriov, NmSettingSriovVf,
-    NmSettingSriovVfVlan, NmSettingUser, NmSettingVeth, NmSettingVlan,
-    NmSettingVpn, NmSettingVrf, NmSettingVxlan, NmSettingWired,
-    NmSettingsConnectionFlag, NmVlanProtocol,
+    NmSettingEthtool, NmSettingGsm, NmSettingInfiniBand, NmSettingIp,
+    NmSettingIpMethod, NmSettingIpTunnel, NmSettingIpVlan, NmSettingLoopback,
+    NmSettingMacVlan, NmSettingOvsBridge, NmSettingOvsDpdk, NmSettingOvsExtIds,
+    NmSettingOvsIface, NmSettingOvsOtherConfig, NmSettingOvsPatch,
+    NmSettingOvsPort, NmSettingSriov, NmSettingSriovVf, NmSettingSriovVfVlan,
+    NmSettingUser, NmSettingVeth, NmSettingVlan, NmSettingVpn, NmSettingVrf,
+    NmSettingVxlan, NmSettingWired, NmSettingsConnectionFlag, NmVlanProtocol,
 };
 pub use self::device::{NmDevice, NmDeviceState, NmDeviceStateReason};
 #[cfg(feature = "query_apply")]
@@ -51,6 +53,8 @@ pub use self::lldp::{
 };
 #[cfg(feature = "query_apply")]
 pub use self::nm_api::NmApi;
+#[cfg(feature = "query_apply")]
+pub use self::secret_agent::{NmSecretAgent, NmSecretCallback};
 
 pub(crate) use self::convert::ToDbusValue;
 #[cfg(feature = "gen_conf")]
@@ -29,9 +29,11 @@ pub struct NmApi<'a> {
 }
 
 impl<'a> NmApi<'a> {
-    pub fn new() -> Result<Self, NmError> {
+    /// Connect to NetworkManager via specified D-Bus address instead of
+    /// system bus, for example `unix:path=/host/run/dbus/system_bus_socket`.
+    pub fn new_with_address(address: Option<&str>) -> Result<Self, NmError> {
         Ok(Self {
-            dbus: NmDbus::new()?,
+            dbus: NmDbus::new(address)?,
             checkpoint: None,
             cp_refresh_time: None,
             cp_timeout: 0,
@@ -95,6 +97,16 @@ impl<'a> NmApi<'a> {
             .checkpoint_rollback(checkpoint_to_rollback.as_str())
     }
 
+    pub fn checkpoints(&self) -> Result<Vec<String>, NmError> {
+        self.dbus.checkpoints()
+    }
+
+    /// Unique D-Bus name of NetworkManager daemon, changed when daemon
+    /// restarted.
+    pub fn name_owner(&self) -> Result<String, NmError> {
+        self.dbus.name_owner()
+    }
+
     fn last_active_checkpoint(&self) -> Result<String, NmError> {
         debug!("last_active_checkpoint");
         let mut checkpoints = self.dbus.checkpoints()?;
@@ -215,6 +227,16 @@ impl<'a> NmApi<'a> {
         Ok(())
     }
 
+    pub fn connection_set_volatile(
+        &mut self,
+        uuid: &str,
+    ) -> Result<(), NmError> {
+        debug!("connection_set_volatile: {}", uuid);
+        self.extend_timeout_if_required()?;
+        let con_obj_path = self.dbus.get_conn_obj_path_by_uuid(uuid)?;
+        self.dbus.connection_set_volatile(&con_obj_path)
+    }
+
     pub fn connection_reapply(
         &mut self,
         nm_conn: &NmConnection,
@@ -400,6 +422,29 @@ impl<'a> NmApi<'a> {
         self.extend_timeout_if_required()?;
         self.dbus.set_global_dns_configuration(config.to_value()?)
     }
+
+    pub fn connectivity_check_enabled(&self) -> Result<bool, NmError> {
+        self.dbus.connectivity_check_enabled()
+    }
+
+    pub fn set_connectivity_check_enabled(
+        &mut self,
+        value: bool,
+    ) -> Result<(), NmError> {
+        self.extend_timeout_if_required()?;
+        self.dbus.set_connectivity_check_enabled(value)
+    }
+
+    pub fn connectivity_check_uri(&self) -> Result<String, NmError> {
+        self.dbus.connectivity_check_uri()
+    }
+
+    /// Reload NetworkManager configuration files, DNS configuration or
+    /// both depending on `flags`. Reload everything when `flags` is 0.
+    pub fn reload(&mut self, flags: u32) -> Result<(), NmError> {
+        self.extend_timeout_if_required()?;
+        self.dbus.reload(flags)
+    }
 }
 
 fn get_nm_ac_obj_path_by_uuid(
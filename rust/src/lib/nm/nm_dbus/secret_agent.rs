@@ -0,0 +1,249 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use zbus::dbus_interface;
+
+use super::{
+    connection::DbusDictionary,
+    dbus_proxy::NetworkManagerAgentManagerProxy,
+    error::{ErrorKind, NmError},
+};
+
+const NM_SECRET_AGENT_OBJ_PATH: &str =
+    "/org/freedesktop/NetworkManager/SecretAgent";
+const NM_SECRET_AGENT_IDENTIFIER: &str = "io.nmstate.SecretAgent";
+const NM_SETTING_VPN_SETTING_NAME: &str = "vpn";
+const NM_SETTING_VPN_SECRETS: &str = "secrets";
+
+/// Callback of secret agent with arguments of interface name, NM setting name
+/// and hints, returning HashMap of NM setting property name to secret.
+/// For `vpn` setting, the HashMap holds the VPN plugin secrets, e.g.
+/// `pskvalue` of libreswan.
+pub type NmSecretCallback = Arc<
+    dyn Fn(&str, &str, &[String]) -> Option<HashMap<String, String>>
+        + Send
+        + Sync,
+>;
+
+struct NmSecretAgentIface {
+    callback: NmSecretCallback,
+}
+
+#[dbus_interface(name = "org.freedesktop.NetworkManager.SecretAgent")]
+impl NmSecretAgentIface {
+    fn get_secrets(
+        &self,
+        connection: HashMap<String, DbusDictionary>,
+        _connection_path: zvariant::OwnedObjectPath,
+        setting_name: &str,
+        hints: Vec<String>,
+        _flags: u32,
+    ) -> zbus::fdo::Result<
+        HashMap<String, HashMap<String, zvariant::Value<'static>>>,
+    > {
+        // VPN connection has no interface name but is named after the
+        // interface by nmstate.
+        let iface_name = connection
+            .get("connection")
+            .and_then(|c| {
+                c.get("interface-name")
+                    .or_else(|| c.get("id"))
+                    .and_then(|v| String::try_from(v.clone()).ok())
+            })
+            .unwrap_or_default();
+        log::debug!(
+            "NetworkManager requesting secrets of {setting_name} for \
+            interface {iface_name}, hints {hints:?}"
+        );
+        match (self.callback)(
+            iface_name.as_str(),
+            setting_name,
+            hints.as_slice(),
+        ) {
+            Some(secrets) => {
+                let mut setting = HashMap::new();
+                // The VPN plugin secrets are stored in `secrets` property
+                // as dictionary.
+                if setting_name == NM_SETTING_VPN_SETTING_NAME {
+                    setting.insert(
+                        NM_SETTING_VPN_SECRETS.to_string(),
+                        zvariant::Value::new(secrets),
+                    );
+                } else {
+                    for (key, secret) in secrets {
+                        setting.insert(key, zvariant::Value::new(secret));
+                    }
+                }
+                let mut ret = HashMap::new();
+                ret.insert(setting_name.to_string(), setting);
+                Ok(ret)
+            }
+            None => Err(zbus::fdo::Error::Failed(format!(
+                "No secrets of {setting_name} found for interface \
+                {iface_name}"
+            ))),
+        }
+    }
+
+    fn cancel_get_secrets(
+        &self,
+        _connection_path: zvariant::OwnedObjectPath,
+        _setting_name: &str,
+    ) {
+    }
+
+    // The secrets are owned by secret provider, nothing to save.
+    fn save_secrets(
+        &self,
+        _connection: HashMap<String, DbusDictionary>,
+        _connection_path: zvariant::OwnedObjectPath,
+    ) {
+    }
+
+    fn delete_secrets(
+        &self,
+        _connection: HashMap<String, DbusDictionary>,
+        _connection_path: zvariant::OwnedObjectPath,
+    ) {
+    }
+}
+
+/// NetworkManager secret agent serving secrets via callback in background
+/// thread. On drop, the agent is unregistered, the thread is stopped and the
+/// D-Bus connection is closed.
+pub struct NmSecretAgent {
+    connection: zbus::Connection,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl NmSecretAgent {
+    /// Register secret agent to NetworkManager, use system bus when address
+    /// is None.
+    pub fn start(
+        address: Option<&str>,
+        callback: NmSecretCallback,
+    ) -> Result<Self, NmError> {
+        // Secret agent is identified by D-Bus connection, hence cannot share
+        // the connection of `NmApi`.
+        let connection = if let Some(address) = address {
+            zbus::Connection::new_for_address(address, true)?
+        } else {
+            zbus::Connection::new_system()?
+        };
+        let stop = Arc::new(AtomicBool::new(false));
+        let thread_stop = stop.clone();
+        let thread_connection = connection.clone();
+        let (tx, rx) = std::sync::mpsc::channel::<Result<(), NmError>>();
+        // The `zbus::ObjectServer` cannot be moved between threads, hence
+        // created in the thread serving it. The registration is also done in
+        // this thread, so that it is the only reader of the connection and
+        // D-Bus replies will not be consumed by others.
+        let thread = std::thread::spawn(move || {
+            let mut object_server = zbus::ObjectServer::new(&thread_connection);
+            let result =
+                zvariant::ObjectPath::try_from(NM_SECRET_AGENT_OBJ_PATH)
+                    .map_err(NmError::from)
+                    .and_then(|path| {
+                        object_server
+                            .at(&path, NmSecretAgentIface { callback })
+                            .map_err(NmError::from)
+                    })
+                    .and_then(|_| {
+                        NetworkManagerAgentManagerProxy::new(
+                            &thread_connection,
+                        )?
+                        .register(NM_SECRET_AGENT_IDENTIFIER)
+                        .map_err(NmError::from)
+                    });
+            let failed = result.is_err();
+            if tx.send(result).is_err() || failed {
+                return;
+            }
+            log::debug!("Registered NetworkManager secret agent");
+            // The thread is woken up by `NmSecretAgent::drop()` after stopped
+            while !thread_stop.load(Ordering::Relaxed) {
+                if let Err(e) = object_server.try_handle_next() {
+                    log::debug!("Secret agent failed to handle message: {e}");
+                    break;
+                }
+            }
+            match NetworkManagerAgentManagerProxy::new(&thread_connection)
+                .and_then(|p| p.unregister())
+            {
+                Ok(()) => {
+                    log::debug!("Unregistered NetworkManager secret agent")
+                }
+                Err(e) => {
+                    log::debug!("Failed to unregister secret agent: {e}")
+                }
+            }
+        });
+        let result = rx.recv().map_err(|e| {
+            NmError::new(
+                ErrorKind::Bug,
+                format!("Secret agent thread quit unexpectedly: {e}"),
+            )
+        });
+        let agent = Self {
+            connection,
+            stop,
+            thread: Some(thread),
+        };
+        // On failure, the agent is dropped to join the thread and close the
+        // connection.
+        result??;
+        Ok(agent)
+    }
+
+    // Send D-Bus ping to the agent itself to wake up the thread blocked on
+    // waiting D-Bus message. The reply is not waited for as the thread is
+    // the only reader of the connection.
+    fn wake_up(&self) -> Result<(), NmError> {
+        let msg = zbus::Message::method(
+            None,
+            self.connection.unique_name(),
+            NM_SECRET_AGENT_OBJ_PATH,
+            Some("org.freedesktop.DBus.Peer"),
+            "Ping",
+            &(),
+        )?;
+        self.connection.send_message(msg)?;
+        Ok(())
+    }
+}
+
+impl Drop for NmSecretAgent {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Closing the connection fails the pending read of the thread when
+        // failed to wake it up, the unregistration is impossible then.
+        let closed = match self.wake_up() {
+            Ok(()) => false,
+            Err(e) => {
+                log::debug!("Failed to wake up secret agent thread: {e}");
+                close_connection(&self.connection);
+                true
+            }
+        };
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                log::debug!("Secret agent thread panicked");
+            }
+        }
+        if !closed {
+            close_connection(&self.connection);
+        }
+    }
+}
+
+fn close_connection(connection: &zbus::Connection) {
+    if let Err(e) = connection.clone().close() {
+        log::debug!("Failed to close secret agent D-Bus connection: {e}");
+    }
+}
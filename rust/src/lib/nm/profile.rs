@@ -3,13 +3,13 @@
 use super::nm_dbus::{NmActiveConnection, NmConnection};
 use super::settings::{
     fix_ip_dhcp_timeout, get_exist_profile, iface_to_nm_connections,
-    remove_nm_mptcp_set, use_uuid_for_controller_reference,
-    use_uuid_for_parent_reference,
+    remove_nm_accept_all_mac_addresses, remove_nm_mptcp_set,
+    use_uuid_for_controller_reference, use_uuid_for_parent_reference,
 };
 
 use crate::{
-    InterfaceType, MergedInterface, MergedInterfaces, MergedNetworkState,
-    NmstateError,
+    Interface, InterfaceType, MergedInterface, MergedInterfaces,
+    MergedNetworkState, NmstateError,
 };
 
 #[allow(dead_code)]
@@ -24,6 +24,7 @@ pub(crate) fn perpare_nm_conns(
     exist_nm_conns: &[NmConnection],
     nm_acs: &[NmActiveConnection],
     mptcp_supported: bool,
+    accept_all_mac_addresses_supported: bool,
     gen_conf_mode: bool,
 ) -> Result<PerparedNmConnections, NmstateError> {
     let mut nm_conns_to_update: Vec<NmConnection> = Vec::new();
@@ -38,7 +39,17 @@ pub(crate) fn perpare_nm_conns(
         .filter(|i| i.is_changed())
         .collect();
 
+    let ordered_ports_index = merged_state.interfaces.get_ordered_ports_index();
+
     ifaces.sort_unstable_by_key(|iface| iface.merged.name());
+    // Ports of controller with `ports-ordered: true` should be activated in
+    // the declared order.
+    ifaces.sort_by_key(|iface| {
+        ordered_ports_index
+            .get(iface.merged.name())
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
     // Use sort_by_key() instead of unstable one, do we can alphabet
     // activation order which is required to simulate the OS boot-up.
     ifaces.sort_by_key(|iface| {
@@ -64,8 +75,16 @@ pub(crate) fn perpare_nm_conns(
         .cloned()
         .collect();
 
+    // NetworkManager cannot manage CAN, batman-adv and IFB interfaces, they
+    // are configured via kernel after activation. Interfaces handled by
+    // plugins are applied by plugins.
     for merged_iface in ifaces.iter().filter(|i| {
-        i.merged.iface_type() != InterfaceType::Unknown && !i.merged.is_absent()
+        i.merged.iface_type() != InterfaceType::Unknown
+            && i.merged.iface_type() != InterfaceType::Can
+            && i.merged.iface_type() != InterfaceType::Batman
+            && i.merged.iface_type() != InterfaceType::Ifb
+            && !matches!(i.merged, Interface::Custom(_))
+            && !i.merged.is_absent()
     }) {
         let iface = if let Some(i) = merged_iface.for_apply.as_ref() {
             i
@@ -90,6 +109,9 @@ pub(crate) fn perpare_nm_conns(
                     );
                 }
             }
+            if !accept_all_mac_addresses_supported {
+                remove_nm_accept_all_mac_addresses(&mut nm_conn);
+            }
 
             if iface.is_up()
                 && !can_skip_activation(
@@ -119,6 +141,14 @@ pub(crate) fn perpare_nm_conns(
         }
     }
 
+    activate_ports_of_ordered_ctrl(
+        &mut nm_conns_to_activate,
+        &nm_conns_to_update,
+        &merged_state.interfaces,
+        exist_nm_conns,
+        &nm_ac_uuids,
+    );
+
     fix_ip_dhcp_timeout(&mut nm_conns_to_update);
 
     use_uuid_for_controller_reference(
@@ -142,6 +172,68 @@ pub(crate) fn perpare_nm_conns(
     })
 }
 
+// Controller with `ports-ordered: true` has autoconnect-ports disabled, hence
+// its ports stay detached after controller reactivated. Activate all its ports
+// in the declared order after the controller.
+fn activate_ports_of_ordered_ctrl(
+    nm_conns_to_activate: &mut Vec<NmConnection>,
+    nm_conns_to_update: &[NmConnection],
+    merged_ifaces: &MergedInterfaces,
+    exist_nm_conns: &[NmConnection],
+    nm_ac_uuids: &[&str],
+) {
+    let mut ctrls: Vec<&MergedInterface> = merged_ifaces
+        .iter()
+        .filter(|i| {
+            i.merged.is_up()
+                && i.for_apply
+                    .as_ref()
+                    .and_then(|i| i.base_iface().ports_ordered)
+                    == Some(true)
+                && nm_conns_to_activate.iter().any(|c| {
+                    c.iface_name() == Some(i.merged.name())
+                        && c.controller().is_none()
+                })
+        })
+        .collect();
+    ctrls.sort_unstable_by_key(|i| i.merged.name());
+
+    for ctrl in ctrls {
+        for port_name in ctrl.merged.ports().unwrap_or_default() {
+            let port_iface = match merged_ifaces.kernel_ifaces.get(port_name) {
+                Some(i) if i.merged.is_up() => i,
+                _ => continue,
+            };
+            let port_nm_conn = nm_conns_to_update
+                .iter()
+                .find(|c| {
+                    c.iface_name() == Some(port_name)
+                        && c.controller().is_some()
+                })
+                .or_else(|| {
+                    get_exist_profile(
+                        exist_nm_conns,
+                        port_name,
+                        &port_iface.merged.iface_type(),
+                        nm_ac_uuids,
+                    )
+                });
+            if let Some(port_nm_conn) = port_nm_conn {
+                log::info!(
+                    "Activating port {} after its controller {} with \
+                    ports-ordered enabled",
+                    port_name,
+                    ctrl.merged.name()
+                );
+                nm_conns_to_activate.retain(|c| {
+                    c.uuid().is_none() || c.uuid() != port_nm_conn.uuid()
+                });
+                nm_conns_to_activate.push(port_nm_conn.clone());
+            }
+        }
+    }
+}
+
 // When a new virtual interface is desired, if its controller is also newly
 // created, in NetworkManager, there is no need to activate the subordinates.
 // For OVS stuff, always return false.
@@ -1,54 +1,104 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::collections::HashSet;
-
 use super::super::{
     device::create_index_for_nm_devs,
     dns::{
-        cur_dns_ifaces_still_valid_for_dns, store_dns_config_to_iface,
+        cur_dns_ifaces_still_valid_for_dns, has_cur_iface_dns_config,
+        purge_cur_iface_dns_config, store_dns_config_to_iface,
         store_dns_search_or_option_to_iface,
     },
     error::nm_error_to_nmstate,
     nm_dbus::{NmApi, NmConnection},
     profile::{perpare_nm_conns, PerparedNmConnections},
     query_apply::{
-        activate_nm_profiles, create_index_for_nm_conns_by_name_type,
-        deactivate_nm_profiles, delete_exist_profiles, delete_orphan_ovs_ports,
+        activate_nm_profiles,
+        connectivity::apply_connectivity_check,
+        create_index_for_nm_conns_by_name_type, deactivate_nm_profiles,
+        delete_exist_profiles, delete_orphan_ovs_ports,
         dispatch::apply_dispatch_script,
         dns::{
-            is_iface_dns_desired, purge_global_dns_config,
-            store_dns_config_via_global_api,
+            is_dns_apply_required, is_iface_dns_desired,
+            purge_global_dns_config, store_dns_config_via_global_api,
         },
+        is_ip_tunnel_changed, is_ipv6_token_supported, is_ipvlan_changed,
         is_mptcp_flags_changed, is_mptcp_supported, is_route_removed,
         is_veth_peer_changed, is_vlan_changed, is_vrf_table_id_changed,
-        is_vxlan_changed, save_nm_profiles,
+        is_vxlan_changed, remove_nm_ipv6_token, save_nm_profiles,
+        set_volatile_nm_profiles,
         vpn::get_match_ipsec_nm_conn,
     },
     route::store_route_config,
     route_rule::store_route_rule_config,
     settings::{iface_type_to_nm, NM_SETTING_OVS_PORT_SETTING_NAME},
+    version::nm_supports_accept_all_mac_addresses_mode,
 };
 
 use crate::{
-    InterfaceIdentifier, InterfaceType, MergedNetworkState, NmstateError,
+    Interface, InterfaceIdentifier, InterfaceType, MergedNetworkState,
+    NmstateError,
 };
 
-// There is plan to simply the `add_net_state`, `chg_net_state`, `del_net_state`
-// `cur_net_state`, `des_net_state` into single struct. Suppress the clippy
-// warning for now
-pub(crate) fn nm_apply(
+// When desired state only contains hostname or DNS, we can apply them without
+// touching any NetworkManager connection unless the DNS config has to be
+// stored into interface profiles.
+pub(crate) fn nm_can_apply_hostname_dns_only(
+    merged_state: &MergedNetworkState,
+) -> bool {
+    if !merged_state.dns.ignored
+        && !cur_dns_ifaces_still_valid_for_dns(&merged_state.interfaces)
+    {
+        return false;
+    }
+    // Global DNS is desired, need to touch connections only when interface
+    // profiles are holding DNS config.
+    if merged_state.dns.use_global {
+        return !has_cur_iface_dns_config(&merged_state.interfaces);
+    }
+    !merged_state.dns.is_changed()
+        || !(merged_state.dns.is_search_or_option_only()
+            || is_iface_dns_desired(merged_state)
+            || merged_state
+                .dns
+                .desired
+                .config
+                .as_ref()
+                .map(|c| c.is_purge())
+                == Some(true))
+}
+
+// Apply hostname, global DNS and connectivity check config only, caller
+// should make sure
+// `nm_can_apply_hostname_dns_only()` is true.
+pub(crate) fn nm_apply_hostname_dns_only(
+    nm_dbus_address: Option<&str>,
     merged_state: &MergedNetworkState,
     checkpoint: &str,
     timeout: u32,
 ) -> Result<(), NmstateError> {
-    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let mut nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
     nm_api.set_checkpoint(checkpoint, timeout);
     nm_api.set_checkpoint_auto_refresh(true);
 
-    if !merged_state.memory_only {
-        delete_ifaces(&mut nm_api, merged_state)?;
+    apply_hostname(&mut nm_api, merged_state)?;
+    apply_connectivity_check(&mut nm_api, merged_state)?;
+
+    if merged_state.dns.is_changed() {
+        purge_global_dns_config(&mut nm_api)?;
+        store_dns_config_via_global_api(
+            &mut nm_api,
+            merged_state.dns.servers.as_slice(),
+            merged_state.dns.searches.as_slice(),
+            merged_state.dns.options.as_slice(),
+        )?;
     }
+    Ok(())
+}
 
+fn apply_hostname(
+    nm_api: &mut NmApi,
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
     if let Some(hostname) = merged_state
         .hostname
         .desired
@@ -64,8 +114,33 @@ pub(crate) fn nm_apply(
             nm_api.hostname_set(hostname).map_err(nm_error_to_nmstate)?;
         }
     }
+    Ok(())
+}
+
+// There is plan to simply the `add_net_state`, `chg_net_state`, `del_net_state`
+// `cur_net_state`, `des_net_state` into single struct. Suppress the clippy
+// warning for now
+pub(crate) fn nm_apply(
+    nm_dbus_address: Option<&str>,
+    merged_state: &MergedNetworkState,
+    checkpoint: &str,
+    timeout: u32,
+) -> Result<(), NmstateError> {
+    let mut nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
+    nm_api.set_checkpoint(checkpoint, timeout);
+    nm_api.set_checkpoint_auto_refresh(true);
+
+    if !merged_state.memory_only {
+        delete_ifaces(&mut nm_api, merged_state)?;
+    }
+
+    apply_hostname(&mut nm_api, merged_state)?;
+    apply_connectivity_check(&mut nm_api, merged_state)?;
 
     let mptcp_supported = is_mptcp_supported(&nm_api);
+    let accept_all_mac_addresses_supported =
+        nm_supports_accept_all_mac_addresses_mode(&nm_api);
 
     let exist_nm_conns =
         nm_api.connections_get().map_err(nm_error_to_nmstate)?;
@@ -76,16 +151,30 @@ pub(crate) fn nm_apply(
 
     let mut merged_state = merged_state.clone();
 
+    merged_state.interfaces.resolve_macsec_mka_keys()?;
+
     store_route_config(&mut merged_state)?;
 
     store_route_rule_config(&mut merged_state)?;
 
-    if merged_state.dns.is_changed()
-        || !cur_dns_ifaces_still_valid_for_dns(&merged_state.interfaces)
-    {
-        purge_global_dns_config(&mut nm_api)?;
+    if is_dns_apply_required(&merged_state) {
+        // Global DNS config is only purged when desired DNS changed,
+        // otherwise DNS config is just moved between interfaces.
+        if merged_state.dns.is_changed() {
+            purge_global_dns_config(&mut nm_api)?;
+        }
 
-        if merged_state.dns.is_search_or_option_only() {
+        if merged_state.dns.use_global {
+            // User opt-in to global DNS, skip choosing interface to hold DNS
+            // config.
+            purge_cur_iface_dns_config(&mut merged_state)?;
+            store_dns_config_via_global_api(
+                &mut nm_api,
+                merged_state.dns.servers.as_slice(),
+                merged_state.dns.searches.as_slice(),
+                merged_state.dns.options.as_slice(),
+            )?;
+        } else if merged_state.dns.is_search_or_option_only() {
             // When user desire static DNS search and dynamic DNS nameserver,
             // we cannot use global DNS in this case because global DNS suppress
             // DNS nameserver learn from DHCP/autoconf.
@@ -98,6 +187,11 @@ pub(crate) fn nm_apply(
             if let Err(e) =
                 store_dns_config_to_iface(&mut merged_state, &nm_acs, &nm_devs)
             {
+                // Do not fallback to global DNS when user desired the
+                // interface to hold DNS config explicitly.
+                if merged_state.dns.interface.is_some() {
+                    return Err(e);
+                }
                 log::info!(
                     "Cannot store DNS to interface profile: {e}, \
                     will try to set via global DNS"
@@ -131,16 +225,32 @@ pub(crate) fn nm_apply(
     }
 
     let PerparedNmConnections {
-        to_store: nm_conns_to_store,
-        to_activate: nm_conns_to_activate,
+        to_store: mut nm_conns_to_store,
+        to_activate: mut nm_conns_to_activate,
         to_deactivate: nm_conns_to_deactivate,
     } = perpare_nm_conns(
         &merged_state,
         exist_nm_conns.as_slice(),
         nm_acs.as_slice(),
         mptcp_supported,
+        accept_all_mac_addresses_supported,
         false,
     )?;
+    if merged_state.secrets_agent_owned {
+        for nm_conn in nm_conns_to_store.iter_mut() {
+            nm_conn.set_missing_secrets_agent_owned();
+        }
+    }
+    // IPv6 token will be applied to kernel after activation
+    let ipv6_token_supported = is_ipv6_token_supported(&nm_api);
+    if !ipv6_token_supported {
+        for nm_conn in nm_conns_to_store
+            .iter_mut()
+            .chain(nm_conns_to_activate.iter_mut())
+        {
+            remove_nm_ipv6_token(nm_conn);
+        }
+    }
 
     let nm_ac_uuids: Vec<&str> =
         nm_acs.iter().map(|nm_ac| &nm_ac.uuid as &str).collect();
@@ -183,6 +293,13 @@ pub(crate) fn nm_apply(
     }
 
     activate_nm_profiles(&mut nm_api, nm_conns_to_activate.as_slice())?;
+    set_volatile_nm_profiles(&mut nm_api, nm_conns_to_store.as_slice())?;
+    if !ipv6_token_supported {
+        merged_state.interfaces.apply_ipv6_token_via_kernel()?;
+    }
+    merged_state.interfaces.apply_can_config_via_kernel()?;
+    merged_state.interfaces.apply_batman_config_via_kernel()?;
+    merged_state.interfaces.apply_ifb_config_via_kernel()?;
 
     deactivate_nm_profiles(&mut nm_api, nm_conns_to_deactivate.as_slice())?;
 
@@ -199,20 +316,32 @@ fn delete_ifaces(
 
     let nm_conns_name_type_index =
         create_index_for_nm_conns_by_name_type(&all_nm_conns);
-    let mut uuids_to_delete: HashSet<&str> = HashSet::new();
+    // Ordered to delete upper interfaces before their lower interfaces
+    let mut uuids_to_delete: Vec<&str> = Vec::new();
 
     for merged_iface in merged_state
         .interfaces
-        .iter()
-        .filter(|i| i.is_changed() && i.merged.is_absent())
+        .get_absent_ifaces_in_teardown_order()
     {
         let iface = &merged_iface.merged;
 
+        // CAN, batman-adv, IFB and plugin interfaces have no NetworkManager
+        // profile
+        if iface.iface_type() == InterfaceType::Can
+            || iface.iface_type() == InterfaceType::Batman
+            || iface.iface_type() == InterfaceType::Ifb
+            || matches!(iface, Interface::Custom(_))
+        {
+            continue;
+        }
+
         if iface.iface_type() == InterfaceType::Ipsec {
             for nm_conn in get_match_ipsec_nm_conn(iface.name(), &all_nm_conns)
             {
                 if let Some(uuid) = nm_conn.uuid() {
-                    uuids_to_delete.insert(uuid);
+                    if !uuids_to_delete.contains(&uuid) {
+                        uuids_to_delete.push(uuid);
+                    }
                 }
             }
             continue;
@@ -261,7 +390,7 @@ fn delete_ifaces(
                             .as_ref()
                             .and_then(|w| w.mac_address.as_ref())
                             .map(|s| s.to_uppercase())
-                            == Some(mac.to_uppercase())
+                            == Some(mac.as_str().to_uppercase())
                         {
                             nm_conns_to_delete.push(nm_conn);
                         }
@@ -272,7 +401,7 @@ fn delete_ifaces(
         // Delete all existing connections for this interface
         for nm_conn in nm_conns_to_delete {
             if let Some(uuid) = nm_conn.uuid() {
-                if !uuids_to_delete.contains(uuid) {
+                if !uuids_to_delete.contains(&uuid) {
                     log::info!(
                         "Deleting NM connection for absent interface \
                         {}/{}: {}",
@@ -280,7 +409,7 @@ fn delete_ifaces(
                         &iface.iface_type(),
                         uuid
                     );
-                    uuids_to_delete.insert(uuid);
+                    uuids_to_delete.push(uuid);
                 }
             }
             // Delete OVS port profile along with OVS system and internal
@@ -289,14 +418,14 @@ fn delete_ifaces(
                 // TODO: handle pre-exist OVS config using name instead of
                 // UUID for controller
                 if let Some(uuid) = nm_conn.controller() {
-                    if !uuids_to_delete.contains(uuid) {
+                    if !uuids_to_delete.contains(&uuid) {
                         log::info!(
                             "Deleting NM OVS port connection {} \
                              for absent OVS interface {}",
                             uuid,
                             &iface.name(),
                         );
-                        uuids_to_delete.insert(uuid);
+                        uuids_to_delete.push(uuid);
                     }
                 }
             }
@@ -309,7 +438,7 @@ fn delete_ifaces(
             .map_err(nm_error_to_nmstate)?;
     }
 
-    delete_orphan_ports(nm_api, &uuids_to_delete)?;
+    delete_orphan_ports(nm_api, uuids_to_delete.as_slice())?;
     delete_remain_virtual_interface_as_desired(nm_api, merged_state)?;
     Ok(())
 }
@@ -324,10 +453,18 @@ fn delete_remain_virtual_interface_as_desired(
     // deletion, remove manually.
     for iface in merged_state
         .interfaces
-        .kernel_ifaces
-        .values()
+        .get_absent_ifaces_in_teardown_order()
+        .into_iter()
+        .chain(
+            merged_state
+                .interfaces
+                .kernel_ifaces
+                .values()
+                .filter(|i| i.is_changed() && i.merged.is_down()),
+        )
         .filter(|i| {
-            i.is_changed() && (i.merged.is_absent() || i.merged.is_down())
+            !i.merged.is_userspace()
+                && !matches!(i.merged, Interface::Custom(_))
         })
         .map(|i| &i.merged)
     {
@@ -356,7 +493,7 @@ fn delete_remain_virtual_interface_as_desired(
 // If any connection still referring to deleted UUID, we should delete it also
 fn delete_orphan_ports(
     nm_api: &mut NmApi,
-    uuids_deleted: &HashSet<&str>,
+    uuids_deleted: &[&str],
 ) -> Result<(), NmstateError> {
     let mut uuids_to_delete = Vec::new();
     let all_nm_conns = nm_api.connections_get().map_err(nm_error_to_nmstate)?;
@@ -365,7 +502,7 @@ fn delete_orphan_ports(
             continue;
         }
         if let Some(ctrl_uuid) = nm_conn.controller() {
-            if uuids_deleted.contains(ctrl_uuid) {
+            if uuids_deleted.contains(&ctrl_uuid) {
                 if let Some(uuid) = nm_conn.uuid() {
                     log::info!(
                         "Deleting NM orphan profile {}/{}: {}",
@@ -412,6 +549,8 @@ fn gen_nm_conn_need_to_deactivate_first(
                     || is_vrf_table_id_changed(nm_conn, activated_nm_con)
                     || is_vlan_changed(nm_conn, activated_nm_con)
                     || is_vxlan_changed(nm_conn, activated_nm_con)
+                    || is_ip_tunnel_changed(nm_conn, activated_nm_con)
+                    || is_ipvlan_changed(nm_conn, activated_nm_con)
                     || is_veth_peer_changed(nm_conn, activated_nm_con)
                     || is_mptcp_flags_changed(nm_conn, activated_nm_con)
                 {
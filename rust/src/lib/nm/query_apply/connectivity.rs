@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::Write;
+
+use super::super::{error::nm_error_to_nmstate, nm_dbus::NmApi};
+use crate::{
+    ConnectivityCheckConfig, ErrorKind, MergedNetworkState, NmstateError,
+};
+
+const CONNECTIVITY_CONF_DIR: &str = "/etc/NetworkManager/conf.d";
+const CONNECTIVITY_CONF_DIR_MEMORY_ONLY: &str = "/run/NetworkManager/conf.d";
+const CONNECTIVITY_CONF_FILE_NAME: &str = "99-nmstate-connectivity.conf";
+
+const NM_MANAGER_RELOAD_FLAG_CONF: u32 = 0x1;
+
+pub(crate) fn nm_connectivity_check_retrieve(
+    nm_api: &NmApi,
+) -> Option<ConnectivityCheckConfig> {
+    let enabled = match nm_api.connectivity_check_enabled() {
+        Ok(e) => e,
+        Err(e) => {
+            log::debug!("Failed to retrieve connectivity check state: {e}");
+            return None;
+        }
+    };
+    let mut ret = ConnectivityCheckConfig::new();
+    ret.enabled = Some(enabled);
+    ret.uri = nm_api.connectivity_check_uri().ok();
+    Some(ret)
+}
+
+pub(crate) fn apply_connectivity_check(
+    nm_api: &mut NmApi,
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    if !merged_state.connectivity_check.is_changed() {
+        return Ok(());
+    }
+    let desired = match merged_state.connectivity_check.desired.as_ref() {
+        Some(d) => d,
+        None => return Ok(()),
+    };
+    // NetworkManager does not expose D-Bus API for changing the URI,
+    // store it into configuration folder and reload.
+    if let Some(uri) = desired.uri.as_deref() {
+        write_connectivity_conf(
+            &connectivity_conf_path(merged_state.memory_only),
+            format!(
+                "# Generated by nmstate, do not edit\n[connectivity]\n\
+                uri={uri}\n"
+            )
+            .as_bytes(),
+        )?;
+        nm_api
+            .reload(NM_MANAGER_RELOAD_FLAG_CONF)
+            .map_err(nm_error_to_nmstate)?;
+    }
+    if let Some(enabled) = desired.enabled {
+        nm_api
+            .set_connectivity_check_enabled(enabled)
+            .map_err(nm_error_to_nmstate)?;
+    }
+    Ok(())
+}
+
+// The NetworkManager checkpoint does not cover the configuration file and
+// the connectivity check state, hence backup them before applying and
+// restore on failure.
+#[derive(Debug)]
+pub(crate) struct NmConnectivityConfBackup {
+    nm_dbus_address: Option<String>,
+    file_path: String,
+    // None means file does not exist
+    content: Option<Vec<u8>>,
+    enabled: Option<bool>,
+}
+
+impl NmConnectivityConfBackup {
+    // Return None if desired state does not change connectivity check.
+    pub(crate) fn new(
+        nm_dbus_address: Option<&str>,
+        desired: Option<&ConnectivityCheckConfig>,
+        memory_only: bool,
+    ) -> Result<Option<Self>, NmstateError> {
+        let desired = match desired {
+            Some(d) if d.uri.is_some() || d.enabled.is_some() => d,
+            _ => return Ok(None),
+        };
+        let file_path = connectivity_conf_path(memory_only);
+        if desired.uri.is_some() && memory_only {
+            // NetworkManager prefers file in /etc over the one of the same
+            // name in /run, memory only URI would never take effect.
+            let persistent_path = connectivity_conf_path(false);
+            if std::path::Path::new(&persistent_path).exists() {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Cannot store connectivity check URI in memory \
+                        only mode as {persistent_path} takes precedence, \
+                        please apply without memory only mode"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        let content = match std::fs::read(&file_path) {
+            Ok(c) => Some(c),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!(
+                        "Failed to read connectivity check configuration \
+                        {file_path}: {e}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        let enabled = if desired.enabled.is_some() {
+            let nm_api = NmApi::new_with_address(nm_dbus_address)
+                .map_err(nm_error_to_nmstate)?;
+            Some(
+                nm_api
+                    .connectivity_check_enabled()
+                    .map_err(nm_error_to_nmstate)?,
+            )
+        } else {
+            None
+        };
+        Ok(Some(Self {
+            nm_dbus_address: nm_dbus_address.map(|a| a.to_string()),
+            file_path,
+            content,
+            enabled,
+        }))
+    }
+
+    // Errors are only logged as the failure of apply is more important to
+    // report.
+    pub(crate) fn restore(&self) {
+        log::info!("Restoring connectivity check configuration");
+        let result = match self.content.as_deref() {
+            Some(content) => write_connectivity_conf(&self.file_path, content),
+            None => match std::fs::remove_file(&self.file_path) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    format!("Failed to remove {}: {e}", self.file_path),
+                )),
+            },
+        };
+        if let Err(e) = result.and_then(|_| {
+            let mut nm_api =
+                NmApi::new_with_address(self.nm_dbus_address.as_deref())
+                    .map_err(nm_error_to_nmstate)?;
+            nm_api
+                .reload(NM_MANAGER_RELOAD_FLAG_CONF)
+                .map_err(nm_error_to_nmstate)?;
+            if let Some(enabled) = self.enabled {
+                nm_api
+                    .set_connectivity_check_enabled(enabled)
+                    .map_err(nm_error_to_nmstate)?;
+            }
+            Ok(())
+        }) {
+            log::warn!("Failed to restore connectivity check config: {e}");
+        }
+    }
+}
+
+fn connectivity_conf_path(memory_only: bool) -> String {
+    let dir = if memory_only {
+        CONNECTIVITY_CONF_DIR_MEMORY_ONLY
+    } else {
+        CONNECTIVITY_CONF_DIR
+    };
+    format!("{dir}/{CONNECTIVITY_CONF_FILE_NAME}")
+}
+
+// Write to temporary file in the same folder and rename, so that
+// NetworkManager never loads partially written file. The temporary file is
+// not ending with `.conf`, hence ignored by NetworkManager.
+pub(crate) fn write_connectivity_conf(
+    file_path: &str,
+    content: &[u8],
+) -> Result<(), NmstateError> {
+    let tmp_path = format!("{file_path}.nmstate-tmp");
+    log::debug!("Storing connectivity check configuration to {file_path}");
+    let result = std::path::Path::new(file_path)
+        .parent()
+        .map(std::fs::create_dir_all)
+        .unwrap_or(Ok(()))
+        .and_then(|_| {
+            let mut fd = std::fs::File::create(&tmp_path)?;
+            fd.write_all(content)?;
+            fd.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&tmp_path, file_path));
+    if let Err(e) = result {
+        std::fs::remove_file(&tmp_path).ok();
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!(
+                "Failed to store connectivity check configuration to \
+                {file_path}: {e}"
+            ),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(())
+}
@@ -1,13 +1,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    nm::nm_dbus::NmDevice,
+    nm::nm_dbus::{NmDevice, NmSettingIpTunnel},
     nm::settings::{
-        NM_SETTING_BOND_SETTING_NAME, NM_SETTING_BRIDGE_SETTING_NAME,
-        NM_SETTING_DUMMY_SETTING_NAME, NM_SETTING_INFINIBAND_SETTING_NAME,
-        NM_SETTING_LOOPBACK_SETTING_NAME, NM_SETTING_MACSEC_SETTING_NAME,
-        NM_SETTING_MACVLAN_SETTING_NAME, NM_SETTING_OVS_BRIDGE_SETTING_NAME,
-        NM_SETTING_OVS_IFACE_SETTING_NAME, NM_SETTING_VETH_SETTING_NAME,
+        NM_DEVICE_TYPE_MODEM_NAME, NM_SETTING_BOND_SETTING_NAME,
+        NM_SETTING_BRIDGE_SETTING_NAME, NM_SETTING_DUMMY_SETTING_NAME,
+        NM_SETTING_INFINIBAND_SETTING_NAME, NM_SETTING_IPVLAN_SETTING_NAME,
+        NM_SETTING_IP_TUNNEL_SETTING_NAME, NM_SETTING_LOOPBACK_SETTING_NAME,
+        NM_SETTING_MACSEC_SETTING_NAME, NM_SETTING_MACVLAN_SETTING_NAME,
+        NM_SETTING_OVS_BRIDGE_SETTING_NAME, NM_SETTING_OVS_IFACE_SETTING_NAME,
+        NM_SETTING_TEAM_SETTING_NAME, NM_SETTING_VETH_SETTING_NAME,
         NM_SETTING_VLAN_SETTING_NAME, NM_SETTING_VRF_SETTING_NAME,
         NM_SETTING_VXLAN_SETTING_NAME, NM_SETTING_WIRED_SETTING_NAME,
     },
@@ -33,9 +35,22 @@ pub(crate) fn nm_dev_iface_type_to_nmstate(nm_dev: &NmDevice) -> InterfaceType {
                 InterfaceType::MacVlan
             }
         }
+        NM_SETTING_IPVLAN_SETTING_NAME => InterfaceType::IpVlan,
         NM_SETTING_LOOPBACK_SETTING_NAME => InterfaceType::Loopback,
         NM_SETTING_INFINIBAND_SETTING_NAME => InterfaceType::InfiniBand,
         NM_SETTING_MACSEC_SETTING_NAME => InterfaceType::MacSec,
+        NM_SETTING_TEAM_SETTING_NAME => InterfaceType::Team,
+        NM_DEVICE_TYPE_MODEM_NAME => InterfaceType::Modem,
+        NM_SETTING_IP_TUNNEL_SETTING_NAME => match nm_dev.ip_tunnel_mode {
+            NmSettingIpTunnel::MODE_GRE => InterfaceType::Gre,
+            NmSettingIpTunnel::MODE_GRETAP => InterfaceType::Gretap,
+            NmSettingIpTunnel::MODE_IP6GRE => InterfaceType::Ip6Gre,
+            NmSettingIpTunnel::MODE_IP6GRETAP => InterfaceType::Ip6Gretap,
+            NmSettingIpTunnel::MODE_IPIP6 | NmSettingIpTunnel::MODE_IP6IP6 => {
+                InterfaceType::Ip6Tnl
+            }
+            _ => InterfaceType::Other(nm_dev.iface_type.to_string()),
+        },
         _ => InterfaceType::Other(nm_dev.iface_type.to_string()),
     }
 }
@@ -3,13 +3,17 @@
 use std::str::FromStr;
 
 use super::super::{
-    dns::extract_ipv6_link_local_iface_from_dns_srv,
+    dns::{
+        cur_dns_ifaces_still_valid_for_dns,
+        extract_ipv6_link_local_iface_from_dns_srv,
+    },
     error::nm_error_to_nmstate,
     nm_dbus::{NmApi, NmDnsEntry, NmGlobalDnsConfig, NmSettingIp},
 };
 
 use crate::{
-    ip::is_ipv6_unicast_link_local, DnsClientState, DnsState, Interfaces,
+    ip::{is_ipv6_addr, is_ipv6_unicast_link_local},
+    DnsClientState, DnsState, Interfaces, Ipv6RaDnssl, Ipv6RaInfo, Ipv6RaRdnss,
     MergedNetworkState, NmstateError,
 };
 
@@ -41,6 +45,7 @@ pub(crate) fn nm_dns_to_nmstate(
         search: nm_ip_setting.dns_search.clone(),
         options: nm_ip_setting.dns_options.clone(),
         priority: nm_ip_setting.dns_priority,
+        ..Default::default()
     }
 }
 
@@ -149,6 +154,80 @@ pub(crate) fn retrieve_dns_info(
     })
 }
 
+// NetworkManager does not expose the source of learned DNS information, treat
+// the IPv6 name servers learned on interface with IPv6 autoconf enabled and
+// DHCPv6 disabled as RDNSS, and search domains of the same DNS entry as DNSSL.
+// The lifetime is not provided by NetworkManager.
+pub(crate) fn retrieve_ipv6_ra_dns_info(
+    nm_api: &mut NmApi,
+    ifaces: &mut Interfaces,
+) -> Result<(), NmstateError> {
+    let nm_dns_entires = nm_api
+        .get_dns_configuration()
+        .map_err(nm_error_to_nmstate)?;
+    for nm_dns_entry in nm_dns_entires
+        .iter()
+        .filter(|e| !e.is_vpn && !e.interface.is_empty())
+    {
+        let ipv6 = match ifaces
+            .kernel_ifaces
+            .get_mut(&nm_dns_entry.interface)
+            .and_then(|i| i.base_iface_mut().ipv6.as_mut())
+        {
+            Some(i)
+                if i.enabled
+                    && i.autoconf == Some(true)
+                    && i.dhcp != Some(true) =>
+            {
+                i
+            }
+            _ => continue,
+        };
+        let static_dns = ipv6.dns.clone().unwrap_or_default();
+        let static_srvs: Vec<&str> = static_dns
+            .server
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|s| s.split('%').next())
+            .collect();
+        let static_schs = static_dns.search.unwrap_or_default();
+
+        let rdnss: Vec<Ipv6RaRdnss> = nm_dns_entry
+            .name_servers
+            .iter()
+            .filter(|s| is_ipv6_addr(s) && !static_srvs.contains(&s.as_str()))
+            .map(|s| Ipv6RaRdnss {
+                server: s.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        // DNS entry of IPv4 config
+        if rdnss.is_empty() {
+            continue;
+        }
+        let dnssl: Vec<Ipv6RaDnssl> = nm_dns_entry
+            .domains
+            .iter()
+            .filter(|d| !static_schs.contains(d))
+            .map(|d| Ipv6RaDnssl {
+                domain: d.to_string(),
+                ..Default::default()
+            })
+            .collect();
+
+        let ra = ipv6.ra.get_or_insert_with(Ipv6RaInfo::new);
+        ra.rdnss.get_or_insert_with(Vec::new).extend(rdnss);
+        if !dnssl.is_empty() {
+            ra.dnssl.get_or_insert_with(Vec::new).extend(dnssl);
+        }
+        if !ipv6.prop_list.contains(&"ra") {
+            ipv6.prop_list.push("ra");
+        }
+    }
+    Ok(())
+}
+
 fn nm_dns_srvs_to_nmstate(nm_dns_entry: &NmDnsEntry) -> Vec<String> {
     let mut srvs = Vec::new();
     for srv in nm_dns_entry.name_servers.as_slice() {
@@ -230,6 +309,15 @@ pub(crate) fn nm_global_dns_to_nmstate(
     }
 }
 
+// DNS config should be applied when desired DNS changed or the interfaces
+// currently holding DNS config cannot hold it any more. DNS requested to be
+// left untouched is never purged or moved.
+pub(crate) fn is_dns_apply_required(merged_state: &MergedNetworkState) -> bool {
+    !merged_state.dns.ignored
+        && (merged_state.dns.is_changed()
+            || !cur_dns_ifaces_still_valid_for_dns(&merged_state.interfaces))
+}
+
 // To save us from NM iface-DNS mess, we prefer global DNS over iface DNS,
 // unless use case like:
 //  1. Has IPv6 link-local address as name server: e.g. `fe80::deef:1%eth1`
@@ -238,7 +326,11 @@ pub(crate) fn nm_global_dns_to_nmstate(
 //  3. User want to force DNS server stored in interface for static IP
 //     interface. This case, user need to state static DNS config along with
 //     static IP config.
+//  4. User defined the interface to hold DNS config explicitly.
 pub(crate) fn is_iface_dns_desired(merged_state: &MergedNetworkState) -> bool {
+    if merged_state.dns.interface.is_some() {
+        return true;
+    }
     if merged_state.dns.is_changed() {
         if extract_ipv6_link_local_iface_from_dns_srv(
             merged_state.dns.servers.as_slice(),
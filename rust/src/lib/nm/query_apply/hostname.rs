@@ -0,0 +1,36 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::{
+    error::nm_error_to_nmstate,
+    nm_dbus::{ErrorKind, NmApi, NmSettingError},
+};
+use crate::NmstateError;
+
+// Set static hostname via NetworkManager, return Ok(false) if NetworkManager
+// is not running or does not support setting static hostname.
+pub(crate) fn nm_hostname_set(
+    nm_dbus_address: Option<&str>,
+    hostname: &str,
+) -> Result<bool, NmstateError> {
+    let mut nm_api = match NmApi::new_with_address(nm_dbus_address) {
+        Ok(n) => n,
+        Err(e) => {
+            log::info!("NetworkManager is not available: {e}");
+            return Ok(false);
+        }
+    };
+    match nm_api.hostname_set(hostname) {
+        Ok(()) => Ok(true),
+        Err(e)
+            if matches!(
+                e.kind,
+                ErrorKind::DbusTransientError
+                    | ErrorKind::Setting(NmSettingError::NotSupported)
+            ) =>
+        {
+            log::info!("NetworkManager cannot set static hostname: {e}");
+            Ok(false)
+        }
+        Err(e) => Err(nm_error_to_nmstate(e)),
+    }
+}
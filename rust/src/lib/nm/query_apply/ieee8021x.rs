@@ -2,7 +2,7 @@
 
 use super::super::nm_dbus::NmSetting8021X;
 
-use crate::Ieee8021XConfig;
+use crate::{Ieee8021XConfig, Secret};
 
 pub(crate) fn nm_802_1x_to_nmstate(
     nm_setting: &NmSetting8021X,
@@ -19,7 +19,10 @@ pub(crate) fn nm_802_1x_to_nmstate(
             .as_deref()
             .and_then(vec_u8_to_file_path),
         ca_cert: nm_setting.ca_cert.as_deref().and_then(vec_u8_to_file_path),
-        private_key_password: nm_setting.private_key_password.clone(),
+        private_key_password: nm_setting
+            .private_key_password
+            .clone()
+            .map(Secret::from),
     }
 }
 
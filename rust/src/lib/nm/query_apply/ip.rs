@@ -4,14 +4,15 @@ use std::convert::TryFrom;
 use std::ops::BitXor;
 
 use super::super::nm_dbus::{
-    NmIpRouteRuleAction, NmSettingIp, NmSettingIpMethod,
+    NmApi, NmConnection, NmIpRouteRuleAction, NmSettingIp, NmSettingIpMethod,
 };
+use super::super::version::is_nm_version_at_least;
 
 use super::dns::nm_dns_to_nmstate;
 
 use crate::{
     AddressFamily, Dhcpv4ClientId, Dhcpv6Duid, InterfaceIpv4, InterfaceIpv6,
-    Ipv6AddrGenMode, RouteRuleAction, RouteRuleEntry, WaitIp,
+    IpNetwork, Ipv6AddrGenMode, RouteRuleAction, RouteRuleEntry, WaitIp,
 };
 
 const ADDR_GEN_MODE_EUI64: i32 = 0;
@@ -62,6 +63,8 @@ pub(crate) fn nm_ip_setting_to_nmstate4(
                 "rules",
                 "dhcp_send_hostname",
                 "dhcp_custom_hostname",
+                "dhcp_send_release",
+                "dad_timeout",
             ],
             dns: Some(nm_dns_to_nmstate("", nm_ip_setting)),
             rules: nm_rules_to_nmstate(false, nm_ip_setting),
@@ -71,6 +74,14 @@ pub(crate) fn nm_ip_setting_to_nmstate4(
                 None
             },
             auto_route_metric: nm_ip_setting.route_metric.map(|i| i as u32),
+            // NM use -1 for global default
+            dad_timeout: if enabled {
+                nm_ip_setting
+                    .dad_timeout
+                    .and_then(|t| u32::try_from(t).ok())
+            } else {
+                None
+            },
             dhcp_send_hostname: if enabled && dhcp == Some(true) {
                 Some(dhcp_send_hostname)
             } else {
@@ -88,6 +99,11 @@ pub(crate) fn nm_ip_setting_to_nmstate4(
             } else {
                 None
             },
+            dhcp_send_release: if enabled && dhcp == Some(true) {
+                nm_ip_setting.dhcp_send_release
+            } else {
+                None
+            },
             ..Default::default()
         }
     } else {
@@ -134,6 +150,7 @@ pub(crate) fn nm_ip_setting_to_nmstate6(
                 "auto_route_metric",
                 "dhcp_send_hostname",
                 "dhcp_custom_hostname",
+                "dhcp_send_release",
             ],
             dns: Some(nm_dns_to_nmstate(iface_name, nm_ip_setting)),
             rules: nm_rules_to_nmstate(true, nm_ip_setting),
@@ -156,6 +173,11 @@ pub(crate) fn nm_ip_setting_to_nmstate6(
             } else {
                 None
             },
+            dhcp_send_release: if enabled && dhcp == Some(true) {
+                nm_ip_setting.dhcp_send_release
+            } else {
+                None
+            },
             ..Default::default()
         };
         // NetworkManager only set IPv6 token to kernel when IPv6 autoconf
@@ -172,6 +194,17 @@ pub(crate) fn nm_ip_setting_to_nmstate6(
     }
 }
 
+// NetworkManager supports `ipv6.token` since 1.4
+pub(crate) fn is_ipv6_token_supported(nm_api: &NmApi) -> bool {
+    is_nm_version_at_least(nm_api.version().unwrap_or_default().as_str(), 1, 4)
+}
+
+pub(crate) fn remove_nm_ipv6_token(nm_conn: &mut NmConnection) {
+    if let Some(nm_ip_setting) = nm_conn.ipv6.as_mut() {
+        nm_ip_setting.token = None;
+    }
+}
+
 // return (auto_dns, auto_gateway, auto_routes, auto_table_id)
 fn parse_dhcp_opts(
     nm_setting: &NmSettingIp,
@@ -199,7 +232,10 @@ fn nm_dhcp_duid_to_nmstate(nm_setting: &NmSettingIp) -> Option<Dhcpv6Duid> {
         Some("ll") => Some(Dhcpv6Duid::LinkLayerAddress),
         Some("llt") => Some(Dhcpv6Duid::LinkLayerAddressPlusTime),
         Some("uuid") => Some(Dhcpv6Duid::Uuid),
-        Some(nm_duid) => Some(Dhcpv6Duid::Other(nm_duid.to_string())),
+        Some(nm_duid) => Some(
+            Dhcpv6Duid::try_from(nm_duid.to_string())
+                .unwrap_or_else(|_| Dhcpv6Duid::Other(nm_duid.to_string())),
+        ),
         None => None,
     }
 }
@@ -210,7 +246,10 @@ fn nm_dhcp_client_id_to_nmstate(
     match nm_setting.dhcp_client_id.as_deref() {
         Some("mac") => Some(Dhcpv4ClientId::LinkLayerAddress),
         Some("duid") => Some(Dhcpv4ClientId::IaidPlusDuid),
-        Some(nm_id) => Some(Dhcpv4ClientId::Other(nm_id.to_string())),
+        Some(nm_id) => Some(
+            Dhcpv4ClientId::try_from(nm_id.to_string())
+                .unwrap_or_else(|_| Dhcpv4ClientId::Other(nm_id.to_string())),
+        ),
         None => None,
     }
 }
@@ -242,6 +281,12 @@ pub(crate) fn query_nmstate_wait_ip(
     match (ipv4_set, ipv6_set) {
         (Some(ipv4_set), Some(ipv6_set)) => {
             match (ipv4_set.may_fail.as_ref(), ipv6_set.may_fail.as_ref()) {
+                (Some(true), Some(true))
+                    if ipv4_set.required_timeout.is_some()
+                        && ipv6_set.required_timeout.is_some() =>
+                {
+                    Some(WaitIp::AnyWithGrace)
+                }
                 (Some(true), Some(true))
                 | (Some(true), None)
                 | (None, Some(true))
@@ -267,6 +312,19 @@ pub(crate) fn query_nmstate_wait_ip(
     }
 }
 
+pub(crate) fn query_nmstate_wait_ip_timeout(
+    ipv4_set: Option<&NmSettingIp>,
+    ipv6_set: Option<&NmSettingIp>,
+) -> Option<u32> {
+    [ipv4_set, ipv6_set]
+        .into_iter()
+        .flatten()
+        .filter(|s| s.may_fail != Some(false))
+        .filter_map(|s| s.required_timeout)
+        .max()
+        .and_then(|t| u32::try_from(t / 1000).ok())
+}
+
 fn nm_rules_to_nmstate(
     is_ipv6: bool,
     ip_set: &NmSettingIp,
@@ -285,12 +343,13 @@ fn nm_rules_to_nmstate(
         if let (Some(from), Some(from_len)) =
             (nm_rule.from.as_ref(), nm_rule.from_len.as_ref())
         {
-            rule.ip_from = Some(format!("{from}/{from_len}"));
+            rule.ip_from =
+                IpNetwork::try_from(format!("{from}/{from_len}")).ok();
         }
         if let (Some(to), Some(to_len)) =
             (nm_rule.to.as_ref(), nm_rule.to_len.as_ref())
         {
-            rule.ip_to = Some(format!("{to}/{to_len}"));
+            rule.ip_to = IpNetwork::try_from(format!("{to}/{to_len}")).ok();
         }
         if let Some(v) = nm_rule.table.as_ref() {
             rule.table_id = Some(*v);
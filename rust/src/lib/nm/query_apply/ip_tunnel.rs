@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+
+use super::super::nm_dbus::{NmConnection, NmSettingIpTunnel};
+
+use crate::{GreConfig, Ip6TnlMode, Ip6TunnelConfig};
+
+pub(crate) fn nm_ip_tunnel_to_gre_conf(
+    nm_set: &NmSettingIpTunnel,
+) -> GreConfig {
+    let mut ret = GreConfig::new();
+    ret.base_iface = nm_set.parent.clone().filter(|p| !p.is_empty());
+    ret.local = nm_set
+        .local
+        .as_deref()
+        .and_then(|a| std::net::IpAddr::from_str(a).ok());
+    ret.remote = nm_set
+        .remote
+        .as_deref()
+        .and_then(|a| std::net::IpAddr::from_str(a).ok());
+    ret.ikey = nm_set.input_key.as_deref().and_then(|k| k.parse().ok());
+    ret.okey = nm_set.output_key.as_deref().and_then(|k| k.parse().ok());
+    ret.ttl = nm_set.ttl.and_then(|v| u8::try_from(v).ok());
+    ret.tos = nm_set.tos.and_then(|v| u8::try_from(v).ok());
+    ret
+}
+
+pub(crate) fn nm_ip_tunnel_to_ip6_tunnel_conf(
+    nm_set: &NmSettingIpTunnel,
+) -> Ip6TunnelConfig {
+    let mut ret = Ip6TunnelConfig::new();
+    ret.mode = match nm_set.mode {
+        Some(NmSettingIpTunnel::MODE_IPIP6) => Some(Ip6TnlMode::Ipip6),
+        Some(NmSettingIpTunnel::MODE_IP6IP6) => Some(Ip6TnlMode::Ip6ip6),
+        _ => None,
+    };
+    ret.base_iface = nm_set.parent.clone().filter(|p| !p.is_empty());
+    ret.local = nm_set
+        .local
+        .as_deref()
+        .and_then(|a| std::net::Ipv6Addr::from_str(a).ok());
+    ret.remote = nm_set
+        .remote
+        .as_deref()
+        .and_then(|a| std::net::Ipv6Addr::from_str(a).ok());
+    ret.ikey = nm_set.input_key.as_deref().and_then(|k| k.parse().ok());
+    ret.okey = nm_set.output_key.as_deref().and_then(|k| k.parse().ok());
+    ret.encap_limit = nm_set
+        .encapsulation_limit
+        .and_then(|v| u8::try_from(v).ok());
+    ret.flow_label = nm_set.flow_label;
+    ret.hop_limit = nm_set.ttl.and_then(|v| u8::try_from(v).ok());
+    ret
+}
+
+// NetworkManager cannot change tunnel endpoints or keys of existing tunnel
+// device, need to deactivate the profile before activating new one.
+pub(crate) fn is_ip_tunnel_changed(
+    new_nm_conn: &NmConnection,
+    cur_nm_conn: &NmConnection,
+) -> bool {
+    if let (Some(new_set), Some(cur_set)) = (
+        new_nm_conn.ip_tunnel.as_ref(),
+        cur_nm_conn.ip_tunnel.as_ref(),
+    ) {
+        new_set.mode != cur_set.mode
+            || new_set.parent != cur_set.parent
+            || new_set.local != cur_set.local
+            || new_set.remote != cur_set.remote
+            || new_set.input_key != cur_set.input_key
+            || new_set.output_key != cur_set.output_key
+    } else {
+        false
+    }
+}
@@ -0,0 +1,31 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::{NmConnection, NmSettingIpVlan};
+
+use crate::{IpVlanConfig, IpVlanMode};
+
+pub(crate) fn nm_ip_vlan_to_ipvlan_conf(
+    nm_set: &NmSettingIpVlan,
+) -> IpVlanConfig {
+    IpVlanConfig {
+        base_iface: nm_set.parent.clone().unwrap_or_default(),
+        mode: nm_set.mode.map(IpVlanMode::from).unwrap_or_default(),
+        private: Some(nm_set.private.unwrap_or_default()),
+        vepa: Some(nm_set.vepa.unwrap_or_default()),
+    }
+}
+
+// NetworkManager cannot change the base interface or mode of existing IP VLAN
+// device, need to deactivate the profile before activating new one.
+pub(crate) fn is_ipvlan_changed(
+    new_nm_conn: &NmConnection,
+    cur_nm_conn: &NmConnection,
+) -> bool {
+    if let (Some(new_set), Some(cur_set)) =
+        (new_nm_conn.ip_vlan.as_ref(), cur_nm_conn.ip_vlan.as_ref())
+    {
+        new_set.parent != cur_set.parent || new_set.mode != cur_set.mode
+    } else {
+        false
+    }
+}
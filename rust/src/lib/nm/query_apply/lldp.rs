@@ -1,17 +1,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::Write;
 
-use super::super::nm_dbus::{
-    NmConnection, NmLldpNeighbor, NmLldpNeighbor8021Vlan,
+use super::super::{
+    error::nm_error_to_nmstate,
+    nm_dbus::{NmApi, NmConnection, NmLldpNeighbor, NmLldpNeighbor8021Vlan},
 };
 
 use crate::{
-    LldpAddressFamily, LldpChassisId, LldpConfig, LldpMacPhyConf,
-    LldpMaxFrameSize, LldpMgmtAddr, LldpMgmtAddrs, LldpNeighborTlv, LldpPortId,
+    query_apply::lldp::get_lldp_neighbors_changes, LldpAddressFamily,
+    LldpChassisId, LldpConfig, LldpMacPhyConf, LldpMaxFrameSize, LldpMgmtAddr,
+    LldpMgmtAddrs, LldpNeighborTlv, LldpNeighborsChange, LldpPortId,
     LldpPpvids, LldpSystemCapabilities, LldpSystemDescription, LldpSystemName,
-    LldpVlan, LldpVlans,
+    LldpVlan, LldpVlans, NmstateError,
 };
 
 pub(crate) fn is_lldp_enabled(nm_conn: &NmConnection) -> bool {
@@ -32,6 +35,59 @@ pub(crate) fn get_lldp(nm_infos: Vec<NmLldpNeighbor>) -> LldpConfig {
     }
 }
 
+const LLDP_POLL_INTERVAL_MILLISECONDS: u64 = 1000;
+
+// Block till LLDP neighbors of specified interfaces changed or `timeout`
+// seconds passed, empty `iface_names` means all interfaces.
+// The same NM D-Bus connection is used for all the polls.
+pub(crate) fn nm_lldp_neighbors_wait_change(
+    nm_dbus_address: Option<&str>,
+    iface_names: &[&str],
+    timeout: u32,
+) -> Result<Vec<LldpNeighborsChange>, NmstateError> {
+    let mut nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
+    let deadline = std::time::Instant::now()
+        + std::time::Duration::from_secs(timeout.into());
+    let old_neighbors = nm_lldp_neighbors_get(&mut nm_api, iface_names)?;
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(
+            LLDP_POLL_INTERVAL_MILLISECONDS,
+        ));
+        let new_neighbors = nm_lldp_neighbors_get(&mut nm_api, iface_names)?;
+        let changes =
+            get_lldp_neighbors_changes(&old_neighbors, &new_neighbors);
+        if !changes.is_empty() {
+            return Ok(changes);
+        }
+        if std::time::Instant::now() >= deadline {
+            log::debug!("No LLDP neighbors change found in {timeout} seconds");
+            return Ok(Vec::new());
+        }
+    }
+}
+
+// Retrieve LLDP neighbors of specified interfaces, empty `iface_names` means
+// all interfaces.
+fn nm_lldp_neighbors_get(
+    nm_api: &mut NmApi,
+    iface_names: &[&str],
+) -> Result<HashMap<String, Vec<Vec<LldpNeighborTlv>>>, NmstateError> {
+    let nm_devs = nm_api.devices_get().map_err(nm_error_to_nmstate)?;
+    let mut ret = HashMap::new();
+    for nm_dev in nm_devs.iter().filter(|d| {
+        d.real
+            && (iface_names.is_empty()
+                || iface_names.contains(&d.name.as_str()))
+    }) {
+        let nm_infos = nm_api
+            .device_lldp_neighbor_get(&nm_dev.obj_path)
+            .map_err(nm_error_to_nmstate)?;
+        ret.insert(nm_dev.name.to_string(), get_lldp(nm_infos).neighbors);
+    }
+    Ok(ret)
+}
+
 fn nm_neighbor_to_nmstate(nm_info: &NmLldpNeighbor) -> Vec<LldpNeighborTlv> {
     let mut ret = Vec::new();
 
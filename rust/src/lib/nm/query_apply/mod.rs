@@ -1,16 +1,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod apply;
+pub(crate) mod connectivity;
 pub(crate) mod device;
 pub(crate) mod dispatch;
 pub(crate) mod dns;
+mod hostname;
 mod ieee8021x;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod lldp;
+mod modem;
 mod mptcp;
 pub(crate) mod ovs;
 mod profile;
 mod route;
+mod secret_agent;
+mod sriov;
 mod user;
 mod veth;
 mod vlan;
@@ -18,20 +25,37 @@ pub(crate) mod vpn;
 mod vrf;
 mod vxlan;
 
-pub(crate) use self::apply::nm_apply;
-pub(crate) use self::dns::retrieve_dns_info;
+pub(crate) use self::apply::{
+    nm_apply, nm_apply_hostname_dns_only, nm_can_apply_hostname_dns_only,
+};
+pub(crate) use self::connectivity::NmConnectivityConfBackup;
+pub(crate) use self::dns::{retrieve_dns_info, retrieve_ipv6_ra_dns_info};
+pub(crate) use self::hostname::nm_hostname_set;
 pub(crate) use self::ieee8021x::nm_802_1x_to_nmstate;
 pub(crate) use self::ip::{
-    nm_ip_setting_to_nmstate4, nm_ip_setting_to_nmstate6, query_nmstate_wait_ip,
+    is_ipv6_token_supported, nm_ip_setting_to_nmstate4,
+    nm_ip_setting_to_nmstate6, query_nmstate_wait_ip,
+    query_nmstate_wait_ip_timeout, remove_nm_ipv6_token,
+};
+pub(crate) use self::ip_tunnel::{
+    is_ip_tunnel_changed, nm_ip_tunnel_to_gre_conf,
+    nm_ip_tunnel_to_ip6_tunnel_conf,
+};
+pub(crate) use self::ip_vlan::{is_ipvlan_changed, nm_ip_vlan_to_ipvlan_conf};
+pub(crate) use self::lldp::{
+    get_lldp, is_lldp_enabled, nm_lldp_neighbors_wait_change,
 };
-pub(crate) use self::lldp::{get_lldp, is_lldp_enabled};
+pub(crate) use self::modem::nm_gsm_to_modem_conf;
 pub(crate) use self::mptcp::{is_mptcp_flags_changed, is_mptcp_supported};
 pub(crate) use self::ovs::delete_orphan_ovs_ports;
 pub(crate) use self::profile::{
     activate_nm_profiles, create_index_for_nm_conns_by_name_type,
-    deactivate_nm_profiles, delete_exist_profiles, save_nm_profiles,
+    deactivate_nm_profiles, delete_exist_profiles, nm_stale_profiles_get,
+    save_nm_profiles, set_volatile_nm_profiles,
 };
 pub(crate) use self::route::is_route_removed;
+pub(crate) use self::secret_agent::nm_secret_agent_start;
+pub(crate) use self::sriov::get_sriov_vlan_trunk_conf;
 pub(crate) use self::user::get_description;
 pub(crate) use self::veth::is_veth_peer_changed;
 pub(crate) use self::vlan::is_vlan_changed;
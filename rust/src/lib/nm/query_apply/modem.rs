@@ -0,0 +1,19 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmSettingGsm;
+
+use crate::{ModemConfig, Secret};
+
+// The SIM PIN is only available in saved connection
+pub(crate) fn nm_gsm_to_modem_conf(
+    nm_set: &NmSettingGsm,
+    nm_saved_set: Option<&NmSettingGsm>,
+) -> ModemConfig {
+    let mut ret = ModemConfig::new();
+    ret.apn = Some(nm_set.apn.clone().unwrap_or_default());
+    ret.pin = nm_saved_set
+        .and_then(|s| s.pin.as_ref())
+        .map(|p| Secret::from(p.as_str()));
+    ret.roaming = Some(!nm_set.home_only.unwrap_or_default());
+    ret
+}
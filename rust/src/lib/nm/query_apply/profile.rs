@@ -8,14 +8,14 @@ use super::super::nm_dbus::{
 use super::super::{
     error::nm_error_to_nmstate,
     settings::{
-        NM_SETTING_BOND_SETTING_NAME, NM_SETTING_BRIDGE_SETTING_NAME,
-        NM_SETTING_OVS_BRIDGE_SETTING_NAME, NM_SETTING_OVS_PORT_SETTING_NAME,
-        NM_SETTING_VETH_SETTING_NAME, NM_SETTING_VRF_SETTING_NAME,
-        NM_SETTING_WIRED_SETTING_NAME,
+        is_nmstate_generated, NM_SETTING_BOND_SETTING_NAME,
+        NM_SETTING_BRIDGE_SETTING_NAME, NM_SETTING_OVS_BRIDGE_SETTING_NAME,
+        NM_SETTING_OVS_PORT_SETTING_NAME, NM_SETTING_VETH_SETTING_NAME,
+        NM_SETTING_VRF_SETTING_NAME, NM_SETTING_WIRED_SETTING_NAME,
     },
 };
 
-use crate::NmstateError;
+use crate::{NmstateError, NmstateStaleProfile};
 
 const ACTIVATION_RETRY_COUNT: usize = 6;
 const ACTIVATION_RETRY_INTERVAL: u64 = 1;
@@ -40,6 +40,11 @@ pub(crate) fn delete_exist_profiles(
         if let Some(uuid) = nm_conn.uuid() {
             excluded_uuids.push(uuid);
         }
+        // Persistent profiles of interface applied in memory only should
+        // be kept.
+        if is_nm_conn_memory_only(nm_conn) {
+            continue;
+        }
         if let Some(name) = nm_conn.iface_name() {
             if let Some(nm_iface_type) = nm_conn.iface_type() {
                 changed_iface_name_types.push((name, nm_iface_type));
@@ -112,6 +117,7 @@ pub(crate) fn save_nm_profiles(
                 nm_conn.iface_name(),
             );
         }
+        let memory_only = memory_only || is_nm_conn_memory_only(nm_conn);
         nm_api
             .connection_add(nm_conn, memory_only)
             .map_err(nm_error_to_nmstate)?;
@@ -119,6 +125,40 @@ pub(crate) fn save_nm_profiles(
     Ok(())
 }
 
+// Volatile profile or profile of interface with `save-to-disk: false`
+// should never touch disk.
+fn is_nm_conn_memory_only(nm_conn: &NmConnection) -> bool {
+    nm_conn.flags.contains(&NmSettingsConnectionFlag::Volatile)
+        || nm_conn.flags.contains(&NmSettingsConnectionFlag::Unsaved)
+}
+
+// Should be invoked after activation as NetworkManager deletes inactive
+// volatile profile immediately.
+pub(crate) fn set_volatile_nm_profiles(
+    nm_api: &mut NmApi,
+    nm_conns: &[NmConnection],
+) -> Result<(), NmstateError> {
+    for nm_conn in nm_conns
+        .iter()
+        .filter(|c| c.flags.contains(&NmSettingsConnectionFlag::Volatile))
+    {
+        if let Some(uuid) = nm_conn.uuid() {
+            log::info!(
+                "Marking connection UUID {}, ID {:?}, type {:?} name {:?} \
+                as volatile",
+                uuid,
+                nm_conn.id(),
+                nm_conn.iface_type(),
+                nm_conn.iface_name(),
+            );
+            nm_api
+                .connection_set_volatile(uuid)
+                .map_err(nm_error_to_nmstate)?;
+        }
+    }
+    Ok(())
+}
+
 pub(crate) fn activate_nm_profiles(
     nm_api: &mut NmApi,
     nm_conns: &[NmConnection],
@@ -191,7 +231,16 @@ fn _activate_nm_profiles(
                     }
                 }
             } else {
-                new_controllers.push(uuid);
+                // Controller with autoconnect-ports disabled will not
+                // activate its ports automatically.
+                if nm_conn
+                    .connection
+                    .as_ref()
+                    .and_then(|c| c.autoconnect_ports)
+                    != Some(false)
+                {
+                    new_controllers.push(uuid);
+                }
                 if let Err(e) = nm_api
                     .connection_activate(uuid)
                     .map_err(nm_error_to_nmstate)
@@ -364,3 +413,56 @@ fn reapply_or_activate(
     }
     Ok(())
 }
+
+// Search profiles created by nmstate whose interface neither exists nor is
+// listed in `desired_iface_names`, delete them if `delete` is true.
+pub(crate) fn nm_stale_profiles_get(
+    nm_dbus_address: Option<&str>,
+    desired_iface_names: &[&str],
+    delete: bool,
+) -> Result<Vec<NmstateStaleProfile>, NmstateError> {
+    let mut nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
+    let nm_devs = nm_api.devices_get().map_err(nm_error_to_nmstate)?;
+    let exist_iface_names: Vec<&str> = nm_devs
+        .iter()
+        .filter(|d| d.real)
+        .map(|d| d.name.as_str())
+        .collect();
+    let nm_conns = nm_api.connections_get().map_err(nm_error_to_nmstate)?;
+
+    let mut ret = Vec::new();
+    for nm_conn in nm_conns.iter().filter(|c| is_nmstate_generated(c)) {
+        // Profile bind to MAC address cannot be matched by interface name
+        let (uuid, iface_name) = if let (Some(u), Some(i)) =
+            (nm_conn.uuid(), nm_conn.iface_name())
+        {
+            (u, i)
+        } else {
+            continue;
+        };
+        if exist_iface_names.contains(&iface_name)
+            || desired_iface_names.contains(&iface_name)
+        {
+            continue;
+        }
+        log::info!(
+            "Found stale connection UUID {}, id {:?} type {:?} name {}",
+            uuid,
+            nm_conn.id(),
+            nm_conn.iface_type(),
+            iface_name,
+        );
+        ret.push(NmstateStaleProfile {
+            uuid: uuid.to_string(),
+            name: nm_conn.id().unwrap_or_default().to_string(),
+            iface_name: iface_name.to_string(),
+            nm_iface_type: nm_conn.iface_type().unwrap_or_default().to_string(),
+        });
+    }
+    if delete {
+        let uuids: Vec<&str> = ret.iter().map(|p| p.uuid.as_str()).collect();
+        delete_profiles(&mut nm_api, uuids.as_slice())?;
+    }
+    Ok(ret)
+}
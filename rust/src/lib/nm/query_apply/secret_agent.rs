@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::super::{
+    error::nm_error_to_nmstate,
+    nm_dbus::{NmSecretAgent, NmSecretCallback},
+};
+use crate::{
+    query_apply::logger::NmstateLogCollector, secret::SecretProviderHolder,
+    NmstateError,
+};
+
+// The secret agent is unregistered when returned object dropped.
+pub(crate) fn nm_secret_agent_start(
+    nm_dbus_address: Option<&str>,
+    provider: &SecretProviderHolder,
+) -> Result<NmSecretAgent, NmstateError> {
+    let provider = provider.0.clone();
+    // The callback is invoked in secret agent thread, capture its logs into
+    // the apply action starting this agent.
+    let collector = NmstateLogCollector::current();
+    let callback: NmSecretCallback =
+        Arc::new(move |iface_name, setting_name, hints| {
+            let get_secrets = || {
+                provider.get_secrets(iface_name, setting_name, hints).map(
+                    |secrets| {
+                        secrets
+                            .into_iter()
+                            .map(|(k, v)| (k, String::from(v)))
+                            .collect::<HashMap<String, String>>()
+                    },
+                )
+            };
+            match collector.as_ref() {
+                Some(collector) => collector.run(get_secrets),
+                None => get_secrets(),
+            }
+        });
+    NmSecretAgent::start(nm_dbus_address, callback).map_err(nm_error_to_nmstate)
+}
@@ -0,0 +1,35 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::NmConnection;
+
+use crate::{EthernetConfig, SrIovConfig, SrIovVfConfig};
+
+// Kernel only reports the first VLAN of VF, hence retrieve VLAN trunk tags
+// from the VLAN list of NM SR-IOV VF setting.
+pub(crate) fn get_sriov_vlan_trunk_conf(
+    nm_conn: &NmConnection,
+) -> Option<EthernetConfig> {
+    let mut vfs = Vec::new();
+    for nm_vf in nm_conn.sriov.as_ref()?.vfs.as_deref()? {
+        if let (Some(id), Some(nm_vlans)) =
+            (nm_vf.index, nm_vf.vlans.as_deref())
+        {
+            // Single VLAN is reported as `vlan-id` by kernel
+            if nm_vlans.len() > 1 {
+                let mut vf = SrIovVfConfig::new();
+                vf.id = id;
+                vf.set_vlan_trunk_ids(nm_vlans.iter().map(|v| v.id).collect());
+                vfs.push(vf);
+            }
+        }
+    }
+    if vfs.is_empty() {
+        None
+    } else {
+        let mut sriov_conf = SrIovConfig::new();
+        sriov_conf.vfs = Some(vfs);
+        let mut eth_conf = EthernetConfig::new();
+        eth_conf.sr_iov = Some(sriov_conf);
+        Some(eth_conf)
+    }
+}
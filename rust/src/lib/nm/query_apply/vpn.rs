@@ -4,6 +4,7 @@ use std::collections::HashMap;
 
 use crate::{
     Interface, InterfaceType, IpsecInterface, LibreswanConfig, NmstateError,
+    Secret,
 };
 
 use super::super::{
@@ -60,8 +61,11 @@ fn get_libreswan_conf(nm_set_vpn: &NmSettingVpn) -> LibreswanConfig {
         ret.ike = data.get("ike").cloned();
         ret.esp = data.get("esp").cloned();
     }
+    if nm_set_vpn.is_psk_agent_owned() {
+        ret.psk_from_secret_agent = Some(true);
+    }
     if let Some(secrets) = nm_set_vpn.secrets.as_ref() {
-        ret.psk = secrets.get("pskvalue").cloned();
+        ret.psk = secrets.get("pskvalue").cloned().map(Secret::from);
     }
     ret
 }
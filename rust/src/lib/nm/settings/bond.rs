@@ -4,10 +4,9 @@ use std::collections::HashMap;
 
 use crate::nm::nm_dbus::{NmConnection, NmSettingBond};
 
+use crate::ifaces::DEFAULT_ARP_MISSED_MAX;
 use crate::{BondConfig, BondInterface, BondOptions};
 
-const DEFAULT_ARP_MISSED_MAX: u8 = 2;
-
 #[cfg(feature = "query_apply")]
 pub(crate) fn get_bond_balance_slb(nm_conn: &NmConnection) -> Option<bool> {
     if let Some(nm_bond_setting) = nm_conn.bond.as_ref() {
@@ -46,10 +45,40 @@ pub(crate) fn gen_nm_bond_setting(
             apply_bond_options(&mut nm_bond_setting, bond_opts);
         }
     }
+    // The `active_slave` is stored in profile, remove it when not desired,
+    // so that it is not re-applied on next activation of this profile.
+    match bond_iface
+        .bond
+        .as_ref()
+        .and_then(|b| b.active_port.as_ref())
+    {
+        Some(active_port) => {
+            nm_bond_setting
+                .options
+                .insert("active_slave".to_string(), active_port.to_string());
+        }
+        None => {
+            nm_bond_setting.options.remove("active_slave");
+        }
+    }
 
     nm_conn.bond = Some(nm_bond_setting);
 }
 
+// Bond options in kernel sysfs `key=value` form, as used by initscripts and
+// dracut.
+#[cfg(feature = "gen_conf")]
+pub(crate) fn gen_nm_bond_options(
+    bond_iface: &BondInterface,
+) -> HashMap<String, String> {
+    let mut nm_conn = NmConnection::default();
+    gen_nm_bond_setting(bond_iface, &mut nm_conn);
+    let mut ret = nm_conn.bond.map(|s| s.options).unwrap_or_default();
+    // The `balance-slb` is OVS only
+    ret.remove("balance-slb");
+    ret
+}
+
 fn apply_bond_mode(nm_bond_set: &mut NmSettingBond, bond_conf: &BondConfig) {
     if let Some(mode) = bond_conf.mode {
         if Some(&mode.to_string()) != nm_bond_set.options.get("mode") {
@@ -101,6 +130,11 @@ fn apply_bond_options(
                 .options
                 .insert("arp_ip_target".to_string(), String::new());
         }
+        if *v == 0 {
+            nm_bond_set
+                .options
+                .insert("ns_ip6_target".to_string(), String::new());
+        }
         nm_bond_set
             .options
             .insert("arp_interval".to_string(), v.to_string());
@@ -110,6 +144,11 @@ fn apply_bond_options(
             .options
             .insert("arp_ip_target".to_string(), v.clone());
     }
+    if let Some(v) = bond_opts.ns_ip6_target.as_ref() {
+        nm_bond_set
+            .options
+            .insert("ns_ip6_target".to_string(), v.clone());
+    }
     if let Some(v) = bond_opts.arp_validate.as_ref() {
         nm_bond_set
             .options
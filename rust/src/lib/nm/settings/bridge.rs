@@ -4,6 +4,8 @@ use crate::nm::nm_dbus::{
     NmConnection, NmSettingBridge, NmSettingBridgeVlanRange, NmVlanProtocol,
 };
 
+#[cfg(feature = "query_apply")]
+use crate::BridgePortVlanRange;
 use crate::{
     BridgePortTrunkTag, BridgePortVlanConfig, BridgePortVlanMode, Interface,
     LinuxBridgeInterface, LinuxBridgeOptions, LinuxBridgeStpOptions,
@@ -27,7 +29,12 @@ pub(crate) fn gen_nm_br_setting(
                 apply_br_options(&mut nm_br_set, br_opts)
             }
 
-            if br_conf.port.is_some() {
+            if let Some(vlan_conf) = br_conf.vlan.as_ref() {
+                nm_br_set.vlans =
+                    Some(nmstate_port_vlans_to_nm_vlan_range(vlan_conf));
+            }
+
+            if br_conf.port.is_some() || br_conf.vlan.is_some() {
                 nm_br_set.vlan_filtering =
                     Some(merged_br_iface.vlan_filtering_is_enabled());
             }
@@ -36,6 +43,47 @@ pub(crate) fn gen_nm_br_setting(
     }
 }
 
+// Convert VLAN filtering configure of bridge itself stored in NetworkManager
+// `bridge.vlans` back to nmstate format.
+#[cfg(feature = "query_apply")]
+pub(crate) fn get_bridge_vlan_conf(
+    nm_conn: &NmConnection,
+) -> Option<BridgePortVlanConfig> {
+    let nm_vlans = nm_conn.bridge.as_ref().and_then(|b| b.vlans.as_ref())?;
+    if nm_vlans.is_empty() {
+        return None;
+    }
+    let mut ret = BridgePortVlanConfig::new();
+    if nm_vlans.len() == 1
+        && nm_vlans[0].pvid
+        && nm_vlans[0].untagged
+        && nm_vlans[0].vid_start == nm_vlans[0].vid_end
+    {
+        ret.mode = Some(BridgePortVlanMode::Access);
+        ret.tag = Some(nm_vlans[0].vid_start);
+        return Some(ret);
+    }
+    let mut trunk_tags = Vec::new();
+    let mut is_native = false;
+    for nm_vlan in nm_vlans {
+        if nm_vlan.pvid && nm_vlan.untagged {
+            ret.tag = Some(nm_vlan.vid_end);
+            is_native = true;
+        } else if nm_vlan.vid_start == nm_vlan.vid_end {
+            trunk_tags.push(BridgePortTrunkTag::Id(nm_vlan.vid_start));
+        } else {
+            trunk_tags.push(BridgePortTrunkTag::IdRange(BridgePortVlanRange {
+                min: nm_vlan.vid_start,
+                max: nm_vlan.vid_end,
+            }));
+        }
+    }
+    ret.mode = Some(BridgePortVlanMode::Trunk);
+    ret.enable_native = Some(is_native);
+    ret.trunk_tags = Some(trunk_tags);
+    Some(ret)
+}
+
 fn apply_br_options(
     nm_br_set: &mut NmSettingBridge,
     br_opts: &LinuxBridgeOptions,
@@ -100,6 +148,9 @@ fn apply_br_options(
     if let Some(v) = br_opts.vlan_default_pvid.as_ref() {
         nm_br_set.vlan_default_pvid = Some((*v).into());
     }
+    if let Some(v) = br_opts.vlan_stats_enabled.as_ref() {
+        nm_br_set.vlan_stats_enabled = Some(*v);
+    }
 
     if let Some(stp_opts) = br_opts.stp.as_ref() {
         apply_stp_setting(nm_br_set, stp_opts);
@@ -1,16 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::super::nm_dbus::{
-    NmConnection, NmSettingConnection, NmSettingMacVlan, NmSettingVeth,
-    NmSettingVrf, NmSettingVxlan, NmSettingsConnectionFlag,
+    NmConnection, NmSettingConnection, NmSettingIpVlan, NmSettingMacVlan,
+    NmSettingVeth, NmSettingVrf, NmSettingVxlan, NmSettingsConnectionFlag,
 };
 use super::{
     bond::{gen_nm_bond_port_setting, gen_nm_bond_setting},
     bridge::{gen_nm_br_port_setting, gen_nm_br_setting},
     ethtool::gen_ethtool_setting,
+    gsm::gen_nm_gsm_setting,
     ieee8021x::gen_nm_802_1x_setting,
     infiniband::gen_nm_ib_setting,
     ip::gen_nm_ip_setting,
+    ip_tunnel::gen_nm_ip_tunnel_setting,
     loopback::gen_nm_loopback_setting,
     macsec::gen_nm_macsec_setting,
     mptcp::apply_mptcp_conf,
@@ -18,6 +20,7 @@ use super::{
         create_ovs_port_nm_conn, gen_nm_iface_ovs_db_setting,
         gen_nm_ovs_br_setting, gen_nm_ovs_iface_setting, get_ovs_port_name,
     },
+    passthrough::apply_nm_passthrough_conf,
     sriov::gen_nm_sriov_setting,
     user::gen_nm_user_setting,
     veth::create_veth_peer_profile_if_not_found,
@@ -41,19 +44,25 @@ pub(crate) const NM_SETTING_BOND_SETTING_NAME: &str = "bond";
 pub(crate) const NM_SETTING_DUMMY_SETTING_NAME: &str = "dummy";
 pub(crate) const NM_SETTING_MACSEC_SETTING_NAME: &str = "macsec";
 pub(crate) const NM_SETTING_MACVLAN_SETTING_NAME: &str = "macvlan";
+pub(crate) const NM_SETTING_IPVLAN_SETTING_NAME: &str = "ipvlan";
 pub(crate) const NM_SETTING_VRF_SETTING_NAME: &str = "vrf";
 pub(crate) const NM_SETTING_VLAN_SETTING_NAME: &str = "vlan";
 pub(crate) const NM_SETTING_VXLAN_SETTING_NAME: &str = "vxlan";
 pub(crate) const NM_SETTING_INFINIBAND_SETTING_NAME: &str = "infiniband";
 pub(crate) const NM_SETTING_LOOPBACK_SETTING_NAME: &str = "loopback";
 pub(crate) const NM_SETTING_VPN_SETTING_NAME: &str = "vpn";
+pub(crate) const NM_SETTING_IP_TUNNEL_SETTING_NAME: &str = "ip-tunnel";
+pub(crate) const NM_SETTING_TEAM_SETTING_NAME: &str = "team";
+pub(crate) const NM_SETTING_GSM_SETTING_NAME: &str = "gsm";
+// The NM device type of modem is not named after its setting name
+pub(crate) const NM_DEVICE_TYPE_MODEM_NAME: &str = "modem";
 
 pub(crate) const NM_SETTING_USER_SPACES: [&str; 2] = [
     NM_SETTING_OVS_BRIDGE_SETTING_NAME,
     NM_SETTING_OVS_PORT_SETTING_NAME,
 ];
 
-pub(crate) const SUPPORTED_NM_KERNEL_IFACE_TYPES: [&str; 13] = [
+pub(crate) const SUPPORTED_NM_KERNEL_IFACE_TYPES: [&str; 15] = [
     NM_SETTING_WIRED_SETTING_NAME,
     NM_SETTING_VETH_SETTING_NAME,
     NM_SETTING_BOND_SETTING_NAME,
@@ -67,6 +76,8 @@ pub(crate) const SUPPORTED_NM_KERNEL_IFACE_TYPES: [&str; 13] = [
     NM_SETTING_LOOPBACK_SETTING_NAME,
     NM_SETTING_INFINIBAND_SETTING_NAME,
     NM_SETTING_MACSEC_SETTING_NAME,
+    NM_SETTING_IP_TUNNEL_SETTING_NAME,
+    NM_SETTING_IPVLAN_SETTING_NAME,
 ];
 
 pub(crate) fn iface_to_nm_connections(
@@ -139,6 +150,12 @@ pub(crate) fn iface_to_nm_connections(
     }
     let mut nm_conn = exist_nm_conn.cloned().unwrap_or_default();
     nm_conn.flags = Vec::new();
+    if base_iface.volatile == Some(true) {
+        nm_conn.flags.push(NmSettingsConnectionFlag::Volatile);
+    }
+    if base_iface.save_to_disk == Some(false) {
+        nm_conn.flags.push(NmSettingsConnectionFlag::Unsaved);
+    }
 
     // Use stable UUID if in gen_conf mode.
     // This enable us to generate the same output for `nm_gen_conf()`
@@ -151,15 +168,19 @@ pub(crate) fn iface_to_nm_connections(
         iface.base_iface().routes.as_deref(),
         &mut nm_conn,
     )?;
-    // InfiniBand over IP and loopback can not have layer 2 configuration.
+    // InfiniBand over IP, loopback and layer 3 tunnels can not have layer 2
+    // configuration.
     if iface.iface_type() != InterfaceType::InfiniBand
         && iface.iface_type() != InterfaceType::Loopback
+        && iface.iface_type() != InterfaceType::Gre
+        && iface.iface_type() != InterfaceType::Ip6Gre
+        && iface.iface_type() != InterfaceType::Ip6Tnl
     {
         gen_nm_wired_setting(iface, &mut nm_conn);
     }
     gen_nm_iface_ovs_db_setting(iface, &mut nm_conn);
     gen_nm_802_1x_setting(iface, &mut nm_conn);
-    gen_nm_user_setting(iface, &mut nm_conn);
+    gen_nm_user_setting(iface, &mut nm_conn, exist_nm_conn.is_none());
     gen_ethtool_setting(iface, &mut nm_conn)?;
 
     match iface {
@@ -239,6 +260,11 @@ pub(crate) fn iface_to_nm_connections(
                 nm_conn.mac_vlan = Some(NmSettingMacVlan::from(conf));
             }
         }
+        Interface::IpVlan(iface) => {
+            if let Some(conf) = iface.ipvlan.as_ref() {
+                nm_conn.ip_vlan = Some(NmSettingIpVlan::from(conf));
+            }
+        }
         Interface::Vrf(iface) => {
             if let Some(vrf_conf) = iface.vrf.as_ref() {
                 nm_conn.vrf = Some(NmSettingVrf::from(vrf_conf));
@@ -256,6 +282,16 @@ pub(crate) fn iface_to_nm_connections(
         Interface::Ipsec(iface) => {
             gen_nm_ipsec_vpn_setting(iface, &mut nm_conn);
         }
+        Interface::Modem(iface) => {
+            gen_nm_gsm_setting(iface, &mut nm_conn);
+        }
+        Interface::Gre(_)
+        | Interface::Gretap(_)
+        | Interface::Ip6Gre(_)
+        | Interface::Ip6Gretap(_)
+        | Interface::Ip6Tnl(_) => {
+            gen_nm_ip_tunnel_setting(iface, &mut nm_conn);
+        }
         _ => (),
     };
 
@@ -335,6 +371,14 @@ pub(crate) fn iface_to_nm_connections(
         nm_conn.ovs_iface = None;
     }
 
+    if let Some(exist_nm_conn) = exist_nm_conn {
+        nm_conn.preserve_unknown_settings(exist_nm_conn);
+    }
+
+    if let Some(nm_passthrough_conf) = base_iface.nm.as_ref() {
+        apply_nm_passthrough_conf(nm_passthrough_conf, &mut nm_conn)?;
+    }
+
     ret.insert(0, nm_conn);
 
     Ok(ret)
@@ -362,6 +406,7 @@ pub(crate) fn iface_type_to_nm(
         InterfaceType::MacVtap => {
             Ok(NM_SETTING_MACVLAN_SETTING_NAME.to_string())
         }
+        InterfaceType::IpVlan => Ok(NM_SETTING_IPVLAN_SETTING_NAME.to_string()),
         InterfaceType::Vrf => Ok(NM_SETTING_VRF_SETTING_NAME.to_string()),
         InterfaceType::Veth => Ok(NM_SETTING_VETH_SETTING_NAME.to_string()),
         InterfaceType::InfiniBand => {
@@ -372,6 +417,14 @@ pub(crate) fn iface_type_to_nm(
         }
         InterfaceType::MacSec => Ok(NM_SETTING_MACSEC_SETTING_NAME.to_string()),
         InterfaceType::Ipsec => Ok(NM_SETTING_VPN_SETTING_NAME.to_string()),
+        InterfaceType::Modem => Ok(NM_SETTING_GSM_SETTING_NAME.to_string()),
+        InterfaceType::Gre
+        | InterfaceType::Gretap
+        | InterfaceType::Ip6Gre
+        | InterfaceType::Ip6Gretap
+        | InterfaceType::Ip6Tnl => {
+            Ok(NM_SETTING_IP_TUNNEL_SETTING_NAME.to_string())
+        }
         InterfaceType::Other(s) => Ok(s.to_string()),
         _ => Err(NmstateError::new(
             ErrorKind::NotImplementedError,
@@ -435,16 +488,21 @@ pub(crate) fn gen_nm_conn_setting(
         nm_conn_set.iface_name = None;
     }
     nm_conn_set.autoconnect = Some(true);
+    // When ports are ordered, nmstate activates the ports one by one instead
+    // of letting NetworkManager activating them in its own order.
     nm_conn_set.autoconnect_ports = if iface.is_controller() {
-        Some(true)
+        Some(iface.base_iface().ports_ordered != Some(true))
     } else {
         None
     };
 
+    // NetworkManager cannot manage batman-adv interface, its ports are
+    // attached via kernel after activation.
     let nm_ctrl_type = iface
         .base_iface()
         .controller_type
         .as_ref()
+        .filter(|t| *t != &InterfaceType::Batman)
         .map(iface_type_to_nm)
         .transpose()?;
     let nm_ctrl_type = nm_ctrl_type.as_deref();
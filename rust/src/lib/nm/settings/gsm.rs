@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::NmConnection;
+
+use crate::ModemInterface;
+
+pub(crate) fn gen_nm_gsm_setting(
+    iface: &ModemInterface,
+    nm_conn: &mut NmConnection,
+) {
+    let mut nm_gsm_set = nm_conn.gsm.as_ref().cloned().unwrap_or_default();
+    if let Some(modem_conf) = iface.modem.as_ref() {
+        if let Some(v) = modem_conf.apn.as_ref() {
+            nm_gsm_set.apn = Some(v.clone());
+        }
+        if let Some(v) = modem_conf.pin.as_ref() {
+            nm_gsm_set.pin = Some(v.as_str().to_string());
+        }
+        if let Some(v) = modem_conf.roaming {
+            nm_gsm_set.home_only = Some(!v);
+        }
+    }
+    nm_conn.gsm = Some(nm_gsm_set)
+}
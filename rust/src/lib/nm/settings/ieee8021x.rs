@@ -2,7 +2,7 @@
 
 use super::super::nm_dbus::{NmConnection, NmSetting8021X};
 
-use crate::{Interface, NetworkState};
+use crate::{Interface, Secret};
 
 pub(crate) fn gen_nm_802_1x_setting(
     iface: &Interface,
@@ -24,8 +24,11 @@ pub(crate) fn gen_nm_802_1x_setting(
             .ca_cert
             .as_deref()
             .map(NmSetting8021X::file_path_to_glib_bytes);
-        if conf.private_key_password.as_deref()
-            == Some(NetworkState::PASSWORD_HID_BY_NMSTATE)
+        if conf
+            .private_key_password
+            .as_ref()
+            .map(Secret::is_hidden)
+            .unwrap_or_default()
         {
             if let Some(cur_pass) = nm_conn
                 .ieee8021x
@@ -35,7 +38,8 @@ pub(crate) fn gen_nm_802_1x_setting(
                 nm_setting.private_key_password = Some(cur_pass.to_string());
             }
         } else {
-            nm_setting.private_key_password = conf.private_key_password.clone();
+            nm_setting.private_key_password =
+                conf.private_key_password.clone().map(String::from);
         }
         nm_conn.ieee8021x = Some(nm_setting);
     }
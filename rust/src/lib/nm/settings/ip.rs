@@ -8,8 +8,8 @@ use super::{
 };
 use crate::nm::nm_dbus::{NmConnection, NmSettingIp, NmSettingIpMethod};
 use crate::{
-    BaseInterface, Dhcpv4ClientId, Dhcpv6Duid, ErrorKind, Interface,
-    InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6, Ipv6AddrGenMode,
+    BaseInterface, Dhcpv4ClientId, Dhcpv6Duid, Dhcpv6Mode, ErrorKind,
+    Interface, InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6, Ipv6AddrGenMode,
     NmstateError, RouteEntry, WaitIp,
 };
 
@@ -60,6 +60,9 @@ fn gen_nm_ipv4_setting(
     let mut nm_setting = nm_conn.ipv4.as_ref().cloned().unwrap_or_default();
     nm_setting.method = Some(method);
     nm_setting.addresses = addresses;
+    if let Some(dad_timeout) = iface_ip.dad_timeout {
+        nm_setting.dad_timeout = i32::try_from(dad_timeout).ok();
+    }
     if iface_ip.is_auto() {
         nm_setting.dhcp_timeout = Some(i32::MAX);
         nm_setting.route_metric = iface_ip.auto_route_metric.map(|i| i.into());
@@ -80,6 +83,9 @@ fn gen_nm_ipv4_setting(
         // Clean old routes
         nm_setting.gateway = None;
         nm_setting.routes = Vec::new();
+        if let Some(v) = iface_ip.dhcp_send_release {
+            nm_setting.dhcp_send_release = Some(v);
+        }
         if Some(false) == iface_ip.dhcp_send_hostname {
             nm_setting.dhcp_send_hostname = Some(false);
         } else {
@@ -147,6 +153,15 @@ fn gen_nm_ipv6_setting(
         .cloned()
         .collect();
     let mut addresses: Vec<String> = Vec::new();
+    if iface_ip.enabled && iface_ip.dhcp_mode == Some(Dhcpv6Mode::Stateless) {
+        return Err(NmstateError::new(
+            ErrorKind::NotImplementedError,
+            "IPv6 `dhcp-mode: stateless` is not supported by NetworkManager \
+            backend yet, please use `ra-triggered` with router advertising \
+            other-config flag only"
+                .to_string(),
+        ));
+    }
     let method = if iface_ip.enabled {
         match (
             iface_ip.dhcp.unwrap_or_default(),
@@ -208,6 +223,9 @@ fn gen_nm_ipv6_setting(
         // Clean old routes
         nm_setting.gateway = None;
         nm_setting.routes = Vec::new();
+        if let Some(v) = iface_ip.dhcp_send_release {
+            nm_setting.dhcp_send_release = Some(v);
+        }
         if Some(false) == iface_ip.dhcp_send_hostname {
             nm_setting.dhcp_send_hostname = Some(false);
         } else {
@@ -289,7 +307,7 @@ fn nmstate_dhcp_client_id_to_nm(client_id: &Dhcpv4ClientId) -> String {
     match client_id {
         Dhcpv4ClientId::LinkLayerAddress => "mac".into(),
         Dhcpv4ClientId::IaidPlusDuid => "duid".into(),
-        Dhcpv4ClientId::Other(s) => s.into(),
+        _ => client_id.to_string(),
     }
 }
 
@@ -340,8 +358,52 @@ fn apply_nmstate_wait_ip(
                 nm_ip_set.may_fail = Some(false);
             }
         }
+        Some(WaitIp::AnyWithGrace) => {
+            if let Some(nm_ip_set) = nm_conn.ipv4.as_mut() {
+                nm_ip_set.may_fail = Some(true);
+            }
+            if let Some(nm_ip_set) = nm_conn.ipv6.as_mut() {
+                nm_ip_set.may_fail = Some(true);
+            }
+        }
         None => (),
     }
+    apply_nmstate_wait_ip_timeout(base_iface, nm_conn);
+}
+
+// NetworkManager `required-timeout` is the minimum time to wait for the
+// IP stack with `may-fail: true` before considering activation done.
+fn apply_nmstate_wait_ip_timeout(
+    base_iface: &BaseInterface,
+    nm_conn: &mut NmConnection,
+) {
+    let timeout_ms = |t: u32| -> i32 {
+        i32::try_from(u64::from(t) * 1000).unwrap_or(i32::MAX)
+    };
+    let (ipv4_timeout, ipv6_timeout) = match base_iface.wait_ip {
+        Some(WaitIp::AnyWithGrace) => {
+            let timeout = timeout_ms(
+                base_iface
+                    .wait_ip_timeout
+                    .unwrap_or(WaitIp::DEFAULT_GRACE_TIMEOUT),
+            );
+            (Some(timeout), Some(timeout))
+        }
+        Some(WaitIp::Ipv4) => {
+            (None, base_iface.wait_ip_timeout.map(timeout_ms))
+        }
+        Some(WaitIp::Ipv6) => {
+            (base_iface.wait_ip_timeout.map(timeout_ms), None)
+        }
+        Some(WaitIp::Any) | Some(WaitIp::Ipv4AndIpv6) => (None, None),
+        None => return,
+    };
+    if let Some(nm_ip_set) = nm_conn.ipv4.as_mut() {
+        nm_ip_set.required_timeout = ipv4_timeout;
+    }
+    if let Some(nm_ip_set) = nm_conn.ipv6.as_mut() {
+        nm_ip_set.required_timeout = ipv6_timeout;
+    }
 }
 
 // Even user not desired IP section changes, we should set ipv4.dhcp_timeout
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::super::nm_dbus::{NmConnection, NmSettingIpTunnel};
+
+use crate::{GreConfig, Interface, Ip6TnlMode, Ip6TunnelConfig};
+
+pub(crate) fn gen_nm_ip_tunnel_setting(
+    iface: &Interface,
+    nm_conn: &mut NmConnection,
+) {
+    let mut setting = nm_conn.ip_tunnel.as_ref().cloned().unwrap_or_default();
+    let mode = match iface {
+        Interface::Gre(iface) => {
+            if let Some(config) = iface.gre.as_ref() {
+                apply_gre_conf(config, &mut setting);
+            }
+            NmSettingIpTunnel::MODE_GRE
+        }
+        Interface::Gretap(iface) => {
+            if let Some(config) = iface.gretap.as_ref() {
+                apply_gre_conf(config, &mut setting);
+            }
+            NmSettingIpTunnel::MODE_GRETAP
+        }
+        Interface::Ip6Gre(iface) => {
+            if let Some(config) = iface.ip6gre.as_ref() {
+                apply_ip6_tunnel_conf(config, &mut setting);
+            }
+            NmSettingIpTunnel::MODE_IP6GRE
+        }
+        Interface::Ip6Gretap(iface) => {
+            if let Some(config) = iface.ip6gretap.as_ref() {
+                apply_ip6_tunnel_conf(config, &mut setting);
+            }
+            NmSettingIpTunnel::MODE_IP6GRETAP
+        }
+        Interface::Ip6Tnl(iface) => {
+            let config = iface.ip6tnl.as_ref();
+            if let Some(config) = config {
+                apply_ip6_tunnel_conf(config, &mut setting);
+            }
+            match config.and_then(|c| c.mode) {
+                Some(Ip6TnlMode::Ipip6) => NmSettingIpTunnel::MODE_IPIP6,
+                Some(Ip6TnlMode::Ip6ip6) => NmSettingIpTunnel::MODE_IP6IP6,
+                // Preserve the inner protocol of existing profile
+                None => match setting.mode {
+                    Some(NmSettingIpTunnel::MODE_IPIP6) => {
+                        NmSettingIpTunnel::MODE_IPIP6
+                    }
+                    _ => NmSettingIpTunnel::MODE_IP6IP6,
+                },
+            }
+        }
+        _ => return,
+    };
+    setting.mode = Some(mode);
+    // Layer 3 tunnel cannot hold wired setting, MTU is stored in ip-tunnel
+    // setting instead.
+    if mode != NmSettingIpTunnel::MODE_GRETAP
+        && mode != NmSettingIpTunnel::MODE_IP6GRETAP
+    {
+        if let Some(mtu) = iface.base_iface().mtu {
+            setting.mtu = Some(mtu as u32);
+        }
+    }
+    nm_conn.ip_tunnel = Some(setting);
+}
+
+fn apply_gre_conf(config: &GreConfig, setting: &mut NmSettingIpTunnel) {
+    if let Some(v) = config.base_iface.as_deref() {
+        setting.parent = Some(v.to_string());
+    }
+    if let Some(v) = config.local.as_ref() {
+        setting.local = Some(v.to_string());
+    }
+    if let Some(v) = config.remote.as_ref() {
+        setting.remote = Some(v.to_string());
+    }
+    if let Some(v) = config.ikey {
+        setting.input_key = Some(v.to_string());
+    }
+    if let Some(v) = config.okey {
+        setting.output_key = Some(v.to_string());
+    }
+    if let Some(v) = config.ttl {
+        setting.ttl = Some(v.into());
+    }
+    if let Some(v) = config.tos {
+        setting.tos = Some(v.into());
+    }
+}
+
+fn apply_ip6_tunnel_conf(
+    config: &Ip6TunnelConfig,
+    setting: &mut NmSettingIpTunnel,
+) {
+    if let Some(v) = config.base_iface.as_deref() {
+        setting.parent = Some(v.to_string());
+    }
+    if let Some(v) = config.local.as_ref() {
+        setting.local = Some(v.to_string());
+    }
+    if let Some(v) = config.remote.as_ref() {
+        setting.remote = Some(v.to_string());
+    }
+    if let Some(v) = config.ikey {
+        setting.input_key = Some(v.to_string());
+    }
+    if let Some(v) = config.okey {
+        setting.output_key = Some(v.to_string());
+    }
+    if let Some(v) = config.encap_limit {
+        setting.encapsulation_limit = Some(v.into());
+    }
+    if let Some(v) = config.flow_label {
+        setting.flow_label = Some(v);
+    }
+    // NetworkManager is using TTL for hop limit of IPv6 tunnel
+    if let Some(v) = config.hop_limit {
+        setting.ttl = Some(v.into());
+    }
+}
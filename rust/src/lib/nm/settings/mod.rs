@@ -5,15 +5,19 @@ mod bridge;
 mod connection;
 mod dns;
 mod ethtool;
+mod gsm;
 mod ieee8021x;
 mod infiniband;
 mod inter_connections;
 mod ip;
+mod ip_tunnel;
+mod ip_vlan;
 mod loopback;
 mod mac_vlan;
 mod macsec;
 mod mptcp;
 mod ovs;
+mod passthrough;
 mod route;
 mod route_rule;
 mod sriov;
@@ -25,28 +29,43 @@ mod vrf;
 mod vxlan;
 mod wired;
 
+#[cfg(feature = "gen_conf")]
+pub(crate) use self::bond::gen_nm_bond_options;
+#[cfg(test)]
+pub(crate) use self::bond::gen_nm_bond_setting;
+#[cfg(any(feature = "query_apply", feature = "gen_conf"))]
+pub(crate) use self::connection::iface_type_to_nm;
 pub(crate) use self::connection::{
     get_exist_profile, iface_to_nm_connections, SUPPORTED_NM_KERNEL_IFACE_TYPES,
 };
 #[cfg(feature = "query_apply")]
 pub(crate) use self::connection::{
-    iface_type_to_nm, NM_SETTING_BOND_SETTING_NAME,
+    NM_DEVICE_TYPE_MODEM_NAME, NM_SETTING_BOND_SETTING_NAME,
     NM_SETTING_BRIDGE_SETTING_NAME, NM_SETTING_DUMMY_SETTING_NAME,
-    NM_SETTING_INFINIBAND_SETTING_NAME, NM_SETTING_LOOPBACK_SETTING_NAME,
-    NM_SETTING_MACSEC_SETTING_NAME, NM_SETTING_MACVLAN_SETTING_NAME,
-    NM_SETTING_OVS_BRIDGE_SETTING_NAME, NM_SETTING_OVS_IFACE_SETTING_NAME,
-    NM_SETTING_OVS_PORT_SETTING_NAME, NM_SETTING_VETH_SETTING_NAME,
+    NM_SETTING_GSM_SETTING_NAME, NM_SETTING_INFINIBAND_SETTING_NAME,
+    NM_SETTING_IPVLAN_SETTING_NAME, NM_SETTING_IP_TUNNEL_SETTING_NAME,
+    NM_SETTING_LOOPBACK_SETTING_NAME, NM_SETTING_MACSEC_SETTING_NAME,
+    NM_SETTING_MACVLAN_SETTING_NAME, NM_SETTING_OVS_BRIDGE_SETTING_NAME,
+    NM_SETTING_OVS_IFACE_SETTING_NAME, NM_SETTING_OVS_PORT_SETTING_NAME,
+    NM_SETTING_TEAM_SETTING_NAME, NM_SETTING_VETH_SETTING_NAME,
     NM_SETTING_VLAN_SETTING_NAME, NM_SETTING_VRF_SETTING_NAME,
     NM_SETTING_VXLAN_SETTING_NAME, NM_SETTING_WIRED_SETTING_NAME,
 };
+#[cfg(test)]
+pub(crate) use self::gsm::gen_nm_gsm_setting;
 pub(crate) use self::inter_connections::{
     use_uuid_for_controller_reference, use_uuid_for_parent_reference,
 };
 pub(crate) use self::ip::fix_ip_dhcp_timeout;
+#[cfg(test)]
+pub(crate) use self::vpn::gen_nm_ipsec_vpn_setting;
 
 #[cfg(feature = "query_apply")]
 pub(crate) use self::bond::get_bond_balance_slb;
 #[cfg(feature = "query_apply")]
-pub(crate) use self::user::NMSTATE_DESCRIPTION;
+pub(crate) use self::bridge::get_bridge_vlan_conf;
+#[cfg(feature = "query_apply")]
+pub(crate) use self::user::{is_nmstate_generated, NMSTATE_DESCRIPTION};
 
 pub(crate) use self::mptcp::remove_nm_mptcp_set;
+pub(crate) use self::wired::remove_nm_accept_all_mac_addresses;
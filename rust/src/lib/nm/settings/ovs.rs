@@ -80,6 +80,9 @@ pub(crate) fn create_ovs_port_nm_conn(
     }
 
     nm_conn.ovs_port = Some(nm_ovs_port_set);
+    if let Some(exist_nm_conn) = exist_nm_conn {
+        nm_conn.preserve_unknown_settings(exist_nm_conn);
+    }
     Ok(nm_conn)
 }
 
@@ -0,0 +1,119 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::nm::nm_dbus::NmConnection;
+use crate::{ErrorKind, NmPassthroughConfig, NmstateError};
+
+// NetworkManager is strict on D-Bus value type. Without knowing the type of
+// each NetworkManager property, we follow the type of existing property if
+// found, otherwise integer is treated as signed 32 bits integer.
+// Properties modeled by nmstate are not allowed here, and `null` value
+// removes the property from NetworkManager profile.
+pub(crate) fn apply_nm_passthrough_conf(
+    conf: &NmPassthroughConfig,
+    nm_conn: &mut NmConnection,
+) -> Result<(), NmstateError> {
+    let settings = match conf.settings.as_ref() {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+    for (setting_name, props) in settings {
+        for (prop_name, value) in props {
+            if NmConnection::is_setting_prop_supported(setting_name, prop_name)
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "NetworkManager setting {setting_name}.{prop_name} \
+                        is managed by nmstate, please use nmstate schema \
+                        instead of `nm.settings`"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if value.is_null() {
+                nm_conn.remove_other_setting_prop(setting_name, prop_name);
+                continue;
+            }
+            let cur_sig = nm_conn
+                .get_other_setting_prop(setting_name, prop_name)
+                .map(|v| v.value_signature().as_str().to_string());
+            let new_value = json_to_zvariant(value, cur_sig.as_deref())
+                .ok_or_else(|| {
+                    let e = NmstateError::new(
+                        ErrorKind::InvalidArgument,
+                        format!(
+                            "Unsupported value {value} for NetworkManager \
+                            setting {setting_name}.{prop_name}"
+                        ),
+                    );
+                    log::error!("{}", e);
+                    e
+                })?;
+            nm_conn.set_other_setting_prop(
+                setting_name,
+                prop_name,
+                zvariant::OwnedValue::from(new_value),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn json_to_zvariant(
+    value: &serde_json::Value,
+    signature: Option<&str>,
+) -> Option<zvariant::Value<'static>> {
+    match value {
+        serde_json::Value::Bool(b) => Some(zvariant::Value::new(*b)),
+        serde_json::Value::String(s) => Some(zvariant::Value::new(s.clone())),
+        serde_json::Value::Number(n) => json_number_to_zvariant(n, signature),
+        serde_json::Value::Array(items) => {
+            if items.iter().all(|i| i.is_string()) {
+                Some(zvariant::Value::new(
+                    items
+                        .iter()
+                        .filter_map(|i| i.as_str().map(|s| s.to_string()))
+                        .collect::<Vec<String>>(),
+                ))
+            } else if signature == Some("ai") {
+                let mut ret: Vec<i32> = Vec::new();
+                for item in items {
+                    ret.push(i32::try_from(item.as_i64()?).ok()?);
+                }
+                Some(zvariant::Value::new(ret))
+            } else {
+                let mut ret: Vec<u32> = Vec::new();
+                for item in items {
+                    ret.push(u32::try_from(item.as_u64()?).ok()?);
+                }
+                Some(zvariant::Value::new(ret))
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            let mut ret: HashMap<String, String> = HashMap::new();
+            for (k, v) in obj {
+                ret.insert(k.to_string(), v.as_str()?.to_string());
+            }
+            Some(zvariant::Value::new(ret))
+        }
+        serde_json::Value::Null => None,
+    }
+}
+
+fn json_number_to_zvariant(
+    n: &serde_json::Number,
+    signature: Option<&str>,
+) -> Option<zvariant::Value<'static>> {
+    Some(match signature {
+        Some("y") => zvariant::Value::new(u8::try_from(n.as_u64()?).ok()?),
+        Some("q") => zvariant::Value::new(u16::try_from(n.as_u64()?).ok()?),
+        Some("n") => zvariant::Value::new(i16::try_from(n.as_i64()?).ok()?),
+        Some("u") => zvariant::Value::new(u32::try_from(n.as_u64()?).ok()?),
+        Some("x") => zvariant::Value::new(n.as_i64()?),
+        Some("t") => zvariant::Value::new(n.as_u64()?),
+        _ => zvariant::Value::new(i32::try_from(n.as_i64()?).ok()?),
+    })
+}
@@ -15,7 +15,7 @@ pub(crate) fn gen_nm_ip_routes(
     let mut ret = Vec::new();
     for route in routes {
         let mut nm_route = NmIpRoute::default();
-        if let Some(v) = route.destination.as_deref() {
+        if let Some(v) = route.destination.as_ref().map(|d| d.as_str()) {
             if (is_ipv6 && !is_ipv6_addr(v)) || (!is_ipv6 && is_ipv6_addr(v)) {
                 continue;
             }
@@ -4,10 +4,7 @@ use std::convert::TryFrom;
 
 use super::super::nm_dbus::NmIpRouteRule;
 
-use crate::{
-    ip::is_ipv6_addr, ip::AddressFamily, ErrorKind, InterfaceIpAddr,
-    NmstateError, RouteRuleEntry,
-};
+use crate::{ip::AddressFamily, ErrorKind, NmstateError, RouteRuleEntry};
 
 const AF_INET6: i32 = 10;
 const AF_INET: i32 = 2;
@@ -25,25 +22,19 @@ pub(crate) fn gen_nm_ip_rules(
                 continue;
             }
         }
-        if let Some(addr) = rule.ip_from.as_deref() {
-            match (is_ipv6, is_ipv6_addr(addr)) {
-                (true, true) | (false, false) => {
-                    let ip_addr = InterfaceIpAddr::try_from(addr)?;
-                    nm_rule.from_len = Some(ip_addr.prefix_length);
-                    nm_rule.from = Some(ip_addr.ip.to_string());
-                }
-                _ => continue,
+        if let Some(ip_net) = rule.ip_from.as_ref() {
+            if is_ipv6 != ip_net.is_ipv6() {
+                continue;
             }
+            nm_rule.from_len = Some(ip_net.prefix_length());
+            nm_rule.from = ip_net.ip().map(|i| i.to_string());
         }
-        if let Some(addr) = rule.ip_to.as_deref() {
-            match (is_ipv6, is_ipv6_addr(addr)) {
-                (true, true) | (false, false) => {
-                    let ip_addr = InterfaceIpAddr::try_from(addr)?;
-                    nm_rule.to_len = Some(ip_addr.prefix_length);
-                    nm_rule.to = Some(ip_addr.ip.to_string());
-                }
-                _ => continue,
+        if let Some(ip_net) = rule.ip_to.as_ref() {
+            if is_ipv6 != ip_net.is_ipv6() {
+                continue;
             }
+            nm_rule.to_len = Some(ip_net.prefix_length());
+            nm_rule.to = ip_net.ip().map(|i| i.to_string());
         }
         nm_rule.priority = match rule.priority {
             Some(RouteRuleEntry::USE_DEFAULT_PRIORITY) | None => {
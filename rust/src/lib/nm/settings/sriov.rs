@@ -65,7 +65,19 @@ fn gen_nm_vfs(
         if let Some(v) = vf.max_tx_rate {
             nm_vf.max_tx_rate = Some(v);
         }
-        if let Some(v) = vf.vlan_id {
+        if let Some(vlan_ids) = vf.get_vlan_trunk_ids() {
+            nm_vf.vlans = Some(
+                vlan_ids
+                    .into_iter()
+                    .map(|id| {
+                        let mut nm_vf_vlan = NmSettingSriovVfVlan::default();
+                        nm_vf_vlan.id = id;
+                        nm_vf_vlan.qos = vf.qos.unwrap_or_default();
+                        nm_vf_vlan
+                    })
+                    .collect(),
+            );
+        } else if let Some(v) = vf.vlan_id {
             let mut nm_vf_vlan = NmSettingSriovVfVlan::default();
             nm_vf_vlan.id = v;
             nm_vf_vlan.qos = vf.qos.unwrap_or_default();
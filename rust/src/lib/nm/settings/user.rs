@@ -5,11 +5,17 @@ use crate::nm::nm_dbus::{NmConnection, NmSettingUser};
 use crate::Interface;
 
 pub(crate) const NMSTATE_DESCRIPTION: &str = "nmstate.interface.description";
+// Marker of profiles created by nmstate
+pub(crate) const NMSTATE_GENERATED: &str = "nmstate.generated";
 
+// The `is_new_profile` indicates the profile is created by nmstate instead
+// of modifying existing one.
 pub(crate) fn gen_nm_user_setting(
     iface: &Interface,
     nm_conn: &mut NmConnection,
+    is_new_profile: bool,
 ) {
+    let is_generated = is_new_profile || is_nmstate_generated(nm_conn);
     if let Some(description) = iface.base_iface().description.as_ref() {
         let mut data: HashMap<String, String> = HashMap::new();
         if !description.is_empty() {
@@ -22,4 +28,21 @@ pub(crate) fn gen_nm_user_setting(
         nm_setting.data = Some(data);
         nm_conn.user = Some(nm_setting);
     }
+    if is_generated {
+        nm_conn
+            .user
+            .get_or_insert_with(NmSettingUser::default)
+            .data
+            .get_or_insert_with(HashMap::new)
+            .insert(NMSTATE_GENERATED.to_string(), "true".to_string());
+    }
+}
+
+pub(crate) fn is_nmstate_generated(nm_conn: &NmConnection) -> bool {
+    nm_conn
+        .user
+        .as_ref()
+        .and_then(|nm_setting| nm_setting.data.as_ref())
+        .map(|data| data.contains_key(NMSTATE_GENERATED))
+        .unwrap_or_default()
 }
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 
 use crate::nm::nm_dbus::{NmConnection, NmSettingVpn};
-use crate::{IpsecInterface, NetworkState};
+use crate::IpsecInterface;
 
 pub(crate) fn gen_nm_ipsec_vpn_setting(
     iface: &IpsecInterface,
@@ -50,8 +50,10 @@ pub(crate) fn gen_nm_ipsec_vpn_setting(
         nm_vpn_set.data = Some(vpn_data);
         nm_vpn_set.service_type =
             Some(NmSettingVpn::SERVICE_TYPE_LIBRESWAN.to_string());
-        if let Some(v) = conf.psk.as_deref() {
-            if v == NetworkState::PASSWORD_HID_BY_NMSTATE {
+        if conf.psk_from_secret_agent == Some(true) {
+            nm_vpn_set.set_psk_agent_owned();
+        } else if let Some(v) = conf.psk.as_ref() {
+            if v.is_hidden() {
                 nm_vpn_set.secrets = nm_conn
                     .vpn
                     .as_ref()
@@ -61,7 +63,7 @@ pub(crate) fn gen_nm_ipsec_vpn_setting(
                 nm_vpn_set
                     .secrets
                     .get_or_insert(HashMap::new())
-                    .insert("pskvalue".to_string(), v.to_string());
+                    .insert("pskvalue".to_string(), v.as_str().to_string());
             }
         }
         nm_conn.vpn = Some(nm_vpn_set);
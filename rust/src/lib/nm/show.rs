@@ -4,42 +4,52 @@ use std::collections::HashMap;
 
 use crate::nm::nm_dbus::{
     NmActiveConnection, NmApi, NmConnection, NmDevice, NmDeviceState,
-    NmLldpNeighbor, NM_ACTIVATION_STATE_FLAG_EXTERNAL,
+    NmDeviceStateReason, NmLldpNeighbor, NM_ACTIVATION_STATE_FLAG_EXTERNAL,
 };
 
 use super::{
     active_connection::create_index_for_nm_acs_by_name_type,
     error::nm_error_to_nmstate,
     query_apply::{
+        connectivity::nm_connectivity_check_retrieve,
         create_index_for_nm_conns_by_name_type,
         device::nm_dev_iface_type_to_nmstate, dispatch::get_dispatches,
         dns::nm_global_dns_to_nmstate, get_description, get_lldp,
-        is_lldp_enabled, is_mptcp_supported, nm_802_1x_to_nmstate,
-        nm_ip_setting_to_nmstate4, nm_ip_setting_to_nmstate6,
+        get_sriov_vlan_trunk_conf, is_lldp_enabled, is_mptcp_supported,
+        nm_802_1x_to_nmstate, nm_gsm_to_modem_conf, nm_ip_setting_to_nmstate4,
+        nm_ip_setting_to_nmstate6, nm_ip_tunnel_to_gre_conf,
+        nm_ip_tunnel_to_ip6_tunnel_conf, nm_ip_vlan_to_ipvlan_conf,
         ovs::merge_ovs_netdev_tun_iface, query_nmstate_wait_ip,
-        retrieve_dns_info, vpn::get_supported_vpn_ifaces,
+        query_nmstate_wait_ip_timeout, retrieve_dns_info,
+        retrieve_ipv6_ra_dns_info, vpn::get_supported_vpn_ifaces,
     },
     settings::{
-        get_bond_balance_slb, NM_SETTING_OVS_IFACE_SETTING_NAME,
+        get_bond_balance_slb, get_bridge_vlan_conf, NM_DEVICE_TYPE_MODEM_NAME,
+        NM_SETTING_GSM_SETTING_NAME, NM_SETTING_OVS_IFACE_SETTING_NAME,
         NM_SETTING_VETH_SETTING_NAME, NM_SETTING_WIRED_SETTING_NAME,
     },
 };
 use crate::{
     BaseInterface, BondConfig, BondInterface, BondOptions, DummyInterface,
-    EthernetInterface, InfiniBandInterface, Interface, InterfaceIdentifier,
-    InterfaceState, InterfaceType, Interfaces, LinuxBridgeInterface,
-    LoopbackInterface, MacSecConfig, MacSecInterface, MacVlanInterface,
-    MacVtapInterface, NetworkState, NmstateError, OvsBridgeInterface,
-    OvsInterface, UnknownInterface, VlanInterface, VrfInterface,
+    EthernetInterface, GreInterface, GretapInterface, InfiniBandInterface,
+    Interface, InterfaceBackend, InterfaceIdentifier, InterfaceIpv4,
+    InterfaceManagedBy, InterfaceState, InterfaceType, Interfaces,
+    Ip6GreInterface, Ip6GretapInterface, Ip6TnlInterface, IpVlanInterface,
+    LinuxBridgeConfig, LinuxBridgeInterface, LoopbackInterface, MacSecConfig,
+    MacSecInterface, MacVlanInterface, MacVtapInterface, ModemInterface,
+    NetworkState, NmstateError, OvsBridgeInterface, OvsInterface, Secret,
+    TeamInterface, UnknownInterface, VlanInterface, VrfInterface,
     VxlanInterface,
 };
 
 pub(crate) fn nm_retrieve(
+    nm_dbus_address: Option<&str>,
     running_config_only: bool,
 ) -> Result<NetworkState, NmstateError> {
     let mut net_state = NetworkState::new();
     net_state.prop_list = vec!["interfaces", "dns"];
-    let mut nm_api = NmApi::new().map_err(nm_error_to_nmstate)?;
+    let mut nm_api = NmApi::new_with_address(nm_dbus_address)
+        .map_err(nm_error_to_nmstate)?;
     let nm_conns = nm_api
         .applied_connections_get()
         .map_err(nm_error_to_nmstate)?;
@@ -85,12 +95,26 @@ pub(crate) fn nm_retrieve(
         }
         match nm_dev.state {
             NmDeviceState::Unmanaged | NmDeviceState::Disconnected => {
-                if let Some(iface) = nm_dev_to_nm_iface(nm_dev) {
+                if let Some(mut iface) = nm_dev_to_nm_iface(nm_dev) {
                     log::debug!(
                         "Found unmanaged or disconnected interface {}/{}",
                         iface.name(),
                         iface.iface_type()
                     );
+                    set_managed_by(
+                        &mut iface,
+                        if nm_dev.state == NmDeviceState::Unmanaged {
+                            InterfaceBackend::NetworkManagerUnmanaged
+                        } else {
+                            InterfaceBackend::NetworkManager
+                        },
+                        None,
+                    );
+                    if nm_dev.state_reason
+                        == NmDeviceStateReason::IpAddressDuplicate
+                    {
+                        set_ipv4_address_conflict(&mut iface);
+                    }
                     net_state.append_interface_data(iface);
                 }
             }
@@ -102,12 +126,17 @@ pub(crate) fn nm_retrieve(
                 );
                 if let Some(state_flag) = nm_ac.map(|nm_ac| nm_ac.state_flags) {
                     if (state_flag & NM_ACTIVATION_STATE_FLAG_EXTERNAL) > 0 {
-                        if let Some(iface) = nm_dev_to_nm_iface(nm_dev) {
+                        if let Some(mut iface) = nm_dev_to_nm_iface(nm_dev) {
                             log::debug!(
                                 "Found external managed interface {}/{}",
                                 iface.name(),
                                 iface.iface_type()
                             );
+                            set_managed_by(
+                                &mut iface,
+                                InterfaceBackend::NetworkManagerExternal,
+                                nm_ac.map(|nm_ac| nm_ac.uuid.as_str()),
+                            );
                             net_state.append_interface_data(iface);
                         }
                         continue;
@@ -163,6 +192,11 @@ pub(crate) fn nm_retrieve(
                         iface.base_iface_mut().prop_list.push("mptcp");
                         iface.base_iface_mut().mptcp = None;
                     }
+                    set_managed_by(
+                        &mut iface,
+                        InterfaceBackend::NetworkManager,
+                        nm_conn.uuid(),
+                    );
 
                     log::debug!(
                         "Found NM interface {}/{}",
@@ -174,7 +208,10 @@ pub(crate) fn nm_retrieve(
             }
         }
     }
-    for iface in get_supported_vpn_ifaces(&nm_saved_conn_uuid_index, &nm_acs)? {
+    for mut iface in
+        get_supported_vpn_ifaces(&nm_saved_conn_uuid_index, &nm_acs)?
+    {
+        set_managed_by(&mut iface, InterfaceBackend::NetworkManager, None);
         net_state.append_interface_data(iface);
     }
 
@@ -208,6 +245,8 @@ pub(crate) fn nm_retrieve(
     net_state.dns.sanitize().ok();
     if running_config_only {
         net_state.dns.running = None;
+    } else {
+        retrieve_ipv6_ra_dns_info(&mut nm_api, &mut net_state.interfaces)?;
     }
 
     for (iface_name, conf) in get_dispatches().drain() {
@@ -219,6 +258,11 @@ pub(crate) fn nm_retrieve(
         }
     }
 
+    if let Some(conf) = nm_connectivity_check_retrieve(&nm_api) {
+        net_state.prop_list.push("connectivity_check");
+        net_state.connectivity_check = Some(conf);
+    }
+
     set_ovs_iface_controller_info(&mut net_state.interfaces);
 
     merge_ovs_netdev_tun_iface(&mut net_state, &nm_devs, &nm_conns);
@@ -257,6 +301,7 @@ pub(crate) fn nm_conn_to_base_iface(
             "description",
             "lldp",
             "wait_ip",
+            "wait_ip_timeout",
             "identifier",
             "profile_name",
         ];
@@ -275,6 +320,10 @@ pub(crate) fn nm_conn_to_base_iface(
         base_iface.ipv6 = ipv6;
         base_iface.wait_ip =
             query_nmstate_wait_ip(nm_conn.ipv4.as_ref(), nm_conn.ipv6.as_ref());
+        base_iface.wait_ip_timeout = query_nmstate_wait_ip_timeout(
+            nm_conn.ipv4.as_ref(),
+            nm_conn.ipv6.as_ref(),
+        );
         base_iface.controller = nm_conn.controller().map(|c| c.to_string());
         base_iface.description = get_description(nm_conn);
         base_iface.identifier = get_identifier(nm_conn);
@@ -313,11 +362,18 @@ fn iface_get(
             InterfaceType::LinuxBridge => Interface::LinuxBridge({
                 let mut iface = LinuxBridgeInterface::new();
                 iface.base = base_iface;
+                if let Some(vlan_conf) = get_bridge_vlan_conf(nm_conn) {
+                    iface.bridge = Some(LinuxBridgeConfig {
+                        vlan: Some(vlan_conf),
+                        ..Default::default()
+                    });
+                }
                 iface
             }),
             InterfaceType::Ethernet => Interface::Ethernet({
                 let mut iface = EthernetInterface::new();
                 iface.base = base_iface;
+                iface.ethernet = get_sriov_vlan_trunk_conf(nm_conn);
                 iface
             }),
             InterfaceType::Bond => Interface::Bond({
@@ -389,14 +445,80 @@ fn iface_get(
                         if let Some(macsec_saved_set) =
                             saved_conn.macsec.as_ref()
                         {
-                            macsec_config.mka_cak =
-                                macsec_saved_set.mka_cak.clone();
+                            macsec_config.mka_cak = macsec_saved_set
+                                .mka_cak
+                                .clone()
+                                .map(Secret::from);
                         }
                     }
                     iface.macsec = Some(macsec_config);
                 }
                 iface
             }),
+            InterfaceType::Gre => Interface::Gre({
+                let mut iface = GreInterface::new();
+                iface.base = base_iface;
+                iface.gre =
+                    nm_conn.ip_tunnel.as_ref().map(nm_ip_tunnel_to_gre_conf);
+                iface
+            }),
+            InterfaceType::Gretap => Interface::Gretap({
+                let mut iface = GretapInterface::new();
+                iface.base = base_iface;
+                iface.gretap =
+                    nm_conn.ip_tunnel.as_ref().map(nm_ip_tunnel_to_gre_conf);
+                iface
+            }),
+            InterfaceType::Ip6Gre => Interface::Ip6Gre({
+                let mut iface = Ip6GreInterface::new();
+                iface.base = base_iface;
+                iface.ip6gre = nm_conn
+                    .ip_tunnel
+                    .as_ref()
+                    .map(nm_ip_tunnel_to_ip6_tunnel_conf);
+                iface
+            }),
+            InterfaceType::Ip6Gretap => Interface::Ip6Gretap({
+                let mut iface = Ip6GretapInterface::new();
+                iface.base = base_iface;
+                iface.ip6gretap = nm_conn
+                    .ip_tunnel
+                    .as_ref()
+                    .map(nm_ip_tunnel_to_ip6_tunnel_conf);
+                iface
+            }),
+            InterfaceType::Ip6Tnl => Interface::Ip6Tnl({
+                let mut iface = Ip6TnlInterface::new();
+                iface.base = base_iface;
+                iface.ip6tnl = nm_conn
+                    .ip_tunnel
+                    .as_ref()
+                    .map(nm_ip_tunnel_to_ip6_tunnel_conf);
+                iface
+            }),
+            InterfaceType::Team => Interface::Team({
+                let mut iface = TeamInterface::new();
+                iface.base = base_iface;
+                iface
+            }),
+            InterfaceType::IpVlan => Interface::IpVlan({
+                let mut iface = IpVlanInterface::new();
+                iface.base = base_iface;
+                iface.ipvlan =
+                    nm_conn.ip_vlan.as_ref().map(nm_ip_vlan_to_ipvlan_conf);
+                iface
+            }),
+            InterfaceType::Modem => Interface::Modem({
+                let mut iface = ModemInterface::new();
+                iface.base = base_iface;
+                iface.modem = nm_conn.gsm.as_ref().map(|nm_set| {
+                    nm_gsm_to_modem_conf(
+                        nm_set,
+                        nm_saved_conn.and_then(|c| c.gsm.as_ref()),
+                    )
+                });
+                iface
+            }),
             _ => {
                 log::debug!("Skip unsupported interface {:?}", base_iface);
                 return None;
@@ -417,11 +539,11 @@ fn get_first_nm_conn<'a>(
     name: &'a str,
     nm_iface_type: &'a str,
 ) -> Option<&'a NmConnection> {
-    // Treating veth as ethernet
-    let nm_iface_type = if nm_iface_type == NM_SETTING_VETH_SETTING_NAME {
-        NM_SETTING_WIRED_SETTING_NAME
-    } else {
-        nm_iface_type
+    // Treating veth as ethernet, modem device is using gsm connection
+    let nm_iface_type = match nm_iface_type {
+        NM_SETTING_VETH_SETTING_NAME => NM_SETTING_WIRED_SETTING_NAME,
+        NM_DEVICE_TYPE_MODEM_NAME => NM_SETTING_GSM_SETTING_NAME,
+        t => t,
     };
     if let Some(nm_conns) = nm_conns_name_type_index.get(&(name, nm_iface_type))
     {
@@ -448,6 +570,7 @@ fn get_nm_ac<'a>(
             name,
             match nm_iface_type {
                 NM_SETTING_VETH_SETTING_NAME => NM_SETTING_WIRED_SETTING_NAME,
+                NM_DEVICE_TYPE_MODEM_NAME => NM_SETTING_GSM_SETTING_NAME,
                 t => t,
             },
         ))
@@ -476,6 +599,34 @@ fn set_ovs_iface_controller_info(ifaces: &mut Interfaces) {
     }
 }
 
+fn set_managed_by(
+    iface: &mut Interface,
+    backend: InterfaceBackend,
+    profile_uuid: Option<&str>,
+) {
+    let base_iface = iface.base_iface_mut();
+    base_iface.prop_list.push("managed_by");
+    base_iface.managed_by = Some(InterfaceManagedBy {
+        backend,
+        profile_uuid: profile_uuid.map(|u| u.to_string()),
+    });
+}
+
+// NetworkManager deactivates the device with reason `IpAddressDuplicate` when
+// IPv4 address conflict detected.
+fn set_ipv4_address_conflict(iface: &mut Interface) {
+    log::warn!(
+        "NetworkManager detected IPv4 address conflict on interface {}",
+        iface.name()
+    );
+    let mut ip_conf = InterfaceIpv4::new();
+    ip_conf.prop_list.push("address_conflict");
+    ip_conf.address_conflict = Some(true);
+    let base_iface = iface.base_iface_mut();
+    base_iface.prop_list.push("ipv4");
+    base_iface.ipv4 = Some(ip_conf);
+}
+
 fn nm_dev_to_nm_iface(nm_dev: &NmDevice) -> Option<Interface> {
     let mut base_iface = BaseInterface::new();
     if nm_dev.name.is_empty() {
@@ -568,6 +719,46 @@ fn nm_dev_to_nm_iface(nm_dev: &NmDevice) -> Option<Interface> {
                 ..Default::default()
             }
         }),
+        InterfaceType::Gre => Interface::Gre({
+            let mut iface = GreInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Gretap => Interface::Gretap({
+            let mut iface = GretapInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Ip6Gre => Interface::Ip6Gre({
+            let mut iface = Ip6GreInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Ip6Gretap => Interface::Ip6Gretap({
+            let mut iface = Ip6GretapInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Ip6Tnl => Interface::Ip6Tnl({
+            let mut iface = Ip6TnlInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Team => Interface::Team({
+            let mut iface = TeamInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::IpVlan => Interface::IpVlan({
+            let mut iface = IpVlanInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
+        InterfaceType::Modem => Interface::Modem({
+            let mut iface = ModemInterface::new();
+            iface.base = base_iface;
+            iface
+        }),
         iface_type
             if iface_type == &InterfaceType::Other("ovs-port".to_string()) =>
         {
@@ -0,0 +1,70 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::{NmConnection, NmSettingBond};
+use crate::{nm::settings::gen_nm_bond_setting, BondInterface};
+
+#[test]
+fn test_nm_bond_setting_active_port() {
+    let iface: BondInterface = serde_yaml::from_str(
+        r"---
+name: bond99
+type: bond
+state: up
+link-aggregation:
+  mode: active-backup
+  port:
+  - eth1
+  - eth2
+  active-port: eth2
+",
+    )
+    .unwrap();
+    let mut nm_conn = NmConnection::default();
+
+    gen_nm_bond_setting(&iface, &mut nm_conn);
+
+    let nm_bond_set = nm_conn.bond.unwrap();
+    assert_eq!(
+        nm_bond_set.options.get("active_slave").map(|s| s.as_str()),
+        Some("eth2")
+    );
+    assert_eq!(
+        nm_bond_set.options.get("mode").map(|s| s.as_str()),
+        Some("active-backup")
+    );
+}
+
+#[test]
+fn test_nm_bond_setting_remove_active_port() {
+    let iface: BondInterface = serde_yaml::from_str(
+        r"---
+name: bond99
+type: bond
+state: up
+link-aggregation:
+  mode: active-backup
+  port:
+  - eth1
+  - eth2
+",
+    )
+    .unwrap();
+    let mut nm_conn = NmConnection::default();
+    let mut nm_bond_set = NmSettingBond::default();
+    nm_bond_set
+        .options
+        .insert("mode".to_string(), "active-backup".to_string());
+    nm_bond_set
+        .options
+        .insert("active_slave".to_string(), "eth2".to_string());
+    nm_conn.bond = Some(nm_bond_set);
+
+    gen_nm_bond_setting(&iface, &mut nm_conn);
+
+    let nm_bond_set = nm_conn.bond.unwrap();
+    assert!(!nm_bond_set.options.contains_key("active_slave"));
+    assert_eq!(
+        nm_bond_set.options.get("mode").map(|s| s.as_str()),
+        Some("active-backup")
+    );
+}
@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::query_apply::connectivity::write_connectivity_conf;
+
+#[test]
+fn test_write_connectivity_conf_replace_exist_file() {
+    let root = std::env::temp_dir().join(format!(
+        "nmstate_test_connectivity_conf_{}",
+        std::process::id()
+    ));
+    let file_path = root.join("conf.d/99-nmstate-connectivity.conf");
+    let file_path = file_path.to_str().unwrap();
+
+    write_connectivity_conf(file_path, b"old").unwrap();
+    write_connectivity_conf(file_path, b"new").unwrap();
+
+    assert_eq!(std::fs::read_to_string(file_path).unwrap(), "new");
+    assert_eq!(std::fs::read_dir(root.join("conf.d")).unwrap().count(), 1);
+    std::fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::NmConnection;
+use crate::{nm::settings::gen_nm_gsm_setting, ModemInterface};
+
+#[test]
+fn test_nm_gsm_setting_pin_not_redacted() {
+    let iface: ModemInterface = serde_yaml::from_str(
+        r#"---
+name: cdc-wdm0
+type: modem
+state: up
+modem:
+  apn: internet.example.com
+  pin: "1234"
+"#,
+    )
+    .unwrap();
+    let mut nm_conn = NmConnection::default();
+
+    gen_nm_gsm_setting(&iface, &mut nm_conn);
+
+    let nm_gsm_set = nm_conn.gsm.unwrap();
+    assert_eq!(nm_gsm_set.pin.as_deref(), Some("1234"));
+    assert_eq!(nm_gsm_set.apn.as_deref(), Some("internet.example.com"));
+}
@@ -1,2 +1,10 @@
 #[cfg(test)]
+mod bond;
+#[cfg(all(test, feature = "query_apply"))]
+mod connectivity;
+#[cfg(test)]
+mod gsm;
+#[cfg(test)]
 mod profiles;
+#[cfg(test)]
+mod vpn;
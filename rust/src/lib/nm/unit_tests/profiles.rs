@@ -1,9 +1,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::nm::nm_dbus::{NmConnection, NmSettingConnection};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use zvariant::{OwnedValue, Value};
+
+use crate::nm::nm_dbus::{
+    NmConnection, NmSettingConnection, NmSettingsConnectionFlag,
+};
 use crate::{
-    nm::settings::use_uuid_for_controller_reference, Interface, InterfaceType,
-    Interfaces, MergedInterfaces, OvsBridgeBondConfig, OvsBridgeBondPortConfig,
+    nm::profile::perpare_nm_conns,
+    nm::settings::{
+        iface_to_nm_connections, is_nmstate_generated,
+        use_uuid_for_controller_reference,
+    },
+    Interface, InterfaceType, Interfaces, MergedInterfaces, MergedNetworkState,
+    NetworkState, OvsBridgeBondConfig, OvsBridgeBondPortConfig,
     OvsBridgeConfig, OvsBridgeInterface, OvsBridgePortConfig,
 };
 
@@ -113,3 +125,474 @@ fn test_use_uuid_for_controller_reference_with_ovs_bond() {
     assert!(p2_nm_con_set.controller == Some(UUID2.to_string()));
     assert!(p2_nm_con_set.controller_type == Some("ovs-port".to_string()));
 }
+
+#[test]
+fn test_volatile_iface_to_nm_connections() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+  volatile: true
+- name: dummy1
+  type: dummy
+  state: up
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false)
+            .unwrap();
+
+    let dummy0_nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy0", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[],
+        &[],
+        false,
+    )
+    .unwrap();
+    assert_eq!(
+        dummy0_nm_conns[0].flags,
+        vec![NmSettingsConnectionFlag::Volatile]
+    );
+
+    let dummy1_nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy1", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[],
+        &[],
+        false,
+    )
+    .unwrap();
+    assert!(dummy1_nm_conns[0].flags.is_empty());
+}
+
+#[test]
+fn test_save_to_disk_false_iface_to_nm_connections() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+  save-to-disk: false
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false)
+            .unwrap();
+
+    let nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy0", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[],
+        &[],
+        false,
+    )
+    .unwrap();
+    assert_eq!(nm_conns[0].flags, vec![NmSettingsConnectionFlag::Unsaved]);
+}
+
+#[test]
+fn test_nmstate_generated_marker() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+  description: test
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false)
+            .unwrap();
+
+    let nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy0", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[],
+        &[],
+        false,
+    )
+    .unwrap();
+    assert!(is_nmstate_generated(&nm_conns[0]));
+    assert!(!is_nmstate_generated(&NmConnection::default()));
+
+    // Keep the marker when modifying nmstate generated profile
+    let nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy0", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[nm_conns[0].clone()],
+        &[],
+        false,
+    )
+    .unwrap();
+    assert!(is_nmstate_generated(&nm_conns[0]));
+}
+
+#[test]
+fn test_nmstate_generated_marker_not_set_on_exist_profile() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+  description: test
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false)
+            .unwrap();
+
+    let nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy0", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[gen_exist_dummy0_nm_conn()],
+        &[],
+        false,
+    )
+    .unwrap();
+    assert_eq!(nm_conns[0].uuid(), Some(UUID1));
+    assert!(!is_nmstate_generated(&nm_conns[0]));
+}
+
+#[test]
+fn test_nm_passthrough_settings() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+  nm:
+    settings:
+      connection:
+        auth-retries: 2
+        zone: trusted
+      ipv4:
+        dhcp-vendor-class-identifier: nmstate
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false)
+            .unwrap();
+
+    let nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy0", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[],
+        &[],
+        false,
+    )
+    .unwrap();
+    let nm_conn_value = nm_conns[0].to_value().unwrap();
+    assert_eq!(
+        nm_conn_value["connection"]["auth-retries"],
+        zvariant::Value::new(2i32)
+    );
+    assert_eq!(
+        nm_conn_value["connection"]["zone"],
+        zvariant::Value::new("trusted")
+    );
+    assert_eq!(
+        nm_conn_value["connection"]["interface-name"],
+        zvariant::Value::new("dummy0")
+    );
+    assert_eq!(
+        nm_conn_value["ipv4"]["dhcp-vendor-class-identifier"],
+        zvariant::Value::new("nmstate")
+    );
+}
+
+fn gen_exist_dummy0_nm_conn() -> NmConnection {
+    let mut nm_conn_value: HashMap<String, HashMap<String, OwnedValue>> =
+        HashMap::new();
+    nm_conn_value.insert(
+        "connection".to_string(),
+        HashMap::from([
+            ("id".to_string(), OwnedValue::from(Value::new("dummy0"))),
+            ("uuid".to_string(), OwnedValue::from(Value::new(UUID1))),
+            ("type".to_string(), OwnedValue::from(Value::new("dummy"))),
+            (
+                "interface-name".to_string(),
+                OwnedValue::from(Value::new("dummy0")),
+            ),
+            (
+                "gateway-ping-timeout".to_string(),
+                OwnedValue::from(Value::new(10u32)),
+            ),
+            ("zone".to_string(), OwnedValue::from(Value::new("trusted"))),
+        ]),
+    );
+    NmConnection::try_from(nm_conn_value).unwrap()
+}
+
+fn gen_dummy0_nm_conn_with_passthrough(
+    nm_settings_yaml: &str,
+) -> Result<NmConnection, crate::NmstateError> {
+    let desired: NetworkState = serde_yaml::from_str(&format!(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+  nm:
+    settings:
+{nm_settings_yaml}"
+    ))
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false)
+            .unwrap();
+
+    let mut nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy0", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[gen_exist_dummy0_nm_conn()],
+        &[],
+        false,
+    )?;
+    Ok(nm_conns.remove(0))
+}
+
+#[test]
+fn test_nm_passthrough_follow_type_of_exist_prop_in_modeled_setting() {
+    let nm_conn = gen_dummy0_nm_conn_with_passthrough(
+        r"      connection:
+        gateway-ping-timeout: 20
+",
+    )
+    .unwrap();
+    let nm_conn_value = nm_conn.to_value().unwrap();
+    assert_eq!(
+        nm_conn_value["connection"]["gateway-ping-timeout"],
+        Value::new(20u32)
+    );
+}
+
+#[test]
+fn test_nm_passthrough_reject_nmstate_owned_prop() {
+    let result = gen_dummy0_nm_conn_with_passthrough(
+        r"      ipv4:
+        dad-timeout: 3000
+",
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), crate::ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_nm_passthrough_remove_prop() {
+    let nm_conn = gen_dummy0_nm_conn_with_passthrough(
+        r"      connection:
+        zone: null
+",
+    )
+    .unwrap();
+    let nm_conn_value = nm_conn.to_value().unwrap();
+    assert!(!nm_conn_value["connection"].contains_key("zone"));
+    assert_eq!(
+        nm_conn_value["connection"]["gateway-ping-timeout"],
+        Value::new(10u32)
+    );
+}
+
+#[test]
+fn test_preserve_unknown_nm_settings() {
+    let mut nm_conn_value: HashMap<String, HashMap<String, OwnedValue>> =
+        HashMap::new();
+    nm_conn_value.insert(
+        "connection".to_string(),
+        HashMap::from([
+            ("id".to_string(), OwnedValue::from(Value::new("dummy0"))),
+            ("uuid".to_string(), OwnedValue::from(Value::new(UUID1))),
+            ("type".to_string(), OwnedValue::from(Value::new("dummy"))),
+            (
+                "interface-name".to_string(),
+                OwnedValue::from(Value::new("dummy0")),
+            ),
+            (
+                "permissions".to_string(),
+                OwnedValue::from(Value::new(vec!["user:foo".to_string()])),
+            ),
+        ]),
+    );
+    nm_conn_value.insert(
+        "802-1x".to_string(),
+        HashMap::from([
+            ("identity".to_string(), OwnedValue::from(Value::new("old"))),
+            (
+                "phase2-auth".to_string(),
+                OwnedValue::from(Value::new("mschapv2")),
+            ),
+        ]),
+    );
+    nm_conn_value.insert(
+        "proxy".to_string(),
+        HashMap::from([(
+            "method".to_string(),
+            OwnedValue::from(Value::new(1i32)),
+        )]),
+    );
+    let exist_nm_conn = NmConnection::try_from(nm_conn_value).unwrap();
+
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+  802.1x:
+    identity: new
+    eap-methods:
+    - peap
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false)
+            .unwrap();
+
+    let nm_conns = iface_to_nm_connections(
+        merged_state
+            .interfaces
+            .get_iface("dummy0", InterfaceType::Dummy)
+            .unwrap(),
+        &merged_state,
+        &[exist_nm_conn],
+        &[],
+        false,
+    )
+    .unwrap();
+    let nm_conn_value = nm_conns[0].to_value().unwrap();
+    assert_eq!(nm_conn_value["connection"]["uuid"], Value::new(UUID1));
+    assert_eq!(
+        nm_conn_value["connection"]["permissions"],
+        Value::new(vec!["user:foo".to_string()])
+    );
+    assert_eq!(nm_conn_value["802-1x"]["identity"], Value::new("new"));
+    assert_eq!(
+        nm_conn_value["802-1x"]["phase2-auth"],
+        Value::new("mschapv2")
+    );
+    assert_eq!(nm_conn_value["proxy"]["method"], Value::new(1i32));
+}
+
+fn gen_nm_conn(
+    uuid: &str,
+    iface_name: &str,
+    iface_type: &str,
+    controller: Option<&str>,
+) -> NmConnection {
+    let mut nm_conn = NmConnection::default();
+    let mut nm_conn_set = NmSettingConnection::default();
+    nm_conn_set.id = Some(iface_name.to_string());
+    nm_conn_set.uuid = Some(uuid.to_string());
+    nm_conn_set.iface_type = Some(iface_type.to_string());
+    nm_conn_set.iface_name = Some(iface_name.to_string());
+    if let Some(ctrl) = controller {
+        nm_conn_set.controller = Some(ctrl.to_string());
+        nm_conn_set.controller_type = Some("bond".to_string());
+    }
+    nm_conn.connection = Some(nm_conn_set);
+    nm_conn
+}
+
+#[test]
+fn test_reactivate_ports_of_ordered_controller() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: bond99
+  type: bond
+  state: up
+  ports-ordered: true
+  link-aggregation:
+    mode: balance-rr
+    port:
+    - eth2
+    - eth1
+",
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+    - eth2
+- name: eth1
+  type: ethernet
+  state: up
+  controller: bond99
+- name: eth2
+  type: ethernet
+  state: up
+  controller: bond99
+",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    let exist_nm_conns = vec![
+        gen_nm_conn(UUID1, "bond99", "bond", None),
+        gen_nm_conn(UUID2, "eth1", "802-3-ethernet", Some(UUID1)),
+        gen_nm_conn(UUID3, "eth2", "802-3-ethernet", Some(UUID1)),
+    ];
+
+    let nm_conns = perpare_nm_conns(
+        &merged_state,
+        &exist_nm_conns,
+        &[],
+        true,
+        true,
+        false,
+    )
+    .unwrap();
+
+    let activate_uuids: Vec<&str> = nm_conns
+        .to_activate
+        .iter()
+        .filter_map(|c| c.uuid())
+        .collect();
+    assert_eq!(activate_uuids, vec![UUID1, UUID3, UUID2]);
+}
@@ -0,0 +1,63 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::nm::nm_dbus::NmConnection;
+use crate::{nm::settings::gen_nm_ipsec_vpn_setting, IpsecInterface};
+
+#[test]
+fn test_nm_ipsec_psk_from_secret_agent() {
+    let iface: IpsecInterface = serde_yaml::from_str(
+        r#"---
+name: hosta_conn
+type: ipsec
+state: up
+libreswan:
+  right: 192.0.2.253
+  psk-from-secret-agent: true
+"#,
+    )
+    .unwrap();
+    let mut nm_conn = NmConnection::default();
+
+    gen_nm_ipsec_vpn_setting(&iface, &mut nm_conn);
+
+    let nm_vpn_set = nm_conn.vpn.as_ref().unwrap();
+    assert!(nm_vpn_set.is_psk_agent_owned());
+    assert_eq!(
+        nm_vpn_set
+            .data
+            .as_ref()
+            .and_then(|d| d.get("pskvalue-flags"))
+            .map(String::as_str),
+        Some("1")
+    );
+    assert_eq!(nm_vpn_set.secrets, None);
+}
+
+#[test]
+fn test_nm_ipsec_psk_not_agent_owned_by_default() {
+    let iface: IpsecInterface = serde_yaml::from_str(
+        r#"---
+name: hosta_conn
+type: ipsec
+state: up
+libreswan:
+  right: 192.0.2.253
+  psk: TOP_SECRET
+"#,
+    )
+    .unwrap();
+    let mut nm_conn = NmConnection::default();
+
+    gen_nm_ipsec_vpn_setting(&iface, &mut nm_conn);
+
+    let nm_vpn_set = nm_conn.vpn.as_ref().unwrap();
+    assert!(!nm_vpn_set.is_psk_agent_owned());
+    assert_eq!(
+        nm_vpn_set
+            .secrets
+            .as_ref()
+            .and_then(|s| s.get("pskvalue"))
+            .map(String::as_str),
+        Some("TOP_SECRET")
+    );
+}
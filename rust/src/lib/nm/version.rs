@@ -1,35 +1,107 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::NmstateError;
+#[cfg(feature = "query_apply")]
+use crate::{ErrorKind, MergedNetworkState, NmstateFeature};
+
+// Minimum NetworkManager version(major, minor) required by features, applying
+// these features to older NetworkManager will fail in validation stage.
+// Please sort this list
+#[cfg(feature = "query_apply")]
+const NM_FEATURE_MIN_VERSIONS: [(NmstateFeature, u32, u32); 4] = [
+    (NmstateFeature::Dhcpv4SendRelease, 1, 48),
+    (NmstateFeature::Dhcpv6SendRelease, 1, 48),
+    (NmstateFeature::OvsDbInterfaceOtherConfig, 1, 42),
+    (NmstateFeature::StaticDnsOption, 1, 2),
+];
 
-// This helper function will help us to avoid introducing new dependencies to
-// the project.
+// NetworkManager 1.32+ supports `accept-all-mac-addresses` property
 #[cfg(feature = "query_apply")]
 pub(crate) fn nm_supports_accept_all_mac_addresses_mode(
-) -> Result<bool, NmstateError> {
-    let version = {
-        let nm_api = crate::nm::nm_dbus::NmApi::new()
-            .map_err(crate::nm::error::nm_error_to_nmstate)?;
-        nm_api
-            .version()
-            .map_err(crate::nm::error::nm_error_to_nmstate)?
-    };
-    let version_split = version.split('.');
-    let supported_version = Vec::<u32>::from([1, 32]);
-    let mut supported_elem = supported_version.iter();
-
-    for v_elem in version_split {
-        if v_elem.chars().all(char::is_numeric) {
-            if let Some(supported_v) = supported_elem.next() {
-                if v_elem.parse::<u32>().unwrap_or_default() < *supported_v {
-                    return Ok(false);
-                }
-            } else {
-                return Ok(true);
-            }
+    nm_api: &crate::nm::nm_dbus::NmApi,
+) -> bool {
+    match nm_api.version() {
+        Ok(version) => is_nm_version_at_least(version.as_str(), 1, 32),
+        Err(e) => {
+            log::debug!("Failed to retrieve NetworkManager version: {e}");
+            false
         }
     }
-    Ok(true)
+}
+
+#[cfg(feature = "query_apply")]
+pub(crate) fn nm_version(
+    nm_dbus_address: Option<&str>,
+) -> Result<String, NmstateError> {
+    let nm_api = crate::nm::nm_dbus::NmApi::new_with_address(nm_dbus_address)
+        .map_err(crate::nm::error::nm_error_to_nmstate)?;
+    nm_api
+        .version()
+        .map_err(crate::nm::error::nm_error_to_nmstate)
+}
+
+// Whether NetworkManager version string is equal or newer than specified
+// major and minor version.
+#[cfg(feature = "query_apply")]
+pub(crate) fn is_nm_version_at_least(
+    version: &str,
+    major: u32,
+    minor: u32,
+) -> bool {
+    let mut versions = version.split('.').map(|v| v.parse::<u32>());
+    match (versions.next(), versions.next()) {
+        (Some(Ok(cur_major)), Some(Ok(cur_minor))) => {
+            (cur_major, cur_minor) >= (major, minor)
+        }
+        _ => false,
+    }
+}
+
+// Features not supported by specified NetworkManager version
+#[cfg(feature = "query_apply")]
+pub(crate) fn nm_unsupported_features(version: &str) -> Vec<NmstateFeature> {
+    NM_FEATURE_MIN_VERSIONS
+        .iter()
+        .filter(|(_, major, minor)| {
+            !is_nm_version_at_least(version, *major, *minor)
+        })
+        .map(|(feature, _, _)| *feature)
+        .collect()
+}
+
+// Fail with clear error when desired state contains feature not supported by
+// running NetworkManager, instead of opaque D-Bus failure in apply stage.
+#[cfg(feature = "query_apply")]
+pub(crate) fn nm_check_features(
+    version: &str,
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    check_feature_versions(version, &merged_state.get_features())
+}
+
+#[cfg(feature = "query_apply")]
+pub(crate) fn check_feature_versions(
+    version: &str,
+    features: &[NmstateFeature],
+) -> Result<(), NmstateError> {
+    for (feature, major, minor) in NM_FEATURE_MIN_VERSIONS
+        .iter()
+        .filter(|(f, _, _)| features.contains(f))
+    {
+        if !is_nm_version_at_least(version, *major, *minor) {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                format!(
+                    "Feature {feature:?} requires NetworkManager >= \
+                    {major}.{minor}, but current NetworkManager version is \
+                    {version}"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    }
+    Ok(())
 }
 
 #[cfg(not(feature = "query_apply"))]
@@ -0,0 +1,44 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// NetworkManager specific interface configurations not modeled by nmstate.
+/// Only supported by NetworkManager backend.
+/// The example yaml of setting NetworkManager properties
+/// `ipv4.dhcp-vendor-class-identifier` and `connection.auth-retries` would be:
+/// ```yml
+/// interfaces:
+/// - name: eth1
+///   type: ethernet
+///   state: up
+///   nm:
+///     settings:
+///       ipv4:
+///         dhcp-vendor-class-identifier: nmstate
+///       connection:
+///         auth-retries: 2
+/// ```
+pub struct NmPassthroughConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Raw NetworkManager settings indexed by setting name (e.g. `ipv4`,
+    /// `802-3-ethernet`) and then property name, both as used by
+    /// NetworkManager D-Bus API. These properties are applied on top of the
+    /// NetworkManager profile generated by nmstate, hence take precedence
+    /// over the nmstate generated ones.
+    /// Property not mentioned here remains untouched, removing a property
+    /// from this section does not reset it in NetworkManager profile, please
+    /// set it to `null` to remove it from NetworkManager profile.
+    /// Properties modeled by nmstate schema are not allowed.
+    pub settings: Option<HashMap<String, HashMap<String, serde_json::Value>>>,
+}
+
+impl NmPassthroughConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
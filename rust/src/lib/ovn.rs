@@ -5,7 +5,10 @@ use std::convert::{TryFrom, TryInto};
 
 use serde::{Deserialize, Serialize};
 
-use crate::{ErrorKind, NmstateError};
+use crate::{
+    warning::nmstate_warn, ErrorKind, Interface, InterfaceType, Interfaces,
+    NetworkState, NmstateError, OvsBridgeInterface,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -72,6 +75,19 @@ impl OvnConfiguration {
         Ok(())
     }
 
+    // Localnet and OVS bridge name of each desired(non-absent) mapping
+    pub(crate) fn get_desired_bridges(&self) -> Vec<(&str, &str)> {
+        self.bridge_mappings
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|m| !m.is_absent())
+            .filter_map(|m| {
+                m.bridge.as_deref().map(|br| (m.localnet.as_str(), br))
+            })
+            .collect()
+    }
+
     pub(crate) fn to_ovsdb_external_id_value(&self) -> Option<String> {
         if let Some(maps) = self.bridge_mappings.as_ref() {
             let mut maps = maps.clone();
@@ -196,11 +212,44 @@ impl MergedOvnConfiguration {
     }
 }
 
+impl NetworkState {
+    // Add OVS bridge to desired state for each desired OVN bridge mapping
+    // referring to OVS bridge not found in desired or current state.
+    pub(crate) fn add_ovs_bridges_for_ovn(&mut self, current: &Interfaces) {
+        let mut new_bridges: Vec<String> = Vec::new();
+        for (_, bridge) in self.ovn.get_desired_bridges() {
+            if self
+                .interfaces
+                .get_iface(bridge, InterfaceType::OvsBridge)
+                .is_none()
+                && current
+                    .get_iface(bridge, InterfaceType::OvsBridge)
+                    .is_none()
+                && !new_bridges.iter().any(|b| b == bridge)
+            {
+                new_bridges.push(bridge.to_string());
+            }
+        }
+        for bridge in new_bridges {
+            nmstate_warn!(
+                "Creating OVS bridge {bridge} for OVN bridge mapping"
+            );
+            let mut iface = OvsBridgeInterface::new();
+            iface.base.name = bridge;
+            self.interfaces.push(Interface::OvsBridge(iface));
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[non_exhaustive]
 pub struct OvnBridgeMapping {
     pub localnet: String,
-    #[serde(skip_serializing)]
+    #[serde(
+        skip_serializing,
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     /// When set to `state: absent`, will delete the existing
     /// `localnet` mapping.
     pub state: Option<OvnBridgeMappingState>,
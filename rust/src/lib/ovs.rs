@@ -6,6 +6,13 @@ use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::{ErrorKind, MergedOvnConfiguration, NmstateError};
 
+// External IDs set by ovn-kubernetes or ovn-controller indicating the OVS
+// bridge or interface is managed by CNI.
+const OVS_CNI_EXTERNAL_ID_KEYS: [&str; 3] =
+    ["iface-id", "sandbox", "ovn-installed"];
+const OVS_CNI_EXTERNAL_ID_KEY_PREFIXES: [&str; 2] =
+    ["k8s.ovn.org/", "ct-zone-"];
+
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
 #[non_exhaustive]
 pub struct OvsDbGlobalConfig {
@@ -138,6 +145,23 @@ impl OvsDbIfaceConfig {
         ret
     }
 
+    // Return the external ID key indicating this OVS bridge or interface is
+    // managed by CNI.
+    pub(crate) fn get_cni_external_id(&self) -> Option<&str> {
+        let mut keys: Vec<&str> = self
+            .get_external_ids()
+            .into_keys()
+            .filter(|k| {
+                OVS_CNI_EXTERNAL_ID_KEYS.contains(k)
+                    || OVS_CNI_EXTERNAL_ID_KEY_PREFIXES
+                        .iter()
+                        .any(|p| k.starts_with(p))
+            })
+            .collect();
+        keys.sort_unstable();
+        keys.first().copied()
+    }
+
     pub(crate) fn get_other_config(&self) -> HashMap<&str, &str> {
         let mut ret = HashMap::new();
         if let Some(cfgs) = self.other_config.as_ref() {
@@ -3,10 +3,11 @@
 use crate::{ovsdb::db::OvsDbConnection, MergedNetworkState, NmstateError};
 
 pub(crate) fn ovsdb_apply(
+    socket_path: Option<&str>,
     merged_state: &MergedNetworkState,
 ) -> Result<(), NmstateError> {
     if merged_state.is_global_ovsdb_changed() {
-        let mut cli = OvsDbConnection::new()?;
+        let mut cli = OvsDbConnection::new(socket_path)?;
         cli.apply_global_conf(&merged_state.ovsdb)
     } else {
         log::debug!("No OVSDB changes");
@@ -72,10 +72,12 @@ impl OvsDbSelect {
 }
 
 impl OvsDbConnection {
-    // TODO: support environment variable OVS_DB_UNIX_SOCKET_PATH
-    pub(crate) fn new() -> Result<Self, NmstateError> {
+    // Use default socket path when socket_path is None
+    pub(crate) fn new(socket_path: Option<&str>) -> Result<Self, NmstateError> {
         Ok(Self {
-            rpc: OvsDbJsonRpc::connect(DEFAULT_OVS_DB_SOCKET_PATH)?,
+            rpc: OvsDbJsonRpc::connect(
+                socket_path.unwrap_or(DEFAULT_OVS_DB_SOCKET_PATH),
+            )?,
         })
     }
 
@@ -164,6 +166,7 @@ impl OvsDbConnection {
                 "_uuid",
                 "type",
                 "mtu",
+                "mtu_request",
                 "options",
             ],
         )
@@ -7,29 +7,31 @@ use serde_json::Value;
 
 use crate::{
     BridgePortTrunkTag, BridgePortVlanConfig, BridgePortVlanMode,
-    BridgePortVlanRange, Interface, InterfaceType, Interfaces, NetworkState,
-    NmstateError, OvsBridgeBondConfig, OvsBridgeBondMode,
-    OvsBridgeBondPortConfig, OvsBridgeConfig, OvsBridgeInterface,
-    OvsBridgeOptions, OvsBridgePortConfig, OvsBridgeStpOptions,
-    OvsDbIfaceConfig, OvsDpdkConfig, OvsInterface, OvsPatchConfig,
-    UnknownInterface,
+    BridgePortVlanRange, Interface, InterfaceBackend, InterfaceManagedBy,
+    InterfaceType, Interfaces, NetworkState, NmstateError, OvsBridgeBondConfig,
+    OvsBridgeBondMode, OvsBridgeBondPortConfig, OvsBridgeConfig,
+    OvsBridgeInterface, OvsBridgeOptions, OvsBridgePortConfig,
+    OvsBridgeStpOptions, OvsDbIfaceConfig, OvsDpdkConfig, OvsInterface,
+    OvsPatchConfig, UnknownInterface,
 };
 
 use super::db::{parse_str_map, OvsDbConnection, OvsDbEntry};
 
-pub(crate) fn ovsdb_is_running() -> bool {
-    if let Ok(mut cli) = OvsDbConnection::new() {
+pub(crate) fn ovsdb_is_running(socket_path: Option<&str>) -> bool {
+    if let Ok(mut cli) = OvsDbConnection::new(socket_path) {
         cli.check_connection()
     } else {
         false
     }
 }
 
-pub(crate) fn ovsdb_retrieve() -> Result<NetworkState, NmstateError> {
+pub(crate) fn ovsdb_retrieve(
+    socket_path: Option<&str>,
+) -> Result<NetworkState, NmstateError> {
     let mut ret = NetworkState::new();
     ret.prop_list.push("interfaces");
     ret.prop_list.push("ovsdb");
-    let mut cli = OvsDbConnection::new()?;
+    let mut cli = OvsDbConnection::new(socket_path)?;
     let ovsdb_ifaces = cli.get_ovs_ifaces()?;
     let ovsdb_brs = cli.get_ovs_bridges()?;
     let ovsdb_ports = cli.get_ovs_ports()?;
@@ -41,6 +43,9 @@ pub(crate) fn ovsdb_retrieve() -> Result<NetworkState, NmstateError> {
         iface.base.prop_list.push("bridge");
         iface.base.prop_list.push("iface_type");
         iface.base.prop_list.push("state");
+        iface.base.prop_list.push("managed_by");
+        iface.base.managed_by =
+            Some(InterfaceManagedBy::new(InterfaceBackend::Ovsdb));
         let external_ids = HashMap::from_iter(
             ovsdb_br
                 .external_ids
@@ -382,6 +387,9 @@ fn ovsdb_iface_to_nmstate(
     };
     iface.base_iface_mut().name = ovsdb_iface.name.to_string();
     iface.base_iface_mut().prop_list.push("ovsdb");
+    iface.base_iface_mut().prop_list.push("managed_by");
+    iface.base_iface_mut().managed_by =
+        Some(InterfaceManagedBy::new(InterfaceBackend::Ovsdb));
 
     if let Some(ctrl) = port_to_ctrl.get(&iface.name()) {
         iface.base_iface_mut().controller = Some(ctrl.to_string());
@@ -413,10 +421,26 @@ fn ovsdb_iface_to_nmstate(
     Some(iface)
 }
 
+// The `mtu` column holds the MTU in effect, while `mtu_request` is what
+// NetworkManager asked for. DPDK NIC might refuse the requested MTU, we
+// report the effective one so that verification could catch it.
 fn get_dpdk_mtu(ovsdb_iface: &OvsDbEntry) -> Option<u64> {
-    if let Some(Value::Number(v)) = ovsdb_iface.options.get("mtu") {
+    let mtu = if let Some(Value::Number(v)) = ovsdb_iface.options.get("mtu") {
         v.as_u64()
     } else {
         None
+    };
+    if let Some(Value::Number(v)) = ovsdb_iface.options.get("mtu_request") {
+        if let Some(mtu_request) = v.as_u64() {
+            if mtu != Some(mtu_request) {
+                log::warn!(
+                    "OVS DPDK interface {} has mtu_request {mtu_request} \
+                    but effective MTU is {}",
+                    ovsdb_iface.name,
+                    mtu.map(|m| m.to_string()).unwrap_or_default()
+                );
+            }
+        }
     }
+    mtu
 }
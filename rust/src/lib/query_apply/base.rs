@@ -76,6 +76,9 @@ impl BaseInterface {
         if other.prop_list.contains(&"permanent_mac_address") {
             self.permanent_mac_address = other.permanent_mac_address.clone();
         }
+        if other.prop_list.contains(&"managed_by") {
+            self.managed_by = other.managed_by.clone();
+        }
         if other.prop_list.contains(&"controller") {
             self.controller = other.controller.clone();
         }
@@ -103,6 +106,9 @@ impl BaseInterface {
         if other.prop_list.contains(&"wait_ip") {
             self.wait_ip = other.wait_ip;
         }
+        if other.prop_list.contains(&"wait_ip_timeout") {
+            self.wait_ip_timeout = other.wait_ip_timeout;
+        }
 
         if other.prop_list.contains(&"ipv4") {
             if let Some(ref other_ipv4) = other.ipv4 {
@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use super::netlink::{
+    nl_apply_link, nl_iface_index, NlAttr, NlLinkMsg, IFLA_IFNAME,
+    IFLA_INFO_DATA, IFLA_INFO_KIND, IFLA_LINKINFO, IFLA_MASTER, NLM_F_CREATE,
+    NLM_F_EXCL, RTM_DELLINK, RTM_NEWLINK, RTM_SETLINK,
+};
+use crate::{
+    BatmanConfig, BatmanInterface, BatmanRoutingAlgorithm, Interface,
+    InterfaceState, MergedInterfaces, NmstateError,
+};
+
+const BATMAN_KIND: &str = "batadv";
+// Attribute in linux/if_link.h
+const IFLA_BATADV_ALGO_NAME: u16 = 1;
+
+impl BatmanInterface {
+    pub(crate) fn update_batman(&mut self, other: &BatmanInterface) {
+        if let Some(conf) = &mut self.batman {
+            conf.update(other.batman.as_ref());
+        } else {
+            self.batman = other.batman.clone();
+        }
+    }
+}
+
+impl BatmanConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.routing_algorithm.is_some() {
+                self.routing_algorithm = other.routing_algorithm;
+            }
+            if other.port.is_some() {
+                self.port = other.port.clone();
+            }
+        }
+    }
+}
+
+impl MergedInterfaces {
+    // Neither NetworkManager nor nispor could manage batman-adv devices,
+    // configure them via netlink instead. The configuration is not
+    // persistent.
+    pub(crate) fn apply_batman_config_via_kernel(
+        &self,
+    ) -> Result<(), NmstateError> {
+        for merged_iface in
+            self.kernel_ifaces.values().filter(|i| i.is_changed())
+        {
+            let apply_iface = match merged_iface.for_apply.as_ref() {
+                Some(Interface::Batman(i)) => i,
+                _ => continue,
+            };
+            let name = apply_iface.base.name.as_str();
+            let cur_iface = if let Some(Interface::Batman(cur_iface)) =
+                merged_iface.current.as_ref()
+            {
+                Some(cur_iface)
+            } else {
+                None
+            };
+            if apply_iface.base.state == InterfaceState::Absent {
+                if cur_iface.is_some() {
+                    let msg = NlLinkMsg::new(nl_iface_index(name)?);
+                    nl_apply_link(name, "delete link", RTM_DELLINK, 0, &msg)?;
+                }
+                continue;
+            }
+            let des_algo = apply_iface
+                .batman
+                .as_ref()
+                .and_then(|c| c.routing_algorithm);
+            let cur_algo = cur_iface
+                .and_then(|i| i.batman.as_ref())
+                .and_then(|c| c.routing_algorithm);
+
+            let cur_ports =
+                cur_iface.and_then(|i| i.ports()).unwrap_or_default();
+            let des_ports =
+                apply_iface.ports().unwrap_or_else(|| cur_ports.clone());
+
+            // Routing algorithm can only be set on creation
+            let recreate = cur_algo.is_some()
+                && des_algo.is_some()
+                && des_algo != cur_algo;
+            let attached_ports = if cur_iface.is_none() || recreate {
+                if recreate {
+                    log::info!(
+                        "Recreating batman-adv interface {name} for \
+                        changing routing algorithm"
+                    );
+                    let msg = NlLinkMsg::new(nl_iface_index(name)?);
+                    nl_apply_link(name, "delete link", RTM_DELLINK, 0, &msg)?;
+                }
+                nl_apply_link(
+                    name,
+                    "create link",
+                    RTM_NEWLINK,
+                    NLM_F_CREATE | NLM_F_EXCL,
+                    &gen_batman_create_msg(name, des_algo.unwrap_or_default()),
+                )?;
+                Vec::new()
+            } else {
+                cur_ports
+            };
+            let index = nl_iface_index(name)?;
+
+            for port in attached_ports.iter().filter(|p| !des_ports.contains(p))
+            {
+                let msg = gen_batman_port_msg(nl_iface_index(port)?, 0);
+                nl_apply_link(port, "unset controller", RTM_SETLINK, 0, &msg)?;
+            }
+            for port in des_ports.iter().filter(|p| !attached_ports.contains(p))
+            {
+                let msg = gen_batman_port_msg(nl_iface_index(port)?, index);
+                nl_apply_link(port, "set controller", RTM_SETLINK, 0, &msg)?;
+            }
+
+            let mut msg = NlLinkMsg::new(index);
+            if apply_iface.base.state == InterfaceState::Up {
+                msg.set_up(true);
+                nl_apply_link(name, "bring up", RTM_SETLINK, 0, &msg)?;
+            } else {
+                msg.set_up(false);
+                nl_apply_link(name, "bring down", RTM_SETLINK, 0, &msg)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Equivalent of `ip link add name <name> type batadv ra <algo>`
+pub(crate) fn gen_batman_create_msg(
+    iface_name: &str,
+    algo: BatmanRoutingAlgorithm,
+) -> NlLinkMsg {
+    let mut msg = NlLinkMsg::default();
+    msg.attrs.push(NlAttr::new_str(IFLA_IFNAME, iface_name));
+    msg.attrs.push(NlAttr::new_nested(
+        IFLA_LINKINFO,
+        vec![
+            NlAttr::new_str(IFLA_INFO_KIND, BATMAN_KIND),
+            NlAttr::new_nested(
+                IFLA_INFO_DATA,
+                vec![NlAttr::new_str(
+                    IFLA_BATADV_ALGO_NAME,
+                    algo.to_string().as_str(),
+                )],
+            ),
+        ],
+    ));
+    msg
+}
+
+// Equivalent of `ip link set dev <port> master <batman>`, zero `ctrl_index`
+// means `nomaster`.
+pub(crate) fn gen_batman_port_msg(
+    port_index: i32,
+    ctrl_index: i32,
+) -> NlLinkMsg {
+    let mut msg = NlLinkMsg::new(port_index);
+    msg.attrs
+        .push(NlAttr::new_u32(IFLA_MASTER, ctrl_index as u32));
+    msg
+}
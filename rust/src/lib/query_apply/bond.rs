@@ -1,8 +1,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BondConfig, BondInterface, BondOptions};
+use crate::{
+    ifaces::DEFAULT_ARP_MISSED_MAX, BondConfig, BondInterface, BondOptions,
+    ErrorKind, Interface, MergedInterfaces, NmstateError,
+};
+
+const KERNEL_OSRELEASE_PATH: &str = "/proc/sys/kernel/osrelease";
+const ARP_MISSED_MAX_MIN_KERNEL_VERSION: (u32, u32) = (5, 17);
+const NS_IP6_TARGET_MIN_KERNEL_VERSION: (u32, u32) = (6, 0);
 
 impl BondInterface {
+    pub(crate) fn sanitize_desired_for_verify(&mut self) {
+        if let Some(bond_conf) = self.bond.as_mut() {
+            bond_conf.active_port = None;
+        }
+    }
+
     pub(crate) fn update_bond(&mut self, other: &BondInterface) {
         if let Some(bond_conf) = &mut self.bond {
             bond_conf.update(other.bond.as_ref());
@@ -41,3 +54,84 @@ impl BondOptions {
         }
     }
 }
+
+impl MergedInterfaces {
+    // Fail early instead of waiting for verification failure when running
+    // kernel does not support desired bond options.
+    pub(crate) fn check_bond_kernel_support(&self) -> Result<(), NmstateError> {
+        let kernel_version = match get_kernel_version() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        for iface in self.kernel_ifaces.values().filter(|i| i.is_changed()) {
+            let bond_opts = if let Some(Interface::Bond(bond_iface)) =
+                iface.for_apply.as_ref()
+            {
+                if let Some(o) =
+                    bond_iface.bond.as_ref().and_then(|b| b.options.as_ref())
+                {
+                    o
+                } else {
+                    continue;
+                }
+            } else {
+                continue;
+            };
+            let mut checks = Vec::new();
+            if bond_opts
+                .arp_missed_max
+                .map(|v| v != DEFAULT_ARP_MISSED_MAX)
+                .unwrap_or_default()
+            {
+                checks.push((
+                    "arp_missed_max",
+                    ARP_MISSED_MAX_MIN_KERNEL_VERSION,
+                ));
+            }
+            if bond_opts
+                .ns_ip6_target
+                .as_ref()
+                .map(|v| !v.is_empty())
+                .unwrap_or_default()
+            {
+                checks
+                    .push(("ns_ip6_target", NS_IP6_TARGET_MIN_KERNEL_VERSION));
+            }
+            for (opt_name, min_version) in checks {
+                if kernel_version < min_version {
+                    let e = NmstateError::new(
+                        ErrorKind::NotSupportedError,
+                        format!(
+                            "Bond option {opt_name} of interface {} \
+                            requires kernel {}.{} or newer, but running \
+                            kernel is {}.{}",
+                            iface.merged.name(),
+                            min_version.0,
+                            min_version.1,
+                            kernel_version.0,
+                            kernel_version.1
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn get_kernel_version() -> Option<(u32, u32)> {
+    std::fs::read_to_string(KERNEL_OSRELEASE_PATH)
+        .ok()
+        .and_then(|v| parse_kernel_version(v.as_str()))
+}
+
+// Parse the major and minor version from kernel release string like
+// `6.2.9-300.fc38.x86_64`.
+pub(crate) fn parse_kernel_version(release: &str) -> Option<(u32, u32)> {
+    let mut items = release.trim().split(|c: char| !c.is_ascii_digit());
+    let major = items.next()?.parse::<u32>().ok()?;
+    let minor = items.next()?.parse::<u32>().ok()?;
+    Some((major, minor))
+}
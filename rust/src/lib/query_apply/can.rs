@@ -0,0 +1,223 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+
+use super::netlink::{
+    nl_apply_link, nl_attr_str, nl_attr_u32, nl_get_link, nl_iface_index,
+    parse_nl_attrs, NlAttr, NlLinkMsg, IFLA_INFO_DATA, IFLA_INFO_KIND,
+    IFLA_LINKINFO, RTM_NEWLINK, RTM_SETLINK,
+};
+use crate::{
+    CanConfig, CanInterface, Interface, InterfaceState, MergedInterfaces,
+    NmstateError,
+};
+
+const CAN_KIND: &str = "can";
+const CAN_BITTIMING_LEN: usize = 32;
+const CAN_CTRLMODE_FD: u32 = 0x20;
+// Attributes in linux/can/netlink.h
+const IFLA_CAN_BITTIMING: u16 = 1;
+const IFLA_CAN_CTRLMODE: u16 = 5;
+const IFLA_CAN_RESTART_MS: u16 = 6;
+const IFLA_CAN_DATA_BITTIMING: u16 = 9;
+
+impl CanInterface {
+    pub(crate) fn update_can(&mut self, other: &CanInterface) {
+        if let Some(conf) = &mut self.can {
+            conf.update(other.can.as_ref());
+        } else {
+            self.can = other.can.clone();
+        }
+    }
+}
+
+impl CanConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.bitrate.is_some() {
+                self.bitrate = other.bitrate;
+            }
+            if other.sample_point.is_some() {
+                self.sample_point = other.sample_point;
+            }
+            if other.restart_ms.is_some() {
+                self.restart_ms = other.restart_ms;
+            }
+            if other.fd.is_some() {
+                self.fd = other.fd;
+            }
+            if other.data_bitrate.is_some() {
+                self.data_bitrate = other.data_bitrate;
+            }
+        }
+    }
+
+    // Whether any property defined in desired differs from current.
+    fn is_changed(&self, current: Option<&Self>) -> bool {
+        let current = match current {
+            Some(c) => c,
+            None => return true,
+        };
+        (self.bitrate.is_some() && self.bitrate != current.bitrate)
+            || (self.sample_point.is_some()
+                && self.sample_point != current.sample_point)
+            || (self.restart_ms.is_some()
+                && self.restart_ms != current.restart_ms)
+            || (self.fd.is_some() && self.fd != current.fd)
+            || (self.data_bitrate.is_some()
+                && self.data_bitrate != current.data_bitrate)
+    }
+
+    // Equivalent of `ip link set dev <name> type can ...`
+    fn to_nl_info_data(&self) -> Vec<NlAttr> {
+        let mut ret = Vec::new();
+        if let Some(bitrate) = self.bitrate {
+            // Kernel calculates the bit timing from bitrate and sample
+            // point, zero sample point means the CiA recommended default.
+            ret.push(NlAttr::new_bytes(
+                IFLA_CAN_BITTIMING,
+                &gen_can_bittiming(bitrate, self.sample_point.unwrap_or(0)),
+            ));
+        }
+        if let Some(v) = self.restart_ms {
+            ret.push(NlAttr::new_u32(IFLA_CAN_RESTART_MS, v));
+        }
+        if let Some(fd) = self.fd {
+            let mut ctrl_mode = CAN_CTRLMODE_FD.to_ne_bytes().to_vec();
+            ctrl_mode.extend_from_slice(
+                &(if fd { CAN_CTRLMODE_FD } else { 0 }).to_ne_bytes(),
+            );
+            ret.push(NlAttr::new_bytes(IFLA_CAN_CTRLMODE, &ctrl_mode));
+            if fd {
+                if let Some(bitrate) = self.data_bitrate {
+                    ret.push(NlAttr::new_bytes(
+                        IFLA_CAN_DATA_BITTIMING,
+                        &gen_can_bittiming(bitrate, 0),
+                    ));
+                }
+            }
+        }
+        ret
+    }
+}
+
+impl MergedInterfaces {
+    // NetworkManager and nispor cannot manage CAN devices, configure them via
+    // netlink instead. The configuration is not persistent.
+    pub(crate) fn apply_can_config_via_kernel(
+        &self,
+    ) -> Result<(), NmstateError> {
+        for merged_iface in
+            self.kernel_ifaces.values().filter(|i| i.is_changed())
+        {
+            let apply_iface = match merged_iface.for_apply.as_ref() {
+                Some(Interface::Can(i)) => i,
+                _ => continue,
+            };
+            let name = apply_iface.base.name.as_str();
+            let index = nl_iface_index(name)?;
+            let cur_conf = if let Some(Interface::Can(cur_iface)) =
+                merged_iface.current.as_ref()
+            {
+                cur_iface.can.as_ref()
+            } else {
+                None
+            };
+            if let (Some(des_conf), Interface::Can(merged)) =
+                (apply_iface.can.as_ref(), &merged_iface.merged)
+            {
+                if des_conf.is_changed(cur_conf) {
+                    // Bit timing can only be changed when link is down
+                    let mut msg = NlLinkMsg::new(index);
+                    msg.set_up(false);
+                    nl_apply_link(name, "bring down", RTM_SETLINK, 0, &msg)?;
+                    let msg = gen_can_link_msg(
+                        index,
+                        merged.can.as_ref().unwrap_or(des_conf),
+                    );
+                    nl_apply_link(
+                        name,
+                        "set CAN config",
+                        RTM_NEWLINK,
+                        0,
+                        &msg,
+                    )?;
+                }
+            }
+            let mut msg = NlLinkMsg::new(index);
+            if apply_iface.base.state == InterfaceState::Up {
+                msg.set_up(true);
+                nl_apply_link(name, "bring up", RTM_SETLINK, 0, &msg)?;
+            } else {
+                msg.set_up(false);
+                nl_apply_link(name, "bring down", RTM_SETLINK, 0, &msg)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn gen_can_link_msg(index: i32, conf: &CanConfig) -> NlLinkMsg {
+    let mut msg = NlLinkMsg::new(index);
+    msg.attrs.push(NlAttr::new_nested(
+        IFLA_LINKINFO,
+        vec![
+            NlAttr::new_str(IFLA_INFO_KIND, CAN_KIND),
+            NlAttr::new_nested(IFLA_INFO_DATA, conf.to_nl_info_data()),
+        ],
+    ));
+    msg
+}
+
+// struct can_bittiming in linux/can/netlink.h with only bitrate and
+// sample point defined.
+fn gen_can_bittiming(bitrate: u32, sample_point: u16) -> Vec<u8> {
+    let mut ret = vec![0u8; CAN_BITTIMING_LEN];
+    ret[0..4].copy_from_slice(&bitrate.to_ne_bytes());
+    ret[4..8].copy_from_slice(&u32::from(sample_point).to_ne_bytes());
+    ret
+}
+
+// Nispor does not expose CAN properties yet, retrieve them via netlink.
+pub(crate) fn get_kernel_can_conf(iface_name: &str) -> Option<CanConfig> {
+    parse_can_link_attrs(nl_get_link(iface_name)?.as_slice())
+}
+
+pub(crate) fn parse_can_link_attrs(link_attrs: &[u8]) -> Option<CanConfig> {
+    let link_attrs = parse_nl_attrs(link_attrs);
+    let link_info = parse_nl_attrs(link_attrs.get(&IFLA_LINKINFO)?);
+    if link_info
+        .get(&IFLA_INFO_KIND)
+        .and_then(|k| nl_attr_str(k))
+        .as_deref()
+        != Some(CAN_KIND)
+    {
+        return None;
+    }
+    let info_data = parse_nl_attrs(link_info.get(&IFLA_INFO_DATA)?);
+    let mut ret = CanConfig::new();
+    if let Some(bt) = info_data.get(&IFLA_CAN_BITTIMING) {
+        ret.bitrate = nl_attr_u32(bt);
+        ret.sample_point = bt
+            .get(4..)
+            .and_then(nl_attr_u32)
+            .and_then(|v| u16::try_from(v).ok());
+    }
+    ret.restart_ms = info_data
+        .get(&IFLA_CAN_RESTART_MS)
+        .and_then(|v| nl_attr_u32(v));
+    // struct can_ctrlmode holds mask and flags
+    let fd = info_data
+        .get(&IFLA_CAN_CTRLMODE)
+        .and_then(|v| v.get(4..))
+        .and_then(nl_attr_u32)
+        .map(|flags| flags & CAN_CTRLMODE_FD > 0)
+        .unwrap_or_default();
+    ret.fd = Some(fd);
+    if fd {
+        ret.data_bitrate = info_data
+            .get(&IFLA_CAN_DATA_BITTIMING)
+            .and_then(|v| nl_attr_u32(v));
+    }
+    Some(ret)
+}
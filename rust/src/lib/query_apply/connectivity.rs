@@ -0,0 +1,48 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ConnectivityCheckConfig, ErrorKind, MergedConnectivityCheckConfig,
+    NmstateError,
+};
+
+impl MergedConnectivityCheckConfig {
+    pub(crate) fn verify(
+        &self,
+        current: Option<&ConnectivityCheckConfig>,
+    ) -> Result<(), NmstateError> {
+        let desired = if let Some(d) = self.desired.as_ref() {
+            d
+        } else {
+            return Ok(());
+        };
+        let current = current.cloned().unwrap_or_default();
+
+        if desired.enabled.is_some() && desired.enabled != current.enabled {
+            let e = NmstateError::new(
+                ErrorKind::VerificationError,
+                format!(
+                    "Verification fail, desire connectivity-check.enabled: \
+                    {:?}, current: {:?}",
+                    desired.enabled, current.enabled
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        if let Some(uri) = desired.uri.as_deref() {
+            if uri != current.uri.as_deref().unwrap_or_default() {
+                let e = NmstateError::new(
+                    ErrorKind::VerificationError,
+                    format!(
+                        "Verification fail, desire connectivity-check.uri: \
+                        {uri}, current: {:?}",
+                        current.uri
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    iface_plugin::IfacePluginHolder, state::merge_json_value, BaseInterface,
+    CustomInterface, Interface, InterfaceState, InterfaceType, Interfaces,
+    MergedNetworkState, NetworkState, NmstateError, NmstateInterfacePlugin,
+};
+
+impl CustomInterface {
+    pub(crate) fn update_custom(&mut self, other: &CustomInterface) {
+        if let Some(other_conf) = other.config.as_ref() {
+            let mut new_conf = other_conf.clone();
+            if let Some(conf) = self.config.as_ref() {
+                merge_json_value(&mut new_conf, conf);
+            }
+            self.config = Some(new_conf);
+        }
+    }
+}
+
+impl NetworkState {
+    pub(crate) fn retrieve_plugin_ifaces(
+        &mut self,
+    ) -> Result<(), NmstateError> {
+        for plugin in self.iface_plugins.clone().iter() {
+            for mut iface in plugin.0.retrieve()? {
+                iface.base.iface_type =
+                    InterfaceType::Other(plugin.0.iface_type().to_string());
+                iface.config_section = plugin.0.config_section().to_string();
+                // Backends report the kernel device as unknown interface,
+                // include its kernel properties like IP and controller.
+                if let Some(Interface::Unknown(exist_iface)) =
+                    self.interfaces.remove_unknown_iface(&iface.base.name)
+                {
+                    iface.base.include_kernel_props(exist_iface.base);
+                }
+                self.interfaces.push(Interface::Custom(iface));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BaseInterface {
+    // Only fill properties not reported by plugin.
+    fn include_kernel_props(&mut self, kernel_base: BaseInterface) {
+        if self.mac_address.is_none() {
+            self.mac_address = kernel_base.mac_address;
+        }
+        if self.mtu.is_none() {
+            self.mtu = kernel_base.mtu;
+        }
+        if self.ipv4.is_none() {
+            self.ipv4 = kernel_base.ipv4;
+        }
+        if self.ipv6.is_none() {
+            self.ipv6 = kernel_base.ipv6;
+        }
+        if self.controller.is_none() {
+            self.controller = kernel_base.controller;
+            self.controller_type = kernel_base.controller_type;
+        }
+    }
+}
+
+impl Interfaces {
+    fn remove_unknown_iface(&mut self, iface_name: &str) -> Option<Interface> {
+        let iface = if let Some(Interface::Unknown(_)) =
+            self.kernel_ifaces.get(iface_name)
+        {
+            self.kernel_ifaces.remove(iface_name)
+        } else {
+            let key = self
+                .user_ifaces
+                .iter()
+                .find(|(_, i)| {
+                    i.name() == iface_name && matches!(i, Interface::Unknown(_))
+                })
+                .map(|(k, _)| k.clone())?;
+            self.user_ifaces.remove(&key)
+        };
+        self.insert_order.retain(|(n, _)| n != iface_name);
+        iface
+    }
+}
+
+impl MergedNetworkState {
+    // Interfaces handled by plugins are untouched by NetworkManager and
+    // kernel backends. Create or modify the ones without controller before
+    // backends, so they exist when backends create their children.
+    pub(crate) fn pre_apply_plugin_ifaces(&self) -> Result<(), NmstateError> {
+        self.apply_plugin_ifaces(false)
+    }
+
+    // The controller of plugin interfaces might be created by backends,
+    // hence attach them after backends. Deletion is also done afterwards
+    // as backends remove their children first.
+    pub(crate) fn post_apply_plugin_ifaces(&self) -> Result<(), NmstateError> {
+        self.apply_plugin_ifaces(true)
+    }
+
+    fn apply_plugin_ifaces(
+        &self,
+        after_backend: bool,
+    ) -> Result<(), NmstateError> {
+        for merged_iface in self
+            .interfaces
+            .kernel_ifaces
+            .values()
+            .filter(|i| i.is_changed())
+        {
+            let apply_iface = match merged_iface.for_apply.as_ref() {
+                Some(Interface::Custom(i)) => i,
+                _ => continue,
+            };
+            let plugin = match find_plugin(
+                self.iface_plugins.as_slice(),
+                &apply_iface.base.iface_type,
+            ) {
+                Some(p) => p,
+                None => continue,
+            };
+            let cur_iface = if let Some(Interface::Custom(i)) =
+                merged_iface.current.as_ref()
+            {
+                Some(i)
+            } else {
+                None
+            };
+            if apply_iface.base.state == InterfaceState::Absent {
+                if after_backend && cur_iface.is_some() {
+                    log::info!(
+                        "Deleting {} interface {} via plugin",
+                        apply_iface.base.iface_type,
+                        apply_iface.base.name
+                    );
+                    plugin.delete(apply_iface.base.name.as_str())?;
+                }
+            } else if let Interface::Custom(merged) = &merged_iface.merged {
+                let has_ctrl = merged
+                    .base
+                    .controller
+                    .as_deref()
+                    .map(|c| !c.is_empty())
+                    .unwrap_or_default();
+                if has_ctrl != after_backend {
+                    continue;
+                }
+                log::info!(
+                    "Applying {} interface {} via plugin",
+                    merged.base.iface_type,
+                    merged.base.name
+                );
+                plugin.apply(merged, cur_iface)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Interfaces of plugins retrieved before applying, used to rollback plugins
+// when NetworkManager checkpoint is rolled back.
+pub(crate) struct PluginIfacesBackup {
+    ifaces: Vec<(IfacePluginHolder, Vec<CustomInterface>)>,
+}
+
+impl PluginIfacesBackup {
+    pub(crate) fn new(
+        plugins: &[IfacePluginHolder],
+    ) -> Result<Self, NmstateError> {
+        let mut ifaces = Vec::new();
+        for plugin in plugins {
+            ifaces.push((plugin.clone(), plugin.0.retrieve()?));
+        }
+        Ok(Self { ifaces })
+    }
+
+    // Errors are only logged as the failure of apply is more important to
+    // report.
+    pub(crate) fn restore(&self) {
+        for (plugin, previous) in self.ifaces.iter() {
+            log::info!(
+                "Rolling back {} interfaces via plugin",
+                plugin.0.iface_type()
+            );
+            if let Err(e) = plugin.0.rollback(previous.as_slice()) {
+                log::warn!(
+                    "Failed to rollback {} interfaces: {e}",
+                    plugin.0.iface_type()
+                );
+            }
+        }
+    }
+}
+
+fn find_plugin<'a>(
+    plugins: &'a [IfacePluginHolder],
+    iface_type: &InterfaceType,
+) -> Option<&'a dyn NmstateInterfacePlugin> {
+    plugins
+        .iter()
+        .find(|p| iface_type == &InterfaceType::Other(p.0.iface_type().into()))
+        .map(|p| p.0.as_ref())
+}
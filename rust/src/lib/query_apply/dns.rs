@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{DnsState, ErrorKind, MergedDnsState, NmstateError};
+use crate::{
+    ip::is_ipv6_addr, DnsState, DnsVerifyOrderMode, ErrorKind, MergedDnsState,
+    NmstateError,
+};
 
 impl MergedDnsState {
     pub(crate) fn verify(
@@ -35,8 +38,11 @@ impl MergedDnsState {
             ));
         };
 
-        if cur_srvs != self.servers
-            && !(cur_conf.server.is_none() && self.servers.is_empty())
+        if !(is_dns_srvs_equal(
+            self.servers.as_slice(),
+            cur_srvs.as_slice(),
+            self.verify_order_mode,
+        ) || (cur_conf.server.is_none() && self.servers.is_empty()))
         {
             return Err(NmstateError::new(
                 ErrorKind::VerificationError,
@@ -89,3 +95,30 @@ impl MergedDnsState {
         Ok(())
     }
 }
+
+fn is_dns_srvs_equal(
+    desired: &[String],
+    current: &[String],
+    mode: DnsVerifyOrderMode,
+) -> bool {
+    match mode {
+        DnsVerifyOrderMode::Strict => desired == current,
+        DnsVerifyOrderMode::PerFamily => {
+            split_dns_srvs_by_family(desired)
+                == split_dns_srvs_by_family(current)
+        }
+        DnsVerifyOrderMode::Ignore => {
+            let mut desired = desired.to_vec();
+            let mut current = current.to_vec();
+            desired.sort_unstable();
+            current.sort_unstable();
+            desired == current
+        }
+    }
+}
+
+// Return IPv4 and IPv6 name servers with their order preserved.
+fn split_dns_srvs_by_family(srvs: &[String]) -> (Vec<&String>, Vec<&String>) {
+    let (ipv6, ipv4) = srvs.iter().partition(|s| is_ipv6_addr(s));
+    (ipv4, ipv6)
+}
@@ -6,7 +6,73 @@ use crate::{
     VethConfig,
 };
 
+const SYSFS_NET_PATH: &str = "/sys/class/net";
+
 impl EthernetInterface {
+    // When kernel does not report carrier on this interface, return the link
+    // diagnostics gathered from sysfs, ethtool and the ethernet information of
+    // `self`, for example:
+    //  eth1 has no carrier(link detected: no, operstate: down, driver: e1000e,
+    //  SFP module: absent, auto-negotiation: true, speed: unknown,
+    //  duplex: unknown)
+    pub(crate) fn link_down_diagnostics(&self) -> Option<String> {
+        let carrier = read_sysfs_net(self.base.name.as_str(), "carrier");
+        if carrier.as_deref() == Some("1") {
+            return None;
+        }
+        let driver = std::fs::read_link(format!(
+            "{SYSFS_NET_PATH}/{}/device/driver",
+            self.base.name
+        ))
+        .ok()
+        .and_then(|p| p.file_name().map(|f| f.to_string_lossy().to_string()));
+        let operstate = read_sysfs_net(self.base.name.as_str(), "operstate");
+        let sfp_module = get_sfp_module_state(self.base.name.as_str());
+        Some(self.gen_link_down_diagnostics(
+            carrier.as_deref(),
+            operstate.as_deref(),
+            driver.as_deref(),
+            sfp_module,
+        ))
+    }
+
+    pub(crate) fn gen_link_down_diagnostics(
+        &self,
+        carrier: Option<&str>,
+        operstate: Option<&str>,
+        driver: Option<&str>,
+        sfp_module: SfpModuleState,
+    ) -> String {
+        let eth_conf = self.ethernet.as_ref();
+        format!(
+            "{}(link detected: {}, operstate: {}, driver: {}, SFP module: {}, \
+            auto-negotiation: {}, speed: {}, duplex: {})",
+            match carrier {
+                Some("0") => format!("{} has no carrier", self.base.name),
+                _ => format!("{} carrier state unknown", self.base.name),
+            },
+            match carrier {
+                Some("0") => "no",
+                _ => "unknown",
+            },
+            operstate.unwrap_or("unknown"),
+            driver.unwrap_or("unknown"),
+            sfp_module,
+            eth_conf
+                .and_then(|e| e.auto_neg)
+                .map(|a| a.to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+            eth_conf
+                .and_then(|e| e.speed)
+                .map(|s| format!("{s}Mb/s"))
+                .unwrap_or_else(|| "unknown".to_string()),
+            eth_conf
+                .and_then(|e| e.duplex)
+                .map(|d| d.to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+        )
+    }
+
     pub(crate) fn sanitize_desired_for_verify(&mut self) {
         if let Some(sriov_conf) =
             self.ethernet.as_mut().and_then(|e| e.sr_iov.as_mut())
@@ -131,6 +197,10 @@ impl NetworkState {
         false
     }
 
+    // Besides ethernet interfaces, the ports of controller(e.g. bond over
+    // VFs) not found in desired and current state might be SR-IOV VFs
+    // pending creation, PF should be configured before controller
+    // activation.
     fn has_missing_eth(&self, current: &Self) -> bool {
         self.interfaces
             .kernel_ifaces
@@ -141,6 +211,17 @@ impl NetworkState {
                         || i.iface_type() == InterfaceType::Unknown)
             })
             .any(|i| !current.interfaces.kernel_ifaces.contains_key(i.name()))
+            || self
+                .interfaces
+                .kernel_ifaces
+                .values()
+                .chain(self.interfaces.user_ifaces.values())
+                .filter(|i| i.is_up() && i.is_controller())
+                .flat_map(|i| i.ports().unwrap_or_default())
+                .any(|port| {
+                    !self.interfaces.kernel_ifaces.contains_key(port)
+                        && !current.interfaces.kernel_ifaces.contains_key(port)
+                })
     }
 
     // Return newly create NetworkState containing only ethernet section of
@@ -203,3 +284,122 @@ impl MergedInterfaces {
         ret
     }
 }
+
+// Return `None` if file not exist or not readable, for example, the `carrier`
+// file cannot be read when interface is administratively down.
+fn read_sysfs_net(iface_name: &str, file: &str) -> Option<String> {
+    std::fs::read_to_string(format!("{SYSFS_NET_PATH}/{iface_name}/{file}"))
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SfpModuleState {
+    Present,
+    Absent,
+    // Driver does not support plug-in module, e.g. copper NIC or virtual NIC
+    NotSupported,
+    Unknown,
+}
+
+impl std::fmt::Display for SfpModuleState {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Present => "present",
+                Self::Absent => "absent",
+                Self::NotSupported => "not supported",
+                Self::Unknown => "unknown",
+            }
+        )
+    }
+}
+
+const SIOCETHTOOL: u64 = 0x8946;
+const ETHTOOL_GMODULEINFO: u32 = 0x42;
+const IFNAMSIZ: usize = 16;
+
+// struct ethtool_modinfo in linux/ethtool.h
+#[repr(C)]
+#[derive(Default)]
+struct EthtoolModInfo {
+    cmd: u32,
+    mod_type: u32,
+    eeprom_len: u32,
+    reserved: [u32; 8],
+}
+
+// struct ifreq in linux/if.h with `ifr_data` as the union member, padded to
+// the size of the union.
+#[repr(C)]
+struct IfReqData {
+    ifr_name: [u8; IFNAMSIZ],
+    ifr_data: *mut EthtoolModInfo,
+    _pad: [u8; 24 - std::mem::size_of::<usize>()],
+}
+
+// Use ETHTOOL_GMODULEINFO to check whether plug-in module(e.g. SFP) is
+// inserted. Drivers without plug-in module support reply EOPNOTSUPP, while
+// drivers with empty cage reply ENODEV or EIO.
+fn get_sfp_module_state(iface_name: &str) -> SfpModuleState {
+    use nix::libc;
+
+    // ENODEV is also the reply for non-existing interface
+    if iface_name.is_empty()
+        || iface_name.len() >= IFNAMSIZ
+        || !std::path::Path::new(&format!("{SYSFS_NET_PATH}/{iface_name}"))
+            .exists()
+    {
+        return SfpModuleState::Unknown;
+    }
+    let mut modinfo = EthtoolModInfo {
+        cmd: ETHTOOL_GMODULEINFO,
+        ..Default::default()
+    };
+    let mut ifr = IfReqData {
+        ifr_name: [0u8; IFNAMSIZ],
+        ifr_data: &mut modinfo,
+        _pad: [0u8; 24 - std::mem::size_of::<usize>()],
+    };
+    ifr.ifr_name[..iface_name.len()].copy_from_slice(iface_name.as_bytes());
+
+    let fd = unsafe {
+        libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0)
+    };
+    if fd < 0 {
+        log::debug!(
+            "Failed to open socket for ethtool query of {iface_name}: {}",
+            nix::errno::Errno::last()
+        );
+        return SfpModuleState::Unknown;
+    }
+    let rc = unsafe {
+        libc::ioctl(fd, SIOCETHTOOL as _, &mut ifr as *mut IfReqData)
+    };
+    let errno = nix::errno::Errno::last();
+    unsafe {
+        libc::close(fd);
+    }
+    if rc == 0 {
+        if modinfo.eeprom_len > 0 {
+            SfpModuleState::Present
+        } else {
+            SfpModuleState::Absent
+        }
+    } else {
+        match errno {
+            nix::errno::Errno::EOPNOTSUPP => SfpModuleState::NotSupported,
+            nix::errno::Errno::ENODEV | nix::errno::Errno::EIO => {
+                SfpModuleState::Absent
+            }
+            e => {
+                log::debug!(
+                    "Failed to query plug-in module of {iface_name}: {e}"
+                );
+                SfpModuleState::Unknown
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{GreConfig, GreInterface, GretapInterface};
+
+impl GreInterface {
+    pub(crate) fn update_gre(&mut self, other: &GreInterface) {
+        if let Some(conf) = &mut self.gre {
+            conf.update(other.gre.as_ref());
+        } else {
+            self.gre = other.gre.clone();
+        }
+    }
+}
+
+impl GretapInterface {
+    pub(crate) fn update_gretap(&mut self, other: &GretapInterface) {
+        if let Some(conf) = &mut self.gretap {
+            conf.update(other.gretap.as_ref());
+        } else {
+            self.gretap = other.gretap.clone();
+        }
+    }
+}
+
+impl GreConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.base_iface.is_some() {
+                self.base_iface = other.base_iface.clone();
+            }
+            if other.local.is_some() {
+                self.local = other.local;
+            }
+            if other.remote.is_some() {
+                self.remote = other.remote;
+            }
+            if other.ikey.is_some() {
+                self.ikey = other.ikey;
+            }
+            if other.okey.is_some() {
+                self.okey = other.okey;
+            }
+            if other.ttl.is_some() {
+                self.ttl = other.ttl;
+            }
+            if other.tos.is_some() {
+                self.tos = other.tos;
+            }
+        }
+    }
+}
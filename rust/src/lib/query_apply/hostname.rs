@@ -1,6 +1,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{ErrorKind, HostNameState, MergedHostNameState, NmstateError};
+use super::net_state::{
+    with_retry, VERIFY_RETRY_COUNT_DEFAULT, VERIFY_RETRY_INTERVAL_MILLISECONDS,
+};
+use crate::{
+    hostnamed::hostnamed_set_static_hostname,
+    nispor::{get_hostname_state, set_running_hostname},
+    nm::nm_hostname_set,
+    ErrorKind, HostNameState, MergedHostNameState, NetworkState, NmstateError,
+};
 
 impl HostNameState {
     pub(crate) fn update(&mut self, other: &Self) {
@@ -13,6 +21,129 @@ impl HostNameState {
     }
 }
 
+impl HostNameState {
+    // The running(transient) hostname takes precedence when both defined.
+    // When only static hostname is desired, the running hostname follows it
+    // like systemd-hostnamed does.
+    // Static hostname is ignored in memory only mode.
+    pub(crate) fn gen_effective(&self, memory_only: bool) -> Self {
+        let config = if memory_only {
+            if self.config.is_some() {
+                log::warn!(
+                    "Ignoring static hostname in memory only mode, only \
+                    running hostname is changed"
+                );
+            }
+            None
+        } else {
+            self.config.clone()
+        };
+        Self {
+            running: self
+                .running
+                .clone()
+                .or_else(|| self.config.clone().filter(|c| !c.is_empty())),
+            config,
+        }
+    }
+}
+
+impl NetworkState {
+    // Whether desired state only contains hostname config
+    pub(crate) fn is_hostname_only(&self) -> bool {
+        self.hostname.is_some()
+            && self.dns.is_empty()
+            && self.rules.is_empty()
+            && self.routes.is_empty()
+            && self.interfaces.is_empty()
+            && self.ovsdb.is_none()
+            && self.ovn.is_none()
+            && self.connectivity_check.is_none()
+    }
+
+    /// Apply the `hostname` section of `NetworkState` only, other sections
+    /// are ignored. Static hostname is set via NetworkManager or via
+    /// systemd-hostnamed when NetworkManager is not available or in
+    /// `kernel only` mode. Running hostname is set via kernel, and follows
+    /// static hostname when not defined. No checkpoint is involved, the
+    /// original hostname is restored on failure instead.
+    /// The [NetworkState::apply()] also use this for state only containing
+    /// hostname.
+    /// Only available for feature `query_apply`.
+    pub fn apply_hostname(&self) -> Result<(), NmstateError> {
+        let _lock = self.acquire_apply_lock()?;
+        self.apply_hostname_only()
+    }
+
+    pub(crate) fn apply_hostname_only(&self) -> Result<(), NmstateError> {
+        let desired = match self.hostname.as_ref() {
+            Some(h) => h.gen_effective(self.memory_only),
+            None => return Ok(()),
+        };
+        let original = get_hostname_state();
+        if let Err(e) = self.set_hostname(&desired) {
+            if let Some(original) = original {
+                self.restore_hostname(&desired, &original);
+            }
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    fn set_static_hostname(&self, hostname: &str) -> Result<(), NmstateError> {
+        let nm_dbus_address = self.nm_dbus_address.as_deref();
+        if self.kernel_only || !nm_hostname_set(nm_dbus_address, hostname)? {
+            hostnamed_set_static_hostname(nm_dbus_address, hostname)?;
+        }
+        Ok(())
+    }
+
+    // Only restore the hostname changed by desired state, errors are only
+    // logged as the failure of apply is more important to report.
+    fn restore_hostname(
+        &self,
+        desired: &HostNameState,
+        original: &HostNameState,
+    ) {
+        log::info!("Restoring original hostname {original:?}");
+        if desired.config.is_some() {
+            if let Err(e) = self.set_static_hostname(
+                original.config.as_deref().unwrap_or_default(),
+            ) {
+                log::warn!("Failed to restore static hostname: {e}");
+            }
+        }
+        if desired.running.is_some() {
+            if let Some(running) = original.running.as_deref() {
+                if let Err(e) = set_running_hostname(running) {
+                    log::warn!("Failed to restore running hostname: {e}");
+                }
+            }
+        }
+    }
+
+    fn set_hostname(
+        &self,
+        desired: &HostNameState,
+    ) -> Result<(), NmstateError> {
+        if let Some(config) = desired.config.as_deref() {
+            self.set_static_hostname(config)?;
+        }
+        if let Some(running) = desired.running.as_deref() {
+            set_running_hostname(running)?;
+        }
+        if !self.no_verify {
+            let merged = MergedHostNameState::new(Some(desired.clone()), None);
+            with_retry(
+                VERIFY_RETRY_INTERVAL_MILLISECONDS,
+                VERIFY_RETRY_COUNT_DEFAULT,
+                || merged.verify(get_hostname_state().as_ref()),
+            )?;
+        }
+        Ok(())
+    }
+}
+
 impl MergedHostNameState {
     pub(crate) fn verify(
         &self,
@@ -20,17 +20,19 @@ impl Interface {
     // This function will clean up desired state before verification
     pub(crate) fn sanitize_desired_for_verify(&mut self) {
         self.base_iface_mut().sanitize_desired_for_verify();
-        if let Interface::Ethernet(iface) = self {
-            iface.sanitize_desired_for_verify();
+        match self {
+            Interface::Ethernet(iface) => iface.sanitize_desired_for_verify(),
+            Interface::Bond(iface) => iface.sanitize_desired_for_verify(),
+            _ => (),
         }
     }
 
     pub(crate) fn verify(&self, current: &Self) -> Result<(), NmstateError> {
-        let mut current = current.clone();
-        self.process_allow_extra_address(&mut current);
+        let mut cur_iface = current.clone();
+        self.process_allow_extra_address(&mut cur_iface);
 
         let self_value = serde_json::to_value(self)?;
-        let current_value = serde_json::to_value(&current)?;
+        let current_value = serde_json::to_value(&cur_iface)?;
 
         if let Some((reference, desire, current)) = get_json_value_difference(
             format!("{}.interface", self.name()),
@@ -68,16 +70,49 @@ impl Interface {
 
             Err(NmstateError::new(
                 ErrorKind::VerificationError,
-                format!(
-                    "Verification failure: {reference} desire '{desire}', \
-                    current '{current}'"
-                ),
+                self.gen_verify_error_msg(&cur_iface, reference.as_str())?,
             ))
         } else {
             Ok(())
         }
     }
 
+    // The secrets are compared using real value, but never included in
+    // the error message.
+    fn gen_verify_error_msg(
+        &self,
+        current: &Self,
+        reference: &str,
+    ) -> Result<String, NmstateError> {
+        let mut self_hidden = self.clone();
+        self_hidden.hide_secrets();
+        let mut current_hidden = current.clone();
+        current_hidden.hide_secrets();
+        let self_value = serde_json::to_value(&self_hidden)?;
+        let current_value = serde_json::to_value(&current_hidden)?;
+
+        Ok(
+            match get_json_value_difference(
+                format!("{}.interface", self.name()),
+                &self_value,
+                &current_value,
+            ) {
+                Some((hidden_reference, desire, current))
+                    if hidden_reference == reference =>
+                {
+                    format!(
+                        "Verification failure: {reference} desire '{desire}', \
+                    current '{current}'"
+                    )
+                }
+                _ => format!(
+                    "Verification failure: {reference} secret differs between \
+                desire and current"
+                ),
+            },
+        )
+    }
+
     pub fn update(&mut self, other: &Interface) {
         self.base_iface_mut().update(other.base_iface());
         if let Self::Unknown(_) = other {
@@ -228,13 +263,134 @@ impl Interface {
                     );
                 }
             }
+            Self::Gre(iface) => {
+                if let Self::Gre(other_iface) = other {
+                    iface.update_gre(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Gretap(iface) => {
+                if let Self::Gretap(other_iface) = other {
+                    iface.update_gretap(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Ip6Gre(iface) => {
+                if let Self::Ip6Gre(other_iface) = other {
+                    iface.update_ip6gre(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Ip6Gretap(iface) => {
+                if let Self::Ip6Gretap(other_iface) = other {
+                    iface.update_ip6gretap(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Ip6Tnl(iface) => {
+                if let Self::Ip6Tnl(other_iface) = other {
+                    iface.update_ip6tnl(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Team(iface) => {
+                if let Self::Team(other_iface) = other {
+                    iface.update_team(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::IpVlan(iface) => {
+                if let Self::IpVlan(other_iface) = other {
+                    iface.update_ipvlan(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Can(iface) => {
+                if let Self::Can(other_iface) = other {
+                    iface.update_can(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Modem(iface) => {
+                if let Self::Modem(other_iface) = other {
+                    iface.update_modem(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Batman(iface) => {
+                if let Self::Batman(other_iface) = other {
+                    iface.update_batman(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
+            Self::Custom(iface) => {
+                if let Self::Custom(other_iface) = other {
+                    iface.update_custom(other_iface);
+                } else {
+                    log::warn!(
+                        "Don't know how to update iface {:?} with {:?}",
+                        iface,
+                        other
+                    );
+                }
+            }
             _ => (),
         }
     }
 }
 
 impl InterfaceType {
-    pub(crate) const SUPPORTED_LIST: [InterfaceType; 16] = [
+    pub(crate) const SUPPORTED_LIST: [InterfaceType; 26] = [
         InterfaceType::Bond,
         InterfaceType::LinuxBridge,
         InterfaceType::Dummy,
@@ -251,5 +407,15 @@ impl InterfaceType {
         InterfaceType::MacSec,
         InterfaceType::Vrf,
         InterfaceType::Ipsec,
+        InterfaceType::Gre,
+        InterfaceType::Gretap,
+        InterfaceType::Ip6Gre,
+        InterfaceType::Ip6Gretap,
+        InterfaceType::Ip6Tnl,
+        InterfaceType::IpVlan,
+        InterfaceType::Can,
+        InterfaceType::Modem,
+        InterfaceType::Batman,
+        InterfaceType::Ifb,
     ];
 }
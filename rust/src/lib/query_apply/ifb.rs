@@ -0,0 +1,98 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::convert::TryFrom;
+
+use super::netlink::{
+    nl_apply_link, nl_iface_index, NlAttr, NlLinkMsg, IFLA_ADDRESS,
+    IFLA_IFNAME, IFLA_INFO_KIND, IFLA_LINKINFO, IFLA_MTU, NLM_F_CREATE,
+    NLM_F_EXCL, RTM_DELLINK, RTM_NEWLINK, RTM_SETLINK,
+};
+use crate::{
+    ErrorKind, IfbInterface, Interface, InterfaceState, MergedInterfaces,
+    NmstateError,
+};
+
+const IFB_KIND: &str = "ifb";
+
+impl MergedInterfaces {
+    // Neither NetworkManager nor nispor could manage IFB devices, configure
+    // them via netlink instead. The configuration is not persistent.
+    pub(crate) fn apply_ifb_config_via_kernel(
+        &self,
+    ) -> Result<(), NmstateError> {
+        for merged_iface in
+            self.kernel_ifaces.values().filter(|i| i.is_changed())
+        {
+            let apply_iface = match merged_iface.for_apply.as_ref() {
+                Some(Interface::Ifb(i)) => i,
+                _ => continue,
+            };
+            let name = apply_iface.base.name.as_str();
+            let cur_exist = matches!(
+                merged_iface.current.as_ref(),
+                Some(Interface::Ifb(_))
+            );
+            if apply_iface.base.state == InterfaceState::Absent {
+                if cur_exist {
+                    let msg = NlLinkMsg::new(nl_iface_index(name)?);
+                    nl_apply_link(name, "delete link", RTM_DELLINK, 0, &msg)?;
+                }
+                continue;
+            }
+            if cur_exist {
+                let msg = gen_ifb_link_msg(nl_iface_index(name)?, apply_iface)?;
+                nl_apply_link(name, "set link", RTM_SETLINK, 0, &msg)?;
+            } else {
+                let msg = gen_ifb_link_msg(0, apply_iface)?;
+                nl_apply_link(
+                    name,
+                    "create link",
+                    RTM_NEWLINK,
+                    NLM_F_CREATE | NLM_F_EXCL,
+                    &msg,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Equivalent of `ip link set dev <name> mtu <mtu> address <mac> up`, zero
+// `index` means `ip link add name <name> type ifb mtu <mtu> ...` instead.
+pub(crate) fn gen_ifb_link_msg(
+    index: i32,
+    iface: &IfbInterface,
+) -> Result<NlLinkMsg, NmstateError> {
+    let mut msg = NlLinkMsg::new(index);
+    if index == 0 {
+        msg.attrs
+            .push(NlAttr::new_str(IFLA_IFNAME, iface.base.name.as_str()));
+        msg.attrs.push(NlAttr::new_nested(
+            IFLA_LINKINFO,
+            vec![NlAttr::new_str(IFLA_INFO_KIND, IFB_KIND)],
+        ));
+    }
+    if let Some(mtu) = iface.base.mtu {
+        let mtu = match u32::try_from(mtu) {
+            Ok(m) => m,
+            Err(_) => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Invalid MTU {mtu} of IFB interface {}",
+                        iface.base.name
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        msg.attrs.push(NlAttr::new_u32(IFLA_MTU, mtu));
+    }
+    if let Some(mac) = iface.base.mac_address.as_ref() {
+        msg.attrs
+            .push(NlAttr::new_bytes(IFLA_ADDRESS, mac.to_bytes().as_slice()));
+    }
+    msg.set_up(iface.base.state == InterfaceState::Up);
+    Ok(msg)
+}
@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
-    NmstateError,
+    ip::warn_wait_ip_missing_family, ErrorKind, Interface, InterfaceType,
+    Interfaces, MergedInterfaces, NmstateError,
 };
 
 impl Interfaces {
@@ -131,6 +131,52 @@ fn verify_desire_absent_but_found_in_current(
     }
 }
 
+// Include link diagnostics in the verification error if the failure might be
+// caused by ethernet interface having no carrier.
+fn append_link_down_diagnostics(
+    e: NmstateError,
+    cur_iface: &Interface,
+) -> NmstateError {
+    if e.kind() != ErrorKind::VerificationError {
+        return e;
+    }
+    if let Interface::Ethernet(eth_iface) = cur_iface {
+        if let Some(diag) = eth_iface.link_down_diagnostics() {
+            log::error!("{}", diag);
+            return NmstateError::new(e.kind(), format!("{}, {diag}", e.msg()));
+        }
+    }
+    e
+}
+
+// Retrying cannot fix IPv4 address conflict, hence raise non-retryable error
+// to fail fast.
+fn append_ipv4_address_conflict_diagnostics(
+    e: NmstateError,
+    cur_iface: &Interface,
+) -> NmstateError {
+    if e.kind() == ErrorKind::VerificationError
+        && cur_iface
+            .base_iface()
+            .ipv4
+            .as_ref()
+            .and_then(|i| i.address_conflict)
+            == Some(true)
+    {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!(
+                "{}, IPv4 address conflict detected on interface {}",
+                e.msg(),
+                cur_iface.name()
+            ),
+        );
+        log::error!("{}", e);
+        return e;
+    }
+    e
+}
+
 impl MergedInterfaces {
     pub(crate) fn verify(
         &self,
@@ -141,6 +187,7 @@ impl MergedInterfaces {
         current.remove_ignored_ifaces(self.ignored_ifaces.as_slice());
         current.remove_unknown_type_port();
         merged.process_allow_extra_ovs_patch_ports_for_verify(&mut current);
+        merged.process_macsec_mka_keys_for_verify(&current);
 
         for iface in current
             .kernel_ifaces
@@ -180,7 +227,16 @@ impl MergedInterfaces {
             {
                 // Do not verify physical interface with state:down
                 if iface.is_up() {
-                    iface.verify(cur_iface)?;
+                    if let Err(e) = iface.verify(cur_iface) {
+                        let e = append_ipv4_address_conflict_diagnostics(
+                            e, cur_iface,
+                        );
+                        return Err(append_link_down_diagnostics(e, cur_iface));
+                    }
+                    warn_wait_ip_missing_family(
+                        des_iface.merged.base_iface(),
+                        cur_iface.base_iface(),
+                    );
                     if let Interface::Ethernet(eth_iface) = iface {
                         if eth_iface.sriov_is_enabled() {
                             eth_iface.verify_sriov(&current)?;
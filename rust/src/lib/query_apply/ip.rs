@@ -1,6 +1,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{Interface, InterfaceIpv4, InterfaceIpv6};
+use std::net::Ipv6Addr;
+use std::str::FromStr;
+
+use super::netlink::{
+    nl_apply_link, nl_iface_index, NlAttr, NlLinkMsg, IFLA_AF_SPEC, RTM_SETLINK,
+};
+use crate::{
+    ErrorKind, Interface, InterfaceIpv4, InterfaceIpv6, MergedInterfaces,
+    NmstateError,
+};
+
+const AF_INET6: u8 = 10;
+const IFLA_INET6_TOKEN: u16 = 7;
 
 impl InterfaceIpv4 {
     // Sort addresses and dedup
@@ -75,6 +87,15 @@ impl InterfaceIpv4 {
         if other.prop_list.contains(&"dhcp_custom_hostname") {
             self.dhcp_custom_hostname = other.dhcp_custom_hostname.clone();
         }
+        if other.prop_list.contains(&"dhcp_send_release") {
+            self.dhcp_send_release = other.dhcp_send_release;
+        }
+        if other.prop_list.contains(&"dad_timeout") {
+            self.dad_timeout = other.dad_timeout;
+        }
+        if other.prop_list.contains(&"address_conflict") {
+            self.address_conflict = other.address_conflict;
+        }
 
         for other_prop_name in &other.prop_list {
             if !self.prop_list.contains(other_prop_name) {
@@ -116,12 +137,18 @@ impl InterfaceIpv6 {
         }
     }
 
-    // Sort addresses and dedup
+    // * Sort addresses and dedup
+    // * Convert `dhcp_mode` to `dhcp` and `autoconf` as backend does not
+    //   report it
     pub(crate) fn sanitize_desired_for_verify(&mut self) {
         if let Some(addrs) = self.addresses.as_mut() {
             addrs.sort_unstable();
             addrs.dedup();
         }
+        if self.enabled {
+            self.sanitize_dhcp_mode().ok();
+        }
+        self.dhcp_mode = None;
     }
     pub(crate) fn update(&mut self, other: &Self) {
         if other.prop_list.contains(&"enabled") {
@@ -133,6 +160,9 @@ impl InterfaceIpv6 {
         if other.prop_list.contains(&"dhcp_duid") {
             self.dhcp_duid = other.dhcp_duid.clone();
         }
+        if other.prop_list.contains(&"dhcp_mode") {
+            self.dhcp_mode = other.dhcp_mode;
+        }
         if other.prop_list.contains(&"autoconf") {
             self.autoconf = other.autoconf;
         }
@@ -154,6 +184,9 @@ impl InterfaceIpv6 {
         if other.prop_list.contains(&"auto_table_id") {
             self.auto_table_id = other.auto_table_id;
         }
+        if other.prop_list.contains(&"ra") {
+            self.ra = other.ra.clone();
+        }
         if other.prop_list.contains(&"dns") {
             self.dns = other.dns.clone();
         }
@@ -175,6 +208,9 @@ impl InterfaceIpv6 {
         if other.prop_list.contains(&"dhcp_custom_hostname") {
             self.dhcp_custom_hostname = other.dhcp_custom_hostname.clone();
         }
+        if other.prop_list.contains(&"dhcp_send_release") {
+            self.dhcp_send_release = other.dhcp_send_release;
+        }
         for other_prop_name in &other.prop_list {
             if !self.prop_list.contains(other_prop_name) {
                 self.prop_list.push(other_prop_name);
@@ -213,3 +249,79 @@ impl Interface {
         }
     }
 }
+
+impl MergedInterfaces {
+    // Fallback for NetworkManager without `ipv6.token` support: set IPv6
+    // token to kernel via netlink after activation. The token is not
+    // persistent.
+    pub(crate) fn apply_ipv6_token_via_kernel(
+        &self,
+    ) -> Result<(), NmstateError> {
+        for iface in self
+            .kernel_ifaces
+            .values()
+            .filter_map(|i| i.for_apply.as_ref())
+            .filter(|i| i.is_up())
+        {
+            let token = match iface
+                .base_iface()
+                .ipv6
+                .as_ref()
+                .filter(|i| i.enabled)
+                .and_then(|i| i.token.as_deref())
+            {
+                Some(t) => t,
+                None => continue,
+            };
+            log::warn!(
+                "NetworkManager does not support IPv6 token, setting IPv6 \
+                token {token} of interface {} to kernel without persisting",
+                iface.name()
+            );
+            let msg = gen_ipv6_token_link_msg(
+                nl_iface_index(iface.name())?,
+                parse_ipv6_token(token)?,
+            );
+            nl_apply_link(
+                iface.name(),
+                format!("set IPv6 token {token}").as_str(),
+                RTM_SETLINK,
+                0,
+                &msg,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+// The `::` means removing the token
+fn parse_ipv6_token(token: &str) -> Result<Ipv6Addr, NmstateError> {
+    if token.is_empty() {
+        return Ok(Ipv6Addr::UNSPECIFIED);
+    }
+    Ipv6Addr::from_str(token).map_err(|e| {
+        let e = NmstateError::new(
+            ErrorKind::InvalidArgument,
+            format!("Invalid IPv6 token {token}: {e}"),
+        );
+        log::error!("{}", e);
+        e
+    })
+}
+
+// Equivalent of `ip token set <token> dev <iface>`
+pub(crate) fn gen_ipv6_token_link_msg(
+    index: i32,
+    token: Ipv6Addr,
+) -> NlLinkMsg {
+    let mut msg = NlLinkMsg::new(index);
+    msg.family = AF_INET6;
+    msg.attrs.push(NlAttr::new_nested(
+        IFLA_AF_SPEC,
+        vec![NlAttr::new_nested(
+            AF_INET6.into(),
+            vec![NlAttr::new_bytes(IFLA_INET6_TOKEN, &token.octets())],
+        )],
+    ));
+    msg
+}
@@ -0,0 +1,69 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    Ip6GreInterface, Ip6GretapInterface, Ip6TnlInterface, Ip6TunnelConfig,
+};
+
+impl Ip6GreInterface {
+    pub(crate) fn update_ip6gre(&mut self, other: &Ip6GreInterface) {
+        if let Some(conf) = &mut self.ip6gre {
+            conf.update(other.ip6gre.as_ref());
+        } else {
+            self.ip6gre = other.ip6gre.clone();
+        }
+    }
+}
+
+impl Ip6GretapInterface {
+    pub(crate) fn update_ip6gretap(&mut self, other: &Ip6GretapInterface) {
+        if let Some(conf) = &mut self.ip6gretap {
+            conf.update(other.ip6gretap.as_ref());
+        } else {
+            self.ip6gretap = other.ip6gretap.clone();
+        }
+    }
+}
+
+impl Ip6TnlInterface {
+    pub(crate) fn update_ip6tnl(&mut self, other: &Ip6TnlInterface) {
+        if let Some(conf) = &mut self.ip6tnl {
+            conf.update(other.ip6tnl.as_ref());
+        } else {
+            self.ip6tnl = other.ip6tnl.clone();
+        }
+    }
+}
+
+impl Ip6TunnelConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.mode.is_some() {
+                self.mode = other.mode;
+            }
+            if other.base_iface.is_some() {
+                self.base_iface = other.base_iface.clone();
+            }
+            if other.local.is_some() {
+                self.local = other.local;
+            }
+            if other.remote.is_some() {
+                self.remote = other.remote;
+            }
+            if other.ikey.is_some() {
+                self.ikey = other.ikey;
+            }
+            if other.okey.is_some() {
+                self.okey = other.okey;
+            }
+            if other.encap_limit.is_some() {
+                self.encap_limit = other.encap_limit;
+            }
+            if other.flow_label.is_some() {
+                self.flow_label = other.flow_label;
+            }
+            if other.hop_limit.is_some() {
+                self.hop_limit = other.hop_limit;
+            }
+        }
+    }
+}
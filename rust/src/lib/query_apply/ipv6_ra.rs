@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{nispor::get_ipv6_ra_routes, Ipv6RaInfo, NetworkState};
+
+impl NetworkState {
+    pub(crate) fn retrieve_ipv6_ra_routes(&mut self) {
+        for (iface_name, routes) in get_ipv6_ra_routes() {
+            if let Some(ipv6) = self
+                .interfaces
+                .kernel_ifaces
+                .get_mut(&iface_name)
+                .and_then(|i| i.base_iface_mut().ipv6.as_mut())
+                .filter(|i| i.enabled)
+            {
+                ipv6.ra.get_or_insert_with(Ipv6RaInfo::new).route =
+                    Some(routes);
+            }
+        }
+    }
+}
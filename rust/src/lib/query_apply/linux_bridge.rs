@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{BridgePortVlanConfig, LinuxBridgeConfig, LinuxBridgeInterface};
+use crate::{
+    BridgePortVlanConfig, ErrorKind, Interface, InterfaceState,
+    LinuxBridgeConfig, LinuxBridgeInterface, MergedInterfaces, NmstateError,
+};
 
 impl LinuxBridgeInterface {
     pub(crate) const INTEGER_ROUNDED_OPTIONS: [&'static str; 5] = [
@@ -18,6 +21,11 @@ impl LinuxBridgeInterface {
     // This is for verifying when user desire `vlan: {}` for resetting VLAN
     // filtering, the new current state will show as `vlan: None`.
     fn treat_none_vlan_as_empty_dict(&mut self) {
+        if let Some(br_conf) = self.bridge.as_mut() {
+            if br_conf.vlan.is_none() {
+                br_conf.vlan = Some(BridgePortVlanConfig::new());
+            }
+        }
         if let Some(port_confs) = self
             .bridge
             .as_mut()
@@ -55,8 +63,60 @@ impl LinuxBridgeInterface {
 impl LinuxBridgeConfig {
     pub(crate) fn update(&mut self, other: Option<&LinuxBridgeConfig>) {
         if let Some(other) = other {
-            self.options = other.options.clone();
-            self.port = other.port.clone();
+            if other.options.is_some() {
+                self.options = other.options.clone();
+            }
+            if other.port.is_some() {
+                self.port = other.port.clone();
+            }
+            if other.vlan.is_some() {
+                self.vlan = other.vlan.clone();
+            }
+        }
+    }
+}
+
+impl MergedInterfaces {
+    // NetworkManager has no setting for `no_linklocal_learn`, set it via
+    // sysfs after bridge activated.
+    pub(crate) fn apply_linux_bridge_no_linklocal_learn(
+        &self,
+    ) -> Result<(), NmstateError> {
+        for iface in self.kernel_ifaces.values() {
+            if let Some(Interface::LinuxBridge(br_iface)) =
+                iface.for_apply.as_ref()
+            {
+                if br_iface.base.state != InterfaceState::Up {
+                    continue;
+                }
+                if let Some(no_linklocal_learn) = br_iface
+                    .bridge
+                    .as_ref()
+                    .and_then(|b| b.options.as_ref())
+                    .and_then(|o| o.no_linklocal_learn)
+                {
+                    let path = format!(
+                        "/sys/class/net/{}/bridge/no_linklocal_learn",
+                        br_iface.base.name
+                    );
+                    if let Err(e) = std::fs::write(
+                        &path,
+                        if no_linklocal_learn { "1" } else { "0" },
+                    ) {
+                        let e = NmstateError::new(
+                            ErrorKind::PluginFailure,
+                            format!(
+                                "Failed to set no_linklocal_learn of linux \
+                                bridge {}: {}",
+                                br_iface.base.name, e
+                            ),
+                        );
+                        log::error!("{}", e);
+                        return Err(e);
+                    }
+                }
+            }
         }
+        Ok(())
     }
 }
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+
+use crate::{
+    nm::nm_lldp_neighbors_wait_change, ErrorKind, LldpNeighborTlv,
+    LldpNeighborsChange, NetworkState, NmstateError,
+};
+
+impl NetworkState {
+    /// Block till LLDP neighbors of any specified interface changed or
+    /// `timeout` seconds passed. Empty `iface_names` means all interfaces.
+    /// Return interfaces with changed LLDP neighbors, empty if timeout.
+    /// LLDP should be enabled on these interfaces by [crate::LldpConfig].
+    /// The [NetworkState::set_kernel_only()] is not supported.
+    /// Only available for feature `query_apply`.
+    pub fn wait_lldp_neighbors_change(
+        &self,
+        iface_names: &[&str],
+        timeout: u32,
+    ) -> Result<Vec<LldpNeighborsChange>, NmstateError> {
+        if self.kernel_only {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                "Waiting LLDP neighbors change is not supported in kernel \
+                only mode"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        nm_lldp_neighbors_wait_change(
+            self.nm_dbus_address.as_deref(),
+            iface_names,
+            timeout,
+        )
+    }
+}
+
+// Missing interface is treated as interface without LLDP neighbor.
+pub(crate) fn get_lldp_neighbors_changes(
+    old: &HashMap<String, Vec<Vec<LldpNeighborTlv>>>,
+    new: &HashMap<String, Vec<Vec<LldpNeighborTlv>>>,
+) -> Vec<LldpNeighborsChange> {
+    let mut ret = Vec::new();
+    for (iface_name, neighbors) in new.iter() {
+        if old.get(iface_name).map(Vec::as_slice).unwrap_or_default()
+            != neighbors.as_slice()
+        {
+            ret.push(LldpNeighborsChange {
+                iface_name: iface_name.to_string(),
+                neighbors: neighbors.clone(),
+            });
+        }
+    }
+    // Interface removed
+    for (iface_name, neighbors) in old.iter() {
+        if !neighbors.is_empty() && !new.contains_key(iface_name) {
+            ret.push(LldpNeighborsChange {
+                iface_name: iface_name.to_string(),
+                neighbors: Vec::new(),
+            });
+        }
+    }
+    ret.sort_unstable_by(|a, b| a.iface_name.cmp(&b.iface_name));
+    ret
+}
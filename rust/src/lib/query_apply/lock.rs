@@ -0,0 +1,216 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::os::unix::io::AsRawFd;
+
+use nix::errno::Errno;
+use nix::fcntl::{flock, FlockArg};
+
+use crate::{ErrorKind, NmstateError};
+
+const APPLY_LOCK_PATH: &str = "/run/nmstate.lock";
+const APPLY_LOCK_RETRY_INTERVAL_MILLISECONDS: u64 = 100;
+
+pub(crate) const DEFAULT_APPLY_LOCK_TIMEOUT: u32 = 60;
+
+// Inter-process exclusive lock held during the whole apply action to prevent
+// other nmstate instances(e.g. cloud-init and kubernetes-nmstate) from
+// interleaving their applies with ours and messing up the checkpoint.
+// The lock is released by kernel when the file descriptor closed on drop or
+// on process exit.
+// The checkpoint of `no commit` apply outlives the process, hence it is
+// stored in the lock file and other applies keep waiting till it been
+// committed, rollbacked or expired.
+#[derive(Debug)]
+pub(crate) struct NmstateApplyLock {
+    fd: File,
+    path: String,
+}
+
+impl NmstateApplyLock {
+    // Wait at most `timeout` seconds for the lock and for the pending
+    // checkpoint stored in lock file. The `is_checkpoint_pending` should
+    // return true if specified checkpoint is still waiting for commit or
+    // rollback.
+    pub(crate) fn new<F>(
+        timeout: u32,
+        is_checkpoint_pending: F,
+    ) -> Result<Self, NmstateError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        Self::new_with_path(APPLY_LOCK_PATH, timeout, is_checkpoint_pending)
+    }
+
+    // Used by checkpoint commit and rollback which are the ones resolving the
+    // pending checkpoint.
+    pub(crate) fn new_for_checkpoint(
+        timeout: u32,
+    ) -> Result<Self, NmstateError> {
+        Self::new_with_path(APPLY_LOCK_PATH, timeout, |_| false)
+    }
+
+    pub(crate) fn new_with_path<F>(
+        path: &str,
+        timeout: u32,
+        is_checkpoint_pending: F,
+    ) -> Result<Self, NmstateError>
+    where
+        F: Fn(&str) -> bool,
+    {
+        let fd = match OpenOptions::new()
+            .create(true)
+            .read(true)
+            .write(true)
+            .truncate(false)
+            .mode(0o600)
+            .open(path)
+        {
+            Ok(f) => f,
+            Err(e) => {
+                let e = NmstateError::new(
+                    ErrorKind::PermissionError,
+                    format!("Failed to open apply lock file {path}: {e}"),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        };
+        let mut lock = Self {
+            fd,
+            path: path.to_string(),
+        };
+        let deadline = std::time::Instant::now()
+            + std::time::Duration::from_secs(timeout.into());
+        let mut logged = false;
+        loop {
+            let pending_checkpoint = match flock(
+                lock.fd.as_raw_fd(),
+                FlockArg::LockExclusiveNonblock,
+            ) {
+                Ok(()) => match lock.pending_checkpoint()? {
+                    Some(cp) if is_checkpoint_pending(cp.as_str()) => {
+                        lock.unlock();
+                        Some(cp)
+                    }
+                    _ => {
+                        log::debug!("Acquired apply lock {path}");
+                        return Ok(lock);
+                    }
+                },
+                Err(Errno::EWOULDBLOCK) | Err(Errno::EINTR) => None,
+                Err(e) => {
+                    let e = NmstateError::new(
+                        ErrorKind::Bug,
+                        format!("Failed to lock apply lock file {path}: {e}"),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+            };
+            if std::time::Instant::now() >= deadline {
+                let e = NmstateError::new(
+                    ErrorKind::PluginFailure,
+                    if let Some(cp) = pending_checkpoint {
+                        format!(
+                            "Timeout on waiting checkpoint {cp} of other \
+                            nmstate apply action to be committed or \
+                            rollbacked after {timeout} seconds"
+                        )
+                    } else {
+                        format!(
+                            "Timeout on waiting other nmstate instance \
+                            to finish its apply action after \
+                            {timeout} seconds"
+                        )
+                    },
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            if !logged {
+                if let Some(cp) = pending_checkpoint {
+                    log::info!(
+                        "Checkpoint {cp} of other nmstate apply action is \
+                        not committed or rollbacked yet, waiting at most \
+                        {timeout} seconds for it"
+                    );
+                } else {
+                    log::info!(
+                        "Another nmstate instance is applying, waiting \
+                        at most {timeout} seconds for it to finish"
+                    );
+                }
+                logged = true;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(
+                APPLY_LOCK_RETRY_INTERVAL_MILLISECONDS,
+            ));
+        }
+    }
+
+    // Store the checkpoint of `no commit` apply, so that other apply
+    // actions wait for it even after current process exits.
+    pub(crate) fn set_pending_checkpoint(
+        &mut self,
+        checkpoint: &str,
+    ) -> Result<(), NmstateError> {
+        self.write_content(checkpoint)
+    }
+
+    // Remove stored checkpoint if it is the specified one. Empty
+    // `checkpoint` means the last checkpoint which always matches.
+    pub(crate) fn clear_pending_checkpoint(
+        &mut self,
+        checkpoint: &str,
+    ) -> Result<(), NmstateError> {
+        match self.pending_checkpoint()? {
+            Some(cp) if checkpoint.is_empty() || cp == checkpoint => {
+                self.write_content("")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn pending_checkpoint(
+        &mut self,
+    ) -> Result<Option<String>, NmstateError> {
+        let mut content = String::new();
+        self.fd
+            .seek(SeekFrom::Start(0))
+            .and_then(|_| self.fd.read_to_string(&mut content))
+            .map_err(|e| self.io_error("read", e))?;
+        let content = content.trim();
+        Ok(if content.is_empty() {
+            None
+        } else {
+            Some(content.to_string())
+        })
+    }
+
+    fn write_content(&mut self, content: &str) -> Result<(), NmstateError> {
+        self.fd
+            .set_len(0)
+            .and_then(|_| self.fd.seek(SeekFrom::Start(0)))
+            .and_then(|_| self.fd.write_all(content.as_bytes()))
+            .and_then(|_| self.fd.sync_data())
+            .map_err(|e| self.io_error("write", e))
+    }
+
+    fn io_error(&self, action: &str, e: std::io::Error) -> NmstateError {
+        let e = NmstateError::new(
+            ErrorKind::Bug,
+            format!("Failed to {action} apply lock file {}: {e}", self.path),
+        );
+        log::error!("{}", e);
+        e
+    }
+
+    fn unlock(&self) {
+        if let Err(e) = flock(self.fd.as_raw_fd(), FlockArg::Unlock) {
+            log::debug!("Failed to unlock apply lock file {}: {e}", self.path);
+        }
+    }
+}
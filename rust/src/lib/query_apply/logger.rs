@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+
+use crate::{
+    warning::{start_warning_collection, stop_warning_collection},
+    ErrorKind, NetworkState, NmstateError, NmstateWarning,
+};
+
+// Whether `NmstateLogger` is installed as global logger.
+static LOGGER_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+// Installed by `NetworkState::apply_with_logs()` when application has no
+// global logger.
+static CAPTURE_ONLY_LOGGER: NmstateLogger = NmstateLogger { inner: None };
+
+thread_local! {
+    // Log collector of the on-going apply action this thread is working for.
+    // `None` means no capture is requested.
+    static LOG_COLLECTOR: RefCell<Option<NmstateLogCollector>> =
+        RefCell::new(None);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+/// Log record emitted by nmstate during an apply action.
+pub struct NmstateLogEntry {
+    /// Log level in upper case, e.g. `WARN`.
+    pub level: String,
+    /// The module path emitting this log, e.g. `nmstate::ifaces::bond`.
+    pub target: String,
+    pub msg: String,
+}
+
+impl From<NmstateWarning> for NmstateLogEntry {
+    fn from(w: NmstateWarning) -> Self {
+        Self {
+            level: log::Level::Warn.to_string(),
+            target: "nmstate".to_string(),
+            msg: w.msg,
+        }
+    }
+}
+
+impl From<&log::Record<'_>> for NmstateLogEntry {
+    fn from(r: &log::Record<'_>) -> Self {
+        Self {
+            level: r.level().to_string(),
+            target: r.target().to_string(),
+            msg: r.args().to_string(),
+        }
+    }
+}
+
+/// Global logger capturing nmstate log records for
+/// [NetworkState::apply_with_logs()] and forwarding all records to the
+/// optional inner logger.
+/// Only available for feature `query_apply`.
+pub struct NmstateLogger {
+    inner: Option<Box<dyn log::Log>>,
+}
+
+impl NmstateLogger {
+    pub fn new(inner: Option<Box<dyn log::Log>>) -> Self {
+        Self { inner }
+    }
+
+    /// Install this logger as global logger of [log] crate with specified
+    /// maximum log level.
+    pub fn init(self, max_level: log::LevelFilter) -> Result<(), NmstateError> {
+        // The global logger lives till process exit
+        set_global_logger(Box::leak(Box::new(self)), max_level)
+    }
+}
+
+fn set_global_logger(
+    logger: &'static NmstateLogger,
+    max_level: log::LevelFilter,
+) -> Result<(), NmstateError> {
+    log::set_logger(logger).map_err(|e| {
+        NmstateError::new(
+            ErrorKind::Bug,
+            format!("Failed to set global logger: {e}"),
+        )
+    })?;
+    LOGGER_INSTALLED.store(true, Ordering::SeqCst);
+    log::set_max_level(max_level);
+    Ok(())
+}
+
+impl log::Log for NmstateLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target().starts_with("nmstate")
+            || self.inner.as_ref().map(|l| l.enabled(metadata)) == Some(true)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.target().starts_with("nmstate") {
+            if let Some(collector) = NmstateLogCollector::current() {
+                collector.push(NmstateLogEntry::from(record));
+            }
+        }
+        if let Some(inner) = self.inner.as_ref() {
+            if inner.enabled(record.metadata()) {
+                inner.log(record);
+            }
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(inner) = self.inner.as_ref() {
+            inner.flush();
+        }
+    }
+}
+
+impl NetworkState {
+    /// Apply the `NetworkState` like [NetworkState::apply()] and return the
+    /// result along with the log records emitted by nmstate during this
+    /// apply, for example the warnings on ignored or changed properties of
+    /// desired state, which are entries of `WARN` level. The log records are
+    /// returned on failure also.
+    /// Records emitted by other threads working for this apply, for example
+    /// the secret agent, are included, while records of concurrent actions
+    /// in other threads are not. Only the records passing the maximum log
+    /// level of [log] crate are captured.
+    /// When no global logger is installed, a capture only [NmstateLogger] is
+    /// installed with `INFO` maximum log level. When a global logger other
+    /// than [NmstateLogger] is installed, only the warnings are returned.
+    /// Only available for feature `query_apply`.
+    pub fn apply_with_logs(
+        &self,
+    ) -> (Result<(), NmstateError>, Vec<NmstateLogEntry>) {
+        if !LOGGER_INSTALLED.load(Ordering::SeqCst)
+            && set_global_logger(&CAPTURE_ONLY_LOGGER, log::LevelFilter::Info)
+                .is_err()
+        {
+            log::debug!(
+                "Global logger is not NmstateLogger, only capturing warnings"
+            );
+        }
+        run_with_logs(LOGGER_INSTALLED.load(Ordering::SeqCst), || self.apply())
+    }
+}
+
+// Run `f` with its log records captured by `NmstateLogCollector` if
+// `NmstateLogger` is installed, otherwise only capture the warnings.
+pub(crate) fn run_with_logs(
+    logger_installed: bool,
+    f: impl FnOnce() -> Result<(), NmstateError>,
+) -> (Result<(), NmstateError>, Vec<NmstateLogEntry>) {
+    if logger_installed {
+        let collector = NmstateLogCollector::default();
+        let result = collector.run(f);
+        (result, collector.take())
+    } else {
+        start_warning_collection();
+        let result = f();
+        let logs = stop_warning_collection()
+            .into_iter()
+            .map(NmstateLogEntry::from)
+            .collect();
+        (result, logs)
+    }
+}
+
+// Log entries captured for single apply action. Shared by all the threads
+// working for this action, threads spawned by nmstate should run their tasks
+// with `NmstateLogCollector::run()` on the collector of the spawning thread.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NmstateLogCollector(Arc<Mutex<Vec<NmstateLogEntry>>>);
+
+impl NmstateLogCollector {
+    // The collector of current thread.
+    pub(crate) fn current() -> Option<Self> {
+        LOG_COLLECTOR.with(|c| c.borrow().clone())
+    }
+
+    // Capture logs of current thread emitted by `f`.
+    pub(crate) fn run<T>(&self, f: impl FnOnce() -> T) -> T {
+        let old = LOG_COLLECTOR.with(|c| c.replace(Some(self.clone())));
+        let ret = f();
+        LOG_COLLECTOR.with(|c| *c.borrow_mut() = old);
+        ret
+    }
+
+    fn push(&self, entry: NmstateLogEntry) {
+        if let Ok(mut entries) = self.0.lock() {
+            entries.push(entry);
+        }
+    }
+
+    pub(crate) fn take(&self) -> Vec<NmstateLogEntry> {
+        self.0
+            .lock()
+            .map(|mut entries| std::mem::take(&mut *entries))
+            .unwrap_or_default()
+    }
+}
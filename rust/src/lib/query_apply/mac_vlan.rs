@@ -19,6 +19,9 @@ impl MacVlanConfig {
             self.base_iface = other.base_iface.clone();
             self.mode = other.mode;
             self.accept_all_mac = other.accept_all_mac;
+            if other.source_mac_addresses.is_some() {
+                self.source_mac_addresses = other.source_mac_addresses.clone();
+            }
         }
     }
 }
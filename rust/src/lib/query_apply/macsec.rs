@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{MacSecConfig, MacSecInterface};
+use crate::{
+    Interface, Interfaces, MacSecConfig, MacSecInterface, MergedInterfaces,
+};
 
 impl MacSecInterface {
     pub(crate) fn update_macsec(&mut self, other: &MacSecInterface) {
@@ -22,3 +24,41 @@ impl MacSecConfig {
         }
     }
 }
+
+impl MergedInterfaces {
+    // NetworkManager only holds the key chosen from `mka-keys` at the time of
+    // applying, hence treat desired `mka-keys` as the current key if it is
+    // one of them.
+    pub(crate) fn process_macsec_mka_keys_for_verify(
+        &mut self,
+        current: &Interfaces,
+    ) {
+        for iface in self.kernel_ifaces.values_mut() {
+            let des_conf = match iface.for_verify.as_mut() {
+                Some(Interface::MacSec(i)) => match i.macsec.as_mut() {
+                    Some(c) if c.mka_keys.is_some() => c,
+                    _ => continue,
+                },
+                _ => continue,
+            };
+            let cur_conf = match current.kernel_ifaces.get(iface.merged.name())
+            {
+                Some(Interface::MacSec(i)) => match i.macsec.as_ref() {
+                    Some(c) => c,
+                    None => continue,
+                },
+                _ => continue,
+            };
+            if let Some(key) = des_conf.mka_keys.as_ref().and_then(|keys| {
+                keys.iter().find(|k| {
+                    Some(k.mka_ckn.as_str()) == cur_conf.mka_ckn.as_deref()
+                        && Some(&k.mka_cak) == cur_conf.mka_cak.as_ref()
+                })
+            }) {
+                des_conf.mka_cak = Some(key.mka_cak.clone());
+                des_conf.mka_ckn = Some(key.mka_ckn.clone());
+                des_conf.mka_keys = None;
+            }
+        }
+    }
+}
@@ -1,27 +1,45 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod base;
-mod bond;
+pub(crate) mod batman;
+pub(crate) mod bond;
+pub(crate) mod can;
+mod connectivity;
+mod custom;
 mod dispatch;
 mod dns;
-mod ethernet;
+pub(crate) mod ethernet;
+mod gre;
 mod hostname;
 mod iface;
+pub(crate) mod ifb;
 mod infiniband;
 mod inter_ifaces;
-mod ip;
+pub(crate) mod ip;
+mod ip6_tunnel;
+mod ip_vlan;
 mod ipsec;
+pub(crate) mod ipv6_ra;
 mod linux_bridge;
+pub(crate) mod lldp;
+pub(crate) mod lock;
+pub(crate) mod logger;
 mod mac_vlan;
 mod mac_vtap;
 mod macsec;
+mod modem;
 mod mptcp;
 mod net_state;
+pub(crate) mod netlink;
 pub(crate) mod ovn;
 mod ovs;
+pub(crate) mod retry;
 mod route;
 mod route_rule;
+mod routing_summary;
 mod sriov;
+pub(crate) mod stale_profile;
+mod team;
 mod vlan;
 mod vrf;
 mod vxlan;
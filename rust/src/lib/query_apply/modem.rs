@@ -0,0 +1,29 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ModemConfig, ModemInterface};
+
+impl ModemInterface {
+    pub(crate) fn update_modem(&mut self, other: &ModemInterface) {
+        if let Some(conf) = &mut self.modem {
+            conf.update(other.modem.as_ref());
+        } else {
+            self.modem = other.modem.clone();
+        }
+    }
+}
+
+impl ModemConfig {
+    fn update(&mut self, other: Option<&Self>) {
+        if let Some(other) = other {
+            if other.apn.is_some() {
+                self.apn = other.apn.clone();
+            }
+            if other.pin.is_some() {
+                self.pin = other.pin.clone();
+            }
+            if other.roaming.is_some() {
+                self.roaming = other.roaming;
+            }
+        }
+    }
+}
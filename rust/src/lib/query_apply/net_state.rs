@@ -1,19 +1,35 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::cell::RefCell;
+use std::net::IpAddr;
+
+use super::{
+    custom::PluginIfacesBackup,
+    lock::{NmstateApplyLock, DEFAULT_APPLY_LOCK_TIMEOUT},
+    retry::{
+        with_transient_retry, RETRY_TRANSIENT_COUNT,
+        RETRY_TRANSIENT_INTERVAL_MILLISECONDS,
+    },
+};
 use crate::{
+    ip::is_ip_in_network,
     nispor::{nispor_apply, nispor_retrieve, set_running_hostname},
     nm::{
-        nm_apply, nm_checkpoint_create, nm_checkpoint_destroy,
-        nm_checkpoint_rollback, nm_checkpoint_timeout_extend, nm_retrieve,
+        nm_apply, nm_apply_hostname_dns_only, nm_can_apply_hostname_dns_only,
+        nm_check_features, nm_checkpoint_create, nm_checkpoint_destroy,
+        nm_checkpoint_exists, nm_checkpoint_rollback,
+        nm_checkpoint_timeout_extend, nm_retrieve, nm_secret_agent_start,
+        nm_version, NmCheckpoint, NmConnectivityConfBackup,
     },
     ovsdb::{ovsdb_apply, ovsdb_is_running, ovsdb_retrieve},
-    ErrorKind, MergedInterfaces, MergedNetworkState, NetworkState,
-    NmstateError,
+    warning::nmstate_warn,
+    DnsState, ErrorKind, Interface, MergedInterfaces, MergedNetworkState,
+    NetworkState, NmstateError, RouteEntry, Routes,
 };
 
 const DEFAULT_ROLLBACK_TIMEOUT: u32 = 60;
-const VERIFY_RETRY_INTERVAL_MILLISECONDS: u64 = 1000;
-const VERIFY_RETRY_COUNT_DEFAULT: usize = 5;
+pub(crate) const VERIFY_RETRY_INTERVAL_MILLISECONDS: u64 = 1000;
+pub(crate) const VERIFY_RETRY_COUNT_DEFAULT: usize = 5;
 const VERIFY_RETRY_COUNT_SRIOV_MIN: usize = 30;
 const VERIFY_RETRY_COUNT_SRIOV_MAX: usize = 300;
 const VERIFY_RETRY_COUNT_KERNEL_MODE: usize = 5;
@@ -21,25 +37,79 @@ const RETRY_NM_COUNT: usize = 2;
 const RETRY_NM_INTERVAL_MILLISECONDS: u64 = 2000;
 
 const MAX_SUPPORTED_INTERFACES: usize = 1000;
+const DEFAULT_ROUTE_TABLE_ID: u32 = 254;
 
 impl NetworkState {
     /// Rollback a checkpoint.
     /// Not available for `kernel only` mode.
     /// Only available for feature `query_apply`.
     pub fn checkpoint_rollback(checkpoint: &str) -> Result<(), NmstateError> {
-        nm_checkpoint_rollback(checkpoint)
+        let mut lock =
+            NmstateApplyLock::new_for_checkpoint(DEFAULT_APPLY_LOCK_TIMEOUT)?;
+        nm_checkpoint_rollback(None, checkpoint)?;
+        lock.clear_pending_checkpoint(checkpoint)
     }
 
     /// Commit a checkpoint.
     /// Not available for `kernel only` mode.
     /// Only available for feature `query_apply`.
     pub fn checkpoint_commit(checkpoint: &str) -> Result<(), NmstateError> {
-        nm_checkpoint_destroy(checkpoint)
+        let mut lock =
+            NmstateApplyLock::new_for_checkpoint(DEFAULT_APPLY_LOCK_TIMEOUT)?;
+        nm_checkpoint_destroy(None, checkpoint)?;
+        lock.clear_pending_checkpoint(checkpoint)
     }
 
     /// Retrieve the `NetworkState`.
     /// Only available for feature `query_apply`.
     pub fn retrieve(&mut self) -> Result<&mut Self, NmstateError> {
+        self.retrieve_kernel_state()?;
+        self.retrieve_ovsdb_state()?;
+        if !self.kernel_only {
+            self.retrieve_nm_state()?;
+        }
+        self.retrieve_ipv6_ra_routes();
+        self.retrieve_plugin_ifaces()?;
+        self.post_retrieve();
+        Ok(self)
+    }
+
+    /// Retrieve the `NetworkState` from kernel only, skipping the query of
+    /// NetworkManager and OpenvSwitch database regardless of
+    /// [NetworkState::set_kernel_only()]. Userspace only interfaces like OVS
+    /// bridge and DNS resolver will not be included.
+    /// Useful for fast health check.
+    /// Only available for feature `query_apply`.
+    pub fn retrieve_kernel_only(&mut self) -> Result<&mut Self, NmstateError> {
+        self.retrieve_kernel_state()?;
+        self.retrieve_ipv6_ra_routes();
+        self.post_retrieve();
+        Ok(self)
+    }
+
+    /// Retrieve the `NetworkState` from NetworkManager and OpenvSwitch
+    /// database only, skipping the query of kernel. Only the configuration
+    /// and state known to NetworkManager will be included, for example, the
+    /// routes and route rules are not included.
+    /// Not available for `kernel only` mode.
+    /// Only available for feature `query_apply`.
+    pub fn retrieve_config_only(&mut self) -> Result<&mut Self, NmstateError> {
+        if self.kernel_only {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                "Cannot retrieve configuration only in kernel only mode"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        self.retrieve_ovsdb_state()?;
+        self.retrieve_nm_state()?;
+        self.post_retrieve();
+        Ok(self)
+    }
+
+    fn retrieve_kernel_state(&mut self) -> Result<(), NmstateError> {
         let state = nispor_retrieve(self.running_config_only)?;
         if state.prop_list.contains(&"hostname") {
             self.hostname = state.hostname;
@@ -53,8 +123,15 @@ impl NetworkState {
         if state.prop_list.contains(&"rules") {
             self.rules = state.rules;
         }
-        if ovsdb_is_running() {
-            match ovsdb_retrieve() {
+        if state.prop_list.contains(&"unsupported_interfaces") {
+            self.unsupported_interfaces = state.unsupported_interfaces;
+        }
+        Ok(())
+    }
+
+    fn retrieve_ovsdb_state(&mut self) -> Result<(), NmstateError> {
+        if ovsdb_is_running(self.ovsdb_socket_path.as_deref()) {
+            match ovsdb_retrieve(self.ovsdb_socket_path.as_deref()) {
                 Ok(mut ovsdb_state) => {
                     ovsdb_state.isolate_ovn()?;
                     self.update_state(&ovsdb_state);
@@ -64,11 +141,26 @@ impl NetworkState {
                 }
             }
         }
-        if !self.kernel_only {
-            let nm_state = nm_retrieve(self.running_config_only)?;
-            // TODO: Priority handling
-            self.update_state(&nm_state);
-        }
+        Ok(())
+    }
+
+    fn retrieve_nm_state(&mut self) -> Result<(), NmstateError> {
+        let nm_state = with_transient_retry(
+            RETRY_TRANSIENT_INTERVAL_MILLISECONDS,
+            RETRY_TRANSIENT_COUNT,
+            || {
+                nm_retrieve(
+                    self.nm_dbus_address.as_deref(),
+                    self.running_config_only,
+                )
+            },
+        )?;
+        // TODO: Priority handling
+        self.update_state(&nm_state);
+        Ok(())
+    }
+
+    fn post_retrieve(&mut self) {
         if !self.include_secrets {
             self.hide_secrets();
         }
@@ -78,7 +170,9 @@ impl NetworkState {
             .user_ifaces
             .retain(|_, iface| !iface.is_ignore());
 
-        Ok(self)
+        if self.include_routing_summary {
+            self.routing_summary = Some(self.gen_routing_summary());
+        }
     }
 
     /// Apply the `NetworkState`.
@@ -94,19 +188,171 @@ impl NetworkState {
                 MAX_SUPPORTED_INTERFACES,
             );
         }
+        let mut lock = self.acquire_apply_lock()?;
+        if self.is_hostname_only() {
+            log::info!(
+                "Desired state only contains hostname, applying without \
+                touching network backends"
+            );
+            return self.apply_hostname_only();
+        }
         if !self.kernel_only {
-            self.apply_with_nm_backend()
+            self.apply_with_nm_backend(&mut lock)
         } else {
             // TODO: Need checkpoint for kernel only mode
             self.apply_without_nm_backend()
         }
     }
 
-    fn apply_with_nm_backend(&self) -> Result<(), NmstateError> {
+    /// Apply the specified [Interface] only without wrapping it into full
+    /// [NetworkState]. The query and apply options of `self`(e.g.
+    /// [NetworkState::set_kernel_only()]) are honored, other network state
+    /// stored in `self` is ignored.
+    /// Only available for feature `query_apply`.
+    pub fn apply_interface(
+        &self,
+        iface: &Interface,
+    ) -> Result<(), NmstateError> {
+        self.new_sub_state_with_iface(iface).apply()
+    }
+
+    /// Verify whether current network state matches the specified
+    /// [Interface]. The query options of `self` are honored, other network
+    /// state stored in `self` is ignored.
+    /// Only available for feature `query_apply`.
+    pub fn verify_interface(
+        &self,
+        iface: &Interface,
+    ) -> Result<(), NmstateError> {
+        self.new_sub_state_with_iface(iface).verify()
+    }
+
+    /// Apply the specified [Routes] only without wrapping it into full
+    /// [NetworkState]. The query and apply options of `self` are honored,
+    /// other network state stored in `self` is ignored.
+    /// Only available for feature `query_apply`.
+    pub fn apply_routes(&self, routes: &Routes) -> Result<(), NmstateError> {
+        self.new_sub_state_with_routes(routes).apply()
+    }
+
+    /// Verify whether current network state matches the specified [Routes].
+    /// The query options of `self` are honored, other network state stored in
+    /// `self` is ignored.
+    /// Only available for feature `query_apply`.
+    pub fn verify_routes(&self, routes: &Routes) -> Result<(), NmstateError> {
+        self.new_sub_state_with_routes(routes).verify()
+    }
+
+    /// Apply the specified [DnsState] only without wrapping it into full
+    /// [NetworkState]. The query and apply options of `self` are honored,
+    /// other network state stored in `self` is ignored.
+    /// Only available for feature `query_apply`.
+    pub fn apply_dns(&self, dns: &DnsState) -> Result<(), NmstateError> {
+        self.new_sub_state_with_dns(dns).apply()
+    }
+
+    /// Verify whether current network state matches the specified
+    /// [DnsState]. The query options of `self` are honored, other network
+    /// state stored in `self` is ignored.
+    /// Only available for feature `query_apply`.
+    pub fn verify_dns(&self, dns: &DnsState) -> Result<(), NmstateError> {
+        self.new_sub_state_with_dns(dns).verify()
+    }
+
+    // Whether desired state only contains hostname, DNS and/or connectivity
+    // check config, which could be applied without merging and activating
+    // interfaces.
+    pub(crate) fn is_hostname_or_dns_only(&self) -> bool {
+        !(self.hostname.is_none()
+            && self.dns.is_empty()
+            && self.connectivity_check.is_none())
+            && self.rules.is_empty()
+            && self.routes.is_empty()
+            && self.interfaces.is_empty()
+            && self.ovsdb.is_none()
+            && self.ovn.is_none()
+    }
+
+    /// Check whether current network state already satisfies `self` as
+    /// desired state without touching network backends, useful for
+    /// detecting configuration drift. Return `Ok(None)` when satisfied,
+    /// otherwise return the mismatch found by verification, for example
+    /// `eth1.interface.mtu desire '1500', current '9000'`. Invalid desired
+    /// state still leads to [NmstateError].
+    /// Only available for feature `query_apply`.
+    pub fn check(&self) -> Result<Option<String>, NmstateError> {
+        self.check_against(&self.retrieve_current_for_verify()?)
+    }
+
+    pub(crate) fn check_against(
+        &self,
+        current: &Self,
+    ) -> Result<Option<String>, NmstateError> {
+        match self.verify_against(current) {
+            Ok(()) => Ok(None),
+            Err(e) if e.kind() == ErrorKind::VerificationError => {
+                Ok(Some(e.msg().to_string()))
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    // Verify current network state against `self` without applying.
+    fn verify(&self) -> Result<(), NmstateError> {
+        self.verify_against(&self.retrieve_current_for_verify()?)
+    }
+
+    fn retrieve_current_for_verify(&self) -> Result<Self, NmstateError> {
+        let mut cur_net_state = self.clone_options_only();
+        cur_net_state.set_include_secrets(true);
+        cur_net_state.retrieve()?;
+        Ok(cur_net_state)
+    }
+
+    pub(crate) fn verify_against(
+        &self,
+        current: &Self,
+    ) -> Result<(), NmstateError> {
+        let merged_state = MergedNetworkState::new(
+            self.clone(),
+            current.clone(),
+            false,
+            self.memory_only,
+        )?;
+        merged_state.verify(current)
+    }
+
+    // Besides waiting other apply action, also wait the checkpoint created by
+    // other `no commit` apply action.
+    pub(crate) fn acquire_apply_lock(
+        &self,
+    ) -> Result<NmstateApplyLock, NmstateError> {
+        let timeout = self
+            .apply_lock_timeout
+            .unwrap_or(DEFAULT_APPLY_LOCK_TIMEOUT);
+        if self.kernel_only {
+            NmstateApplyLock::new(timeout, |_| false)
+        } else {
+            let nm_dbus_address = self.nm_dbus_address.as_deref();
+            NmstateApplyLock::new(timeout, |checkpoint| {
+                nm_checkpoint_exists(nm_dbus_address, checkpoint)
+            })
+        }
+    }
+
+    // The checkpoint of `no commit` apply is stored into the apply lock file,
+    // so that other apply actions wait for its commit or rollback.
+    fn apply_with_nm_backend(
+        &self,
+        lock: &mut NmstateApplyLock,
+    ) -> Result<(), NmstateError> {
         let mut merged_state = None;
         let mut cur_net_state = NetworkState::new();
         cur_net_state.set_kernel_only(self.kernel_only);
         cur_net_state.set_include_secrets(true);
+        cur_net_state.nm_dbus_address = self.nm_dbus_address.clone();
+        cur_net_state.ovsdb_socket_path = self.ovsdb_socket_path.clone();
+        cur_net_state.iface_plugins = self.iface_plugins.clone();
         if let Err(e) = cur_net_state.retrieve() {
             if e.kind().can_retry() {
                 log::info!("Retrying on: {}", e);
@@ -129,16 +375,26 @@ impl NetworkState {
             None
         };
 
+        // Query once for feature validations of both SR-IOV PF state and
+        // full desired state.
+        let nm_version = nm_version(self.nm_dbus_address.as_deref())?;
+
         if pf_state.is_none() {
             // Do early pre-apply validation before checkpoint.
-            merged_state = Some(MergedNetworkState::new(
+            let state = MergedNetworkState::new(
                 self.clone(),
                 cur_net_state.clone(),
                 false,
                 self.memory_only,
-            )?);
+            )?;
+            nm_check_features(nm_version.as_str(), &state)?;
+            state.interfaces.check_bond_kernel_support()?;
+            state.ovn.validate_bridges(&state.interfaces)?;
+            merged_state = Some(state);
         }
 
+        let management_ip = self.get_reachable_management_ip(&cur_net_state);
+
         let timeout = if let Some(t) = self.timeout {
             t
         } else if pf_state.is_some() {
@@ -147,7 +403,19 @@ impl NetworkState {
             DEFAULT_ROLLBACK_TIMEOUT
         };
 
-        let checkpoint = match nm_checkpoint_create(timeout) {
+        let nm_dbus_address = self.nm_dbus_address.as_deref();
+        let connectivity_backup = NmConnectivityConfBackup::new(
+            nm_dbus_address,
+            self.connectivity_check.as_ref(),
+            self.memory_only,
+        )?;
+        let plugin_backup =
+            PluginIfacesBackup::new(self.iface_plugins.as_slice())?;
+        let checkpoint = match with_transient_retry(
+            RETRY_TRANSIENT_INTERVAL_MILLISECONDS,
+            RETRY_TRANSIENT_COUNT,
+            || nm_checkpoint_create(nm_dbus_address, timeout),
+        ) {
             Ok(c) => c,
             Err(e) => {
                 if e.kind().can_retry() {
@@ -155,7 +423,7 @@ impl NetworkState {
                     std::thread::sleep(std::time::Duration::from_millis(
                         RETRY_NM_INTERVAL_MILLISECONDS,
                     ));
-                    nm_checkpoint_create(timeout)?
+                    nm_checkpoint_create(nm_dbus_address, timeout)?
                 } else {
                     return Err(e);
                 }
@@ -163,8 +431,12 @@ impl NetworkState {
         };
 
         log::info!("Created checkpoint {}", &checkpoint);
+        let checkpoint = NmCheckpoint::new(nm_dbus_address, checkpoint);
+        // The current state is refreshed after applying SR-IOV PF, keep the
+        // one before apply for rollback without checkpoint.
+        let pre_apply_state = cur_net_state.clone();
 
-        with_nm_checkpoint(&checkpoint, self.no_commit, || {
+        with_nm_checkpoint(nm_dbus_address, &checkpoint, self.no_commit, || {
             if let Some(pf_state) = pf_state {
                 let pf_merged_state = MergedNetworkState::new(
                     pf_state,
@@ -180,15 +452,20 @@ impl NetworkState {
                     &checkpoint,
                     verify_count,
                     timeout,
+                    management_ip.as_ref(),
                 )?;
                 // Refresh current state
                 cur_net_state.retrieve()?;
-                merged_state = Some(MergedNetworkState::new(
+                let state = MergedNetworkState::new(
                     self.clone(),
                     cur_net_state.clone(),
                     false,
                     self.memory_only,
-                )?);
+                )?;
+                nm_check_features(nm_version.as_str(), &state)?;
+                state.interfaces.check_bond_kernel_support()?;
+                state.ovn.validate_bridges(&state.interfaces)?;
+                merged_state = Some(state);
             }
 
             let merged_state = if let Some(merged_state) = merged_state {
@@ -212,41 +489,140 @@ impl NetworkState {
                 &checkpoint,
                 verify_count,
                 timeout,
+                management_ip.as_ref(),
             )
         })
+        .map_err(|e| {
+            if checkpoint.is_lost() {
+                if let Err(e) = self
+                    .nm_rollback_without_checkpoint(&pre_apply_state, timeout)
+                {
+                    log::error!(
+                        "Failed to rollback to the state before apply: {e}"
+                    );
+                }
+            }
+            if let Some(backup) = connectivity_backup.as_ref() {
+                backup.restore();
+            }
+            plugin_backup.restore();
+            e
+        })
+        .and_then(|()| {
+            if self.no_commit {
+                lock.set_pending_checkpoint(checkpoint.path())
+            } else {
+                Ok(())
+            }
+        })
     }
 
     fn apply_with_nm_backend_and_under_checkpoint(
         &self,
         merged_state: &MergedNetworkState,
         cur_net_state: &Self,
-        checkpoint: &str,
+        checkpoint: &NmCheckpoint,
         retry_count: usize,
         timeout: u32,
+        management_ip: Option<&IpAddr>,
     ) -> Result<(), NmstateError> {
+        let nm_dbus_address = self.nm_dbus_address.as_deref();
+        let ovsdb_socket_path = self.ovsdb_socket_path.as_deref();
+        let hostname_dns_only = self.is_hostname_or_dns_only()
+            && nm_can_apply_hostname_dns_only(merged_state);
+        if hostname_dns_only {
+            log::info!(
+                "Desired state only contains hostname or DNS, applying \
+                without touching interfaces"
+            );
+        }
+        // Hold the secret agent till verification done, NetworkManager
+        // requests secrets during activation.
+        let secret_provider =
+            self.secret_provider.as_ref().filter(|_| !hostname_dns_only);
+        let secret_agent = RefCell::new(match secret_provider {
+            Some(provider) => {
+                Some(nm_secret_agent_start(nm_dbus_address, provider)?)
+            }
+            None => None,
+        });
         // NM might have unknown race problem found by verify stage,
         // we try to apply the state again if so.
         with_retry(RETRY_NM_INTERVAL_MILLISECONDS, RETRY_NM_COUNT, || {
-            nm_checkpoint_timeout_extend(checkpoint, timeout)?;
-            nm_apply(merged_state, checkpoint, timeout)?;
-            if merged_state.is_global_ovsdb_changed() && ovsdb_is_running() {
-                ovsdb_apply(merged_state)?;
+            // Transient D-Bus failures(e.g. NetworkManager restarting) are
+            // retried with backoff, other failures are retried by the outer
+            // retry.
+            with_transient_retry(
+                RETRY_TRANSIENT_INTERVAL_MILLISECONDS,
+                RETRY_TRANSIENT_COUNT,
+                || {
+                    // NetworkManager restarted, resume by applying the whole
+                    // desired state again if checkpoint still exists.
+                    if checkpoint.revalidate()? {
+                        // NetworkManager forgets registered secret agents
+                        // on restart.
+                        if let Some(provider) = secret_provider {
+                            secret_agent.replace(Some(nm_secret_agent_start(
+                                nm_dbus_address,
+                                provider,
+                            )?));
+                        }
+                    }
+                    let checkpoint_path = checkpoint.path();
+                    nm_checkpoint_timeout_extend(
+                        nm_dbus_address,
+                        checkpoint_path,
+                        timeout,
+                    )?;
+                    if hostname_dns_only {
+                        nm_apply_hostname_dns_only(
+                            nm_dbus_address,
+                            merged_state,
+                            checkpoint_path,
+                            timeout,
+                        )
+                    } else {
+                        merged_state.pre_apply_plugin_ifaces()?;
+                        nm_apply(
+                            nm_dbus_address,
+                            merged_state,
+                            checkpoint_path,
+                            timeout,
+                        )
+                    }
+                },
+            )?;
+            if !hostname_dns_only {
+                merged_state.post_apply_plugin_ifaces()?;
+                apply_runtime_only_settings(merged_state)?;
+                if merged_state.is_global_ovsdb_changed()
+                    && ovsdb_is_running(ovsdb_socket_path)
+                {
+                    ovsdb_apply(ovsdb_socket_path, merged_state)?;
+                }
             }
             if let Some(running_hostname) =
                 self.hostname.as_ref().and_then(|c| c.running.as_ref())
             {
                 set_running_hostname(running_hostname)?;
             }
-            if !self.no_verify {
+            if !self.no_verify || management_ip.is_some() {
                 with_retry(
                     VERIFY_RETRY_INTERVAL_MILLISECONDS,
                     retry_count,
                     || {
-                        nm_checkpoint_timeout_extend(checkpoint, timeout)?;
+                        nm_checkpoint_timeout_extend(
+                            nm_dbus_address,
+                            checkpoint.path(),
+                            timeout,
+                        )?;
                         let mut new_cur_net_state = cur_net_state.clone();
                         new_cur_net_state.set_include_secrets(true);
                         new_cur_net_state.retrieve()?;
-                        merged_state.verify(&new_cur_net_state)
+                        if !self.no_verify {
+                            merged_state.verify(&new_cur_net_state)?;
+                        }
+                        verify_management_ip(management_ip, &new_cur_net_state)
                     },
                 )
             } else {
@@ -255,20 +631,146 @@ impl NetworkState {
         })
     }
 
+    // NetworkManager lost the checkpoint on restart, restore the state before
+    // apply by applying the revert of desired state under new checkpoint.
+    fn nm_rollback_without_checkpoint(
+        &self,
+        pre_apply_state: &Self,
+        timeout: u32,
+    ) -> Result<(), NmstateError> {
+        log::info!("Rollbacking to the state before apply without checkpoint");
+        let revert_state = self.generate_revert(pre_apply_state)?;
+        let mut cur_net_state = pre_apply_state.clone();
+        cur_net_state.retrieve()?;
+        let merged_state = MergedNetworkState::new(
+            revert_state,
+            cur_net_state,
+            false,
+            self.memory_only,
+        )?;
+        let nm_dbus_address = self.nm_dbus_address.as_deref();
+        let checkpoint = NmCheckpoint::new(
+            nm_dbus_address,
+            nm_checkpoint_create(nm_dbus_address, timeout)?,
+        );
+        with_nm_checkpoint(nm_dbus_address, &checkpoint, false, || {
+            nm_apply(nm_dbus_address, &merged_state, checkpoint.path(), timeout)
+        })?;
+        log::info!("Rollbacked to the state before apply");
+        Ok(())
+    }
+
+    // Only protect management IP which is reachable before applying,
+    // otherwise apply will always fail.
+    fn get_reachable_management_ip(&self, current: &Self) -> Option<IpAddr> {
+        let ip = self.management_ip?;
+        if current.is_ip_reachable(&ip) {
+            log::info!(
+                "Will rollback if route to management IP {ip} is gone after \
+                applying"
+            );
+            Some(ip)
+        } else {
+            nmstate_warn!(
+                "Management IP {ip} is not reachable in current network \
+                state, ignoring"
+            );
+            None
+        }
+    }
+
+    // Whether any up interface holds IP address in the same subnet of
+    // specified IP or any running route in main route table could reach it.
+    pub(crate) fn is_ip_reachable(&self, ip: &IpAddr) -> bool {
+        let is_iface_up = |iface_name: &str| {
+            self.interfaces
+                .kernel_ifaces
+                .get(iface_name)
+                .map(|i| i.is_up())
+                .unwrap_or_default()
+        };
+        for iface in self.interfaces.kernel_ifaces.values() {
+            if !iface.is_up() {
+                continue;
+            }
+            let base_iface = iface.base_iface();
+            let ipv4_addrs = base_iface
+                .ipv4
+                .as_ref()
+                .and_then(|i| i.addresses.as_deref())
+                .unwrap_or_default();
+            let ipv6_addrs = base_iface
+                .ipv6
+                .as_ref()
+                .and_then(|i| i.addresses.as_deref())
+                .unwrap_or_default();
+            if ipv4_addrs
+                .iter()
+                .chain(ipv6_addrs.iter())
+                .any(|addr| is_ip_in_network(ip, &addr.ip, addr.prefix_length))
+            {
+                return true;
+            }
+        }
+        self.routes
+            .running
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .filter(|rt| {
+                !rt.is_absent()
+                    && rt.route_type.is_none()
+                    && matches!(
+                        rt.table_id,
+                        None | Some(RouteEntry::USE_DEFAULT_ROUTE_TABLE)
+                            | Some(DEFAULT_ROUTE_TABLE_ID)
+                    )
+            })
+            .any(|rt| {
+                rt.destination.as_ref().map(|d| d.contains(ip)) == Some(true)
+                    && rt
+                        .next_hop_iface
+                        .as_deref()
+                        .map(is_iface_up)
+                        .unwrap_or(true)
+            })
+    }
+
     fn apply_without_nm_backend(&self) -> Result<(), NmstateError> {
         let mut cur_net_state = NetworkState::new();
         cur_net_state.set_kernel_only(self.kernel_only);
         cur_net_state.set_include_secrets(true);
+        cur_net_state.nm_dbus_address = self.nm_dbus_address.clone();
+        cur_net_state.ovsdb_socket_path = self.ovsdb_socket_path.clone();
+        cur_net_state.iface_plugins = self.iface_plugins.clone();
         cur_net_state.retrieve()?;
 
+        let mut desired = self.clone();
+        if desired.management_ip.take().is_some() {
+            nmstate_warn!(
+                "Ignoring management IP in kernel only mode, rollback is \
+                only supported by NetworkManager backend"
+            );
+        }
+        if desired.connectivity_check.take().is_some() {
+            nmstate_warn!(
+                "Ignoring connectivity-check in kernel only mode, it is \
+                NetworkManager configuration"
+            );
+        }
+
         let merged_state = MergedNetworkState::new(
-            self.clone(),
+            desired,
             cur_net_state.clone(),
             false,
             self.memory_only,
         )?;
+        merged_state.interfaces.check_bond_kernel_support()?;
 
+        merged_state.pre_apply_plugin_ifaces()?;
         nispor_apply(&merged_state)?;
+        merged_state.post_apply_plugin_ifaces()?;
+        apply_runtime_only_settings(&merged_state)?;
         if let Some(running_hostname) =
             self.hostname.as_ref().and_then(|c| c.running.as_ref())
         {
@@ -289,6 +791,68 @@ impl NetworkState {
         }
     }
 
+    // Create empty [NetworkState] with only the query and apply options
+    // copied from `self`.
+    pub(crate) fn clone_options_only(&self) -> Self {
+        Self {
+            kernel_only: self.kernel_only,
+            no_verify: self.no_verify,
+            no_commit: self.no_commit,
+            timeout: self.timeout,
+            include_secrets: self.include_secrets,
+            include_status_data: self.include_status_data,
+            running_config_only: self.running_config_only,
+            include_routing_summary: self.include_routing_summary,
+            memory_only: self.memory_only,
+            nm_dbus_address: self.nm_dbus_address.clone(),
+            ovsdb_socket_path: self.ovsdb_socket_path.clone(),
+            apply_lock_timeout: self.apply_lock_timeout,
+            dns_verify_order_mode: self.dns_verify_order_mode,
+            ignored_route_rule_priorities: self
+                .ignored_route_rule_priorities
+                .clone(),
+            ignore_routes: self.ignore_routes,
+            ignore_route_rules: self.ignore_route_rules,
+            ignore_dns: self.ignore_dns,
+            protect_cni_owned_ovs: self.protect_cni_owned_ovs,
+            auto_absent_children: self.auto_absent_children,
+            ovn_auto_create_bridges: self.ovn_auto_create_bridges,
+            strict: self.strict,
+            management_ip: self.management_ip,
+            secret_provider: self.secret_provider.clone(),
+            secrets_agent_owned: self.secrets_agent_owned,
+            iface_plugins: self.iface_plugins.clone(),
+            ..Default::default()
+        }
+    }
+
+    // Create [NetworkState] holding specified [Interface] only with the
+    // query and apply options copied from `self`.
+    pub(crate) fn new_sub_state_with_iface(&self, iface: &Interface) -> Self {
+        let mut net_state = self.clone_options_only();
+        net_state.prop_list.push("interfaces");
+        net_state.append_interface_data(iface.clone());
+        net_state
+    }
+
+    // Create [NetworkState] holding specified [Routes] only with the
+    // query and apply options copied from `self`.
+    pub(crate) fn new_sub_state_with_routes(&self, routes: &Routes) -> Self {
+        let mut net_state = self.clone_options_only();
+        net_state.prop_list.push("routes");
+        net_state.routes = routes.clone();
+        net_state
+    }
+
+    // Create [NetworkState] holding specified [DnsState] only with the
+    // query and apply options copied from `self`.
+    pub(crate) fn new_sub_state_with_dns(&self, dns: &DnsState) -> Self {
+        let mut net_state = self.clone_options_only();
+        net_state.prop_list.push("dns");
+        net_state.dns = dns.clone();
+        net_state
+    }
+
     pub(crate) fn update_state(&mut self, other: &Self) {
         if other.prop_list.contains(&"hostname") {
             if let Some(h) = self.hostname.as_mut() {
@@ -311,11 +875,24 @@ impl NetworkState {
         if other.prop_list.contains(&"ovn") {
             self.ovn = other.ovn.clone();
         }
+        if other.prop_list.contains(&"connectivity_check") {
+            self.connectivity_check = other.connectivity_check.clone();
+        }
     }
 }
 
+// Apply the settings not supported by backends via sysfs.
+fn apply_runtime_only_settings(
+    merged_state: &MergedNetworkState,
+) -> Result<(), NmstateError> {
+    merged_state
+        .interfaces
+        .apply_linux_bridge_no_linklocal_learn()
+}
+
 fn with_nm_checkpoint<T>(
-    checkpoint: &str,
+    nm_dbus_address: Option<&str>,
+    checkpoint: &NmCheckpoint,
     no_commit: bool,
     func: T,
 ) -> Result<(), NmstateError>
@@ -324,8 +901,9 @@ where
 {
     match func() {
         Ok(()) => {
+            let checkpoint = checkpoint.path();
             if !no_commit {
-                nm_checkpoint_destroy(checkpoint)?;
+                nm_checkpoint_destroy(nm_dbus_address, checkpoint)?;
 
                 log::info!("Destroyed checkpoint {}", checkpoint);
             } else {
@@ -333,8 +911,12 @@ where
             }
             Ok(())
         }
+        // Caller should rollback without checkpoint
+        Err(e) if checkpoint.is_lost() => Err(e),
         Err(e) => {
-            if let Err(e) = nm_checkpoint_rollback(checkpoint) {
+            let checkpoint = checkpoint.path();
+            if let Err(e) = nm_checkpoint_rollback(nm_dbus_address, checkpoint)
+            {
                 log::warn!("nm_checkpoint_rollback() failed: {}", e);
             }
             log::info!("Rollbacked to checkpoint {}", checkpoint);
@@ -343,7 +925,7 @@ where
     }
 }
 
-fn with_retry<T>(
+pub(crate) fn with_retry<T>(
     interval_ms: u64,
     count: usize,
     func: T,
@@ -394,10 +976,32 @@ impl MergedNetworkState {
         self.dns.verify(&current.dns)?;
         self.ovsdb.verify(&current.ovsdb)?;
         self.ovn.verify(&current.ovn)?;
+        self.connectivity_check
+            .verify(current.connectivity_check.as_ref())?;
         Ok(())
     }
 }
 
+fn verify_management_ip(
+    management_ip: Option<&IpAddr>,
+    current: &NetworkState,
+) -> Result<(), NmstateError> {
+    if let Some(ip) = management_ip {
+        if !current.is_ip_reachable(ip) {
+            let e = NmstateError::new(
+                ErrorKind::VerificationError,
+                format!(
+                    "Route to management IP {ip} is gone after applying, \
+                    rolling back"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
 fn get_proper_verify_retry_count(merged_ifaces: &MergedInterfaces) -> usize {
     match merged_ifaces.get_sriov_vf_count() {
         0 => VERIFY_RETRY_COUNT_DEFAULT,
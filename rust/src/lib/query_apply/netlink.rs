@@ -0,0 +1,364 @@
+// SPDX-License-Identifier: Apache-2.0
+
+// Minimal rtnetlink client for link properties neither NetworkManager nor
+// nispor could configure.
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::os::unix::io::RawFd;
+
+use nix::libc;
+
+use crate::{ErrorKind, NmstateError};
+
+const NLMSG_HDR_LEN: usize = 16;
+const IFINFOMSG_LEN: usize = 16;
+const NLA_HDR_LEN: usize = 4;
+const NLA_F_NESTED: u16 = 1 << 15;
+const NLA_F_NET_BYTEORDER: u16 = 1 << 14;
+const NLMSG_ERROR: u16 = 2;
+const NETLINK_ROUTE: i32 = 0;
+
+const NLM_F_REQUEST: u16 = 0x1;
+const NLM_F_ACK: u16 = 0x4;
+pub(crate) const NLM_F_EXCL: u16 = 0x200;
+pub(crate) const NLM_F_CREATE: u16 = 0x400;
+
+pub(crate) const RTM_NEWLINK: u16 = 16;
+pub(crate) const RTM_DELLINK: u16 = 17;
+pub(crate) const RTM_GETLINK: u16 = 18;
+pub(crate) const RTM_SETLINK: u16 = 19;
+
+pub(crate) const IFLA_ADDRESS: u16 = 1;
+pub(crate) const IFLA_IFNAME: u16 = 3;
+pub(crate) const IFLA_MTU: u16 = 4;
+pub(crate) const IFLA_MASTER: u16 = 10;
+pub(crate) const IFLA_LINKINFO: u16 = 18;
+pub(crate) const IFLA_AF_SPEC: u16 = 26;
+pub(crate) const IFLA_INFO_KIND: u16 = 1;
+pub(crate) const IFLA_INFO_DATA: u16 = 2;
+
+const IFF_UP: u32 = 0x1;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum NlAttrValue {
+    Bytes(Vec<u8>),
+    Nested(Vec<NlAttr>),
+}
+
+// Netlink attribute(struct nlattr) with its payload
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NlAttr {
+    kind: u16,
+    value: NlAttrValue,
+}
+
+impl NlAttr {
+    pub(crate) fn new_bytes(kind: u16, value: &[u8]) -> Self {
+        Self {
+            kind,
+            value: NlAttrValue::Bytes(value.to_vec()),
+        }
+    }
+
+    pub(crate) fn new_u32(kind: u16, value: u32) -> Self {
+        Self::new_bytes(kind, &value.to_ne_bytes())
+    }
+
+    // Null-terminated string
+    pub(crate) fn new_str(kind: u16, value: &str) -> Self {
+        let mut bytes = value.as_bytes().to_vec();
+        bytes.push(0);
+        Self {
+            kind,
+            value: NlAttrValue::Bytes(bytes),
+        }
+    }
+
+    pub(crate) fn new_nested(kind: u16, attrs: Vec<NlAttr>) -> Self {
+        Self {
+            kind,
+            value: NlAttrValue::Nested(attrs),
+        }
+    }
+
+    fn emit(&self, buffer: &mut Vec<u8>) {
+        let start = buffer.len();
+        buffer.extend_from_slice(&[0u8; NLA_HDR_LEN]);
+        let kind = match &self.value {
+            NlAttrValue::Bytes(v) => {
+                buffer.extend_from_slice(v);
+                self.kind
+            }
+            NlAttrValue::Nested(attrs) => {
+                for attr in attrs {
+                    attr.emit(buffer);
+                }
+                self.kind | NLA_F_NESTED
+            }
+        };
+        let len = (buffer.len() - start) as u16;
+        buffer[start..start + 2].copy_from_slice(&len.to_ne_bytes());
+        buffer[start + 2..start + 4].copy_from_slice(&kind.to_ne_bytes());
+        buffer.resize(nl_align(buffer.len()), 0);
+    }
+}
+
+fn nl_align(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+// Parse netlink attributes into HashMap of attribute type(with flags
+// stripped) to payload. Malformed trailing data is ignored.
+pub(crate) fn parse_nl_attrs(mut data: &[u8]) -> HashMap<u16, &[u8]> {
+    let mut ret = HashMap::new();
+    while data.len() >= NLA_HDR_LEN {
+        let len = u16::from_ne_bytes([data[0], data[1]]) as usize;
+        let kind = u16::from_ne_bytes([data[2], data[3]])
+            & !(NLA_F_NESTED | NLA_F_NET_BYTEORDER);
+        if len < NLA_HDR_LEN || len > data.len() {
+            break;
+        }
+        ret.insert(kind, &data[NLA_HDR_LEN..len]);
+        data = &data[std::cmp::min(nl_align(len), data.len())..];
+    }
+    ret
+}
+
+pub(crate) fn nl_attr_u32(data: &[u8]) -> Option<u32> {
+    let mut bytes = [0u8; 4];
+    bytes.copy_from_slice(data.get(..4)?);
+    Some(u32::from_ne_bytes(bytes))
+}
+
+pub(crate) fn nl_attr_str(data: &[u8]) -> Option<String> {
+    let end = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+    std::str::from_utf8(&data[..end])
+        .ok()
+        .map(|s| s.to_string())
+}
+
+// Link message(struct ifinfomsg) with attributes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct NlLinkMsg {
+    pub(crate) family: u8,
+    pub(crate) index: i32,
+    pub(crate) flags: u32,
+    pub(crate) change: u32,
+    pub(crate) attrs: Vec<NlAttr>,
+}
+
+impl NlLinkMsg {
+    pub(crate) fn new(index: i32) -> Self {
+        Self {
+            index,
+            ..Default::default()
+        }
+    }
+
+    // Change administrative state of the link
+    pub(crate) fn set_up(&mut self, up: bool) {
+        if up {
+            self.flags |= IFF_UP;
+        } else {
+            self.flags &= !IFF_UP;
+        }
+        self.change |= IFF_UP;
+    }
+
+    pub(crate) fn to_bytes(
+        &self,
+        msg_type: u16,
+        flags: u16,
+        seq: u32,
+    ) -> Vec<u8> {
+        let mut buffer = vec![0u8; NLMSG_HDR_LEN];
+        buffer[4..6].copy_from_slice(&msg_type.to_ne_bytes());
+        buffer[6..8].copy_from_slice(
+            &(flags | NLM_F_REQUEST | NLM_F_ACK).to_ne_bytes(),
+        );
+        buffer[8..12].copy_from_slice(&seq.to_ne_bytes());
+        buffer.push(self.family);
+        buffer.push(0);
+        // ifi_type
+        buffer.extend_from_slice(&0u16.to_ne_bytes());
+        buffer.extend_from_slice(&self.index.to_ne_bytes());
+        buffer.extend_from_slice(&self.flags.to_ne_bytes());
+        buffer.extend_from_slice(&self.change.to_ne_bytes());
+        for attr in &self.attrs {
+            attr.emit(&mut buffer);
+        }
+        let len = buffer.len() as u32;
+        buffer[0..4].copy_from_slice(&len.to_ne_bytes());
+        buffer
+    }
+}
+
+struct NlSocket(RawFd);
+
+impl NlSocket {
+    fn new() -> Result<Self, std::io::Error> {
+        let fd = unsafe {
+            libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                NETLINK_ROUTE,
+            )
+        };
+        if fd < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(Self(fd))
+        }
+    }
+
+    fn send(&self, buffer: &[u8]) -> Result<(), std::io::Error> {
+        let mut addr: libc::sockaddr_nl = unsafe { std::mem::zeroed() };
+        addr.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+        let rc = unsafe {
+            libc::sendto(
+                self.0,
+                buffer.as_ptr() as *const libc::c_void,
+                buffer.len(),
+                0,
+                &addr as *const libc::sockaddr_nl as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            )
+        };
+        if rc < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(())
+        }
+    }
+
+    fn recv(&self) -> Result<Vec<u8>, std::io::Error> {
+        // Peek the size first as link message with SR-IOV VF information
+        // could be large.
+        let size = unsafe {
+            libc::recv(
+                self.0,
+                std::ptr::null_mut(),
+                0,
+                libc::MSG_PEEK | libc::MSG_TRUNC,
+            )
+        };
+        if size < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        let mut buffer = vec![0u8; size as usize];
+        let size = unsafe {
+            libc::recv(
+                self.0,
+                buffer.as_mut_ptr() as *mut libc::c_void,
+                buffer.len(),
+                0,
+            )
+        };
+        if size < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        buffer.truncate(size as usize);
+        Ok(buffer)
+    }
+}
+
+impl Drop for NlSocket {
+    fn drop(&mut self) {
+        unsafe {
+            libc::close(self.0);
+        }
+    }
+}
+
+// Send link request and wait for the acknowledgement. Return attributes of
+// the replied link message if any.
+fn nl_link_request(
+    msg_type: u16,
+    flags: u16,
+    msg: &NlLinkMsg,
+) -> Result<Option<Vec<u8>>, std::io::Error> {
+    const SEQ: u32 = 1;
+    let socket = NlSocket::new()?;
+    socket.send(msg.to_bytes(msg_type, flags, SEQ).as_slice())?;
+    let mut ret = None;
+    loop {
+        let buffer = socket.recv()?;
+        let mut data = buffer.as_slice();
+        while data.len() >= NLMSG_HDR_LEN {
+            let len = nl_attr_u32(data).unwrap_or_default() as usize;
+            let reply_type = u16::from_ne_bytes([data[4], data[5]]);
+            let reply_seq = nl_attr_u32(&data[8..]).unwrap_or_default();
+            if len < NLMSG_HDR_LEN || len > data.len() {
+                break;
+            }
+            let payload = &data[NLMSG_HDR_LEN..len];
+            if reply_seq == SEQ {
+                if reply_type == NLMSG_ERROR {
+                    let errno = nl_attr_u32(payload).unwrap_or_default() as i32;
+                    return if errno == 0 {
+                        Ok(ret)
+                    } else {
+                        Err(std::io::Error::from_raw_os_error(-errno))
+                    };
+                } else if reply_type == RTM_NEWLINK
+                    && payload.len() >= IFINFOMSG_LEN
+                {
+                    ret = Some(payload[IFINFOMSG_LEN..].to_vec());
+                }
+            }
+            data = &data[std::cmp::min(nl_align(len), data.len())..];
+        }
+    }
+}
+
+pub(crate) fn nl_iface_index(iface_name: &str) -> Result<i32, NmstateError> {
+    let index = match CString::new(iface_name) {
+        Ok(name) => unsafe { libc::if_nametoindex(name.as_ptr()) },
+        Err(_) => 0,
+    };
+    if index == 0 {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to find kernel interface {iface_name}"),
+        );
+        log::error!("{}", e);
+        Err(e)
+    } else {
+        Ok(index as i32)
+    }
+}
+
+// Return attributes of specified link, `None` if not found or failed.
+pub(crate) fn nl_get_link(iface_name: &str) -> Option<Vec<u8>> {
+    let mut msg = NlLinkMsg::default();
+    msg.attrs.push(NlAttr::new_str(IFLA_IFNAME, iface_name));
+    match nl_link_request(RTM_GETLINK, 0, &msg) {
+        Ok(attrs) => attrs,
+        Err(e) => {
+            log::debug!("Failed to query interface {iface_name}: {e}");
+            None
+        }
+    }
+}
+
+// Send link request of specified interface, the `action` is used in error
+// message.
+pub(crate) fn nl_apply_link(
+    iface_name: &str,
+    action: &str,
+    msg_type: u16,
+    flags: u16,
+    msg: &NlLinkMsg,
+) -> Result<(), NmstateError> {
+    log::debug!("Sending netlink request to {action} of {iface_name}");
+    if let Err(e) = nl_link_request(msg_type, flags, msg) {
+        let e = NmstateError::new(
+            ErrorKind::PluginFailure,
+            format!("Failed to {action} of interface {iface_name}: {e}"),
+        );
+        log::error!("{}", e);
+        return Err(e);
+    }
+    Ok(())
+}
@@ -1,8 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    state::get_json_value_difference, ErrorKind, MergedOvnConfiguration,
-    NmstateError, OvnConfiguration,
+    state::get_json_value_difference, ErrorKind, InterfaceType,
+    MergedInterfaces, MergedOvnConfiguration, NmstateError, OvnConfiguration,
 };
 
 impl MergedOvnConfiguration {
@@ -54,4 +54,34 @@ impl MergedOvnConfiguration {
             Ok(())
         }
     }
+
+    // Each desired bridge mapping should refer to OVS bridge exists in
+    // desired or current state, otherwise OVN will fail at runtime when
+    // creating localnet port.
+    pub(crate) fn validate_bridges(
+        &self,
+        merged_ifaces: &MergedInterfaces,
+    ) -> Result<(), NmstateError> {
+        for (localnet, bridge) in self.desired.get_desired_bridges() {
+            if !merged_ifaces
+                .get_iface(bridge, InterfaceType::OvsBridge)
+                .map(|i| !i.merged.is_absent())
+                .unwrap_or_default()
+            {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The OVN bridge mapping {localnet}:{bridge} is \
+                        referring to OVS bridge {bridge} which does not \
+                        exist in desired or current state, please define \
+                        it in desired state or use \
+                        NetworkState::set_ovn_auto_create_bridges()"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
 }
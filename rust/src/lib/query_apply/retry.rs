@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, NmstateError};
+
+pub(crate) const RETRY_TRANSIENT_COUNT: usize = 5;
+pub(crate) const RETRY_TRANSIENT_INTERVAL_MILLISECONDS: u64 = 500;
+const RETRY_TRANSIENT_MAX_INTERVAL_MILLISECONDS: u64 = 8000;
+
+// Retry the function on transient backend failure (e.g. NetworkManager
+// restarting or D-Bus timeout) with exponential backoff starting from
+// `interval_ms`. Once `count` attempts exhausted, return
+// `ErrorKind::BackendUnavailable` error with attempt count included.
+pub(crate) fn with_transient_retry<T, F>(
+    interval_ms: u64,
+    count: usize,
+    mut func: F,
+) -> Result<T, NmstateError>
+where
+    F: FnMut() -> Result<T, NmstateError>,
+{
+    let mut interval_ms = interval_ms;
+    let mut cur_count = 1usize;
+    loop {
+        match func() {
+            Err(e) if e.kind() == ErrorKind::BackendUnavailable => {
+                if cur_count >= count {
+                    let e = NmstateError::new(
+                        ErrorKind::BackendUnavailable,
+                        format!(
+                            "Giving up after {cur_count} attempts: {}",
+                            e.msg()
+                        ),
+                    );
+                    log::error!("{}", e);
+                    return Err(e);
+                }
+                log::info!(
+                    "Retrying in {interval_ms} milliseconds on transient \
+                    failure: {e}"
+                );
+                std::thread::sleep(std::time::Duration::from_millis(
+                    interval_ms,
+                ));
+                interval_ms = (interval_ms * 2)
+                    .min(RETRY_TRANSIENT_MAX_INTERVAL_MILLISECONDS);
+                cur_count += 1;
+            }
+            result => return result,
+        }
+    }
+}
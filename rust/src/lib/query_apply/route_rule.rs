@@ -1,7 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ErrorKind, MergedRouteRules, NmstateError, RouteRuleEntry, RouteRules,
+    route_rule::is_priority_ignored, ErrorKind, MergedRouteRules, NmstateError,
+    RouteRuleEntry, RouteRules,
 };
 
 impl MergedRouteRules {
@@ -18,6 +19,12 @@ impl MergedRouteRules {
                         continue;
                     }
                 }
+                if is_priority_ignored(
+                    cur_rule,
+                    self.ignored_priorities.as_slice(),
+                ) {
+                    continue;
+                }
                 cur_rules.push(cur_rule);
             }
         }
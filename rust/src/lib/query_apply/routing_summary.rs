@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetworkState, RouteEntry, RoutingSummary, RoutingSummaryGateway};
+
+const DEFAULT_TABLE_ID: u32 = 254; // main route table ID
+
+impl NetworkState {
+    // Generate the routing summary from running routes and route rules
+    pub(crate) fn gen_routing_summary(&self) -> RoutingSummary {
+        let mut ret = RoutingSummary::new();
+        for rt in self.routes.running.as_deref().unwrap_or_default() {
+            let table_id = match rt.table_id {
+                None | Some(RouteEntry::USE_DEFAULT_ROUTE_TABLE) => {
+                    DEFAULT_TABLE_ID
+                }
+                Some(t) => t,
+            };
+            *ret.route_counts.entry(table_id).or_default() += 1;
+
+            if rt.destination.as_ref().map(|d| d.prefix_length()) == Some(0) {
+                let gateway = RoutingSummaryGateway {
+                    next_hop_interface: rt.next_hop_iface.clone(),
+                    next_hop_address: rt.next_hop_addr.clone(),
+                    table_id: if table_id == DEFAULT_TABLE_ID {
+                        None
+                    } else {
+                        Some(table_id)
+                    },
+                };
+                if rt.is_ipv6() {
+                    ret.ipv6_default_gateways.push(gateway);
+                } else {
+                    ret.ipv4_default_gateways.push(gateway);
+                }
+            }
+        }
+        ret.route_rule_count = self
+            .rules
+            .config
+            .as_ref()
+            .map(|r| r.len())
+            .unwrap_or_default();
+        ret
+    }
+}
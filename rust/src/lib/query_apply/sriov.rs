@@ -10,10 +10,23 @@ use crate::{
 impl SrIovConfig {
     // * Set 'vfs: []' to None which is just reverting all VF config to default.
     // * Set `vf.iface_name` empty string,
+    // * Convert `vf.vlan_trunk_tags` into the format of queried state, single
+    //   VLAN is reported as `vf.vlan_id`.
     pub(crate) fn sanitize_desired_for_verify(&mut self) {
         if let Some(vfs) = self.vfs.as_mut() {
             for vf in vfs.iter_mut() {
                 vf.iface_name = String::new();
+                if let Some(vlan_ids) = vf.get_vlan_trunk_ids() {
+                    if vlan_ids.len() > 1 {
+                        vf.set_vlan_trunk_ids(vlan_ids);
+                        // Kernel only reports the first VLAN of VF
+                        vf.vlan_id = None;
+                        vf.qos = None;
+                    } else {
+                        vf.vlan_trunk_tags = None;
+                        vf.vlan_id = vlan_ids.first().copied();
+                    }
+                }
             }
             if vfs.is_empty() {
                 self.vfs = None;
@@ -26,8 +39,23 @@ impl SrIovConfig {
             if let Some(total_vfs) = other.total_vfs {
                 self.total_vfs = Some(total_vfs);
             }
-            if let Some(vfs) = other.vfs.as_ref() {
-                self.vfs = Some(vfs.clone());
+            if let Some(other_vfs) = other.vfs.as_ref() {
+                match self.vfs.as_mut() {
+                    // The VLAN trunk tags retrieved from NetworkManager
+                    Some(vfs) => {
+                        for other_vf in other_vfs {
+                            if let Some(vf) =
+                                vfs.iter_mut().find(|v| v.id == other_vf.id)
+                            {
+                                if other_vf.vlan_trunk_tags.is_some() {
+                                    vf.vlan_trunk_tags =
+                                        other_vf.vlan_trunk_tags.clone();
+                                }
+                            }
+                        }
+                    }
+                    None => self.vfs = Some(other_vfs.clone()),
+                }
             }
         }
     }
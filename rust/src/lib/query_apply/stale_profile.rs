@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::Serialize;
+
+use crate::{nm::nm_stale_profiles_get, ErrorKind, NetworkState, NmstateError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+/// NetworkManager profile created by nmstate which no longer corresponds to
+/// any existing interface or interface in desired state.
+/// Generated by [NetworkState::list_stale_profiles()] and
+/// [NetworkState::prune_stale_profiles()].
+pub struct NmstateStaleProfile {
+    /// UUID of NetworkManager profile.
+    pub uuid: String,
+    /// Name of NetworkManager profile.
+    pub name: String,
+    /// Interface name of NetworkManager profile.
+    pub iface_name: String,
+    /// NetworkManager interface type, for example `802-3-ethernet`.
+    pub nm_iface_type: String,
+}
+
+impl NetworkState {
+    /// List NetworkManager profiles created by nmstate whose interface
+    /// neither exists nor is defined in this `NetworkState` (interfaces
+    /// marked as absent are ignored), for example profiles left behind by
+    /// removed hardware.
+    /// Please include virtual interfaces in down state into this
+    /// `NetworkState`, otherwise their profiles are treated as stale.
+    /// Only available for feature `query_apply`.
+    pub fn list_stale_profiles(
+        &self,
+    ) -> Result<Vec<NmstateStaleProfile>, NmstateError> {
+        self.get_stale_profiles(false)
+    }
+
+    /// Delete the NetworkManager profiles listed by
+    /// [NetworkState::list_stale_profiles()] and return them.
+    /// Only available for feature `query_apply`.
+    pub fn prune_stale_profiles(
+        &self,
+    ) -> Result<Vec<NmstateStaleProfile>, NmstateError> {
+        self.get_stale_profiles(true)
+    }
+
+    fn get_stale_profiles(
+        &self,
+        delete: bool,
+    ) -> Result<Vec<NmstateStaleProfile>, NmstateError> {
+        if self.kernel_only {
+            let e = NmstateError::new(
+                ErrorKind::NotSupportedError,
+                "Searching stale NetworkManager profiles is not supported \
+                in kernel only mode"
+                    .to_string(),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        let desired_iface_names: Vec<&str> = self
+            .interfaces
+            .iter()
+            .filter(|i| !i.is_absent())
+            .map(|i| i.name())
+            .collect();
+        nm_stale_profiles_get(
+            self.nm_dbus_address.as_deref(),
+            desired_iface_names.as_slice(),
+            delete,
+        )
+    }
+}
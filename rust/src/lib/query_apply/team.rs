@@ -0,0 +1,20 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::TeamInterface;
+
+impl TeamInterface {
+    pub(crate) fn update_team(&mut self, other: &TeamInterface) {
+        if let Some(other_conf) = other.team.as_ref() {
+            let mut new_conf = other_conf.clone();
+            if let Some(conf) = self.team.as_ref() {
+                if new_conf.runner.is_none() {
+                    new_conf.runner = conf.runner;
+                }
+                if new_conf.port.is_none() {
+                    new_conf.port.clone_from(&conf.port);
+                }
+            }
+            self.team = Some(new_conf);
+        }
+    }
+}
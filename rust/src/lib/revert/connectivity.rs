@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ConnectivityCheckConfig, MergedConnectivityCheckConfig};
+
+impl MergedConnectivityCheckConfig {
+    // Only include the properties mentioned in desired state
+    pub(crate) fn generate_revert(&self) -> Option<ConnectivityCheckConfig> {
+        if !self.is_changed() {
+            return None;
+        }
+        let desired = self.desired.as_ref()?;
+        let current = self.current.as_ref()?;
+        let mut ret = ConnectivityCheckConfig::new();
+        if desired.enabled.is_some() {
+            ret.enabled = current.enabled;
+        }
+        if desired.uri.is_some() {
+            ret.uri = Some(current.uri.clone().unwrap_or_default());
+        }
+        Some(ret)
+    }
+}
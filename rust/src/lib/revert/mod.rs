@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
+mod connectivity;
 mod dns;
 mod hostname;
 mod ifaces;
@@ -20,6 +20,9 @@ impl NetworkState {
             dns: merged_state.dns.generate_revert(),
             ovsdb: merged_state.ovsdb.generate_revert(),
             ovn: merged_state.ovn.generate_revert(),
+            connectivity_check: merged_state
+                .connectivity_check
+                .generate_revert(),
             hostname: merged_state.hostname.generate_revert(),
             prop_list: vec!["interfaces"],
             ..Default::default()
@@ -6,9 +6,10 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
+use crate::warning::nmstate_warn;
 use crate::{
-    ip::{is_ipv6_addr, sanitize_ip_network},
-    ErrorKind, InterfaceType, MergedInterfaces, NmstateError,
+    ip::{is_ipv6_addr, is_ipv6_unicast_link_local},
+    ErrorKind, InterfaceType, IpNetwork, MergedInterfaces, NmstateError,
 };
 
 const DEFAULT_TABLE_ID: u32 = 254; // main route table ID
@@ -94,6 +95,7 @@ impl Routes {
                             ),
                         ));
                     } else if route.next_hop_iface.is_none()
+                        && route.next_hop_addr_scope().is_none()
                         && (route.route_type.is_none()
                             || !no_nexthop_route_type
                                 .contains(&route.route_type.unwrap()))
@@ -107,7 +109,9 @@ impl Routes {
                         ));
                     }
                 }
-                if let Some(dst) = route.destination.as_deref() {
+                if let Some(dst) =
+                    route.destination.as_ref().map(|d| d.as_str())
+                {
                     validate_route_dst(dst)?;
                 }
             }
@@ -136,13 +140,18 @@ impl Default for RouteState {
 #[serde(deny_unknown_fields)]
 /// Route entry
 pub struct RouteEntry {
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     /// Only used for delete route when applying.
     pub state: Option<RouteState>,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Route destination address or network
     /// Mandatory for every non-absent routes.
-    pub destination: Option<String>,
+    /// IP address without prefix length will be treated as host route.
+    pub destination: Option<IpNetwork>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         rename = "next-hop-interface"
@@ -188,7 +197,11 @@ pub struct RouteEntry {
     pub weight: Option<u16>,
     /// Route type
     /// Serialize and deserialize to/from `route-type`.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     pub route_type: Option<RouteType>,
 }
 
@@ -244,7 +257,6 @@ impl RouteEntry {
 
     pub(crate) fn is_match(&self, other: &Self) -> bool {
         if self.destination.as_ref().is_some()
-            && self.destination.as_deref() != Some("")
             && self.destination != other.destination
         {
             return false;
@@ -283,38 +295,27 @@ impl RouteEntry {
             !self
                 .destination
                 .as_ref()
-                .map(|d| is_ipv6_addr(d.as_str()))
+                .map(|d| d.is_ipv6())
                 .unwrap_or_default(),
             self.table_id.unwrap_or(DEFAULT_TABLE_ID),
             self.next_hop_iface
                 .as_deref()
                 .unwrap_or(LOOPBACK_IFACE_NAME),
-            self.destination.as_deref().unwrap_or(""),
+            self.destination
+                .as_ref()
+                .map(|d| d.as_str())
+                .unwrap_or_default(),
             self.next_hop_addr.as_deref().unwrap_or(""),
             self.weight.unwrap_or_default(),
         )
     }
 
     pub(crate) fn sanitize(&mut self) -> Result<(), NmstateError> {
-        if let Some(dst) = self.destination.as_ref() {
-            if dst.is_empty() {
-                self.destination = None;
-            } else {
-                let new_dst = sanitize_ip_network(dst)?;
-                if dst != &new_dst {
-                    log::warn!(
-                        "Route destination {} sanitized to {}",
-                        dst,
-                        new_dst
-                    );
-                    self.destination = Some(new_dst);
-                }
-            }
-        }
+        self.sanitize_next_hop_scope()?;
         if let Some(via) = self.next_hop_addr.as_ref() {
             let new_via = format!("{}", via.parse::<std::net::IpAddr>()?);
             if via != &new_via {
-                log::warn!(
+                nmstate_warn!(
                     "Route next-hop-address {} sanitized to {}",
                     via,
                     new_via
@@ -332,8 +333,8 @@ impl RouteEntry {
                     ),
                 ));
             }
-            if let Some(dst) = self.destination.as_deref() {
-                if is_ipv6_addr(dst) {
+            if let Some(dst) = self.destination.as_ref() {
+                if dst.is_ipv6() {
                     return Err(NmstateError::new(
                         ErrorKind::NotSupportedError,
                         "IPv6 ECMP route with weight is not supported yet"
@@ -346,8 +347,67 @@ impl RouteEntry {
     }
 
     pub(crate) fn is_ipv6(&self) -> bool {
-        self.destination.as_ref().map(|d| is_ipv6_addr(d.as_str()))
-            == Some(true)
+        self.destination.as_ref().map(|d| d.is_ipv6()) == Some(true)
+    }
+
+    // Return the interface name of scoped IPv6 link local next hop address,
+    // e.g. `eth1` for `fe80::1%eth1`.
+    fn next_hop_addr_scope(&self) -> Option<&str> {
+        self.next_hop_addr
+            .as_deref()
+            .and_then(|via| via.split_once('%'))
+            .map(|(_, scope)| scope)
+    }
+
+    // Move the scope of IPv6 link local next hop address into
+    // `next-hop-interface`, e.g. `fe80::1%eth1` copied from router
+    // advertisement.
+    fn sanitize_next_hop_scope(&mut self) -> Result<(), NmstateError> {
+        let via = match self.next_hop_addr.as_deref() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+        let (addr, scope) = match via.split_once('%') {
+            Some((addr, scope)) => (addr, scope),
+            None => return Ok(()),
+        };
+        let is_link_local = match addr.parse::<std::net::IpAddr>() {
+            Ok(std::net::IpAddr::V6(ip)) => is_ipv6_unicast_link_local(&ip),
+            _ => false,
+        };
+        if !is_link_local || scope.is_empty() {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Invalid route next-hop-address {via}, only IPv6 link \
+                    local address is allowed to have '%' character, the \
+                    correct format should be 'fe80::1%eth1'"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        match self.next_hop_iface.as_deref() {
+            Some(iface) if iface != scope => {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "The scope of route next-hop-address {via} is \
+                        not matching next-hop-interface {iface}"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            }
+            Some(_) => (),
+            None => self.next_hop_iface = Some(scope.to_string()),
+        }
+        nmstate_warn!(
+            "Route next-hop-address {via} sanitized to {addr} with \
+            next-hop-interface {scope}"
+        );
+        self.next_hop_addr = Some(addr.to_string());
+        Ok(())
     }
 }
 
@@ -427,6 +487,7 @@ impl MergedRoutes {
                 desired_routes.push(rt);
             }
         }
+        check_duplicate_routes(desired_routes.as_slice())?;
 
         let mut changed_ifaces: HashSet<&str> = HashSet::new();
 
@@ -610,6 +671,38 @@ impl MergedRoutes {
     }
 }
 
+// Routes sharing the same destination, next hop and route table are merged
+// into one with warning, unless they are using different route type.
+fn check_duplicate_routes(routes: &[RouteEntry]) -> Result<(), NmstateError> {
+    let mut checked: Vec<&RouteEntry> = Vec::new();
+    for rt in routes.iter().filter(|rt| !rt.is_absent()) {
+        if let Some(dup_rt) = checked.iter().find(|r| **r == rt) {
+            if dup_rt.route_type != rt.route_type {
+                let e = NmstateError::new(
+                    ErrorKind::InvalidArgument,
+                    format!(
+                        "Route '{rt}' conflicts with route '{dup_rt}', they \
+                        have the same destination, next hop and route table \
+                        but different route type"
+                    ),
+                );
+                log::error!("{}", e);
+                return Err(e);
+            } else if dup_rt.metric != rt.metric {
+                nmstate_warn!(
+                    "Route '{rt}' conflicts with route '{dup_rt}' on metric, \
+                    only one of them will be applied"
+                );
+            } else {
+                nmstate_warn!("Route '{rt}' is defined more than once");
+            }
+        } else {
+            checked.push(rt);
+        }
+    }
+    Ok(())
+}
+
 // Validating if the route destination network is valid,
 // 0.0.0.0/8 and its subnet cannot be used as the route destination network
 fn validate_route_dst(dst: &str) -> Result<(), NmstateError> {
@@ -1,12 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use std::convert::TryFrom;
+use std::ops::RangeInclusive;
 
 use serde::{Deserialize, Serialize};
 
+use crate::warning::nmstate_warn;
 use crate::{
-    ip::{is_ipv6_addr, sanitize_ip_network, AddressFamily},
-    ErrorKind, InterfaceIpAddr, InterfaceType, NmstateError,
+    ip::{is_ipv6_addr, AddressFamily},
+    ErrorKind, InterfaceType, IpNetwork, NmstateError,
 };
 
 const ROUTE_RULE_DEFAULT_PRIORIRY: i64 = 30000;
@@ -64,23 +65,39 @@ impl Default for RouteRuleState {
 #[serde(deny_unknown_fields)]
 pub struct RouteRuleEntry {
     /// Indicate the address family of the route rule.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     pub family: Option<AddressFamily>,
     /// Indicate this is normal route rule or absent route rule.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     pub state: Option<RouteRuleState>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_ip_network_or_empty"
+    )]
     /// Source prefix to match.
     /// Serialize and deserialize to/from `ip-from`.
-    /// When setting to empty string in absent route rule, it will only delete
-    /// route rule __without__ `ip-from`.
-    pub ip_from: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// When setting to empty string or empty [IpNetwork] in absent route rule,
+    /// it will only delete route rule __without__ `ip-from`.
+    pub ip_from: Option<IpNetwork>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_ip_network_or_empty"
+    )]
     /// Destination prefix to match.
     /// Serialize and deserialize to/from `ip-to`.
-    /// When setting to empty string in absent route rule, it will only delete
-    /// route rule __without__ `ip-to`.
-    pub ip_to: Option<String>,
+    /// When setting to empty string or empty [IpNetwork] in absent route rule,
+    /// it will only delete route rule __without__ `ip-to`.
+    pub ip_to: Option<IpNetwork>,
     #[serde(
         skip_serializing_if = "Option::is_none",
         default,
@@ -115,7 +132,11 @@ pub struct RouteRuleEntry {
     /// Select the fwmask value to match
     pub fwmask: Option<u32>,
     /// Actions for matching packages.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_enum_ignore_case"
+    )]
     pub action: Option<RouteRuleAction>,
     /// Incoming interface.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -126,6 +147,8 @@ pub struct RouteRuleEntry {
     /// Serialize into `suppress-prefix-length`.
     #[serde(
         skip_serializing_if = "Option::is_none",
+        default,
+        deserialize_with = "crate::deserializer::option_u32_or_string",
         alias = "suppress_prefixlength"
     )]
     pub suppress_prefix_length: Option<u32>,
@@ -219,23 +242,12 @@ impl RouteRuleEntry {
     }
 
     pub(crate) fn is_match(&self, other: &Self) -> bool {
-        if let Some(ip_from) = self.ip_from.as_deref() {
+        if let Some(ip_from) = self.ip_from.as_ref() {
             if !ip_from.is_empty() {
-                let ip_from = if !ip_from.contains('/') {
-                    match InterfaceIpAddr::try_from(ip_from) {
-                        Ok(i) => i.to_string(),
-                        Err(e) => {
-                            log::error!("{}", e);
-                            return false;
-                        }
-                    }
-                } else {
-                    ip_from.to_string()
-                };
-                if other.ip_from != Some(ip_from) {
+                if other.ip_from.as_ref() != Some(ip_from) {
                     return false;
                 }
-            } else if other.ip_from.as_deref().map(|s| s.is_empty())
+            } else if other.ip_from.as_ref().map(|i| i.is_empty())
                 == Some(false)
             {
                 // Use desire 'ip_from: ""' means it should only match empty
@@ -243,24 +255,12 @@ impl RouteRuleEntry {
                 return false;
             }
         }
-        if let Some(ip_to) = self.ip_to.as_deref() {
+        if let Some(ip_to) = self.ip_to.as_ref() {
             if !ip_to.is_empty() {
-                let ip_to = if !ip_to.contains('/') {
-                    match InterfaceIpAddr::try_from(ip_to) {
-                        Ok(ref i) => i.to_string(),
-                        Err(e) => {
-                            log::error!("{}", e);
-                            return false;
-                        }
-                    }
-                } else {
-                    ip_to.to_string()
-                };
-                if other.ip_to != Some(ip_to) {
+                if other.ip_to.as_ref() != Some(ip_to) {
                     return false;
                 }
-            } else if other.ip_to.as_deref().map(|s| s.is_empty())
-                == Some(false)
+            } else if other.ip_to.as_ref().map(|i| i.is_empty()) == Some(false)
             {
                 // Use desire 'ip_to: ""' means it should only match empty
                 // ip_to
@@ -335,8 +335,11 @@ impl RouteRuleEntry {
             },
             self.table_id
                 .unwrap_or(RouteRuleEntry::USE_DEFAULT_ROUTE_TABLE),
-            self.ip_from.as_deref().unwrap_or(""),
-            self.ip_to.as_deref().unwrap_or(""),
+            self.ip_from
+                .as_ref()
+                .map(|i| i.as_str())
+                .unwrap_or_default(),
+            self.ip_to.as_ref().map(|i| i.as_str()).unwrap_or_default(),
             self.priority
                 .unwrap_or(RouteRuleEntry::USE_DEFAULT_PRIORITY),
             self.fwmark.unwrap_or(0),
@@ -350,43 +353,23 @@ impl RouteRuleEntry {
         if let Some(ip) = self.ip_from.as_ref() {
             if ip.is_empty() {
                 self.ip_from = None;
-            } else {
-                let new_ip = sanitize_ip_network(ip)?;
-                if self.family.is_none() {
-                    match is_ipv6_addr(new_ip.as_str()) {
-                        true => self.family = Some(AddressFamily::IPv6),
-                        false => self.family = Some(AddressFamily::IPv4),
-                    };
-                }
-                if ip != &new_ip {
-                    log::warn!(
-                        "Route rule ip-from {} sanitized to {}",
-                        ip,
-                        new_ip
-                    );
-                    self.ip_from = Some(new_ip);
-                }
+            } else if self.family.is_none() {
+                self.family = Some(if ip.is_ipv6() {
+                    AddressFamily::IPv6
+                } else {
+                    AddressFamily::IPv4
+                });
             }
         }
         if let Some(ip) = self.ip_to.as_ref() {
             if ip.is_empty() {
                 self.ip_to = None;
-            } else {
-                let new_ip = sanitize_ip_network(ip)?;
-                if self.family.is_none() {
-                    match is_ipv6_addr(new_ip.as_str()) {
-                        true => self.family = Some(AddressFamily::IPv6),
-                        false => self.family = Some(AddressFamily::IPv4),
-                    };
-                }
-                if ip != &new_ip {
-                    log::warn!(
-                        "Route rule ip-to {} sanitized to {}",
-                        ip,
-                        new_ip
-                    );
-                    self.ip_to = Some(new_ip);
-                }
+            } else if self.family.is_none() {
+                self.family = Some(if ip.is_ipv6() {
+                    AddressFamily::IPv6
+                } else {
+                    AddressFamily::IPv4
+                });
             }
         }
         self.validate_ip_from_to()?;
@@ -517,12 +500,16 @@ pub(crate) struct MergedRouteRules {
     // The `for_verify` hold the same data as `for_apply` except the
     // auto set priority.
     pub(crate) for_verify: Vec<RouteRuleEntry>,
+    // Current route rules with priority in these ranges are managed by other
+    // tools, nmstate should neither remove nor verify them.
+    pub(crate) ignored_priorities: Vec<RangeInclusive<i64>>,
 }
 
 impl MergedRouteRules {
     pub(crate) fn new(
         desired: RouteRules,
         current: RouteRules,
+        ignored_priorities: &[RangeInclusive<i64>],
     ) -> Result<Self, NmstateError> {
         let mut for_apply: Vec<RouteRuleEntry> = Vec::new();
         let mut merged_rules: Vec<RouteRuleEntry> = Vec::new();
@@ -536,6 +523,13 @@ impl MergedRouteRules {
 
         if let Some(cur_rules) = current.config.as_ref() {
             for rule in cur_rules {
+                if is_priority_ignored(rule, ignored_priorities) {
+                    log::debug!(
+                        "Ignoring route rule {rule} as its priority is \
+                        managed outside of nmstate"
+                    );
+                    continue;
+                }
                 if des_absent_rules
                     .as_slice()
                     .iter()
@@ -559,6 +553,7 @@ impl MergedRouteRules {
                 for_apply.push(rule);
             }
         }
+        check_duplicate_rules(for_apply.as_slice())?;
 
         let for_verify = for_apply.clone();
 
@@ -569,6 +564,7 @@ impl MergedRouteRules {
             current,
             for_apply,
             for_verify,
+            ignored_priorities: ignored_priorities.to_vec(),
         })
     }
 
@@ -598,6 +594,44 @@ impl MergedRouteRules {
     }
 }
 
+// Identical desired route rules are merged with warning, while route rules
+// sharing the same priority and selector but pointing to different route
+// table or action are treated as conflict.
+fn check_duplicate_rules(rules: &[RouteRuleEntry]) -> Result<(), NmstateError> {
+    let mut checked: Vec<&RouteRuleEntry> = Vec::new();
+    for rule in rules.iter().filter(|r| !r.is_absent()) {
+        if checked.iter().any(|r| *r == rule && r.iif == rule.iif) {
+            nmstate_warn!("Route rule {rule} is defined more than once");
+            continue;
+        }
+        if let Some(conflict_rule) = checked.iter().find(|r| {
+            r.priority.is_some()
+                && r.priority != Some(RouteRuleEntry::USE_DEFAULT_PRIORITY)
+                && r.priority == rule.priority
+                && r.family == rule.family
+                && r.ip_from == rule.ip_from
+                && r.ip_to == rule.ip_to
+                && r.fwmark == rule.fwmark
+                && r.fwmask == rule.fwmask
+                && r.iif == rule.iif
+                && r.suppress_prefix_length == rule.suppress_prefix_length
+        }) {
+            let e = NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!(
+                    "Route rule {rule} conflicts with route rule \
+                    {conflict_rule}, they have the same priority and \
+                    selector but different route table or action"
+                ),
+            );
+            log::error!("{}", e);
+            return Err(e);
+        }
+        checked.push(rule);
+    }
+    Ok(())
+}
+
 fn set_auto_priority(
     for_apply: &mut [RouteRuleEntry],
     merged: &[RouteRuleEntry],
@@ -617,6 +651,17 @@ fn set_auto_priority(
     }
 }
 
+pub(crate) fn is_priority_ignored(
+    rule: &RouteRuleEntry,
+    ignored_priorities: &[RangeInclusive<i64>],
+) -> bool {
+    if let Some(priority) = rule.priority {
+        ignored_priorities.iter().any(|r| r.contains(&priority))
+    } else {
+        false
+    }
+}
+
 fn get_max_rule_priority(rules: &[RouteRuleEntry]) -> i64 {
     rules
         .iter()
@@ -0,0 +1,58 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Query only summary of running routes and route rules, generated when
+/// [crate::NetworkState::set_include_routing_summary()] set to true.
+/// Serialize and deserialize to/from `routing-summary` of
+/// [crate::NetworkState].
+/// Ignored during apply.
+///
+/// Example yaml output would be:
+///
+/// ```yml
+/// routing-summary:
+///   route-counts:
+///     254: 12
+///     500: 3
+///   route-rule-count: 2
+///   ipv4-default-gateways:
+///   - next-hop-interface: eth1
+///     next-hop-address: 192.0.2.1
+///   ipv6-default-gateways: []
+/// ```
+pub struct RoutingSummary {
+    /// Running route count indexed by route table ID.
+    pub route_counts: BTreeMap<u32, usize>,
+    /// Count of route rules.
+    pub route_rule_count: usize,
+    /// Next hops of running IPv4 default routes.
+    pub ipv4_default_gateways: Vec<RoutingSummaryGateway>,
+    /// Next hops of running IPv6 default routes.
+    pub ipv6_default_gateways: Vec<RoutingSummaryGateway>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[non_exhaustive]
+/// Next hop of default route.
+pub struct RoutingSummaryGateway {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_hop_interface: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_hop_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Route table ID, omitted for main route table.
+    pub table_id: Option<u32>,
+}
+
+impl RoutingSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
@@ -0,0 +1,110 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::NetworkState;
+
+#[derive(Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+/// Secret string like password, pre-shared key or MACsec CAK.
+/// The [std::fmt::Debug] and [std::fmt::Display] output is always
+/// `<_password_hid_by_nmstate>`, so that logs and error messages will not leak
+/// credentials.
+/// The serialization contains the real secret, please use
+/// [NetworkState::hide_secrets()] before dumping state.
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(secret: String) -> Self {
+        Self(secret)
+    }
+
+    /// The real secret string. Intentionally not implementing
+    /// [std::ops::Deref], so that every access of the real secret is
+    /// explicit.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    pub(crate) fn hidden() -> Self {
+        Self(NetworkState::PASSWORD_HID_BY_NMSTATE.to_string())
+    }
+
+    pub(crate) fn is_hidden(&self) -> bool {
+        self.0 == NetworkState::PASSWORD_HID_BY_NMSTATE
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Secret({})", NetworkState::PASSWORD_HID_BY_NMSTATE)
+    }
+}
+
+impl std::fmt::Display for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", NetworkState::PASSWORD_HID_BY_NMSTATE)
+    }
+}
+
+impl From<String> for Secret {
+    fn from(secret: String) -> Self {
+        Self(secret)
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(secret: &str) -> Self {
+        Self(secret.to_string())
+    }
+}
+
+impl From<Secret> for String {
+    fn from(secret: Secret) -> Self {
+        secret.0
+    }
+}
+
+/// Provider of secrets requested by NetworkManager during activation, for
+/// example 802.1X private key password, MACsec CAK or IPsec PSK, so that
+/// credentials could come from vault instead of being stored in
+/// NetworkManager profiles.
+/// Register via [NetworkState::set_secret_provider()].
+pub trait NmstateSecretProvider: Send + Sync {
+    /// Return secrets of specified NetworkManager setting (e.g. `802-1x`,
+    /// `macsec`, `vpn`) for specified interface as HashMap of NetworkManager
+    /// setting property name (e.g. `private-key-password`, `mka-cak`) or
+    /// VPN plugin secret name (e.g. `pskvalue`) to secret.
+    /// The `hints` holds the names of requested properties and might be
+    /// empty. Return `None` if no secret is available.
+    fn get_secrets(
+        &self,
+        iface_name: &str,
+        setting_name: &str,
+        hints: &[String],
+    ) -> Option<HashMap<String, Secret>>;
+}
+
+// Wrapper of secret provider allowing `NetworkState` to derive `Debug`,
+// `PartialEq` and `Eq`.
+#[derive(Clone)]
+pub(crate) struct SecretProviderHolder(
+    pub(crate) Arc<dyn NmstateSecretProvider>,
+);
+
+impl std::fmt::Debug for SecretProviderHolder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SecretProvider")
+    }
+}
+
+impl PartialEq for SecretProviderHolder {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SecretProviderHolder {}
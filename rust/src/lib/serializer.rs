@@ -21,3 +21,140 @@ where
         serializer.serialize_none()
     }
 }
+
+// Properties holding MAC address or list of MAC addresses.
+const MAC_ADDRESS_KEYS: [&str; 3] = [
+    "mac-address",
+    "permanent-mac-address",
+    "source-mac-addresses",
+];
+
+// Top level properties of `NetworkState` in stable order.
+const NET_STATE_KEY_ORDER: [&str; 7] = [
+    "hostname",
+    "dns-resolver",
+    "route-rules",
+    "routes",
+    "interfaces",
+    "ovs-db",
+    "ovn",
+];
+
+// Common interface properties in stable order, the remaining interface
+// specific properties are placed afterwards in alphabetical order.
+const IFACE_KEY_ORDER: [&str; 15] = [
+    "name",
+    "profile-name",
+    "description",
+    "type",
+    "state",
+    "identifier",
+    "mac-address",
+    "mtu",
+    "controller",
+    "accept-all-mac-addresses",
+    "ipv4",
+    "ipv6",
+    "mptcp",
+    "lldp",
+    "ethtool",
+];
+
+// Reorder the keys of serialized `NetworkState` to the documented stable
+// order. Properties not listed in the ordering lists and all nested mappings
+// are sorted alphabetically. Array items are kept in original order.
+pub(crate) fn net_state_value_to_stable_order(
+    value: serde_json::Value,
+) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(mut map) => {
+            let mut ret = serde_json::Map::new();
+            for key in NET_STATE_KEY_ORDER {
+                if let Some(v) = map.remove(key) {
+                    let v = if key == "interfaces" {
+                        match v {
+                            serde_json::Value::Array(ifaces) => {
+                                serde_json::Value::Array(
+                                    ifaces
+                                        .into_iter()
+                                        .map(|i| {
+                                            sort_map_keys(i, &IFACE_KEY_ORDER)
+                                        })
+                                        .collect(),
+                                )
+                            }
+                            v => sort_map_keys(v, &[]),
+                        }
+                    } else {
+                        sort_map_keys(v, &[])
+                    };
+                    ret.insert(key.to_string(), v);
+                }
+            }
+            if let serde_json::Value::Object(remains) =
+                sort_map_keys(serde_json::Value::Object(map), &[])
+            {
+                ret.extend(remains);
+            }
+            serde_json::Value::Object(ret)
+        }
+        v => v,
+    }
+}
+
+fn sort_map_keys(
+    value: serde_json::Value,
+    priorities: &[&str],
+) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(mut map) => {
+            let mut ret = serde_json::Map::new();
+            for key in priorities {
+                if let Some(v) = map.remove(*key) {
+                    ret.insert(key.to_string(), sort_map_keys(v, &[]));
+                }
+            }
+            let mut remains: Vec<(String, serde_json::Value)> =
+                map.into_iter().collect();
+            remains.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+            for (k, v) in remains {
+                ret.insert(k, sort_map_keys(v, &[]));
+            }
+            serde_json::Value::Object(ret)
+        }
+        serde_json::Value::Array(items) => serde_json::Value::Array(
+            items.into_iter().map(|v| sort_map_keys(v, &[])).collect(),
+        ),
+        v => v,
+    }
+}
+
+// Convert serialized MAC addresses to lower case. The [crate::MacAddress]
+// itself is always stored in upper case.
+pub(crate) fn lowercase_mac_addresses(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, v) in map.iter_mut() {
+                if MAC_ADDRESS_KEYS.contains(&key.as_str()) {
+                    lowercase_str_values(v);
+                } else {
+                    lowercase_mac_addresses(v);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            items.iter_mut().for_each(lowercase_mac_addresses)
+        }
+        _ => (),
+    }
+}
+
+fn lowercase_str_values(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(s) => *s = s.to_ascii_lowercase(),
+        serde_json::Value::Array(items) => {
+            items.iter_mut().for_each(lowercase_str_values)
+        }
+        _ => (),
+    }
+}
@@ -10,8 +10,11 @@ use serde::Serialize;
 // Please sort this list
 pub enum NmstateFeature {
     Dhcpv4CustomHostname,
+    Dhcpv4SendRelease,
     Dhcpv6CustomHostname,
+    Dhcpv6SendRelease,
     IfaceNameReferedBySriovVfId,
+    Ipv6Token,
     Lldp,
     MacBasedIdentifier,
     Mptcp,
@@ -19,6 +22,7 @@ pub enum NmstateFeature {
     OvsBond,
     OvsDbGlobal,
     OvsDbInterface,
+    OvsDbInterfaceOtherConfig,
     OvsDpdk,
     OvsPatch,
     Sriov,
@@ -44,6 +44,14 @@ impl BaseInterface {
         if self.ovsdb.as_ref().map(|o| !o.is_empty()) == Some(true) {
             ret.push(NmstateFeature::OvsDbInterface);
         }
+        if self
+            .ovsdb
+            .as_ref()
+            .map(|o| !o.get_other_config().is_empty())
+            == Some(true)
+        {
+            ret.push(NmstateFeature::OvsDbInterfaceOtherConfig);
+        }
         if let Some(i) = self.ipv4.as_ref() {
             ret.append(&mut i.get_features());
         }
@@ -4,20 +4,29 @@ use crate::{InterfaceIpv4, InterfaceIpv6, NmstateFeature};
 
 impl InterfaceIpv4 {
     pub(crate) fn get_features(&self) -> Vec<NmstateFeature> {
+        let mut ret = Vec::new();
         if self.dhcp_custom_hostname.is_some() {
-            vec![NmstateFeature::Dhcpv4CustomHostname]
-        } else {
-            Vec::new()
+            ret.push(NmstateFeature::Dhcpv4CustomHostname);
         }
+        if self.dhcp_send_release.is_some() {
+            ret.push(NmstateFeature::Dhcpv4SendRelease);
+        }
+        ret
     }
 }
 
 impl InterfaceIpv6 {
     pub(crate) fn get_features(&self) -> Vec<NmstateFeature> {
+        let mut ret = Vec::new();
         if self.dhcp_custom_hostname.is_some() {
-            vec![NmstateFeature::Dhcpv6CustomHostname]
-        } else {
-            Vec::new()
+            ret.push(NmstateFeature::Dhcpv6CustomHostname);
+        }
+        if self.dhcp_send_release.is_some() {
+            ret.push(NmstateFeature::Dhcpv6SendRelease);
+        }
+        if self.token.is_some() {
+            ret.push(NmstateFeature::Ipv6Token);
         }
+        ret
     }
 }
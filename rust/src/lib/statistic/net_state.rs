@@ -32,13 +32,7 @@ impl NetworkState {
         let merged_state =
             MergedNetworkState::new(self.clone(), current, false, false)?;
 
-        features.append(&mut merged_state.interfaces.get_features());
-        features.append(&mut merged_state.dns.get_features());
-        features.append(&mut merged_state.routes.get_features());
-        features.append(&mut merged_state.rules.get_features());
-        features.append(&mut merged_state.ovsdb.get_features());
-        features.append(&mut merged_state.ovn.get_features());
-        features.append(&mut merged_state.hostname.get_features());
+        features.append(&mut merged_state.get_features());
 
         features.sort_unstable();
 
@@ -48,3 +42,17 @@ impl NetworkState {
         })
     }
 }
+
+impl MergedNetworkState {
+    pub(crate) fn get_features(&self) -> Vec<NmstateFeature> {
+        let mut features = Vec::new();
+        features.append(&mut self.interfaces.get_features());
+        features.append(&mut self.dns.get_features());
+        features.append(&mut self.routes.get_features());
+        features.append(&mut self.rules.get_features());
+        features.append(&mut self.ovsdb.get_features());
+        features.append(&mut self.ovn.get_features());
+        features.append(&mut self.hostname.get_features());
+        features
+    }
+}
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::BaseInterface;
+use crate::{BaseInterface, ErrorKind, InterfaceBackend, MacAddress};
 
 #[test]
 fn test_base_iface_stringlized_attributes() {
@@ -26,5 +26,63 @@ mac-address: "d4:ee:07:25:42:5a"
     )
     .unwrap();
     iface.sanitize(true).unwrap();
-    assert_eq!(iface.mac_address, Some(String::from("D4:EE:07:25:42:5A")));
+    assert_eq!(
+        iface.mac_address.as_ref().map(|i| i.as_str()),
+        Some("D4:EE:07:25:42:5A")
+    );
+}
+
+#[test]
+fn test_base_iface_invalid_mac_address() {
+    let result = serde_yaml::from_str::<BaseInterface>(
+        r#"
+name: "eth1"
+mac-address: "d4:ee:07:25:42:5a:"
+"#,
+    );
+    assert!(result.is_err());
+    for mac in [
+        "d4:ee:07:25:42:5",
+        "d4-ee-07-25-42-5a",
+        "zz:ee:07:25:42:5a",
+        "AA",
+        "d4:ee:07:25:42",
+    ] {
+        assert_eq!(
+            mac.parse::<MacAddress>().err().map(|e| e.kind()),
+            Some(ErrorKind::InvalidArgument)
+        );
+    }
+}
+
+#[test]
+fn test_infiniband_mac_address() {
+    let mac: MacAddress =
+        "80:00:00:00:fe:80:00:00:00:00:00:00:00:1a:2b:3c:4d:5e:6f:70"
+            .parse()
+            .unwrap();
+    assert_eq!(
+        mac.as_str(),
+        "80:00:00:00:FE:80:00:00:00:00:00:00:00:1A:2B:3C:4D:5E:6F:70"
+    );
+    assert_eq!(mac.to_bytes().len(), 20);
+}
+
+#[test]
+fn test_base_iface_managed_by_ignored_when_apply() {
+    let mut iface: BaseInterface = serde_yaml::from_str(
+        r#"
+name: "eth1"
+managed-by:
+  backend: network-manager
+  profile-uuid: 5bc2a4a1-2f4d-4b5c-8e4e-6b8e4b6b9f47
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        iface.managed_by.as_ref().map(|m| m.backend),
+        Some(InterfaceBackend::NetworkManager)
+    );
+    iface.sanitize(true).unwrap();
+    assert_eq!(iface.managed_by, None);
 }
@@ -0,0 +1,182 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BatmanInterface, BatmanRoutingAlgorithm, Interface, InterfaceType,
+    Interfaces, MergedInterfaces,
+};
+
+#[test]
+fn test_batman_kernel_routing_algorithm() {
+    let iface: BatmanInterface = serde_yaml::from_str(
+        r"---
+name: bat0
+type: batman-adv
+state: up
+batman-adv:
+  routing-algorithm: BATMAN_V
+  ports:
+  - wlan0
+  - eth1
+",
+    )
+    .unwrap();
+
+    let conf = iface.batman.as_ref().unwrap();
+    assert_eq!(
+        conf.routing_algorithm,
+        Some(BatmanRoutingAlgorithm::BatmanV)
+    );
+    assert_eq!(
+        serde_yaml::to_value(conf.routing_algorithm).unwrap(),
+        serde_yaml::Value::String("batman-v".to_string())
+    );
+    assert_eq!(
+        "BATMAN_IV".parse::<BatmanRoutingAlgorithm>().unwrap(),
+        BatmanRoutingAlgorithm::BatmanIv
+    );
+    assert!("BATMAN_III".parse::<BatmanRoutingAlgorithm>().is_err());
+}
+
+#[test]
+fn test_batman_ports_get_controller() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: wlan0
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bat0
+  type: batman-adv
+  state: up
+  batman-adv:
+    routing-algorithm: batman-iv
+    port:
+    - wlan0
+    - eth1
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let merged_iface = merged_ifaces
+        .get_iface("bat0", InterfaceType::Batman)
+        .unwrap();
+    if let Some(Interface::Batman(apply_iface)) =
+        merged_iface.for_apply.as_ref()
+    {
+        assert_eq!(apply_iface.ports(), Some(vec!["eth1", "wlan0"]));
+    } else {
+        panic!("Expecting batman-adv interface for apply");
+    }
+
+    for port_name in ["eth1", "wlan0"] {
+        let apply_iface = merged_ifaces
+            .get_iface(port_name, InterfaceType::Ethernet)
+            .unwrap()
+            .for_apply
+            .as_ref()
+            .unwrap();
+        assert_eq!(
+            apply_iface.base_iface().controller,
+            Some("bat0".to_string())
+        );
+        assert_eq!(
+            apply_iface.base_iface().controller_type,
+            Some(InterfaceType::Batman)
+        );
+    }
+}
+
+#[test]
+fn test_batman_verify() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bat0
+  type: batman-adv
+  state: up
+  batman-adv:
+    routing-algorithm: batman-iv
+    port:
+    - eth1
+- name: eth1
+  type: ethernet
+  state: up
+  controller: bat0
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bat0
+  type: batman-adv
+  state: up
+  batman-adv:
+    port:
+    - eth1
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_batman_netlink_create_msg() {
+    use crate::query_apply::netlink::{
+        nl_attr_str, parse_nl_attrs, IFLA_IFNAME, IFLA_INFO_DATA,
+        IFLA_INFO_KIND, IFLA_LINKINFO,
+    };
+
+    let msg = crate::query_apply::batman::gen_batman_create_msg(
+        "bat0",
+        BatmanRoutingAlgorithm::BatmanV,
+    );
+    let bytes = msg.to_bytes(16, 0, 1);
+    // Skip nlmsghdr and ifinfomsg
+    let link_attrs = parse_nl_attrs(&bytes[32..]);
+    assert_eq!(
+        nl_attr_str(link_attrs[&IFLA_IFNAME]).as_deref(),
+        Some("bat0")
+    );
+    let link_info = parse_nl_attrs(link_attrs[&IFLA_LINKINFO]);
+    assert_eq!(
+        nl_attr_str(link_info[&IFLA_INFO_KIND]).as_deref(),
+        Some("batadv")
+    );
+    let info_data = parse_nl_attrs(link_info[&IFLA_INFO_DATA]);
+    // IFLA_BATADV_ALGO_NAME
+    assert_eq!(nl_attr_str(info_data[&1]).as_deref(), Some("BATMAN_V"));
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_batman_netlink_port_msg() {
+    use crate::query_apply::netlink::{
+        nl_attr_u32, parse_nl_attrs, IFLA_MASTER,
+    };
+
+    let msg = crate::query_apply::batman::gen_batman_port_msg(3, 7);
+    assert_eq!(msg.index, 3);
+    let bytes = msg.to_bytes(19, 0, 1);
+    let link_attrs = parse_nl_attrs(&bytes[32..]);
+    assert_eq!(nl_attr_u32(link_attrs[&IFLA_MASTER]), Some(7));
+
+    // Detach
+    let msg = crate::query_apply::batman::gen_batman_port_msg(3, 0);
+    let bytes = msg.to_bytes(19, 0, 1);
+    let link_attrs = parse_nl_attrs(&bytes[32..]);
+    assert_eq!(nl_attr_u32(link_attrs[&IFLA_MASTER]), Some(0));
+}
@@ -3,8 +3,8 @@
 use crate::{
     BondAdSelect, BondAllPortsActive, BondArpAllTargets, BondArpValidate,
     BondFailOverMac, BondInterface, BondLacpRate, BondMode,
-    BondPrimaryReselect, BondXmitHashPolicy, ErrorKind, Interface, Interfaces,
-    MergedInterface,
+    BondPrimaryReselect, BondXmitHashPolicy, ErrorKind, Interface,
+    InterfaceType, Interfaces, MergedInterface, MergedInterfaces,
 };
 
 #[test]
@@ -205,6 +205,36 @@ link-aggregation:
     }
 }
 
+#[test]
+fn test_bond_enum_ignore_case() {
+    let iface: BondInterface = serde_yaml::from_str(
+        r"---
+name: bond99
+type: bond
+state: up
+link-aggregation:
+  mode: Active_Backup
+  options:
+    arp_validate: FILTER_ACTIVE
+    fail_over_mac: Follow
+    primary_reselect: BETTER
+    xmit_hash_policy: Layer2+3
+",
+    )
+    .unwrap();
+
+    let bond_conf = iface.bond.as_ref().unwrap();
+    let opts = bond_conf.options.as_ref().unwrap();
+    assert_eq!(bond_conf.mode, Some(BondMode::ActiveBackup));
+    assert_eq!(opts.arp_validate, Some(BondArpValidate::FilterActive));
+    assert_eq!(opts.fail_over_mac, Some(BondFailOverMac::Follow));
+    assert_eq!(opts.primary_reselect, Some(BondPrimaryReselect::Better));
+    assert_eq!(opts.xmit_hash_policy, Some(BondXmitHashPolicy::Layer23));
+    assert!(serde_yaml::to_string(&iface)
+        .unwrap()
+        .contains("mode: active-backup"));
+}
+
 #[test]
 fn test_bond_stringlized_attributes() {
     let iface: BondInterface = serde_yaml::from_str(
@@ -618,3 +648,434 @@ fn test_bond_deprecated_prop() {
 
     assert_eq!(iface.ports(), Some(vec!["eth1", "eth2"]));
 }
+
+#[test]
+fn test_bond_copy_mac_from_port_perm_mac() {
+    let mut cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+- name: eth2
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+",
+    )
+    .unwrap();
+    set_perm_mac(&mut cur_ifaces, "eth1", "00:23:45:67:89:1B");
+    set_perm_mac(&mut cur_ifaces, "eth2", "00:23:45:67:89:1A");
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  copy-mac-from: eth1
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+    - eth2
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+    let merged_iface = merged_ifaces.kernel_ifaces.get("bond99").unwrap();
+
+    let apply_iface = merged_iface.for_apply.as_ref().unwrap();
+    assert_eq!(
+        apply_iface
+            .base_iface()
+            .mac_address
+            .as_ref()
+            .map(|i| i.as_str()),
+        Some("00:23:45:67:89:1B")
+    );
+    assert!(apply_iface.base_iface().copy_mac_from.is_none());
+    let verify_iface = merged_iface.for_verify.as_ref().unwrap();
+    assert_eq!(
+        verify_iface
+            .base_iface()
+            .mac_address
+            .as_ref()
+            .map(|i| i.as_str()),
+        Some("00:23:45:67:89:1B")
+    );
+}
+
+#[test]
+fn test_bond_copy_mac_from_non_port() {
+    let mut cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+    set_perm_mac(&mut cur_ifaces, "eth1", "00:23:45:67:89:1B");
+    set_perm_mac(&mut cur_ifaces, "eth2", "00:23:45:67:89:1A");
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  copy-mac-from: eth2
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_bond_copy_mac_from_with_mac_restricted_mode() {
+    let mut cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+    set_perm_mac(&mut cur_ifaces, "eth1", "00:23:45:67:89:1B");
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  copy-mac-from: eth1
+  link-aggregation:
+    mode: active-backup
+    options:
+      fail_over_mac: active
+    port:
+    - eth1
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+fn set_perm_mac(ifaces: &mut Interfaces, iface_name: &str, mac: &str) {
+    if let Some(iface) =
+        ifaces.get_iface_mut(iface_name, InterfaceType::Ethernet)
+    {
+        iface.base_iface_mut().permanent_mac_address =
+            Some(mac.parse().unwrap());
+    }
+}
+
+#[test]
+fn test_bond_ports_ordered() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  ports-ordered: true
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth2
+    - eth1
+- name: bond98
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth4
+    - eth3
+",
+    )
+    .unwrap();
+
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+- name: eth2
+  type: ethernet
+- name: eth3
+  type: ethernet
+- name: eth4
+  type: ethernet
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let ordered_ports_index = merged_ifaces.get_ordered_ports_index();
+    assert_eq!(ordered_ports_index.len(), 2);
+    assert_eq!(ordered_ports_index.get("eth2"), Some(&0));
+    assert_eq!(ordered_ports_index.get("eth1"), Some(&1));
+}
+
+#[test]
+fn test_ports_ordered_ignored_on_non_controller() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+state: up
+ports-ordered: true
+",
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+
+    assert_eq!(iface.base_iface().ports_ordered, None);
+}
+
+#[test]
+fn test_bond_active_port_not_verified() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+    - eth2
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    active-port: eth2
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    let merged_iface = merged_ifaces.kernel_ifaces.get("bond99").unwrap();
+    if let Some(Interface::Bond(apply_iface)) = merged_iface.for_apply.as_ref()
+    {
+        assert_eq!(
+            apply_iface
+                .bond
+                .as_ref()
+                .and_then(|b| b.active_port.as_deref()),
+            Some("eth2")
+        );
+    } else {
+        panic!("Expecting bond for apply");
+    }
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_bond_active_port_invalid_mode() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    mode: 802.3ad
+    port:
+    - eth1
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    active-port: eth1
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_bond_active_port_not_bond_port() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    active-port: eth2
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_bond_ns_ip6_target_compressed() {
+    let mut iface: BondInterface = serde_yaml::from_str(
+        r"---
+        name: bond99
+        type: bond
+        state: up
+        link-aggregation:
+          mode: active-backup
+          options:
+            arp_interval: 100
+            ns_ip6_target: 2001:db8:0:0::1, 2001:db8::0002
+        ",
+    )
+    .unwrap();
+    iface.sanitize().unwrap();
+    assert_eq!(
+        iface
+            .bond
+            .as_ref()
+            .and_then(|b| b.options.as_ref())
+            .and_then(|o| o.ns_ip6_target.as_deref()),
+        Some("2001:db8::1,2001:db8::2")
+    );
+}
+
+#[test]
+fn test_bond_ns_ip6_target_invalid_ipv6() {
+    let mut iface: BondInterface = serde_yaml::from_str(
+        r"---
+        name: bond99
+        type: bond
+        state: up
+        link-aggregation:
+          mode: active-backup
+          options:
+            ns_ip6_target: 192.0.2.1
+        ",
+    )
+    .unwrap();
+    let result = iface.sanitize();
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_bond_arp_missed_max_and_ns_ip6_target_invalid_mode() {
+    for opt in ["arp_missed_max: 5", "ns_ip6_target: 2001:db8::1"] {
+        let des_iface: Interface = serde_yaml::from_str(&format!(
+            r"---
+            name: bond99
+            type: bond
+            state: up
+            link-aggregation:
+              mode: 802.3ad
+              options:
+                {opt}
+            "
+        ))
+        .unwrap();
+        let mut merged_iface =
+            MergedInterface::new(Some(des_iface), None).unwrap();
+        let result = merged_iface.post_inter_ifaces_process_bond();
+        assert!(result.is_err());
+        if let Err(e) = result {
+            assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        }
+    }
+}
+
+#[test]
+fn test_bond_arp_missed_max_default_value_in_lacp_mode() {
+    let des_iface: Interface = serde_yaml::from_str(
+        r"---
+        name: bond99
+        type: bond
+        state: up
+        link-aggregation:
+          mode: 802.3ad
+          options:
+            arp_missed_max: 2
+        ",
+    )
+    .unwrap();
+    let mut merged_iface = MergedInterface::new(Some(des_iface), None).unwrap();
+    merged_iface.post_inter_ifaces_process_bond().unwrap();
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_parse_kernel_version() {
+    assert_eq!(
+        crate::query_apply::bond::parse_kernel_version(
+            "6.2.9-300.fc38.x86_64\n"
+        ),
+        Some((6, 2))
+    );
+    assert_eq!(
+        crate::query_apply::bond::parse_kernel_version("5.17"),
+        Some((5, 17))
+    );
+    assert_eq!(crate::query_apply::bond::parse_kernel_version("foo"), None);
+}
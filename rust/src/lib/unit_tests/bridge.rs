@@ -119,6 +119,7 @@ type: linux-bridge
 state: up
 bridge:
   options:
+    gc-timer: "299"
     group-forward-mask: "300"
     group-fwd-mask: "301"
     hash-max: "302"
@@ -134,6 +135,8 @@ bridge:
     multicast-snooping: "no"
     multicast-startup-query-count: "310"
     multicast-startup-query-interval: "311"
+    vlan-stats-enabled: "yes"
+    no-linklocal-learn: "true"
     stp:
       enabled: "false"
       forward-delay: "16"
@@ -186,6 +189,7 @@ bridge:
     assert_eq!(stp_opts.max_age, Some(20));
     assert_eq!(stp_opts.priority, Some(32768));
 
+    assert_eq!(opts.gc_timer, Some(299));
     assert_eq!(opts.group_forward_mask, Some(300));
     assert_eq!(opts.group_fwd_mask, Some(301));
     assert_eq!(opts.hash_max, Some(302));
@@ -201,6 +205,8 @@ bridge:
     assert_eq!(opts.multicast_snooping, Some(false));
     assert_eq!(opts.multicast_startup_query_count, Some(310));
     assert_eq!(opts.multicast_startup_query_interval, Some(311));
+    assert_eq!(opts.vlan_stats_enabled, Some(true));
+    assert_eq!(opts.no_linklocal_learn, Some(true));
 }
 
 #[test]
@@ -739,3 +745,282 @@ fn test_linux_bridge_deprecated_prop() {
 
     assert_eq!(iface.ports(), Some(vec!["eth1", "eth2"]));
 }
+
+fn gen_cur_hw_offload_bridge() -> Interfaces {
+    serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+      hw-offload: true
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_linux_bridge_port_hw_offload_unchanged() {
+    let cur_ifaces = gen_cur_hw_offload_bridge();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+      hw-offload: true
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    let merged_iface = merged_ifaces.kernel_ifaces.get("br0").unwrap();
+    if let Some(Interface::LinuxBridge(apply_iface)) =
+        merged_iface.for_apply.as_ref()
+    {
+        let port_conf =
+            &apply_iface.bridge.as_ref().unwrap().port.as_ref().unwrap()[0];
+        assert_eq!(port_conf.hw_offload, None);
+    } else {
+        panic!("Expecting linux bridge for apply");
+    }
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_linux_bridge_port_hw_offload_changed() {
+    let cur_ifaces = gen_cur_hw_offload_bridge();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+      hw-offload: false
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+fn test_linux_bridge_self_vlan_enables_vlan_filtering() {
+    let cur_iface: Interface = serde_yaml::from_str(
+        r"---
+name: br0
+type: linux-bridge
+state: up
+bridge:
+  port:
+  - name: eth1
+",
+    )
+    .unwrap();
+    let des_iface: Interface = serde_yaml::from_str(
+        r"---
+name: br0
+type: linux-bridge
+state: up
+bridge:
+  vlan:
+    mode: trunk
+    enable-native: true
+    tag: 100
+    trunk-tags:
+    - id: 101
+    - id-range:
+        min: 200
+        max: 299
+",
+    )
+    .unwrap();
+
+    let merged_iface =
+        MergedInterface::new(Some(des_iface), Some(cur_iface)).unwrap();
+
+    if let Interface::LinuxBridge(iface) = &merged_iface.merged {
+        assert!(iface.vlan_filtering_is_enabled());
+        let vlan_conf = iface.bridge.as_ref().unwrap().vlan.as_ref().unwrap();
+        assert_eq!(vlan_conf.tag, Some(100));
+        assert_eq!(
+            vlan_conf.trunk_tags.as_deref(),
+            Some(
+                [
+                    BridgePortTrunkTag::Id(101),
+                    BridgePortTrunkTag::IdRange(BridgePortVlanRange {
+                        min: 200,
+                        max: 299
+                    })
+                ]
+                .as_slice()
+            )
+        );
+    } else {
+        panic!("Expecting a LinuxBridge but got {:?}", merged_iface.merged);
+    }
+}
+
+#[test]
+fn test_linux_bridge_verify_self_vlan_removal() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port: []
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    vlan: {}
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_linux_bridge_port_vlan_ranges_compacted() {
+    let mut desired: LinuxBridgeInterface = serde_yaml::from_str(
+        r"
+        name: br0
+        type: linux-bridge
+        state: up
+        bridge:
+          port:
+            - name: eth1
+              vlan:
+                mode: trunk
+                trunk-tags:
+                  - id-range:
+                      min: 101
+                      max: 4094
+                  - id: 100
+                  - id: 2
+                  - id-range:
+                      min: 3
+                      max: 10
+                  - id: 50
+        ",
+    )
+    .unwrap();
+
+    desired.sanitize(true).unwrap();
+
+    let port_conf = &desired.bridge.as_ref().unwrap().port.as_ref().unwrap()[0];
+    assert_eq!(
+        port_conf.vlan.as_ref().unwrap().trunk_tags.as_deref(),
+        Some(
+            [
+                BridgePortTrunkTag::IdRange(BridgePortVlanRange {
+                    min: 2,
+                    max: 10
+                }),
+                BridgePortTrunkTag::Id(50),
+                BridgePortTrunkTag::IdRange(BridgePortVlanRange {
+                    min: 100,
+                    max: 4094
+                }),
+            ]
+            .as_slice()
+        )
+    );
+}
+
+#[test]
+fn test_linux_bridge_verify_port_vlan_ids_vs_range() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+      vlan:
+        mode: trunk
+        trunk-tags:
+        - id-range:
+            min: 100
+            max: 102
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+      vlan:
+        mode: trunk
+        trunk-tags:
+        - id: 102
+        - id: 100
+        - id: 101
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_linux_bridge_no_linklocal_learn_in_gen_conf() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    options:
+      no-linklocal-learn: true
+",
+    )
+    .unwrap();
+
+    let result =
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), true, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
@@ -0,0 +1,181 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CanInterface, ErrorKind, Interfaces, MergedInterfaces};
+
+#[test]
+fn test_can_stringlized_attributes() {
+    let iface: CanInterface = serde_yaml::from_str(
+        r#"---
+name: can0
+type: can
+state: up
+can:
+  bitrate: "500000"
+  sample-point: "875"
+  restart-ms: "100"
+  fd: "true"
+  data-bitrate: "2000000"
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.can.unwrap();
+    assert_eq!(conf.bitrate, Some(500000));
+    assert_eq!(conf.sample_point, Some(875));
+    assert_eq!(conf.restart_ms, Some(100));
+    assert_eq!(conf.fd, Some(true));
+    assert_eq!(conf.data_bitrate, Some(2000000));
+}
+
+#[test]
+fn test_can_invalid_sample_point() {
+    let iface: CanInterface = serde_yaml::from_str(
+        r"---
+name: can0
+type: can
+state: up
+can:
+  bitrate: 500000
+  sample-point: 1000
+",
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_can_data_bitrate_without_fd() {
+    let iface: CanInterface = serde_yaml::from_str(
+        r"---
+name: can0
+type: can
+state: up
+can:
+  bitrate: 500000
+  fd: false
+  data-bitrate: 2000000
+",
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_can_with_ip() {
+    let iface: CanInterface = serde_yaml::from_str(
+        r"---
+name: can0
+type: can
+state: up
+ipv4:
+  enabled: true
+  dhcp: true
+",
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_can_verify() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: can0
+  type: can
+  state: up
+  can:
+    bitrate: 500000
+    sample-point: 875
+    restart-ms: 0
+    fd: false
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: can0
+  type: can
+  state: up
+  can:
+    bitrate: 500000
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_can_netlink_msg_round_trip() {
+    let iface: CanInterface = serde_yaml::from_str(
+        r#"---
+name: can0
+type: can
+state: up
+can:
+  bitrate: 500000
+  sample-point: 875
+  restart-ms: 100
+  fd: true
+  data-bitrate: 2000000
+"#,
+    )
+    .unwrap();
+    let conf = iface.can.unwrap();
+
+    let msg = crate::query_apply::can::gen_can_link_msg(3, &conf);
+    let bytes = msg.to_bytes(16, 0, 1);
+    // Skip nlmsghdr and ifinfomsg
+    let parsed =
+        crate::query_apply::can::parse_can_link_attrs(&bytes[32..]).unwrap();
+
+    assert_eq!(parsed, conf);
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_can_netlink_msg_fd_off() {
+    let iface: CanInterface = serde_yaml::from_str(
+        r#"---
+name: can0
+type: can
+state: up
+can:
+  bitrate: 250000
+  fd: false
+"#,
+    )
+    .unwrap();
+    let conf = iface.can.unwrap();
+
+    let msg = crate::query_apply::can::gen_can_link_msg(3, &conf);
+    let bytes = msg.to_bytes(16, 0, 1);
+    let parsed =
+        crate::query_apply::can::parse_can_link_attrs(&bytes[32..]).unwrap();
+
+    assert_eq!(parsed.bitrate, Some(250000));
+    // Zero sample point means kernel default
+    assert_eq!(parsed.sample_point, Some(0));
+    assert_eq!(parsed.restart_ms, None);
+    assert_eq!(parsed.fd, Some(false));
+    assert_eq!(parsed.data_bitrate, None);
+}
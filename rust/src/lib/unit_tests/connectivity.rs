@@ -0,0 +1,129 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ConnectivityCheckConfig, ErrorKind, MergedConnectivityCheckConfig,
+    MergedNetworkState, NetworkState,
+};
+
+#[test]
+fn test_connectivity_check_deserialize() {
+    let state: NetworkState = serde_yaml::from_str(
+        r"---
+        connectivity-check:
+          enabled: 'false'
+          uri: http://example.org/check
+        ",
+    )
+    .unwrap();
+
+    let conf = state.connectivity_check.unwrap();
+    assert_eq!(conf.enabled, Some(false));
+    assert_eq!(conf.uri.as_deref(), Some("http://example.org/check"));
+    assert!(state.prop_list.contains(&"connectivity_check"));
+}
+
+#[test]
+fn test_connectivity_check_only_state() {
+    let state: NetworkState = serde_yaml::from_str(
+        r"---
+        connectivity-check:
+          enabled: false
+        ",
+    )
+    .unwrap();
+
+    assert!(!state.is_empty());
+    assert!(state.is_hostname_or_dns_only());
+    assert!(!state.is_hostname_only());
+}
+
+#[test]
+fn test_connectivity_check_enable_without_uri() {
+    let desired: ConnectivityCheckConfig =
+        serde_yaml::from_str("enabled: true").unwrap();
+    let current: ConnectivityCheckConfig = serde_yaml::from_str(
+        r"---
+        enabled: false
+        uri: ''
+        ",
+    )
+    .unwrap();
+
+    let result =
+        MergedConnectivityCheckConfig::new(Some(desired), Some(current));
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_connectivity_check_enable_with_current_uri() {
+    let desired: ConnectivityCheckConfig =
+        serde_yaml::from_str("enabled: true").unwrap();
+    let current: ConnectivityCheckConfig = serde_yaml::from_str(
+        r"---
+        enabled: false
+        uri: http://example.org/check
+        ",
+    )
+    .unwrap();
+
+    let merged =
+        MergedConnectivityCheckConfig::new(Some(desired), Some(current))
+            .unwrap();
+
+    assert!(merged.is_changed());
+}
+
+#[test]
+fn test_connectivity_check_verify_fail() {
+    let desired: ConnectivityCheckConfig =
+        serde_yaml::from_str("enabled: false").unwrap();
+    let current: ConnectivityCheckConfig = serde_yaml::from_str(
+        r"---
+        enabled: true
+        uri: http://example.org/check
+        ",
+    )
+    .unwrap();
+
+    let merged =
+        MergedConnectivityCheckConfig::new(Some(desired), None).unwrap();
+    let result = merged.verify(Some(&current));
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+fn test_connectivity_check_gen_revert() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        connectivity-check:
+          enabled: false
+        ",
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        connectivity-check:
+          enabled: true
+          uri: http://example.org/check
+        ",
+    )
+    .unwrap();
+
+    let revert = desired.generate_revert(&current).unwrap();
+
+    let conf = revert.connectivity_check.unwrap();
+    assert_eq!(conf.enabled, Some(true));
+    assert_eq!(conf.uri, None);
+
+    let merged =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    assert!(merged.connectivity_check.is_changed());
+}
@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, InterfaceType, MergedNetworkState, NetworkState};
+
+#[test]
+fn test_iface_defaults() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+defaults:
+  interfaces:
+  - type: ethernet
+    mtu: 9000
+    lldp:
+      enabled: true
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+  mtu: 1500
+- name: eth3
+  type: ethernet
+  state: absent
+- name: br0
+  type: linux-bridge
+  state: up
+",
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, NetworkState::new(), true, false)
+            .unwrap();
+
+    let eth1 = merged_state
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    assert_eq!(eth1.base_iface().mtu, Some(9000));
+    assert_eq!(
+        eth1.base_iface().lldp.as_ref().map(|l| l.enabled),
+        Some(true)
+    );
+    let eth2 = merged_state
+        .interfaces
+        .get_iface("eth2", InterfaceType::Ethernet)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    assert_eq!(eth2.base_iface().mtu, Some(1500));
+    let br0 = merged_state
+        .interfaces
+        .get_iface("br0", InterfaceType::LinuxBridge)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    assert_eq!(br0.base_iface().mtu, None);
+}
+
+#[test]
+fn test_iface_defaults_with_name() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+defaults:
+  interfaces:
+  - type: ethernet
+    name: eth1
+    mtu: 9000
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+
+    let result =
+        MergedNetworkState::new(desired, NetworkState::new(), true, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
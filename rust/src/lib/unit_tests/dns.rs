@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{DnsState, ErrorKind, MergedDnsState};
+use crate::{
+    DnsState, DnsVerifyOrderMode, ErrorKind, MergedDnsState,
+    MergedNetworkState, NetworkState,
+};
 
 #[test]
 fn test_dns_verify_uncompressed_srvs() {
@@ -46,7 +49,12 @@ fn test_dns_verify_uncompressed_srvs() {
     )
     .unwrap();
 
-    let merged = MergedDnsState::new(desired, DnsState::new()).unwrap();
+    let merged = MergedDnsState::new(
+        desired,
+        DnsState::new(),
+        DnsVerifyOrderMode::default(),
+    )
+    .unwrap();
 
     merged.verify(&current).unwrap();
 }
@@ -120,3 +128,155 @@ fn test_not_purge() {
     .unwrap();
     assert!(!desired.config.unwrap().is_purge());
 }
+
+fn gen_dns_verify_order_test_states() -> (DnsState, DnsState) {
+    let current: DnsState = serde_yaml::from_str(
+        r"---
+        config:
+          server:
+          - 192.0.2.251
+          - 2001:db8::1
+          - 192.0.2.250
+          - 2001:db8::2
+        ",
+    )
+    .unwrap();
+    let desired: DnsState = serde_yaml::from_str(
+        r"---
+        config:
+          server:
+          - 2001:db8::1
+          - 2001:db8::2
+          - 192.0.2.251
+          - 192.0.2.250
+        ",
+    )
+    .unwrap();
+    (desired, current)
+}
+
+#[test]
+fn test_dns_verify_order_strict() {
+    let (desired, current) = gen_dns_verify_order_test_states();
+    let merged = MergedDnsState::new(
+        desired,
+        DnsState::new(),
+        DnsVerifyOrderMode::Strict,
+    )
+    .unwrap();
+
+    let result = merged.verify(&current);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+fn test_dns_verify_order_per_family() {
+    let (desired, current) = gen_dns_verify_order_test_states();
+    let merged = MergedDnsState::new(
+        desired,
+        DnsState::new(),
+        DnsVerifyOrderMode::PerFamily,
+    )
+    .unwrap();
+
+    merged.verify(&current).unwrap();
+
+    let current: DnsState = serde_yaml::from_str(
+        r"---
+        config:
+          server:
+          - 192.0.2.250
+          - 2001:db8::1
+          - 192.0.2.251
+          - 2001:db8::2
+        ",
+    )
+    .unwrap();
+    assert!(merged.verify(&current).is_err());
+}
+
+#[test]
+fn test_dns_verify_order_ignore() {
+    let (desired, _) = gen_dns_verify_order_test_states();
+    let current: DnsState = serde_yaml::from_str(
+        r"---
+        config:
+          server:
+          - 192.0.2.250
+          - 2001:db8::2
+          - 192.0.2.251
+          - 2001:db8::1
+        ",
+    )
+    .unwrap();
+    let merged = MergedDnsState::new(
+        desired,
+        DnsState::new(),
+        DnsVerifyOrderMode::Ignore,
+    )
+    .unwrap();
+
+    merged.verify(&current).unwrap();
+}
+
+#[test]
+fn test_dns_global_not_purge_and_changed() {
+    let current: DnsState = serde_yaml::from_str(
+        r"---
+        config:
+          server:
+          - 192.0.2.251
+        ",
+    )
+    .unwrap();
+    let desired: DnsState = serde_yaml::from_str(
+        r"---
+        config:
+          global: true
+        ",
+    )
+    .unwrap();
+    assert!(!desired.config.as_ref().unwrap().is_purge());
+    let merged =
+        MergedDnsState::new(desired, current, DnsVerifyOrderMode::default())
+            .unwrap();
+
+    assert!(merged.use_global);
+    assert!(merged.is_changed());
+    assert_eq!(merged.servers, vec!["192.0.2.251".to_string()]);
+}
+
+#[test]
+fn test_dns_global_with_interface() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          ipv4:
+            enabled: true
+            dhcp: false
+            address:
+            - ip: 192.0.2.1
+              prefix-length: 24
+        dns-resolver:
+          config:
+            global: true
+            interface: eth1
+            server:
+            - 192.0.2.251
+        ",
+    )
+    .unwrap();
+    let result =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
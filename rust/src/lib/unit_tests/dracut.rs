@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, NetworkState};
+
+#[test]
+fn test_dracut_cmdline_bond_vlan_bridge() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+    - 2001:db8::1
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-interface: br0
+    next-hop-address: 192.0.2.254
+  - destination: 198.51.100.0/24
+    next-hop-interface: br0
+    next-hop-address: 192.0.2.253
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+- name: bond0
+  type: bond
+  state: up
+  mtu: 9000
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+    port:
+    - eth1
+    - eth2
+- name: bond0.100
+  type: vlan
+  state: up
+  vlan:
+    base-iface: bond0
+    id: 100
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: bond0.100
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    dhcp: true
+    autoconf: true
+    address:
+    - ip: 2001:db8::2
+      prefix-length: 64
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        desired.gen_dracut_cmdline().unwrap(),
+        "bond=bond0:eth1,eth2:mode=active-backup,miimon=100:9000 \
+        vlan=bond0.100:bond0 \
+        bridge=br0:bond0.100 \
+        ip=192.0.2.2::192.0.2.254:24::br0:none \
+        ip=br0:dhcp6 \
+        ip=[2001:db8::2]:::64::br0:none \
+        rd.route=198.51.100.0/24:192.0.2.253:br0 \
+        nameserver=192.0.2.1 \
+        nameserver=[2001:db8::1]"
+    );
+}
+
+#[test]
+fn test_dracut_cmdline_dhcp_with_mac() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1a
+  ipv4:
+    enabled: true
+    dhcp: true
+  ipv6:
+    enabled: false
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        desired.gen_dracut_cmdline().unwrap(),
+        "ip=eth1:dhcp::00:23:45:67:89:1A"
+    );
+}
+
+#[test]
+fn test_dracut_cmdline_unsupported_iface_type() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: dummy0
+  type: dummy
+  state: up
+",
+    )
+    .unwrap();
+
+    let result = desired.gen_dracut_cmdline();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+fn test_dracut_cmdline_bond_arp_ip_target() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+    options:
+      arp_interval: 100
+      arp_ip_target: 192.0.2.1,192.0.2.2
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        desired.gen_dracut_cmdline().unwrap(),
+        "bond=bond0:eth1:mode=active-backup,arp_interval=100,\
+        arp_ip_target=192.0.2.1;192.0.2.2"
+    );
+}
+
+#[test]
+fn test_dracut_cmdline_bond_opt_with_delimiter() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: 802.3ad
+    port:
+    - eth1
+    options:
+      ad_actor_system: 02:00:00:00:00:01
+",
+    )
+    .unwrap();
+
+    let result = desired.gen_dracut_cmdline();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
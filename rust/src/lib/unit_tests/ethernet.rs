@@ -1,7 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "query_apply")]
+use crate::query_apply::ethernet::SfpModuleState;
 use crate::{
-    ErrorKind, EthernetInterface, InterfaceType, Interfaces, MergedInterfaces,
+    ErrorKind, EthernetInterface, Interface, InterfaceType, Interfaces,
+    MergedInterfaces,
 };
 
 #[test]
@@ -198,3 +201,123 @@ fn test_new_veth_without_peer_config() {
         assert!(e.msg().contains("Veth interface veth1 does not exist"));
     }
 }
+
+#[test]
+fn test_new_veth_with_peer_described() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: veth1
+  type: veth
+  state: up
+  veth:
+    peer: veth1peer
+- name: veth1peer
+  type: veth
+  state: up
+  mtu: 1400
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), false, false)
+            .unwrap();
+
+    let peer_iface = merged_ifaces
+        .get_iface("veth1peer", InterfaceType::Ethernet)
+        .unwrap();
+    if let Some(Interface::Ethernet(iface)) = peer_iface.for_apply.as_ref() {
+        assert_eq!(iface.veth.as_ref().unwrap().peer.as_str(), "veth1");
+        assert_eq!(iface.base.mtu, Some(1400));
+    } else {
+        panic!(
+            "Expecting ethernet interface, got {:?}",
+            peer_iface.for_apply
+        );
+    }
+}
+
+#[test]
+fn test_veth_peer_described_with_other_peer() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: veth1
+  type: veth
+  state: up
+  veth:
+    peer: veth1peer
+- name: veth1peer
+  type: veth
+  state: up
+  veth:
+    peer: veth2
+",
+    )
+    .unwrap();
+
+    let result =
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ethernet_link_down_diagnostics() {
+    let iface: EthernetInterface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+state: up
+ethernet:
+  auto-negotiation: false
+  speed: 1000
+  duplex: full
+",
+    )
+    .unwrap();
+
+    let diag = iface.gen_link_down_diagnostics(
+        Some("0"),
+        Some("down"),
+        Some("ixgbe"),
+        SfpModuleState::Absent,
+    );
+
+    assert_eq!(
+        diag,
+        "eth1 has no carrier(link detected: no, operstate: down, \
+        driver: ixgbe, SFP module: absent, auto-negotiation: false, \
+        speed: 1000Mb/s, duplex: full)"
+    );
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ethernet_link_down_diagnostics_sysfs_not_readable() {
+    let iface: EthernetInterface = serde_yaml::from_str(
+        r"---
+name: not_exist_eth0
+type: ethernet
+state: up
+",
+    )
+    .unwrap();
+
+    let diag = iface.link_down_diagnostics().unwrap();
+
+    assert!(diag.starts_with("not_exist_eth0 carrier state unknown"));
+    assert!(!diag.contains("has no carrier"));
+    assert!(diag.contains("link detected: unknown"));
+    assert!(diag.contains("SFP module: unknown"));
+    assert!(diag.contains("speed: unknown"));
+}
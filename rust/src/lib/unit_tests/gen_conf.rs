@@ -0,0 +1,269 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::os::unix::fs::PermissionsExt;
+
+use crate::{ErrorKind, NetworkState};
+
+fn gen_test_state() -> NetworkState {
+    serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_gen_nm_keyfiles() {
+    let keyfiles = gen_test_state().gen_nm_keyfiles().unwrap();
+
+    assert_eq!(keyfiles.len(), 2);
+    let mut file_names: Vec<&str> =
+        keyfiles.iter().map(|k| k.file_name.as_str()).collect();
+    file_names.sort_unstable();
+    assert_eq!(file_names, vec!["br0.nmconnection", "eth1.nmconnection"]);
+    for keyfile in keyfiles.as_slice() {
+        assert!(!keyfile.uuid.is_empty());
+        assert!(keyfile
+            .content
+            .contains(format!("uuid={}", keyfile.uuid).as_str()));
+    }
+}
+
+#[test]
+fn test_write_nm_keyfiles() {
+    let root = std::env::temp_dir()
+        .join(format!("nmstate_test_gen_conf_{}", std::process::id()));
+    let net_state = gen_test_state();
+
+    let file_paths =
+        net_state.write_nm_keyfiles(root.to_str().unwrap()).unwrap();
+
+    assert_eq!(file_paths.len(), 2);
+    for keyfile in net_state.gen_nm_keyfiles().unwrap() {
+        let file_path = root
+            .join("etc/NetworkManager/system-connections")
+            .join(keyfile.file_name.as_str());
+        assert!(file_paths.contains(&file_path.display().to_string()));
+        let metadata = std::fs::metadata(&file_path).unwrap();
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            keyfile.content
+        );
+    }
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_apply_offline() {
+    let root = std::env::temp_dir()
+        .join(format!("nmstate_test_apply_offline_{}", std::process::id()));
+    let net_state: NetworkState = serde_yaml::from_str(
+        r"---
+hostname:
+  config: host.example.org
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+    - 2001:db8::1
+    search:
+    - example.org
+    options:
+    - rotate
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: true
+  ipv6:
+    enabled: true
+    dhcp: true
+    autoconf: true
+",
+    )
+    .unwrap();
+
+    let file_paths = net_state.apply_offline(root.to_str().unwrap()).unwrap();
+
+    let hostname_path = root.join("etc/hostname");
+    let resolv_conf_path = root.join("etc/resolv.conf");
+    assert_eq!(file_paths.len(), 3);
+    assert!(file_paths.contains(&hostname_path.display().to_string()));
+    assert!(file_paths.contains(&resolv_conf_path.display().to_string()));
+    assert_eq!(
+        std::fs::read_to_string(&hostname_path).unwrap(),
+        "host.example.org\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(&resolv_conf_path).unwrap(),
+        "# Generated by nmstate\nsearch example.org\nnameserver 192.0.2.1\n\
+        nameserver 2001:db8::1\noptions rotate\n"
+    );
+    let metadata = std::fs::metadata(&resolv_conf_path).unwrap();
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o644);
+    assert!(root
+        .join("etc/NetworkManager/system-connections/eth1.nmconnection")
+        .exists());
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+fn gen_apply_offline_dns_state() -> NetworkState {
+    serde_yaml::from_str(
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: true
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_apply_offline_keep_resolv_conf_symlink() {
+    let root = std::env::temp_dir().join(format!(
+        "nmstate_test_apply_offline_symlink_{}",
+        std::process::id()
+    ));
+    let resolv_conf_path = root.join("etc/resolv.conf");
+    std::fs::create_dir_all(root.join("etc")).unwrap();
+    std::os::unix::fs::symlink(
+        "../run/systemd/resolve/stub-resolv.conf",
+        &resolv_conf_path,
+    )
+    .unwrap();
+
+    let file_paths = gen_apply_offline_dns_state()
+        .apply_offline(root.to_str().unwrap())
+        .unwrap();
+
+    assert!(!file_paths.contains(&resolv_conf_path.display().to_string()));
+    assert!(std::fs::symlink_metadata(&resolv_conf_path)
+        .unwrap()
+        .file_type()
+        .is_symlink());
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_apply_offline_keep_resolv_conf_not_owned() {
+    let root = std::env::temp_dir().join(format!(
+        "nmstate_test_apply_offline_not_owned_{}",
+        std::process::id()
+    ));
+    let resolv_conf_path = root.join("etc/resolv.conf");
+    std::fs::create_dir_all(root.join("etc")).unwrap();
+    std::fs::write(&resolv_conf_path, "nameserver 198.51.100.1\n").unwrap();
+
+    let file_paths = gen_apply_offline_dns_state()
+        .apply_offline(root.to_str().unwrap())
+        .unwrap();
+
+    assert!(!file_paths.contains(&resolv_conf_path.display().to_string()));
+    assert_eq!(
+        std::fs::read_to_string(&resolv_conf_path).unwrap(),
+        "nameserver 198.51.100.1\n"
+    );
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+fn gen_verify_test_state() -> NetworkState {
+    serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: bond0
+  type: bond
+  state: up
+  mtu: 9000
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    dhcp: true
+    autoconf: true
+    address:
+    - ip: 2001:db8::1
+      prefix-length: 64
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_verify_nm_keyfiles() {
+    let net_state = gen_verify_test_state();
+    let keyfiles = net_state.gen_nm_keyfiles().unwrap();
+    let contents: Vec<&str> =
+        keyfiles.iter().map(|k| k.content.as_str()).collect();
+
+    net_state.verify_nm_keyfiles(contents.as_slice()).unwrap();
+}
+
+#[test]
+fn test_verify_nm_keyfiles_ip_mismatch() {
+    let net_state = gen_verify_test_state();
+    let keyfiles = net_state.gen_nm_keyfiles().unwrap();
+    let contents: Vec<String> = keyfiles
+        .iter()
+        .map(|k| k.content.replace("192.0.2.1/24", "192.0.2.2/24"))
+        .collect();
+    let contents: Vec<&str> = contents.iter().map(String::as_str).collect();
+
+    let result = net_state.verify_nm_keyfiles(contents.as_slice());
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert!(e.msg().contains("192.0.2.2/24"));
+    }
+}
+
+#[test]
+fn test_verify_nm_keyfiles_missing_port() {
+    let net_state = gen_verify_test_state();
+    let keyfiles = net_state.gen_nm_keyfiles().unwrap();
+    let contents: Vec<&str> = keyfiles
+        .iter()
+        .filter(|k| k.file_name != "eth1.nmconnection")
+        .map(|k| k.content.as_str())
+        .collect();
+
+    let result = net_state.verify_nm_keyfiles(contents.as_slice());
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert!(e.msg().contains("eth1"));
+    }
+}
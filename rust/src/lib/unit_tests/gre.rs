@@ -0,0 +1,115 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, GreInterface, GretapInterface, Interface};
+
+#[test]
+fn test_gre_stringlized_attributes() {
+    let iface: GreInterface = serde_yaml::from_str(
+        r#"---
+name: gre1
+type: gre
+state: up
+gre:
+  base-iface: eth1
+  local: 192.0.2.1
+  remote: 192.0.2.2
+  ikey: "100"
+  okey: "200"
+  ttl: "64"
+  tos: "1"
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.gre.unwrap();
+    assert_eq!(conf.base_iface.as_deref(), Some("eth1"));
+    assert_eq!(conf.local, Some("192.0.2.1".parse().unwrap()));
+    assert_eq!(conf.remote, Some("192.0.2.2".parse().unwrap()));
+    assert_eq!(conf.ikey, Some(100));
+    assert_eq!(conf.okey, Some(200));
+    assert_eq!(conf.ttl, Some(64));
+    assert_eq!(conf.tos, Some(1));
+}
+
+#[test]
+fn test_gretap_parent() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: gretap1
+type: gretap
+state: up
+gretap:
+  base-iface: eth1
+  remote: 192.0.2.2
+",
+    )
+    .unwrap();
+
+    assert!(matches!(iface, Interface::Gretap(_)));
+    assert_eq!(iface.parent(), Some("eth1"));
+}
+
+#[test]
+fn test_gre_ipv6_endpoint() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: gre1
+type: gre
+state: up
+gre:
+  local: 192.0.2.1
+  remote: 2001:db8::2
+",
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_gretap_ipv6_endpoint_ignored_for_current() {
+    let mut iface = GretapInterface::new();
+    iface.base.name = "gretap1".to_string();
+    iface.gretap = Some(serde_yaml::from_str("remote: 2001:db8::2").unwrap());
+    let mut iface = Interface::Gretap(iface);
+
+    assert!(iface.sanitize(false).is_ok());
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_gre_gen_nm_keyfile() {
+    let net_state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: gre1
+  type: gre
+  state: up
+  mtu: 1400
+  gre:
+    local: 192.0.2.1
+    remote: 192.0.2.2
+    ikey: 100
+    ttl: 64
+",
+    )
+    .unwrap();
+
+    let keyfiles = net_state.gen_nm_keyfiles().unwrap();
+
+    assert_eq!(keyfiles.len(), 1);
+    let content = keyfiles[0].content.as_str();
+    assert!(content.contains("type=ip-tunnel"));
+    assert!(content.contains("[ip-tunnel]"));
+    assert!(content.contains("mode=2"));
+    assert!(content.contains("local=192.0.2.1"));
+    assert!(content.contains("remote=192.0.2.2"));
+    assert!(content.contains("input-key=100"));
+    assert!(content.contains("ttl=64"));
+    assert!(content.contains("mtu=1400"));
+    assert!(!content.contains("[ethernet]"));
+}
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{HostNameState, NetworkState};
+
+#[test]
+fn test_hostname_running_follow_config() {
+    let desired: HostNameState =
+        serde_yaml::from_str("config: host-a").unwrap();
+
+    let effective = desired.gen_effective(false);
+
+    assert_eq!(effective.running.as_deref(), Some("host-a"));
+    assert_eq!(effective.config.as_deref(), Some("host-a"));
+}
+
+#[test]
+fn test_hostname_running_take_precedence() {
+    let desired: HostNameState = serde_yaml::from_str(
+        r"---
+        running: host-b
+        config: host-a
+        ",
+    )
+    .unwrap();
+
+    let effective = desired.gen_effective(false);
+
+    assert_eq!(effective.running.as_deref(), Some("host-b"));
+    assert_eq!(effective.config.as_deref(), Some("host-a"));
+}
+
+#[test]
+fn test_hostname_memory_only_ignore_config() {
+    let desired: HostNameState =
+        serde_yaml::from_str("config: host-a").unwrap();
+
+    let effective = desired.gen_effective(true);
+
+    assert_eq!(effective.running.as_deref(), Some("host-a"));
+    assert_eq!(effective.config, None);
+}
+
+#[test]
+fn test_hostname_remove_config_not_touch_running() {
+    let desired: HostNameState = serde_yaml::from_str("config: ''").unwrap();
+
+    let effective = desired.gen_effective(false);
+
+    assert_eq!(effective.running, None);
+    assert_eq!(effective.config.as_deref(), Some(""));
+}
+
+#[test]
+fn test_is_hostname_only() {
+    let state: NetworkState = serde_yaml::from_str(
+        r"---
+        hostname:
+          config: host-a
+        ",
+    )
+    .unwrap();
+    assert!(state.is_hostname_only());
+
+    let state: NetworkState = serde_yaml::from_str(
+        r"---
+        hostname:
+          config: host-a
+        dns-resolver:
+          config:
+            server:
+            - 192.0.2.1
+        ",
+    )
+    .unwrap();
+    assert!(!state.is_hostname_only());
+}
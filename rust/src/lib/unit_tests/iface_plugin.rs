@@ -0,0 +1,260 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::{Arc, Mutex};
+
+use crate::{
+    CustomInterface, ErrorKind, Interface, InterfaceState, InterfaceType,
+    MergedNetworkState, NetworkState, NmstateError, NmstateInterfacePlugin,
+};
+
+struct TestIfacePlugin {
+    iface_type: &'static str,
+}
+
+impl NmstateInterfacePlugin for TestIfacePlugin {
+    fn iface_type(&self) -> &str {
+        self.iface_type
+    }
+
+    fn config_section(&self) -> &str {
+        "foo-config"
+    }
+
+    fn validate(&self, iface: &CustomInterface) -> Result<(), NmstateError> {
+        if iface
+            .config
+            .as_ref()
+            .and_then(|c| c.get("mode"))
+            .and_then(|m| m.as_str())
+            == Some("invalid")
+        {
+            Err(NmstateError::new(
+                ErrorKind::InvalidArgument,
+                format!("Invalid mode of {}", iface.base.name),
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn retrieve(&self) -> Result<Vec<CustomInterface>, NmstateError> {
+        Ok(Vec::new())
+    }
+
+    fn apply(
+        &self,
+        _iface: &CustomInterface,
+        _current: Option<&CustomInterface>,
+    ) -> Result<(), NmstateError> {
+        Ok(())
+    }
+
+    fn delete(&self, _iface_name: &str) -> Result<(), NmstateError> {
+        Ok(())
+    }
+}
+
+fn new_test_plugin() -> Arc<TestIfacePlugin> {
+    Arc::new(TestIfacePlugin { iface_type: "foo" })
+}
+
+fn new_cur_foo_iface(plugin: &TestIfacePlugin) -> Interface {
+    let mut iface = CustomInterface::new(plugin);
+    iface.base.name = "foo0".to_string();
+    iface.base.state = InterfaceState::Up;
+    iface.config = Some(serde_json::json!({"mode": "fast", "speed": 100}));
+    Interface::Custom(iface)
+}
+
+#[test]
+fn test_iface_plugin_merge_and_verify() {
+    let plugin = new_test_plugin();
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: foo0
+  type: foo
+  state: up
+  foo-config:
+    mode: slow
+",
+    )
+    .unwrap();
+    desired.register_iface_plugin(plugin.clone());
+
+    let mut current = NetworkState::new();
+    current.interfaces.push(new_cur_foo_iface(&plugin));
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    let merged_iface = merged_state
+        .interfaces
+        .get_iface("foo0", InterfaceType::Other("foo".to_string()))
+        .unwrap();
+    assert!(merged_iface.is_changed());
+    if let Interface::Custom(iface) = &merged_iface.merged {
+        assert_eq!(
+            iface.config,
+            Some(serde_json::json!({"mode": "slow", "speed": 100}))
+        );
+    } else {
+        panic!("Expecting custom interface, got {:?}", merged_iface.merged);
+    }
+
+    let mut new_current = NetworkState::new();
+    new_current.interfaces.push(merged_iface.merged.clone());
+    merged_state
+        .interfaces
+        .verify(&new_current.interfaces)
+        .unwrap();
+
+    let mut old_current = NetworkState::new();
+    old_current.interfaces.push(new_cur_foo_iface(&plugin));
+    let result = merged_state.interfaces.verify(&old_current.interfaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[test]
+fn test_iface_plugin_serialize() {
+    let plugin = new_test_plugin();
+    let iface = new_cur_foo_iface(&plugin);
+    let value = serde_json::to_value(&iface).unwrap();
+    assert_eq!(value["type"], serde_json::json!("foo"));
+    assert_eq!(value["foo-config"]["mode"], serde_json::json!("fast"));
+}
+
+#[test]
+fn test_iface_plugin_validate() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: foo0
+  type: foo
+  state: up
+  foo-config:
+    mode: invalid
+",
+    )
+    .unwrap();
+    desired.register_iface_plugin(new_test_plugin());
+
+    let result =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_iface_plugin_conflict_with_builtin_type() {
+    let mut desired = NetworkState::new();
+    desired.register_iface_plugin(Arc::new(TestIfacePlugin {
+        iface_type: "bond",
+    }));
+
+    let result =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_iface_plugin_iface_as_bond_port_and_vlan_parent() {
+    let plugin = new_test_plugin();
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - foo0
+- name: bond0.100
+  type: vlan
+  state: up
+  vlan:
+    base-iface: foo0
+    id: 100
+",
+    )
+    .unwrap();
+    desired.register_iface_plugin(plugin.clone());
+
+    let mut current = NetworkState::new();
+    current.interfaces.push(new_cur_foo_iface(&plugin));
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    let merged_iface =
+        merged_state.interfaces.kernel_ifaces.get("foo0").unwrap();
+    assert!(merged_iface.is_changed());
+    if let Interface::Custom(iface) = &merged_iface.merged {
+        assert_eq!(iface.base.controller.as_deref(), Some("bond0"));
+    } else {
+        panic!("Expecting custom interface, got {:?}", merged_iface.merged);
+    }
+}
+
+struct RecordIfacePlugin {
+    ifaces: Mutex<Vec<CustomInterface>>,
+}
+
+impl NmstateInterfacePlugin for RecordIfacePlugin {
+    fn iface_type(&self) -> &str {
+        "foo"
+    }
+
+    fn retrieve(&self) -> Result<Vec<CustomInterface>, NmstateError> {
+        Ok(self.ifaces.lock().unwrap().clone())
+    }
+
+    fn apply(
+        &self,
+        iface: &CustomInterface,
+        _current: Option<&CustomInterface>,
+    ) -> Result<(), NmstateError> {
+        let mut ifaces = self.ifaces.lock().unwrap();
+        ifaces.retain(|i| i.base.name != iface.base.name);
+        ifaces.push(iface.clone());
+        Ok(())
+    }
+
+    fn delete(&self, iface_name: &str) -> Result<(), NmstateError> {
+        self.ifaces
+            .lock()
+            .unwrap()
+            .retain(|i| i.base.name != iface_name);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_iface_plugin_default_rollback() {
+    let plugin = RecordIfacePlugin {
+        ifaces: Mutex::new(Vec::new()),
+    };
+    let mut foo0 = CustomInterface::new(&plugin);
+    foo0.base.name = "foo0".to_string();
+    foo0.config = Some(serde_json::json!({"mode": "fast"}));
+    plugin.apply(&foo0, None).unwrap();
+    let previous = plugin.retrieve().unwrap();
+
+    let mut changed_foo0 = foo0.clone();
+    changed_foo0.config = Some(serde_json::json!({"mode": "slow"}));
+    plugin.apply(&changed_foo0, Some(&foo0)).unwrap();
+    let mut foo1 = CustomInterface::new(&plugin);
+    foo1.base.name = "foo1".to_string();
+    plugin.apply(&foo1, None).unwrap();
+
+    plugin.rollback(previous.as_slice()).unwrap();
+
+    assert_eq!(plugin.retrieve().unwrap(), vec![foo0]);
+}
@@ -2,13 +2,33 @@
 
 use crate::{
     unit_tests::testlib::{
-        new_eth_iface, new_ovs_br_iface, new_ovs_iface, new_unknown_iface,
-        new_vlan_iface,
+        bond_with_ports, new_bond_iface, new_eth_iface, new_ovs_br_iface,
+        new_ovs_iface, new_unknown_iface, new_vlan_iface,
     },
-    BondMode, Interface, InterfaceState, InterfaceType, Interfaces,
-    MergedInterfaces,
+    BondMode, ErrorKind, Interface, InterfaceState, InterfaceType, Interfaces,
+    MergedInterfaces, NetworkState,
 };
 
+#[test]
+fn test_iface_type_and_state_ignore_case() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: br0
+type: Linux_Bridge
+state: ABSENT
+",
+    )
+    .unwrap();
+
+    assert_eq!(iface.iface_type(), InterfaceType::LinuxBridge);
+    assert_eq!(iface.base_iface().state, InterfaceState::Absent);
+
+    assert_eq!(
+        serde_yaml::from_str::<InterfaceType>("Foo_Bar").unwrap(),
+        InterfaceType::Other("Foo_Bar".to_string())
+    );
+}
+
 #[test]
 fn test_resolve_unknown_type_absent_eth() {
     let mut cur_ifaces = Interfaces::new();
@@ -355,3 +375,247 @@ fn test_ifaces_iter_mut() {
     assert_eq!(ifaces_vec[0].base_iface().mtu, Some(1280));
     assert_eq!(ifaces_vec[1].base_iface().mtu, Some(1280));
 }
+
+#[test]
+fn test_present_iface_kept_as_is() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+    cur_ifaces.push(new_vlan_iface("eth1.10", "eth1", 10));
+
+    let mut ifaces = Interfaces::new();
+    let mut iface = new_unknown_iface("eth1.10");
+    iface.base_iface_mut().state = InterfaceState::Present;
+    ifaces.push(iface);
+
+    let merged_ifaces =
+        MergedInterfaces::new(ifaces, cur_ifaces, false, false).unwrap();
+
+    let iface = merged_ifaces
+        .get_iface("eth1.10", InterfaceType::Vlan)
+        .unwrap();
+    assert!(!iface.is_desired());
+    assert!(!iface.is_changed());
+    assert!(iface.merged.is_up());
+    assert_eq!(
+        merged_ifaces.present_ifaces,
+        vec![("eth1.10".to_string(), InterfaceType::Vlan)]
+    );
+}
+
+#[test]
+fn test_present_iface_not_exist() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+
+    let mut ifaces = Interfaces::new();
+    let mut iface = new_unknown_iface("eth2");
+    iface.base_iface_mut().state = InterfaceState::Present;
+    ifaces.push(iface);
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_present_iface_with_absent_parent() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+    cur_ifaces.push(new_eth_iface("eth2"));
+    cur_ifaces.push(bond_with_ports("bond0", &["eth1", "eth2"]));
+    cur_ifaces.push(new_vlan_iface("bond0.10", "bond0", 10));
+
+    let mut ifaces = Interfaces::new();
+    let mut iface = new_bond_iface("bond0");
+    iface.base_iface_mut().state = InterfaceState::Absent;
+    ifaces.push(iface);
+    let mut iface = new_unknown_iface("bond0.10");
+    iface.base_iface_mut().state = InterfaceState::Present;
+    ifaces.push(iface);
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_mac_vlan_over_bond_port() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+
+    let mut ifaces = Interfaces::new();
+    ifaces.push(bond_with_ports("bond0", &["eth1"]));
+    ifaces.push(
+        serde_yaml::from_str(
+            r"---
+name: mac0
+type: mac-vlan
+state: up
+mac-vlan:
+  base-iface: eth1
+  mode: vepa
+",
+        )
+        .unwrap(),
+    );
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("port of bond"));
+    }
+}
+
+#[test]
+fn test_vlan_over_bond_port() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+
+    let mut ifaces = Interfaces::new();
+    ifaces.push(bond_with_ports("bond0", &["eth1"]));
+    ifaces.push(new_vlan_iface("eth1.10", "eth1", 10));
+
+    assert!(MergedInterfaces::new(ifaces, cur_ifaces, false, false).is_ok());
+}
+
+#[test]
+fn test_vlan_over_loopback() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(
+        serde_yaml::from_str(
+            r"---
+name: lo
+type: loopback
+state: up
+",
+        )
+        .unwrap(),
+    );
+
+    let mut ifaces = Interfaces::new();
+    ifaces.push(new_vlan_iface("lo.10", "lo", 10));
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_iface_deserialize_error_include_path_and_name() {
+    let result = serde_yaml::from_str::<NetworkState>(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+    - ip: 192.0.2.2
+      prefix-length: abc
+",
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        let msg = e.to_string();
+        assert!(msg.contains(
+            "interfaces[1].ipv4.address[1].prefix-length of interface eth2:"
+        ));
+    }
+}
+
+#[test]
+fn test_iface_deserialize_error_of_ipv4_custom_check() {
+    let result = serde_yaml::from_str::<Interfaces>(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    autoconf: true
+",
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert!(e.to_string().contains(
+            "interfaces[0].ipv4.autoconf of interface eth1: \
+            autoconf is not allowed for IPv4"
+        ));
+    }
+}
+
+#[test]
+fn test_iface_deserialize_error_of_try_from_conversion() {
+    let result = serde_yaml::from_str::<Interfaces>(
+        r"---
+- name: bond99
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+      fail_over_mac: invalid_value
+",
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert!(e.to_string().contains(
+            "interfaces[0].link-aggregation.options.fail_over_mac of \
+            interface bond99:"
+        ));
+    }
+}
+
+#[test]
+fn test_vlan_over_team() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(
+        serde_yaml::from_str(
+            r"---
+name: team0
+type: team
+state: up
+",
+        )
+        .unwrap(),
+    );
+
+    let mut ifaces = Interfaces::new();
+    ifaces.push(new_vlan_iface("team0.10", "team0", 10));
+
+    assert!(MergedInterfaces::new(ifaces, cur_ifaces, false, false).is_ok());
+}
+
+#[test]
+fn test_vlan_over_other_iface_type() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(
+        serde_yaml::from_str(
+            r"---
+name: wg0
+type: wireguard
+state: up
+",
+        )
+        .unwrap(),
+    );
+
+    let mut ifaces = Interfaces::new();
+    ifaces.push(new_vlan_iface("wg0.10", "wg0", 10));
+
+    assert!(MergedInterfaces::new(ifaces, cur_ifaces, false, false).is_ok());
+}
@@ -2,8 +2,9 @@
 
 use crate::{
     unit_tests::testlib::{
-        bond_with_ports, bridge_with_ports, new_br_iface, new_eth_iface,
-        new_nested_4_ifaces, new_ovs_br_iface, new_ovs_iface,
+        bond_with_ports, bridge_with_ports, new_bond_iface, new_br_iface,
+        new_eth_iface, new_nested_4_ifaces, new_ovs_br_iface, new_ovs_iface,
+        new_vlan_iface,
     },
     ErrorKind, Interface, InterfaceState, InterfaceType, Interfaces,
     MergedInterfaces, OvsBridgeInterface,
@@ -859,3 +860,92 @@ fn test_gen_topoligies_ovs_bridge() {
         .join(" -> ")]
     );
 }
+
+#[test]
+fn test_port_controller_not_exist() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_br_iface("br0"));
+    cur_ifaces.push(new_eth_iface("eth1"));
+
+    let mut ifaces = Interfaces::new();
+    let mut iface = new_eth_iface("eth1");
+    iface.base_iface_mut().controller = Some("br1".to_string());
+    ifaces.push(iface);
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("br1"));
+    }
+}
+
+#[test]
+fn test_port_controller_marked_absent() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_br_iface("br0"));
+    cur_ifaces.push(new_eth_iface("eth1"));
+
+    let mut ifaces = Interfaces::new();
+    let mut br_iface = new_br_iface("br0");
+    br_iface.base_iface_mut().state = InterfaceState::Absent;
+    ifaces.push(br_iface);
+    let mut iface = new_eth_iface("eth1");
+    iface.base_iface_mut().controller = Some("br0".to_string());
+    ifaces.push(iface);
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("absent"));
+    }
+}
+
+#[test]
+fn test_controller_port_cycle() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+
+    let mut ifaces = Interfaces::new();
+    ifaces.push(bridge_with_ports("br0", &["eth1", "br0.10"]));
+    ifaces.push(new_vlan_iface("br0.10", "br0", 10));
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("br0 -> br0.10 -> br0"));
+    }
+}
+
+#[test]
+fn test_absent_ifaces_teardown_order() {
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+    cur_ifaces.push(new_eth_iface("eth2"));
+    cur_ifaces.push(bond_with_ports("bond0", &["eth1", "eth2"]));
+    cur_ifaces.push(new_vlan_iface("bond0.10", "bond0", 10));
+
+    let mut ifaces = Interfaces::new();
+    for mut iface in [
+        new_eth_iface("eth1"),
+        new_bond_iface("bond0"),
+        new_eth_iface("eth2"),
+        new_vlan_iface("bond0.10", "bond0", 10),
+    ] {
+        iface.base_iface_mut().state = InterfaceState::Absent;
+        ifaces.push(iface);
+    }
+
+    let merged_ifaces =
+        MergedInterfaces::new(ifaces, cur_ifaces, false, false).unwrap();
+
+    let iface_names: Vec<&str> = merged_ifaces
+        .get_absent_ifaces_in_teardown_order()
+        .iter()
+        .map(|i| i.merged.name())
+        .collect();
+
+    assert_eq!(iface_names, vec!["bond0.10", "bond0", "eth1", "eth2"]);
+}
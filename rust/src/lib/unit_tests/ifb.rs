@@ -0,0 +1,121 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Interface, InterfaceType, Interfaces, MergedInterfaces};
+
+#[test]
+fn test_ifb_iface_deserialize() {
+    let ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ifb0
+  type: ifb
+  state: up
+  mtu: 1500
+",
+    )
+    .unwrap();
+
+    let iface = ifaces.get_iface("ifb0", InterfaceType::Ifb).unwrap();
+    assert!(matches!(iface, Interface::Ifb(_)));
+    assert_eq!(iface.base_iface().mtu, Some(1500));
+    assert_eq!(
+        serde_yaml::to_value(iface.iface_type()).unwrap(),
+        serde_yaml::Value::String("ifb".to_string())
+    );
+}
+
+#[test]
+fn test_ifb_verify() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ifb0
+  type: ifb
+  state: up
+  mtu: 1500
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ifb0
+  type: ifb
+  state: up
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ifb_netlink_create_msg() {
+    use crate::query_apply::netlink::{
+        nl_attr_str, nl_attr_u32, parse_nl_attrs, IFLA_ADDRESS, IFLA_IFNAME,
+        IFLA_INFO_KIND, IFLA_LINKINFO, IFLA_MTU,
+    };
+    use crate::IfbInterface;
+
+    let iface: IfbInterface = serde_yaml::from_str(
+        r"---
+name: ifb0
+type: ifb
+state: up
+mac-address: 1E:E4:0A:26:9C:0B
+mtu: 1400
+",
+    )
+    .unwrap();
+
+    let msg = crate::query_apply::ifb::gen_ifb_link_msg(0, &iface).unwrap();
+    let bytes = msg.to_bytes(16, 0, 1);
+    // Skip nlmsghdr and ifinfomsg
+    let link_attrs = parse_nl_attrs(&bytes[32..]);
+    assert_eq!(
+        nl_attr_str(link_attrs[&IFLA_IFNAME]).as_deref(),
+        Some("ifb0")
+    );
+    let link_info = parse_nl_attrs(link_attrs[&IFLA_LINKINFO]);
+    assert_eq!(
+        nl_attr_str(link_info[&IFLA_INFO_KIND]).as_deref(),
+        Some("ifb")
+    );
+    assert_eq!(nl_attr_u32(link_attrs[&IFLA_MTU]), Some(1400));
+    assert_eq!(
+        link_attrs[&IFLA_ADDRESS],
+        &[0x1e, 0xe4, 0x0a, 0x26, 0x9c, 0x0b]
+    );
+    assert_eq!(msg.flags, 1);
+    assert_eq!(msg.change, 1);
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ifb_netlink_set_msg() {
+    use crate::query_apply::netlink::{
+        nl_attr_u32, parse_nl_attrs, IFLA_IFNAME, IFLA_LINKINFO, IFLA_MTU,
+    };
+    use crate::IfbInterface;
+
+    let iface: IfbInterface = serde_yaml::from_str(
+        r"---
+name: ifb0
+type: ifb
+state: down
+mtu: 1500
+",
+    )
+    .unwrap();
+
+    let msg = crate::query_apply::ifb::gen_ifb_link_msg(5, &iface).unwrap();
+    assert_eq!(msg.index, 5);
+    let bytes = msg.to_bytes(19, 0, 1);
+    let link_attrs = parse_nl_attrs(&bytes[32..]);
+    assert!(!link_attrs.contains_key(&IFLA_IFNAME));
+    assert!(!link_attrs.contains_key(&IFLA_LINKINFO));
+    assert_eq!(nl_attr_u32(link_attrs[&IFLA_MTU]), Some(1500));
+    assert_eq!(msg.flags, 0);
+    assert_eq!(msg.change, 1);
+}
@@ -1,8 +1,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    ip::sanitize_ip_network, unit_tests::testlib::new_eth_iface, BaseInterface,
-    ErrorKind, Interface, InterfaceState, Interfaces, MergedInterfaces,
+    ip::{sanitize_ip_network, warn_wait_ip_missing_family},
+    unit_tests::testlib::new_eth_iface,
+    warning::{start_warning_collection, stop_warning_collection},
+    BaseInterface, Dhcpv4ClientId, Dhcpv6Mode, ErrorKind, Interface,
+    InterfaceIpAddr, InterfaceIpv4, InterfaceIpv6, InterfaceState, Interfaces,
+    MergedInterfaces, NetworkState, WaitIp,
 };
 
 fn gen_test_eth_ifaces() -> Interfaces {
@@ -51,6 +55,52 @@ ipv6:
     assert_eq!(ipv6_conf.addresses.as_deref().unwrap()[0].prefix_length, 64);
 }
 
+#[test]
+fn test_ip_address_in_cidr_string() {
+    let iface: BaseInterface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+ipv4:
+  enabled: true
+  address:
+  - 192.0.2.5/24
+  - ip: 192.0.2.6
+    prefix-length: 24
+  - 192.0.2.7
+ipv6:
+  enabled: true
+  address:
+  - 2001:db8:1::5/64
+"#,
+    )
+    .unwrap();
+    let ipv4_addrs = iface.ipv4.as_ref().unwrap().addresses.as_deref().unwrap();
+    let ipv6_addrs = iface.ipv6.as_ref().unwrap().addresses.as_deref().unwrap();
+
+    assert_eq!(ipv4_addrs[0].to_string(), "192.0.2.5/24");
+    assert_eq!(ipv4_addrs[1].to_string(), "192.0.2.6/24");
+    assert_eq!(ipv4_addrs[2].to_string(), "192.0.2.7/32");
+    assert_eq!(ipv6_addrs[0].to_string(), "2001:db8:1::5/64");
+}
+
+#[test]
+fn test_ip_address_invalid_cidr_string() {
+    let result = serde_yaml::from_str::<BaseInterface>(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+ipv4:
+  enabled: true
+  address:
+  - 192.0.2.5/abc
+"#,
+    );
+    assert!(result.is_err());
+}
+
 #[test]
 fn test_ip_ignore_deserialize_error_of_absent_iface() {
     let iface: Interface = serde_yaml::from_str(
@@ -462,6 +512,88 @@ fn test_sanitize_ip_network_ipv6_net() {
     );
 }
 
+#[test]
+fn test_ip_addr_network_and_contains() {
+    let addr = InterfaceIpAddr::try_from("192.0.3.1/23").unwrap();
+    assert_eq!(addr.network().as_str(), "192.0.2.0/23");
+    assert!(addr.contains(&"192.0.2.254".parse().unwrap()));
+    assert!(!addr.contains(&"192.0.4.1".parse().unwrap()));
+    assert!(!addr.contains(&"2001:db8:1::1".parse().unwrap()));
+
+    let addr = InterfaceIpAddr::try_from("2001:db8:1::f/64").unwrap();
+    assert_eq!(addr.network().as_str(), "2001:db8:1::/64");
+    assert!(addr.contains(&"2001:db8:1::ffff".parse().unwrap()));
+    assert!(!addr.contains(&"2001:db8:2::1".parse().unwrap()));
+}
+
+#[test]
+fn test_ip_addr_subnet_overlap() {
+    let addr = InterfaceIpAddr::try_from("192.0.2.1/24").unwrap();
+    let other = InterfaceIpAddr::try_from("192.0.2.129/25").unwrap();
+    assert!(addr.is_subnet_overlap(&other));
+    assert!(other.is_subnet_overlap(&addr));
+    let other = InterfaceIpAddr::try_from("192.0.3.1/32").unwrap();
+    assert!(!addr.is_subnet_overlap(&other));
+    let other = InterfaceIpAddr::try_from("2001:db8:1::1/0").unwrap();
+    assert!(!addr.is_subnet_overlap(&other));
+    assert_eq!(
+        InterfaceIpAddr::mask_ip(&"192.0.2.1".parse().unwrap(), 0),
+        "0.0.0.0".parse::<std::net::IpAddr>().unwrap()
+    );
+}
+
+#[test]
+fn test_ip_addr_broadcast() {
+    let addr = InterfaceIpAddr::try_from("192.0.2.1/24").unwrap();
+    assert_eq!(addr.broadcast(), Some("192.0.2.255".parse().unwrap()));
+    let addr = InterfaceIpAddr::try_from("198.51.100.9/29").unwrap();
+    assert_eq!(addr.broadcast(), Some("198.51.100.15".parse().unwrap()));
+    let addr = InterfaceIpAddr::try_from("192.0.2.1/31").unwrap();
+    assert_eq!(addr.broadcast(), None);
+    let addr = InterfaceIpAddr::try_from("2001:db8:1::1/64").unwrap();
+    assert_eq!(addr.broadcast(), None);
+}
+
+#[test]
+fn test_ip_addr_hosts() {
+    let addr = InterfaceIpAddr::try_from("192.0.2.5/29").unwrap();
+    let hosts: Vec<String> = addr.hosts().map(|a| a.to_string()).collect();
+    assert_eq!(
+        hosts,
+        vec![
+            "192.0.2.1/29",
+            "192.0.2.2/29",
+            "192.0.2.3/29",
+            "192.0.2.4/29",
+            "192.0.2.5/29",
+            "192.0.2.6/29",
+        ]
+    );
+
+    let addr = InterfaceIpAddr::try_from("192.0.2.1/31").unwrap();
+    assert_eq!(addr.hosts().count(), 2);
+    let addr = InterfaceIpAddr::try_from("192.0.2.1").unwrap();
+    assert_eq!(addr.hosts().count(), 1);
+
+    let addr = InterfaceIpAddr::try_from("2001:db8:1::/126").unwrap();
+    let hosts: Vec<String> = addr.hosts().map(|a| a.to_string()).collect();
+    assert_eq!(
+        hosts,
+        vec![
+            "2001:db8:1::/126",
+            "2001:db8:1::1/126",
+            "2001:db8:1::2/126",
+            "2001:db8:1::3/126",
+        ]
+    );
+
+    let addr = InterfaceIpAddr::try_from(
+        "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/127",
+    )
+    .unwrap();
+    assert_eq!(addr.hosts().count(), 2);
+}
+
 #[test]
 fn test_auto_ip_lift_time() {
     let left_fmt: BaseInterface = serde_yaml::from_str(
@@ -541,3 +673,678 @@ fn test_auto_ip_lift_time() {
     assert_eq!(left_fmt, life_time_fmt);
     assert_eq!(iproute_fmt, life_time_fmt);
 }
+
+#[test]
+fn test_ipv6_dhcp_mode_implies_dhcp_and_autoconf() {
+    let mut ip: InterfaceIpv6 = serde_yaml::from_str(
+        r"---
+        enabled: true
+        dhcp-mode: no-ra
+        ",
+    )
+    .unwrap();
+    ip.sanitize(true).unwrap();
+
+    assert_eq!(ip.dhcp_mode, Some(Dhcpv6Mode::NoRa));
+    assert_eq!(ip.dhcp, Some(true));
+    assert_eq!(ip.autoconf, Some(false));
+
+    let mut ip: InterfaceIpv6 = serde_yaml::from_str(
+        r"---
+        enabled: true
+        dhcp-mode: ra-triggered
+        ",
+    )
+    .unwrap();
+    ip.sanitize(true).unwrap();
+
+    assert_eq!(ip.dhcp, Some(true));
+    assert_eq!(ip.autoconf, Some(true));
+}
+
+#[test]
+fn test_ipv6_dhcp_mode_conflict_with_autoconf() {
+    let mut ip: InterfaceIpv6 = serde_yaml::from_str(
+        r"---
+        enabled: true
+        autoconf: true
+        dhcp-mode: no-ra
+        ",
+    )
+    .unwrap();
+    let result = ip.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipv4_dhcp_mode_not_allowed() {
+    let result = serde_yaml::from_str::<InterfaceIpv4>(
+        r"---
+        enabled: true
+        dhcp: true
+        dhcp-mode: no-ra
+        ",
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_ipv4_dad_timeout_too_big() {
+    let mut ip: InterfaceIpv4 = serde_yaml::from_str(
+        r"---
+        enabled: true
+        dhcp: true
+        dad-timeout: 30001
+        ",
+    )
+    .unwrap();
+    let result = ip.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipv4_address_conflict_is_query_only() {
+    let mut ip: InterfaceIpv4 = serde_yaml::from_str(
+        r#"---
+        enabled: true
+        dhcp: true
+        dad-timeout: "3000"
+        address-conflict: true
+        "#,
+    )
+    .unwrap();
+    ip.sanitize(true).unwrap();
+
+    assert_eq!(ip.dad_timeout, Some(3000));
+    assert_eq!(ip.address_conflict, None);
+}
+
+#[test]
+fn test_ipv6_dad_timeout_not_allowed() {
+    let result = serde_yaml::from_str::<InterfaceIpv6>(
+        r"---
+        enabled: true
+        dad-timeout: 3000
+        ",
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_ipv6_dad_timeout_not_allowed_error_kind() {
+    let result = NetworkState::new_from_yaml(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  ipv6:
+    enabled: true
+    dad-timeout: 3000
+",
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipv4_dhcp_client_id_ignored_warning() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    dhcp-client-id: mac
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+",
+    )
+    .unwrap();
+
+    start_warning_collection();
+    MergedInterfaces::new(desired.clone(), gen_test_eth_ifaces(), false, false)
+        .unwrap();
+    // Duplicate warnings should be removed
+    MergedInterfaces::new(desired, gen_test_eth_ifaces(), false, false)
+        .unwrap();
+    let warnings = stop_warning_collection();
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].msg.contains("dhcp-client-id"));
+    assert!(stop_warning_collection().is_empty());
+}
+
+#[test]
+fn test_dhcp_client_id_and_duid_hex() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+state: up
+ipv4:
+  enabled: true
+  dhcp: true
+  dhcp-client-id: 01:00:23:45:67:89:AB
+ipv6:
+  enabled: true
+  dhcp: true
+  dhcp-duid: 00:04:ec:b1:6d:80:a6:11:47:b2
+",
+    )
+    .unwrap();
+    let base_iface = iface.base_iface();
+
+    assert_eq!(
+        base_iface.ipv4.as_ref().unwrap().dhcp_client_id,
+        Some(Dhcpv4ClientId::Hex(vec![
+            0x01, 0x00, 0x23, 0x45, 0x67, 0x89, 0xab
+        ]))
+    );
+    assert_eq!(
+        base_iface
+            .ipv6
+            .as_ref()
+            .unwrap()
+            .dhcp_duid
+            .as_ref()
+            .map(|d| d.to_string())
+            .as_deref(),
+        Some("00:04:ec:b1:6d:80:a6:11:47:b2")
+    );
+}
+
+#[test]
+fn test_dhcp_client_id_backend_keyword() {
+    let ipv4: InterfaceIpv4 = serde_yaml::from_str(
+        r"---
+enabled: true
+dhcp: true
+dhcp-client-id: stable
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        ipv4.dhcp_client_id,
+        Some(Dhcpv4ClientId::Other("stable".to_string()))
+    );
+}
+
+#[test]
+fn test_invalid_dhcp_client_id_and_duid() {
+    for client_id in ["01:0g", "01:", "01:2:03", "01 02 03"] {
+        let result = serde_yaml::from_str::<InterfaceIpv4>(&format!(
+            "enabled: true\ndhcp: true\ndhcp-client-id: '{client_id}'"
+        ));
+        assert!(result.is_err(), "{client_id} should be invalid");
+    }
+    // DUID should hold at least 3 bytes
+    let result = serde_yaml::from_str::<InterfaceIpv6>(
+        "enabled: true\ndhcp: true\ndhcp-duid: '00:01'",
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_duplicate_static_ip_warning() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+",
+    )
+    .unwrap();
+    let mut current = gen_test_eth_ifaces();
+    current.push(new_eth_iface("eth2"));
+
+    start_warning_collection();
+    MergedInterfaces::new(desired, current, false, false).unwrap();
+    let warnings = stop_warning_collection();
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].msg.contains("192.0.2.1"));
+    assert!(warnings[0].msg.contains("eth1, eth2"));
+}
+
+#[test]
+fn test_duplicate_static_ip_in_different_vrf() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+- name: vrf0
+  type: vrf
+  state: up
+  vrf:
+    port:
+    - eth1
+    route-table-id: 100
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+",
+    )
+    .unwrap();
+    let mut current = gen_test_eth_ifaces();
+    current.push(new_eth_iface("eth2"));
+
+    start_warning_collection();
+    MergedInterfaces::new(desired, current, false, false).unwrap();
+    let warnings = stop_warning_collection();
+
+    assert!(warnings.is_empty());
+}
+
+fn gen_overlapping_subnet_ifaces() -> Interfaces {
+    serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.129
+      prefix-length: 25
+    - ip: 198.51.100.1
+      prefix-length: 24
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_overlapping_subnet_warning() {
+    let mut current = gen_test_eth_ifaces();
+    current.push(new_eth_iface("eth2"));
+    let merged_ifaces = MergedInterfaces::new(
+        gen_overlapping_subnet_ifaces(),
+        current,
+        false,
+        false,
+    )
+    .unwrap();
+
+    start_warning_collection();
+    merged_ifaces.check_overlapping_subnets(false).unwrap();
+    let warnings = stop_warning_collection();
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].msg.contains("192.0.2.1/24"));
+    assert!(warnings[0].msg.contains("192.0.2.129/25"));
+}
+
+#[test]
+fn test_overlapping_subnet_strict() {
+    let mut current = gen_test_eth_ifaces();
+    current.push(new_eth_iface("eth2"));
+    let merged_ifaces = MergedInterfaces::new(
+        gen_overlapping_subnet_ifaces(),
+        current,
+        false,
+        false,
+    )
+    .unwrap();
+
+    let result = merged_ifaces.check_overlapping_subnets(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_overlapping_subnet_ignore_host_address() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv6:
+    enabled: true
+    address:
+    - ip: 2001:db8::1
+      prefix-length: 64
+- name: eth2
+  type: ethernet
+  state: up
+  ipv6:
+    enabled: true
+    address:
+    - ip: 2001:db8::100
+      prefix-length: 128
+",
+    )
+    .unwrap();
+    let mut current = gen_test_eth_ifaces();
+    current.push(new_eth_iface("eth2"));
+    let merged_ifaces =
+        MergedInterfaces::new(desired, current, false, false).unwrap();
+
+    assert!(merged_ifaces.check_overlapping_subnets(true).is_ok());
+}
+
+#[test]
+fn test_wait_ip_any_with_grace_serde() {
+    let iface: BaseInterface = serde_yaml::from_str(
+        r#"---
+name: eth1
+type: ethernet
+state: up
+wait-ip: any-with-grace
+wait-ip-timeout: "45"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(iface.wait_ip, Some(WaitIp::AnyWithGrace));
+    assert_eq!(iface.wait_ip_timeout, Some(45));
+    assert!(serde_yaml::to_string(&iface)
+        .unwrap()
+        .contains("wait-ip: any-with-grace"));
+}
+
+#[test]
+fn test_wait_ip_timeout_with_wait_ip_any() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  wait-ip: any
+  wait-ip-timeout: 10
+  ipv4:
+    enabled: true
+    dhcp: true
+  ipv6:
+    enabled: true
+    dhcp: true
+    autoconf: true
+",
+    )
+    .unwrap();
+
+    let result =
+        MergedInterfaces::new(desired, gen_test_eth_ifaces(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("wait-ip-timeout"));
+    }
+}
+
+#[test]
+fn test_wait_ip_any_with_grace_warn_missing_ipv6() {
+    let des_iface: BaseInterface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+state: up
+wait-ip: any-with-grace
+",
+    )
+    .unwrap();
+    let cur_iface: BaseInterface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+state: up
+ipv4:
+  enabled: true
+  address:
+  - ip: 192.0.2.1
+    prefix-length: 24
+ipv6:
+  enabled: true
+  address:
+  - ip: fe80::1
+    prefix-length: 64
+",
+    )
+    .unwrap();
+
+    start_warning_collection();
+    warn_wait_ip_missing_family(&des_iface, &cur_iface);
+    let warnings = stop_warning_collection();
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].msg.contains("IPv6"));
+}
+
+#[test]
+fn test_ipv6_ra_info_is_query_only() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+state: up
+ipv6:
+  enabled: true
+  autoconf: true
+  dhcp: false
+  router-advertisement:
+    route:
+    - destination: ::/0
+      next-hop-address: fe80::1
+      metric: 1024
+      lifetime: 1795sec
+    rdnss:
+    - server: 2001:db8::53
+    dnssl:
+    - domain: example.org
+",
+    )
+    .unwrap();
+
+    let ra = iface
+        .base_iface()
+        .ipv6
+        .as_ref()
+        .unwrap()
+        .ra
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        ra.route.as_ref().unwrap()[0].lifetime.as_deref(),
+        Some("1795sec")
+    );
+    assert_eq!(ra.rdnss.as_ref().unwrap()[0].server, "2001:db8::53");
+    assert_eq!(ra.dnssl.as_ref().unwrap()[0].domain, "example.org");
+
+    iface.sanitize(true).unwrap();
+    assert!(iface.base_iface().ipv6.as_ref().unwrap().ra.is_none());
+}
+
+#[test]
+fn test_ipv4_ra_info_not_allowed() {
+    let result = serde_yaml::from_str::<InterfaceIpv4>(
+        r"---
+enabled: true
+router-advertisement:
+  rdnss:
+  - server: 192.0.2.53
+",
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_dhcp_send_release() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+state: up
+ipv4:
+  enabled: true
+  dhcp: true
+  dhcp-send-release: false
+ipv6:
+  enabled: true
+  dhcp: true
+  autoconf: false
+  dhcp-send-release: true
+",
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+    let base_iface = iface.base_iface();
+    assert_eq!(
+        base_iface.ipv4.as_ref().unwrap().dhcp_send_release,
+        Some(false)
+    );
+    assert_eq!(
+        base_iface.ipv6.as_ref().unwrap().dhcp_send_release,
+        Some(true)
+    );
+    assert!(serde_yaml::to_string(&iface)
+        .unwrap()
+        .contains("dhcp-send-release: false"));
+}
+
+#[test]
+fn test_dhcp_send_release_ignored_when_dhcp_disabled() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: eth1
+type: ethernet
+state: up
+ipv4:
+  enabled: true
+  dhcp: false
+  dhcp-send-release: false
+ipv6:
+  enabled: true
+  dhcp: false
+  autoconf: true
+  dhcp-send-release: false
+",
+    )
+    .unwrap();
+    iface.sanitize(true).unwrap();
+    let base_iface = iface.base_iface();
+    assert_eq!(base_iface.ipv4.as_ref().unwrap().dhcp_send_release, None);
+    assert_eq!(base_iface.ipv6.as_ref().unwrap().dhcp_send_release, None);
+}
+
+#[test]
+fn test_ipv4_address_conflict_verify_error_kind() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+",
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: false
+    address-conflict: true
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    let result = merged_ifaces.verify(&cur_ifaces);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("IPv4 address conflict detected"));
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ipv6_token_netlink_msg() {
+    let token: std::net::Ipv6Addr = "::fac1".parse().unwrap();
+    let msg = crate::query_apply::ip::gen_ipv6_token_link_msg(3, token);
+    let bytes = msg.to_bytes(19, 0, 1);
+
+    // nlmsghdr(16) + ifinfomsg(16) + IFLA_AF_SPEC(4) + AF_INET6(4) +
+    // IFLA_INET6_TOKEN(4 + 16)
+    assert_eq!(bytes.len(), 60);
+    assert_eq!(
+        u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]),
+        60
+    );
+    // ifi_family is AF_INET6
+    assert_eq!(bytes[16], 10);
+    assert_eq!(
+        i32::from_ne_bytes([bytes[20], bytes[21], bytes[22], bytes[23]]),
+        3
+    );
+    // Nested IFLA_AF_SPEC
+    assert_eq!(u16::from_ne_bytes([bytes[32], bytes[33]]), 28);
+    assert_eq!(u16::from_ne_bytes([bytes[34], bytes[35]]), 26 | 1 << 15);
+    // Nested AF_INET6
+    assert_eq!(u16::from_ne_bytes([bytes[36], bytes[37]]), 24);
+    assert_eq!(u16::from_ne_bytes([bytes[38], bytes[39]]), 10 | 1 << 15);
+    // IFLA_INET6_TOKEN
+    assert_eq!(u16::from_ne_bytes([bytes[40], bytes[41]]), 20);
+    assert_eq!(u16::from_ne_bytes([bytes[42], bytes[43]]), 7);
+    assert_eq!(&bytes[44..], &token.octets());
+}
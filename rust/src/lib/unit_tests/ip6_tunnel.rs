@@ -0,0 +1,150 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, Interface, Ip6GreInterface, Ip6TnlMode};
+
+#[test]
+fn test_ip6gre_stringlized_attributes() {
+    let iface: Ip6GreInterface = serde_yaml::from_str(
+        r#"---
+name: ip6gre1
+type: ip6gre
+state: up
+ip6gre:
+  base-iface: eth1
+  local: 2001:db8::1
+  remote: 2001:db8::2
+  ikey: "100"
+  okey: "200"
+  encap-limit: "4"
+  flow-label: "1000"
+  hop-limit: "64"
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.ip6gre.unwrap();
+    assert_eq!(conf.base_iface.as_deref(), Some("eth1"));
+    assert_eq!(conf.local, Some("2001:db8::1".parse().unwrap()));
+    assert_eq!(conf.remote, Some("2001:db8::2".parse().unwrap()));
+    assert_eq!(conf.ikey, Some(100));
+    assert_eq!(conf.okey, Some(200));
+    assert_eq!(conf.encap_limit, Some(4));
+    assert_eq!(conf.flow_label, Some(1000));
+    assert_eq!(conf.hop_limit, Some(64));
+}
+
+#[test]
+fn test_ip6tnl_mode_and_parent() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: ip6tnl1
+type: ip6tnl
+state: up
+ip6tnl:
+  mode: ipip6
+  base-iface: eth1
+  remote: 2001:db8::2
+",
+    )
+    .unwrap();
+
+    assert_eq!(iface.parent(), Some("eth1"));
+    if let Interface::Ip6Tnl(iface) = iface {
+        assert_eq!(iface.ip6tnl.unwrap().mode, Some(Ip6TnlMode::Ipip6));
+    } else {
+        panic!("Expecting ip6tnl interface, got {iface:?}");
+    }
+}
+
+#[test]
+fn test_ip6gretap_ipv4_endpoint() {
+    let result = serde_yaml::from_str::<Interface>(
+        r"---
+name: ip6gretap1
+type: ip6gretap
+state: up
+ip6gretap:
+  remote: 192.0.2.2
+",
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_ip6gre_invalid_flow_label() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: ip6gre1
+type: ip6gre
+state: up
+ip6gre:
+  remote: 2001:db8::2
+  flow-label: 1048576
+",
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ip6tnl_with_key() {
+    let mut iface: Interface = serde_yaml::from_str(
+        r"---
+name: ip6tnl1
+type: ip6tnl
+state: up
+ip6tnl:
+  remote: 2001:db8::2
+  ikey: 100
+",
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_ip6tnl_gen_nm_keyfile() {
+    let net_state: crate::NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: ip6tnl1
+  type: ip6tnl
+  state: up
+  mtu: 1400
+  ip6tnl:
+    mode: ipip6
+    local: 2001:db8::1
+    remote: 2001:db8::2
+    encap-limit: 4
+    flow-label: 1000
+    hop-limit: 64
+",
+    )
+    .unwrap();
+
+    let keyfiles = net_state.gen_nm_keyfiles().unwrap();
+
+    assert_eq!(keyfiles.len(), 1);
+    let content = keyfiles[0].content.as_str();
+    assert!(content.contains("type=ip-tunnel"));
+    assert!(content.contains("mode=5"));
+    assert!(content.contains("local=2001:db8::1"));
+    assert!(content.contains("remote=2001:db8::2"));
+    assert!(content.contains("encapsulation-limit=4"));
+    assert!(content.contains("flow-label=1000"));
+    assert!(content.contains("ttl=64"));
+    assert!(content.contains("mtu=1400"));
+    assert!(!content.contains("[ethernet]"));
+}
@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, Interfaces, IpVlanInterface, IpVlanMode,
+    MergedInterfaces,
+};
+
+#[test]
+fn test_ipvlan_stringlized_attributes() {
+    let iface: IpVlanInterface = serde_yaml::from_str(
+        r#"---
+name: ipvlan0
+type: ipvlan
+state: up
+ipvlan:
+  base-iface: "eth1"
+  mode: "l3s"
+  private: "true"
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.ipvlan.unwrap();
+    assert_eq!(conf.mode, IpVlanMode::L3S);
+    assert_eq!(conf.private, Some(true));
+    assert_eq!(conf.vepa, None);
+}
+
+#[test]
+fn test_ipvlan_private_and_vepa() {
+    let iface: IpVlanInterface = serde_yaml::from_str(
+        r"---
+name: ipvlan0
+type: ipvlan
+state: up
+ipvlan:
+  base-iface: eth1
+  mode: l2
+  private: true
+  vepa: true
+",
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipvlan_on_bond_port() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  controller: bond0
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l3
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_ipvlan_verify() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l2
+    private: false
+    vepa: true
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: ipvlan0
+  type: ipvlan
+  state: up
+  ipvlan:
+    base-iface: eth1
+    mode: l2
+    vepa: true
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+
+    let iface = merged_ifaces
+        .kernel_ifaces
+        .get("ipvlan0")
+        .and_then(|i| i.for_apply.as_ref())
+        .unwrap();
+    assert_eq!(iface.parent(), Some("eth1"));
+    assert!(matches!(iface, Interface::IpVlan(_)));
+}
@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetworkState;
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
+    MergedNetworkState, NetworkState,
+};
 
 #[test]
 fn test_ipsec_hide_psk() {
@@ -41,3 +44,241 @@ fn test_ipsec_hide_psk() {
         .unwrap()
         .contains("TOP_SECRET"));
 }
+
+#[test]
+fn test_ipsec_multiple_connections_add_and_remove() {
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: tunnel_a
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.252
+            ikev2: insist
+        - name: tunnel_b
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.253
+            ikev2: insist",
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: tunnel_a
+          type: ipsec
+          state: absent
+        - name: tunnel_c
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.254
+            ikev2: insist",
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    let ifaces = &merged_state.interfaces;
+    let tunnel_a = ifaces.get_iface("tunnel_a", InterfaceType::Ipsec).unwrap();
+    assert!(tunnel_a.merged.is_absent());
+    let tunnel_b = ifaces.get_iface("tunnel_b", InterfaceType::Ipsec).unwrap();
+    assert!(!tunnel_b.is_changed());
+    let tunnel_c = ifaces.get_iface("tunnel_c", InterfaceType::Ipsec).unwrap();
+    assert!(tunnel_c.is_changed());
+    if let Interface::Ipsec(iface) = &tunnel_c.merged {
+        assert_eq!(
+            iface.libreswan.as_ref().map(|c| c.right.as_str()),
+            Some("192.0.2.254")
+        );
+    } else {
+        panic!("Expecting ipsec interface but got {:?}", tunnel_c.merged);
+    }
+}
+
+#[test]
+fn test_ipsec_verify_psk() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: hosta_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.253
+            psk: TOP_SECRET",
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: hosta_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.253
+            psk: OLD_SECRET",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    let result = merged_ifaces.verify(&cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert!(e.msg().contains("hosta_conn.interface.libreswan.psk"));
+        assert!(!e.msg().contains("TOP_SECRET"));
+        assert!(!e.msg().contains("OLD_SECRET"));
+    }
+}
+
+#[test]
+fn test_ipsec_psk_redacted_in_debug() {
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+        name: hosta_conn
+        type: ipsec
+        state: up
+        libreswan:
+          right: 192.0.2.253
+          psk: TOP_SECRET",
+    )
+    .unwrap();
+
+    assert!(!format!("{iface:?}").contains("TOP_SECRET"));
+    assert!(serde_yaml::to_string(&iface)
+        .unwrap()
+        .contains("TOP_SECRET"));
+    if let Interface::Ipsec(iface) = iface {
+        let psk = iface.libreswan.as_ref().unwrap().psk.as_ref().unwrap();
+        assert_eq!(psk.as_str(), "TOP_SECRET");
+        assert!(!psk.to_string().contains("TOP_SECRET"));
+    } else {
+        panic!("Expecting ipsec interface but got {:?}", iface);
+    }
+}
+
+#[test]
+fn test_ipsec_psk_from_secret_agent_conflicts_with_psk() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: hosta_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.253
+            psk: TOP_SECRET
+            psk-from-secret-agent: true",
+    )
+    .unwrap();
+
+    let result =
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("psk-from-secret-agent"));
+        assert!(!e.msg().contains("TOP_SECRET"));
+    }
+}
+
+#[test]
+fn test_ipsec_verify_psk_from_secret_agent() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: hosta_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.253
+            psk-from-secret-agent: true",
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: hosta_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.253
+            psk: TOP_SECRET",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    let result = merged_ifaces.verify(&cur_ifaces);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert!(e.msg().contains("psk-from-secret-agent"));
+        assert!(!e.msg().contains("TOP_SECRET"));
+    }
+}
+
+#[test]
+fn test_ipsec_merge_psk_from_secret_agent() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: hosta_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.253
+            psk-from-secret-agent: true
+        - name: hostb_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.254
+            psk: NEW_SECRET",
+    )
+    .unwrap();
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+        - name: hosta_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.253
+            psk: OLD_SECRET
+        - name: hostb_conn
+          type: ipsec
+          state: up
+          libreswan:
+            right: 192.0.2.254
+            psk-from-secret-agent: true",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces, false, false).unwrap();
+
+    let hosta = merged_ifaces
+        .get_iface("hosta_conn", InterfaceType::Ipsec)
+        .unwrap();
+    let hostb = merged_ifaces
+        .get_iface("hostb_conn", InterfaceType::Ipsec)
+        .unwrap();
+    if let (Interface::Ipsec(hosta), Interface::Ipsec(hostb)) =
+        (&hosta.merged, &hostb.merged)
+    {
+        let hosta_conf = hosta.libreswan.as_ref().unwrap();
+        assert_eq!(hosta_conf.psk, None);
+        assert_eq!(hosta_conf.psk_from_secret_agent, Some(true));
+        let hostb_conf = hostb.libreswan.as_ref().unwrap();
+        assert_eq!(
+            hostb_conf.psk.as_ref().map(|p| p.as_str()),
+            Some("NEW_SECRET")
+        );
+        assert_eq!(hostb_conf.psk_from_secret_agent, None);
+    } else {
+        panic!("Expecting ipsec interfaces");
+    }
+}
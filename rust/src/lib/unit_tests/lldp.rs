@@ -1,6 +1,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::LldpConfig;
+use std::collections::HashMap;
+
+use crate::{
+    query_apply::lldp::get_lldp_neighbors_changes, LldpConfig, LldpNeighborTlv,
+    LldpSystemName,
+};
 
 #[test]
 fn test_lldp_stringlized_attributes() {
@@ -33,3 +38,34 @@ fn test_lldp_stringlized_attributes() {
         assert!(!conf.enabled);
     }
 }
+
+#[test]
+fn test_lldp_neighbors_changes() {
+    let neighbor = vec![LldpNeighborTlv::SystemName(LldpSystemName(
+        "switch1".to_string(),
+    ))];
+    let mut old = HashMap::new();
+    old.insert("eth1".to_string(), vec![neighbor.clone()]);
+    old.insert("eth2".to_string(), vec![neighbor.clone()]);
+    old.insert("eth3".to_string(), vec![neighbor.clone()]);
+    let mut new = HashMap::new();
+    new.insert("eth1".to_string(), vec![neighbor.clone()]);
+    new.insert("eth2".to_string(), Vec::new());
+    new.insert("eth4".to_string(), vec![neighbor.clone()]);
+    new.insert("eth5".to_string(), Vec::new());
+
+    let changes = get_lldp_neighbors_changes(&old, &new);
+
+    assert_eq!(changes.len(), 3);
+    assert_eq!(changes[0].iface_name, "eth2");
+    assert!(changes[0].neighbors.is_empty());
+    assert_eq!(changes[1].iface_name, "eth3");
+    assert!(changes[1].neighbors.is_empty());
+    assert_eq!(changes[2].iface_name, "eth4");
+    assert_eq!(changes[2].neighbors, vec![neighbor]);
+    assert!(get_lldp_neighbors_changes(&new, &new).is_empty());
+    // Missing interface is identical to interface without neighbors
+    assert!(get_lldp_neighbors_changes(&HashMap::new(), &new)
+        .iter()
+        .all(|c| !c.neighbors.is_empty()));
+}
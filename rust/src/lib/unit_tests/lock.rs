@@ -0,0 +1,109 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{query_apply::lock::NmstateApplyLock, ErrorKind};
+
+fn gen_lock_path(name: &str) -> String {
+    std::env::temp_dir()
+        .join(format!("nmstate_test_lock_{}_{}", name, std::process::id()))
+        .display()
+        .to_string()
+}
+
+#[test]
+fn test_apply_lock_contention() {
+    let path = gen_lock_path("contention");
+    let lock = NmstateApplyLock::new_with_path(&path, 0, |_| false).unwrap();
+
+    let result = NmstateApplyLock::new_with_path(&path, 0, |_| false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::PluginFailure);
+        assert!(e.msg().contains("other nmstate instance"));
+    }
+
+    drop(lock);
+    assert!(NmstateApplyLock::new_with_path(&path, 0, |_| false).is_ok());
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_apply_lock_wait_for_release() {
+    let path = gen_lock_path("wait");
+    let lock = NmstateApplyLock::new_with_path(&path, 0, |_| false).unwrap();
+
+    let handle = std::thread::spawn(move || {
+        std::thread::sleep(std::time::Duration::from_millis(300));
+        drop(lock);
+    });
+
+    assert!(NmstateApplyLock::new_with_path(&path, 5, |_| false).is_ok());
+    handle.join().unwrap();
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_apply_lock_wait_for_pending_checkpoint() {
+    let path = gen_lock_path("pending");
+    let checkpoint = "/org/freedesktop/NetworkManager/Checkpoint/1";
+    let mut lock =
+        NmstateApplyLock::new_with_path(&path, 0, |_| false).unwrap();
+    lock.set_pending_checkpoint(checkpoint).unwrap();
+    drop(lock);
+
+    let result =
+        NmstateApplyLock::new_with_path(&path, 0, |cp| cp == checkpoint);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::PluginFailure);
+        assert!(e.msg().contains(checkpoint));
+    }
+
+    // Commit or rollback does not wait for pending checkpoint
+    let mut lock =
+        NmstateApplyLock::new_with_path(&path, 0, |_| false).unwrap();
+    assert_eq!(
+        lock.pending_checkpoint().unwrap().as_deref(),
+        Some(checkpoint)
+    );
+    lock.clear_pending_checkpoint("/org/freedesktop/NetworkManager/2")
+        .unwrap();
+    assert_eq!(
+        lock.pending_checkpoint().unwrap().as_deref(),
+        Some(checkpoint)
+    );
+    lock.clear_pending_checkpoint(checkpoint).unwrap();
+    drop(lock);
+
+    assert!(
+        NmstateApplyLock::new_with_path(&path, 0, |cp| cp == checkpoint)
+            .is_ok()
+    );
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_apply_lock_ignore_expired_checkpoint() {
+    let path = gen_lock_path("expired");
+    let mut lock =
+        NmstateApplyLock::new_with_path(&path, 0, |_| false).unwrap();
+    lock.set_pending_checkpoint("/org/freedesktop/NetworkManager/1")
+        .unwrap();
+    drop(lock);
+
+    assert!(NmstateApplyLock::new_with_path(&path, 0, |_| false).is_ok());
+    std::fs::remove_file(&path).ok();
+}
+
+#[test]
+fn test_apply_lock_fail_on_open_failure() {
+    let result = NmstateApplyLock::new_with_path(
+        "/not_exist_nmstate_dir/nmstate.lock",
+        0,
+        |_| false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::PermissionError);
+    }
+}
@@ -0,0 +1,82 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use log::Log;
+
+use crate::{
+    query_apply::logger::{run_with_logs, NmstateLogCollector},
+    warning::nmstate_warn,
+    ErrorKind, NmstateError, NmstateLogger,
+};
+
+fn log_to(logger: &NmstateLogger, target: &str, msg: &str) {
+    logger.log(
+        &log::Record::builder()
+            .level(log::Level::Warn)
+            .target(target)
+            .args(format_args!("{msg}"))
+            .build(),
+    );
+}
+
+#[test]
+fn test_logger_capture_nmstate_logs() {
+    let logger = NmstateLogger::new(None);
+    let collector = NmstateLogCollector::default();
+
+    log_to(&logger, "nmstate::ifaces::bond", "before capture");
+    collector.run(|| {
+        log_to(&logger, "nmstate::ifaces::bond", "Ignoring bond option");
+        log_to(&logger, "zbus::connection", "Not from nmstate");
+    });
+    log_to(&logger, "nmstate::ifaces::bond", "after capture");
+
+    let logs = collector.take();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].level, "WARN");
+    assert_eq!(logs[0].target, "nmstate::ifaces::bond");
+    assert_eq!(logs[0].msg, "Ignoring bond option");
+    assert!(collector.take().is_empty());
+    assert!(NmstateLogCollector::current().is_none());
+}
+
+#[test]
+fn test_logger_capture_nmstate_logs_of_other_thread() {
+    let logger = std::sync::Arc::new(NmstateLogger::new(None));
+    let collector = NmstateLogCollector::default();
+
+    collector.run(|| {
+        log_to(&logger, "nmstate::query_apply", "from apply thread");
+        let thread_collector = NmstateLogCollector::current().unwrap();
+        let thread_logger = logger.clone();
+        std::thread::spawn(move || {
+            thread_collector.run(|| {
+                log_to(&thread_logger, "nmstate::nm", "from helper thread")
+            })
+        })
+        .join()
+        .unwrap();
+    });
+
+    let msgs: Vec<String> =
+        collector.take().into_iter().map(|l| l.msg).collect();
+    assert_eq!(msgs, vec!["from apply thread", "from helper thread"]);
+}
+
+#[test]
+fn test_logger_return_warnings_on_failure_without_logger() {
+    let (result, logs) = run_with_logs(false, || {
+        nmstate_warn!("Ignoring bond option");
+        Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            "Invalid bond mode".to_string(),
+        ))
+    });
+
+    assert_eq!(
+        result.err().map(|e| e.kind()),
+        Some(ErrorKind::InvalidArgument)
+    );
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].level, "WARN");
+    assert_eq!(logs[0].msg, "Ignoring bond option");
+}
@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::MacVlanInterface;
+use crate::{
+    ErrorKind, Interfaces, MacVlanInterface, MacVlanMode, MergedInterfaces,
+};
 
 #[test]
 fn test_mac_vlan_stringlized_attributes() {
@@ -20,3 +22,116 @@ mac-vlan:
     let mac_conf = iface.mac_vlan.unwrap();
     assert_eq!(mac_conf.accept_all_mac, Some(true));
 }
+
+#[test]
+fn test_mac_vlan_mode_ignore_case() {
+    let iface: MacVlanInterface = serde_yaml::from_str(
+        r"---
+name: mac1
+type: mac-vlan
+state: up
+mac-vlan:
+  base-iface: eth1
+  mode: PassThru
+",
+    )
+    .unwrap();
+
+    assert_eq!(iface.mac_vlan.as_ref().unwrap().mode, MacVlanMode::Passthru);
+    assert!(serde_yaml::to_string(&iface)
+        .unwrap()
+        .contains("mode: passthru"));
+}
+
+#[test]
+fn test_mac_vlan_source_mac_addresses_on_non_source_mode() {
+    let mut iface: MacVlanInterface = serde_yaml::from_str(
+        r"---
+name: mac1
+type: mac-vlan
+state: up
+mac-vlan:
+  base-iface: eth1
+  mode: vepa
+  source-mac-addresses:
+  - 00:23:45:67:89:1a
+",
+    )
+    .unwrap();
+
+    let result = iface.sanitize(true);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+fn gen_cur_source_mode_mac_vlan() -> Interfaces {
+    serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: mac1
+  type: mac-vlan
+  state: up
+  mac-vlan:
+    base-iface: eth1
+    mode: source
+    source-mac-addresses:
+    - 00:23:45:67:89:1B
+    - 00:23:45:67:89:1A
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_mac_vlan_source_mac_addresses_unchanged() {
+    let cur_ifaces = gen_cur_source_mode_mac_vlan();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: mac1
+  type: mac-vlan
+  state: up
+  mac-vlan:
+    base-iface: eth1
+    mode: source
+    source-mac-addresses:
+    - 00:23:45:67:89:1a
+    - 00:23:45:67:89:1b
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
+
+#[test]
+fn test_mac_vlan_source_mac_addresses_change_not_supported() {
+    let cur_ifaces = gen_cur_source_mode_mac_vlan();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: mac1
+  type: mac-vlan
+  state: up
+  mac-vlan:
+    base-iface: eth1
+    mode: source
+    macvlan-source-mac-list:
+    - 00:23:45:67:89:1C
+    - 00:23:45:67:89:1A
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(des_ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
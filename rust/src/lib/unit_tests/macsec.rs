@@ -0,0 +1,398 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    unit_tests::testlib::new_eth_iface, ErrorKind, Interface, Interfaces,
+    MacSecInterface, MergedInterfaces,
+};
+
+fn gen_macsec_iface_with_keys() -> MacSecInterface {
+    serde_yaml::from_str(
+        r#"---
+name: macsec0
+type: macsec
+state: up
+macsec:
+  encrypt: true
+  base-iface: eth1
+  mka-keys:
+    - mka-cak: 50b71a8ef0bd5751ea76de6d6c98c03a
+      mka-ckn: f2b4297d39da7330910a74abc0449feb
+      not-after: "2000"
+    - mka-cak: 7e0b3c46ee0bd5751ea76de6d6c98c12
+      mka-ckn: a1b4297d39da7330910a74abc0449fcc
+      not-before: 1000
+  port: 0
+  validation: strict
+  send-sci: true
+"#,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_macsec_stringlized_attributes() {
+    let iface: MacSecInterface = serde_yaml::from_str(
+        r#"---
+name: macsec0
+type: macsec
+state: up
+macsec:
+  encrypt: "yes"
+  base-iface: eth1
+  mka-cak: 50b71a8ef0bd5751ea76de6d6c98c03a
+  mka-ckn: f2b4297d39da7330910a74abc0449feb
+  port: "10"
+  validation: strict
+  send-sci: "false"
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.macsec.unwrap();
+    assert!(conf.encrypt);
+    assert_eq!(conf.port, 10);
+    assert!(!conf.send_sci);
+}
+
+#[test]
+fn test_macsec_mka_keys_get_active() {
+    let iface = gen_macsec_iface_with_keys();
+    let conf = iface.macsec.as_ref().unwrap();
+
+    assert_eq!(
+        conf.get_active_mka_key(500).map(|k| k.mka_ckn.as_str()),
+        Some("f2b4297d39da7330910a74abc0449feb")
+    );
+    // Both keys are active, the one activated latest wins
+    assert_eq!(
+        conf.get_active_mka_key(1500).map(|k| k.mka_ckn.as_str()),
+        Some("a1b4297d39da7330910a74abc0449fcc")
+    );
+    assert_eq!(
+        conf.get_active_mka_key(2000).map(|k| k.mka_ckn.as_str()),
+        Some("a1b4297d39da7330910a74abc0449fcc")
+    );
+}
+
+#[test]
+fn test_macsec_mka_keys_resolve() {
+    let iface = gen_macsec_iface_with_keys();
+    let mut conf = iface.macsec.unwrap();
+
+    conf.resolve_mka_keys(100).unwrap();
+
+    assert_eq!(conf.mka_keys, None);
+    assert_eq!(
+        conf.mka_cak.as_ref().map(|c| c.as_str()),
+        Some("50b71a8ef0bd5751ea76de6d6c98c03a")
+    );
+    assert_eq!(
+        conf.mka_ckn.as_deref(),
+        Some("f2b4297d39da7330910a74abc0449feb")
+    );
+}
+
+#[test]
+fn test_macsec_mka_keys_none_active() {
+    let mut iface = gen_macsec_iface_with_keys();
+    let conf = iface.macsec.as_mut().unwrap();
+    if let Some(keys) = conf.mka_keys.as_mut() {
+        keys.pop();
+    }
+
+    let result = conf.resolve_mka_keys(3000);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_macsec_mka_keys_conflict_with_mka_cak() {
+    let mut iface = gen_macsec_iface_with_keys();
+    if let Some(conf) = iface.macsec.as_mut() {
+        conf.mka_cak = Some("50b71a8ef0bd5751ea76de6d6c98c03a".into());
+        conf.mka_ckn = Some("f2b4297d39da7330910a74abc0449feb".to_string());
+    }
+
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_macsec_mka_keys_invalid_window() {
+    let mut iface = gen_macsec_iface_with_keys();
+    if let Some(key) = iface
+        .macsec
+        .as_mut()
+        .and_then(|c| c.mka_keys.as_mut())
+        .and_then(|k| k.get_mut(1))
+    {
+        key.not_after = Some(1000);
+    }
+
+    let result = iface.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+fn gen_macsec_yaml(base_iface: &str, mtu: u64) -> String {
+    format!(
+        r"---
+name: macsec0
+type: macsec
+state: up
+mtu: {mtu}
+macsec:
+  encrypt: true
+  base-iface: {base_iface}
+  port: 0
+  validation: strict
+  send-sci: true
+"
+    )
+}
+
+#[test]
+fn test_macsec_over_loopback() {
+    let mut ifaces = Interfaces::new();
+    ifaces.push(
+        serde_yaml::from_str(gen_macsec_yaml("lo", 1468).as_str()).unwrap(),
+    );
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(
+        serde_yaml::from_str(
+            r"---
+name: lo
+type: loopback
+state: up
+",
+        )
+        .unwrap(),
+    );
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_macsec_mtu_exceed_parent() {
+    let mut ifaces = Interfaces::new();
+    ifaces.push(
+        serde_yaml::from_str(gen_macsec_yaml("eth1", 1480).as_str()).unwrap(),
+    );
+    let mut cur_ifaces = Interfaces::new();
+    let mut eth1 = new_eth_iface("eth1");
+    eth1.base_iface_mut().mtu = Some(1500);
+    cur_ifaces.push(eth1);
+
+    let result = MergedInterfaces::new(ifaces, cur_ifaces, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_macsec_over_vlan_over_bond_up_order() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: macsec0
+  type: macsec
+  state: up
+  macsec:
+    encrypt: true
+    base-iface: bond0.10
+    port: 0
+    validation: strict
+    send-sci: true
+- name: bond0.10
+  type: vlan
+  state: up
+  vlan:
+    base-iface: bond0
+    id: 10
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+    - eth2
+",
+    )
+    .unwrap();
+    ifaces.push(new_eth_iface("eth1"));
+    ifaces.push(new_eth_iface("eth2"));
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+    cur_ifaces.push(new_eth_iface("eth2"));
+
+    let merged_ifaces =
+        MergedInterfaces::new(ifaces, cur_ifaces, false, false).unwrap();
+
+    let get_priority = |name: &str| {
+        merged_ifaces.kernel_ifaces[name]
+            .for_apply
+            .as_ref()
+            .unwrap()
+            .base_iface()
+            .up_priority
+    };
+
+    assert_eq!(get_priority("bond0"), 0);
+    assert_eq!(get_priority("eth1"), 1);
+    assert_eq!(get_priority("bond0.10"), 1);
+    assert_eq!(get_priority("macsec0"), 2);
+}
+
+#[test]
+fn test_macsec_over_bond_up_after_bond_ports() {
+    let mut ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1
+",
+    )
+    .unwrap();
+    ifaces.push(
+        serde_yaml::from_str(gen_macsec_yaml("bond0", 1468).as_str()).unwrap(),
+    );
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+
+    let merged_ifaces =
+        MergedInterfaces::new(ifaces, cur_ifaces, false, false).unwrap();
+
+    let get_priority = |name: &str| {
+        merged_ifaces.kernel_ifaces[name]
+            .for_apply
+            .as_ref()
+            .unwrap()
+            .base_iface()
+            .up_priority
+    };
+
+    assert_eq!(get_priority("eth1"), 1);
+    assert_eq!(get_priority("macsec0"), 2);
+}
+
+#[test]
+fn test_macsec_mka_keys_kept_after_sanitize() {
+    let mut iface = gen_macsec_iface_with_keys();
+
+    iface.sanitize(true).unwrap();
+
+    let conf = iface.macsec.unwrap();
+    assert_eq!(conf.mka_keys.map(|k| k.len()), Some(2));
+    assert_eq!(conf.mka_cak, None);
+    assert_eq!(conf.mka_ckn, None);
+}
+
+#[cfg(feature = "gen_conf")]
+#[test]
+fn test_macsec_mka_keys_not_supported_by_gen_conf() {
+    let mut ifaces = Interfaces::new();
+    ifaces.push(Interface::MacSec(gen_macsec_iface_with_keys()));
+    ifaces.push(new_eth_iface("eth1"));
+
+    let merged_ifaces =
+        MergedInterfaces::new(ifaces, Interfaces::new(), true, false).unwrap();
+    let result = merged_ifaces.validate_macsec_mka_keys_for_gen_conf();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_macsec_mka_keys_verify_any_key_in_use() {
+    let mut ifaces = Interfaces::new();
+    ifaces.push(Interface::MacSec(gen_macsec_iface_with_keys()));
+    let mut cur_ifaces = Interfaces::new();
+    cur_ifaces.push(new_eth_iface("eth1"));
+    let mut merged_ifaces =
+        MergedInterfaces::new(ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+
+    let mut cur_iface = gen_macsec_iface_with_keys();
+    if let Some(conf) = cur_iface.macsec.as_mut() {
+        conf.mka_keys = None;
+        conf.mka_cak = Some("7e0b3c46ee0bd5751ea76de6d6c98c12".into());
+        conf.mka_ckn = Some("a1b4297d39da7330910a74abc0449fcc".to_string());
+    }
+    cur_ifaces.push(Interface::MacSec(cur_iface));
+
+    merged_ifaces.process_macsec_mka_keys_for_verify(&cur_ifaces);
+
+    let des_iface =
+        match merged_ifaces.kernel_ifaces["macsec0"].for_verify.as_ref() {
+            Some(Interface::MacSec(i)) => i,
+            _ => panic!("Should be MACsec interface"),
+        };
+    let des_conf = des_iface.macsec.as_ref().unwrap();
+    assert_eq!(des_conf.mka_keys, None);
+    assert_eq!(
+        des_conf.mka_ckn.as_deref(),
+        Some("a1b4297d39da7330910a74abc0449fcc")
+    );
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_macsec_verify_error_not_leaking_mka_cak() {
+    let mut des_iface: MacSecInterface =
+        serde_yaml::from_str(gen_macsec_yaml("eth1", 1468).as_str()).unwrap();
+    if let Some(conf) = des_iface.macsec.as_mut() {
+        conf.mka_cak = Some("50b71a8ef0bd5751ea76de6d6c98c03a".into());
+        conf.mka_ckn = Some("f2b4297d39da7330910a74abc0449feb".to_string());
+    }
+    let mut cur_iface = des_iface.clone();
+    if let Some(conf) = cur_iface.macsec.as_mut() {
+        conf.mka_cak = Some("7e0b3c46ee0bd5751ea76de6d6c98c12".into());
+    }
+    let des_iface = Interface::MacSec(des_iface);
+    let mut cur_iface = Interface::MacSec(cur_iface);
+
+    let result = des_iface.verify(&cur_iface);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+        assert!(e.msg().contains("macsec0.interface.macsec.mka-cak"));
+        assert!(!e.msg().contains("50b71a8ef0bd5751ea76de6d6c98c03a"));
+        assert!(!e.msg().contains("7e0b3c46ee0bd5751ea76de6d6c98c12"));
+    }
+
+    // Difference in other property is still shown without secret
+    if let Interface::MacSec(iface) = &mut cur_iface {
+        if let Some(conf) = iface.macsec.as_mut() {
+            conf.mka_cak = Some("50b71a8ef0bd5751ea76de6d6c98c03a".into());
+            conf.port = 10;
+        }
+    }
+    let result = des_iface.verify(&cur_iface);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert!(e.msg().contains("macsec0.interface.macsec.port"));
+        assert!(!e.msg().contains("50b71a8ef0bd5751ea76de6d6c98c03a"));
+    }
+}
@@ -1,37 +1,71 @@
 #[cfg(test)]
 mod base;
 #[cfg(test)]
+mod batman;
+#[cfg(test)]
 mod bond;
 #[cfg(test)]
 mod bridge;
 #[cfg(test)]
+mod can;
+#[cfg(all(test, feature = "query_apply"))]
+mod connectivity;
+#[cfg(test)]
+mod defaults;
+#[cfg(test)]
 mod dns;
+#[cfg(all(test, feature = "gen_conf"))]
+mod dracut;
 #[cfg(test)]
 mod ethernet;
 #[cfg(test)]
 mod ethtool;
+#[cfg(all(test, feature = "gen_conf"))]
+mod gen_conf;
 #[cfg(test)]
 mod gen_revert;
 #[cfg(test)]
+mod gre;
+#[cfg(all(test, feature = "query_apply"))]
+mod hostname;
+#[cfg(test)]
+mod iface_plugin;
+#[cfg(test)]
 mod ifaces;
 #[cfg(test)]
 mod ifaces_ctrller;
 #[cfg(test)]
+mod ifb;
+#[cfg(test)]
 mod infiniband;
 #[cfg(test)]
 mod ip;
 #[cfg(test)]
+mod ip6_tunnel;
+#[cfg(test)]
+mod ip_vlan;
+#[cfg(test)]
 mod ipsec;
 #[cfg(test)]
 mod lldp;
+#[cfg(all(test, feature = "query_apply"))]
+mod lock;
+#[cfg(all(test, feature = "query_apply"))]
+mod logger;
 #[cfg(test)]
 mod mac_vlan;
 #[cfg(test)]
 mod mac_vtap;
 #[cfg(test)]
+mod macsec;
+#[cfg(test)]
+mod modem;
+#[cfg(test)]
 mod mptcp;
 #[cfg(test)]
 mod net_state;
+#[cfg(all(test, feature = "gen_conf"))]
+mod networkd;
 #[cfg(test)]
 mod nm;
 #[cfg(test)]
@@ -42,17 +76,25 @@ mod ovs;
 mod ovsdb;
 #[cfg(test)]
 mod policy;
+#[cfg(all(test, feature = "query_apply"))]
+mod retry;
 #[cfg(test)]
 mod route;
 #[cfg(test)]
 mod route_rule;
+#[cfg(all(test, feature = "query_apply"))]
+mod routing_summary;
 #[cfg(test)]
 mod sriov;
 #[cfg(test)]
 mod statistic;
 #[cfg(test)]
+mod team;
+#[cfg(test)]
 mod testlib;
 #[cfg(test)]
+mod unknown_field;
+#[cfg(test)]
 mod vlan;
 #[cfg(test)]
 mod vrf;
@@ -0,0 +1,122 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ErrorKind, Interface, InterfaceType, Interfaces, MergedInterfaces,
+    ModemInterface, NetworkState,
+};
+
+#[test]
+fn test_modem_stringlized_attributes() {
+    let iface: ModemInterface = serde_yaml::from_str(
+        r#"---
+name: cdc-wdm0
+type: modem
+state: up
+modem:
+  apn: internet.example.com
+  pin: "1234"
+  roaming: "false"
+"#,
+    )
+    .unwrap();
+
+    let conf = iface.modem.unwrap();
+    assert_eq!(conf.apn.as_deref(), Some("internet.example.com"));
+    assert_eq!(conf.pin.as_ref().map(|p| p.as_str()), Some("1234"));
+    assert_eq!(conf.roaming, Some(false));
+}
+
+#[test]
+fn test_modem_is_userspace_iface_along_with_ethernet() {
+    let ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+- name: cdc-wdm0
+  type: modem
+  state: up
+  modem:
+    apn: internet.example.com
+",
+    )
+    .unwrap();
+
+    assert!(ifaces.get_iface("eth1", InterfaceType::Ethernet).is_some());
+    assert!(matches!(
+        ifaces.get_iface("cdc-wdm0", InterfaceType::Modem),
+        Some(Interface::Modem(_))
+    ));
+    assert!(!ifaces.kernel_ifaces.contains_key("cdc-wdm0"));
+}
+
+#[test]
+fn test_modem_hide_pin() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: cdc-wdm0
+  type: modem
+  state: up
+  modem:
+    apn: internet.example.com
+    pin: '9876'
+",
+    )
+    .unwrap();
+
+    state.hide_secrets();
+    assert!(!serde_yaml::to_string(&state).unwrap().contains("9876"));
+}
+
+#[test]
+fn test_modem_invalid_pin() {
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: cdc-wdm0
+  type: modem
+  state: up
+  modem:
+    pin: 12a4
+",
+    )
+    .unwrap();
+
+    let result =
+        MergedInterfaces::new(des_ifaces, Interfaces::new(), false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_modem_verify() {
+    let cur_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: cdc-wdm0
+  type: modem
+  state: up
+  modem:
+    apn: internet.example.com
+    pin: '1234'
+    roaming: true
+",
+    )
+    .unwrap();
+    let des_ifaces: Interfaces = serde_yaml::from_str(
+        r"---
+- name: cdc-wdm0
+  type: modem
+  state: up
+  modem:
+    roaming: true
+",
+    )
+    .unwrap();
+
+    let merged_ifaces =
+        MergedInterfaces::new(des_ifaces, cur_ifaces.clone(), false, false)
+            .unwrap();
+    merged_ifaces.verify(&cur_ifaces).unwrap();
+}
@@ -1,6 +1,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::NetworkState;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::{
+    warning::{start_warning_collection, stop_warning_collection},
+    ErrorKind, Interface, InterfaceType, MacVlanInterface, MergedNetworkState,
+    NetworkState, NmstateSecretProvider, Secret,
+};
 
 #[test]
 fn test_invalid_top_key() {
@@ -23,3 +30,883 @@ fn test_invalid_top_type() {
 
     assert!(result.is_err());
 }
+
+#[test]
+fn test_sub_state_with_iface_keep_options_only() {
+    let mut net_state: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+",
+    )
+    .unwrap();
+    net_state.set_kernel_only(true).set_memory_only(true);
+    let iface: Interface = serde_yaml::from_str(
+        r"---
+name: eth2
+type: ethernet
+state: up
+",
+    )
+    .unwrap();
+
+    let sub_state = net_state.new_sub_state_with_iface(&iface);
+
+    assert!(sub_state.kernel_only);
+    assert!(sub_state.memory_only);
+    assert!(sub_state.dns.is_empty());
+    assert_eq!(sub_state.prop_list, vec!["interfaces"]);
+    let ifaces = sub_state.interfaces.to_vec();
+    assert_eq!(ifaces.len(), 1);
+    assert_eq!(ifaces[0].name(), "eth2");
+}
+
+#[test]
+fn test_migrate_deprecated_keys_of_old_schema() {
+    start_warning_collection();
+    let net_state: NetworkState = serde_yaml::from_str(
+        r"---
+version: 1
+interfaces:
+- name: mac0
+  type: mac-vlan
+  mac-vlan:
+    base-iface: eth1
+    mode: passthru
+    accept-all-mac: true
+route-rules:
+  config:
+  - ip-to: 192.0.2.0/24
+    suppress_prefixlength: 0
+",
+    )
+    .unwrap();
+
+    assert_eq!(net_state.version, Some(2));
+    let iface: MacVlanInterface = match &net_state.interfaces.to_vec()[0] {
+        Interface::MacVlan(i) => i.clone(),
+        _ => panic!("Should be mac-vlan interface"),
+    };
+    assert_eq!(iface.mac_vlan.unwrap().accept_all_mac, Some(true));
+    assert_eq!(
+        net_state.rules.config.as_ref().unwrap()[0].suppress_prefix_length,
+        Some(0)
+    );
+    let warnings = stop_warning_collection();
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings[0]
+        .msg
+        .contains("`accept-all-mac` of interface mac0"));
+    assert!(warnings[1].msg.contains("`suppress_prefixlength`"));
+}
+
+#[test]
+fn test_unknown_schema_version() {
+    let result = serde_yaml::from_str::<NetworkState>(
+        r"---
+version: 99
+interfaces: []
+",
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_generate_merged() {
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+  ipv4:
+    enabled: true
+    address:
+    - 192.0.2.3/24
+- name: eth2
+  type: ethernet
+  state: up
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-address: 192.0.2.2
+    next-hop-interface: eth1
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.250
+",
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+routes:
+  config:
+  - destination: 203.0.113.0/24
+    next-hop-address: 192.0.2.1
+    next-hop-interface: eth1
+",
+    )
+    .unwrap();
+
+    let merged = desired.generate_merged(&current).unwrap();
+
+    let ifaces = merged.interfaces.to_vec();
+    assert_eq!(ifaces.len(), 1);
+    assert_eq!(ifaces[0].name(), "eth1");
+    assert_eq!(ifaces[0].base_iface().mtu, Some(9000));
+    let rts = merged.routes.config.unwrap();
+    assert_eq!(rts.len(), 2);
+    assert_eq!(
+        rts[0].destination.as_ref().map(|i| i.as_str()),
+        Some("198.51.100.0/24")
+    );
+    assert_eq!(
+        rts[1].destination.as_ref().map(|i| i.as_str()),
+        Some("203.0.113.0/24")
+    );
+    assert!(merged.dns.is_empty());
+}
+
+#[test]
+fn test_unsupported_interfaces_ignored_when_apply() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+unsupported-interfaces:
+- name: wg0
+  type: wireguard
+",
+    )
+    .unwrap();
+
+    assert_eq!(desired.unsupported_interfaces.len(), 1);
+    assert_eq!(desired.unsupported_interfaces[0].name, "wg0");
+    assert_eq!(
+        desired.unsupported_interfaces[0].iface_type,
+        InterfaceType::Other("wireguard".to_string())
+    );
+    assert!(desired.is_empty());
+}
+
+#[test]
+fn test_hide_secrets() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  802.1x:
+    identity: client.example.org
+    eap-methods:
+    - tls
+    private-key: /etc/pki/802-1x-test/client.example.org.key
+    private-key-password: TOP_SECRET_8021X
+- name: macsec0
+  type: macsec
+  state: up
+  macsec:
+    encrypt: true
+    base-iface: eth1
+    mka-cak: 50b71a8ef0bd5751ea76de6d6c98c03a
+    mka-ckn: f2b4297d39da7330910a74abc0449feb45b5c0b9fc23df1430e1898fcf1c4550
+    port: 0
+    validation: strict
+    send-sci: true
+- name: hosta_conn
+  type: ipsec
+  state: up
+  libreswan:
+    right: 192.0.2.253
+    psk: TOP_SECRET_PSK
+",
+    )
+    .unwrap();
+
+    state.hide_secrets();
+
+    let output = serde_yaml::to_string(&state).unwrap();
+    assert!(!output.contains("TOP_SECRET_8021X"));
+    assert!(!output.contains("50b71a8ef0bd5751ea76de6d6c98c03a"));
+    assert!(!output.contains("TOP_SECRET_PSK"));
+    assert_eq!(
+        output
+            .matches(NetworkState::PASSWORD_HID_BY_NMSTATE)
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn test_is_hostname_or_dns_only() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+hostname:
+  config: host.example.org
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+",
+    )
+    .unwrap();
+    assert!(desired.is_hostname_or_dns_only());
+
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+    assert!(!desired.is_hostname_or_dns_only());
+    assert!(!NetworkState::new().is_hostname_or_dns_only());
+}
+
+#[test]
+fn test_ignore_routes_rules_and_dns() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.254
+route-rules:
+  config:
+  - ip-to: 198.51.100.0/24
+    route-table: 200
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+",
+    )
+    .unwrap();
+    desired
+        .set_ignore_routes(true)
+        .set_ignore_route_rules(true)
+        .set_ignore_dns(true);
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    assert!(!merged_state.routes.is_changed());
+    assert!(!merged_state.rules.is_changed());
+    assert!(!merged_state.dns.is_changed());
+    assert!(!merged_state.prop_list.contains(&"routes"));
+    assert!(!merged_state.prop_list.contains(&"rules"));
+    assert!(!merged_state.prop_list.contains(&"dns"));
+}
+
+#[test]
+fn test_net_state_sanitize() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+routes:
+  config:
+  - destination: 2001:db8:1::/64
+    next-hop-interface: eth1
+    next-hop-address: 2001:0db8::00fe
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    dhcp: false
+    dhcp-client-id: mac
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    address:
+    - ip: 2001:db8::1
+      prefix-length: 64
+",
+    )
+    .unwrap();
+
+    let (sanitized, warnings) = desired.sanitize().unwrap();
+
+    let route = &sanitized.routes.config.as_ref().unwrap()[0];
+    assert_eq!(route.next_hop_addr.as_deref(), Some("2001:db8::fe"));
+    let iface = sanitized
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet);
+    assert_eq!(
+        iface
+            .unwrap()
+            .base_iface()
+            .ipv4
+            .as_ref()
+            .unwrap()
+            .dhcp_client_id,
+        None
+    );
+    assert_eq!(warnings.len(), 2);
+    assert!(warnings.iter().any(|w| w.msg.contains("dhcp-client-id")));
+    assert!(warnings.iter().any(|w| w.msg.contains("2001:db8::fe")));
+}
+
+#[test]
+fn test_net_state_sanitize_invalid() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: balance-rr
+    port:
+    - bond0
+",
+    )
+    .unwrap();
+
+    let result = desired.sanitize();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_net_state_canonicalize() {
+    let state1: NetworkState = serde_yaml::from_str(
+        r"---
+routes:
+  config:
+  - destination: 2001:db8:2::/64
+    next-hop-interface: eth1
+    next-hop-address: 2001:DB8::FE
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.254
+interfaces:
+- name: eth2
+  type: ethernet
+  state: up
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1a
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+    - ip: 192.0.2.1
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    address:
+    - ip: 2001:DB8::2
+      prefix-length: 64
+    - ip: 2001:db8::1
+      prefix-length: 64
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: balance-rr
+    port:
+    - eth2
+",
+    )
+    .unwrap();
+    let state2: NetworkState = serde_yaml::from_str(
+        r"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.254
+  - destination: 2001:db8:2::/64
+    next-hop-interface: eth1
+    next-hop-address: 2001:db8::fe
+interfaces:
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: balance-rr
+    port:
+    - eth2
+- name: eth1
+  type: ethernet
+  state: up
+  mac-address: 00:23:45:67:89:1A
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+    - ip: 192.0.2.1
+      prefix-length: 24
+    - ip: 192.0.2.2
+      prefix-length: 24
+  ipv6:
+    enabled: true
+    address:
+    - ip: 2001:db8::2
+      prefix-length: 64
+    - ip: 2001:db8::1
+      prefix-length: 64
+- name: eth2
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+
+    assert_ne!(
+        serde_yaml::to_string(&state1).unwrap(),
+        serde_yaml::to_string(&state2).unwrap()
+    );
+    let canonical = state1.canonicalize().unwrap();
+    assert_eq!(
+        serde_yaml::to_string(&canonical).unwrap(),
+        serde_yaml::to_string(&state2.canonicalize().unwrap()).unwrap()
+    );
+    let eth1 = canonical
+        .interfaces
+        .get_iface("eth1", InterfaceType::Ethernet)
+        .unwrap();
+    // MAC address is only converted to lower case when serializing
+    assert_eq!(
+        eth1.base_iface().mac_address.as_ref().map(|m| m.as_str()),
+        Some("00:23:45:67:89:1A")
+    );
+    assert_eq!(
+        eth1.base_iface().mac_address,
+        state2
+            .interfaces
+            .get_iface("eth1", InterfaceType::Ethernet)
+            .and_then(|i| i.base_iface().mac_address.clone())
+    );
+    let canonical_yaml = state1.to_canonical_yaml().unwrap();
+    assert_eq!(canonical_yaml, state2.to_canonical_yaml().unwrap());
+    assert!(canonical_yaml.contains("mac-address: 00:23:45:67:89:1a"));
+    let ipv4_addrs: Vec<String> = eth1
+        .base_iface()
+        .ipv4
+        .as_ref()
+        .and_then(|i| i.addresses.as_ref())
+        .unwrap()
+        .iter()
+        .map(|a| a.ip.to_string())
+        .collect();
+    assert_eq!(ipv4_addrs, vec!["192.0.2.2", "192.0.2.1"]);
+}
+
+#[test]
+fn test_net_state_to_stable_yaml() {
+    let state: NetworkState = serde_yaml::from_str(
+        r"---
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.254
+interfaces:
+- name: eth1
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+  mtu: 1500
+  state: up
+  type: ethernet
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.253
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        state.to_stable_yaml().unwrap(),
+        r"dns-resolver:
+  config:
+    server:
+    - 192.0.2.253
+route-rules: {}
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-address: 192.0.2.254
+    next-hop-interface: eth1
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+  ipv4:
+    address:
+    - ip: 192.0.2.1
+      prefix-length: 24
+    dhcp: false
+    enabled: true
+"
+    );
+}
+
+#[test]
+fn test_net_state_trim_defaults() {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+  min-mtu: 68
+  max-mtu: 9000
+  accept-all-mac-addresses: false
+  lldp:
+    enabled: false
+  ethtool:
+    feature:
+      rx-checksum: true
+      tx-generic-segmentation: true
+      rx-gro-list: false
+    ring:
+      rx: 256
+      rx-max: 4096
+      tx: 256
+      tx-max: 4096
+- name: eth2
+  type: ethernet
+  state: up
+  ethtool:
+    feature:
+      rx-gro: true
+      tx-nocache-copy: false
+",
+    )
+    .unwrap();
+
+    state.trim_defaults();
+
+    assert_eq!(
+        state.to_stable_yaml().unwrap(),
+        r"dns-resolver: {}
+route-rules: {}
+routes: {}
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+  ethtool:
+    feature:
+      rx-checksum: true
+    ring:
+      rx: 256
+      tx: 256
+- name: eth2
+  type: ethernet
+  state: up
+"
+    );
+}
+
+#[test]
+fn test_auto_absent_children() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: absent
+",
+    )
+    .unwrap();
+    desired.set_auto_absent_children(true);
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+- name: eth1.10
+  type: vlan
+  state: up
+  vlan:
+    base-iface: eth1
+    id: 10
+- name: mac0
+  type: mac-vlan
+  state: up
+  mac-vlan:
+    base-iface: eth1.10
+    mode: vepa
+- name: eth2.10
+  type: vlan
+  state: up
+  vlan:
+    base-iface: eth2
+    id: 10
+",
+    )
+    .unwrap();
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    for (iface_name, iface_type) in [
+        ("eth1.10", InterfaceType::Vlan),
+        ("mac0", InterfaceType::MacVlan),
+    ] {
+        let iface = merged_state
+            .interfaces
+            .get_iface(iface_name, iface_type)
+            .unwrap();
+        assert!(iface.is_changed());
+        assert!(iface.merged.is_absent());
+    }
+    assert!(!merged_state
+        .interfaces
+        .get_iface("eth2.10", InterfaceType::Vlan)
+        .unwrap()
+        .is_changed());
+}
+
+struct TestSecretProvider;
+
+impl NmstateSecretProvider for TestSecretProvider {
+    fn get_secrets(
+        &self,
+        _iface_name: &str,
+        _setting_name: &str,
+        _hints: &[String],
+    ) -> Option<HashMap<String, Secret>> {
+        None
+    }
+}
+
+fn gen_8021x_secret_states() -> (NetworkState, NetworkState) {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  802.1x:
+    identity: client.example.org
+    eap-methods:
+    - tls
+    private-key: /etc/pki/client.key
+",
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap();
+    (desired, current)
+}
+
+#[test]
+fn test_secret_provider_keep_secrets_system_owned_by_default() {
+    let (mut desired, current) = gen_8021x_secret_states();
+    desired.set_secret_provider(Arc::new(TestSecretProvider));
+    let sub_state = desired.clone_options_only();
+    assert!(sub_state.secret_provider.is_some());
+    assert_eq!(sub_state.secret_provider, desired.secret_provider);
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    assert!(!merged_state.secrets_agent_owned);
+}
+
+#[test]
+fn test_secret_provider_mark_merged_state_secrets_agent_owned() {
+    let (mut desired, current) = gen_8021x_secret_states();
+    desired.set_secret_provider(Arc::new(TestSecretProvider));
+    desired.set_secrets_agent_owned(true);
+    assert!(desired.clone_options_only().secrets_agent_owned);
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    assert!(merged_state.secrets_agent_owned);
+}
+
+#[test]
+fn test_secrets_agent_owned_without_secret_provider() {
+    let (mut desired, current) = gen_8021x_secret_states();
+    desired.set_secrets_agent_owned(true);
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    assert!(!merged_state.secrets_agent_owned);
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_management_ip_reachable() {
+    let net_state: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    address:
+    - ip: 192.0.2.10
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  state: down
+routes:
+  running:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+  - destination: 203.0.113.0/24
+    next-hop-interface: eth2
+    next-hop-address: 192.0.2.1
+  - destination: 2001:db8::/64
+    next-hop-interface: eth1
+    table-id: 100
+",
+    )
+    .unwrap();
+
+    assert!(net_state.is_ip_reachable(&"192.0.2.99".parse().unwrap()));
+    assert!(net_state.is_ip_reachable(&"198.51.100.8".parse().unwrap()));
+    assert!(!net_state.is_ip_reachable(&"203.0.113.8".parse().unwrap()));
+    assert!(!net_state.is_ip_reachable(&"2001:db8::8".parse().unwrap()));
+    assert!(!net_state.is_ip_reachable(&"10.0.0.1".parse().unwrap()));
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_verify_against_current() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+",
+    )
+    .unwrap();
+    let mut current: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+",
+    )
+    .unwrap();
+
+    desired.verify_against(&current).unwrap();
+
+    current = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+",
+    )
+    .unwrap();
+    let result = desired.verify_against(&current);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::VerificationError);
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_check_against_current() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 1500
+",
+    )
+    .unwrap();
+    let mut current = desired.clone();
+
+    assert_eq!(desired.check_against(&current).unwrap(), None);
+
+    current = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  mtu: 9000
+",
+    )
+    .unwrap();
+    let mismatch = desired.check_against(&current).unwrap().unwrap();
+
+    assert!(mismatch.contains("mtu"));
+    assert!(mismatch.contains("9000"));
+}
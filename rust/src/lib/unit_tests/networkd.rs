@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    warning::{start_warning_collection, stop_warning_collection},
+    ErrorKind, NetworkState,
+};
+
+#[test]
+fn test_networkd_gen_conf_bond_vlan_bridge() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+dns-resolver:
+  config:
+    server:
+    - 192.0.2.1
+    search:
+    - example.org
+routes:
+  config:
+  - destination: 0.0.0.0/0
+    next-hop-interface: br0
+    next-hop-address: 192.0.2.254
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+- name: eth2
+  type: ethernet
+  state: up
+- name: bond0
+  type: bond
+  state: up
+  mtu: 9000
+  link-aggregation:
+    mode: active-backup
+    options:
+      miimon: 100
+    port:
+    - eth1
+    - eth2
+- name: bond0.100
+  type: vlan
+  state: up
+  vlan:
+    base-iface: bond0
+    id: 100
+- name: br0
+  type: linux-bridge
+  state: up
+  bridge:
+    options:
+      stp:
+        enabled: false
+    port:
+    - name: bond0.100
+  ipv4:
+    enabled: true
+    dhcp: false
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+  ipv6:
+    enabled: false
+",
+    )
+    .unwrap();
+
+    start_warning_collection();
+    let confs = desired.gen_networkd_conf().unwrap();
+    assert!(stop_warning_collection().is_empty());
+    let mut confs = confs["systemd-networkd"].clone();
+    confs.sort_unstable();
+
+    let file_names: Vec<&str> =
+        confs.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(
+        file_names,
+        vec![
+            "bond0.100.netdev",
+            "bond0.100.network",
+            "bond0.netdev",
+            "bond0.network",
+            "br0.netdev",
+            "br0.network",
+            "eth1.network",
+            "eth2.network",
+        ]
+    );
+    assert_eq!(
+        confs[2].1,
+        "[NetDev]\nName=bond0\nKind=bond\nMTUBytes=9000\n\n\
+        [Bond]\nMode=active-backup\nMIIMonitorSec=100ms\n"
+    );
+    assert!(confs[3].1.contains("VLAN=bond0.100\n"));
+    assert!(confs[0].1.contains("[VLAN]\nId=100\n"));
+    assert!(confs[1].1.contains("Bridge=br0\n"));
+    assert!(confs[4].1.contains("[Bridge]\nSTP=no\n"));
+    assert!(confs[5].1.contains("DHCP=no\n"));
+    assert!(confs[5].1.contains("LinkLocalAddressing=no\n"));
+    assert!(confs[5].1.contains("Address=192.0.2.2/24\n"));
+    assert!(confs[5].1.contains("DNS=192.0.2.1\nDomains=example.org\n"));
+    assert!(confs[5]
+        .1
+        .contains("[Route]\nDestination=0.0.0.0/0\nGateway=192.0.2.254\n"));
+    assert!(confs[6].1.contains("Bond=bond0\n"));
+    assert!(!confs[6].1.contains("DNS="));
+}
+
+#[test]
+fn test_networkd_gen_conf_unsupported_iface_type() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: br0
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: eth1
+",
+    )
+    .unwrap();
+
+    let result = desired.gen_networkd_conf();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+fn test_networkd_gen_conf_warn_dropped_props() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.254
+    weight: 2
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ethtool:
+    feature:
+      rx-checksum: false
+  ipv4:
+    enabled: true
+    dhcp: true
+    dhcp-client-id: mac
+",
+    )
+    .unwrap();
+
+    start_warning_collection();
+    desired.gen_networkd_conf().unwrap();
+    let warnings: Vec<String> = stop_warning_collection()
+        .into_iter()
+        .map(|w| w.msg)
+        .collect();
+
+    assert_eq!(warnings.len(), 3);
+    assert!(warnings[0].contains("property ethtool of interface eth1"));
+    assert!(warnings[1].contains("ipv4.dhcp-client-id"));
+    assert!(warnings[2].contains("property weight of route"));
+}
@@ -1,7 +1,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    nm::dns::{reselect_dns_ifaces, store_dns_config_to_iface},
+    nm::{
+        dns::{reselect_dns_ifaces, store_dns_config_to_iface},
+        is_dns_apply_required, nm_can_apply_hostname_dns_only,
+    },
     DnsClientState, ErrorKind, InterfaceType, MergedNetworkState, NetworkState,
 };
 
@@ -386,3 +389,332 @@ fn test_copy_ip_stack_if_marked_for_dns() {
     assert!(iface.base_iface().ipv6.is_some());
     assert!(iface.base_iface().ipv4.is_some());
 }
+
+#[test]
+fn test_dns_use_desired_interface() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - 8.8.8.8
+            - 2001:4860:4860::8888
+            interface: dummy1
+        interfaces:
+          - name: dummy0
+            type: dummy
+            state: up
+            ipv4:
+              address:
+              - ip: 192.0.2.251
+                prefix-length: 24
+              dhcp: false
+              enabled: true
+            ipv6:
+              enabled: true
+              autoconf: false
+              dhcp: false
+              address:
+              - ip: 2001:db8::1
+                prefix-length: 64",
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+          - name: dummy1
+            type: dummy
+            state: up
+            ipv4:
+              enabled: true
+              dhcp: true
+            ipv6:
+              enabled: true
+              dhcp: true
+              autoconf: true",
+    )
+    .unwrap();
+
+    let mut merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    let (v4_iface, v6_iface) =
+        reselect_dns_ifaces(&merged_state, &[], &[], &[], &[]);
+
+    assert_eq!(v4_iface, "dummy1");
+    assert_eq!(v6_iface, "dummy1");
+
+    store_dns_config_to_iface(&mut merged_state, &[], &[]).unwrap();
+
+    let iface = merged_state
+        .interfaces
+        .get_iface("dummy1", InterfaceType::Dummy)
+        .unwrap()
+        .for_apply
+        .as_ref()
+        .unwrap();
+    assert_eq!(
+        iface
+            .base_iface()
+            .ipv4
+            .as_ref()
+            .and_then(|i| i.dns.as_ref())
+            .and_then(|d| d.server.clone()),
+        Some(vec!["8.8.8.8".to_string()])
+    );
+    assert_eq!(
+        iface
+            .base_iface()
+            .ipv6
+            .as_ref()
+            .and_then(|i| i.dns.as_ref())
+            .and_then(|d| d.server.clone()),
+        Some(vec!["2001:4860:4860::8888".to_string()])
+    );
+}
+
+#[test]
+fn test_dns_desired_interface_has_ipv6_disabled() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - 2001:4860:4860::8888
+            interface: eth1
+        ",
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+          - name: eth1
+            type: ethernet
+            state: up
+            ipv4:
+              enabled: true
+              dhcp: true
+            ipv6:
+              enabled: false
+        ",
+    )
+    .unwrap();
+
+    let result = MergedNetworkState::new(desired, current, false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_dns_desired_interface_not_exist() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - 8.8.8.8
+            interface: eth9
+        ",
+    )
+    .unwrap();
+
+    let result =
+        MergedNetworkState::new(desired, NetworkState::new(), false, false);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_dns_only_apply_via_global_dns() {
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+          - name: eth1
+            type: ethernet
+            state: up
+            ipv6:
+              enabled: true
+              dhcp: true
+              autoconf: true",
+    )
+    .unwrap();
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - 2001:4860:4860::8888",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, current.clone(), false, false)
+            .unwrap();
+    assert!(nm_can_apply_hostname_dns_only(&merged_state));
+
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - fe80::deef:1%eth1",
+    )
+    .unwrap();
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    assert!(!nm_can_apply_hostname_dns_only(&merged_state));
+}
+
+fn gen_dns_held_by_iface_state(dns_iface_name: &str) -> NetworkState {
+    let mut state: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - 8.8.8.8
+        interfaces:
+          - name: dummy0
+            type: dummy
+            state: up
+            ipv4:
+              enabled: true
+              dhcp: true
+          - name: dummy1
+            type: dummy
+            state: up
+            ipv4:
+              enabled: true
+              dhcp: true",
+    )
+    .unwrap();
+    let dns_conf: DnsClientState = serde_yaml::from_str(
+        r"---
+        server:
+        - 8.8.8.8",
+    )
+    .unwrap();
+    if let Some(ipv4) = state
+        .interfaces
+        .get_iface_mut(dns_iface_name, InterfaceType::Dummy)
+        .and_then(|i| i.base_iface_mut().ipv4.as_mut())
+    {
+        ipv4.dns = Some(dns_conf);
+    }
+    state
+}
+
+#[test]
+fn test_dns_desired_interface_unchanged() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - 8.8.8.8
+            interface: dummy1",
+    )
+    .unwrap();
+
+    let merged_state = MergedNetworkState::new(
+        desired,
+        gen_dns_held_by_iface_state("dummy1"),
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert!(!merged_state.dns.is_changed());
+}
+
+#[test]
+fn test_dns_desired_interface_moved() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - 8.8.8.8
+            interface: dummy1",
+    )
+    .unwrap();
+
+    let merged_state = MergedNetworkState::new(
+        desired,
+        gen_dns_held_by_iface_state("dummy0"),
+        false,
+        false,
+    )
+    .unwrap();
+
+    assert!(merged_state.dns.is_changed());
+}
+
+fn gen_dns_iface_ipv4_disabled_states() -> (NetworkState, NetworkState) {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+          - name: dummy0
+            type: dummy
+            state: up
+            ipv4:
+              enabled: false",
+    )
+    .unwrap();
+    let mut current: NetworkState = serde_yaml::from_str(
+        r"---
+        dns-resolver:
+          config:
+            server:
+            - 192.0.2.1
+        interfaces:
+          - name: dummy0
+            type: dummy
+            state: up
+            ipv4:
+              enabled: true
+              address:
+              - ip: 192.0.2.251
+                prefix-length: 24
+              dhcp: false",
+    )
+    .unwrap();
+    let dns_conf: DnsClientState = serde_yaml::from_str(
+        r"---
+        server:
+        - 192.0.2.1",
+    )
+    .unwrap();
+    if let Some(ipv4) = current
+        .interfaces
+        .get_iface_mut("dummy0", InterfaceType::Dummy)
+        .and_then(|i| i.base_iface_mut().ipv4.as_mut())
+    {
+        ipv4.dns = Some(dns_conf);
+    }
+    (desired, current)
+}
+
+#[test]
+fn test_dns_apply_required_when_dns_iface_invalid() {
+    let (desired, current) = gen_dns_iface_ipv4_disabled_states();
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    assert!(!merged_state.dns.is_changed());
+    assert!(is_dns_apply_required(&merged_state));
+}
+
+#[test]
+fn test_dns_apply_not_required_when_dns_ignored() {
+    let (mut desired, current) = gen_dns_iface_ipv4_disabled_states();
+    desired.set_ignore_dns(true);
+
+    let merged_state =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    assert!(!is_dns_apply_required(&merged_state));
+}
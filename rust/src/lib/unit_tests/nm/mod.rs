@@ -6,3 +6,5 @@ mod dns;
 mod route;
 #[cfg(test)]
 mod route_rule;
+#[cfg(test)]
+mod version;
@@ -0,0 +1,76 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    nm::{
+        check_feature_versions, is_nm_version_at_least, nm_unsupported_features,
+    },
+    ErrorKind, NmstateFeature,
+};
+
+#[test]
+fn test_nm_version_at_least() {
+    assert!(is_nm_version_at_least("1.40.0", 1, 40));
+    assert!(is_nm_version_at_least("1.45.2-1.fc39", 1, 40));
+    assert!(is_nm_version_at_least("2.0.0", 1, 40));
+    assert!(!is_nm_version_at_least("1.39.90", 1, 40));
+    assert!(!is_nm_version_at_least("", 1, 40));
+}
+
+#[test]
+fn test_nm_check_feature_versions() {
+    let features = [
+        NmstateFeature::OvsDbInterface,
+        NmstateFeature::OvsDbInterfaceOtherConfig,
+    ];
+    assert!(check_feature_versions("1.42.0", &features).is_ok());
+    assert!(check_feature_versions("1.45.2-1.fc39", &features).is_ok());
+
+    let result = check_feature_versions("1.40.16", &features);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+        assert!(e.msg().contains("requires NetworkManager >= 1.42"));
+    }
+}
+
+#[test]
+fn test_nm_unsupported_features() {
+    assert_eq!(
+        nm_unsupported_features("1.40.16"),
+        vec![
+            NmstateFeature::Dhcpv4SendRelease,
+            NmstateFeature::Dhcpv6SendRelease,
+            NmstateFeature::OvsDbInterfaceOtherConfig
+        ]
+    );
+    assert_eq!(
+        nm_unsupported_features("1.42.0"),
+        vec![
+            NmstateFeature::Dhcpv4SendRelease,
+            NmstateFeature::Dhcpv6SendRelease,
+        ]
+    );
+    assert!(nm_unsupported_features("1.48.0").is_empty());
+}
+
+#[test]
+fn test_nm_check_feature_versions_dhcp_send_release() {
+    let features = [NmstateFeature::Dhcpv4SendRelease];
+    assert!(check_feature_versions("1.48.0", &features).is_ok());
+
+    let result = check_feature_versions("1.46.2", &features);
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+        assert!(e.msg().contains("requires NetworkManager >= 1.48"));
+    }
+}
+
+#[test]
+fn test_nm_check_feature_versions_ipv6_token() {
+    // IPv6 token is applied via kernel for NetworkManager older than 1.4
+    let features = [NmstateFeature::Ipv6Token];
+    for version in ["1.4.0", "1.2.6", ""] {
+        assert!(check_feature_versions(version, &features).is_ok());
+    }
+}
@@ -3,8 +3,8 @@
 use std::convert::TryFrom;
 
 use crate::{
-    ErrorKind, MergedOvnConfiguration, OvnBridgeMapping, OvnBridgeMappingState,
-    OvnConfiguration,
+    ErrorKind, InterfaceType, MergedNetworkState, MergedOvnConfiguration,
+    NetworkState, OvnBridgeMapping, OvnBridgeMappingState, OvnConfiguration,
 };
 
 #[test]
@@ -265,3 +265,149 @@ fn test_ovn_map_support_state_present() {
         }]
     )
 }
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ovn_map_to_undefined_bridge() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        ovn:
+          bridge-mappings:
+          - localnet: net1
+            bridge: br1",
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: br0
+          type: ovs-bridge
+          state: up
+          bridge:
+            port:
+            - name: br0",
+    )
+    .unwrap();
+
+    let merged =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+    let result = merged.ovn.validate_bridges(&merged.interfaces);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("br1"));
+    }
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ovn_map_to_bridge_in_current_or_desired() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: br1
+          type: ovs-bridge
+          state: up
+        ovn:
+          bridge-mappings:
+          - localnet: net0
+            bridge: br0
+          - localnet: net1
+            bridge: br1",
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: br0
+          type: ovs-bridge
+          state: up
+          bridge:
+            port:
+            - name: br0",
+    )
+    .unwrap();
+
+    let merged =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    merged.ovn.validate_bridges(&merged.interfaces).unwrap();
+}
+
+#[cfg(feature = "query_apply")]
+#[test]
+fn test_ovn_map_to_absent_bridge() {
+    let desired: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: br0
+          type: ovs-bridge
+          state: absent
+        ovn:
+          bridge-mappings:
+          - localnet: net0
+            bridge: br0",
+    )
+    .unwrap();
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: br0
+          type: ovs-bridge
+          state: up
+          bridge:
+            port:
+            - name: br0",
+    )
+    .unwrap();
+
+    let merged =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    assert!(merged.ovn.validate_bridges(&merged.interfaces).is_err());
+}
+
+#[test]
+fn test_ovn_auto_create_bridges() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"---
+        ovn:
+          bridge-mappings:
+          - localnet: net0
+            bridge: br0
+          - localnet: net1
+            bridge: br1
+          - localnet: net2
+            bridge: br1",
+    )
+    .unwrap();
+    desired.set_ovn_auto_create_bridges(true);
+    let current: NetworkState = serde_yaml::from_str(
+        r"---
+        interfaces:
+        - name: br0
+          type: ovs-bridge
+          state: up
+          bridge:
+            port:
+            - name: br0",
+    )
+    .unwrap();
+
+    let merged =
+        MergedNetworkState::new(desired, current, false, false).unwrap();
+
+    let br0 = merged
+        .interfaces
+        .get_iface("br0", InterfaceType::OvsBridge)
+        .unwrap();
+    assert!(!br0.is_desired());
+    let br1 = merged
+        .interfaces
+        .get_iface("br1", InterfaceType::OvsBridge)
+        .unwrap();
+    assert!(br1.is_desired());
+    assert!(br1.merged.is_up());
+    assert_eq!(br1.merged.ports(), Some(vec!["br1"]));
+}
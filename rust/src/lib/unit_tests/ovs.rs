@@ -2,7 +2,8 @@
 
 use crate::{
     ErrorKind, Interface, InterfaceType, Interfaces, MergedInterface,
-    MergedInterfaces, OvsBridgeInterface, OvsInterface,
+    MergedInterfaces, MergedNetworkState, NetworkState, OvsBridgeInterface,
+    OvsInterface,
 };
 
 #[test]
@@ -531,6 +532,28 @@ fn test_ovs_bridge_vlan_filter_no_trunk_tags_with_trunk_mode() {
     }
 }
 
+#[test]
+fn test_ovs_dpdk_stringlized_attributes() {
+    let iface: OvsInterface = serde_yaml::from_str(
+        r#"
+        name: ovs0
+        type: ovs-interface
+        state: up
+        dpdk:
+          devargs: 0000:af:00.1
+          n_rxq: "2"
+          n_rxq_desc: "1024"
+          n_txq_desc: "2048"
+        "#,
+    )
+    .unwrap();
+
+    let dpdk_conf = iface.dpdk.unwrap();
+    assert_eq!(dpdk_conf.rx_queue, Some(2));
+    assert_eq!(dpdk_conf.n_rxq_desc, Some(1024));
+    assert_eq!(dpdk_conf.n_txq_desc, Some(2048));
+}
+
 #[test]
 fn test_validate_dpdk_n_rxq_desc() {
     let desired: OvsInterface = serde_yaml::from_str(
@@ -921,3 +944,112 @@ fn test_ovs_bridge_deprecated_prop() {
 
     assert_eq!(iface.ports(), Some(vec!["eth1"]));
 }
+
+#[test]
+fn test_validate_dpdk_rx_queue_zero() {
+    let desired: OvsInterface = serde_yaml::from_str(
+        r"
+        name: ovs0
+        type: ovs-interface
+        state: up
+        dpdk:
+          devargs: 0000:af:00.1
+          n_rxq: 0
+        ",
+    )
+    .unwrap();
+
+    let result = desired.sanitize(true);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+fn gen_cni_owned_ovs_current() -> NetworkState {
+    serde_yaml::from_str(
+        r"---
+interfaces:
+- name: br-int
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: br-int
+    - name: veth1
+- name: br-int
+  type: ovs-interface
+  state: up
+- name: veth1
+  type: veth
+  state: up
+  ovs-db:
+    external_ids:
+      iface-id: pod1
+      k8s.ovn.org/nad: default
+- name: br1
+  type: ovs-bridge
+  state: up
+  bridge:
+    port:
+    - name: br1
+- name: br1
+  type: ovs-interface
+  state: up
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_ovs_bridge_with_cni_owned_port() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: br-int
+  type: ovs-bridge
+  state: absent
+",
+    )
+    .unwrap();
+
+    MergedNetworkState::new(
+        desired.clone(),
+        gen_cni_owned_ovs_current(),
+        false,
+        false,
+    )
+    .unwrap();
+
+    desired.set_protect_cni_owned_ovs(true);
+    let result = MergedNetworkState::new(
+        desired,
+        gen_cni_owned_ovs_current(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("iface-id"));
+    }
+}
+
+#[test]
+fn test_ovs_bridge_not_owned_by_cni() {
+    let mut desired: NetworkState = serde_yaml::from_str(
+        r"---
+interfaces:
+- name: br1
+  type: ovs-bridge
+  state: absent
+",
+    )
+    .unwrap();
+    desired.set_protect_cni_owned_ovs(true);
+
+    MergedNetworkState::new(desired, gen_cni_owned_ovs_current(), false, false)
+        .unwrap();
+}
@@ -262,6 +262,9 @@ fn test_policy_capture_route_rule() {
 
     let rules = state.rules.config.as_ref().unwrap();
     assert_eq!(rules.len(), 1);
-    assert_eq!(rules[0].ip_from, Some("2001:db8:b::/64".to_string()));
+    assert_eq!(
+        rules[0].ip_from.as_ref().map(|i| i.as_str()),
+        Some("2001:db8:b::/64")
+    );
     assert_eq!(rules[0].table_id, Some(500));
 }
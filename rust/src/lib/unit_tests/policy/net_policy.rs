@@ -58,8 +58,12 @@ desiredState:
     assert_eq!(ifaces[0].name(), "br1");
     assert_eq!(ifaces[0].ports(), Some(vec!["eth1"]));
     assert_eq!(
-        ifaces[0].base_iface().mac_address,
-        Some("11:22:33:44:55:66".to_string())
+        ifaces[0]
+            .base_iface()
+            .mac_address
+            .as_ref()
+            .map(|i| i.as_str()),
+        Some("11:22:33:44:55:66")
     );
 }
 
@@ -152,9 +156,15 @@ desiredState:
     );
     let routes = state.routes.config.as_ref().unwrap();
     assert_eq!(routes.len(), 2);
-    assert_eq!(routes[0].destination, Some("0.0.0.0/0".to_string()));
+    assert_eq!(
+        routes[0].destination.as_ref().map(|i| i.as_str()),
+        Some("0.0.0.0/0")
+    );
     assert_eq!(routes[0].next_hop_iface, Some("br1".to_string()));
-    assert_eq!(routes[1].destination, Some("192.51.100.0/24".to_string()));
+    assert_eq!(
+        routes[1].destination.as_ref().map(|i| i.as_str()),
+        Some("192.51.100.0/24")
+    );
     assert_eq!(routes[1].next_hop_iface, Some("br1".to_string()));
 }
 
@@ -245,9 +255,15 @@ fn test_policy_convert_dhcp_to_static_with_dns() {
     );
     let routes = state.routes.config.as_ref().unwrap();
     assert_eq!(routes.len(), 2);
-    assert_eq!(routes[0].destination, Some("0.0.0.0/0".to_string()));
+    assert_eq!(
+        routes[0].destination.as_ref().map(|i| i.as_str()),
+        Some("0.0.0.0/0")
+    );
     assert_eq!(routes[0].next_hop_iface, Some("eth1".to_string()));
-    assert_eq!(routes[1].destination, Some("192.51.100.0/24".to_string()));
+    assert_eq!(
+        routes[1].destination.as_ref().map(|i| i.as_str()),
+        Some("192.51.100.0/24")
+    );
     assert_eq!(routes[1].next_hop_iface, Some("eth1".to_string()));
     let dns_config = state.dns.config.as_ref().unwrap();
     assert_eq!(
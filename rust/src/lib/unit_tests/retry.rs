@@ -0,0 +1,52 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    query_apply::retry::with_transient_retry, ErrorKind, NmstateError,
+};
+
+#[test]
+fn test_transient_retry_recovered() {
+    let mut count = 0;
+    let result = with_transient_retry(1, 5, || {
+        count += 1;
+        if count < 3 {
+            Err(NmstateError::new(
+                ErrorKind::BackendUnavailable,
+                "NetworkManager restarting".to_string(),
+            ))
+        } else {
+            Ok(count)
+        }
+    });
+    assert_eq!(result.unwrap(), 3);
+}
+
+#[test]
+fn test_transient_retry_exhausted() {
+    let mut count = 0;
+    let result: Result<(), NmstateError> = with_transient_retry(1, 3, || {
+        count += 1;
+        Err(NmstateError::new(
+            ErrorKind::BackendUnavailable,
+            "NetworkManager restarting".to_string(),
+        ))
+    });
+    assert_eq!(count, 3);
+    let e = result.unwrap_err();
+    assert_eq!(e.kind(), ErrorKind::BackendUnavailable);
+    assert!(e.msg().starts_with("Giving up after 3 attempts"));
+}
+
+#[test]
+fn test_transient_retry_not_retry_other_error() {
+    let mut count = 0;
+    let result: Result<(), NmstateError> = with_transient_retry(1, 3, || {
+        count += 1;
+        Err(NmstateError::new(
+            ErrorKind::InvalidArgument,
+            "invalid".to_string(),
+        ))
+    });
+    assert_eq!(count, 1);
+    assert_eq!(result.unwrap_err().kind(), ErrorKind::InvalidArgument);
+}
@@ -7,7 +7,9 @@ use crate::{
         TEST_IPV4_NET1, TEST_IPV6_ADDR1, TEST_IPV6_ADDR2, TEST_IPV6_NET1,
         TEST_IPV6_NET2, TEST_NIC, TEST_ROUTE_METRIC,
     },
-    ErrorKind, InterfaceType, MergedRoutes, RouteEntry, RouteState, Routes,
+    warning::{start_warning_collection, stop_warning_collection},
+    ErrorKind, InterfaceType, MergedRoutes, NetworkState, RouteEntry,
+    RouteState, Routes,
 };
 
 #[test]
@@ -207,7 +209,10 @@ destination: "192.0.2.1/24"
     )
     .unwrap();
     route.sanitize().unwrap();
-    assert_eq!(route.destination, Some("192.0.2.0/24".to_string()));
+    assert_eq!(
+        route.destination.as_ref().map(|i| i.as_str()),
+        Some("192.0.2.0/24")
+    );
 }
 
 #[test]
@@ -219,7 +224,10 @@ destination: "192.0.2.1"
     )
     .unwrap();
     route.sanitize().unwrap();
-    assert_eq!(route.destination, Some("192.0.2.1/32".to_string()));
+    assert_eq!(
+        route.destination.as_ref().map(|i| i.as_str()),
+        Some("192.0.2.1/32")
+    );
 }
 
 #[test]
@@ -231,7 +239,10 @@ destination: "2001:db8:1::1/64"
     )
     .unwrap();
     route.sanitize().unwrap();
-    assert_eq!(route.destination, Some("2001:db8:1::/64".to_string()));
+    assert_eq!(
+        route.destination.as_ref().map(|i| i.as_str()),
+        Some("2001:db8:1::/64")
+    );
 }
 
 #[test]
@@ -243,7 +254,10 @@ destination: "2001:db8:1::1"
     )
     .unwrap();
     route.sanitize().unwrap();
-    assert_eq!(route.destination, Some("2001:db8:1::1/128".to_string()));
+    assert_eq!(
+        route.destination.as_ref().map(|i| i.as_str()),
+        Some("2001:db8:1::1/128")
+    );
 }
 
 #[test]
@@ -256,25 +270,27 @@ next-hop-address: "2001:db8:a:0000:000::1"
     )
     .unwrap();
     route.sanitize().unwrap();
-    assert_eq!(route.destination, Some("2001:db8:1::1/128".to_string()));
+    assert_eq!(
+        route.destination.as_ref().map(|i| i.as_str()),
+        Some("2001:db8:1::1/128")
+    );
     assert_eq!(route.next_hop_addr, Some("2001:db8:a::1".to_string()));
 }
 
 #[test]
 fn test_route_not_allowing_empty_dst() {
-    let desired: Routes = serde_yaml::from_str(
+    let result = NetworkState::new_from_yaml(
         r#"
-        config:
-        - destination: ""
-          state: absent
+        routes:
+          config:
+          - destination: ""
+            state: absent
         "#,
-    )
-    .unwrap();
-    let result = desired.validate();
+    );
     assert!(result.is_err());
     if let Err(e) = result {
         assert_eq!(e.kind(), ErrorKind::InvalidArgument);
-        assert!(e.msg().contains("Invalid IP address"));
+        assert!(e.msg().contains("Invalid IP network"));
     }
 }
 
@@ -353,16 +369,15 @@ config:
     assert!(result.is_err());
     assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidArgument);
 
-    let routes2: Routes = serde_yaml::from_str(
+    let result = NetworkState::new_from_yaml(
         r"
-config:
-- destination: 0.0.0.0/f
-  next-hop-address: 192.0.2.1
-  next-hop-interface: eth1
+routes:
+  config:
+  - destination: 0.0.0.0/f
+    next-hop-address: 192.0.2.1
+    next-hop-interface: eth1
 ",
-    )
-    .unwrap();
-    let result = routes2.validate();
+    );
     assert!(result.is_err());
     assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidArgument);
 
@@ -379,29 +394,27 @@ config:
     assert!(result.is_err());
     assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidArgument);
 
-    let routes4: Routes = serde_yaml::from_str(
+    let result = NetworkState::new_from_yaml(
         r"
-config:
-- destination: 0.0.0.0.0/0
-  next-hop-address: 192.0.2.1
-  next-hop-interface: eth1
+routes:
+  config:
+  - destination: 0.0.0.0.0/0
+    next-hop-address: 192.0.2.1
+    next-hop-interface: eth1
 ",
-    )
-    .unwrap();
-    let result = routes4.validate();
+    );
     assert!(result.is_err());
     assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidArgument);
 
-    let routes5: Routes = serde_yaml::from_str(
+    let result = NetworkState::new_from_yaml(
         r"
-config:
-- destination: 0.0.0.0.0/7
-  next-hop-address: 192.0.2.1
-  next-hop-interface: eth1
+routes:
+  config:
+  - destination: 0.0.0.0.0/7
+    next-hop-address: 192.0.2.1
+    next-hop-interface: eth1
 ",
-    )
-    .unwrap();
-    let result = routes5.validate();
+    );
     assert!(result.is_err());
     assert_eq!(result.err().unwrap().kind(), ErrorKind::InvalidArgument);
 }
@@ -432,3 +445,157 @@ fn test_route_matching_empty_via_with_none() {
     assert!(!absent_route.is_match(&not_match_route));
     assert!(!absent_route.is_match(&match_route));
 }
+
+#[test]
+fn test_route_dst_sanitized_on_deserialize() {
+    let routes: Routes = serde_yaml::from_str(
+        r"
+config:
+- destination: 198.51.100.1/24
+- destination: 2001:db8::1
+",
+    )
+    .unwrap();
+    let config = routes.config.unwrap();
+    assert_eq!(
+        config[0].destination.as_ref().map(|i| i.as_str()),
+        Some("198.51.100.0/24")
+    );
+    assert_eq!(
+        config[1].destination.as_ref().map(|i| i.as_str()),
+        Some("2001:db8::1/128")
+    );
+    assert!(config[1].destination.as_ref().unwrap().is_ipv6());
+}
+
+#[test]
+fn test_route_conflict_route_type() {
+    let des_routes: Routes = serde_yaml::from_str(
+        r"
+        config:
+        - destination: 198.51.100.0/24
+          route-type: blackhole
+        - destination: 198.51.100.0/24
+          route-type: unreachable
+        ",
+    )
+    .unwrap();
+    let merged_ifaces = gen_merged_ifaces_for_route_test();
+
+    let result = MergedRoutes::new(des_routes, Routes::new(), &merged_ifaces);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("different route type"));
+    }
+}
+
+#[test]
+fn test_route_conflict_metric_warning() {
+    let des_routes: Routes = serde_yaml::from_str(
+        r"
+        config:
+        - destination: 198.51.100.0/24
+          next-hop-interface: eth1
+          next-hop-address: 192.0.2.1
+          metric: 100
+        - destination: 198.51.100.0/24
+          next-hop-interface: eth1
+          next-hop-address: 192.0.2.1
+          metric: 200
+        ",
+    )
+    .unwrap();
+    let merged_ifaces = gen_merged_ifaces_for_route_test();
+
+    start_warning_collection();
+    MergedRoutes::new(des_routes, Routes::new(), &merged_ifaces).unwrap();
+    let warnings = stop_warning_collection();
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].msg.contains("on metric"));
+}
+
+#[test]
+fn test_route_link_local_next_hop_with_scope() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r"
+        destination: ::/0
+        next-hop-address: FE80::1%eth1
+        ",
+    )
+    .unwrap();
+
+    route.sanitize().unwrap();
+
+    assert_eq!(route.next_hop_addr.as_deref(), Some("fe80::1"));
+    assert_eq!(route.next_hop_iface.as_deref(), Some("eth1"));
+}
+
+#[test]
+fn test_route_link_local_next_hop_scope_mismatch() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r"
+        destination: ::/0
+        next-hop-interface: eth2
+        next-hop-address: fe80::1%eth1
+        ",
+    )
+    .unwrap();
+
+    let result = route.sanitize();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_scope_on_global_next_hop() {
+    let mut route: RouteEntry = serde_yaml::from_str(
+        r"
+        destination: ::/0
+        next-hop-address: 2001:db8::1%eth1
+        ",
+    )
+    .unwrap();
+
+    let result = route.sanitize();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_route_link_local_gateway_merge_and_verify() {
+    let des_routes: Routes = serde_yaml::from_str(
+        r"
+        config:
+        - destination: ::/0
+          next-hop-address: fe80::1%eth1
+        ",
+    )
+    .unwrap();
+    let cur_routes: Routes = serde_yaml::from_str(
+        r"
+        config:
+        - destination: ::/0
+          next-hop-interface: eth1
+          next-hop-address: fe80::1
+          metric: 1024
+          table-id: 254
+        ",
+    )
+    .unwrap();
+    let merged_ifaces = gen_merged_ifaces_for_route_test();
+
+    let merged_routes =
+        MergedRoutes::new(des_routes, Routes::new(), &merged_ifaces).unwrap();
+
+    assert!(merged_routes.indexed.contains_key("eth1"));
+    merged_routes.verify(&cur_routes, &[]).unwrap();
+}
@@ -1,8 +1,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    unit_tests::testlib::gen_test_rule_entries, MergedRouteRules,
-    RouteRuleEntry, RouteRules,
+    unit_tests::testlib::gen_test_rule_entries,
+    warning::{start_warning_collection, stop_warning_collection},
+    ErrorKind, MergedRouteRules, RouteRuleEntry, RouteRules,
 };
 
 #[test]
@@ -22,11 +23,17 @@ fn test_route_rule_stringlized_attributes() {
         r#"
 priority: "500"
 route-table: "129"
+fwmark: "0x10"
+fwmask: " 255 "
+suppress-prefix-length: "0"
 "#,
     )
     .unwrap();
     assert_eq!(rule.table_id, Some(129));
     assert_eq!(rule.priority, Some(500));
+    assert_eq!(rule.fwmark, Some(0x10));
+    assert_eq!(rule.fwmask, Some(255));
+    assert_eq!(rule.suppress_prefix_length, Some(0));
 }
 
 #[test]
@@ -41,8 +48,8 @@ ip-from: 192.0.3.2/24
 
     rule.sanitize().unwrap();
 
-    assert_eq!(rule.ip_to.unwrap(), "192.0.3.0/24");
-    assert_eq!(rule.ip_from.unwrap(), "192.0.3.0/24");
+    assert_eq!(rule.ip_to.unwrap().as_str(), "192.0.3.0/24");
+    assert_eq!(rule.ip_from.unwrap().as_str(), "192.0.3.0/24");
 }
 
 #[test]
@@ -57,8 +64,8 @@ ip-from: 2001:db8:2::ffff/64
 
     rule.sanitize().unwrap();
 
-    assert_eq!(rule.ip_to.unwrap(), "2001:db8:1::/64");
-    assert_eq!(rule.ip_from.unwrap(), "2001:db8:2::/64");
+    assert_eq!(rule.ip_to.unwrap().as_str(), "2001:db8:1::/64");
+    assert_eq!(rule.ip_from.unwrap().as_str(), "2001:db8:2::/64");
 }
 
 #[test]
@@ -73,8 +80,8 @@ ip-from: 192.0.3.2
 
     rule.sanitize().unwrap();
 
-    assert_eq!(rule.ip_to.unwrap(), "192.0.3.1/32");
-    assert_eq!(rule.ip_from.unwrap(), "192.0.3.2/32");
+    assert_eq!(rule.ip_to.unwrap().as_str(), "192.0.3.1/32");
+    assert_eq!(rule.ip_from.unwrap().as_str(), "192.0.3.2/32");
 }
 
 #[test]
@@ -89,8 +96,8 @@ ip-from: 2001:db8:2::ffff
 
     rule.sanitize().unwrap();
 
-    assert_eq!(rule.ip_to.unwrap(), "2001:db8:1::2/128");
-    assert_eq!(rule.ip_from.unwrap(), "2001:db8:2::ffff/128");
+    assert_eq!(rule.ip_to.unwrap().as_str(), "2001:db8:1::2/128");
+    assert_eq!(rule.ip_from.unwrap().as_str(), "2001:db8:2::ffff/128");
 }
 
 #[test]
@@ -105,8 +112,15 @@ ip-from: 2001:db8:2:0000::ffff
 
     rule.sanitize().unwrap();
 
-    assert_eq!(rule.ip_to.unwrap(), "2001:db8:1::2/128");
-    assert_eq!(rule.ip_from.unwrap(), "2001:db8:2::ffff/128");
+    assert_eq!(rule.ip_to.unwrap().as_str(), "2001:db8:1::2/128");
+    assert_eq!(rule.ip_from.unwrap().as_str(), "2001:db8:2::ffff/128");
+}
+
+#[test]
+fn test_route_rule_invalid_ip_from_to() {
+    for yml in ["ip-from: 192.0.3.256/24", "ip-to: 2001:db8:1::2/129"] {
+        assert!(serde_yaml::from_str::<RouteRuleEntry>(yml).is_err());
+    }
 }
 
 #[test]
@@ -249,7 +263,7 @@ fn test_route_rule_auto_priority_increasing_from_desire() {
     )
     .unwrap();
 
-    let merged = MergedRouteRules::new(des_rules, cur_rules).unwrap();
+    let merged = MergedRouteRules::new(des_rules, cur_rules, &[]).unwrap();
 
     let mut rules = merged.for_apply;
     rules.sort_unstable();
@@ -298,7 +312,7 @@ fn test_route_rule_auto_priority_increasing_from_empty() {
 
     let cur_rules = RouteRules::new();
 
-    let merged = MergedRouteRules::new(des_rules, cur_rules).unwrap();
+    let merged = MergedRouteRules::new(des_rules, cur_rules, &[]).unwrap();
 
     let mut rules = merged.for_apply;
     rules.sort_unstable();
@@ -320,3 +334,108 @@ fn test_route_rule_auto_priority_increasing_from_empty() {
     );
     assert_eq!(rules[2].priority, Some(30002));
 }
+
+#[test]
+fn test_route_rule_ignored_priorities() {
+    let des_rules: RouteRules = serde_yaml::from_str(
+        r"
+        config:
+        - state: absent
+          route-table: 51820
+        - ip-to: 192.168.2.30
+          route-table: 200
+          family: ipv4
+        ",
+    )
+    .unwrap();
+    let cur_rules: RouteRules = serde_yaml::from_str(
+        r"
+        config:
+        - ip-to: 192.168.2.31
+          priority: 30001
+          route-table: 51820
+          family: ipv4
+        - fwmark: 0xca6c
+          priority: 32764
+          route-table: 51820
+          family: ipv4
+        ",
+    )
+    .unwrap();
+
+    let merged =
+        MergedRouteRules::new(des_rules, cur_rules.clone(), &[32000..=32765])
+            .unwrap();
+
+    let mut rules = merged.for_apply.clone();
+    rules.sort_unstable();
+
+    assert_eq!(rules.len(), 2);
+    assert!(rules[0].is_absent());
+    assert_eq!(rules[0].priority, Some(30001));
+    assert!(!rules[1].is_absent());
+    assert_eq!(rules[1].priority, Some(30000));
+
+    let cur_rules_after_apply: RouteRules = serde_yaml::from_str(
+        r"
+        config:
+        - ip-to: 192.168.2.30/32
+          priority: 30000
+          route-table: 200
+          family: ipv4
+        - fwmark: 0xca6c
+          priority: 32764
+          route-table: 51820
+          family: ipv4
+        ",
+    )
+    .unwrap();
+    merged.verify(&cur_rules_after_apply, &[]).unwrap();
+}
+
+#[test]
+fn test_route_rule_conflict_table() {
+    let des_rules: RouteRules = serde_yaml::from_str(
+        r"
+        config:
+        - ip-to: 192.0.2.0/24
+          priority: 1000
+          route-table: 100
+        - ip-to: 192.0.2.0/24
+          priority: 1000
+          route-table: 200
+        ",
+    )
+    .unwrap();
+
+    let result = MergedRouteRules::new(des_rules, RouteRules::new(), &[]);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+        assert!(e.msg().contains("conflicts with route rule"));
+    }
+}
+
+#[test]
+fn test_route_rule_duplicate_warning() {
+    let des_rules: RouteRules = serde_yaml::from_str(
+        r"
+        config:
+        - ip-to: 192.0.2.0/24
+          priority: 1000
+          route-table: 100
+        - ip-to: 192.0.2.0/24
+          priority: 1000
+          route-table: 100
+        ",
+    )
+    .unwrap();
+
+    start_warning_collection();
+    MergedRouteRules::new(des_rules, RouteRules::new(), &[]).unwrap();
+    let warnings = stop_warning_collection();
+
+    assert_eq!(warnings.len(), 1);
+    assert!(warnings[0].msg.contains("defined more than once"));
+}
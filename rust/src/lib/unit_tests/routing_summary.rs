@@ -0,0 +1,72 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{NetworkState, RoutingSummary};
+
+#[test]
+fn test_gen_routing_summary() {
+    let state: NetworkState = serde_yaml::from_str(
+        r"---
+        routes:
+          running:
+          - destination: 0.0.0.0/0
+            next-hop-interface: eth1
+            next-hop-address: 192.0.2.1
+            table-id: 254
+          - destination: 192.0.2.0/24
+            next-hop-interface: eth1
+            table-id: 254
+          - destination: ::/0
+            next-hop-interface: eth2
+            next-hop-address: 2001:db8:1::1
+            table-id: 500
+          - destination: 2001:db8:1::/64
+            next-hop-interface: eth2
+            table-id: 500
+          - destination: 198.51.100.0/24
+            next-hop-interface: eth2
+            table-id: 500
+        route-rules:
+          config:
+          - ip-to: 192.0.2.0/24
+            route-table: 500
+        ",
+    )
+    .unwrap();
+
+    let summary = state.gen_routing_summary();
+
+    let expected: RoutingSummary = serde_yaml::from_str(
+        r"---
+        route-counts:
+          254: 2
+          500: 3
+        route-rule-count: 1
+        ipv4-default-gateways:
+        - next-hop-interface: eth1
+          next-hop-address: 192.0.2.1
+        ipv6-default-gateways:
+        - next-hop-interface: eth2
+          next-hop-address: 2001:db8:1::1
+          table-id: 500
+        ",
+    )
+    .unwrap();
+    assert_eq!(summary, expected);
+}
+
+#[test]
+fn test_routing_summary_ignored_by_deserialize_of_net_state() {
+    let state: NetworkState = serde_yaml::from_str(
+        r"---
+        routing-summary:
+          route-counts:
+            254: 1
+          route-rule-count: 0
+          ipv4-default-gateways: []
+          ipv6-default-gateways: []
+        ",
+    )
+    .unwrap();
+    assert!(state.routing_summary.is_some());
+    assert!(state.is_empty());
+}
@@ -1,10 +1,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::convert::TryFrom;
+
 use crate::{
-    state::get_json_value_difference, unit_tests::testlib::new_eth_iface,
+    state::get_json_value_difference,
+    unit_tests::testlib::new_eth_iface,
+    warning::{start_warning_collection, stop_warning_collection},
     BridgePortVlanMode, ErrorKind, EthernetConfig, EthernetDuplex, Interface,
-    InterfaceType, Interfaces, MergedInterfaces, NetworkState, SrIovConfig,
-    SrIovVfConfig,
+    InterfaceType, Interfaces, MacAddress, MergedInterfaces, NetworkState,
+    SrIovConfig, SrIovVfConfig,
 };
 
 #[test]
@@ -19,7 +23,7 @@ fn test_sriov_vf_mac_mix_case() {
         let mut sriov_conf = SrIovConfig::new();
         let mut vf_conf = SrIovVfConfig::new();
         vf_conf.id = 0;
-        vf_conf.mac_address = Some("00:11:22:33:44:FF".into());
+        vf_conf.mac_address = MacAddress::try_from("00:11:22:33:44:FF").ok();
         vf_conf.iface_name = "eth1v0".to_string();
         sriov_conf.vfs = Some(vec![vf_conf]);
         sriov_conf.total_vfs = Some(1);
@@ -38,7 +42,7 @@ fn test_sriov_vf_mac_mix_case() {
         let mut sriov_conf = SrIovConfig::new();
         let mut vf_conf = SrIovVfConfig::new();
         vf_conf.id = 0;
-        vf_conf.mac_address = Some("00:11:22:33:44:Ff".into());
+        vf_conf.mac_address = MacAddress::try_from("00:11:22:33:44:Ff").ok();
         sriov_conf.vfs = Some(vec![vf_conf]);
         eth_conf.sr_iov = Some(sriov_conf);
         eth_iface.ethernet = Some(eth_conf);
@@ -839,3 +843,267 @@ fn test_get_sriov_vf_count() {
 
     assert_eq!(merged_ifaces.get_sriov_vf_count(), 32);
 }
+
+#[test]
+fn test_sriov_vf_vlan_trunk_tags() {
+    let mut sriov_conf: SrIovConfig = serde_yaml::from_str(
+        r#"
+        total-vfs: 1
+        vfs:
+        - id: 0
+          qos: 5
+          vlan-trunk-tags:
+          - id: 100
+          - id-range:
+              min: 200
+              max: 202
+        "#,
+    )
+    .unwrap();
+    sriov_conf.sanitize().unwrap();
+    let vf = &sriov_conf.vfs.as_ref().unwrap()[0];
+
+    assert_eq!(vf.get_vlan_trunk_ids(), Some(vec![100, 200, 201, 202]));
+
+    sriov_conf.sanitize_desired_for_verify();
+    let vf = &sriov_conf.vfs.as_ref().unwrap()[0];
+
+    assert_eq!(vf.vlan_trunk_tags.as_ref().map(|t| t.len()), Some(2));
+    assert_eq!(vf.get_vlan_trunk_ids(), Some(vec![100, 200, 201, 202]));
+    assert_eq!(vf.qos, None);
+}
+
+#[test]
+fn test_sriov_vf_single_vlan_trunk_tag_verify_as_vlan_id() {
+    let mut sriov_conf: SrIovConfig = serde_yaml::from_str(
+        r#"
+        vfs:
+        - id: 0
+          qos: 5
+          vlan-trunk-tags:
+          - id: 100
+        "#,
+    )
+    .unwrap();
+    sriov_conf.sanitize_desired_for_verify();
+    let vf = &sriov_conf.vfs.as_ref().unwrap()[0];
+
+    assert_eq!(vf.vlan_trunk_tags, None);
+    assert_eq!(vf.vlan_id, Some(100));
+    assert_eq!(vf.qos, Some(5));
+}
+
+#[test]
+fn test_sriov_vf_update_vlan_trunk_tags_only() {
+    let mut cur_conf: SrIovConfig = serde_yaml::from_str(
+        r#"
+        total-vfs: 2
+        vfs:
+        - id: 0
+          iface-name: eth1v0
+          vlan-id: 100
+        - id: 1
+          iface-name: eth1v1
+        "#,
+    )
+    .unwrap();
+    let mut nm_vf = SrIovVfConfig::new();
+    nm_vf.set_vlan_trunk_ids(vec![101, 100, 102, 200]);
+    let mut nm_conf = SrIovConfig::new();
+    nm_conf.vfs = Some(vec![nm_vf]);
+
+    cur_conf.update(Some(&nm_conf));
+
+    let vfs = cur_conf.vfs.as_ref().unwrap();
+    assert_eq!(vfs.len(), 2);
+    assert_eq!(vfs[0].iface_name, "eth1v0");
+    assert_eq!(vfs[0].vlan_id, Some(100));
+    assert_eq!(vfs[0].get_vlan_trunk_ids(), Some(vec![100, 101, 102, 200]));
+    assert_eq!(vfs[1].vlan_trunk_tags, None);
+}
+
+#[test]
+fn test_sriov_vf_vlan_trunk_tags_conflict_with_vlan_id() {
+    let mut sriov_conf: SrIovConfig = serde_yaml::from_str(
+        r#"
+        vfs:
+        - id: 0
+          vlan-id: 10
+          vlan-trunk-tags:
+          - id: 100
+        "#,
+    )
+    .unwrap();
+    let result = sriov_conf.sanitize();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_sriov_vf_vlan_trunk_tags_overlap() {
+    let mut sriov_conf: SrIovConfig = serde_yaml::from_str(
+        r#"
+        vfs:
+        - id: 0
+          vlan-trunk-tags:
+          - id: 101
+          - id-range:
+              min: 100
+              max: 200
+        "#,
+    )
+    .unwrap();
+    let result = sriov_conf.sanitize();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_has_sriov_and_missing_bond_port() {
+    let desired = serde_yaml::from_str::<NetworkState>(
+        r"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+          ethernet:
+            sr-iov:
+              total-vfs: 2
+        - name: bond0
+          type: bond
+          state: up
+          link-aggregation:
+            mode: active-backup
+            port:
+            - eth1v0
+            - eth1v1
+        ",
+    )
+    .unwrap();
+    let current = serde_yaml::from_str::<NetworkState>(
+        r"---
+        interfaces:
+        - name: eth1
+          type: ethernet
+          state: up
+        ",
+    )
+    .unwrap();
+
+    assert!(desired.has_sriov_and_missing_eth(&current));
+}
+
+fn gen_bond_over_vf_current() -> Interfaces {
+    serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethernet:
+    sr-iov:
+      total-vfs: 2
+      vfs:
+      - id: 0
+        iface-name: eth1v0
+      - id: 1
+        iface-name: eth1v1
+- name: eth2
+  type: ethernet
+  state: up
+  ethernet:
+    sr-iov:
+      total-vfs: 1
+      vfs:
+      - id: 0
+        iface-name: eth2v0
+- name: eth1v0
+  type: ethernet
+  state: up
+- name: eth1v1
+  type: ethernet
+  state: up
+- name: eth2v0
+  type: ethernet
+  state: up
+",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_bond_over_vf_of_same_pf() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1v0
+    - eth1v1
+",
+    )
+    .unwrap();
+
+    start_warning_collection();
+    MergedInterfaces::new(desired, gen_bond_over_vf_current(), false, false)
+        .unwrap();
+    let warnings = stop_warning_collection();
+
+    assert!(warnings.iter().any(|w| w.msg.contains("same PF eth1")));
+}
+
+#[test]
+fn test_bond_over_vf_with_admin_mac() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+- name: eth1
+  type: ethernet
+  state: up
+  ethernet:
+    sr-iov:
+      total-vfs: 2
+      vfs:
+      - id: 0
+        mac-address: 02:00:00:00:00:01
+- name: bond0
+  type: bond
+  state: up
+  link-aggregation:
+    mode: active-backup
+    port:
+    - eth1v0
+    - eth2v0
+",
+    )
+    .unwrap();
+
+    let result = MergedInterfaces::new(
+        desired.clone(),
+        gen_bond_over_vf_current(),
+        false,
+        false,
+    );
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+
+    let mut desired = desired;
+    if let Some(Interface::Bond(bond_iface)) =
+        desired.get_iface_mut("bond0", InterfaceType::Bond)
+    {
+        bond_iface.bond.as_mut().unwrap().options =
+            Some(serde_yaml::from_str("fail_over_mac: active").unwrap());
+    }
+    MergedInterfaces::new(desired, gen_bond_over_vf_current(), false, false)
+        .unwrap();
+}
@@ -0,0 +1,74 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    BondMode, ErrorKind, InterfaceType, Interfaces, MergedInterfaces,
+    TeamInterface,
+};
+
+fn gen_team_iface(runner: &str) -> TeamInterface {
+    serde_yaml::from_str(&format!(
+        r"---
+name: team0
+type: team
+state: ignore
+mtu: 9000
+ipv4:
+  enabled: true
+  address:
+  - ip: 192.0.2.1
+    prefix-length: 24
+team:
+  runner: {runner}
+  port:
+  - eth1
+  - eth2
+"
+    ))
+    .unwrap()
+}
+
+#[test]
+fn test_team_to_bond() {
+    let bond_iface = gen_team_iface("activebackup").to_bond().unwrap();
+
+    assert_eq!(bond_iface.base.name, "team0");
+    assert_eq!(bond_iface.base.iface_type, InterfaceType::Bond);
+    assert_eq!(bond_iface.base.mtu, Some(9000));
+    assert!(bond_iface.base.ipv4.is_some());
+    let bond_conf = bond_iface.bond.unwrap();
+    assert_eq!(bond_conf.mode, Some(BondMode::ActiveBackup));
+    assert_eq!(
+        bond_conf.port,
+        Some(vec!["eth1".to_string(), "eth2".to_string()])
+    );
+}
+
+#[test]
+fn test_team_to_bond_random_runner() {
+    let result = gen_team_iface("random").to_bond();
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
+
+#[test]
+fn test_team_is_query_only() {
+    let desired: Interfaces = serde_yaml::from_str(
+        r"---
+- name: team0
+  type: team
+  state: up
+",
+    )
+    .unwrap();
+
+    let result =
+        MergedInterfaces::new(desired, Interfaces::new(), false, false);
+
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::NotSupportedError);
+    }
+}
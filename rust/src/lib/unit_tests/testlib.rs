@@ -1,12 +1,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
+use std::convert::TryFrom;
+
 use crate::{
     BondConfig, BondInterface, BondMode, EthernetInterface, Interface,
-    InterfaceType, Interfaces, LinuxBridgeConfig, LinuxBridgeInterface,
-    LinuxBridgePortConfig, MergedInterfaces, OvsBridgeConfig,
-    OvsBridgeInterface, OvsBridgePortConfig, OvsInterface, RouteEntry,
-    RouteRuleEntry, RouteRules, Routes, UnknownInterface, VlanConfig,
-    VlanInterface,
+    InterfaceType, Interfaces, IpNetwork, LinuxBridgeConfig,
+    LinuxBridgeInterface, LinuxBridgePortConfig, MergedInterfaces,
+    OvsBridgeConfig, OvsBridgeInterface, OvsBridgePortConfig, OvsInterface,
+    RouteEntry, RouteRuleEntry, RouteRules, Routes, UnknownInterface,
+    VlanConfig, VlanInterface,
 };
 
 pub(crate) fn new_eth_iface(name: &str) -> Interface {
@@ -27,7 +29,7 @@ pub(crate) fn new_br_iface(name: &str) -> Interface {
     Interface::LinuxBridge(iface)
 }
 
-fn new_bond_iface(name: &str) -> Interface {
+pub(crate) fn new_bond_iface(name: &str) -> Interface {
     let mut iface = BondInterface::new();
     iface.base.name = name.to_string();
     Interface::Bond(iface)
@@ -166,7 +168,7 @@ pub(crate) fn gen_route_entry(
     next_hop_addr: &str,
 ) -> RouteEntry {
     let mut ret = RouteEntry::new();
-    ret.destination = Some(dst.to_string());
+    ret.destination = Some(dst.parse().unwrap());
     ret.next_hop_iface = Some(next_hop_iface.to_string());
     ret.next_hop_addr = Some(next_hop_addr.to_string());
     ret.metric = Some(TEST_ROUTE_METRIC);
@@ -211,8 +213,8 @@ fn gen_rule_entry(
     RouteRuleEntry {
         family: None,
         state: None,
-        ip_from: Some(ip_from.to_string()),
-        ip_to: Some(ip_to.to_string()),
+        ip_from: Some(IpNetwork::try_from(ip_from).unwrap()),
+        ip_to: Some(IpNetwork::try_from(ip_to).unwrap()),
         table_id: Some(table_id),
         priority: Some(priority),
         fwmark: Some(fwmark),
@@ -0,0 +1,147 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{ErrorKind, NetworkState, UnknownFieldMode};
+
+const STATE_WITH_UNKNOWN_FIELDS: &str = r"---
+foo-top: 1
+routes:
+  config:
+  - destination: 198.51.100.0/24
+    next-hop-interface: eth1
+    next-hop-address: 192.0.2.1
+    foo-route: true
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ipv4:
+    enabled: true
+    foo-ip: abc
+    address:
+    - ip: 192.0.2.2
+      prefix-length: 24
+- name: eth2
+  type: ethernet
+  foo-iface: abc
+  ethernet:
+    speed: 1000
+    foo-eth: true
+";
+
+#[test]
+fn test_unknown_field_mode_reject() {
+    let result = NetworkState::new_from_yaml_with_mode(
+        STATE_WITH_UNKNOWN_FIELDS,
+        UnknownFieldMode::Reject,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_unknown_field_mode_warn() {
+    let (state, warnings) = NetworkState::new_from_yaml_with_mode(
+        STATE_WITH_UNKNOWN_FIELDS,
+        UnknownFieldMode::Warn,
+    )
+    .unwrap();
+
+    let mut msgs: Vec<String> = warnings.into_iter().map(|w| w.msg).collect();
+    msgs.sort_unstable();
+    assert_eq!(
+        msgs,
+        vec![
+            "Ignoring unknown property foo-top".to_string(),
+            "Ignoring unknown property interfaces[0].ipv4.foo-ip".to_string(),
+            "Ignoring unknown property interfaces[1].ethernet.foo-eth"
+                .to_string(),
+            "Ignoring unknown property interfaces[1].foo-iface".to_string(),
+            "Ignoring unknown property routes.config[0].foo-route".to_string(),
+        ]
+    );
+    let ifaces = state.interfaces.to_vec();
+    assert_eq!(ifaces.len(), 2);
+    assert_eq!(
+        ifaces[0]
+            .base_iface()
+            .ipv4
+            .as_ref()
+            .unwrap()
+            .addresses
+            .as_ref()
+            .unwrap()
+            .len(),
+        1
+    );
+    assert_eq!(state.routes.config.as_ref().unwrap().len(), 1);
+}
+
+#[test]
+fn test_unknown_field_mode_warn_invalid_value() {
+    let result = NetworkState::new_from_yaml_with_mode(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  mtu: abc
+",
+        UnknownFieldMode::Warn,
+    );
+    assert!(result.is_err());
+    if let Err(e) = result {
+        assert_eq!(e.kind(), ErrorKind::InvalidArgument);
+    }
+}
+
+#[test]
+fn test_unknown_field_mode_warn_keep_valid_prop_of_same_name() {
+    let (state, warnings) = NetworkState::new_from_yaml_with_mode(
+        r"---
+interfaces:
+- name: eth1
+  type: ethernet
+  state: up
+  ethernet:
+    speed: 1000
+    enabled: true
+  ipv4:
+    enabled: true
+    dhcp: true
+",
+        UnknownFieldMode::Warn,
+    )
+    .unwrap();
+
+    let msgs: Vec<String> = warnings.into_iter().map(|w| w.msg).collect();
+    assert_eq!(
+        msgs,
+        vec!["Ignoring unknown property interfaces[0].ethernet.enabled"
+            .to_string()]
+    );
+    let ifaces = state.interfaces.to_vec();
+    let ipv4 = ifaces[0].base_iface().ipv4.as_ref().unwrap();
+    assert!(ipv4.enabled);
+    assert_eq!(ipv4.dhcp, Some(true));
+}
+
+#[test]
+fn test_unknown_field_mode_warn_ignore_absent_iface() {
+    let (state, warnings) = NetworkState::new_from_yaml_with_mode(
+        r"---
+foo-top: 1
+interfaces:
+- name: eth1
+  type: ethernet
+  state: absent
+  foo-iface: abc
+",
+        UnknownFieldMode::Warn,
+    )
+    .unwrap();
+
+    let msgs: Vec<String> = warnings.into_iter().map(|w| w.msg).collect();
+    assert_eq!(msgs, vec!["Ignoring unknown property foo-top".to_string()]);
+    assert_eq!(state.interfaces.to_vec().len(), 1);
+}
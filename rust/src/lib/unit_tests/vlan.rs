@@ -22,6 +22,30 @@ vlan:
     assert_eq!(iface.vlan.unwrap().id, 101);
 }
 
+#[test]
+fn test_vlan_protocol_ignore_case() {
+    let iface: VlanInterface = serde_yaml::from_str(
+        r"---
+name: vlan1
+type: vlan
+state: up
+vlan:
+  base-iface: eth1
+  id: 101
+  protocol: 802.1AD
+",
+    )
+    .unwrap();
+
+    assert_eq!(
+        iface.vlan.as_ref().unwrap().protocol,
+        Some(VlanProtocol::Ieee8021Ad)
+    );
+    assert!(serde_yaml::to_string(&iface)
+        .unwrap()
+        .contains("protocol: 802.1ad"));
+}
+
 #[test]
 fn test_vlan_get_parent_up_priority_plus_one() {
     let desired: Interfaces = serde_yaml::from_str(
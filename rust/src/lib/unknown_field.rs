@@ -0,0 +1,508 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{
+    de::{self, DeserializeOwned, Visitor},
+    Deserialize, Deserializer,
+};
+use serde_json::Value;
+
+use crate::{
+    ip::{InterfaceIp, InterfaceIpAddrObj},
+    BatmanConfig, BondConfig, CanConfig, ConnectivityCheckConfig,
+    DispatchConfig, DnsClientState, DnsState, EthernetConfig, EthtoolConfig,
+    GreConfig, HostNameState, Ieee8021XConfig, InfiniBandConfig,
+    InterfaceState, InterfaceType, Ip6TunnelConfig, IpVlanConfig,
+    LibreswanConfig, LinuxBridgeConfig, LldpConfig, MacSecConfig,
+    MacVlanConfig, MacVtapConfig, ModemConfig, MptcpConfig,
+    NetworkStateDefaults, NmPassthroughConfig, OvnBridgeMapping,
+    OvnConfiguration, OvsBridgeConfig, OvsDpdkConfig, OvsPatchConfig,
+    RouteEntry, RouteRuleEntry, RouteRules, Routes, RoutingSummary, TeamConfig,
+    VethConfig, VlanConfig, VrfConfig, VxlanConfig,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+/// How to handle unknown properties when deserializing
+/// [crate::NetworkState], for example properties introduced by newer version
+/// of nmstate.
+pub enum UnknownFieldMode {
+    /// Raise [crate::ErrorKind::InvalidArgument] on unknown property.
+    Reject,
+    /// Remove unknown properties with warning holding the full path of each
+    /// removed property, e.g. `interfaces[0].ipv4.foo`.
+    Warn,
+}
+
+impl Default for UnknownFieldMode {
+    fn default() -> Self {
+        Self::Reject
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PathSeg {
+    Key(String),
+    Index(usize),
+}
+
+// Properties of serde derived struct are taken from the property names
+// serde generated for it, hence only the glue of custom `Deserialize`
+// implementations and `serde(flatten)` structs is maintained here.
+// Properties without schema are left to serde.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Schema {
+    Any,
+    Object {
+        keys: Vec<&'static str>,
+        children: Vec<(&'static str, Schema)>,
+    },
+    List(Box<Schema>),
+    // Interface whose properties depend on its type
+    Interface,
+}
+
+// Properties handled by `NetworkState::deserialize()`
+const NET_STATE_KEYS: [&str; 12] = [
+    "version",
+    "interfaces",
+    "dns-resolver",
+    "routes",
+    "route-rules",
+    "ovs-db",
+    "ovn",
+    "hostname",
+    "connectivity-check",
+    "defaults",
+    "unsupported-interfaces",
+    "routing-summary",
+];
+
+// Properties of `BaseInterface` which is flattened into every interface
+const BASE_IFACE_KEYS: [&str; 28] = [
+    "name",
+    "profile-name",
+    "description",
+    "type",
+    "state",
+    "identifier",
+    "mac-address",
+    "mtu",
+    "min-mtu",
+    "max-mtu",
+    "wait-ip",
+    "wait-ip-timeout",
+    "ipv4",
+    "ipv6",
+    "mptcp",
+    "controller",
+    "accept-all-mac-addresses",
+    "copy-mac-from",
+    "ports-ordered",
+    "volatile",
+    "save-to-disk",
+    "ovs-db",
+    "802.1x",
+    "lldp",
+    "ethtool",
+    "dispatch",
+    "nm",
+    "managed-by",
+];
+
+impl Schema {
+    // Schema of serde derived struct with nested schema of specified
+    // properties. Serde does not expose the property names of struct holding
+    // `serde(flatten)` member, `Schema::Any` is used for them.
+    fn of<T>(children: Vec<(&'static str, Schema)>) -> Self
+    where
+        T: DeserializeOwned,
+    {
+        match T::deserialize(FieldsProbe).err().and_then(|e| e.0) {
+            Some(keys) => Self::Object {
+                keys: keys.to_vec(),
+                children,
+            },
+            None => Self::Any,
+        }
+    }
+
+    fn list_of<T>(children: Vec<(&'static str, Schema)>) -> Self
+    where
+        T: DeserializeOwned,
+    {
+        Self::List(Box::new(Self::of::<T>(children)))
+    }
+
+    pub(crate) fn net_state() -> Self {
+        Self::Object {
+            keys: NET_STATE_KEYS.to_vec(),
+            children: vec![
+                ("interfaces", Self::List(Box::new(Self::Interface))),
+                (
+                    "dns-resolver",
+                    Self::of::<DnsState>(vec![
+                        ("config", Self::of::<DnsClientState>(Vec::new())),
+                        ("running", Self::of::<DnsClientState>(Vec::new())),
+                    ]),
+                ),
+                (
+                    "routes",
+                    Self::of::<Routes>(vec![
+                        ("config", Self::list_of::<RouteEntry>(Vec::new())),
+                        ("running", Self::list_of::<RouteEntry>(Vec::new())),
+                    ]),
+                ),
+                (
+                    "route-rules",
+                    Self::of::<RouteRules>(vec![(
+                        "config",
+                        Self::list_of::<RouteRuleEntry>(Vec::new()),
+                    )]),
+                ),
+                ("ovs-db", Self::ovsdb()),
+                (
+                    "ovn",
+                    Self::of::<OvnConfiguration>(vec![(
+                        "bridge-mappings",
+                        Self::list_of::<OvnBridgeMapping>(Vec::new()),
+                    )]),
+                ),
+                ("hostname", Self::of::<HostNameState>(Vec::new())),
+                (
+                    "connectivity-check",
+                    Self::of::<ConnectivityCheckConfig>(Vec::new()),
+                ),
+                ("defaults", Self::of::<NetworkStateDefaults>(Vec::new())),
+                ("routing-summary", Self::of::<RoutingSummary>(Vec::new())),
+            ],
+        }
+    }
+
+    // Both `OvsDbGlobalConfig` and `OvsDbIfaceConfig` hold free-form
+    // `external_ids` and `other_config` only.
+    fn ovsdb() -> Self {
+        Self::Object {
+            keys: vec!["external_ids", "other_config"],
+            children: Vec::new(),
+        }
+    }
+
+    fn ip() -> Self {
+        Self::of::<InterfaceIp>(vec![(
+            "address",
+            Self::list_of::<InterfaceIpAddrObj>(Vec::new()),
+        )])
+    }
+
+    // Like `Interface::deserialize()`, all properties except name, type and
+    // state are ignored for absent interface. Interface of unknown type
+    // accepts any property.
+    pub(crate) fn iface(v: &Value) -> Self {
+        if let Ok(Some(InterfaceState::Absent)) =
+            crate::deserializer::option_enum_ignore_case(&v["state"])
+        {
+            return Self::Any;
+        }
+        let iface_type = match Option::<InterfaceType>::deserialize(&v["type"])
+        {
+            Ok(Some(t)) => t,
+            _ => return Self::Any,
+        };
+        let type_children = match iface_type {
+            InterfaceType::Ethernet | InterfaceType::Veth => vec![
+                ("ethernet", Self::of::<EthernetConfig>(Vec::new())),
+                ("veth", Self::of::<VethConfig>(Vec::new())),
+            ],
+            InterfaceType::LinuxBridge => {
+                vec![("bridge", Self::of::<LinuxBridgeConfig>(Vec::new()))]
+            }
+            InterfaceType::Bond => {
+                vec![("link-aggregation", Self::of::<BondConfig>(Vec::new()))]
+            }
+            InterfaceType::Vlan => {
+                vec![("vlan", Self::of::<VlanConfig>(Vec::new()))]
+            }
+            InterfaceType::Vxlan => {
+                vec![("vxlan", Self::of::<VxlanConfig>(Vec::new()))]
+            }
+            InterfaceType::Dummy
+            | InterfaceType::Loopback
+            | InterfaceType::Ifb => Vec::new(),
+            InterfaceType::OvsInterface => vec![
+                ("patch", Self::of::<OvsPatchConfig>(Vec::new())),
+                ("dpdk", Self::of::<OvsDpdkConfig>(Vec::new())),
+            ],
+            InterfaceType::OvsBridge => {
+                vec![("bridge", Self::of::<OvsBridgeConfig>(Vec::new()))]
+            }
+            InterfaceType::MacVlan => {
+                vec![("mac-vlan", Self::of::<MacVlanConfig>(Vec::new()))]
+            }
+            InterfaceType::MacVtap => {
+                vec![("mac-vtap", Self::of::<MacVtapConfig>(Vec::new()))]
+            }
+            InterfaceType::Vrf => {
+                vec![("vrf", Self::of::<VrfConfig>(Vec::new()))]
+            }
+            InterfaceType::InfiniBand => {
+                vec![("infiniband", Self::of::<InfiniBandConfig>(Vec::new()))]
+            }
+            InterfaceType::MacSec => {
+                vec![("macsec", Self::of::<MacSecConfig>(Vec::new()))]
+            }
+            InterfaceType::Ipsec => {
+                vec![("libreswan", Self::of::<LibreswanConfig>(Vec::new()))]
+            }
+            InterfaceType::Gre => {
+                vec![("gre", Self::of::<GreConfig>(Vec::new()))]
+            }
+            InterfaceType::Gretap => {
+                vec![("gretap", Self::of::<GreConfig>(Vec::new()))]
+            }
+            InterfaceType::Ip6Gre => {
+                vec![("ip6gre", Self::of::<Ip6TunnelConfig>(Vec::new()))]
+            }
+            InterfaceType::Ip6Gretap => {
+                vec![("ip6gretap", Self::of::<Ip6TunnelConfig>(Vec::new()))]
+            }
+            InterfaceType::Ip6Tnl => {
+                vec![("ip6tnl", Self::of::<Ip6TunnelConfig>(Vec::new()))]
+            }
+            InterfaceType::Team => {
+                vec![("team", Self::of::<TeamConfig>(Vec::new()))]
+            }
+            InterfaceType::IpVlan => {
+                vec![("ipvlan", Self::of::<IpVlanConfig>(Vec::new()))]
+            }
+            InterfaceType::Can => {
+                vec![("can", Self::of::<CanConfig>(Vec::new()))]
+            }
+            InterfaceType::Modem => {
+                vec![("modem", Self::of::<ModemConfig>(Vec::new()))]
+            }
+            InterfaceType::Batman => {
+                vec![("batman-adv", Self::of::<BatmanConfig>(Vec::new()))]
+            }
+            _ => return Self::Any,
+        };
+        let mut keys = BASE_IFACE_KEYS.to_vec();
+        keys.extend(type_children.iter().map(|(k, _)| *k));
+        let mut children = vec![
+            ("ipv4", Self::ip()),
+            ("ipv6", Self::ip()),
+            ("mptcp", Self::of::<MptcpConfig>(Vec::new())),
+            ("lldp", Self::of::<LldpConfig>(Vec::new())),
+            ("ethtool", Self::of::<EthtoolConfig>(Vec::new())),
+            ("802.1x", Self::of::<Ieee8021XConfig>(Vec::new())),
+            ("dispatch", Self::of::<DispatchConfig>(Vec::new())),
+            ("nm", Self::of::<NmPassthroughConfig>(Vec::new())),
+            ("ovs-db", Self::ovsdb()),
+        ];
+        children.extend(type_children);
+        Self::Object { keys, children }
+    }
+}
+
+// Deserializer reporting the property names of serde derived struct through
+// its error, every other request fails.
+struct FieldsProbe;
+
+#[derive(Debug)]
+struct FieldsProbeResult(Option<&'static [&'static str]>);
+
+impl std::fmt::Display for FieldsProbeResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.0)
+    }
+}
+
+impl std::error::Error for FieldsProbeResult {}
+
+impl de::Error for FieldsProbeResult {
+    fn custom<T: std::fmt::Display>(_msg: T) -> Self {
+        Self(None)
+    }
+}
+
+impl<'de> Deserializer<'de> for FieldsProbe {
+    type Error = FieldsProbeResult;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(FieldsProbeResult(None))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(FieldsProbeResult(Some(fields)))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+// Remove the properties not defined in schema. Return the paths of removed
+// properties.
+pub(crate) fn strip_unknown_properties(
+    v: &mut Value,
+    schema: &Schema,
+) -> Vec<Vec<PathSeg>> {
+    let mut removed = Vec::new();
+    strip_unknown(v, schema, &mut Vec::new(), &mut removed);
+    removed
+}
+
+fn strip_unknown(
+    v: &mut Value,
+    schema: &Schema,
+    cur_path: &mut Vec<PathSeg>,
+    removed: &mut Vec<Vec<PathSeg>>,
+) {
+    match (schema, v) {
+        (Schema::Object { keys, children }, Value::Object(map)) => {
+            let unknown_keys: Vec<String> = map
+                .keys()
+                .filter(|k| !keys.contains(&k.as_str()))
+                .cloned()
+                .collect();
+            for key in unknown_keys {
+                map.remove(key.as_str());
+                cur_path.push(PathSeg::Key(key));
+                removed.push(cur_path.clone());
+                cur_path.pop();
+            }
+            for (key, sub_schema) in children {
+                if let Some(sub_v) = map.get_mut(*key) {
+                    cur_path.push(PathSeg::Key(key.to_string()));
+                    strip_unknown(sub_v, sub_schema, cur_path, removed);
+                    cur_path.pop();
+                }
+            }
+        }
+        (Schema::List(sub_schema), Value::Array(items)) => {
+            for (i, sub_v) in items.iter_mut().enumerate() {
+                cur_path.push(PathSeg::Index(i));
+                strip_unknown(sub_v, sub_schema, cur_path, removed);
+                cur_path.pop();
+            }
+        }
+        (Schema::Interface, v) => {
+            let iface_schema = Schema::iface(v);
+            strip_unknown(v, &iface_schema, cur_path, removed);
+        }
+        _ => (),
+    }
+}
+
+// Collect the paths of properties matching `filter` in document order.
+// The `skip_keys` of top level object are ignored.
+pub(crate) fn collect_paths<F>(
+    v: &Value,
+    skip_keys: &[&str],
+    cur_path: &mut Vec<PathSeg>,
+    paths: &mut Vec<Vec<PathSeg>>,
+    filter: &F,
+) where
+    F: Fn(Option<&str>, &Value) -> bool,
+{
+    match v {
+        Value::Object(map) => {
+            for (k, sub_v) in map.iter() {
+                if cur_path.is_empty() && skip_keys.contains(&k.as_str()) {
+                    continue;
+                }
+                cur_path.push(PathSeg::Key(k.to_string()));
+                if filter(Some(k.as_str()), sub_v) {
+                    paths.push(cur_path.clone());
+                }
+                collect_paths(sub_v, skip_keys, cur_path, paths, filter);
+                cur_path.pop();
+            }
+        }
+        Value::Array(items) => {
+            for (i, sub_v) in items.iter().enumerate() {
+                cur_path.push(PathSeg::Index(i));
+                if filter(None, sub_v) {
+                    paths.push(cur_path.clone());
+                }
+                collect_paths(sub_v, skip_keys, cur_path, paths, filter);
+                cur_path.pop();
+            }
+        }
+        _ => (),
+    }
+}
+
+pub(crate) fn is_removal_changing_error<T>(
+    v: &Value,
+    path: &[PathSeg],
+    err_msg: &str,
+) -> bool
+where
+    T: DeserializeOwned,
+{
+    let mut new_v = v.clone();
+    remove_path(&mut new_v, path);
+    match T::deserialize(new_v) {
+        Ok(_) => true,
+        Err(e) => e.to_string() != err_msg,
+    }
+}
+
+fn get_value_mut<'a>(
+    v: &'a mut Value,
+    path: &[PathSeg],
+) -> Option<&'a mut Value> {
+    let mut cur = v;
+    for seg in path {
+        cur = match seg {
+            PathSeg::Key(k) => cur.get_mut(k.as_str())?,
+            PathSeg::Index(i) => cur.get_mut(*i)?,
+        };
+    }
+    Some(cur)
+}
+
+pub(crate) fn remove_path(v: &mut Value, path: &[PathSeg]) {
+    let (last, parents) = match path.split_last() {
+        Some(p) => p,
+        None => return,
+    };
+    match (last, get_value_mut(v, parents)) {
+        (PathSeg::Key(k), Some(Value::Object(map))) => {
+            map.remove(k.as_str());
+        }
+        (PathSeg::Index(i), Some(Value::Array(items))) if *i < items.len() => {
+            items.remove(*i);
+        }
+        _ => (),
+    }
+}
+
+pub(crate) fn path_to_string(path: &[PathSeg]) -> String {
+    let mut ret = String::new();
+    for seg in path {
+        match seg {
+            PathSeg::Key(k) => {
+                if !ret.is_empty() {
+                    ret.push('.');
+                }
+                ret.push_str(k.as_str());
+            }
+            PathSeg::Index(i) => ret.push_str(format!("[{i}]").as_str()),
+        }
+    }
+    ret
+}
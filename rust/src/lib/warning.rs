@@ -0,0 +1,65 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use std::cell::RefCell;
+
+use serde::Serialize;
+
+thread_local! {
+    // Warnings of the on-going action in current thread.
+    // `None` means no collection is requested.
+    static WARNINGS: RefCell<Option<Vec<NmstateWarning>>> =
+        RefCell::new(None);
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[non_exhaustive]
+/// Non-fatal issue found in desired state, for example ignored or
+/// deprecated property.
+pub struct NmstateWarning {
+    pub msg: String,
+}
+
+impl NmstateWarning {
+    pub fn new(msg: String) -> Self {
+        Self { msg }
+    }
+}
+
+impl std::fmt::Display for NmstateWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.msg)
+    }
+}
+
+// Emit warning log and also store it into warning list if collection is
+// started by `start_warning_collection()` in current thread.
+macro_rules! nmstate_warn {
+    ($($arg:tt)+) => {{
+        let msg = format!($($arg)+);
+        log::warn!("{}", msg);
+        $crate::warning::push_warning(msg);
+    }};
+}
+
+pub(crate) use nmstate_warn;
+
+pub(crate) fn push_warning(msg: String) {
+    WARNINGS.with(|warnings| {
+        if let Some(warnings) = warnings.borrow_mut().as_mut() {
+            // Sanitize might be invoked multiple times on retry
+            if !warnings.iter().any(|w| w.msg == msg) {
+                warnings.push(NmstateWarning::new(msg));
+            }
+        }
+    });
+}
+
+pub(crate) fn start_warning_collection() {
+    WARNINGS.with(|warnings| *warnings.borrow_mut() = Some(Vec::new()));
+}
+
+pub(crate) fn stop_warning_collection() -> Vec<NmstateWarning> {
+    WARNINGS
+        .with(|warnings| warnings.borrow_mut().take())
+        .unwrap_or_default()
+}
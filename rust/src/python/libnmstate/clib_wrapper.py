@@ -5,6 +5,7 @@ import json
 import logging
 
 from .error import (
+    NmstateBackendUnavailableError,
     NmstateDependencyError,
     NmstateError,
     NmstateInternalError,
@@ -250,6 +251,8 @@ def map_error(err_kind, err_msg):
         return NmstateDependencyError(err_msg)
     elif err_kind == "PermissionError":
         return NmstatePermissionError(err_msg)
+    elif err_kind == "BackendUnavailable":
+        return NmstateBackendUnavailableError(err_msg)
     else:
         return NmstateError(f"{err_kind}: {err_msg}")
 
@@ -54,6 +54,15 @@ class NmstatePermissionError(NmstateError, PermissionError):
     pass
 
 
+class NmstateBackendUnavailableError(NmstateError):
+    """
+    Backend daemon like NetworkManager is not reachable even after retries,
+    for example daemon keeps restarting.
+    """
+
+    pass
+
+
 class NmstateConflictError(NmstateError, RuntimeError):
     """
     Something else is already editing the network state via Nmstate.
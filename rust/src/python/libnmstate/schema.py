@@ -20,11 +20,20 @@ class Interface:
     COPY_MAC_FROM = "copy-mac-from"
     ACCEPT_ALL_MAC_ADDRESSES = "accept-all-mac-addresses"
     WAIT_IP = "wait-ip"
+    WAIT_IP_TIMEOUT = "wait-ip-timeout"
     CONTROLLER = "controller"
+    PORTS_ORDERED = "ports-ordered"
+    VOLATILE = "volatile"
+    SAVE_TO_DISK = "save-to-disk"
     PROFILE_NAME = "profile-name"
     IDENTIFIER = "identifier"
     IDENTIFIER_NAME = "name"
     IDENTIFIER_MAC = "mac-address"
+    MANAGED_BY = "managed-by"
+    MANAGED_BY_BACKEND = "backend"
+    MANAGED_BY_PROFILE_UUID = "profile-uuid"
+    NM = "nm"
+    NM_SETTINGS = "settings"
 
 
 class Route:
@@ -79,6 +88,8 @@ class DNS:
     SERVER = "server"
     SEARCH = "search"
     OPTIONS = "options"
+    INTERFACE = "interface"
+    GLOBAL = "global"
 
 
 class Constants:
@@ -94,6 +105,7 @@ class InterfaceState:
     UP = "up"
     ABSENT = "absent"
     IGNORE = "ignore"
+    PRESENT = "present"
 
 
 class InterfaceType:
@@ -118,6 +130,17 @@ class InterfaceType:
     OTHER = "other"
     LOOPBACK = "loopback"
     IPSEC = "ipsec"
+    GRE = "gre"
+    GRETAP = "gretap"
+    IP6GRE = "ip6gre"
+    IP6GRETAP = "ip6gretap"
+    IP6TNL = "ip6tnl"
+    TEAM = "team"
+    IPVLAN = "ipvlan"
+    CAN = "can"
+    MODEM = "modem"
+    BATMAN = "batman-adv"
+    IFB = "ifb"
 
     VIRT_TYPES = (
         BOND,
@@ -131,6 +154,12 @@ class InterfaceType:
         VLAN,
         VXLAN,
         IPSEC,
+        GRE,
+        GRETAP,
+        IP6GRE,
+        IP6GRETAP,
+        IP6TNL,
+        IPVLAN,
     )
 
 
@@ -154,19 +183,38 @@ class InterfaceIP:
     ALLOW_EXTRA_ADDRESS = "allow-extra-address"
     DHCP_SEND_HOSTNAME = "dhcp-send-hostname"
     DHCP_CUSTOM_HOSTNAME = "dhcp-custom-hostname"
+    DHCP_SEND_RELEASE = "dhcp-send-release"
 
 
 class InterfaceIPv4(InterfaceIP):
     DHCP_CLIENT_ID = "dhcp-client-id"
+    DAD_TIMEOUT = "dad-timeout"
+    ADDRESS_CONFLICT = "address-conflict"
 
 
 class InterfaceIPv6(InterfaceIP):
     AUTOCONF = "autoconf"
     DHCP_DUID = "dhcp-duid"
+    DHCP_MODE = "dhcp-mode"
+    DHCP_MODE_RA_TRIGGERED = "ra-triggered"
+    DHCP_MODE_STATELESS = "stateless"
+    DHCP_MODE_NO_RA = "no-ra"
     ADDR_GEN_MODE = "addr-gen-mode"
     ADDR_GEN_MODE_EUI64 = "eui64"
     ADDR_GEN_MODE_STABLE_PRIVACY = "stable-privacy"
     TOKEN = "token"
+    ROUTER_ADVERTISEMENT = "router-advertisement"
+
+    class RouterAdvertisement:
+        ROUTE = "route"
+        RDNSS = "rdnss"
+        DNSSL = "dnssl"
+        DESTINATION = "destination"
+        NEXT_HOP_ADDRESS = "next-hop-address"
+        METRIC = "metric"
+        SERVER = "server"
+        DOMAIN = "domain"
+        LIFETIME = "lifetime"
 
 
 class Bond:
@@ -178,6 +226,7 @@ class Bond:
     PORTS = "ports"
     OPTIONS_SUBTREE = "options"
     PORTS_CONFIG_SUBTREE = "ports-config"
+    ACTIVE_PORT = "active-port"
 
     class PortsConfig:
         NAME = "name"
@@ -234,6 +283,7 @@ class Bridge:
 class LinuxBridge(Bridge):
     TYPE = "linux-bridge"
     MULTICAST_SUBTREE = "multicast"
+    VLAN_SUBTREE = "vlan"
 
     class Options:
         GROUP_FORWARD_MASK = "group-forward-mask"
@@ -256,6 +306,8 @@ class LinuxBridge(Bridge):
         MULTICAST_STARTUP_QUERY_INTERVAL = "multicast-startup-query-interval"
         VLAN_PROTOCOL = "vlan-protocol"
         VLAN_DEFAULT_PVID = "vlan-default-pvid"
+        VLAN_STATS_ENABLED = "vlan-stats-enabled"
+        NO_LINKLOCAL_LEARN = "no-linklocal-learn"
 
         # Read only properties begin
         HELLO_TIMER = "hello-timer"
@@ -266,6 +318,8 @@ class LinuxBridge(Bridge):
         STP_HAIRPIN_MODE = "stp-hairpin-mode"
         STP_PATH_COST = "stp-path-cost"
         STP_PRIORITY = "stp-priority"
+        # Read only property
+        HW_OFFLOAD = "hw-offload"
 
 
 class Ethernet:
@@ -294,6 +348,7 @@ class Ethernet:
             MAX_TX_RATE = "max-tx-rate"
             VLAN_ID = "vlan-id"
             QOS = "qos"
+            VLAN_TRUNK_TAGS = "vlan-trunk-tags"
 
 
 class Veth:
@@ -326,6 +381,76 @@ class VXLAN:
     DESTINATION_PORT = "destination-port"
 
 
+class Gre:
+    TYPE = InterfaceType.GRE
+    CONFIG_SUBTREE = "gre"
+
+    BASE_IFACE = "base-iface"
+    LOCAL = "local"
+    REMOTE = "remote"
+    IKEY = "ikey"
+    OKEY = "okey"
+    TTL = "ttl"
+    TOS = "tos"
+
+
+class Gretap(Gre):
+    TYPE = InterfaceType.GRETAP
+    CONFIG_SUBTREE = "gretap"
+
+
+class Ip6Gre:
+    TYPE = InterfaceType.IP6GRE
+    CONFIG_SUBTREE = "ip6gre"
+
+    BASE_IFACE = "base-iface"
+    LOCAL = "local"
+    REMOTE = "remote"
+    IKEY = "ikey"
+    OKEY = "okey"
+    ENCAP_LIMIT = "encap-limit"
+    FLOW_LABEL = "flow-label"
+    HOP_LIMIT = "hop-limit"
+
+
+class Ip6Gretap(Ip6Gre):
+    TYPE = InterfaceType.IP6GRETAP
+    CONFIG_SUBTREE = "ip6gretap"
+
+
+class Ip6Tnl:
+    TYPE = InterfaceType.IP6TNL
+    CONFIG_SUBTREE = "ip6tnl"
+
+    MODE = "mode"
+    BASE_IFACE = "base-iface"
+    LOCAL = "local"
+    REMOTE = "remote"
+    ENCAP_LIMIT = "encap-limit"
+    FLOW_LABEL = "flow-label"
+    HOP_LIMIT = "hop-limit"
+
+    class Mode:
+        IP6IP6 = "ip6ip6"
+        IPIP6 = "ipip6"
+
+
+class Team:
+    TYPE = InterfaceType.TEAM
+    CONFIG_SUBTREE = "team"
+
+    RUNNER = "runner"
+    PORT = "port"
+
+    class Runner:
+        BROADCAST = "broadcast"
+        ROUNDROBIN = "roundrobin"
+        RANDOM = "random"
+        ACTIVEBACKUP = "activebackup"
+        LOADBALANCE = "loadbalance"
+        LACP = "lacp"
+
+
 class OvsDB:
     KEY = "ovs-db"
     OVS_DB_SUBTREE = "ovs-db"
@@ -433,6 +558,7 @@ class MacVlan:
     BASE_IFACE = "base-iface"
     MODE = "mode"
     PROMISCUOUS = "promiscuous"
+    SOURCE_MAC_ADDRESSES = "source-mac-addresses"
 
     class Mode:
         UNKNOWN = "unknown"
@@ -448,6 +574,50 @@ class MacVtap(MacVlan):
     CONFIG_SUBTREE = "mac-vtap"
 
 
+class Can:
+    TYPE = InterfaceType.CAN
+    CONFIG_SUBTREE = "can"
+    BITRATE = "bitrate"
+    SAMPLE_POINT = "sample-point"
+    RESTART_MS = "restart-ms"
+    FD = "fd"
+    DATA_BITRATE = "data-bitrate"
+
+
+class Modem:
+    TYPE = InterfaceType.MODEM
+    CONFIG_SUBTREE = "modem"
+    APN = "apn"
+    PIN = "pin"
+    ROAMING = "roaming"
+
+
+class Batman:
+    TYPE = InterfaceType.BATMAN
+    CONFIG_SUBTREE = "batman-adv"
+    ROUTING_ALGORITHM = "routing-algorithm"
+    PORT_SUBTREE = "port"
+
+    class RoutingAlgorithm:
+        BATMAN_IV = "batman-iv"
+        BATMAN_V = "batman-v"
+
+
+class IpVlan:
+    TYPE = InterfaceType.IPVLAN
+    CONFIG_SUBTREE = "ipvlan"
+    BASE_IFACE = "base-iface"
+    MODE = "mode"
+    PRIVATE = "private"
+    VEPA = "vepa"
+
+    class Mode:
+        UNKNOWN = "unknown"
+        L2 = "l2"
+        L3 = "l3"
+        L3S = "l3s"
+
+
 class MacSec:
     CONFIG_SUBTREE = "macsec"
     ENCRYPT = "encrypt"
@@ -523,6 +693,29 @@ class HostNameState:
     RUNNING = "running"
 
 
+class UnsupportedInterface:
+    KEY = "unsupported-interfaces"
+    NAME = "name"
+    TYPE = "type"
+
+
+class RoutingSummary:
+    KEY = "routing-summary"
+    ROUTE_COUNTS = "route-counts"
+    ROUTE_RULE_COUNT = "route-rule-count"
+    IPV4_DEFAULT_GATEWAYS = "ipv4-default-gateways"
+    IPV6_DEFAULT_GATEWAYS = "ipv6-default-gateways"
+    NEXT_HOP_INTERFACE = "next-hop-interface"
+    NEXT_HOP_ADDRESS = "next-hop-address"
+    TABLE_ID = "table-id"
+
+
+class ConnectivityCheck:
+    KEY = "connectivity-check"
+    ENABLED = "enabled"
+    URI = "uri"
+
+
 class Mptcp:
     ADDRESS_FLAGS = "address-flags"
     FLAG_SIGNAL = "signal"